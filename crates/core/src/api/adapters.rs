@@ -56,7 +56,7 @@ mod async_adapters {
         }
     }
 
-    /// Streams items from an [`ItemsClient`] using token/skip-based pagination.
+    /// Streams items from an [`ItemsClient`] using token- or offset-based pagination.
     pub fn stream_pages<T>(
         client: T,
         initial_search: Search,
@@ -79,7 +79,7 @@ mod async_adapters {
                 }
                 match next {
                     Some(next_fields) => {
-                        current_search.additional_fields.extend(next_fields);
+                        current_search.items.set_pagination(next_fields);
                         page = client.search(current_search.clone()).await?;
                     }
                     None => break,
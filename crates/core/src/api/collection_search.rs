@@ -0,0 +1,194 @@
+use super::Result;
+use crate::{Bbox, Error};
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Parameters for the [collection
+/// search](https://github.com/stac-api-extensions/collection-search)
+/// extension to the collections endpoint.
+///
+/// # Examples
+///
+/// ```
+/// use stac::{Bbox, api::CollectionSearch};
+///
+/// let search = CollectionSearch::default()
+///     .bbox(Bbox::new(-106., 41., -105., 42.))
+///     .q("landsat")
+///     .limit(10);
+/// ```
+#[derive(Clone, Default, Debug, Serialize, Deserialize, PartialEq)]
+pub struct CollectionSearch {
+    /// The maximum number of results to return (page size).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u64>,
+
+    /// Requested bounding box.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bbox: Option<Bbox>,
+
+    /// Single date+time, or a range ('/' separator), formatted to [RFC 3339,
+    /// section 5.6](https://tools.ietf.org/html/rfc3339#section-5.6).
+    ///
+    /// Use double dots `..` for open date ranges.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub datetime: Option<String>,
+
+    /// Free-text search terms.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub q: Option<String>,
+
+    /// Additional fields.
+    #[serde(flatten)]
+    pub additional_fields: Map<String, Value>,
+}
+
+/// GET parameters for the collection search extension.
+#[derive(Clone, Default, Debug, Serialize, Deserialize, PartialEq)]
+pub struct GetCollectionSearch {
+    /// The maximum number of results to return (page size).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<String>,
+
+    /// Requested bounding box.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bbox: Option<String>,
+
+    /// Single date+time, or a range ('/' separator).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub datetime: Option<String>,
+
+    /// Free-text search terms.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub q: Option<String>,
+
+    /// Additional fields.
+    #[serde(flatten)]
+    pub additional_fields: IndexMap<String, String>,
+}
+
+impl CollectionSearch {
+    /// Sets the limit of this collection search.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::api::CollectionSearch;
+    /// let search = CollectionSearch::default().limit(10);
+    /// ```
+    pub fn limit(mut self, limit: u64) -> CollectionSearch {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Sets the bbox of this collection search.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Bbox, api::CollectionSearch};
+    /// let search = CollectionSearch::default().bbox(Bbox::new(-106., 41., -105., 42.));
+    /// ```
+    pub fn bbox(mut self, bbox: Bbox) -> CollectionSearch {
+        self.bbox = Some(bbox);
+        self
+    }
+
+    /// Sets the datetime of this collection search.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::api::CollectionSearch;
+    /// let search = CollectionSearch::default().datetime("2023-01-01T00:00:00Z/..");
+    /// ```
+    pub fn datetime(mut self, datetime: impl ToString) -> CollectionSearch {
+        self.datetime = Some(datetime.to_string());
+        self
+    }
+
+    /// Sets the free-text search terms of this collection search.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::api::CollectionSearch;
+    /// let search = CollectionSearch::default().q("sentinel,landsat");
+    /// ```
+    pub fn q(mut self, q: impl ToString) -> CollectionSearch {
+        self.q = Some(q.to_string());
+        self
+    }
+}
+
+impl From<CollectionSearch> for GetCollectionSearch {
+    fn from(search: CollectionSearch) -> GetCollectionSearch {
+        GetCollectionSearch {
+            limit: search.limit.map(|n| n.to_string()),
+            bbox: search.bbox.map(|bbox| {
+                Vec::from(bbox)
+                    .into_iter()
+                    .map(|n| n.to_string())
+                    .collect::<Vec<_>>()
+                    .join(",")
+            }),
+            datetime: search.datetime,
+            q: search.q,
+            additional_fields: search
+                .additional_fields
+                .into_iter()
+                .map(|(key, value)| match value {
+                    Value::String(s) => (key, s),
+                    value => (key, value.to_string()),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl TryFrom<GetCollectionSearch> for CollectionSearch {
+    type Error = Error;
+
+    fn try_from(get_search: GetCollectionSearch) -> Result<CollectionSearch> {
+        Ok(CollectionSearch {
+            limit: get_search.limit.map(|limit| limit.parse()).transpose()?,
+            bbox: get_search.bbox.map(|bbox| bbox.parse()).transpose()?,
+            datetime: get_search.datetime,
+            q: get_search.q,
+            additional_fields: get_search
+                .additional_fields
+                .into_iter()
+                .map(|(key, value)| (key, Value::String(value)))
+                .collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CollectionSearch, GetCollectionSearch};
+    use crate::Bbox;
+
+    #[test]
+    fn get_round_trip() {
+        let search = CollectionSearch::default()
+            .limit(10)
+            .bbox(Bbox::new(-106., 41., -105., 42.))
+            .datetime("2023-01-01T00:00:00Z/..")
+            .q("landsat");
+        let get_search = GetCollectionSearch::from(search.clone());
+        assert_eq!(get_search.limit.as_deref(), Some("10"));
+        assert_eq!(get_search.bbox.as_deref(), Some("-106,41,-105,42"));
+        assert_eq!(CollectionSearch::try_from(get_search).unwrap(), search);
+    }
+
+    #[test]
+    fn invalid_bbox() {
+        let get_search = GetCollectionSearch {
+            bbox: Some("-106,41".to_string()),
+            ..Default::default()
+        };
+        assert!(CollectionSearch::try_from(get_search).is_err());
+    }
+}
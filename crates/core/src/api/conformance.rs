@@ -19,6 +19,9 @@ pub const GEOJSON_URI: &str = "http://www.opengis.net/spec/ogcapi-features-1/1.0
 /// The item search conformance uri.
 pub const ITEM_SEARCH_URI: &str = "https://api.stacspec.org/v1.0.0/item-search";
 
+/// The OGC API - Features - Part 2 (coordinate reference systems by reference) uri.
+pub const CRS_URI: &str = "http://www.opengis.net/spec/ogcapi-features-2/1.0/conf/crs";
+
 /// The filter conformance uris.
 pub const FILTER_URIS: [&str; 5] = [
     "http://www.opengis.net/spec/ogcapi-features-3/1.0/conf/filter",
@@ -28,6 +31,22 @@ pub const FILTER_URIS: [&str; 5] = [
     "http://www.opengis.net/spec/cql2/1.0/conf/cql2-json",
 ];
 
+/// The sort conformance uris.
+pub const SORT_URIS: [&str; 2] = [
+    "https://api.stacspec.org/v1.0.0/item-search#sort",
+    "https://api.stacspec.org/v1.0.0/ogcapi-features#sort",
+];
+
+/// The free-text search conformance uris for items.
+pub const FREE_TEXT_URIS: [&str; 2] = [
+    "https://api.stacspec.org/v1.0.0-rc.1/item-search#free-text",
+    "https://api.stacspec.org/v1.0.0-rc.1/ogcapi-features#free-text",
+];
+
+/// The free-text search conformance uri for collections.
+pub const COLLECTION_SEARCH_FREE_TEXT_URI: &str =
+    "https://api.stacspec.org/v1.0.0-rc.1/collection-search#free-text";
+
 /// To support "generic" clients that want to access multiple OGC API Features
 /// implementations - and not "just" a specific API / server, the server has to
 /// declare the conformance classes it implements and conforms to.
@@ -100,6 +119,50 @@ impl Conformance {
             .extend(FILTER_URIS.iter().map(|s| s.to_string()));
         self
     }
+
+    /// Adds [sort](https://github.com/stac-api-extensions/sort) conformance
+    /// classes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::api::Conformance;
+    /// let conformance = Conformance::new().item_search().sort();
+    /// ```
+    pub fn sort(mut self) -> Conformance {
+        self.conforms_to
+            .extend(SORT_URIS.iter().map(|s| s.to_string()));
+        self
+    }
+
+    /// Adds [free-text search](https://github.com/stac-api-extensions/freetext-search)
+    /// conformance classes for items.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::api::Conformance;
+    /// let conformance = Conformance::new().item_search().free_text();
+    /// ```
+    pub fn free_text(mut self) -> Conformance {
+        self.conforms_to
+            .extend(FREE_TEXT_URIS.iter().map(|s| s.to_string()));
+        self
+    }
+
+    /// Adds [OGC API - Features - Part
+    /// 2](https://docs.ogc.org/is/18-058r1/18-058r1.html) conformance class.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::api::Conformance;
+    /// let conformance = Conformance::new().ogcapi_features().crs();
+    /// ```
+    pub fn crs(mut self) -> Conformance {
+        self.conforms_to.push(CRS_URI.to_string());
+        self
+    }
 }
 
 impl Default for Conformance {
@@ -1,6 +1,7 @@
 use super::{Fields, Filter, Result, Search, Sortby};
 use crate::Error;
 use chrono::{DateTime, FixedOffset};
+use cql2::Expr;
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
@@ -13,10 +14,24 @@ pub struct Items {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub limit: Option<u64>,
 
+    /// The number of matching items to skip before the first returned item.
+    ///
+    /// This isn't part of STAC API, which uses opaque pagination tokens, but
+    /// backends that can page by position advertise it and use it in their
+    /// pagination links.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<u64>,
+
     /// Requested bounding box.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub bbox: Option<Bbox>,
 
+    /// The coordinate reference system of the bbox.
+    ///
+    /// If not set, the bbox is in CRS84.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "bbox-crs")]
+    pub bbox_crs: Option<String>,
+
     /// Single date+time, or a range ('/' separator), formatted to [RFC 3339,
     /// section 5.6](https://tools.ietf.org/html/rfc3339#section-5.6).
     ///
@@ -38,6 +53,12 @@ pub struct Items {
     #[serde(skip_serializing_if = "Option::is_none", rename = "filter-crs")]
     pub filter_crs: Option<String>,
 
+    /// The coordinate reference system of the returned geometries.
+    ///
+    /// Only CRS84 is currently supported.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub crs: Option<String>,
+
     /// CQL2 filter expression.
     #[serde(skip_serializing_if = "Option::is_none", flatten)]
     pub filter: Option<Filter>,
@@ -63,10 +84,18 @@ pub struct GetItems {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub limit: Option<String>,
 
+    /// The number of matching items to skip before the first returned item.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<String>,
+
     /// Requested bounding box.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub bbox: Option<String>,
 
+    /// The coordinate reference system of the bbox.
+    #[serde(skip_serializing_if = "Option::is_none", rename = "bbox-crs")]
+    pub bbox_crs: Option<String>,
+
     /// Single date+time, or a range ('/' separator), formatted to [RFC 3339,
     /// section 5.6](https://tools.ietf.org/html/rfc3339#section-5.6).
     ///
@@ -88,6 +117,12 @@ pub struct GetItems {
     #[serde(skip_serializing_if = "Option::is_none", rename = "filter-crs")]
     pub filter_crs: Option<String>,
 
+    /// The coordinate reference system of the returned geometries.
+    ///
+    /// Only CRS84 is currently supported.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub crs: Option<String>,
+
     /// This should always be cql2-text if present.
     #[serde(skip_serializing_if = "Option::is_none", rename = "filter-lang")]
     pub filter_lang: Option<String>,
@@ -160,6 +195,10 @@ impl Items {
 
     /// Returns true if this item's geometry matches this search's bbox.
     ///
+    /// If the search bbox is three-dimensional and the item has a
+    /// three-dimensional bbox, their elevation ranges must overlap as well.
+    /// Items without elevation information are matched on x and y only.
+    ///
     /// If **stac** is not built with the `geo` feature, this will return an error.
     ///
     /// # Examples
@@ -185,8 +224,12 @@ impl Items {
         if let Some(bbox) = self.bbox.as_ref() {
             #[cfg(feature = "geo")]
             {
+                let elevation_matches = item
+                    .bbox
+                    .map(|item_bbox| bbox.elevation_intersects(&item_bbox))
+                    .unwrap_or(true);
                 let bbox: geo::Rect = (*bbox).into();
-                item.intersects(&bbox)
+                Ok(elevation_matches && item.intersects(&bbox)?)
             }
             #[cfg(not(feature = "geo"))]
             {
@@ -248,7 +291,11 @@ impl Items {
 
     /// Returns true if this item matches this search's filter.
     ///
-    /// Currently unsupported, always raises an error if filter is set.
+    /// Both cql2-text and cql2-json filters are supported. The filter is
+    /// evaluated against the item with [Item::matches_cql2], so properties
+    /// are referenced by name, e.g. `eo:cloud_cover < 10`. Returns an error
+    /// if the filter can't be parsed, or can't be evaluated against this
+    /// item (e.g. because it doesn't have a property the filter uses).
     ///
     /// # Examples
     ///
@@ -259,18 +306,39 @@ impl Items {
     /// let mut search = Search::new();
     /// let mut item = Item::new("item-id");
     /// assert!(search.filter_matches(&item).unwrap());
+    /// let _ = item.properties.additional_fields.insert("eo:cloud_cover".into(), 5.into());
+    /// search.filter = Some("eo:cloud_cover < 10".parse().unwrap());
+    /// assert!(search.filter_matches(&item).unwrap());
+    /// search.filter = Some("id = 'another-id'".parse().unwrap());
+    /// assert!(!search.filter_matches(&item).unwrap());
     /// search.filter = Some(Default::default());
     /// assert!(search.filter_matches(&item).is_err());
     /// ```
-    pub fn filter_matches(&self, _: &Item) -> Result<bool> {
-        if self.filter.as_ref().is_some() {
-            // TODO implement
-            Err(Error::Unimplemented("filter"))
-        } else {
-            Ok(true)
+    pub fn filter_matches(&self, item: &Item) -> Result<bool> {
+        match self.filter_expr()? {
+            Some(expr) => item.clone().matches_cql2(expr),
+            None => Ok(true),
         }
     }
 
+    /// Returns this search's filter as a CQL2 expression, if it has one.
+    ///
+    /// Useful to parse the filter once before matching many items.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::api::Items;
+    ///
+    /// let mut items = Items::default();
+    /// assert!(items.filter_expr().unwrap().is_none());
+    /// items.filter = Some("id = 'an-id'".parse().unwrap());
+    /// assert!(items.filter_expr().unwrap().is_some());
+    /// ```
+    pub fn filter_expr(&self) -> Result<Option<Expr>> {
+        self.filter.clone().map(Expr::try_from).transpose()
+    }
+
     /// Converts this items object to a search in the given collection.
     ///
     /// # Examples
@@ -293,6 +361,83 @@ impl Items {
         }
     }
 
+    /// Converts this query's bbox to CRS84 and checks that the requested
+    /// output crs is supported.
+    ///
+    /// If `bbox-crs` is set, the bbox is reprojected and `bbox-crs` is cleared.
+    /// Reprojecting from anything other than CRS84 requires the `proj`
+    /// feature. See [crs::bbox_to_crs84](crate::crs::bbox_to_crs84) for the
+    /// supported coordinate reference systems.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::api::Items;
+    ///
+    /// let items = Items {
+    ///     bbox: Some(stac::Bbox::new(-105.0, 40.0, -104.0, 41.0)),
+    ///     bbox_crs: Some(stac::crs::CRS84.to_string()),
+    ///     ..Default::default()
+    /// };
+    /// let items = items.into_crs84().unwrap();
+    /// assert!(items.bbox_crs.is_none());
+    ///
+    /// let items = Items {
+    ///     crs: Some("http://www.opengis.net/def/crs/EPSG/0/3857".to_string()),
+    ///     ..Default::default()
+    /// };
+    /// assert!(items.into_crs84().is_err());
+    /// ```
+    pub fn into_crs84(mut self) -> Result<Items> {
+        if let Some(crs) = self.crs.as_deref()
+            && !crate::crs::is_crs84(crs)
+        {
+            return Err(Error::UnsupportedCrs(crs.to_string()));
+        }
+        if let Some(bbox_crs) = self.bbox_crs.take()
+            && let Some(bbox) = self.bbox
+        {
+            self.bbox = Some(crate::crs::bbox_to_crs84(bbox, &bbox_crs)?);
+        }
+        Ok(self)
+    }
+
+    /// Applies pagination information, e.g. from
+    /// [ItemCollection::next](crate::api::ItemCollection::next), to this
+    /// query.
+    ///
+    /// An `offset` (a number or a numeric string) sets [Items::offset], and
+    /// everything else (e.g. a `token`) is set as an additional field.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::api::Items;
+    /// use serde_json::json;
+    ///
+    /// let mut items = Items::default();
+    /// let pagination = json!({"offset": 10, "token": "next:an-id"});
+    /// items.set_pagination(pagination.as_object().unwrap().clone());
+    /// assert_eq!(items.offset, Some(10));
+    /// assert_eq!(items.additional_fields["token"], "next:an-id");
+    /// ```
+    pub fn set_pagination(&mut self, pagination: Map<String, Value>) {
+        for (key, value) in pagination {
+            let offset = (key == "offset")
+                .then(|| {
+                    value
+                        .as_u64()
+                        .or_else(|| value.as_str().and_then(|s| s.parse().ok()))
+                })
+                .flatten();
+            if let Some(offset) = offset {
+                self.offset = Some(offset);
+            } else {
+                let _ = self.additional_fields.insert(key, value);
+            }
+        }
+    }
+
     /// Converts the filter to cql2-json, if it is set.
     pub fn into_cql2_json(mut self) -> Result<Items> {
         if let Some(filter) = self.filter {
@@ -321,6 +466,7 @@ impl TryFrom<Items> for GetItems {
         };
         Ok(GetItems {
             limit: items.limit.map(|n| n.to_string()),
+            offset: items.offset.map(|n| n.to_string()),
             bbox: items.bbox.map(|bbox| {
                 Vec::from(bbox)
                     .into_iter()
@@ -328,6 +474,7 @@ impl TryFrom<Items> for GetItems {
                     .collect::<Vec<_>>()
                     .join(",")
             }),
+            bbox_crs: items.bbox_crs,
             datetime: items.datetime,
             fields: items.fields.map(|fields| fields.to_string()),
             sortby: if items.sortby.is_empty() {
@@ -343,6 +490,7 @@ impl TryFrom<Items> for GetItems {
                 )
             },
             filter_crs: items.filter_crs,
+            crs: items.crs,
             filter_lang: if filter.is_some() {
                 Some("cql2-text".to_string())
             } else {
@@ -385,13 +533,16 @@ impl TryFrom<GetItems> for Items {
 
         Ok(Items {
             limit: get_items.limit.map(|limit| limit.parse()).transpose()?,
+            offset: get_items.offset.map(|offset| offset.parse()).transpose()?,
             bbox,
+            bbox_crs: get_items.bbox_crs,
             datetime: get_items.datetime,
             fields: get_items
                 .fields
                 .map(|fields| fields.parse().expect("infallible")),
             sortby,
             filter_crs: get_items.filter_crs,
+            crs: get_items.crs,
             filter: get_items.filter.map(Filter::Cql2Text),
             query: None,
             additional_fields: get_items
@@ -436,11 +587,14 @@ mod tests {
 
         let get_items = GetItems {
             limit: Some("42".to_string()),
+            offset: Some("10".to_string()),
             bbox: Some("-1,-2,1,2".to_string()),
+            bbox_crs: Some("http://www.opengis.net/def/crs/EPSG/0/4326".to_string()),
             datetime: Some("2023".to_string()),
             fields: Some("+foo,-bar".to_string()),
             sortby: Some("-foo".to_string()),
             filter_crs: None,
+            crs: None,
             filter_lang: Some("cql2-text".to_string()),
             filter: Some("dummy text".to_string()),
             additional_fields,
@@ -448,10 +602,15 @@ mod tests {
 
         let items: Items = get_items.try_into().unwrap();
         assert_eq!(items.limit.unwrap(), 42);
+        assert_eq!(items.offset.unwrap(), 10);
         assert_eq!(
             items.bbox.unwrap(),
             vec![-1.0, -2.0, 1.0, 2.0].try_into().unwrap()
         );
+        assert_eq!(
+            items.bbox_crs.unwrap(),
+            "http://www.opengis.net/def/crs/EPSG/0/4326"
+        );
         assert_eq!(items.datetime.unwrap(), "2023");
         assert_eq!(
             items.fields.unwrap(),
@@ -481,7 +640,9 @@ mod tests {
 
         let items = Items {
             limit: Some(42),
+            offset: Some(10),
             bbox: Some(vec![-1.0, -2.0, 1.0, 2.0].try_into().unwrap()),
+            bbox_crs: None,
             datetime: Some("2023".to_string()),
             fields: Some(Fields {
                 include: vec!["foo".to_string()],
@@ -492,6 +653,7 @@ mod tests {
                 direction: Direction::Descending,
             }],
             filter_crs: None,
+            crs: None,
             filter: Some(Filter::Cql2Text("dummy text".to_string())),
             query: None,
             additional_fields,
@@ -499,6 +661,7 @@ mod tests {
 
         let get_items: GetItems = items.try_into().unwrap();
         assert_eq!(get_items.limit.unwrap(), "42");
+        assert_eq!(get_items.offset.unwrap(), "10");
         assert_eq!(get_items.bbox.unwrap(), "-1,-2,1,2");
         assert_eq!(get_items.datetime.unwrap(), "2023");
         assert_eq!(get_items.fields.unwrap(), "foo,-bar");
@@ -507,6 +670,28 @@ mod tests {
         assert_eq!(get_items.additional_fields["token"], "\"foobar\"");
     }
 
+    #[test]
+    fn offset_round_trip() {
+        let items: Items = serde_json::from_value(json!({"limit": 1, "offset": 2})).unwrap();
+        assert_eq!(items.offset, Some(2));
+        assert!(items.additional_fields.is_empty());
+        let value = serde_json::to_value(items).unwrap();
+        assert_eq!(value["offset"], 2);
+    }
+
+    #[test]
+    fn set_pagination() {
+        let mut items = Items {
+            offset: Some(1),
+            ..Default::default()
+        };
+        let mut pagination = Map::new();
+        let _ = pagination.insert("offset".to_string(), 3.into());
+        items.set_pagination(pagination);
+        assert_eq!(items.offset, Some(3));
+        assert!(items.additional_fields.is_empty());
+    }
+
     #[test]
     fn filter() {
         let value = json!({
@@ -516,4 +701,37 @@ mod tests {
         let items: Items = serde_json::from_value(value).unwrap();
         assert!(items.filter.is_some());
     }
+
+    #[test]
+    #[cfg(feature = "geo")]
+    fn bbox_matches_3d() {
+        use crate::Item;
+
+        let mut item = Item::new("an-id");
+        item.set_geometry(Some(
+            serde_json::from_value(json!({
+                "type": "Point",
+                "coordinates": [-105.1, 41.1, 100.0]
+            }))
+            .unwrap(),
+        ))
+        .unwrap();
+        let mut items = Items {
+            bbox: Some(
+                vec![-106.0, 41.0, 0.0, -105.0, 42.0, 200.0]
+                    .try_into()
+                    .unwrap(),
+            ),
+            ..Default::default()
+        };
+        assert!(items.bbox_matches(&item).unwrap());
+        items.bbox = Some(
+            vec![-106.0, 41.0, 150.0, -105.0, 42.0, 200.0]
+                .try_into()
+                .unwrap(),
+        );
+        assert!(!items.bbox_matches(&item).unwrap());
+        items.bbox = Some(vec![-106.0, 41.0, -105.0, 42.0].try_into().unwrap());
+        assert!(items.bbox_matches(&item).unwrap());
+    }
 }
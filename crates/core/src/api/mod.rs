@@ -71,6 +71,7 @@
 
 mod adapters;
 mod client;
+mod collection_search;
 mod collections;
 mod conformance;
 mod fields;
@@ -95,17 +96,18 @@ pub use client::{
 };
 #[cfg(not(feature = "async"))]
 pub use client::{CollectionsClient, ItemsClient, PagedCollectionsClient, TransactionClient};
+pub use collection_search::{CollectionSearch, GetCollectionSearch};
 pub use collections::Collections;
 pub use conformance::{
-    COLLECTIONS_URI, CORE_URI, Conformance, FEATURES_URI, FILTER_URIS, GEOJSON_URI,
-    ITEM_SEARCH_URI, OGC_API_FEATURES_URI,
+    COLLECTION_SEARCH_FREE_TEXT_URI, COLLECTIONS_URI, CORE_URI, CRS_URI, Conformance, FEATURES_URI,
+    FILTER_URIS, FREE_TEXT_URIS, GEOJSON_URI, ITEM_SEARCH_URI, OGC_API_FEATURES_URI, SORT_URIS,
 };
 pub use fields::Fields;
 pub use filter::Filter;
 pub use item_collection::{Context, ItemCollection};
 pub use items::{GetItems, Items};
 pub use root::Root;
-pub use search::{GetSearch, Search};
+pub use search::{GetSearch, Search, intersects_geometry};
 pub use sort::{Direction, Sortby};
 pub use url_builder::UrlBuilder;
 
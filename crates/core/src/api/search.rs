@@ -1,8 +1,8 @@
 use super::{Fields, GetItems, Items, Result, Sortby};
 use crate::Error;
 use chrono::{DateTime, FixedOffset, NaiveDate, NaiveTime, TimeZone, Utc};
-use geojson::Geometry;
-use serde::{Deserialize, Serialize};
+use geojson::{GeoJson, Geometry};
+use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::{Map, Value};
 use stac::{Bbox, Item};
 use std::ops::{Deref, DerefMut};
@@ -16,8 +16,14 @@ pub struct Search {
 
     /// Searches items by performing intersection between their geometry and provided GeoJSON geometry.
     ///
-    /// All GeoJSON geometry types must be supported.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    /// All GeoJSON geometry types must be supported. A GeoJSON Feature or
+    /// FeatureCollection is also accepted when deserializing, see
+    /// [intersects_geometry].
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "deserialize_intersects"
+    )]
     pub intersects: Option<Geometry>,
 
     /// Array of Item ids to return.
@@ -108,6 +114,12 @@ impl Search {
         self
     }
 
+    /// Sets the offset of this search.
+    pub fn offset(mut self, offset: u64) -> Search {
+        self.items.offset = Some(offset);
+        self
+    }
+
     /// Sets the sortby of this search.
     pub fn sortby(mut self, sortby: Vec<Sortby>) -> Search {
         self.items.sortby = sortby;
@@ -145,6 +157,72 @@ impl Search {
         }
     }
 
+    /// Converts this search's bbox to CRS84.
+    ///
+    /// See [Items::into_crs84] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::api::Search;
+    ///
+    /// let search = Search::new().bbox(stac::Bbox::new(-105.0, 40.0, -104.0, 41.0));
+    /// let search = search.into_crs84().unwrap();
+    /// ```
+    pub fn into_crs84(mut self) -> Result<Search> {
+        self.items = self.items.into_crs84()?;
+        Ok(self)
+    }
+
+    /// Returns the size, in bytes, of this search's serialized intersects geometry.
+    ///
+    /// Returns zero if there is no intersects.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::api::Search;
+    /// use geojson::Geometry;
+    ///
+    /// let search = Search::new();
+    /// assert_eq!(search.intersects_size().unwrap(), 0);
+    /// let search = search.intersects(Geometry::new_point(vec![0.0, 0.0]));
+    /// assert!(search.intersects_size().unwrap() > 0);
+    /// ```
+    pub fn intersects_size(&self) -> Result<usize> {
+        match self.intersects.as_ref() {
+            Some(intersects) => Ok(serde_json::to_string(intersects)?.len()),
+            None => Ok(0),
+        }
+    }
+
+    /// Returns an error if this search's serialized intersects geometry is
+    /// larger than `max_size` bytes.
+    ///
+    /// Very large geometries (e.g. polygons with thousands of vertices) can
+    /// blow past URL length limits when converted to GET parameters, and can
+    /// be expensive for servers to process. Returns the search unchanged if
+    /// it is small enough.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::api::Search;
+    /// use geojson::Geometry;
+    ///
+    /// let search = Search::new().intersects(Geometry::new_point(vec![0.0, 0.0]));
+    /// let search = search.check_intersects_size(1024).unwrap();
+    /// search.check_intersects_size(8).unwrap_err();
+    /// ```
+    pub fn check_intersects_size(self, max_size: usize) -> Result<Search> {
+        let size = self.intersects_size()?;
+        if size > max_size {
+            Err(Error::IntersectsTooLarge(size, max_size))
+        } else {
+            Ok(self)
+        }
+    }
+
     /// Returns true if this item matches this search.
     ///
     /// # Examples
@@ -438,6 +516,63 @@ fn expand_datetime_to_end(s: &str) -> Result<DateTime<FixedOffset>> {
     Err(Error::UnrecognizedDateFormat(s.to_string()))
 }
 
+/// Converts GeoJSON into a geometry that can be used as a search's intersects.
+///
+/// Geometries are returned as-is, and features are replaced by their
+/// geometry. A feature collection becomes a geometry collection of its
+/// features' geometries (or just the geometry, if there's only one feature),
+/// which intersects everything that the union of the geometries intersects.
+///
+/// # Examples
+///
+/// ```
+/// use stac::api::intersects_geometry;
+///
+/// let geojson = r#"{"type": "Feature", "geometry": {"type": "Point", "coordinates": [0, 0]}, "properties": {}}"#;
+/// let geometry = intersects_geometry(geojson.parse().unwrap()).unwrap();
+/// assert_eq!(geometry, stac::Geometry::new_point(vec![0.0, 0.0]));
+/// ```
+pub fn intersects_geometry(geojson: GeoJson) -> Result<Geometry> {
+    match geojson {
+        GeoJson::Geometry(geometry) => Ok(geometry),
+        GeoJson::Feature(feature) => feature
+            .geometry
+            .ok_or(Error::InvalidIntersects("feature has no geometry")),
+        GeoJson::FeatureCollection(feature_collection) => {
+            let mut geometries = feature_collection
+                .features
+                .into_iter()
+                .map(|feature| {
+                    feature.geometry.ok_or(Error::InvalidIntersects(
+                        "feature collection has a feature without a geometry",
+                    ))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            match geometries.len() {
+                0 => Err(Error::InvalidIntersects(
+                    "feature collection has no features",
+                )),
+                1 => Ok(geometries.remove(0)),
+                _ => Ok(Geometry::new(
+                    geojson::GeometryValue::new_geometry_collection(geometries),
+                )),
+            }
+        }
+    }
+}
+
+fn deserialize_intersects<'de, D>(
+    deserializer: D,
+) -> std::result::Result<Option<Geometry>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<GeoJson>::deserialize(deserializer)?
+        .map(intersects_geometry)
+        .transpose()
+        .map_err(serde::de::Error::custom)
+}
+
 impl TryFrom<Search> for GetSearch {
     type Error = Error;
 
@@ -473,7 +608,11 @@ impl TryFrom<GetSearch> for Search {
         let items: Items = get_search.items.try_into()?;
         let intersects = get_search
             .intersects
-            .map(|intersects| serde_json::from_str(&intersects))
+            .map(|intersects| {
+                serde_json::from_str(&intersects)
+                    .map_err(Error::from)
+                    .and_then(intersects_geometry)
+            })
             .transpose()?;
         let collections = get_search
             .collections
@@ -718,4 +857,110 @@ mod tests {
             "2023-01-01T00:00:00+00:00/2023-12-31T23:59:59+00:00"
         );
     }
+
+    #[test]
+    fn intersects_too_large() {
+        let coordinates: Vec<Vec<f64>> = (0..1000).map(|i| vec![i as f64, 0.0]).collect();
+        let geometry: Geometry = serde_json::from_value(serde_json::json!({
+            "type": "LineString",
+            "coordinates": coordinates,
+        }))
+        .unwrap();
+        let search = Search::new().intersects(geometry);
+        let size = search.intersects_size().unwrap();
+        let search = search.check_intersects_size(size).unwrap();
+        let error = search.check_intersects_size(size - 1).unwrap_err();
+        assert!(matches!(error, Error::IntersectsTooLarge(s, m) if s == size && m == size - 1));
+    }
+
+    #[test]
+    fn intersects_feature() {
+        let search: Search = serde_json::from_value(serde_json::json!({
+            "intersects": {
+                "type": "Feature",
+                "geometry": {"type": "Point", "coordinates": [1.0, 2.0]},
+                "properties": {},
+            }
+        }))
+        .unwrap();
+        assert_eq!(
+            search.intersects.unwrap(),
+            Geometry::new_point(vec![1.0, 2.0])
+        );
+    }
+
+    #[test]
+    fn intersects_feature_collection() {
+        let feature_collection = serde_json::json!({
+            "type": "FeatureCollection",
+            "features": [
+                {
+                    "type": "Feature",
+                    "geometry": {"type": "Point", "coordinates": [1.0, 2.0]},
+                    "properties": {},
+                },
+                {
+                    "type": "Feature",
+                    "geometry": {"type": "Point", "coordinates": [3.0, 4.0]},
+                    "properties": {},
+                },
+            ],
+        });
+        let get_search = GetSearch {
+            intersects: Some(feature_collection.to_string()),
+            ..Default::default()
+        };
+        let search: Search = get_search.try_into().unwrap();
+        assert_eq!(
+            serde_json::to_value(search.intersects.unwrap()).unwrap(),
+            serde_json::json!({
+                "type": "GeometryCollection",
+                "geometries": [
+                    {"type": "Point", "coordinates": [1.0, 2.0]},
+                    {"type": "Point", "coordinates": [3.0, 4.0]},
+                ],
+            })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "geo")]
+    fn matches_bbox_only_item() {
+        let mut item = Item::new("an-id");
+        item.bbox = Some(Bbox::new(1., 2., 3., 4.));
+        assert!(
+            Search::new()
+                .bbox(Bbox::new(2., 3., 5., 5.))
+                .matches(&item)
+                .unwrap()
+        );
+        assert!(
+            Search::new()
+                .intersects(Bbox::new(0., 0., 1.5, 2.5).to_geometry())
+                .matches(&item)
+                .unwrap()
+        );
+        assert!(
+            !Search::new()
+                .bbox(Bbox::new(10., 10., 11., 11.))
+                .matches(&item)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn intersects_without_geometry() {
+        let result: std::result::Result<Search, _> = serde_json::from_value(serde_json::json!({
+            "intersects": {"type": "Feature", "geometry": null, "properties": {}}
+        }));
+        let _ = result.unwrap_err();
+        let get_search = GetSearch {
+            intersects: Some(r#"{"type": "FeatureCollection", "features": []}"#.to_string()),
+            ..Default::default()
+        };
+        assert!(matches!(
+            Search::try_from(get_search).unwrap_err(),
+            Error::InvalidIntersects(_)
+        ));
+    }
 }
@@ -1,4 +1,4 @@
-use crate::{Band, DataType, Result, Statistics};
+use crate::{Band, DataType, Result, Statistics, mime, role::Role};
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
@@ -113,13 +113,168 @@ pub trait Assets {
     /// ```
     fn assets_mut(&mut self) -> &mut IndexMap<String, Asset>;
 
+    /// Returns the keys and assets that have a role.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Asset, Assets, Item, role::Role};
+    ///
+    /// let mut item = Item::new("an-id");
+    /// item.assets.insert("data".into(), Asset::new("./data.tif").role(Role::Data));
+    /// item.assets.insert("thumbnail".into(), Asset::new("./thumbnail.png").role(Role::Thumbnail));
+    /// let keys: Vec<_> = item.assets_with_role(Role::Data).map(|(key, _)| key).collect();
+    /// assert_eq!(keys, ["data"]);
+    /// ```
+    fn assets_with_role(&self, role: impl AsRef<str>) -> impl Iterator<Item = (&str, &Asset)> {
+        self.assets()
+            .iter()
+            .filter(move |(_, asset)| asset.roles.iter().any(|r| r == role.as_ref()))
+            .map(|(key, asset)| (key.as_str(), asset))
+    }
+
+    /// Returns the thumbnail asset.
+    ///
+    /// This is the first asset with the `thumbnail` role, or else the asset
+    /// with the `thumbnail` key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Asset, Assets, Item, role::Role};
+    ///
+    /// let mut item = Item::new("an-id");
+    /// assert!(item.thumbnail().is_none());
+    /// item.assets.insert("preview".into(), Asset::new("./preview.png").role(Role::Thumbnail));
+    /// assert_eq!(item.thumbnail().unwrap().href, "./preview.png");
+    /// ```
+    fn thumbnail(&self) -> Option<&Asset> {
+        self.assets_with_role(Role::Thumbnail)
+            .map(|(_, asset)| asset)
+            .next()
+            .or_else(|| self.assets().get(Role::Thumbnail.as_str()))
+    }
+
+    /// Returns the key and the first asset with a media type.
+    ///
+    /// See [Asset::has_media_type] for how media types are matched.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Asset, Assets, Item};
+    ///
+    /// let mut item = Item::new("an-id");
+    /// let mut asset = Asset::new("./data.tif");
+    /// asset.r#type = Some(stac::mime::COG.to_string());
+    /// item.assets.insert("data".into(), asset);
+    /// let (key, _) = item.asset_by_media_type("image/tiff").unwrap();
+    /// assert_eq!(key, "data");
+    /// ```
+    fn asset_by_media_type(&self, media_type: &str) -> Option<(&str, &Asset)> {
+        self.assets()
+            .iter()
+            .find(|(_, asset)| asset.has_media_type(media_type))
+            .map(|(key, asset)| (key.as_str(), asset))
+    }
+
+    /// Keeps only the assets for which a predicate returns true.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Asset, Assets, Item, role::Role};
+    ///
+    /// let mut item = Item::new("an-id");
+    /// item.assets.insert("data".into(), Asset::new("./data.tif").role(Role::Data));
+    /// item.assets.insert("thumbnail".into(), Asset::new("./thumbnail.png").role(Role::Thumbnail));
+    /// item.retain_assets(|_, asset| asset.has_role(Role::Data));
+    /// assert_eq!(item.assets.len(), 1);
+    /// ```
+    fn retain_assets(&mut self, mut f: impl FnMut(&str, &Asset) -> bool) {
+        self.assets_mut().retain(|key, asset| f(key, asset));
+    }
+
     /// Makes all asset hrefs absolute.
+    ///
+    /// This is the same as [Assets::make_asset_hrefs_absolute].
     fn make_assets_absolute(&mut self, base: &str) -> Result<()> {
+        self.make_asset_hrefs_absolute(base)
+    }
+
+    /// Replaces every asset href with the result of a function.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Asset, Assets, Item};
+    ///
+    /// let mut item = Item::new("an-id");
+    /// item.assets.insert("data".into(), Asset::new("s3://bucket/data.tif"));
+    /// item.map_asset_hrefs(|href| href.replace("s3://bucket/", "https://bucket.test/"));
+    /// assert_eq!(item.assets["data"].href, "https://bucket.test/data.tif");
+    /// ```
+    fn map_asset_hrefs(&mut self, mut f: impl FnMut(&str) -> String) {
+        for asset in self.assets_mut().values_mut() {
+            asset.href = f(&asset.href);
+        }
+    }
+
+    /// Makes all relative asset hrefs absolute with respect to a base.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Asset, Assets, Item};
+    ///
+    /// let mut item = Item::new("an-id");
+    /// item.assets.insert("data".into(), Asset::new("./data.tif"));
+    /// item.make_asset_hrefs_absolute("/data/item.json").unwrap();
+    /// assert_eq!(item.assets["data"].href, "/data/data.tif");
+    /// ```
+    fn make_asset_hrefs_absolute(&mut self, base: &str) -> Result<()> {
         for asset in self.assets_mut().values_mut() {
             asset.href = crate::href::make_absolute(&asset.href, base)?.into();
         }
         Ok(())
     }
+
+    /// Makes asset hrefs relative to a base.
+    ///
+    /// Only hrefs on the same filesystem or host as the base are changed, so
+    /// e.g. `https://` asset hrefs stay absolute when the base is a local
+    /// path.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Asset, Assets, Item};
+    ///
+    /// let mut item = Item::new("an-id");
+    /// item.assets.insert("data".into(), Asset::new("/data/an-id/data.tif"));
+    /// item.assets.insert("thumbnail".into(), Asset::new("https://stac.test/thumbnail.png"));
+    /// item.make_asset_hrefs_relative("/data/an-id/an-id.json");
+    /// assert_eq!(item.assets["data"].href, "./data.tif");
+    /// assert_eq!(item.assets["thumbnail"].href, "https://stac.test/thumbnail.png");
+    /// ```
+    fn make_asset_hrefs_relative(&mut self, base: &str) {
+        self.map_asset_hrefs(|href| {
+            if crate::href::is_absolute(href) {
+                crate::href::make_relative(href, base)
+            } else {
+                href.to_string()
+            }
+        })
+    }
+}
+
+/// Lowercases a media type and removes whitespace around its parameters.
+fn normalize_media_type(media_type: &str) -> String {
+    media_type
+        .split(';')
+        .map(|part| part.trim().to_ascii_lowercase())
+        .collect::<Vec<_>>()
+        .join(";")
 }
 
 impl Asset {
@@ -150,6 +305,75 @@ impl Asset {
         }
     }
 
+    /// Creates a new asset for a [Zarr](https://zarr.dev/) store.
+    ///
+    /// The asset has the [zarr media type](crate::mime::APPLICATION_ZARR) and
+    /// the `data` and `zarr` roles.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::Asset;
+    ///
+    /// let asset = Asset::zarr("s3://bucket/data.zarr");
+    /// assert_eq!(asset.r#type.as_deref(), Some(stac::mime::APPLICATION_ZARR));
+    /// assert_eq!(asset.roles, vec!["data", "zarr"]);
+    /// ```
+    pub fn zarr(href: impl ToString) -> Asset {
+        let mut asset = Asset::new(href).role(Role::Data).role("zarr");
+        asset.r#type = Some(mime::APPLICATION_ZARR.to_string());
+        asset
+    }
+
+    /// Creates a new asset for a [NetCDF](https://www.unidata.ucar.edu/software/netcdf/) file.
+    ///
+    /// The asset has the [NetCDF media type](crate::mime::APPLICATION_NETCDF)
+    /// and the `data` role.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::Asset;
+    ///
+    /// let asset = Asset::netcdf("s3://bucket/data.nc");
+    /// assert_eq!(asset.r#type.as_deref(), Some(stac::mime::APPLICATION_NETCDF));
+    /// assert_eq!(asset.roles, vec!["data"]);
+    /// ```
+    pub fn netcdf(href: impl ToString) -> Asset {
+        let mut asset = Asset::new(href).role(Role::Data);
+        asset.r#type = Some(mime::APPLICATION_NETCDF.to_string());
+        asset
+    }
+
+    /// Creates a new asset for [Kerchunk](https://fsspec.github.io/kerchunk/) references.
+    ///
+    /// References that end in `.parquet` get the parquet media type, and
+    /// everything else is JSON. The asset has the `references` role, since it
+    /// points at data rather than being data itself.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::Asset;
+    ///
+    /// let asset = Asset::kerchunk("s3://bucket/references.json");
+    /// assert_eq!(asset.r#type.as_deref(), Some("application/json"));
+    /// assert_eq!(asset.roles, vec!["references"]);
+    ///
+    /// let asset = Asset::kerchunk("s3://bucket/references.parquet");
+    /// assert_eq!(asset.r#type.as_deref(), Some("application/vnd.apache.parquet"));
+    /// ```
+    pub fn kerchunk(href: impl ToString) -> Asset {
+        let mut asset = Asset::new(href).role("references");
+        let r#type = if asset.href.trim_end_matches('/').ends_with(".parquet") {
+            mime::APPLICATION_PARQUET
+        } else {
+            mime::APPLICATION_KERCHUNK_JSON
+        };
+        asset.r#type = Some(r#type.to_string());
+        asset
+    }
+
     /// Adds a role to this asset, returning the modified asset.
     ///
     /// Useful for builder patterns.
@@ -166,6 +390,53 @@ impl Asset {
         self.roles.dedup();
         self
     }
+
+    /// Returns true if this asset has a well-known role.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Asset, role::Role};
+    ///
+    /// let asset = Asset::new("asset/dataset.tif").role(Role::Data);
+    /// assert!(asset.has_role(Role::Data));
+    /// assert!(!asset.has_role(Role::Thumbnail));
+    /// ```
+    pub fn has_role(&self, role: Role) -> bool {
+        self.roles.iter().any(|r| r == role.as_str())
+    }
+
+    /// Returns true if this asset has a media type.
+    ///
+    /// Media types are compared case-insensitively. If the media type
+    /// doesn't have any parameters, the asset's parameters are ignored, so
+    /// `image/tiff` matches a cloud-optimized GeoTIFF.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::Asset;
+    ///
+    /// let mut asset = Asset::new("./data.tif");
+    /// asset.r#type = Some(stac::mime::COG.to_string());
+    /// assert!(asset.has_media_type("image/tiff"));
+    /// assert!(asset.has_media_type(stac::mime::COG));
+    /// assert!(!asset.has_media_type("image/png"));
+    /// ```
+    pub fn has_media_type(&self, media_type: &str) -> bool {
+        let Some(r#type) = self.r#type.as_deref() else {
+            return false;
+        };
+        let media_type = media_type.trim();
+        if media_type.contains(';') {
+            normalize_media_type(r#type) == normalize_media_type(media_type)
+        } else {
+            r#type
+                .split(';')
+                .next()
+                .is_some_and(|essence| essence.trim().eq_ignore_ascii_case(media_type))
+        }
+    }
 }
 
 impl From<String> for Asset {
@@ -185,6 +456,71 @@ mod tests {
     use super::{Asset, Assets};
     use crate::Item;
 
+    #[test]
+    fn make_asset_hrefs_relative() {
+        let mut item = Item::new("an-id");
+        for (key, href) in [
+            ("local", "/data/an-id/data.tif"),
+            ("sibling", "/data/other/data.tif"),
+            ("relative", "./data.tif"),
+            ("same-host", "https://stac.test/data/an-id/data.tif"),
+            ("other-host", "https://other.test/data/an-id/data.tif"),
+            ("object-store", "s3://bucket/data.tif"),
+        ] {
+            let _ = item.assets.insert(key.to_string(), Asset::new(href));
+        }
+        let mut local = item.clone();
+        local.make_asset_hrefs_relative("/data/an-id/an-id.json");
+        assert_eq!(local.assets["local"].href, "./data.tif");
+        assert_eq!(local.assets["sibling"].href, "../other/data.tif");
+        assert_eq!(local.assets["relative"].href, "./data.tif");
+        assert_eq!(
+            local.assets["same-host"].href,
+            "https://stac.test/data/an-id/data.tif"
+        );
+        assert_eq!(local.assets["object-store"].href, "s3://bucket/data.tif");
+
+        item.make_asset_hrefs_relative("https://stac.test/data/an-id/an-id.json");
+        assert_eq!(item.assets["same-host"].href, "./data.tif");
+        assert_eq!(
+            item.assets["other-host"].href,
+            "https://other.test/data/an-id/data.tif"
+        );
+        assert_eq!(item.assets["local"].href, "/data/an-id/data.tif");
+
+        item.make_asset_hrefs_absolute("https://stac.test/data/an-id/an-id.json")
+            .unwrap();
+        assert_eq!(
+            item.assets["same-host"].href,
+            "https://stac.test/data/an-id/data.tif"
+        );
+    }
+
+    #[test]
+    fn has_media_type() {
+        let mut asset = Asset::new("./data.tif");
+        assert!(!asset.has_media_type("image/tiff"));
+        asset.r#type = Some("Image/TIFF;application=geotiff; profile=cloud-optimized".to_string());
+        assert!(asset.has_media_type("image/tiff"));
+        assert!(asset.has_media_type(crate::mime::COG));
+        assert!(!asset.has_media_type("image/tiff; application=geotiff"));
+        assert!(!asset.has_media_type("image/tiff-fx"));
+    }
+
+    #[test]
+    fn thumbnail_by_key() {
+        let mut item = Item::new("an-id");
+        let _ = item
+            .assets
+            .insert("thumbnail".to_string(), Asset::new("./thumbnail.png"));
+        assert_eq!(item.thumbnail().unwrap().href, "./thumbnail.png");
+        let _ = item.assets.insert(
+            "preview".to_string(),
+            Asset::new("./preview.png").role("thumbnail"),
+        );
+        assert_eq!(item.thumbnail().unwrap().href, "./preview.png");
+    }
+
     #[test]
     fn new() {
         let asset = Asset::new("an-href");
@@ -0,0 +1,259 @@
+//! Auditing of fields that conversions don't know how to handle.
+//!
+//! Vendor-specific fields survive most conversions untouched, but some paths
+//! (e.g. flattening an item for stac-geoparquet) can't represent them and
+//! drop them with a warning. A [FieldAudit] counts these fields by name, so
+//! you can check that nothing important was lost:
+//!
+//! ```
+//! use stac::{Item, audit::FieldAudit};
+//!
+//! let mut item = Item::new("an-id");
+//! let _ = item.additional_fields.insert("vendor_field".into(), 42.into());
+//! let mut audit = FieldAudit::new();
+//! let _ = item.into_flat_item_with_audit(&mut audit).unwrap();
+//! assert_eq!(audit.count("vendor_field"), 1);
+//! ```
+
+use serde::Serialize;
+use serde_json::Value;
+use std::{collections::BTreeMap, fmt::Display};
+
+const ITEM_KEYS: [&str; 10] = [
+    "type",
+    "stac_version",
+    "stac_extensions",
+    "id",
+    "geometry",
+    "bbox",
+    "properties",
+    "links",
+    "assets",
+    "collection",
+];
+
+const CATALOG_KEYS: [&str; 7] = [
+    "type",
+    "stac_version",
+    "stac_extensions",
+    "id",
+    "title",
+    "description",
+    "links",
+];
+
+const COLLECTION_KEYS: [&str; 14] = [
+    "type",
+    "stac_version",
+    "stac_extensions",
+    "id",
+    "title",
+    "description",
+    "keywords",
+    "license",
+    "providers",
+    "extent",
+    "summaries",
+    "links",
+    "assets",
+    "item_assets",
+];
+
+const ITEM_COLLECTION_KEYS: [&str; 3] = ["type", "features", "links"];
+
+/// Counts of fields that weren't handled as-is, keyed by field name.
+///
+/// Nested fields are named by their path, e.g. `properties.id` or
+/// `assets.thumbnail`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct FieldAudit {
+    counts: BTreeMap<String, u64>,
+}
+
+impl FieldAudit {
+    /// Creates a new, empty audit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::audit::FieldAudit;
+    ///
+    /// let audit = FieldAudit::new();
+    /// assert!(audit.is_empty());
+    /// ```
+    pub fn new() -> FieldAudit {
+        FieldAudit::default()
+    }
+
+    /// Records one occurrence of a field.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::audit::FieldAudit;
+    ///
+    /// let mut audit = FieldAudit::new();
+    /// audit.record("vendor_field");
+    /// audit.record("vendor_field");
+    /// assert_eq!(audit.count("vendor_field"), 2);
+    /// ```
+    pub fn record(&mut self, field: impl ToString) {
+        *self.counts.entry(field.to_string()).or_default() += 1;
+    }
+
+    /// Records the top-level fields of a STAC value that aren't part of the
+    /// core spec for its type.
+    ///
+    /// Fields with an extension prefix, e.g. `sci:doi`, are assumed to be
+    /// handled. The items in an item collection are audited too. Values
+    /// without a STAC `type` are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::audit::FieldAudit;
+    /// use serde_json::json;
+    ///
+    /// let mut audit = FieldAudit::new();
+    /// audit.record_unknown_fields(&json!({
+    ///     "type": "Catalog",
+    ///     "id": "an-id",
+    ///     "description": "a description",
+    ///     "links": [],
+    ///     "sci:doi": "10.5061/dryad.s2v81.2",
+    ///     "vendor_field": 42,
+    /// }));
+    /// assert_eq!(audit.count("vendor_field"), 1);
+    /// assert_eq!(audit.count("sci:doi"), 0);
+    /// ```
+    pub fn record_unknown_fields(&mut self, value: &Value) {
+        let Some(object) = value.as_object() else {
+            return;
+        };
+        let keys: &[&str] = match object.get("type").and_then(Value::as_str) {
+            Some("Feature") => &ITEM_KEYS,
+            Some("Catalog") => &CATALOG_KEYS,
+            Some("Collection") => &COLLECTION_KEYS,
+            Some("FeatureCollection") => &ITEM_COLLECTION_KEYS,
+            _ => return,
+        };
+        for key in object.keys() {
+            if !keys.contains(&key.as_str()) && !key.contains(':') {
+                self.record(key);
+            }
+        }
+        if let Some(features) = object.get("features").and_then(Value::as_array) {
+            for feature in features {
+                self.record_unknown_fields(feature);
+            }
+        }
+    }
+
+    /// Returns the number of times a field was recorded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::audit::FieldAudit;
+    ///
+    /// let audit = FieldAudit::new();
+    /// assert_eq!(audit.count("vendor_field"), 0);
+    /// ```
+    pub fn count(&self, field: &str) -> u64 {
+        self.counts.get(field).copied().unwrap_or_default()
+    }
+
+    /// Returns true if no fields were recorded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::audit::FieldAudit;
+    ///
+    /// assert!(FieldAudit::new().is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// Iterates over the recorded fields and their counts, sorted by field
+    /// name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::audit::FieldAudit;
+    ///
+    /// let mut audit = FieldAudit::new();
+    /// audit.record("vendor_field");
+    /// let counts: Vec<_> = audit.iter().collect();
+    /// assert_eq!(counts, vec![("vendor_field", 1)]);
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = (&str, u64)> {
+        self.counts
+            .iter()
+            .map(|(field, &count)| (field.as_str(), count))
+    }
+
+    /// Adds the counts from another audit to this one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::audit::FieldAudit;
+    ///
+    /// let mut audit = FieldAudit::new();
+    /// audit.record("vendor_field");
+    /// let mut other = FieldAudit::new();
+    /// other.record("vendor_field");
+    /// audit.merge(other);
+    /// assert_eq!(audit.count("vendor_field"), 2);
+    /// ```
+    pub fn merge(&mut self, other: FieldAudit) {
+        for (field, count) in other.counts {
+            *self.counts.entry(field).or_default() += count;
+        }
+    }
+}
+
+impl Display for FieldAudit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, (field, count)) in self.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{field}: {count}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FieldAudit;
+    use serde_json::json;
+
+    #[test]
+    fn record_unknown_fields_in_item_collection() {
+        let mut audit = FieldAudit::new();
+        audit.record_unknown_fields(&json!({
+            "type": "FeatureCollection",
+            "features": [
+                {"type": "Feature", "id": "a", "vendor_field": 1, "properties": {"foo": "bar"}},
+                {"type": "Feature", "id": "b", "vendor_field": 2},
+            ],
+            "numberMatched": 2,
+        }));
+        assert_eq!(audit.count("vendor_field"), 2);
+        assert_eq!(audit.count("numberMatched"), 1);
+        assert_eq!(audit.count("foo"), 0);
+        assert_eq!(audit.to_string(), "numberMatched: 1\nvendor_field: 2");
+    }
+
+    #[test]
+    fn ignore_untyped_values() {
+        let mut audit = FieldAudit::new();
+        audit.record_unknown_fields(&json!({"foo": "bar"}));
+        assert!(audit.is_empty());
+    }
+}
@@ -1,6 +1,7 @@
 use crate::{Error, Result};
 use geojson::Geometry;
 use serde::{Deserialize, Serialize};
+use std::str::FromStr;
 
 /// A bounding box.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
@@ -49,6 +50,102 @@ impl Bbox {
         }
     }
 
+    /// Returns this bbox if it's valid, or an error that describes the
+    /// most likely mistake.
+    ///
+    /// Latitudes outside of `[-90, 90]`, when the longitudes would be valid
+    /// latitudes, are reported as swapped longitude and latitude. A minimum
+    /// latitude (or elevation) greater than its maximum is reported as an
+    /// ordering mistake. A minimum longitude greater than its maximum is
+    /// allowed, since that's how bboxes that cross the antimeridian are
+    /// written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::Bbox;
+    ///
+    /// Bbox::new(-106., 39., -104., 41.).validate().unwrap();
+    /// Bbox::new(170., -10., -170., 10.).validate().unwrap();
+    /// Bbox::new(39., -106., 41., -104.).validate().unwrap_err();
+    /// Bbox::new(-106., 41., -104., 39.).validate().unwrap_err();
+    /// ```
+    pub fn validate(self) -> Result<Bbox> {
+        if self.looks_swapped() {
+            Err(Error::InvalidBbox(
+                self.into(),
+                "latitude is outside of [-90, 90], are longitude and latitude swapped?",
+            ))
+        } else if self.has_inverted_y_or_z() {
+            Err(Error::InvalidBbox(
+                self.into(),
+                "a minimum latitude or elevation is greater than its maximum, expected xmin,ymin,xmax,ymax",
+            ))
+        } else {
+            Ok(self)
+        }
+    }
+
+    /// Fixes swapped longitude and latitude, and minimum latitudes (or
+    /// elevations) that are greater than their maximums.
+    ///
+    /// Returns true if this bbox was changed. Longitudes are only reordered
+    /// if they were swapped with the latitudes, so bboxes that cross the
+    /// antimeridian are left as they are.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::Bbox;
+    ///
+    /// let mut bbox = Bbox::new(41., -104., 39., -106.);
+    /// assert!(bbox.fix());
+    /// assert_eq!(bbox, Bbox::new(-106., 39., -104., 41.));
+    /// assert!(!bbox.fix());
+    /// let mut bbox = Bbox::new(170., -10., -170., 10.);
+    /// assert!(!bbox.fix());
+    /// ```
+    pub fn fix(&mut self) -> bool {
+        let mut values: Vec<f64> = (*self).into();
+        let n = values.len() / 2;
+        let mut fixed = false;
+        let swapped = self.looks_swapped();
+        if swapped {
+            values.swap(0, 1);
+            values.swap(n, n + 1);
+            fixed = true;
+        }
+        // The longitudes came from the latitudes if they were swapped, so
+        // they can't cross the antimeridian.
+        let start = if swapped { 0 } else { 1 };
+        for i in start..n {
+            if values[i] > values[i + n] {
+                values.swap(i, i + n);
+                fixed = true;
+            }
+        }
+        if fixed {
+            *self = values
+                .try_into()
+                .expect("the number of values should be unchanged");
+        }
+        fixed
+    }
+
+    fn looks_swapped(&self) -> bool {
+        let latitude = -90.0..=90.0;
+        !(latitude.contains(&self.ymin()) && latitude.contains(&self.ymax()))
+            && latitude.contains(&self.xmin())
+            && latitude.contains(&self.xmax())
+    }
+
+    fn has_inverted_y_or_z(&self) -> bool {
+        match self {
+            Bbox::TwoDimensional([_, ymin, _, ymax]) => ymin > ymax,
+            Bbox::ThreeDimensional([_, ymin, zmin, _, ymax, zmax]) => ymin > ymax || zmin > zmax,
+        }
+    }
+
     /// Updates this bbox with another bbox's values.
     ///
     /// # Examples
@@ -152,6 +249,46 @@ impl Bbox {
         }
     }
 
+    /// Returns true if this bbox's elevation range overlaps another's.
+    ///
+    /// Two-dimensional bounding boxes don't constrain elevation, so if either
+    /// bbox is two-dimensional this always returns true.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::Bbox;
+    ///
+    /// let bbox: Bbox = vec![0., 0., 10., 1., 1., 20.].try_into().unwrap();
+    /// let other: Bbox = vec![0., 0., 15., 1., 1., 30.].try_into().unwrap();
+    /// assert!(bbox.elevation_intersects(&other));
+    /// let other: Bbox = vec![0., 0., 25., 1., 1., 30.].try_into().unwrap();
+    /// assert!(!bbox.elevation_intersects(&other));
+    /// assert!(bbox.elevation_intersects(&Bbox::new(0., 0., 1., 1.)));
+    /// ```
+    pub fn elevation_intersects(&self, other: &Bbox) -> bool {
+        match (self.zmin().zip(self.zmax()), other.zmin().zip(other.zmax())) {
+            (Some((zmin, zmax)), Some((other_zmin, other_zmax))) => {
+                zmin <= other_zmax && other_zmin <= zmax
+            }
+            _ => true,
+        }
+    }
+
+    /// Returns a two-dimensional copy of this bbox, dropping any elevation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::Bbox;
+    ///
+    /// let bbox: Bbox = vec![1., 2., 3., 4., 5., 6.].try_into().unwrap();
+    /// assert_eq!(bbox.to_two_dimensional(), Bbox::new(1., 2., 4., 5.));
+    /// ```
+    pub fn to_two_dimensional(&self) -> Bbox {
+        Bbox::new(self.xmin(), self.ymin(), self.xmax(), self.ymax())
+    }
+
     /// Converts this bbox to a [Geometry](geojson::Geometry).
     ///
     /// # Examples
@@ -203,6 +340,30 @@ impl TryFrom<Vec<f64>> for Bbox {
     }
 }
 
+impl FromStr for Bbox {
+    type Err = Error;
+
+    /// Parses a bbox from comma- or whitespace-separated values, and
+    /// [validates](Bbox::validate) it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::Bbox;
+    ///
+    /// let bbox: Bbox = "-106,39,-104,41".parse().unwrap();
+    /// assert_eq!(bbox, "-106 39 -104 41".parse().unwrap());
+    /// ```
+    fn from_str(s: &str) -> Result<Bbox> {
+        let values = s
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|s| !s.is_empty())
+            .map(str::parse)
+            .collect::<std::result::Result<Vec<f64>, _>>()?;
+        Bbox::try_from(values)?.validate()
+    }
+}
+
 impl From<Bbox> for Vec<f64> {
     fn from(bbox: Bbox) -> Vec<f64> {
         match bbox {
@@ -255,4 +416,67 @@ mod tests {
             ]])
         )
     }
+
+    #[test]
+    fn elevation_intersects() {
+        let bbox: Bbox = vec![0., 0., 10., 1., 1., 20.].try_into().unwrap();
+        let below: Bbox = vec![0., 0., -10., 1., 1., 9.].try_into().unwrap();
+        let touching: Bbox = vec![0., 0., 20., 1., 1., 30.].try_into().unwrap();
+        assert!(!bbox.elevation_intersects(&below));
+        assert!(bbox.elevation_intersects(&touching));
+        assert!(Bbox::new(0., 0., 1., 1.).elevation_intersects(&below));
+    }
+
+    #[test]
+    fn from_str() {
+        let bbox = Bbox::new(-106., 39., -104., 41.);
+        assert_eq!("-106,39,-104,41".parse::<Bbox>().unwrap(), bbox);
+        assert_eq!("-106 39 -104 41".parse::<Bbox>().unwrap(), bbox);
+        assert_eq!("-106, 39, -104, 41".parse::<Bbox>().unwrap(), bbox);
+        assert_eq!(
+            "0 0 10 1 1 20".parse::<Bbox>().unwrap(),
+            Bbox::ThreeDimensional([0., 0., 10., 1., 1., 20.])
+        );
+        let _ = "-106,39,-104".parse::<Bbox>().unwrap_err();
+        let _ = "-106,39,-104,north".parse::<Bbox>().unwrap_err();
+    }
+
+    #[test]
+    fn from_str_mistakes() {
+        let crate::Error::InvalidBbox(values, message) =
+            "39,-106,41,-104".parse::<Bbox>().unwrap_err()
+        else {
+            panic!("expected an invalid bbox error");
+        };
+        assert_eq!(values, [39., -106., 41., -104.]);
+        assert!(message.contains("swapped"));
+        let crate::Error::InvalidBbox(_, message) = "-106,41,-104,39".parse::<Bbox>().unwrap_err()
+        else {
+            panic!("expected an invalid bbox error");
+        };
+        assert!(message.contains("minimum"));
+    }
+
+    #[test]
+    fn from_str_antimeridian() {
+        let bbox: Bbox = "170,-10,-170,10".parse().unwrap();
+        assert_eq!(bbox, Bbox::new(170., -10., -170., 10.));
+        let mut fixed = bbox;
+        assert!(!fixed.fix());
+        let mut bbox = Bbox::new(170., 10., -170., -10.);
+        assert!(bbox.fix());
+        assert_eq!(bbox, Bbox::new(170., -10., -170., 10.));
+    }
+
+    #[test]
+    fn fix() {
+        let mut bbox = Bbox::new(39., -106., 41., -104.);
+        assert!(bbox.fix());
+        assert_eq!(bbox, Bbox::new(-106., 39., -104., 41.));
+        let mut bbox = Bbox::ThreeDimensional([0., 0., 20., 1., 1., 10.]);
+        assert!(bbox.fix());
+        assert_eq!(bbox, Bbox::ThreeDimensional([0., 0., 10., 1., 1., 20.]));
+        let mut bbox = Bbox::new(-106., 39., -104., 41.);
+        assert!(!bbox.fix());
+    }
 }
@@ -1,4 +1,4 @@
-use crate::{Error, Link, Result, STAC_VERSION, Version};
+use crate::{Error, Item, Link, Links, Result, STAC_VERSION, SelfHref, Version};
 use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::{Map, Value};
 use stac_derive::{Fields, Links, Migrate, SelfHref};
@@ -104,6 +104,61 @@ impl Catalog {
             self_href: None,
         }
     }
+
+    /// Adds a child catalog or collection that will live at `href`.
+    ///
+    /// This adds a `child` link to this catalog, sets the child's self href,
+    /// and gives the child `parent` and `root` links. The root is this
+    /// catalog's root, or this catalog if it doesn't have one.
+    ///
+    /// Returns [Error::NoHref] if this catalog doesn't have a self href or a
+    /// `self` link.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Catalog, Collection, Links, SelfHref};
+    ///
+    /// let mut catalog = Catalog::new("an-id", "a description");
+    /// catalog.set_self_href("catalog/catalog.json");
+    /// let mut collection = Collection::new("a-collection", "a description");
+    /// catalog
+    ///     .add_child(&mut collection, "catalog/a-collection/collection.json")
+    ///     .unwrap();
+    /// assert_eq!(catalog.iter_child_links().count(), 1);
+    /// assert_eq!(collection.parent_link().unwrap().href, "catalog/catalog.json");
+    /// assert_eq!(collection.root_link().unwrap().href, "catalog/catalog.json");
+    /// ```
+    pub fn add_child<C>(&mut self, child: &mut C, href: impl ToString) -> Result<()>
+    where
+        C: Links + SelfHref,
+    {
+        crate::link::link_child(self, child, Link::child(href.to_string()))
+    }
+
+    /// Adds an item that will live at `href`.
+    ///
+    /// This adds an `item` link to this catalog, sets the item's self href,
+    /// and gives the item `parent` and `root` links.
+    ///
+    /// Returns [Error::NoHref] if this catalog doesn't have a self href or a
+    /// `self` link.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Catalog, Item, Links, SelfHref};
+    ///
+    /// let mut catalog = Catalog::new("an-id", "a description");
+    /// catalog.set_self_href("catalog/catalog.json");
+    /// let mut item = Item::new("an-item");
+    /// catalog.add_item(&mut item, "catalog/an-item/an-item.json").unwrap();
+    /// assert_eq!(catalog.iter_item_links().count(), 1);
+    /// assert_eq!(item.self_href(), Some("catalog/an-item/an-item.json"));
+    /// ```
+    pub fn add_item(&mut self, item: &mut Item, href: impl ToString) -> Result<()> {
+        crate::link::link_child(self, item, Link::item(href.to_string()))
+    }
 }
 
 impl TryFrom<Catalog> for Map<String, Value> {
@@ -168,4 +223,36 @@ mod tests {
             serde_json::to_value(Catalog::new("an-id", "a description")).unwrap();
         assert_eq!(value.as_object().unwrap()["type"], "Catalog");
     }
+
+    #[test]
+    fn add_child() {
+        use crate::{Collection, Error, Item, Links, SelfHref};
+
+        let mut catalog = Catalog::new("root", "a description");
+        let mut collection = Collection::new("a-collection", "a description");
+        let mut item = Item::new("an-item");
+        assert!(matches!(
+            catalog.add_child(&mut collection, "root/a-collection/collection.json"),
+            Err(Error::NoHref)
+        ));
+
+        catalog.set_self_href("root/catalog.json");
+        catalog
+            .add_child(&mut collection, "root/a-collection/collection.json")
+            .unwrap();
+        catalog
+            .add_child(&mut collection, "root/a-collection/collection.json")
+            .unwrap();
+        assert_eq!(catalog.iter_child_links().count(), 1);
+        collection
+            .link_item(&mut item, "root/a-collection/an-item/an-item.json")
+            .unwrap();
+        assert_eq!(item.root_link().unwrap().href, "root/catalog.json");
+        assert_eq!(
+            item.parent_link().unwrap().href,
+            "root/a-collection/collection.json"
+        );
+        assert_eq!(item.collection.as_deref(), Some("a-collection"));
+        assert_eq!(collection.iter_item_links().count(), 1);
+    }
 }
@@ -1,6 +1,6 @@
 use crate::{
     Asset, Assets, Bbox, Error, Item, ItemAsset, Link, Links, Migrate, Result, STAC_VERSION,
-    SelfHref, Version,
+    SelfHref, Version, role::ProviderRole,
 };
 use chrono::{DateTime, Utc};
 use indexmap::IndexMap;
@@ -314,6 +314,244 @@ impl Collection {
         self.update_extents(item);
         self.maybe_add_item_link(item)
     }
+
+    /// Adds a child catalog or collection that will live at `href`.
+    ///
+    /// This adds a `child` link to this collection, sets the child's self
+    /// href, and gives the child `parent` and `root` links. The root is this
+    /// collection's root, or this collection if it doesn't have one.
+    ///
+    /// Returns [Error::NoHref] if this collection doesn't have a self href or
+    /// a `self` link.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Catalog, Collection, Links, SelfHref};
+    ///
+    /// let mut collection = Collection::new("an-id", "a description");
+    /// collection.set_self_href("an-id/collection.json");
+    /// let mut catalog = Catalog::new("a-catalog", "a description");
+    /// collection
+    ///     .add_child(&mut catalog, "an-id/a-catalog/catalog.json")
+    ///     .unwrap();
+    /// assert_eq!(catalog.parent_link().unwrap().href, "an-id/collection.json");
+    /// ```
+    pub fn add_child<C>(&mut self, child: &mut C, href: impl ToString) -> Result<()>
+    where
+        C: Links + SelfHref,
+    {
+        crate::link::link_child(self, child, Link::child(href.to_string()))
+    }
+
+    /// Links an item that will live at `href` to this collection.
+    ///
+    /// Like [Collection::add_item], this updates this collection's extents
+    /// and adds an `item` link. It also sets the item's self href and
+    /// `collection` id, and gives the item `parent`, `root`, and `collection`
+    /// links.
+    ///
+    /// Returns [Error::NoHref] if this collection doesn't have a self href or
+    /// a `self` link.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Collection, Item, Links, SelfHref};
+    ///
+    /// let mut collection = Collection::new("an-id", "a description");
+    /// collection.set_self_href("an-id/collection.json");
+    /// let mut item: Item = stac::read("examples/simple-item.json").unwrap();
+    /// collection.link_item(&mut item, "an-id/an-item/an-item.json").unwrap();
+    /// assert_eq!(item.collection.as_deref(), Some("an-id"));
+    /// assert_eq!(item.link("collection").unwrap().href, "an-id/collection.json");
+    /// ```
+    pub fn link_item(&mut self, item: &mut Item, href: impl ToString) -> Result<()> {
+        crate::link::link_child(self, item, Link::item(href.to_string()))?;
+        let collection_href = item
+            .parent_link()
+            .map(|link| link.href.clone())
+            .expect("the item was just given a parent link");
+        item.set_link(Link::collection(collection_href));
+        item.collection = Some(self.id.clone());
+        self.update_extents(item);
+        Ok(())
+    }
+
+    /// Recomputes this collection's extents and summaries from its items.
+    ///
+    /// The spatial and temporal extents are replaced with the bounds of the
+    /// items. Existing summaries of item properties are recomputed: ranges
+    /// (objects with `minimum` and `maximum`) get the smallest and largest
+    /// values, and sets (arrays) get the distinct values. Other summaries, e.g.
+    /// JSON schemas, are left as they are, and no summaries are added.
+    ///
+    /// Does nothing if there are no items.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Collection, Item};
+    /// use serde_json::json;
+    ///
+    /// let mut collection = Collection::new("an-id", "a description");
+    /// let _ = collection.summaries.insert(
+    ///     [("gsd".to_string(), json!({"minimum": 0, "maximum": 0}))].into_iter().collect(),
+    /// );
+    /// let mut item: Item = stac::read("examples/simple-item.json").unwrap();
+    /// let _ = item.properties.additional_fields.insert("gsd".into(), 10.into());
+    /// collection.refresh_from_items(&[item]);
+    /// assert_eq!(collection.summaries.unwrap()["gsd"], json!({"minimum": 10, "maximum": 10}));
+    /// ```
+    pub fn refresh_from_items<'a>(&mut self, items: impl IntoIterator<Item = &'a Item>) {
+        let mut ranges: IndexMap<String, Option<(Value, Value)>> = IndexMap::new();
+        let mut sets: IndexMap<String, Vec<Value>> = IndexMap::new();
+        for (key, summary) in self.summaries.iter().flatten() {
+            if summary.is_array() {
+                let _ = sets.insert(key.clone(), Vec::new());
+            } else if summary.get("minimum").is_some() && summary.get("maximum").is_some() {
+                let _ = ranges.insert(key.clone(), None);
+            }
+        }
+        let mut extent: Option<Extent> = None;
+        for item in items {
+            let extent = extent.get_or_insert_with(|| Extent {
+                spatial: SpatialExtent { bbox: Vec::new() },
+                temporal: TemporalExtent {
+                    interval: Vec::new(),
+                },
+                additional_fields: Map::new(),
+            });
+            if let Some(bbox) = item.bbox {
+                extent.spatial.update(bbox);
+            }
+            let (start, end) = item.datetimes();
+            extent.temporal.update(start, end);
+            for (key, range) in ranges.iter_mut() {
+                if let Some(value) = item.properties.additional_fields.get(key) {
+                    update_range(range, value);
+                }
+            }
+            for (key, set) in sets.iter_mut() {
+                let values = match item.properties.additional_fields.get(key) {
+                    Some(Value::Array(values)) => values.as_slice(),
+                    Some(value) => std::slice::from_ref(value),
+                    None => &[],
+                };
+                for value in values {
+                    if !set.contains(value) {
+                        set.push(value.clone());
+                    }
+                }
+            }
+        }
+        let Some(extent) = extent else {
+            return;
+        };
+        if !extent.spatial.bbox.is_empty() {
+            self.extent.spatial = extent.spatial;
+        }
+        self.extent.temporal = extent.temporal;
+        if let Some(summaries) = self.summaries.as_mut() {
+            for (key, range) in ranges {
+                if let Some((minimum, maximum)) = range
+                    && let Some(Value::Object(summary)) = summaries.get_mut(&key)
+                {
+                    let _ = summary.insert("minimum".to_string(), minimum);
+                    let _ = summary.insert("maximum".to_string(), maximum);
+                }
+            }
+            for (key, set) in sets {
+                let _ = summaries.insert(key, Value::Array(set));
+            }
+        }
+    }
+
+    /// Computes summaries of the given item properties.
+    ///
+    /// If every value of a property is a number, its summary is a range (an
+    /// object with `minimum` and `maximum`). Otherwise, its summary is a set
+    /// (an array) of the distinct values. Array property values, e.g.
+    /// `instruments`, are flattened. Existing summaries for the fields are
+    /// replaced, and fields that none of the items have are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Collection, Item};
+    /// use serde_json::json;
+    ///
+    /// let mut item: Item = stac::read("examples/simple-item.json").unwrap();
+    /// let _ = item.properties.additional_fields.insert("gsd".into(), 10.into());
+    /// let mut collection = Collection::new("an-id", "a description");
+    /// collection.update_summaries(&[item], &["gsd", "platform"]);
+    /// let summaries = collection.summaries.unwrap();
+    /// assert_eq!(summaries["gsd"], json!({"minimum": 10, "maximum": 10}));
+    /// assert!(summaries.get("platform").is_none());
+    /// ```
+    pub fn update_summaries(&mut self, items: &[Item], fields: &[&str]) {
+        for field in fields {
+            let mut set = Vec::new();
+            for item in items {
+                let values = match item.properties.additional_fields.get(*field) {
+                    Some(Value::Array(values)) => values.as_slice(),
+                    Some(value) => std::slice::from_ref(value),
+                    None => &[],
+                };
+                for value in values {
+                    if !set.contains(value) {
+                        set.push(value.clone());
+                    }
+                }
+            }
+            if set.is_empty() {
+                continue;
+            }
+            let summary = if set.iter().all(Value::is_number) {
+                let mut range = None;
+                for value in &set {
+                    update_range(&mut range, value);
+                }
+                let (minimum, maximum) = range.expect("there should be at least one value");
+                let mut summary = Map::new();
+                let _ = summary.insert("minimum".to_string(), minimum);
+                let _ = summary.insert("maximum".to_string(), maximum);
+                Value::Object(summary)
+            } else {
+                Value::Array(set)
+            };
+            let _ = self
+                .summaries
+                .get_or_insert_with(Map::new)
+                .insert(field.to_string(), summary);
+        }
+    }
+}
+
+fn update_range(range: &mut Option<(Value, Value)>, value: &Value) {
+    fn less_than(a: &Value, b: &Value) -> bool {
+        match (a, b) {
+            (Value::Number(a), Value::Number(b)) => {
+                a.as_f64().zip(b.as_f64()).is_some_and(|(a, b)| a < b)
+            }
+            (Value::String(a), Value::String(b)) => a < b,
+            _ => false,
+        }
+    }
+
+    if !(value.is_number() || value.is_string()) {
+        return;
+    }
+    if let Some((minimum, maximum)) = range {
+        if less_than(value, minimum) {
+            *minimum = value.clone();
+        }
+        if less_than(maximum, value) {
+            *maximum = value.clone();
+        }
+    } else {
+        *range = Some((value.clone(), value.clone()));
+    }
 }
 
 impl Provider {
@@ -335,6 +573,22 @@ impl Provider {
             additional_fields: Map::new(),
         }
     }
+
+    /// Returns true if this provider has a well-known role.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Provider, role::ProviderRole};
+    ///
+    /// let mut provider = Provider::new("a-name");
+    /// assert!(!provider.has_role(ProviderRole::Host));
+    /// provider.roles = Some(vec![ProviderRole::Host.to_string()]);
+    /// assert!(provider.has_role(ProviderRole::Host));
+    /// ```
+    pub fn has_role(&self, role: ProviderRole) -> bool {
+        self.roles.iter().flatten().any(|r| r == role.as_str())
+    }
 }
 
 impl Default for SpatialExtent {
@@ -346,7 +600,7 @@ impl Default for SpatialExtent {
 }
 
 impl SpatialExtent {
-    fn update(&mut self, other: Bbox) {
+    pub(crate) fn update(&mut self, other: Bbox) {
         if self.bbox.is_empty() {
             self.bbox.push(other);
         } else {
@@ -356,7 +610,7 @@ impl SpatialExtent {
 }
 
 impl TemporalExtent {
-    fn update(&mut self, start: Option<DateTime<Utc>>, end: Option<DateTime<Utc>>) {
+    pub(crate) fn update(&mut self, start: Option<DateTime<Utc>>, end: Option<DateTime<Utc>>) {
         if self.interval.is_empty() {
             self.interval.push([start, end]);
         } else {
@@ -478,6 +732,125 @@ mod tests {
             let link = collection.link("item").unwrap();
             assert!(link.href.to_string().ends_with("simple-item.json"));
         }
+
+        #[test]
+        fn refresh_from_items() {
+            use crate::Item;
+            use serde_json::json;
+
+            let mut collection = Collection::new("an-id", "a description");
+            collection.summaries = Some(
+                json!({
+                    "gsd": {"minimum": 0, "maximum": 0},
+                    "platform": [],
+                    "instruments": [],
+                    "proj:code": {"type": "string"},
+                })
+                .as_object()
+                .unwrap()
+                .clone(),
+            );
+            let mut items = Vec::new();
+            for (i, (gsd, platform)) in [(10, "a"), (30, "b"), (20, "a")].into_iter().enumerate() {
+                let mut item = Item::new(format!("item-{i}"));
+                item.bbox = Some(Bbox::new(i as f64, 0., i as f64 + 1., 1.));
+                item.properties.datetime =
+                    Some(format!("2024-01-0{}T00:00:00Z", i + 1).parse().unwrap());
+                let _ = item
+                    .properties
+                    .additional_fields
+                    .insert("gsd".into(), gsd.into());
+                let _ = item
+                    .properties
+                    .additional_fields
+                    .insert("platform".into(), platform.into());
+                let _ = item
+                    .properties
+                    .additional_fields
+                    .insert("instruments".into(), json!(["x", platform]));
+                items.push(item);
+            }
+            collection.refresh_from_items(&items);
+            assert_eq!(collection.extent.spatial.bbox, [Bbox::new(0., 0., 3., 1.)]);
+            assert_eq!(
+                collection.extent.temporal.interval[0][0].unwrap(),
+                "2024-01-01T00:00:00Z".parse::<DateTime<Utc>>().unwrap()
+            );
+            assert_eq!(
+                collection.extent.temporal.interval[0][1].unwrap(),
+                "2024-01-03T00:00:00Z".parse::<DateTime<Utc>>().unwrap()
+            );
+            let summaries = collection.summaries.as_ref().unwrap();
+            assert_eq!(summaries["gsd"], json!({"minimum": 10, "maximum": 30}));
+            assert_eq!(summaries["platform"], json!(["a", "b"]));
+            assert_eq!(summaries["instruments"], json!(["x", "a", "b"]));
+            assert_eq!(summaries["proj:code"], json!({"type": "string"}));
+
+            let before = collection.clone();
+            collection.refresh_from_items(&[]);
+            assert_eq!(collection, before);
+        }
+
+        #[test]
+        fn update_summaries() {
+            use crate::Item;
+            use serde_json::json;
+
+            let mut collection = Collection::new("an-id", "a description");
+            collection.summaries = Some(
+                json!({
+                    "gsd": [0],
+                    "proj:code": {"type": "string"},
+                })
+                .as_object()
+                .unwrap()
+                .clone(),
+            );
+            let mut items = Vec::new();
+            for (i, (cloud_cover, platform)) in [(12.5, "a"), (3.0, "b"), (40.0, "a")]
+                .into_iter()
+                .enumerate()
+            {
+                let mut item = Item::new(format!("item-{i}"));
+                let _ = item
+                    .properties
+                    .additional_fields
+                    .insert("eo:cloud_cover".into(), cloud_cover.into());
+                let _ = item
+                    .properties
+                    .additional_fields
+                    .insert("platform".into(), platform.into());
+                let _ = item
+                    .properties
+                    .additional_fields
+                    .insert("instruments".into(), json!(["x", platform]));
+                let _ = item
+                    .properties
+                    .additional_fields
+                    .insert("gsd".into(), 10.into());
+                items.push(item);
+            }
+            collection.update_summaries(
+                &items,
+                &[
+                    "eo:cloud_cover",
+                    "platform",
+                    "instruments",
+                    "gsd",
+                    "missing",
+                ],
+            );
+            let summaries = collection.summaries.as_ref().unwrap();
+            assert_eq!(
+                summaries["eo:cloud_cover"],
+                json!({"minimum": 3.0, "maximum": 40.0})
+            );
+            assert_eq!(summaries["platform"], json!(["a", "b"]));
+            assert_eq!(summaries["instruments"], json!(["x", "a", "b"]));
+            assert_eq!(summaries["gsd"], json!({"minimum": 10, "maximum": 10}));
+            assert_eq!(summaries["proj:code"], json!({"type": "string"}));
+            assert!(summaries.get("missing").is_none());
+        }
     }
 
     mod provider {
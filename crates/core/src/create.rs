@@ -0,0 +1,877 @@
+//! Create items from raster assets.
+//!
+//! A [Raster] is read from the header of a GeoTIFF (including cloud-optimized
+//! GeoTIFFs) with a small, pure-Rust parser, so no GDAL is required. Only the
+//! first image file directory is read, and no pixel data is decoded:
+//!
+//! ```
+//! use stac::create::Raster;
+//!
+//! let raster = Raster::from_path("assets/dataset_geo.tif").unwrap();
+//! assert_eq!(raster.epsg, Some(32621));
+//! let item = raster.to_item("dataset_geo", "assets/dataset_geo.tif");
+//! assert_eq!(item.properties.additional_fields["proj:code"], "EPSG:32621");
+//! ```
+//!
+//! Band data types and nodata values are set on the asset's `bands`, which
+//! replace `raster:bands` in STAC v1.1. Projection information is set as
+//! [projection extension](https://github.com/stac-extensions/projection)
+//! fields on the item's properties. The item's bbox and geometry are only set
+//! if the raster's crs can be reprojected to WGS 84, which requires the
+//! `proj` feature for anything other than EPSG:4326.
+//!
+//! Items can also be created for any other file with [item_for_asset], which
+//! adds a data asset with a [media type](media_type) inferred from the href's
+//! extension, and a datetime can be [inferred](datetime_from_filename) from a
+//! file name.
+
+use crate::{Asset, Band, Bbox, DataType, Error, Item, Result, crs, mime, role::Role};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use serde_json::json;
+use std::{
+    fs::File,
+    io::{BufReader, Read, Seek, SeekFrom},
+    path::Path,
+};
+
+/// The key of the asset added by [Raster::to_item].
+pub const DATA_ASSET_KEY: &str = "data";
+
+/// The projection extension schema uri.
+pub const PROJECTION_EXTENSION: &str =
+    "https://stac-extensions.github.io/projection/v2.0.0/schema.json";
+
+const MAX_TAG_SIZE: u64 = 1 << 20;
+
+const IMAGE_WIDTH: u16 = 256;
+const IMAGE_LENGTH: u16 = 257;
+const BITS_PER_SAMPLE: u16 = 258;
+const SAMPLES_PER_PIXEL: u16 = 277;
+const TILE_WIDTH: u16 = 322;
+const SAMPLE_FORMAT: u16 = 339;
+const MODEL_PIXEL_SCALE: u16 = 33550;
+const MODEL_TIEPOINT: u16 = 33922;
+const MODEL_TRANSFORMATION: u16 = 34264;
+const GEO_KEY_DIRECTORY: u16 = 34735;
+const GDAL_NODATA: u16 = 42113;
+
+const RASTER_TYPE_GEO_KEY: u16 = 1025;
+const GEOGRAPHIC_TYPE_GEO_KEY: u16 = 2048;
+const PROJECTED_CS_TYPE_GEO_KEY: u16 = 3072;
+const RASTER_PIXEL_IS_POINT: u16 = 2;
+const USER_DEFINED: u16 = 32767;
+
+/// Metadata about a raster, read from a GeoTIFF header.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Raster {
+    /// The width of the raster, in pixels.
+    pub width: u64,
+
+    /// The height of the raster, in pixels.
+    pub height: u64,
+
+    /// The bands, with their data types and nodata values.
+    pub bands: Vec<Band>,
+
+    /// The EPSG code of the raster's crs, if it has one.
+    pub epsg: Option<u32>,
+
+    /// The affine transform from pixel to crs coordinates, in the
+    /// `[a, b, c, d, e, f]` order used by `proj:transform`.
+    pub transform: Option<[f64; 6]>,
+
+    /// Whether the raster is tiled, as cloud-optimized GeoTIFFs are.
+    pub tiled: bool,
+}
+
+#[derive(Debug)]
+struct Entry {
+    field_type: u16,
+    count: u64,
+    value: [u8; 8],
+}
+
+struct Tiff<R> {
+    reader: R,
+    big_endian: bool,
+    bigtiff: bool,
+}
+
+impl Raster {
+    /// Reads a raster from a GeoTIFF.
+    ///
+    /// Only the header and the tags of the first image are read.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::create::Raster;
+    /// use std::fs::File;
+    ///
+    /// let raster = Raster::read(File::open("assets/dataset_geo.tif").unwrap()).unwrap();
+    /// assert_eq!(raster.width, 2658);
+    /// assert_eq!(raster.height, 2667);
+    /// ```
+    pub fn read(reader: impl Read + Seek) -> Result<Raster> {
+        let mut tiff = Tiff::new(reader)?;
+        let entries = tiff.first_ifd()?;
+        let get = |tag: u16| {
+            entries
+                .iter()
+                .find(|(t, _)| *t == tag)
+                .map(|(_, entry)| entry)
+        };
+        let width = get(IMAGE_WIDTH)
+            .map(|entry| tiff.numbers(entry))
+            .transpose()?
+            .and_then(|values| values.first().copied())
+            .ok_or_else(|| Error::InvalidTiff("no image width".to_string()))?;
+        let height = get(IMAGE_LENGTH)
+            .map(|entry| tiff.numbers(entry))
+            .transpose()?
+            .and_then(|values| values.first().copied())
+            .ok_or_else(|| Error::InvalidTiff("no image length".to_string()))?;
+        let samples_per_pixel = match get(SAMPLES_PER_PIXEL) {
+            Some(entry) => tiff.numbers(entry)?.first().copied().unwrap_or(1.0),
+            None => 1.0,
+        };
+        let bits_per_sample = match get(BITS_PER_SAMPLE) {
+            Some(entry) => tiff.numbers(entry)?,
+            None => vec![1.0],
+        };
+        let sample_format = match get(SAMPLE_FORMAT) {
+            Some(entry) => tiff.numbers(entry)?,
+            None => vec![1.0],
+        };
+        let nodata = match get(GDAL_NODATA) {
+            Some(entry) => tiff
+                .ascii(entry)?
+                .trim()
+                .parse::<f64>()
+                .ok()
+                .filter(|nodata| nodata.is_finite()),
+            None => None,
+        };
+        let bands = (0..samples_per_pixel as usize)
+            .map(|i| {
+                let bits = bits_per_sample.get(i).or(bits_per_sample.last());
+                let format = sample_format.get(i).or(sample_format.last());
+                Band {
+                    name: None,
+                    description: None,
+                    nodata,
+                    data_type: Some(data_type(
+                        format.copied().unwrap_or(1.0) as u16,
+                        bits.copied().unwrap_or(1.0) as u16,
+                    )),
+                    statistics: None,
+                    unit: None,
+                    additional_fields: Default::default(),
+                }
+            })
+            .collect();
+
+        let geo_keys = match get(GEO_KEY_DIRECTORY) {
+            Some(entry) => tiff.numbers(entry)?,
+            None => Vec::new(),
+        };
+        let geo_key = |key: u16| {
+            geo_keys
+                .get(4..)
+                .unwrap_or_default()
+                .chunks_exact(4)
+                .find(|chunk| chunk[0] as u16 == key && chunk[1] == 0.0)
+                .map(|chunk| chunk[3] as u16)
+        };
+        let epsg = geo_key(PROJECTED_CS_TYPE_GEO_KEY)
+            .or_else(|| geo_key(GEOGRAPHIC_TYPE_GEO_KEY))
+            .filter(|&code| code != USER_DEFINED && code != 0)
+            .map(u32::from);
+
+        let mut transform = if let Some(entry) = get(MODEL_TRANSFORMATION) {
+            let m = tiff.numbers(entry)?;
+            if m.len() < 16 {
+                return Err(Error::InvalidTiff(
+                    "model transformation does not have 16 values".to_string(),
+                ));
+            }
+            Some([m[0], m[1], m[3], m[4], m[5], m[7]])
+        } else if let (Some(tiepoint), Some(scale)) = (get(MODEL_TIEPOINT), get(MODEL_PIXEL_SCALE))
+        {
+            let tiepoint = tiff.numbers(tiepoint)?;
+            let scale = tiff.numbers(scale)?;
+            if tiepoint.len() < 6 || scale.len() < 2 {
+                return Err(Error::InvalidTiff(
+                    "model tiepoint or pixel scale is too short".to_string(),
+                ));
+            }
+            Some([
+                scale[0],
+                0.0,
+                tiepoint[3] - tiepoint[0] * scale[0],
+                0.0,
+                -scale[1],
+                tiepoint[4] + tiepoint[1] * scale[1],
+            ])
+        } else {
+            None
+        };
+        if geo_key(RASTER_TYPE_GEO_KEY) == Some(RASTER_PIXEL_IS_POINT)
+            && let Some(transform) = transform.as_mut()
+        {
+            transform[2] -= (transform[0] + transform[1]) / 2.0;
+            transform[5] -= (transform[3] + transform[4]) / 2.0;
+        }
+
+        Ok(Raster {
+            width: width as u64,
+            height: height as u64,
+            bands,
+            epsg,
+            transform,
+            tiled: get(TILE_WIDTH).is_some(),
+        })
+    }
+
+    /// Reads a raster from a GeoTIFF on the local filesystem.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::create::Raster;
+    ///
+    /// let raster = Raster::from_path("assets/dataset_geo.tif").unwrap();
+    /// assert_eq!(raster.bands.len(), 1);
+    /// ```
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Raster> {
+        let file = File::open(path)?;
+        Raster::read(BufReader::new(file))
+    }
+
+    /// Returns the bounding box of the raster in its own crs.
+    ///
+    /// Returns `None` if the raster isn't georeferenced.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::create::Raster;
+    ///
+    /// let raster = Raster::from_path("assets/dataset_geo.tif").unwrap();
+    /// let bbox = raster.bbox().unwrap();
+    /// assert_eq!(bbox.xmin(), 373235.0056337867);
+    /// ```
+    pub fn bbox(&self) -> Option<Bbox> {
+        let [a, b, c, d, e, f] = self.transform?;
+        let (width, height) = (self.width as f64, self.height as f64);
+        let corners = [(0.0, 0.0), (width, 0.0), (0.0, height), (width, height)]
+            .map(|(col, row)| (a * col + b * row + c, d * col + e * row + f));
+        let mut bbox = Bbox::new(corners[0].0, corners[0].1, corners[0].0, corners[0].1);
+        for (x, y) in corners {
+            bbox.update(Bbox::new(x, y, x, y));
+        }
+        Some(bbox)
+    }
+
+    /// Returns the bounding box of the raster in WGS 84.
+    ///
+    /// Returns `None` if the raster isn't georeferenced. Rasters in EPSG:4326
+    /// are used as-is, and any other crs requires the `proj` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "proj")]
+    /// # {
+    /// use stac::create::Raster;
+    ///
+    /// let raster = Raster::from_path("assets/dataset_geo.tif").unwrap();
+    /// let bbox = raster.wgs84_bbox().unwrap().unwrap();
+    /// assert!(bbox.xmin() > -60.0 && bbox.xmax() < -55.0);
+    /// # }
+    /// ```
+    pub fn wgs84_bbox(&self) -> Result<Option<Bbox>> {
+        let (Some(bbox), Some(epsg)) = (self.bbox(), self.epsg) else {
+            return Ok(None);
+        };
+        if epsg == 4326 {
+            Ok(Some(bbox))
+        } else {
+            crs::bbox_to_crs84(bbox, &format!("EPSG:{epsg}")).map(Some)
+        }
+    }
+
+    /// Returns an asset for this raster.
+    ///
+    /// The asset has the `data` role, a GeoTIFF media type (cloud-optimized,
+    /// if the raster is tiled), and the raster's bands.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::create::Raster;
+    ///
+    /// let raster = Raster::from_path("assets/dataset_geo.tif").unwrap();
+    /// let asset = raster.asset("assets/dataset_geo.tif");
+    /// assert_eq!(asset.bands.len(), 1);
+    /// ```
+    pub fn asset(&self, href: impl ToString) -> Asset {
+        let mut asset = Asset::new(href).role(Role::Data);
+        asset.r#type = Some(if self.tiled { mime::COG } else { mime::GEOTIFF }.to_string());
+        asset.bands = self.bands.clone();
+        asset
+    }
+
+    /// Creates an item for this raster, with one asset.
+    ///
+    /// Projection fields are set on the item's properties. The bbox and
+    /// geometry are set if the raster can be reprojected to WGS 84, otherwise
+    /// a warning is logged and they're left empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::create::Raster;
+    ///
+    /// let raster = Raster::from_path("assets/dataset_geo.tif").unwrap();
+    /// let item = raster.to_item("dataset_geo", "assets/dataset_geo.tif");
+    /// assert!(item.assets.contains_key("data"));
+    /// ```
+    pub fn to_item(&self, id: impl ToString, href: impl ToString) -> Item {
+        let mut item = Item::new(id);
+        let _ = item
+            .assets
+            .insert(DATA_ASSET_KEY.to_string(), self.asset(href));
+        let fields = &mut item.properties.additional_fields;
+        if let Some(epsg) = self.epsg {
+            let _ = fields.insert("proj:code".to_string(), format!("EPSG:{epsg}").into());
+        }
+        let _ = fields.insert("proj:shape".to_string(), json!([self.height, self.width]));
+        if let Some(transform) = self.transform {
+            let _ = fields.insert("proj:transform".to_string(), json!(transform));
+        }
+        if let Some(bbox) = self.bbox() {
+            let _ = fields.insert("proj:bbox".to_string(), json!(Vec::<f64>::from(bbox)));
+        }
+        item.extensions.push(PROJECTION_EXTENSION.to_string());
+        match self.wgs84_bbox() {
+            Ok(Some(bbox)) => {
+                item.bbox = Some(bbox);
+                item.geometry = Some(bbox.to_geometry());
+            }
+            Ok(None) => {}
+            Err(err) => log::warn!("could not reproject raster bbox to WGS 84: {err}"),
+        }
+        item
+    }
+}
+
+/// Returns the media type for an href, inferred from its extension.
+///
+/// GeoTIFFs are assumed to be cloud-optimized. Returns `None` if the
+/// extension isn't recognized.
+///
+/// # Examples
+///
+/// ```
+/// use stac::create;
+///
+/// assert_eq!(create::media_type("s3://bucket/data.tif"), Some(stac::mime::COG));
+/// assert_eq!(create::media_type("data.parquet"), Some("application/vnd.apache.parquet"));
+/// assert_eq!(create::media_type("data.unknown"), None);
+/// ```
+pub fn media_type(href: &str) -> Option<&'static str> {
+    let href = href.split(['?', '#']).next().unwrap_or(href);
+    let (_, extension) = file_name(href).rsplit_once('.')?;
+    let media_type = match extension.to_ascii_lowercase().as_str() {
+        "tif" | "tiff" => mime::COG,
+        "jp2" => mime::IMAGE_JP2,
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "geojson" => mime::APPLICATION_GEOJSON,
+        "json" => "application/json",
+        "gpkg" => mime::APPLICATION_GEOPACKAGE,
+        "fgb" => "application/vnd.flatgeobuf",
+        "parquet" | "geoparquet" => mime::APPLICATION_PARQUET,
+        "nc" => mime::APPLICATION_NETCDF,
+        "h5" | "hdf5" => mime::APPLICATION_HDF5,
+        "hdf" => mime::APPLICATION_HDF,
+        "copc" | "laz" => mime::APPLICATION_COPC,
+        "pmtiles" => mime::APPLICATION_PMTILES,
+        "zarr" => mime::APPLICATION_ZARR,
+        "xml" => "application/xml",
+        "txt" => "text/plain",
+        _ => return None,
+    };
+    Some(media_type)
+}
+
+/// Creates an item for a file, with one data asset.
+///
+/// The item's id is the file's stem, and the asset's media type is inferred
+/// with [media_type]. The file isn't read, so the item doesn't have a
+/// geometry or bbox. Use [Raster] to create items with spatial information
+/// from GeoTIFFs.
+///
+/// # Examples
+///
+/// ```
+/// use stac::create;
+///
+/// let item = create::item_for_asset("s3://bucket/roads.gpkg").unwrap();
+/// assert_eq!(item.id, "roads");
+/// assert_eq!(item.assets["data"].r#type.as_deref(), Some(stac::mime::APPLICATION_GEOPACKAGE));
+/// ```
+pub fn item_for_asset(href: &str) -> Result<Item> {
+    let name = file_name(href.split(['?', '#']).next().unwrap_or(href));
+    let id = name.rsplit_once('.').map_or(name, |(stem, _)| stem);
+    if id.is_empty() {
+        return Err(Error::InvalidFilePath(href.to_string()));
+    }
+    let mut asset = Asset::new(href).role(Role::Data);
+    asset.r#type = media_type(href).map(String::from);
+    let mut item = Item::new(id);
+    let _ = item.assets.insert(DATA_ASSET_KEY.to_string(), asset);
+    Ok(item)
+}
+
+/// Infers a datetime from a file name.
+///
+/// The first date or datetime in the name is used. These patterns are
+/// recognized, and datetimes without a time are at midnight UTC:
+///
+/// - `20240315T101500` and `2024-03-15T10:15:00`
+/// - `20240315101500`
+/// - `2024-03-15` and `2024_03_15`
+/// - `20240315`
+///
+/// If the name is an href, only its last segment is used.
+///
+/// # Examples
+///
+/// ```
+/// use stac::create;
+///
+/// let datetime = create::datetime_from_filename("S2A_20240315T101500_B04.tif").unwrap();
+/// assert_eq!(datetime.to_rfc3339(), "2024-03-15T10:15:00+00:00");
+/// assert!(create::datetime_from_filename("dataset.tif").is_none());
+/// ```
+pub fn datetime_from_filename(name: &str) -> Option<DateTime<Utc>> {
+    const DATETIME_FORMATS: [(&str, usize); 3] = [
+        ("%Y%m%dT%H%M%S", 15),
+        ("%Y-%m-%dT%H:%M:%S", 19),
+        ("%Y%m%d%H%M%S", 14),
+    ];
+    const DATE_FORMATS: [(&str, usize); 3] = [("%Y-%m-%d", 10), ("%Y_%m_%d", 10), ("%Y%m%d", 8)];
+
+    let name = file_name(name);
+    let bytes = name.as_bytes();
+    // The end of a match can't be in the middle of a number.
+    let ends_cleanly = |end: usize| bytes.get(end).is_none_or(|b| !b.is_ascii_digit());
+    for start in 0..bytes.len() {
+        if !bytes[start].is_ascii_digit() || (start > 0 && bytes[start - 1].is_ascii_digit()) {
+            continue;
+        }
+        for (format, len) in DATETIME_FORMATS {
+            let end = start + len;
+            if let Some(s) = name.get(start..end)
+                && ends_cleanly(end)
+                && let Ok(datetime) = NaiveDateTime::parse_from_str(s, format)
+            {
+                return Some(datetime.and_utc());
+            }
+        }
+        for (format, len) in DATE_FORMATS {
+            let end = start + len;
+            if let Some(s) = name.get(start..end)
+                && ends_cleanly(end)
+                && let Ok(date) = NaiveDate::parse_from_str(s, format)
+            {
+                return date.and_hms_opt(0, 0, 0).map(|datetime| datetime.and_utc());
+            }
+        }
+    }
+    None
+}
+
+fn file_name(href: &str) -> &str {
+    href.trim_end_matches(['/', '\\'])
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or(href)
+}
+
+impl<R: Read + Seek> Tiff<R> {
+    fn new(mut reader: R) -> Result<Tiff<R>> {
+        let mut header = [0; 4];
+        reader.read_exact(&mut header)?;
+        let big_endian = match &header[..2] {
+            b"II" => false,
+            b"MM" => true,
+            _ => return Err(Error::InvalidTiff("invalid byte order".to_string())),
+        };
+        let mut tiff = Tiff {
+            reader,
+            big_endian,
+            bigtiff: false,
+        };
+        match u16_from(big_endian, &header[2..]) {
+            42 => {}
+            43 => tiff.bigtiff = true,
+            version => return Err(Error::InvalidTiff(format!("invalid version: {version}"))),
+        }
+        Ok(tiff)
+    }
+
+    fn first_ifd(&mut self) -> Result<Vec<(u16, Entry)>> {
+        let offset = if self.bigtiff {
+            let _ = self.read_u16()?;
+            let _ = self.read_u16()?;
+            self.read_u64()?
+        } else {
+            u64::from(self.read_u32()?)
+        };
+        let _ = self.reader.seek(SeekFrom::Start(offset))?;
+        let count = if self.bigtiff {
+            self.read_u64()?
+        } else {
+            u64::from(self.read_u16()?)
+        };
+        let mut entries = Vec::new();
+        for _ in 0..count {
+            let tag = self.read_u16()?;
+            let field_type = self.read_u16()?;
+            let count = if self.bigtiff {
+                self.read_u64()?
+            } else {
+                u64::from(self.read_u32()?)
+            };
+            let mut value = [0; 8];
+            let len = if self.bigtiff { 8 } else { 4 };
+            self.reader.read_exact(&mut value[..len])?;
+            entries.push((
+                tag,
+                Entry {
+                    field_type,
+                    count,
+                    value,
+                },
+            ));
+        }
+        Ok(entries)
+    }
+
+    fn bytes(&mut self, entry: &Entry) -> Result<Vec<u8>> {
+        let size = match entry.field_type {
+            1 | 2 | 6 | 7 => 1,
+            3 | 8 => 2,
+            4 | 9 | 11 => 4,
+            5 | 10 | 12 | 16 | 17 => 8,
+            field_type => {
+                return Err(Error::InvalidTiff(format!(
+                    "unsupported field type: {field_type}"
+                )));
+            }
+        };
+        let len = entry.count * size;
+        if len > MAX_TAG_SIZE {
+            return Err(Error::InvalidTiff(format!("tag is too large: {len} bytes")));
+        }
+        let inline = if self.bigtiff { 8 } else { 4 };
+        if len <= inline {
+            return Ok(entry.value[..len as usize].to_vec());
+        }
+        let offset = if self.bigtiff {
+            u64_from(self.big_endian, &entry.value)
+        } else {
+            u64::from(u32_from(self.big_endian, &entry.value))
+        };
+        let _ = self.reader.seek(SeekFrom::Start(offset))?;
+        let mut bytes = vec![0; len as usize];
+        self.reader.read_exact(&mut bytes)?;
+        Ok(bytes)
+    }
+
+    fn numbers(&mut self, entry: &Entry) -> Result<Vec<f64>> {
+        let bytes = self.bytes(entry)?;
+        let be = self.big_endian;
+        let numbers = match entry.field_type {
+            1 | 7 => bytes.iter().map(|&b| f64::from(b)).collect(),
+            6 => bytes.iter().map(|&b| f64::from(b as i8)).collect(),
+            3 => map_chunks(&bytes, 2, |b| f64::from(u16_from(be, b))),
+            8 => map_chunks(&bytes, 2, |b| f64::from(u16_from(be, b) as i16)),
+            4 => map_chunks(&bytes, 4, |b| f64::from(u32_from(be, b))),
+            9 => map_chunks(&bytes, 4, |b| f64::from(u32_from(be, b) as i32)),
+            11 => map_chunks(&bytes, 4, |b| f64::from(f32::from_bits(u32_from(be, b)))),
+            12 => map_chunks(&bytes, 8, |b| f64::from_bits(u64_from(be, b))),
+            16 => map_chunks(&bytes, 8, |b| u64_from(be, b) as f64),
+            17 => map_chunks(&bytes, 8, |b| u64_from(be, b) as i64 as f64),
+            5 => map_chunks(&bytes, 8, |b| {
+                f64::from(u32_from(be, &b[..4])) / f64::from(u32_from(be, &b[4..]))
+            }),
+            10 => map_chunks(&bytes, 8, |b| {
+                f64::from(u32_from(be, &b[..4]) as i32) / f64::from(u32_from(be, &b[4..]) as i32)
+            }),
+            field_type => {
+                return Err(Error::InvalidTiff(format!(
+                    "field type is not numeric: {field_type}"
+                )));
+            }
+        };
+        Ok(numbers)
+    }
+
+    fn ascii(&mut self, entry: &Entry) -> Result<String> {
+        let bytes = self.bytes(entry)?;
+        let bytes = bytes.split(|&b| b == 0).next().unwrap_or_default();
+        Ok(String::from_utf8_lossy(bytes).into_owned())
+    }
+
+    fn read_u16(&mut self) -> Result<u16> {
+        let mut bytes = [0; 2];
+        self.reader.read_exact(&mut bytes)?;
+        Ok(u16_from(self.big_endian, &bytes))
+    }
+
+    fn read_u32(&mut self) -> Result<u32> {
+        let mut bytes = [0; 4];
+        self.reader.read_exact(&mut bytes)?;
+        Ok(u32_from(self.big_endian, &bytes))
+    }
+
+    fn read_u64(&mut self) -> Result<u64> {
+        let mut bytes = [0; 8];
+        self.reader.read_exact(&mut bytes)?;
+        Ok(u64_from(self.big_endian, &bytes))
+    }
+}
+
+fn map_chunks(bytes: &[u8], size: usize, f: impl Fn(&[u8]) -> f64) -> Vec<f64> {
+    bytes.chunks_exact(size).map(f).collect()
+}
+
+fn u16_from(big_endian: bool, bytes: &[u8]) -> u16 {
+    let bytes = [bytes[0], bytes[1]];
+    if big_endian {
+        u16::from_be_bytes(bytes)
+    } else {
+        u16::from_le_bytes(bytes)
+    }
+}
+
+fn u32_from(big_endian: bool, bytes: &[u8]) -> u32 {
+    let bytes = [bytes[0], bytes[1], bytes[2], bytes[3]];
+    if big_endian {
+        u32::from_be_bytes(bytes)
+    } else {
+        u32::from_le_bytes(bytes)
+    }
+}
+
+fn u64_from(big_endian: bool, bytes: &[u8]) -> u64 {
+    let mut array = [0; 8];
+    array.copy_from_slice(&bytes[..8]);
+    if big_endian {
+        u64::from_be_bytes(array)
+    } else {
+        u64::from_le_bytes(array)
+    }
+}
+
+fn data_type(sample_format: u16, bits: u16) -> DataType {
+    match (sample_format, bits) {
+        (1, 8) => DataType::UInt8,
+        (1, 16) => DataType::UInt16,
+        (1, 32) => DataType::UInt32,
+        (1, 64) => DataType::UInt64,
+        (2, 8) => DataType::Int8,
+        (2, 16) => DataType::Int16,
+        (2, 32) => DataType::Int32,
+        (2, 64) => DataType::Int64,
+        (3, 16) => DataType::Float16,
+        (3, 32) => DataType::Float32,
+        (3, 64) => DataType::Float64,
+        (5, 32) => DataType::CInt16,
+        (5, 64) => DataType::CInt32,
+        (6, 64) => DataType::CFloat32,
+        (6, 128) => DataType::CFloat64,
+        _ => DataType::Other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Raster;
+    use crate::{DataType, Item, mime};
+    use std::io::Cursor;
+
+    /// Builds a little-endian GeoTIFF header with the given (tag, type, values) entries.
+    fn tiff(entries: &[(u16, u16, Vec<u8>)]) -> Vec<u8> {
+        let mut bytes = b"II\x2a\x00\x08\x00\x00\x00".to_vec();
+        let ifd_len = 2 + entries.len() * 12 + 4;
+        let mut data = Vec::new();
+        bytes.extend((entries.len() as u16).to_le_bytes());
+        for (tag, field_type, values) in entries {
+            let size = match field_type {
+                2 => 1,
+                3 => 2,
+                12 => 8,
+                _ => unimplemented!(),
+            };
+            bytes.extend(tag.to_le_bytes());
+            bytes.extend(field_type.to_le_bytes());
+            bytes.extend(((values.len() / size) as u32).to_le_bytes());
+            if values.len() <= 4 {
+                let mut value = values.clone();
+                value.resize(4, 0);
+                bytes.extend(value);
+            } else {
+                bytes.extend(((8 + ifd_len + data.len()) as u32).to_le_bytes());
+                data.extend(values);
+            }
+        }
+        bytes.extend(0u32.to_le_bytes());
+        bytes.extend(data);
+        bytes
+    }
+
+    fn shorts(values: &[u16]) -> Vec<u8> {
+        values
+            .iter()
+            .flat_map(|value| value.to_le_bytes())
+            .collect()
+    }
+
+    fn doubles(values: &[f64]) -> Vec<u8> {
+        values
+            .iter()
+            .flat_map(|value| value.to_le_bytes())
+            .collect()
+    }
+
+    #[test]
+    fn projected() {
+        let raster = Raster::from_path("assets/dataset_geo.tif").unwrap();
+        assert_eq!(raster.width, 2658);
+        assert_eq!(raster.height, 2667);
+        assert_eq!(raster.epsg, Some(32621));
+        assert!(!raster.tiled);
+        assert_eq!(raster.bands.len(), 1);
+        assert_eq!(raster.bands[0].data_type, Some(DataType::UInt16));
+        let [a, _, c, _, e, f] = raster.transform.unwrap();
+        assert_eq!(a, 100.01126757344893);
+        assert_eq!(c, 373235.0056337867);
+        assert_eq!(e, -100.01126757344893);
+        assert_eq!(f, 8285964.994366214);
+
+        let item = raster.to_item("dataset_geo", "assets/dataset_geo.tif");
+        let fields = &item.properties.additional_fields;
+        assert_eq!(fields["proj:shape"], serde_json::json!([2667, 2658]));
+        assert_eq!(fields["proj:bbox"][0], 373235.0056337867);
+        assert_eq!(item.assets["data"].r#type.as_deref(), Some(mime::GEOTIFF));
+        #[cfg(not(feature = "proj"))]
+        assert!(item.bbox.is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "proj")]
+    fn projected_bbox() {
+        let item = Item::from_href("assets/dataset_geo.tif").unwrap();
+        let bbox = item.bbox.unwrap();
+        assert!(bbox.xmin() > -60.0 && bbox.xmax() < -55.0);
+        assert!(bbox.ymin() > 15.0 && bbox.ymax() < 20.0);
+        assert!(item.geometry.is_some());
+    }
+
+    #[test]
+    fn not_georeferenced() {
+        let raster = Raster::from_path("assets/dataset.tif").unwrap();
+        assert_eq!(raster.width, 2658);
+        assert!(raster.epsg.is_none());
+        assert!(raster.bbox().is_none());
+        let item = raster.to_item("dataset", "assets/dataset.tif");
+        assert!(item.bbox.is_none());
+        assert!(item.geometry.is_none());
+        assert!(!item.properties.additional_fields.contains_key("proj:code"));
+    }
+
+    #[test]
+    fn geographic() {
+        let bytes = tiff(&[
+            (256, 3, shorts(&[10])),
+            (257, 3, shorts(&[20])),
+            (258, 3, shorts(&[32, 32])),
+            (277, 3, shorts(&[2])),
+            (322, 3, shorts(&[256])),
+            (339, 3, shorts(&[3, 3])),
+            (33550, 12, doubles(&[0.1, 0.1, 0.0])),
+            (33922, 12, doubles(&[0.0, 0.0, 0.0, -105.0, 41.0, 0.0])),
+            (
+                34735,
+                3,
+                shorts(&[1, 1, 0, 2, 1025, 0, 1, 2, 2048, 0, 1, 4326]),
+            ),
+            (42113, 2, b"-9999\0".to_vec()),
+        ]);
+        let raster = Raster::read(Cursor::new(bytes)).unwrap();
+        assert_eq!(raster.epsg, Some(4326));
+        assert!(raster.tiled);
+        assert_eq!(raster.bands.len(), 2);
+        assert_eq!(raster.bands[1].data_type, Some(DataType::Float32));
+        assert_eq!(raster.bands[1].nodata, Some(-9999.0));
+
+        let item = raster.to_item("an-id", "an-id.tif");
+        let bbox = item.bbox.unwrap();
+        assert!((bbox.xmin() - -105.05).abs() < 1e-9);
+        assert!((bbox.ymax() - 41.05).abs() < 1e-9);
+        assert!((bbox.xmax() - -104.05).abs() < 1e-9);
+        assert!((bbox.ymin() - 39.05).abs() < 1e-9);
+        assert!(item.geometry.is_some());
+        assert_eq!(item.assets["data"].r#type.as_deref(), Some(mime::COG));
+        assert_eq!(item.properties.additional_fields["proj:code"], "EPSG:4326");
+    }
+
+    #[test]
+    fn not_a_tiff() {
+        let _ = Raster::read(Cursor::new(b"not a tiff".to_vec())).unwrap_err();
+        let _ = Item::from_href("examples/simple-item.json").unwrap_err();
+        let _ = Item::from_href("https://stac.test/an-id.tif").unwrap_err();
+    }
+
+    #[test]
+    fn datetime_from_filename() {
+        for (name, expected) in [
+            ("S2A_20240315T101500_B04.tif", "2024-03-15T10:15:00+00:00"),
+            ("data-2024-03-15T10:15:00.tif", "2024-03-15T10:15:00+00:00"),
+            ("20240315101500.tif", "2024-03-15T10:15:00+00:00"),
+            (
+                "s3://bucket/2023/landsat_2024_03_15.tif",
+                "2024-03-15T00:00:00+00:00",
+            ),
+            (
+                "LC08_L2SP_042034_20240315_02_T1.tif",
+                "2024-03-15T00:00:00+00:00",
+            ),
+        ] {
+            assert_eq!(
+                super::datetime_from_filename(name).unwrap().to_rfc3339(),
+                expected,
+                "{name}"
+            );
+        }
+        assert!(super::datetime_from_filename("dataset_123456789.tif").is_none());
+        assert!(super::datetime_from_filename("20241345.tif").is_none());
+    }
+
+    #[test]
+    fn item_for_asset() {
+        let item = super::item_for_asset("https://stac.test/roads.fgb?token=abc").unwrap();
+        assert_eq!(item.id, "roads");
+        assert_eq!(
+            item.assets["data"].r#type.as_deref(),
+            Some("application/vnd.flatgeobuf")
+        );
+        assert_eq!(item.assets["data"].roles, ["data"]);
+        assert!(super::item_for_asset("s3://bucket/").is_err());
+    }
+}
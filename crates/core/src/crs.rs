@@ -0,0 +1,428 @@
+//! Coordinate reference systems, as used by [OGC API - Features - Part 2](https://docs.ogc.org/is/18-058r1/18-058r1.html).
+//!
+//! STAC is always stored in [CRS84], but APIs may accept bounding boxes in
+//! other coordinate reference systems via the `bbox-crs` parameter, and
+//! items may carry their native geometries in `proj:geometry`.
+//! Reprojecting those bounding boxes and geometries requires the `proj`
+//! feature.
+
+use crate::{Bbox, Error, Geometry, Result};
+use serde_json::Value;
+
+/// The OGC CRS84 uri, i.e. WGS 84 longitude and latitude.
+pub const CRS84: &str = "http://www.opengis.net/def/crs/OGC/1.3/CRS84";
+
+/// The OGC CRS84h uri, i.e. WGS 84 longitude, latitude, and ellipsoidal height.
+pub const CRS84H: &str = "http://www.opengis.net/def/crs/OGC/0/CRS84h";
+
+const EPSG_URI_PREFIX: &str = "http://www.opengis.net/def/crs/EPSG/0/";
+
+/// Returns true if this crs identifier refers to CRS84 (or CRS84h).
+///
+/// # Examples
+///
+/// ```
+/// use stac::crs;
+///
+/// assert!(crs::is_crs84(crs::CRS84));
+/// assert!(crs::is_crs84("OGC:CRS84"));
+/// assert!(!crs::is_crs84("http://www.opengis.net/def/crs/EPSG/0/4326"));
+/// ```
+pub fn is_crs84(crs: &str) -> bool {
+    matches!(
+        crs.trim_start_matches('[').trim_end_matches(']'),
+        CRS84 | CRS84H | "OGC:CRS84" | "OGC:CRS84h"
+    )
+}
+
+/// Returns the EPSG code of a crs identifier, if there is one.
+///
+/// Both uris (`http://www.opengis.net/def/crs/EPSG/0/4326`) and CURIEs
+/// (`EPSG:4326`) are supported.
+///
+/// # Examples
+///
+/// ```
+/// use stac::crs;
+///
+/// assert_eq!(crs::epsg_code("http://www.opengis.net/def/crs/EPSG/0/32615"), Some(32615));
+/// assert_eq!(crs::epsg_code("EPSG:3857"), Some(3857));
+/// assert_eq!(crs::epsg_code(crs::CRS84), None);
+/// ```
+pub fn epsg_code(crs: &str) -> Option<u32> {
+    let crs = crs.trim_start_matches('[').trim_end_matches(']');
+    crs.strip_prefix(EPSG_URI_PREFIX)
+        .or_else(|| crs.strip_prefix("EPSG:"))
+        .and_then(|code| code.parse().ok())
+}
+
+/// Returns the uri for an EPSG code.
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(stac::crs::epsg_uri(3857), "http://www.opengis.net/def/crs/EPSG/0/3857");
+/// ```
+pub fn epsg_uri(code: u32) -> String {
+    format!("{EPSG_URI_PREFIX}{code}")
+}
+
+/// Reprojects a bounding box from the given crs to CRS84.
+///
+/// Bounding boxes that are already in CRS84 are returned unchanged. Any
+/// elevation values are passed through as-is. Without the `proj` feature, all
+/// other crs values return an error.
+///
+/// With the `proj` feature, EPSG:4326 (which has a latitude-first axis order),
+/// EPSG:3857, and the WGS 84 UTM zones (EPSG:32601-32660 and EPSG:32701-32760)
+/// are supported.
+///
+/// # Examples
+///
+/// ```
+/// use stac::{Bbox, crs};
+///
+/// let bbox = Bbox::new(-105.0, 40.0, -104.0, 41.0);
+/// assert_eq!(crs::bbox_to_crs84(bbox, crs::CRS84).unwrap(), bbox);
+/// ```
+pub fn bbox_to_crs84(bbox: Bbox, crs: &str) -> Result<Bbox> {
+    if is_crs84(crs) {
+        return Ok(bbox);
+    }
+    #[cfg(feature = "proj")]
+    {
+        let code = epsg_code(crs).ok_or_else(|| Error::UnsupportedCrs(crs.to_string()))?;
+        proj::bbox_to_crs84(bbox, code)
+            .and_then(|option| option.ok_or_else(|| Error::UnsupportedCrs(crs.to_string())))
+    }
+    #[cfg(not(feature = "proj"))]
+    {
+        let _ = bbox;
+        Err(Error::FeatureNotEnabled("proj"))
+    }
+}
+
+/// Reprojects a GeoJSON geometry from the given crs to CRS84.
+///
+/// Geometries that are already in CRS84 are returned unchanged, and any
+/// elevation values are passed through as-is. EPSG:4326 (which has a
+/// latitude-first axis order) is always supported, and other crs values are
+/// supported with the `proj` feature, as for [bbox_to_crs84].
+///
+/// # Examples
+///
+/// ```
+/// use stac::{Geometry, crs};
+///
+/// let geometry = Geometry::new_point(vec![-105.1, 41.1]);
+/// assert_eq!(crs::geometry_to_crs84(geometry.clone(), crs::CRS84).unwrap(), geometry);
+/// ```
+pub fn geometry_to_crs84(geometry: Geometry, crs: &str) -> Result<Geometry> {
+    if is_crs84(crs) {
+        return Ok(geometry);
+    }
+    let code = epsg_code(crs).ok_or_else(|| Error::UnsupportedCrs(crs.to_string()))?;
+    let mut value = serde_json::to_value(geometry)?;
+    let mut positions = Vec::new();
+    collect_positions(&value, &mut positions);
+    if code == 4326 {
+        // EPSG:4326 is latitude, longitude
+        for position in positions.iter_mut() {
+            *position = (position.1, position.0, position.2);
+        }
+    } else {
+        #[cfg(feature = "proj")]
+        {
+            if !proj::positions_to_crs84(&mut positions, code)? {
+                return Err(Error::UnsupportedCrs(crs.to_string()));
+            }
+        }
+        #[cfg(not(feature = "proj"))]
+        {
+            return Err(Error::FeatureNotEnabled("proj"));
+        }
+    }
+    replace_positions(&mut value, &mut positions.into_iter());
+    serde_json::from_value(value).map_err(Error::from)
+}
+
+/// Collects the x and y of every position in a GeoJSON geometry, in order.
+fn collect_positions(value: &Value, positions: &mut Vec<(f64, f64, f64)>) {
+    match value {
+        Value::Array(values) if values.first().is_some_and(Value::is_number) => {
+            let x = values[0].as_f64().unwrap_or_default();
+            let y = values.get(1).and_then(Value::as_f64).unwrap_or_default();
+            positions.push((x, y, 0.0));
+        }
+        Value::Array(values) => {
+            for value in values {
+                collect_positions(value, positions);
+            }
+        }
+        Value::Object(object) => {
+            for key in ["coordinates", "geometries"] {
+                if let Some(value) = object.get(key) {
+                    collect_positions(value, positions);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Replaces the x and y of every position in a GeoJSON geometry, in the
+/// order they were collected.
+fn replace_positions(value: &mut Value, positions: &mut impl Iterator<Item = (f64, f64, f64)>) {
+    match value {
+        Value::Array(values) if values.first().is_some_and(Value::is_number) => {
+            if let Some((x, y, _)) = positions.next() {
+                values[0] = x.into();
+                if values.len() > 1 {
+                    values[1] = y.into();
+                }
+            }
+        }
+        Value::Array(values) => {
+            for value in values {
+                replace_positions(value, positions);
+            }
+        }
+        Value::Object(object) => {
+            for key in ["coordinates", "geometries"] {
+                if let Some(value) = object.get_mut(key) {
+                    replace_positions(value, positions);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Returns true if [bbox_to_crs84] can reproject from this crs.
+///
+/// # Examples
+///
+/// ```
+/// use stac::crs;
+///
+/// assert!(crs::is_supported(crs::CRS84));
+/// assert!(!crs::is_supported("EPSG:99999"));
+/// ```
+pub fn is_supported(crs: &str) -> bool {
+    if is_crs84(crs) {
+        return true;
+    }
+    #[cfg(feature = "proj")]
+    {
+        epsg_code(crs)
+            .map(|code| proj::proj_string(code).is_some())
+            .unwrap_or(false)
+    }
+    #[cfg(not(feature = "proj"))]
+    {
+        false
+    }
+}
+
+#[cfg(feature = "proj")]
+mod proj {
+    use crate::{Bbox, Result};
+    use proj4rs::{
+        Proj,
+        transform::{Transform, TransformClosure},
+    };
+
+    /// Number of points sampled along each edge of a bbox, so curved edges are
+    /// covered after reprojection.
+    const EDGE_SAMPLES: usize = 21;
+
+    const WGS84: &str = "+proj=longlat +datum=WGS84 +no_defs";
+
+    struct Points(Vec<(f64, f64, f64)>);
+
+    impl Transform for Points {
+        fn transform_coordinates<F: TransformClosure>(
+            &mut self,
+            f: &mut F,
+        ) -> proj4rs::errors::Result<()> {
+            for point in self.0.iter_mut() {
+                *point = f(point.0, point.1, point.2)?;
+            }
+            Ok(())
+        }
+    }
+
+    pub(super) fn proj_string(code: u32) -> Option<String> {
+        match code {
+            4326 => Some(WGS84.to_string()),
+            3857 => Some(
+                "+proj=merc +a=6378137 +b=6378137 +lat_ts=0 +lon_0=0 +x_0=0 +y_0=0 +k=1 +units=m +no_defs"
+                    .to_string(),
+            ),
+            32601..=32660 => Some(format!(
+                "+proj=utm +zone={} +datum=WGS84 +units=m +no_defs",
+                code - 32600
+            )),
+            32701..=32760 => Some(format!(
+                "+proj=utm +zone={} +south +datum=WGS84 +units=m +no_defs",
+                code - 32700
+            )),
+            _ => None,
+        }
+    }
+
+    pub(super) fn bbox_to_crs84(bbox: Bbox, code: u32) -> Result<Option<Bbox>> {
+        if code == 4326 {
+            // EPSG:4326 is latitude, longitude
+            return Ok(Some(swap_axes(bbox)));
+        }
+        let mut points = edge_points(&bbox);
+        if !positions_to_crs84(&mut points, code)? {
+            return Ok(None);
+        }
+        let mut xmin = f64::INFINITY;
+        let mut ymin = f64::INFINITY;
+        let mut xmax = f64::NEG_INFINITY;
+        let mut ymax = f64::NEG_INFINITY;
+        for (x, y, _) in points {
+            xmin = xmin.min(x);
+            ymin = ymin.min(y);
+            xmax = xmax.max(x);
+            ymax = ymax.max(y);
+        }
+        let reprojected = match (bbox.zmin(), bbox.zmax()) {
+            (Some(zmin), Some(zmax)) => {
+                Bbox::ThreeDimensional([xmin, ymin, zmin, xmax, ymax, zmax])
+            }
+            _ => Bbox::new(xmin, ymin, xmax, ymax),
+        };
+        Ok(Some(reprojected))
+    }
+
+    /// Reprojects positions in place to CRS84 longitude and latitude degrees.
+    ///
+    /// Returns false if the EPSG code isn't supported.
+    pub(super) fn positions_to_crs84(
+        positions: &mut Vec<(f64, f64, f64)>,
+        code: u32,
+    ) -> Result<bool> {
+        if code == 4326 {
+            // EPSG:4326 is latitude, longitude
+            for position in positions.iter_mut() {
+                *position = (position.1, position.0, position.2);
+            }
+            return Ok(true);
+        }
+        let Some(proj_string) = proj_string(code) else {
+            return Ok(false);
+        };
+        let from = Proj::from_proj_string(&proj_string)?;
+        let to = Proj::from_proj_string(WGS84)?;
+        let mut points = Points(std::mem::take(positions));
+        proj4rs::transform::transform(&from, &to, &mut points)?;
+        *positions = points
+            .0
+            .into_iter()
+            .map(|(x, y, z)| (x.to_degrees(), y.to_degrees(), z))
+            .collect();
+        Ok(true)
+    }
+
+    fn swap_axes(bbox: Bbox) -> Bbox {
+        match bbox {
+            Bbox::TwoDimensional([ymin, xmin, ymax, xmax]) => Bbox::new(xmin, ymin, xmax, ymax),
+            Bbox::ThreeDimensional([ymin, xmin, zmin, ymax, xmax, zmax]) => {
+                Bbox::ThreeDimensional([xmin, ymin, zmin, xmax, ymax, zmax])
+            }
+        }
+    }
+
+    fn edge_points(bbox: &Bbox) -> Vec<(f64, f64, f64)> {
+        let (xmin, ymin, xmax, ymax) = (bbox.xmin(), bbox.ymin(), bbox.xmax(), bbox.ymax());
+        let mut points = Vec::with_capacity(EDGE_SAMPLES * 4);
+        for i in 0..EDGE_SAMPLES {
+            let t = i as f64 / (EDGE_SAMPLES - 1) as f64;
+            let x = xmin + (xmax - xmin) * t;
+            let y = ymin + (ymax - ymin) * t;
+            points.push((x, ymin, 0.0));
+            points.push((x, ymax, 0.0));
+            points.push((xmin, y, 0.0));
+            points.push((xmax, y, 0.0));
+        }
+        points
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Bbox;
+
+    #[test]
+    fn crs84_is_passthrough() {
+        let bbox = Bbox::new(-105.0, 40.0, -104.0, 41.0);
+        assert_eq!(super::bbox_to_crs84(bbox, super::CRS84).unwrap(), bbox);
+    }
+
+    #[test]
+    #[cfg(not(feature = "proj"))]
+    fn requires_proj() {
+        let bbox = Bbox::new(-105.0, 40.0, -104.0, 41.0);
+        assert!(super::bbox_to_crs84(bbox, "EPSG:3857").is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "proj")]
+    fn epsg_4326_axis_order() {
+        let bbox = Bbox::new(40.0, -105.0, 41.0, -104.0);
+        assert_eq!(
+            super::bbox_to_crs84(bbox, &super::epsg_uri(4326)).unwrap(),
+            Bbox::new(-105.0, 40.0, -104.0, 41.0)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "proj")]
+    fn web_mercator() {
+        let bbox = Bbox::new(-20037508.34, -7.0, 20037508.34, 7.0);
+        let reprojected = super::bbox_to_crs84(bbox, "EPSG:3857").unwrap();
+        assert!((reprojected.xmin() + 180.0).abs() < 1e-6);
+        assert!((reprojected.xmax() - 180.0).abs() < 1e-6);
+        assert!(reprojected.ymin().abs() < 1e-3);
+    }
+
+    #[test]
+    #[cfg(feature = "proj")]
+    fn geometry_utm() {
+        let geometry = crate::Geometry::new_point(vec![500000.0, 0.0]);
+        let geometry = super::geometry_to_crs84(geometry, "EPSG:32615").unwrap();
+        let value = serde_json::to_value(geometry).unwrap();
+        let coordinates = value["coordinates"].as_array().unwrap();
+        assert!((coordinates[0].as_f64().unwrap() + 93.0).abs() < 1e-6);
+        assert!(coordinates[1].as_f64().unwrap().abs() < 1e-6);
+    }
+
+    #[test]
+    fn geometry_epsg_4326_axis_order() {
+        let geometry: crate::Geometry = serde_json::from_value(serde_json::json!({
+            "type": "Polygon",
+            "coordinates": [[[40.0, -105.0, 10.0], [41.0, -105.0, 10.0], [41.0, -104.0, 10.0], [40.0, -105.0, 10.0]]]
+        }))
+        .unwrap();
+        let geometry = super::geometry_to_crs84(geometry, "EPSG:4326").unwrap();
+        let value = serde_json::to_value(geometry).unwrap();
+        assert_eq!(
+            value["coordinates"][0][2],
+            serde_json::json!([-104.0, 41.0, 10.0])
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "proj")]
+    fn unsupported() {
+        let bbox = Bbox::new(0.0, 0.0, 1.0, 1.0);
+        assert!(matches!(
+            super::bbox_to_crs84(bbox, "EPSG:99999").unwrap_err(),
+            crate::Error::UnsupportedCrs(_)
+        ));
+    }
+}
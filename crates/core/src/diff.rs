@@ -0,0 +1,448 @@
+//! Differences between STAC values and trees.
+//!
+//! A [Diff] compares two sets of STAC objects, e.g. the items of a catalog
+//! before and after an ETL rerun, and reports which objects were added,
+//! removed, or changed. Changes are recorded as [JSON
+//! Patch](https://datatracker.ietf.org/doc/html/rfc6902) operations:
+//!
+//! ```
+//! use stac::{Item, diff::Diff};
+//!
+//! let before = Item::new("an-id");
+//! let mut after = before.clone();
+//! after.properties.title = Some("A title".to_string());
+//! let diff = Diff::new([before.into()], [after.into()]).unwrap();
+//! assert!(diff.added.is_empty());
+//! assert_eq!(diff.changed["an-id"].len(), 1);
+//! ```
+
+use crate::{Extent, Item, Result, SpatialExtent, TemporalExtent, Value, patch::Operation};
+use serde::Serialize;
+use serde_json::Map;
+use std::{collections::BTreeMap, fmt::Display};
+
+/// The differences between two sets of STAC objects.
+///
+/// Objects are matched by id. Items that belong to a collection are matched by
+/// `{collection}/{id}`, so items with the same id in different collections
+/// are kept apart.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Diff {
+    /// The objects that are only in the second set.
+    pub added: Vec<String>,
+
+    /// The objects that are only in the first set.
+    pub removed: Vec<String>,
+
+    /// The JSON Patch operations that turn each changed object in the first
+    /// set into the one in the second set.
+    pub changed: BTreeMap<String, Vec<Operation>>,
+
+    /// The extents of each set's items, if they're different.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extent: Option<ExtentDrift>,
+}
+
+/// The extents of the items in two sets of STAC objects.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExtentDrift {
+    /// The extent of the items in the first set.
+    pub before: Option<Extent>,
+
+    /// The extent of the items in the second set.
+    pub after: Option<Extent>,
+}
+
+impl Diff {
+    /// Compares two sets of STAC objects.
+    ///
+    /// Item collections are compared item by item.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Item, diff::Diff};
+    ///
+    /// let diff = Diff::new([Item::new("a").into()], [Item::new("b").into()]).unwrap();
+    /// assert_eq!(diff.added, vec!["b"]);
+    /// assert_eq!(diff.removed, vec!["a"]);
+    /// ```
+    pub fn new(
+        before: impl IntoIterator<Item = Value>,
+        after: impl IntoIterator<Item = Value>,
+    ) -> Result<Diff> {
+        Diff::ignoring(before, after, &[])
+    }
+
+    /// Compares two sets of STAC objects, ignoring some fields.
+    ///
+    /// Fields are named by their dot-delimited path, e.g. `links` or
+    /// `properties.updated`. This is useful for fields that always change,
+    /// like timestamps or the hrefs of links.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Item, Link, diff::Diff};
+    ///
+    /// let before = Item::new("an-id");
+    /// let mut after = before.clone();
+    /// after.links.push(Link::new("./other.json", "related"));
+    /// let diff = Diff::ignoring([before.into()], [after.into()], &["links"]).unwrap();
+    /// assert!(diff.is_empty());
+    /// ```
+    pub fn ignoring(
+        before: impl IntoIterator<Item = Value>,
+        after: impl IntoIterator<Item = Value>,
+        ignore: &[&str],
+    ) -> Result<Diff> {
+        let (before, before_extent) = objects(before, ignore)?;
+        let (mut after, after_extent) = objects(after, ignore)?;
+        let mut diff = Diff::default();
+        for (key, before) in before {
+            if let Some(after) = after.remove(&key) {
+                let operations = json_patch(&before, &after);
+                if !operations.is_empty() {
+                    let _ = diff.changed.insert(key, operations);
+                }
+            } else {
+                diff.removed.push(key);
+            }
+        }
+        diff.added = after.into_keys().collect();
+        if before_extent != after_extent {
+            diff.extent = Some(ExtentDrift {
+                before: before_extent,
+                after: after_extent,
+            });
+        }
+        Ok(diff)
+    }
+
+    /// Returns true if there are no differences.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Item, diff::Diff};
+    ///
+    /// let item = Item::new("an-id");
+    /// let diff = Diff::new([item.clone().into()], [item.into()]).unwrap();
+    /// assert!(diff.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.changed.is_empty()
+            && self.extent.is_none()
+    }
+}
+
+impl Display for Diff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for key in &self.added {
+            writeln!(f, "+ {key}")?;
+        }
+        for key in &self.removed {
+            writeln!(f, "- {key}")?;
+        }
+        for (key, operations) in &self.changed {
+            writeln!(f, "~ {key}")?;
+            for operation in operations {
+                writeln!(f, "    {operation}")?;
+            }
+        }
+        if let Some(extent) = self.extent.as_ref() {
+            let bbox = |extent: &Option<Extent>| {
+                extent
+                    .as_ref()
+                    .and_then(|extent| serde_json::to_string(&extent.spatial.bbox).ok())
+                    .unwrap_or_else(|| "none".to_string())
+            };
+            let interval = |extent: &Option<Extent>| {
+                extent
+                    .as_ref()
+                    .and_then(|extent| serde_json::to_string(&extent.temporal.interval).ok())
+                    .unwrap_or_else(|| "none".to_string())
+            };
+            writeln!(
+                f,
+                "spatial extent: {} -> {}",
+                bbox(&extent.before),
+                bbox(&extent.after)
+            )?;
+            writeln!(
+                f,
+                "temporal extent: {} -> {}",
+                interval(&extent.before),
+                interval(&extent.after)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the JSON Patch operations that turn one JSON value into another.
+///
+/// Objects are compared key by key and arrays element by element, so the
+/// patch only touches what changed. Apply the operations with
+/// [apply_patch](crate::patch::apply_patch).
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+/// use stac::{diff::json_patch, patch::Operation};
+///
+/// let operations = json_patch(&json!({"a": 1, "b": [1, 2]}), &json!({"a": 2, "b": [1]}));
+/// assert_eq!(
+///     operations,
+///     vec![
+///         Operation::Replace { path: "/a".to_string(), value: json!(2) },
+///         Operation::Remove { path: "/b/1".to_string() },
+///     ]
+/// );
+/// ```
+pub fn json_patch(before: &serde_json::Value, after: &serde_json::Value) -> Vec<Operation> {
+    let mut operations = Vec::new();
+    add_operations(&mut operations, String::new(), before, after);
+    operations
+}
+
+fn add_operations(
+    operations: &mut Vec<Operation>,
+    path: String,
+    before: &serde_json::Value,
+    after: &serde_json::Value,
+) {
+    use serde_json::Value::{Array, Object};
+
+    match (before, after) {
+        (Object(before), Object(after)) => {
+            for (key, before) in before {
+                let path = format!("{path}/{}", escape(key));
+                if let Some(after) = after.get(key) {
+                    add_operations(operations, path, before, after);
+                } else {
+                    operations.push(Operation::Remove { path });
+                }
+            }
+            for (key, after) in after {
+                if !before.contains_key(key) {
+                    operations.push(Operation::Add {
+                        path: format!("{path}/{}", escape(key)),
+                        value: after.clone(),
+                    });
+                }
+            }
+        }
+        (Array(before), Array(after)) => {
+            for (i, (before, after)) in before.iter().zip(after).enumerate() {
+                add_operations(operations, format!("{path}/{i}"), before, after);
+            }
+            for (i, after) in after.iter().enumerate().skip(before.len()) {
+                operations.push(Operation::Add {
+                    path: format!("{path}/{i}"),
+                    value: after.clone(),
+                });
+            }
+            // Remove from the end, so the indices of the remaining elements
+            // don't shift.
+            for i in (after.len()..before.len()).rev() {
+                operations.push(Operation::Remove {
+                    path: format!("{path}/{i}"),
+                });
+            }
+        }
+        (before, after) => {
+            if before != after {
+                operations.push(Operation::Replace {
+                    path,
+                    value: after.clone(),
+                });
+            }
+        }
+    }
+}
+
+fn escape(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+fn objects(
+    values: impl IntoIterator<Item = Value>,
+    ignore: &[&str],
+) -> Result<(BTreeMap<String, serde_json::Value>, Option<Extent>)> {
+    let mut objects = BTreeMap::new();
+    let mut extent = None;
+    for value in values {
+        match value {
+            Value::Item(item) => add_item(&mut objects, &mut extent, item, ignore)?,
+            Value::ItemCollection(item_collection) => {
+                for item in item_collection.items {
+                    add_item(&mut objects, &mut extent, item, ignore)?;
+                }
+            }
+            Value::Catalog(catalog) => {
+                let _ = objects.insert(catalog.id.clone(), to_value(catalog, ignore)?);
+            }
+            Value::Collection(collection) => {
+                let _ = objects.insert(collection.id.clone(), to_value(collection, ignore)?);
+            }
+        }
+    }
+    Ok((objects, extent))
+}
+
+fn add_item(
+    objects: &mut BTreeMap<String, serde_json::Value>,
+    extent: &mut Option<Extent>,
+    item: Item,
+    ignore: &[&str],
+) -> Result<()> {
+    let extent = extent.get_or_insert_with(|| Extent {
+        spatial: SpatialExtent { bbox: Vec::new() },
+        temporal: TemporalExtent {
+            interval: Vec::new(),
+        },
+        additional_fields: Map::new(),
+    });
+    if let Some(bbox) = item.bbox {
+        extent.spatial.update(bbox);
+    }
+    let (start, end) = item.datetimes();
+    extent.temporal.update(start, end);
+    let key = match item.collection.as_ref() {
+        Some(collection) => format!("{collection}/{}", item.id),
+        None => item.id.clone(),
+    };
+    let _ = objects.insert(key, to_value(item, ignore)?);
+    Ok(())
+}
+
+fn to_value(value: impl Serialize, ignore: &[&str]) -> Result<serde_json::Value> {
+    let mut value = serde_json::to_value(value)?;
+    for field in ignore {
+        let mut parts = field.split('.').peekable();
+        let mut object = value.as_object_mut();
+        while let (Some(part), Some(map)) = (parts.next(), object) {
+            if parts.peek().is_none() {
+                let _ = map.remove(part);
+                break;
+            }
+            object = map.get_mut(part).and_then(serde_json::Value::as_object_mut);
+        }
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Diff, json_patch};
+    use crate::{Catalog, Item, ItemCollection, patch::Operation};
+    use serde_json::json;
+
+    #[test]
+    fn json_patch_escapes_keys() {
+        let operations = json_patch(&json!({}), &json!({"a/b~c": 1}));
+        assert_eq!(
+            operations,
+            vec![Operation::Add {
+                path: "/a~1b~0c".to_string(),
+                value: json!(1)
+            }]
+        );
+    }
+
+    #[test]
+    fn json_patch_arrays() {
+        let operations = json_patch(&json!([1, 2, 3]), &json!([1]));
+        assert_eq!(
+            operations,
+            vec![
+                Operation::Remove {
+                    path: "/2".to_string()
+                },
+                Operation::Remove {
+                    path: "/1".to_string()
+                },
+            ]
+        );
+        let operations = json_patch(&json!([1]), &json!([2, 3]));
+        assert_eq!(
+            operations,
+            vec![
+                Operation::Replace {
+                    path: "/0".to_string(),
+                    value: json!(2)
+                },
+                Operation::Add {
+                    path: "/1".to_string(),
+                    value: json!(3)
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn items_in_collections() {
+        let mut a = Item::new("an-id");
+        a.collection = Some("a".to_string());
+        let mut b = Item::new("an-id");
+        b.collection = Some("b".to_string());
+        let diff = Diff::new(
+            [ItemCollection::from(vec![a]).into()],
+            [ItemCollection::from(vec![b]).into()],
+        )
+        .unwrap();
+        assert_eq!(diff.added, vec!["b/an-id"]);
+        assert_eq!(diff.removed, vec!["a/an-id"]);
+    }
+
+    #[test]
+    fn extent_drift() {
+        let before: Item = crate::read("examples/simple-item.json").unwrap();
+        let mut after = before.clone();
+        after.bbox = Some(crate::Bbox::new(-180., -90., 180., 90.));
+        let diff = Diff::new([before.into()], [after.into()]).unwrap();
+        let extent = diff.extent.unwrap();
+        assert_ne!(extent.before, extent.after);
+
+        let diff = Diff::new(
+            [Catalog::new("a", "").into()],
+            [Catalog::new("a", "").into()],
+        );
+        assert!(diff.unwrap().is_empty());
+    }
+
+    #[test]
+    fn ignore_nested_field() {
+        let before = Item::new("an-id");
+        let mut after = before.clone();
+        let _ = after
+            .properties
+            .additional_fields
+            .insert("updated".to_string(), "2024-01-01T00:00:00Z".into());
+        assert!(
+            !Diff::new([before.clone().into()], [after.clone().into()])
+                .unwrap()
+                .is_empty()
+        );
+        let diff = Diff::ignoring([before.into()], [after.into()], &["properties.updated"]);
+        assert!(diff.unwrap().is_empty());
+    }
+
+    #[test]
+    fn display() {
+        let before = Item::new("an-id");
+        let mut after = before.clone();
+        after.properties.title = Some("A title".to_string());
+        let mut new = before.clone();
+        new.id = "new".to_string();
+        let diff = Diff::new([before.into()], [after.into(), new.into()]).unwrap();
+        assert_eq!(
+            diff.to_string(),
+            "+ new\n~ an-id\n    add /properties/title: \"A title\"\n"
+        );
+    }
+}
@@ -27,6 +27,11 @@ pub enum Error {
     #[error(transparent)]
     Geojson(#[from] Box<geojson::Error>),
 
+    /// Two items have the same id (and collection) and the merge strategy
+    /// doesn't allow it.
+    #[error("duplicate item id: {0}")]
+    DuplicateItemId(String),
+
     /// An empty datetime interval.
     #[error("empty datetime interval")]
     EmptyDatetimeInterval,
@@ -45,6 +50,10 @@ pub enum Error {
         expected: String,
     },
 
+    /// A search's intersects geometry is larger than the configured maximum.
+    #[error("intersects geometry is too large: {0} bytes (max {1})")]
+    IntersectsTooLarge(usize, usize),
+
     /// Returned when a property name conflicts with a top-level STAC field, or
     /// it's an invalid top-level field name.
     #[error("invalid attribute name: {0}")]
@@ -62,6 +71,28 @@ pub enum Error {
     #[error("invalid file path: {0}")]
     InvalidFilePath(String),
 
+    /// This GeoJSON can't be used as a search's intersects geometry.
+    #[error("invalid intersects: {0}")]
+    InvalidIntersects(&'static str),
+
+    /// A JSON Patch operation could not be applied.
+    #[error("cannot apply patch operation at \"{path}\": {reason}")]
+    InvalidPatch {
+        /// The JSON pointer of the operation.
+        path: String,
+
+        /// Why the operation could not be applied.
+        reason: &'static str,
+    },
+
+    /// This string is not a valid layout template.
+    #[error("invalid template: {0}")]
+    InvalidTemplate(String),
+
+    /// This file is not a TIFF we can read.
+    #[error("invalid tiff: {0}")]
+    InvalidTiff(String),
+
     /// [std::io::Error]
     #[error(transparent)]
     Io(#[from] std::io::Error),
@@ -70,10 +101,30 @@ pub enum Error {
     #[error("no \"{0}\" field in the JSON object")]
     MissingField(&'static str),
 
+    /// An item doesn't have a value for a layout template variable.
+    #[error("item \"{id}\" has no value for template variable \"{variable}\"")]
+    MissingTemplateValue {
+        /// The id of the item.
+        id: String,
+
+        /// The name of the variable.
+        variable: String,
+    },
+
     /// There is not an href, when an href is required.
     #[error("no href")]
     NoHref,
 
+    /// A number is NaN or infinite.
+    #[error("non-finite number in field \"{field}\" of item \"{id}\"")]
+    NonFiniteNumber {
+        /// The id of the item.
+        id: String,
+
+        /// The path to the field, e.g. `assets.data.statistics.mean`.
+        field: String,
+    },
+
     /// There are no items, when items are required.
     #[error("no items")]
     NoItems,
@@ -113,6 +164,50 @@ pub enum Error {
     #[error(transparent)]
     TryFromInt(#[from] std::num::TryFromIntError),
 
+    /// A field has values of different types across items.
+    #[error(
+        "type conflict in field \"{field}\" for item \"{id}\": expected {expected}, found {found}"
+    )]
+    TypeConflict {
+        /// The field name.
+        field: String,
+
+        /// The id of the offending item.
+        id: String,
+
+        /// The type that was expected, e.g. `number`.
+        expected: String,
+
+        /// The type that was found, e.g. `string`.
+        found: String,
+    },
+
+    /// The extension isn't known, e.g. when pinning an extension version.
+    #[error("unknown extension: {0}")]
+    UnknownExtension(String),
+
+    /// The extension is known, but the version isn't.
+    #[error("unknown version of the {name} extension: {version}")]
+    UnknownExtensionVersion {
+        /// The extension's name, e.g. `projection`.
+        name: String,
+
+        /// The version that isn't known.
+        version: String,
+    },
+
+    /// Unknown item collection merge strategy.
+    #[error("unknown merge strategy (expected newest, left, right, or error): {0}")]
+    UnknownMergeStrategy(String),
+
+    /// Unknown asset or provider role.
+    #[error("unknown role: {0}")]
+    UnknownRole(String),
+
+    /// Unknown non-finite number policy.
+    #[error("unknown non-finite policy (expected error, null, or clamp): {0}")]
+    UnknownNonFinitePolicy(String),
+
     /// Returned when the `type` field of a STAC object does not equal `"Feature"`, `"Catalog"`, or `"Collection"`.
     #[error("unknown \"type\": {0}")]
     UnknownType(String),
@@ -121,6 +216,23 @@ pub enum Error {
     #[error("this functionality is not yet implemented: {0}")]
     Unimplemented(&'static str),
 
+    /// The coordinate reference system isn't supported.
+    #[error("unsupported crs: {0}")]
+    UnsupportedCrs(String),
+
+    /// There's no field migration between two major versions of an extension.
+    #[error("no migration for the {name} extension from {from} to {to}")]
+    UnsupportedExtensionMigration {
+        /// The extension's name, e.g. `raster`.
+        name: String,
+
+        /// The version being migrated from.
+        from: String,
+
+        /// The version being migrated to.
+        to: String,
+    },
+
     /// Unsupported geoparquet type
     #[error("unsupported geoparquet type")]
     UnsupportedGeoparquetType,
@@ -168,6 +280,34 @@ pub enum Error {
     #[cfg(feature = "geoparquet")]
     Parquet(#[from] parquet::errors::ParquetError),
 
+    /// A stac-geoparquet file doesn't have exactly one collection in its
+    /// metadata, so it can't be read as a single collection.
+    #[error("expected one collection in the stac-geoparquet metadata, found {0}")]
+    #[cfg(feature = "geoparquet")]
+    CollectionCount(usize),
+
+    /// A custom parquet key-value metadata key is reserved by geoparquet,
+    /// stac-geoparquet, or arrow.
+    #[error("reserved parquet metadata key: {0}")]
+    #[cfg(feature = "geoparquet")]
+    ReservedMetadataKey(String),
+
+    /// An upsert has an item with the same id as one of its deletes.
+    #[error("item is both upserted and deleted: {0}")]
+    #[cfg(feature = "geoparquet")]
+    UpsertConflict(String),
+
+    /// An upsert deletes every item, and a stac-geoparquet file can't be
+    /// written without any items.
+    #[error("upsert would delete every item, and stac-geoparquet can't be written without items")]
+    #[cfg(feature = "geoparquet")]
+    UpsertDeletesEverything,
+
+    /// [proj4rs::errors::Error]
+    #[error(transparent)]
+    #[cfg(feature = "proj")]
+    Proj(#[from] proj4rs::errors::Error),
+
     /// Invalid year value.
     #[error("invalid year: {0}")]
     InvalidYear(i32),
@@ -0,0 +1,252 @@
+//! Automatic repairs for common item problems.
+//!
+//! [fix_item] normalizes an item in place and returns what it changed:
+//!
+//! ```
+//! use stac::{Item, fix::{Fix, fix_item}};
+//!
+//! let mut item = Item::new("an-id");
+//! item.extensions = vec!["an-extension".to_string(), "an-extension".to_string()];
+//! let fixes = fix_item(&mut item);
+//! assert_eq!(
+//!     fixes,
+//!     vec![Fix::DuplicateExtension { extension: "an-extension".to_string() }]
+//! );
+//! assert_eq!(item.extensions, vec!["an-extension"]);
+//! ```
+
+use crate::{Item, create, item::TOP_LEVEL_ATTRIBUTES, link::Rel};
+use serde::Serialize;
+use std::{collections::HashSet, fmt::Display};
+
+/// A change made by [fix_item].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "fix", rename_all = "snake_case")]
+pub enum Fix {
+    /// The missing bbox was filled from the geometry.
+    Bbox,
+
+    /// A duplicate entry was removed from `stac_extensions`.
+    DuplicateExtension {
+        /// The extension.
+        extension: String,
+    },
+
+    /// The links were sorted.
+    SortedLinks,
+
+    /// An asset's media type was set from its href's file extension.
+    MediaType {
+        /// The asset key.
+        key: String,
+
+        /// The old media type.
+        from: Option<String>,
+
+        /// The new media type.
+        to: String,
+    },
+
+    /// A property that collides with a top-level item field was removed.
+    PropertyCollision {
+        /// The property key.
+        key: String,
+    },
+}
+
+/// Fixes common problems with an item, and returns what was changed.
+///
+/// - A missing `bbox` is filled from the geometry (requires the `geo` feature)
+/// - Duplicate `stac_extensions` are removed
+/// - Links are sorted, with `self`, `root`, `parent`, and `collection` first
+///   and the rest grouped by rel
+/// - Asset media types are set from their hrefs' file extensions when they're
+///   missing or clearly wrong, e.g. a `.tif` with an `application/json` type
+/// - Properties that collide with top-level item fields, e.g.
+///   `properties.id`, are removed
+///
+/// # Examples
+///
+/// ```
+/// use stac::{Asset, Item, fix::{Fix, fix_item}};
+///
+/// let mut item = Item::new("an-id");
+/// let _ = item.assets.insert("data".to_string(), Asset::new("data.tif"));
+/// let fixes = fix_item(&mut item);
+/// assert!(matches!(fixes[0], Fix::MediaType { .. }));
+/// assert_eq!(item.assets["data"].r#type.as_deref(), Some(stac::mime::COG));
+/// ```
+pub fn fix_item(item: &mut Item) -> Vec<Fix> {
+    let mut fixes = Vec::new();
+
+    #[cfg(feature = "geo")]
+    if item.bbox.is_none() && item.geometry.is_some() && item.update_bbox().is_ok() {
+        fixes.push(Fix::Bbox);
+    }
+
+    let mut seen = HashSet::new();
+    item.extensions.retain(|extension| {
+        if seen.insert(extension.clone()) {
+            true
+        } else {
+            fixes.push(Fix::DuplicateExtension {
+                extension: extension.clone(),
+            });
+            false
+        }
+    });
+
+    let mut links = item.links.clone();
+    links.sort_by_cached_key(|link| link_order(&link.rel));
+    if links != item.links {
+        item.links = links;
+        fixes.push(Fix::SortedLinks);
+    }
+
+    for (key, asset) in item.assets.iter_mut() {
+        let Some(media_type) = create::media_type(&asset.href) else {
+            continue;
+        };
+        if asset
+            .r#type
+            .as_deref()
+            .is_none_or(|r#type| !is_compatible(r#type, media_type))
+        {
+            fixes.push(Fix::MediaType {
+                key: key.clone(),
+                from: asset.r#type.replace(media_type.to_string()),
+                to: media_type.to_string(),
+            });
+        }
+    }
+
+    for key in TOP_LEVEL_ATTRIBUTES {
+        if item.properties.additional_fields.remove(key).is_some() {
+            fixes.push(Fix::PropertyCollision {
+                key: key.to_string(),
+            });
+        }
+    }
+
+    fixes
+}
+
+impl Display for Fix {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Fix::Bbox => write!(f, "filled bbox from geometry"),
+            Fix::DuplicateExtension { extension } => {
+                write!(f, "removed duplicate extension: {extension}")
+            }
+            Fix::SortedLinks => write!(f, "sorted links"),
+            Fix::MediaType { key, from, to } => match from {
+                Some(from) => write!(f, "changed media type of asset {key}: {from} -> {to}"),
+                None => write!(f, "set media type of asset {key}: {to}"),
+            },
+            Fix::PropertyCollision { key } => write!(f, "removed colliding property: {key}"),
+        }
+    }
+}
+
+fn link_order(rel: &Rel) -> (u8, String) {
+    let rank = match rel {
+        Rel::SelfLink => 0,
+        Rel::Root => 1,
+        Rel::Parent => 2,
+        Rel::Collection => 3,
+        _ => 4,
+    };
+    (rank, rel.to_string())
+}
+
+/// Returns true if a media type is close enough to the one inferred from a
+/// file extension that it shouldn't be changed.
+///
+/// Media types match if they're the same without their parameters, or, for
+/// non-image types, if they have the same top-level type (e.g.
+/// `application/geo+json` for a `.json` file).
+fn is_compatible(media_type: &str, inferred: &str) -> bool {
+    let essence = |media_type: &str| {
+        media_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase()
+    };
+    let (media_type, inferred) = (essence(media_type), essence(inferred));
+    if media_type == inferred {
+        return true;
+    }
+    match (media_type.split_once('/'), inferred.split_once('/')) {
+        (Some((top_level, _)), Some((inferred_top_level, _))) => {
+            top_level != "image" && top_level == inferred_top_level
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Fix, fix_item};
+    use crate::{Asset, Item, Link};
+
+    #[test]
+    fn sort_links() {
+        let mut item = Item::new("an-id");
+        item.links = vec![
+            Link::new("b.json", "related"),
+            Link::new("a.json", "alternate"),
+            Link::root("catalog.json"),
+            Link::self_("item.json"),
+        ];
+        assert_eq!(fix_item(&mut item), vec![Fix::SortedLinks]);
+        let rels: Vec<_> = item.links.iter().map(|link| link.rel.as_str()).collect();
+        assert_eq!(rels, vec!["self", "root", "alternate", "related"]);
+        assert!(fix_item(&mut item).is_empty());
+    }
+
+    #[test]
+    fn media_types() {
+        let mut item = Item::new("an-id");
+        let mut asset = Asset::new("data.json");
+        asset.r#type = Some(crate::mime::APPLICATION_GEOJSON.to_string());
+        let _ = item.assets.insert("geojson".to_string(), asset);
+        let mut asset = Asset::new("data.png");
+        asset.r#type = Some("image/jpeg".to_string());
+        let _ = item.assets.insert("thumbnail".to_string(), asset);
+        assert_eq!(
+            fix_item(&mut item),
+            vec![Fix::MediaType {
+                key: "thumbnail".to_string(),
+                from: Some("image/jpeg".to_string()),
+                to: "image/png".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn property_collisions() {
+        let mut item = Item::new("an-id");
+        let _ = item
+            .properties
+            .additional_fields
+            .insert("id".to_string(), "another-id".into());
+        assert_eq!(
+            fix_item(&mut item),
+            vec![Fix::PropertyCollision {
+                key: "id".to_string()
+            }]
+        );
+        assert!(!item.properties.additional_fields.contains_key("id"));
+    }
+
+    #[test]
+    #[cfg(feature = "geo")]
+    fn bbox() {
+        let mut item = Item::new("an-id");
+        item.geometry = Some(geojson::Geometry::new_point(vec![-105.1, 41.1]));
+        assert_eq!(fix_item(&mut item), vec![Fix::Bbox]);
+        assert!(item.bbox.is_some());
+    }
+}
@@ -3,24 +3,33 @@
 use crate::{Error, Result};
 use geo::{Rect, coord};
 
-/// Creates a two-dimensional rectangle from four coordinates.
+/// Creates a two-dimensional rectangle from four or six coordinates.
+///
+/// Three-dimensional (six value) bounding boxes are flattened to their x and y
+/// extents, since [Rect] is always two-dimensional. Use
+/// [Bbox::elevation_intersects](crate::Bbox::elevation_intersects) to compare
+/// elevations.
 ///
 /// # Examples
 ///
 /// ```
 /// let bbox = stac::geo::bbox(&vec![-106.0, 41.0, -105.0, 42.0]).unwrap();
+/// let bbox_3d = stac::geo::bbox(&vec![-106.0, 41.0, 0.0, -105.0, 42.0, 100.0]).unwrap();
+/// assert_eq!(bbox, bbox_3d);
 /// ```
 pub fn bbox(coordinates: &[f64]) -> Result<Rect> {
-    if coordinates.len() == 4 {
-        Ok(Rect::new(
+    match coordinates.len() {
+        4 => Ok(Rect::new(
             coord! { x: coordinates[0], y: coordinates[1] },
             coord! { x: coordinates[2], y: coordinates[3] },
-        ))
-    } else {
-        // TODO support three dimensional
-        Err(Error::InvalidBbox(
+        )),
+        6 => Ok(Rect::new(
+            coord! { x: coordinates[0], y: coordinates[1] },
+            coord! { x: coordinates[3], y: coordinates[4] },
+        )),
+        _ => Err(Error::InvalidBbox(
             coordinates.to_vec(),
-            "unsupported 3D bbox",
-        ))
+            "must have 4 or 6 values",
+        )),
     }
 }
@@ -0,0 +1,218 @@
+use crate::{Error, Result};
+use arrow_schema::{DataType, Schema};
+use indexmap::IndexMap;
+use serde_json::Value;
+use std::collections::HashSet;
+
+/// What to do when a top-level field has values of different types, e.g. a
+/// property that is sometimes a number and sometimes a string.
+///
+/// Nulls never conflict, and neither do integers and floats.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TypeConflicts {
+    /// Return an [Error::TypeConflict] with the field name and the id of the
+    /// first offending item.
+    #[default]
+    Error,
+
+    /// Convert every non-null value of the field to a string.
+    ///
+    /// Arrays and objects are converted to their JSON representation.
+    PromoteToString,
+
+    /// Convert every non-null value of the field to a float.
+    ///
+    /// Strings are parsed as floats. If a string can't be parsed, or a value
+    /// is a boolean, array, or object, an [Error::TypeConflict] is returned.
+    PromoteToFloat,
+}
+
+/// Resolves type conflicts in flattened item values according to the policy.
+///
+/// If a schema is provided (e.g. when encoding a later batch), its field types
+/// are treated as having been seen first.
+pub(super) fn coerce(
+    values: &mut [Value],
+    policy: TypeConflicts,
+    schema: Option<&Schema>,
+) -> Result<()> {
+    let mut kinds: IndexMap<String, &'static str> = IndexMap::new();
+    if let Some(schema) = schema {
+        for field in schema.fields() {
+            if let Some(kind) = data_type_kind(field.data_type()) {
+                let _ = kinds.insert(field.name().clone(), kind);
+            }
+        }
+    }
+    let mut conflicts = HashSet::new();
+    for value in values.iter() {
+        let Some(object) = value.as_object() else {
+            continue;
+        };
+        for (key, value) in object {
+            let Some(kind) = kind(value) else {
+                continue;
+            };
+            let expected = *kinds.entry(key.clone()).or_insert(kind);
+            if expected != kind {
+                if policy == TypeConflicts::Error {
+                    return Err(type_conflict(key, object, expected, kind));
+                }
+                let _ = conflicts.insert(key.clone());
+            }
+        }
+    }
+    if conflicts.is_empty() {
+        return Ok(());
+    }
+    for value in values.iter_mut() {
+        let Some(object) = value.as_object_mut() else {
+            continue;
+        };
+        let id = object.get("id").cloned();
+        for key in &conflicts {
+            let Some(value) = object.get_mut(key) else {
+                continue;
+            };
+            match policy {
+                TypeConflicts::Error => unreachable!("errors are returned while scanning"),
+                TypeConflicts::PromoteToString => match value {
+                    Value::Null | Value::String(_) => {}
+                    _ => *value = Value::String(value.to_string()),
+                },
+                TypeConflicts::PromoteToFloat => {
+                    let float = match value {
+                        Value::Null => continue,
+                        Value::Number(number) => number.as_f64(),
+                        Value::String(s) => s.trim().parse::<f64>().ok(),
+                        _ => None,
+                    };
+                    let float = float
+                        .and_then(serde_json::Number::from_f64)
+                        .ok_or_else(|| Error::TypeConflict {
+                            field: key.clone(),
+                            id: id_string(id.as_ref()),
+                            expected: "number".to_string(),
+                            found: kind(value).unwrap_or("null").to_string(),
+                        })?;
+                    *value = Value::Number(float);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn type_conflict(
+    key: &str,
+    object: &serde_json::Map<String, Value>,
+    expected: &str,
+    found: &str,
+) -> Error {
+    Error::TypeConflict {
+        field: key.to_string(),
+        id: id_string(object.get("id")),
+        expected: expected.to_string(),
+        found: found.to_string(),
+    }
+}
+
+fn id_string(id: Option<&Value>) -> String {
+    match id {
+        Some(Value::String(id)) => id.clone(),
+        Some(id) => id.to_string(),
+        None => String::new(),
+    }
+}
+
+fn kind(value: &Value) -> Option<&'static str> {
+    match value {
+        Value::Null => None,
+        Value::Bool(_) => Some("boolean"),
+        Value::Number(_) => Some("number"),
+        Value::String(_) => Some("string"),
+        Value::Array(_) => Some("array"),
+        Value::Object(_) => Some("object"),
+    }
+}
+
+fn data_type_kind(data_type: &DataType) -> Option<&'static str> {
+    match data_type {
+        DataType::Boolean => Some("boolean"),
+        DataType::Int8
+        | DataType::Int16
+        | DataType::Int32
+        | DataType::Int64
+        | DataType::UInt8
+        | DataType::UInt16
+        | DataType::UInt32
+        | DataType::UInt64
+        | DataType::Float16
+        | DataType::Float32
+        | DataType::Float64 => Some("number"),
+        DataType::Utf8
+        | DataType::LargeUtf8
+        | DataType::Utf8View
+        | DataType::Dictionary(_, _)
+        | DataType::Timestamp(_, _) => Some("string"),
+        DataType::List(_) | DataType::LargeList(_) | DataType::FixedSizeList(_, _) => Some("array"),
+        DataType::Struct(_) => Some("object"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TypeConflicts;
+    use serde_json::json;
+
+    fn values() -> Vec<serde_json::Value> {
+        vec![
+            json!({"id": "a", "foo": 1}),
+            json!({"id": "b", "foo": "2.5"}),
+            json!({"id": "c", "foo": null}),
+        ]
+    }
+
+    #[test]
+    fn error() {
+        let error = super::coerce(&mut values(), TypeConflicts::Error, None).unwrap_err();
+        assert!(matches!(
+            error,
+            crate::Error::TypeConflict { field, id, expected, found }
+                if field == "foo" && id == "b" && expected == "number" && found == "string"
+        ));
+    }
+
+    #[test]
+    fn promote_to_string() {
+        let mut values = values();
+        super::coerce(&mut values, TypeConflicts::PromoteToString, None).unwrap();
+        assert_eq!(values[0]["foo"], "1");
+        assert_eq!(values[1]["foo"], "2.5");
+        assert!(values[2]["foo"].is_null());
+    }
+
+    #[test]
+    fn promote_to_float() {
+        let mut values = values();
+        super::coerce(&mut values, TypeConflicts::PromoteToFloat, None).unwrap();
+        assert_eq!(values[0]["foo"], 1.0);
+        assert_eq!(values[1]["foo"], 2.5);
+        assert!(values[2]["foo"].is_null());
+    }
+
+    #[test]
+    fn promote_to_float_unparseable() {
+        let mut values = vec![json!({"id": "a", "foo": 1}), json!({"id": "b", "foo": "x"})];
+        let error = super::coerce(&mut values, TypeConflicts::PromoteToFloat, None).unwrap_err();
+        assert!(matches!(error, crate::Error::TypeConflict { id, .. } if id == "b"));
+    }
+
+    #[test]
+    fn integers_and_floats() {
+        let mut values = vec![json!({"id": "a", "foo": 1}), json!({"id": "b", "foo": 1.5})];
+        super::coerce(&mut values, TypeConflicts::Error, None).unwrap();
+        assert_eq!(values[0]["foo"], 1);
+    }
+}
@@ -38,7 +38,7 @@ const TOP_LEVEL_KEYS: [&str; 10] = [
     "collection",
 ];
 
-use crate::{Error, datetime::parse_datetime_permissively};
+use crate::{Error, audit::FieldAudit, datetime::parse_datetime_permissively};
 use arrow_array::{RecordBatchReader, cast::*, types::*, *};
 use arrow_cast::display::{ArrayFormatter, FormatOptions};
 use arrow_json::JsonSerializable;
@@ -464,6 +464,10 @@ fn set_geometry_column_for_json_rows(
         .filter_map(|(i, maybe_row)| maybe_row.as_mut().map(|row| (i, row)))
     {
         use GeoArrowType::*;
+        if array.is_null(i) {
+            let _ = row.insert(col_name.to_string(), Value::Null);
+            continue;
+        }
         let value = match array.data_type() {
             Point(_) => GeometryValue::from(&array.as_point().value(i)?.to_point()),
             LineString(_) => {
@@ -517,17 +521,52 @@ pub fn from_record_batch_reader<R: RecordBatchReader>(
     Ok(rows)
 }
 
+/// Creates STAC JSON values from a record batch reader, recording any fields
+/// that are dropped.
+///
+/// Assets that aren't objects are dropped, and recorded as `assets.{key}`.
+pub fn from_record_batch_reader_with_audit<R: RecordBatchReader>(
+    reader: R,
+    audit: &mut FieldAudit,
+) -> Result<Vec<serde_json::Map<String, Value>>, Error> {
+    let mut rows = Vec::new();
+    for result in reader {
+        let record_batch = result?;
+        rows.extend(record_batch_to_json_rows_with_audit(
+            record_batch,
+            Some(audit),
+        )?);
+    }
+    Ok(rows)
+}
+
 pub(crate) fn record_batch_to_json_rows(
     record_batch: RecordBatch,
+) -> Result<Vec<JsonMap<String, Value>>, Error> {
+    record_batch_to_json_rows_with_audit(record_batch, None)
+}
+
+fn record_batch_to_json_rows_with_audit(
+    record_batch: RecordBatch,
+    mut audit: Option<&mut FieldAudit>,
 ) -> Result<Vec<JsonMap<String, Value>>, Error> {
     let mut rows: Vec<Option<JsonMap<String, Value>>> =
         iter::repeat_n(Some(JsonMap::new()), record_batch.num_rows()).collect();
     let schema = record_batch.schema();
+    let mut has_geometry = schema.column_with_name("geometry").is_some();
     for (j, col) in record_batch.columns().iter().enumerate() {
         let field = schema.field(j);
         let col_name = field.name();
         if field.extension_type_name().is_some() & GeoArrowType::try_from(field).is_ok() {
             let array = from_arrow_array(col, field)?;
+            // A primary geometry column with a custom name is read back as
+            // the item geometry.
+            let col_name = if has_geometry {
+                col_name.as_str()
+            } else {
+                has_geometry = true;
+                "geometry"
+            };
             set_geometry_column_for_json_rows(&mut rows, array, col_name)?;
         } else {
             set_column_for_json_rows(&mut rows, col, col_name, false)?;
@@ -562,13 +601,14 @@ pub(crate) fn record_batch_to_json_rows(
                     row.insert("stac_extensions".to_string(), stac_extensions);
                 }
             }
-            unflatten(row)
+            unflatten(row, audit.as_deref_mut())
         })
         .collect::<Result<_, _>>()
 }
 
 fn unflatten(
     mut item: serde_json::Map<String, Value>,
+    audit: Option<&mut FieldAudit>,
 ) -> Result<serde_json::Map<String, Value>, Error> {
     let mut properties = serde_json::Map::new();
     let keys: Vec<_> = item
@@ -582,7 +622,15 @@ fn unflatten(
         })
         .collect();
     if let Some(assets) = item.get_mut("assets").and_then(|a| a.as_object_mut()) {
+        if let Some(audit) = audit {
+            for (key, _) in assets.iter().filter(|(_, asset)| !asset.is_object()) {
+                audit.record(format!("assets.{key}"));
+            }
+        }
         assets.retain(|_, asset| asset.is_object());
+        for asset in assets.values_mut().filter_map(Value::as_object_mut) {
+            unflatten_bands(asset);
+        }
     }
     for key in keys {
         if let Some(value) = item.remove(&key) {
@@ -602,6 +650,49 @@ fn unflatten(
     Ok(item)
 }
 
+/// Rebuilds an asset's `bands` from fields written by [Bands::Flatten](super::Bands::Flatten).
+fn unflatten_bands(asset: &mut serde_json::Map<String, Value>) {
+    let keys: Vec<_> = asset
+        .keys()
+        .filter(|key| key.starts_with("bands:"))
+        .cloned()
+        .collect();
+    let mut bands: Vec<serde_json::Map<String, Value>> = Vec::new();
+    for key in keys {
+        let Some((index, field)) = key["bands:".len()..].split_once(':') else {
+            continue;
+        };
+        let Ok(index) = index.parse::<usize>() else {
+            continue;
+        };
+        let Some(value) = asset.remove(&key) else {
+            continue;
+        };
+        if value.is_null() {
+            continue;
+        }
+        if bands.len() <= index {
+            bands.resize_with(index + 1, Default::default);
+        }
+        if let Some(statistic) = field.strip_prefix("statistics:") {
+            if let Value::Object(statistics) = bands[index]
+                .entry("statistics")
+                .or_insert_with(|| Value::Object(Default::default()))
+            {
+                let _ = statistics.insert(statistic.to_string(), value);
+            }
+        } else {
+            let _ = bands[index].insert(field.to_string(), value);
+        }
+    }
+    if !bands.is_empty() && !asset.contains_key("bands") {
+        let _ = asset.insert(
+            "bands".to_string(),
+            Value::Array(bands.into_iter().map(Value::Object).collect()),
+        );
+    }
+}
+
 fn convert_bbox(obj: serde_json::Map<String, Value>) -> Value {
     if let Some((((xmin, ymin), xmax), ymax)) = obj
         .get("xmin")
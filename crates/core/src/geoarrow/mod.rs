@@ -1,11 +1,16 @@
 //! Convert items to geoarrow record batches.
 
+mod coerce;
 pub mod json;
+mod report;
 
-use crate::{Error, Item, ItemCollection, Result};
-use arrow_array::{Array, RecordBatch, RecordBatchReader, builder::BinaryBuilder, cast::AsArray};
+use crate::{Error, Item, ItemCollection, Result, audit::FieldAudit};
+use arrow_array::{
+    Array, RecordBatch, RecordBatchReader, builder::BinaryBuilder, cast::AsArray, new_null_array,
+};
 use arrow_json::ReaderBuilder;
 use arrow_schema::{DataType, Field, SchemaBuilder, SchemaRef, TimeUnit};
+pub use coerce::TypeConflicts;
 use geo_types::Geometry;
 use geoarrow_array::{
     GeoArrowArray,
@@ -13,8 +18,16 @@ use geoarrow_array::{
     builder::GeometryBuilder,
 };
 use geoarrow_schema::{GeoArrowType, GeometryType, Metadata};
+pub use report::{FieldReport, SchemaReport};
 use serde_json::{Value, json};
-use std::{io::Cursor, sync::Arc};
+use std::{
+    collections::BTreeSet,
+    io::Cursor,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
 
 /// Datetime columns.
 pub const DATETIME_COLUMNS: [&str; 8] = [
@@ -42,16 +55,49 @@ pub fn encode_with_options(items: Vec<Item>, options: Options) -> Result<(Record
     Ok((record_batch, encoder.into_schema()))
 }
 
+/// Encodes items into a record batch with options, recording any fields that
+/// are dropped.
+///
+/// Invalid attributes are always dropped, regardless of
+/// [Options::drop_invalid_attributes]. See
+/// [Item::into_flat_item_with_audit] for how they're recorded.
+///
+/// # Examples
+///
+/// ```
+/// use stac::{Item, audit::FieldAudit, geoarrow::{self, Options}};
+///
+/// let mut item = Item::new("an-id");
+/// let _ = item.additional_fields.insert("vendor_field".into(), 42.into());
+/// let mut audit = FieldAudit::new();
+/// let (record_batch, schema) =
+///     geoarrow::encode_with_audit(vec![item], Options::default(), &mut audit).unwrap();
+/// assert_eq!(audit.count("vendor_field"), 1);
+/// ```
+pub fn encode_with_audit(
+    items: Vec<Item>,
+    options: Options,
+    audit: &mut FieldAudit,
+) -> Result<(RecordBatch, SchemaRef)> {
+    let options = Options {
+        drop_invalid_attributes: true,
+        ..options
+    };
+    let (encoder, record_batch) = Encoder::build(items, options, Some(audit))?;
+    Ok((record_batch, encoder.into_schema()))
+}
+
 /// A structure for encoding [Items](Item) into a [RecordBatch].
 #[derive(Debug)]
 pub struct Encoder {
     options: Options,
     base_schema: SchemaRef,
     schema: SchemaRef,
+    non_finite_count: AtomicUsize,
 }
 
 /// Options for encoding to arrow.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Options {
     /// Whether to drop invalid attributes.
     ///
@@ -60,6 +106,99 @@ pub struct Options {
     ///
     /// Invalid attributes are values in `properties` that would conflict with a STAC-defined top-level key.
     pub drop_invalid_attributes: bool,
+
+    /// Whether to rewind polygon rings to follow the RFC 7946 winding order.
+    ///
+    /// If true, exterior rings are made counterclockwise and holes are made
+    /// clockwise before encoding. Defaults to false, which writes geometries
+    /// as-is.
+    pub normalize_winding_order: bool,
+
+    /// What to do when a field has values of different types across items.
+    ///
+    /// Defaults to [TypeConflicts::Error].
+    pub type_conflicts: TypeConflicts,
+
+    /// What to do with NaN and infinite numbers in bboxes, geometries, and
+    /// asset `nodata` and `statistics` values.
+    ///
+    /// Defaults to [Policy::Null](crate::non_finite::Policy::Null), which
+    /// matches how they've always been written to JSON. The number of
+    /// replaced values is available from [Encoder::non_finite_count].
+    pub non_finite: crate::non_finite::Policy,
+
+    /// How asset `bands` are encoded.
+    ///
+    /// Defaults to [Bands::Nested].
+    pub bands: Bands,
+
+    /// How the primary geometry column is encoded.
+    ///
+    /// Defaults to [GeometryEncoding::Native].
+    pub geometry_encoding: GeometryEncoding,
+
+    /// The name of the primary geometry column.
+    ///
+    /// Defaults to `geometry`. When reading, a geoarrow column with another
+    /// name is read back as the item geometry if there isn't a `geometry`
+    /// column.
+    pub geometry_column: String,
+
+    /// What to do when later items don't fit the schema of the first ones.
+    ///
+    /// Defaults to [SchemaEvolution::Error].
+    pub schema_evolution: SchemaEvolution,
+}
+
+/// What an [Encoder] does when items encode to a different schema than the
+/// items it was created with.
+///
+/// A parquet file has one schema, so columns can't be added once writing has
+/// started. To keep properties that only appear in later items, create the
+/// encoder from a sample that has them, e.g. with [Encoder::from_sample].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SchemaEvolution {
+    /// Return an [Error::ArrowSchemaMismatch].
+    #[default]
+    Error,
+
+    /// Conform the record batch to the encoder's schema.
+    ///
+    /// Missing columns are filled with nulls, columns with a different type
+    /// are cast to the encoder's type (e.g. integers are widened to floats),
+    /// and fields that aren't in the schema are dropped with a warning.
+    Unify,
+}
+
+/// How the primary geometry column is encoded.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GeometryEncoding {
+    /// A native geoarrow geometry array.
+    #[default]
+    Native,
+
+    /// A `geoarrow.wkb` array of well-known binary, as expected by
+    /// [stac-geoparquet](https://github.com/stac-utils/stac-geoparquet) 1.0
+    /// and most downstream tools.
+    Wkb,
+}
+
+/// How STAC 1.1 asset `bands` arrays are encoded.
+///
+/// Both strategies are read back into `bands` arrays.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Bands {
+    /// Write `bands` as a list of structs, as-is.
+    #[default]
+    Nested,
+
+    /// Explode each band's fields into their own asset fields, named
+    /// `bands:{index}:{field}`.
+    ///
+    /// Band `statistics` are exploded too, e.g. `bands:0:statistics:mean`,
+    /// so they can be queried directly, e.g. in DuckDB with
+    /// `assets.data."bands:0:statistics:mean"`.
+    Flatten,
 }
 
 #[derive(Debug)]
@@ -83,17 +222,56 @@ impl Encoder {
     /// let (encoder, record_batch) = Encoder::new(vec![item], Options::default()).unwrap();
     /// ```
     pub fn new(items: Vec<Item>, options: Options) -> Result<(Encoder, RecordBatch)> {
+        Encoder::build(items, options, None)
+    }
+
+    /// Creates a new encoder whose schema is inferred from sample items,
+    /// without encoding them.
+    ///
+    /// Items encoded later are conformed to the sample's schema, as with
+    /// [SchemaEvolution::Unify], so the sample should include every property
+    /// that should be kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Item, geoarrow::{Encoder, Options}};
+    ///
+    /// let mut sample = Item::new("sample");
+    /// let _ = sample.properties.additional_fields.insert("foo".into(), "bar".into());
+    /// let encoder = Encoder::from_sample(vec![sample], Options::default()).unwrap();
+    /// let record_batch = encoder.encode(vec![Item::new("an-id")]).unwrap();
+    /// assert!(record_batch.column_by_name("foo").is_some());
+    /// ```
+    pub fn from_sample(sample: Vec<Item>, options: Options) -> Result<Encoder> {
+        let options = Options {
+            schema_evolution: SchemaEvolution::Unify,
+            ..options
+        };
+        let (encoder, _) = Encoder::build(sample, options, None)?;
+        encoder.non_finite_count.store(0, Ordering::Relaxed);
+        Ok(encoder)
+    }
+
+    fn build(
+        items: Vec<Item>,
+        options: Options,
+        audit: Option<&mut FieldAudit>,
+    ) -> Result<(Encoder, RecordBatch)> {
         let mut writer = Writer::new(items.len());
-        for result in iter_items(items, options.drop_invalid_attributes) {
+        let mut non_finite_count = 0;
+        for result in iter_items(items, &options, audit, &mut non_finite_count) {
             writer.add(result?)?;
         }
+        coerce::coerce(&mut writer.values, options.type_conflicts, None)?;
         let base_schema = writer.infer_base_schema()?;
-        let record_batch = writer.write(base_schema.clone())?;
+        let record_batch = writer.write(base_schema.clone(), &options)?;
         Ok((
             Encoder {
                 options,
                 base_schema,
                 schema: record_batch.schema().clone(),
+                non_finite_count: AtomicUsize::new(non_finite_count),
             },
             record_batch,
         ))
@@ -114,17 +292,79 @@ impl Encoder {
     /// ```
     pub fn encode(&self, items: Vec<Item>) -> Result<RecordBatch> {
         let mut writer = Writer::new(items.len());
-        for result in iter_items(items, self.options.drop_invalid_attributes) {
+        let mut non_finite_count = 0;
+        for result in iter_items(items, &self.options, None, &mut non_finite_count) {
             writer.add(result?)?;
         }
-        let record_batch = writer.write(self.base_schema.clone())?;
-        if record_batch.schema() != self.schema {
-            Err(Error::ArrowSchemaMismatch)
-        } else {
+        let _ = self
+            .non_finite_count
+            .fetch_add(non_finite_count, Ordering::Relaxed);
+        coerce::coerce(
+            &mut writer.values,
+            self.options.type_conflicts,
+            Some(&self.base_schema),
+        )?;
+        if self.options.schema_evolution == SchemaEvolution::Unify {
+            let dropped: BTreeSet<&str> = writer
+                .values
+                .iter()
+                .filter_map(Value::as_object)
+                .flat_map(|object| object.keys())
+                .filter(|key| self.base_schema.field_with_name(key).is_err())
+                .map(String::as_str)
+                .collect();
+            if !dropped.is_empty() {
+                tracing::warn!(
+                    "dropping fields that aren't in the schema: {}",
+                    dropped.into_iter().collect::<Vec<_>>().join(", ")
+                );
+            }
+        }
+        let record_batch = writer.write(self.base_schema.clone(), &self.options)?;
+        if record_batch.schema() == self.schema {
             Ok(record_batch)
+        } else if self.options.schema_evolution == SchemaEvolution::Unify {
+            conform(&record_batch, &self.schema)
+        } else {
+            Err(Error::ArrowSchemaMismatch)
         }
     }
 
+    /// Returns this encoder's schema.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Item, geoarrow::{Encoder, Options}};
+    ///
+    /// let (encoder, record_batch) = Encoder::new(vec![Item::new("an-id")], Options::default()).unwrap();
+    /// assert_eq!(encoder.schema(), record_batch.schema());
+    /// ```
+    pub fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    /// Returns the number of non-finite numbers that this encoder has
+    /// replaced, per [Options::non_finite].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Asset, Item, geoarrow::{Encoder, Options}};
+    ///
+    /// let mut item = Item::new("an-id");
+    /// let mut asset = Asset::new("a-href");
+    /// asset.nodata = Some(f64::NAN);
+    /// let _ = item.assets.insert("data".to_string(), asset);
+    /// let (encoder, _) = Encoder::new(vec![item.clone()], Options::default()).unwrap();
+    /// assert_eq!(encoder.non_finite_count(), 1);
+    /// let _ = encoder.encode(vec![item]).unwrap();
+    /// assert_eq!(encoder.non_finite_count(), 2);
+    /// ```
+    pub fn non_finite_count(&self) -> usize {
+        self.non_finite_count.load(Ordering::Relaxed)
+    }
+
     /// Consumes this encoder and returns its schema.
     ///
     /// # Examples
@@ -157,9 +397,13 @@ impl Writer {
             .as_object_mut()
             .expect("a flat item should serialize to an object");
         if let Some(value) = object.remove("geometry") {
-            let geometry: geojson::Geometry = serde_json::from_value(value)?;
-            self.geometry_builder
-                .push_geometry(Some(&(Geometry::try_from(geometry).map_err(Box::new)?)))?;
+            if value.is_null() {
+                self.geometry_builder.push_geometry(None::<&Geometry>)?;
+            } else {
+                let geometry: geojson::Geometry = serde_json::from_value(value)?;
+                self.geometry_builder
+                    .push_geometry(Some(&(Geometry::try_from(geometry).map_err(Box::new)?)))?;
+            }
         }
         if let Some(value) = object.remove("proj:geometry") {
             let geometry: geojson::Geometry = serde_json::from_value(value)?;
@@ -197,7 +441,7 @@ impl Writer {
         Ok(Arc::new(schema_builder.finish()))
     }
 
-    fn write(mut self, base_schema: SchemaRef) -> Result<RecordBatch> {
+    fn write(mut self, base_schema: SchemaRef, options: &Options) -> Result<RecordBatch> {
         let mut decoder = ReaderBuilder::new(base_schema.clone()).build_decoder()?;
         decoder.serialize(&self.values)?;
         let record_batch = decoder.flush()?.ok_or(Error::NoItems)?;
@@ -225,8 +469,25 @@ impl Writer {
             }
         }
         let geometry_array = self.geometry_builder.finish();
-        columns.push(geometry_array.to_array_ref());
-        schema_builder.push(geometry_array.data_type().to_field("geometry", true));
+        match options.geometry_encoding {
+            GeometryEncoding::Native => {
+                columns.push(geometry_array.to_array_ref());
+                schema_builder.push(
+                    geometry_array
+                        .data_type()
+                        .to_field(options.geometry_column.as_str(), true),
+                );
+            }
+            GeometryEncoding::Wkb => {
+                let wkb_array = geoarrow_array::cast::to_wkb::<i32>(&geometry_array)?;
+                columns.push(wkb_array.to_array_ref());
+                schema_builder.push(
+                    wkb_array
+                        .data_type()
+                        .to_field(options.geometry_column.as_str(), true),
+                );
+            }
+        }
         let proj_geometry_array = self.proj_geometry_builder.finish();
         if !proj_geometry_array.is_empty() {
             let data_type = proj_geometry_array.data_type().clone();
@@ -239,24 +500,100 @@ impl Writer {
     }
 }
 
+/// Conforms a record batch to a schema, filling missing columns with nulls and
+/// casting columns with a different type.
+fn conform(record_batch: &RecordBatch, schema: &SchemaRef) -> Result<RecordBatch> {
+    for field in record_batch.schema().fields() {
+        if schema.field_with_name(field.name()).is_err() {
+            tracing::warn!("dropping column that isn't in the schema: {}", field.name());
+        }
+    }
+    let mut columns = Vec::with_capacity(schema.fields().len());
+    for field in schema.fields() {
+        let column = match record_batch.column_by_name(field.name()) {
+            Some(column) if column.data_type() == field.data_type() => column.clone(),
+            Some(column) => arrow_cast::cast(column, field.data_type())?,
+            None => new_null_array(field.data_type(), record_batch.num_rows()),
+        };
+        columns.push(column);
+    }
+    let record_batch = RecordBatch::try_new(schema.clone(), columns)?;
+    Ok(record_batch)
+}
+
 impl Default for Options {
     fn default() -> Self {
         Options {
             drop_invalid_attributes: true,
+            normalize_winding_order: false,
+            type_conflicts: TypeConflicts::default(),
+            non_finite: crate::non_finite::Policy::default(),
+            bands: Bands::default(),
+            geometry_encoding: GeometryEncoding::default(),
+            geometry_column: "geometry".to_string(),
+            schema_evolution: SchemaEvolution::default(),
         }
     }
 }
 
-fn iter_items(
-    items: Vec<Item>,
-    drop_invalid_attributes: bool,
-) -> impl Iterator<Item = Result<Value>> {
-    items.into_iter().map(move |item| {
-        item.into_flat_item(drop_invalid_attributes)
-            .and_then(|flat_item| serde_json::to_value(flat_item).map_err(Error::from))
+fn iter_items<'a>(
+    items: impl IntoIterator<Item = Item> + 'a,
+    options: &'a Options,
+    mut audit: Option<&'a mut FieldAudit>,
+    non_finite_count: &'a mut usize,
+) -> impl Iterator<Item = Result<Value>> + 'a {
+    items.into_iter().map(move |mut item| {
+        let count = item.replace_non_finite(options.non_finite)?;
+        if count > 0 {
+            tracing::debug!(
+                "replaced {count} non-finite number(s) in item {} ({})",
+                item.id,
+                options.non_finite
+            );
+            *non_finite_count += count;
+        }
+        if options.normalize_winding_order {
+            let _ = item.normalize_winding_order()?;
+        }
+        let mut value = item
+            .flatten(options.drop_invalid_attributes, audit.as_deref_mut())
+            .and_then(|flat_item| serde_json::to_value(flat_item).map_err(Error::from))?;
+        if options.bands == Bands::Flatten {
+            flatten_bands(&mut value);
+        }
+        Ok(value)
     })
 }
 
+fn flatten_bands(value: &mut Value) {
+    let Some(assets) = value.get_mut("assets").and_then(Value::as_object_mut) else {
+        return;
+    };
+    for asset in assets.values_mut().filter_map(Value::as_object_mut) {
+        let Some(Value::Array(bands)) = asset.remove("bands") else {
+            continue;
+        };
+        for (i, band) in bands.into_iter().enumerate() {
+            let Value::Object(band) = band else {
+                continue;
+            };
+            for (key, value) in band {
+                match (key.as_str(), value) {
+                    ("statistics", Value::Object(statistics)) => {
+                        for (statistic, value) in statistics {
+                            let _ =
+                                asset.insert(format!("bands:{i}:statistics:{statistic}"), value);
+                        }
+                    }
+                    (_, value) => {
+                        let _ = asset.insert(format!("bands:{i}:{key}"), value);
+                    }
+                }
+            }
+        }
+    }
+}
+
 /// Converts a single [RecordBatch] to a vector of [Item]s.
 ///
 /// # Examples
@@ -302,6 +639,36 @@ pub fn from_record_batch_reader<R: RecordBatchReader>(reader: R) -> Result<ItemC
     Ok(item_collection)
 }
 
+/// Converts a [RecordBatchReader] to an [ItemCollection], recording any
+/// fields that are dropped.
+///
+/// Assets that aren't objects are dropped, and recorded as
+/// `assets.{key}`.
+///
+/// # Examples
+///
+/// ```
+/// use stac::{Item, audit::FieldAudit, geoarrow};
+/// use arrow_array::RecordBatchIterator;
+///
+/// let (record_batch, schema) = geoarrow::encode(vec![Item::new("an-id")]).unwrap();
+/// let reader = RecordBatchIterator::new(vec![record_batch].into_iter().map(Ok), schema);
+/// let mut audit = FieldAudit::new();
+/// let item_collection = geoarrow::from_record_batch_reader_with_audit(reader, &mut audit).unwrap();
+/// assert!(audit.is_empty());
+/// ```
+pub fn from_record_batch_reader_with_audit<R: RecordBatchReader>(
+    reader: R,
+    audit: &mut FieldAudit,
+) -> Result<ItemCollection> {
+    let item_collection = json::from_record_batch_reader_with_audit(reader, audit)?
+        .into_iter()
+        .map(|item| serde_json::from_value(Value::Object(item)).map_err(Error::from))
+        .collect::<Result<Vec<_>>>()
+        .map(ItemCollection::from)?;
+    Ok(item_collection)
+}
+
 /// Converts a geometry column to geoarrow native type.
 ///
 /// # Examples
@@ -432,10 +799,42 @@ fn convert_bbox(bbox: Value) -> Result<Value> {
 
 #[cfg(test)]
 mod tests {
-    use super::Encoder;
-    use crate::{Item, ItemCollection};
+    use super::{Encoder, Options, SchemaEvolution};
+    use crate::{Error, Item, ItemCollection};
     use arrow_array::RecordBatchIterator;
 
+    #[test]
+    fn schema_evolution() {
+        let item = Item::new("a");
+        let mut proj_item = Item::new("b");
+        let _ = proj_item.properties.additional_fields.insert(
+            "proj:geometry".into(),
+            json!({"type": "Point", "coordinates": [1., 2.]}),
+        );
+        let (encoder, _) = Encoder::new(vec![item.clone()], Options::default()).unwrap();
+        assert!(matches!(
+            encoder.encode(vec![proj_item.clone()]).unwrap_err(),
+            Error::ArrowSchemaMismatch
+        ));
+
+        let options = Options {
+            schema_evolution: SchemaEvolution::Unify,
+            ..Default::default()
+        };
+        let (encoder, _) = Encoder::new(vec![item.clone()], options).unwrap();
+        let record_batch = encoder.encode(vec![proj_item.clone()]).unwrap();
+        assert_eq!(record_batch.schema(), encoder.schema());
+
+        let encoder = Encoder::from_sample(vec![proj_item], Options::default()).unwrap();
+        let record_batch = encoder.encode(vec![item]).unwrap();
+        assert!(
+            record_batch
+                .column_by_name("proj:geometry")
+                .unwrap()
+                .is_null(0)
+        );
+    }
+
     #[test]
     fn has_type() {
         let item: Item = crate::read("examples/simple-item.json").unwrap();
@@ -466,6 +865,20 @@ mod tests {
         .unwrap();
     }
 
+    #[test]
+    fn roundtrip_bbox_only() {
+        let mut item: Item = crate::read("examples/simple-item.json").unwrap();
+        item.geometry = None;
+        let (record_batch, schema) = super::encode(vec![item.clone()]).unwrap();
+        let item_collection = super::from_record_batch_reader(RecordBatchIterator::new(
+            vec![record_batch].into_iter().map(Ok),
+            schema,
+        ))
+        .unwrap();
+        assert!(item_collection.items[0].geometry.is_none());
+        assert_eq!(item_collection.items[0].bbox, item.bbox);
+    }
+
     #[test]
     fn with_wkb_geometry() {
         let item: Item = crate::read("examples/simple-item.json").unwrap();
@@ -473,6 +886,36 @@ mod tests {
         let _ = super::with_wkb_geometry(record_batch, "geometry").unwrap();
     }
 
+    #[test]
+    fn wkb_geometry_encoding() {
+        let item: Item = crate::read("examples/simple-item.json").unwrap();
+        let options = super::Options {
+            geometry_encoding: super::GeometryEncoding::Wkb,
+            ..Default::default()
+        };
+        let (record_batch, _) = super::encode_with_options(vec![item.clone()], options).unwrap();
+        let schema = record_batch.schema();
+        let (_, field) = schema.column_with_name("geometry").unwrap();
+        assert_eq!(field.extension_type_name(), Some("geoarrow.wkb"));
+        let items = super::items_from_record_batch(record_batch).unwrap();
+        assert_eq!(items[0].geometry, item.geometry);
+    }
+
+    #[test]
+    fn geometry_column() {
+        let item: Item = crate::read("examples/simple-item.json").unwrap();
+        let options = super::Options {
+            geometry_column: "geom".to_string(),
+            ..Default::default()
+        };
+        let (record_batch, _) = super::encode_with_options(vec![item.clone()], options).unwrap();
+        assert!(record_batch.schema().column_with_name("geom").is_some());
+        assert!(record_batch.schema().column_with_name("geometry").is_none());
+        let items = super::items_from_record_batch(record_batch).unwrap();
+        assert_eq!(items[0].geometry, item.geometry);
+        assert!(!items[0].properties.additional_fields.contains_key("geom"));
+    }
+
     #[test]
     fn has_proj_geometry() {
         let item: Item =
@@ -486,6 +929,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn normalize_winding_order() {
+        let mut item: Item = crate::read("examples/simple-item.json").unwrap();
+        let mut geometry = serde_json::to_value(item.geometry.take().unwrap()).unwrap();
+        geometry["coordinates"][0].as_array_mut().unwrap().reverse();
+        item.geometry = Some(serde_json::from_value(geometry).unwrap());
+        let options = super::Options {
+            normalize_winding_order: true,
+            ..Default::default()
+        };
+        let (record_batch, _) = super::encode_with_options(vec![item], options).unwrap();
+        let items = super::items_from_record_batch(record_batch).unwrap();
+        assert!(items[0].has_rfc7946_winding_order().unwrap());
+    }
+
+    #[test]
+    fn roundtrip_3d_bbox() {
+        let mut item: Item = crate::read("examples/simple-item.json").unwrap();
+        let bbox: crate::Bbox = vec![172.9, 1.3, 10.0, 173.0, 1.4, 20.0].try_into().unwrap();
+        item.bbox = Some(bbox);
+        let (record_batch, _) = super::encode(vec![item]).unwrap();
+        let items = super::items_from_record_batch(record_batch).unwrap();
+        assert_eq!(items[0].bbox.unwrap(), bbox);
+    }
+
     #[test]
     fn two_batches() {
         let item: Item = crate::read("examples/simple-item.json").unwrap();
@@ -493,6 +961,136 @@ mod tests {
         let _ = encoder.encode(vec![item]).unwrap();
     }
 
+    #[test]
+    fn encode_with_audit() {
+        use crate::audit::FieldAudit;
+
+        let mut item = Item::new("an-id");
+        let _ = item
+            .additional_fields
+            .insert("vendor_field".into(), 42.into());
+        let _ = item
+            .properties
+            .additional_fields
+            .insert("collection".into(), "not-here".into());
+        let mut audit = FieldAudit::new();
+        let options = super::Options {
+            drop_invalid_attributes: false,
+            ..Default::default()
+        };
+        let _ = super::encode_with_audit(vec![item.clone(), item], options, &mut audit).unwrap();
+        assert_eq!(audit.count("vendor_field"), 2);
+        assert_eq!(audit.count("properties.collection"), 2);
+    }
+
+    #[test]
+    fn flatten_bands() {
+        use super::{Bands, Options};
+        use crate::{Band, Statistics};
+
+        let mut item: Item = crate::read("data/bands-v1.1.0.json").unwrap();
+        item.assets.get_mut("example").unwrap().bands[0].statistics = Some(Statistics {
+            mean: Some(42.),
+            minimum: Some(0.),
+            maximum: None,
+            stddev: None,
+            valid_percent: None,
+        });
+        let options = Options {
+            bands: Bands::Flatten,
+            ..Default::default()
+        };
+        let (record_batch, _) = super::encode_with_options(vec![item.clone()], options).unwrap();
+        let assets = record_batch
+            .schema()
+            .field_with_name("assets")
+            .unwrap()
+            .to_string();
+        assert!(assets.contains("bands:0:statistics:mean"));
+        assert!(assets.contains("bands:3:raster:spatial_resolution"));
+        let items = super::items_from_record_batch(record_batch).unwrap();
+        let bands: &[Band] = &items[0].assets["example"].bands;
+        assert_eq!(bands, item.assets["example"].bands.as_slice());
+    }
+
+    #[test]
+    fn type_conflicts() {
+        use super::{Options, TypeConflicts};
+
+        let mut a = Item::new("a");
+        let _ = a
+            .properties
+            .additional_fields
+            .insert("foo".into(), 42.into());
+        let mut b = Item::new("b");
+        let _ = b
+            .properties
+            .additional_fields
+            .insert("foo".into(), "bar".into());
+        let error = super::encode(vec![a.clone(), b.clone()]).unwrap_err();
+        assert!(
+            matches!(error, crate::Error::TypeConflict { field, id, .. } if field == "foo" && id == "b")
+        );
+        let options = Options {
+            type_conflicts: TypeConflicts::PromoteToString,
+            ..Default::default()
+        };
+        let (record_batch, _) = super::encode_with_options(vec![a, b], options).unwrap();
+        let items = super::items_from_record_batch(record_batch).unwrap();
+        assert_eq!(items[0].properties.additional_fields["foo"], "42");
+    }
+
+    #[test]
+    fn type_conflicts_across_batches() {
+        use super::{Options, TypeConflicts};
+
+        let mut a = Item::new("a");
+        let _ = a
+            .properties
+            .additional_fields
+            .insert("foo".into(), "bar".into());
+        let mut b = Item::new("b");
+        let _ = b
+            .properties
+            .additional_fields
+            .insert("foo".into(), 42.into());
+        let options = Options {
+            type_conflicts: TypeConflicts::PromoteToString,
+            ..Default::default()
+        };
+        let (encoder, _) = Encoder::new(vec![a], options).unwrap();
+        let _ = encoder.encode(vec![b]).unwrap();
+    }
+
+    #[test]
+    fn non_finite() {
+        use super::Options;
+        use crate::{Asset, non_finite::Policy};
+
+        let mut item: Item = crate::read("examples/simple-item.json").unwrap();
+        let mut asset = Asset::new("a-href");
+        asset.nodata = Some(f64::INFINITY);
+        let _ = item.assets.insert("data".to_string(), asset);
+        let options = Options {
+            non_finite: Policy::Error,
+            ..Default::default()
+        };
+        assert!(super::encode_with_options(vec![item.clone()], options).is_err());
+        let options = Options {
+            non_finite: Policy::Clamp,
+            ..Default::default()
+        };
+        let (record_batch, _) = super::encode_with_options(vec![item.clone()], options).unwrap();
+        let items = super::items_from_record_batch(record_batch).unwrap();
+        assert_eq!(items[0].assets["data"].nodata, Some(f64::MAX));
+
+        assert_eq!(Options::default().non_finite, Policy::default());
+        let encoder = Encoder::from_sample(vec![item.clone()], Options::default()).unwrap();
+        assert_eq!(encoder.non_finite_count(), 0);
+        let _ = encoder.encode(vec![item.clone(), item]).unwrap();
+        assert_eq!(encoder.non_finite_count(), 2);
+    }
+
     #[test]
     fn dictionary_encoded_columns() {
         use arrow_schema::DataType;
@@ -0,0 +1,287 @@
+use super::{DATETIME_COLUMNS, DICTIONARY_COLUMNS, GeometryEncoding, Options, Writer};
+use crate::{Item, Result};
+use arrow_schema::{DataType, Field, TimeUnit};
+use geoarrow_schema::{GeoArrowType, GeometryType, WkbType};
+use indexmap::IndexMap;
+use serde_json::Value;
+use std::collections::{BTreeSet, HashSet};
+
+/// A report of the arrow schema that would be used to encode some items, with
+/// per-field statistics.
+///
+/// Use this to check what a stac-geoparquet file's schema will look like, and
+/// to find type conflicts, before writing.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaReport {
+    /// The number of items that were scanned.
+    pub num_items: usize,
+
+    /// The fields, in schema order.
+    pub fields: Vec<FieldReport>,
+}
+
+/// Inferred type and statistics for one top-level field.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldReport {
+    /// The field name.
+    pub name: String,
+
+    /// The inferred arrow data type.
+    pub data_type: DataType,
+
+    /// Whether the field is missing or null for any item.
+    pub nullable: bool,
+
+    /// The number of items that have a non-null value for this field.
+    pub count: usize,
+
+    /// The number of distinct non-null values.
+    ///
+    /// This isn't computed for geometry columns, and is always zero for them.
+    pub distinct_count: usize,
+
+    /// The JSON types seen for this field, e.g. `string` or `number`.
+    ///
+    /// More than one non-null type means the field has conflicting types,
+    /// which are handled according to [TypeConflicts](super::TypeConflicts).
+    pub json_types: BTreeSet<String>,
+}
+
+#[derive(Debug, Default)]
+struct Stats {
+    count: usize,
+    distinct: HashSet<String>,
+    json_types: BTreeSet<String>,
+}
+
+impl SchemaReport {
+    /// Scans items and reports the inferred schema.
+    ///
+    /// This uses the same inference as the [Encoder](super::Encoder), but
+    /// doesn't fail on type conflicts — instead, they're reported.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Item, geoarrow::SchemaReport};
+    ///
+    /// let mut a = Item::new("a");
+    /// let _ = a.properties.additional_fields.insert("foo".into(), 42.into());
+    /// let mut b = Item::new("b");
+    /// let _ = b.properties.additional_fields.insert("foo".into(), "bar".into());
+    /// let report = SchemaReport::new(&[a, b], Default::default()).unwrap();
+    /// assert_eq!(report.conflicts().next().unwrap().name, "foo");
+    /// ```
+    pub fn new(items: &[Item], options: Options) -> Result<SchemaReport> {
+        let geometry_column = options.geometry_column.clone();
+        let geometry_type = match options.geometry_encoding {
+            GeometryEncoding::Native => {
+                GeoArrowType::Geometry(GeometryType::new(Default::default()))
+            }
+            GeometryEncoding::Wkb => GeoArrowType::Wkb(WkbType::new(Default::default())),
+        };
+        let mut writer = Writer::new(items.len());
+        for result in super::iter_items(items.iter().cloned(), options) {
+            writer.add(result?)?;
+        }
+        let mut stats: IndexMap<String, Stats> = IndexMap::new();
+        for value in &writer.values {
+            let Some(object) = value.as_object() else {
+                continue;
+            };
+            for (key, value) in object {
+                let stats = stats.entry(key.clone()).or_default();
+                let _ = stats.json_types.insert(json_type(value).to_string());
+                if !value.is_null() {
+                    stats.count += 1;
+                    let _ = stats.distinct.insert(value.to_string());
+                }
+            }
+        }
+        let num_items = writer.values.len();
+        let schema =
+            arrow_json::reader::infer_json_schema_from_iterator(writer.values.iter().map(Ok))?;
+        let mut fields = Vec::with_capacity(schema.fields().len() + 1);
+        for field in schema.fields() {
+            let name = field.name().as_str();
+            let data_type = if DATETIME_COLUMNS.contains(&name) {
+                DataType::Timestamp(TimeUnit::Millisecond, Some("UTC".into()))
+            } else if DICTIONARY_COLUMNS.contains(&name) && field.data_type() == &DataType::Utf8 {
+                DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8))
+            } else {
+                field.data_type().clone()
+            };
+            let stats = stats.shift_remove(name).unwrap_or_default();
+            let mut json_types = stats.json_types;
+            let _ = json_types.remove("null");
+            fields.push(FieldReport {
+                name: name.to_string(),
+                data_type,
+                nullable: stats.count < num_items,
+                count: stats.count,
+                distinct_count: stats.distinct.len(),
+                json_types,
+            });
+        }
+        let geometry_field: Field = geometry_type.to_field(geometry_column.as_str(), true);
+        let count = items.iter().filter(|item| item.geometry.is_some()).count();
+        fields.push(FieldReport {
+            name: geometry_column,
+            data_type: geometry_field.data_type().clone(),
+            nullable: count < num_items,
+            count,
+            distinct_count: 0,
+            json_types: BTreeSet::new(),
+        });
+        let count = items
+            .iter()
+            .filter(|item| {
+                item.properties
+                    .additional_fields
+                    .get("proj:geometry")
+                    .is_some_and(|value| !value.is_null())
+            })
+            .count();
+        if count > 0 {
+            fields.push(FieldReport {
+                name: "proj:geometry".to_string(),
+                data_type: DataType::Binary,
+                nullable: count < num_items,
+                count,
+                distinct_count: 0,
+                json_types: BTreeSet::new(),
+            });
+        }
+        Ok(SchemaReport { num_items, fields })
+    }
+
+    /// Returns the field with the given name, if there is one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Item, geoarrow::SchemaReport};
+    ///
+    /// let report = SchemaReport::new(&[Item::new("an-id")], Default::default()).unwrap();
+    /// assert_eq!(report.field("id").unwrap().distinct_count, 1);
+    /// ```
+    pub fn field(&self, name: &str) -> Option<&FieldReport> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+
+    /// Returns an iterator over the fields that have more than one non-null
+    /// JSON type.
+    pub fn conflicts(&self) -> impl Iterator<Item = &FieldReport> {
+        self.fields.iter().filter(|field| field.has_conflict())
+    }
+
+    /// Returns true if any field has conflicting types.
+    pub fn has_conflicts(&self) -> bool {
+        self.conflicts().next().is_some()
+    }
+}
+
+impl FieldReport {
+    /// Returns true if this field has more than one non-null JSON type.
+    pub fn has_conflict(&self) -> bool {
+        self.json_types.len() > 1
+    }
+}
+
+fn json_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SchemaReport;
+    use crate::{
+        Item,
+        geoarrow::{GeometryEncoding, Options},
+    };
+    use arrow_schema::DataType;
+
+    #[test]
+    fn simple_item() {
+        let item: Item = crate::read("examples/simple-item.json").unwrap();
+        let report = SchemaReport::new(&[item], Default::default()).unwrap();
+        assert_eq!(report.num_items, 1);
+        assert!(!report.has_conflicts());
+        assert!(matches!(
+            report.field("datetime").unwrap().data_type,
+            DataType::Timestamp(_, _)
+        ));
+        assert!(matches!(
+            report.field("type").unwrap().data_type,
+            DataType::Dictionary(_, _)
+        ));
+        assert_eq!(report.field("geometry").unwrap().count, 1);
+    }
+
+    #[test]
+    fn geometry_options() {
+        let item: Item = crate::read("examples/simple-item.json").unwrap();
+        let options = Options {
+            geometry_encoding: GeometryEncoding::Wkb,
+            geometry_column: "geom".to_string(),
+            ..Default::default()
+        };
+        let (record_batch, _) =
+            crate::geoarrow::encode_with_options(vec![item.clone()], options.clone()).unwrap();
+        let report = SchemaReport::new(&[item], options).unwrap();
+        assert!(report.field("geometry").is_none());
+        let geom = report.field("geom").unwrap();
+        assert_eq!(geom.count, 1);
+        let schema = record_batch.schema();
+        let (_, field) = schema.column_with_name("geom").unwrap();
+        assert_eq!(&geom.data_type, field.data_type());
+    }
+
+    #[test]
+    fn nullability_and_cardinality() {
+        let mut a = Item::new("a");
+        let _ = a
+            .properties
+            .additional_fields
+            .insert("foo".into(), "bar".into());
+        let mut b = Item::new("b");
+        let _ = b
+            .properties
+            .additional_fields
+            .insert("foo".into(), "bar".into());
+        let c = Item::new("c");
+        let report = SchemaReport::new(&[a, b, c], Default::default()).unwrap();
+        let foo = report.field("foo").unwrap();
+        assert!(foo.nullable);
+        assert_eq!(foo.count, 2);
+        assert_eq!(foo.distinct_count, 1);
+        assert_eq!(foo.data_type, DataType::Utf8);
+        let id = report.field("id").unwrap();
+        assert!(!id.nullable);
+        assert_eq!(id.distinct_count, 3);
+    }
+
+    #[test]
+    fn integers_and_floats_do_not_conflict() {
+        let mut a = Item::new("a");
+        let _ = a
+            .properties
+            .additional_fields
+            .insert("foo".into(), 1.into());
+        let mut b = Item::new("b");
+        let _ = b
+            .properties
+            .additional_fields
+            .insert("foo".into(), 1.5.into());
+        let report = SchemaReport::new(&[a, b], Default::default()).unwrap();
+        assert!(!report.has_conflicts());
+        assert_eq!(report.field("foo").unwrap().data_type, DataType::Float64);
+    }
+}
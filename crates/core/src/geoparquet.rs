@@ -10,13 +10,26 @@ use geoparquet::{
     reader::{GeoParquetReaderBuilder, GeoParquetRecordBatchReader},
     writer::{GeoParquetRecordBatchEncoder, GeoParquetWriterOptionsBuilder},
 };
+use indexmap::IndexMap;
 pub use parquet::basic::{BrotliLevel, Compression, GzipLevel, ZstdLevel};
 use parquet::{
     arrow::{ArrowWriter, arrow_reader::ParquetRecordBatchReaderBuilder},
-    file::{metadata::KeyValue, properties::WriterProperties, reader::ChunkReader},
+    file::{
+        metadata::{KeyValue, ParquetMetaData},
+        properties::{EnabledStatistics, WriterProperties},
+        reader::ChunkReader,
+    },
+    schema::types::ColumnPath,
 };
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, io::Write};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    fs::File,
+    io::{Read, Write},
+    num::NonZeroUsize,
+    path::Path,
+    sync::Mutex,
+};
 
 /// Default stac-geoparquet compression
 pub fn default_compression() -> Compression {
@@ -30,7 +43,17 @@ pub const DEFAULT_STAC_MAX_ROW_GROUP_ROW_COUNT: usize = 150_000;
 pub const METADATA_KEY: &str = "stac-geoparquet";
 
 /// The stac-geoparquet version.
-pub const VERSION: &str = "1.0.0";
+pub const VERSION: &str = "1.1.0";
+
+/// The parquet key-value metadata key that holds the stac-geoparquet version,
+/// as of stac-geoparquet 1.1.
+pub const VERSION_KEY: &str = "stac:geoparquet_version";
+
+/// Parquet key-value metadata keys that are managed by the writer and can't
+/// be set as custom metadata.
+pub const RESERVED_METADATA_KEYS: [&str; 4] = ["geo", METADATA_KEY, VERSION_KEY, "ARROW:schema"];
+
+const BBOX_COVERING_FIELDS: [&str; 4] = ["xmin", "ymin", "xmax", "ymax"];
 
 /// Options for writing stac-geoparquet files.
 #[derive(Debug, Copy, Clone, PartialEq)]
@@ -40,6 +63,13 @@ pub struct WriterOptions {
 
     /// Maximum number of rows in a row group
     pub max_row_group_row_count: usize,
+
+    /// Declare the `bbox` column as the geometry's bbox covering.
+    ///
+    /// Per [geoparquet 1.1](https://github.com/opengeospatial/geoparquet/blob/v1.1.0/format-specs/geoparquet.md#bbox-covering-encoding),
+    /// readers can use the covering's column statistics to skip row groups
+    /// when filtering by bbox. Items with a geometry must have a bbox.
+    pub bbox_covering: bool,
 }
 
 /// An encoder for writing stac-geoparquet
@@ -90,6 +120,21 @@ impl WriterOptions {
         self.max_row_group_row_count = size;
         self
     }
+
+    /// Sets whether to declare the `bbox` column as a bbox covering and write
+    /// its statistics.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::geoparquet::WriterOptions;
+    ///
+    /// let options = WriterOptions::new().with_bbox_covering(true);
+    /// ```
+    pub fn with_bbox_covering(mut self, bbox_covering: bool) -> Self {
+        self.bbox_covering = bbox_covering;
+        self
+    }
 }
 
 impl Default for WriterOptions {
@@ -97,6 +142,7 @@ impl Default for WriterOptions {
         Self {
             compression: Some(default_compression()),
             max_row_group_row_count: DEFAULT_STAC_MAX_ROW_GROUP_ROW_COUNT,
+            bbox_covering: false,
         }
     }
 }
@@ -147,10 +193,635 @@ where
 /// assert!(count > 0);
 /// ```
 pub fn from_reader_iter<R>(reader: R) -> Result<impl Iterator<Item = Result<Vec<Item>>>>
+where
+    R: ChunkReader + 'static,
+{
+    Ok(ReaderBuilder::new(reader).build()?.batches())
+}
+
+/// Returns an iterator that yields one [ItemCollection] per row group from a
+/// [ChunkReader].
+///
+/// Only one row group's worth of items is held in memory at a time, so this
+/// can be used to process large stac-geoparquet files.
+///
+/// # Examples
+///
+/// ```
+/// use std::fs::File;
+///
+/// let file = File::open("data/extended-item.parquet").unwrap();
+/// for result in stac::geoparquet::read_chunks(file).unwrap() {
+///     let item_collection = result.unwrap();
+///     assert!(!item_collection.items.is_empty());
+/// }
+/// ```
+pub fn read_chunks<R>(reader: R) -> Result<impl Iterator<Item = Result<ItemCollection>>>
+where
+    R: ChunkReader + 'static,
+{
+    let builder = ParquetRecordBatchReaderBuilder::try_new(reader)?;
+    chunks(builder)
+}
+
+/// Reads many [ChunkReader]s concurrently into a single [ItemCollection].
+///
+/// Up to `parallelism` readers are read at once, each on its own thread. A
+/// `parallelism` of zero uses [std::thread::available_parallelism]. Threads
+/// take the next reader as soon as they're done with their last one, so one
+/// large file doesn't hold up the rest. The files don't need to share a
+/// schema, since each one is decoded into [Item]s on its own. Items are
+/// returned in reader order, and the first error (in reader order) is
+/// returned if any reader fails.
+///
+/// Each reader is copied into memory before it's decoded, so the readers
+/// don't need to be `'static`.
+///
+/// # Examples
+///
+/// ```
+/// use std::fs::File;
+///
+/// let files = vec![
+///     File::open("data/extended-item.parquet").unwrap(),
+///     File::open("data/extended-item.parquet").unwrap(),
+/// ];
+/// let item_collection = stac::geoparquet::from_readers(files, 2).unwrap();
+/// assert_eq!(item_collection.items.len(), 2);
+/// ```
+pub fn from_readers<R>(readers: Vec<R>, parallelism: usize) -> Result<ItemCollection>
+where
+    R: ChunkReader,
+{
+    read_parallel(readers, parallelism, |reader| {
+        let mut buf = Vec::new();
+        let _ = reader.get_read(0)?.read_to_end(&mut buf)?;
+        from_reader(Bytes::from(buf))
+    })
+}
+
+/// Reads many stac-geoparquet files concurrently into a single
+/// [ItemCollection].
+///
+/// Files are opened and read on the worker threads. See [from_readers] for
+/// how `parallelism`, ordering, and errors are handled.
+///
+/// # Examples
+///
+/// ```
+/// let item_collection = stac::geoparquet::from_paths(
+///     ["data/extended-item.parquet", "data/extended-item.parquet"],
+///     0,
+/// )
+/// .unwrap();
+/// assert_eq!(item_collection.items.len(), 2);
+/// ```
+pub fn from_paths<P>(
+    paths: impl IntoIterator<Item = P>,
+    parallelism: usize,
+) -> Result<ItemCollection>
+where
+    P: AsRef<Path> + Send,
+{
+    read_parallel(paths.into_iter().collect(), parallelism, |path| {
+        from_reader(File::open(path)?)
+    })
+}
+
+fn read_parallel<T, F>(inputs: Vec<T>, parallelism: usize, f: F) -> Result<ItemCollection>
+where
+    T: Send,
+    F: Fn(T) -> Result<ItemCollection> + Sync,
+{
+    let parallelism = if parallelism == 0 {
+        std::thread::available_parallelism().map_or(1, NonZeroUsize::get)
+    } else {
+        parallelism
+    }
+    .min(inputs.len())
+    .max(1);
+    let queue: Mutex<VecDeque<(usize, T)>> = Mutex::new(inputs.into_iter().enumerate().collect());
+    let mut results = std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..parallelism)
+            .map(|_| {
+                let f = &f;
+                let queue = &queue;
+                scope.spawn(move || {
+                    let mut results = Vec::new();
+                    loop {
+                        // The lock is released before the input is read.
+                        let next = queue
+                            .lock()
+                            .unwrap_or_else(|err| err.into_inner())
+                            .pop_front();
+                        let Some((i, input)) = next else {
+                            break;
+                        };
+                        results.push((i, f(input)));
+                    }
+                    results
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("geoparquet reader thread panicked"))
+            .collect::<Vec<_>>()
+    });
+    results.sort_by_key(|(i, _)| *i);
+    let mut items = Vec::new();
+    for (_, result) in results {
+        items.extend(result?.items);
+    }
+    Ok(items.into())
+}
+
+/// Appends items to existing stac-geoparquet data and writes the combined
+/// data to a [std::io::Write].
+///
+/// When the new items encode to the same arrow schema as the existing data,
+/// the existing record batches are copied to the output as-is, without
+/// decoding them into items. Otherwise, the existing data is decoded and
+/// re-encoded one row group at a time, so the whole file is never held in
+/// memory as items, and the new items must fit the existing items' schema or
+/// an [Error::ArrowSchemaMismatch] is returned. Any collections and custom
+/// metadata in the existing file are preserved.
+///
+/// # Examples
+///
+/// ```
+/// use std::{fs::File, io::Cursor};
+/// use stac::{Item, geoparquet::WriterOptions};
+///
+/// let item_collection =
+///     stac::geoparquet::from_reader(File::open("data/extended-item.parquet").unwrap()).unwrap();
+/// let mut item = item_collection.items[0].clone();
+/// item.id = "another-item".to_string();
+/// let file = File::open("data/extended-item.parquet").unwrap();
+/// let mut cursor = Cursor::new(Vec::new());
+/// stac::geoparquet::append(file, &mut cursor, vec![item], WriterOptions::new()).unwrap();
+/// ```
+pub fn append<R, W>(
+    reader: R,
+    writer: W,
+    items: Vec<Item>,
+    writer_options: WriterOptions,
+) -> Result<()>
+where
+    R: ChunkReader + 'static,
+    W: Write + Send,
+{
+    let builder = ParquetRecordBatchReaderBuilder::try_new(reader)?;
+    let metadata = stac_geoparquet_metadata(builder.metadata())?;
+    let key_value_metadata = custom_key_value_metadata(builder.metadata());
+    if !items.is_empty() {
+        let (mut state, record_batch) = WriterState::new(Options::default(), items.clone())?;
+        if record_batch.schema().fields() == builder.schema().fields() {
+            state.set_bbox_covering(writer_options.bbox_covering);
+            for collection in metadata.collections.into_values() {
+                state.add_collection(collection);
+            }
+            for (key, value) in key_value_metadata {
+                state.add_key_value_metadata(key, value)?;
+            }
+            let geo = geo_metadata(builder.metadata());
+            let mut arrow_writer =
+                ArrowWriter::try_new(writer, record_batch.schema(), Some(writer_options.into()))?;
+            for result in builder.build()? {
+                arrow_writer.write(&result?)?;
+            }
+            arrow_writer.write(&record_batch)?;
+            for mut key_value in state.into_metadata()? {
+                if key_value.key == "geo"
+                    && let Some(geo) = geo.as_deref()
+                {
+                    key_value = merge_geo_metadata(geo, key_value)?;
+                }
+                arrow_writer.append_key_value_metadata(key_value);
+            }
+            let _ = arrow_writer.finish()?;
+            return Ok(());
+        }
+    }
+    let mut writer = LazyWriter::new(writer, writer_options);
+    for result in chunks(builder)? {
+        writer.write(result?.items)?;
+    }
+    writer.write(items)?;
+    writer.finish(metadata, key_value_metadata)
+}
+
+/// A summary of the changes made by [upsert].
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UpsertSummary {
+    /// The ids of items that were added.
+    pub inserted: Vec<String>,
+
+    /// The ids of items that replaced existing items.
+    pub updated: Vec<String>,
+
+    /// The ids of items that were removed, i.e. the tombstones.
+    pub deleted: Vec<String>,
+}
+
+/// Merges a change set into existing stac-geoparquet data and writes the
+/// result to a [std::io::Write].
+///
+/// Items are matched by id. Existing items with an id in `deletes` are
+/// dropped, existing items with the same id as one of `items` are replaced in
+/// place, and any remaining `items` are added at the end. As with [append],
+/// the existing data is processed one row group at a time and collection
+/// metadata is preserved.
+///
+/// Returns an [UpsertSummary] that lists what changed, an
+/// [Error::UpsertConflict] if an id is in both `items` and `deletes`, or an
+/// [Error::UpsertDeletesEverything] if no items would be left, since
+/// stac-geoparquet can't be written without them.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Cursor;
+/// use stac::{Item, geoparquet::WriterOptions};
+///
+/// let mut cursor = Cursor::new(Vec::new());
+/// stac::geoparquet::into_writer(&mut cursor, vec![Item::new("a"), Item::new("b")]).unwrap();
+/// let bytes = bytes::Bytes::from(cursor.into_inner());
+/// let mut cursor = Cursor::new(Vec::new());
+/// let summary = stac::geoparquet::upsert(
+///     bytes,
+///     &mut cursor,
+///     vec![Item::new("b"), Item::new("c")],
+///     vec!["a".to_string()],
+///     WriterOptions::new(),
+/// )
+/// .unwrap();
+/// assert_eq!(summary.inserted, vec!["c"]);
+/// assert_eq!(summary.updated, vec!["b"]);
+/// assert_eq!(summary.deleted, vec!["a"]);
+/// ```
+pub fn upsert<R, W>(
+    reader: R,
+    writer: W,
+    items: Vec<Item>,
+    deletes: Vec<String>,
+    writer_options: WriterOptions,
+) -> Result<UpsertSummary>
+where
+    R: ChunkReader + 'static,
+    W: Write + Send,
+{
+    let deletes: HashSet<String> = deletes.into_iter().collect();
+    let mut changes: IndexMap<String, Item> = items
+        .into_iter()
+        .map(|item| (item.id.clone(), item))
+        .collect();
+    if let Some(id) = changes.keys().find(|id| deletes.contains(*id)) {
+        return Err(Error::UpsertConflict(id.clone()));
+    }
+    let mut summary = UpsertSummary::default();
+    let builder = ParquetRecordBatchReaderBuilder::try_new(reader)?;
+    let metadata = stac_geoparquet_metadata(builder.metadata())?;
+    let key_value_metadata = custom_key_value_metadata(builder.metadata());
+    let mut writer = LazyWriter::new(writer, writer_options);
+    for result in chunks(builder)? {
+        let mut items = Vec::new();
+        for item in result?.items {
+            if deletes.contains(&item.id) {
+                summary.deleted.push(item.id);
+            } else if let Some(changed) = changes.shift_remove(&item.id) {
+                summary.updated.push(item.id);
+                items.push(changed);
+            } else {
+                items.push(item);
+            }
+        }
+        writer.write(items)?;
+    }
+    summary.inserted = changes.keys().cloned().collect();
+    writer.write(changes.into_values().collect())?;
+    if writer.writer.is_none() {
+        // The arrow schema comes from the items, so there's nothing to write.
+        return Err(Error::UpsertDeletesEverything);
+    }
+    writer.finish(metadata, key_value_metadata)?;
+    Ok(summary)
+}
+
+/// Returns the collections embedded in a stac-geoparquet file's metadata,
+/// sorted by id.
+///
+/// Only the file's footer is read.
+///
+/// # Examples
+///
+/// ```
+/// use std::fs::File;
+///
+/// let collections =
+///     stac::geoparquet::collections(File::open("data/extended-item.parquet").unwrap()).unwrap();
+/// ```
+pub fn collections<R>(reader: R) -> Result<Vec<Collection>>
+where
+    R: ChunkReader + 'static,
+{
+    let builder = ParquetRecordBatchReaderBuilder::try_new(reader)?;
+    let metadata = stac_geoparquet_metadata(builder.metadata())?;
+    let mut collections: Vec<Collection> = metadata.collections.into_values().collect();
+    collections.sort_by(|a, b| a.id.cmp(&b.id));
+    Ok(collections)
+}
+
+/// Information about a stac-geoparquet file, read from its footer.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FileInfo {
+    /// The number of items.
+    pub num_items: i64,
+
+    /// The number of row groups.
+    pub num_row_groups: usize,
+
+    /// The stac-geoparquet version, if the file has stac-geoparquet metadata.
+    pub version: Option<String>,
+
+    /// The ids of the collections stored in the stac-geoparquet metadata.
+    pub collections: Vec<String>,
+
+    /// Custom key-value metadata, i.e. everything except the keys in
+    /// [RESERVED_METADATA_KEYS].
+    pub key_value_metadata: IndexMap<String, String>,
+}
+
+/// Returns the stac-geoparquet metadata of a file.
+///
+/// Both stac-geoparquet 1.0 files, which store a single `collection`, and 1.1
+/// files, which store a map of `collections`, are supported. If the file has
+/// no stac-geoparquet metadata, the default metadata is returned.
+///
+/// Only the file's footer is read.
+///
+/// # Examples
+///
+/// ```
+/// use std::fs::File;
+///
+/// let metadata =
+///     stac::geoparquet::metadata(File::open("data/extended-item.parquet").unwrap()).unwrap();
+/// ```
+pub fn metadata<R>(reader: R) -> Result<Metadata>
 where
     R: ChunkReader + 'static,
 {
     let builder = ParquetRecordBatchReaderBuilder::try_new(reader)?;
+    stac_geoparquet_metadata(builder.metadata())
+}
+
+/// Returns information about a stac-geoparquet file, including its custom
+/// key-value metadata.
+///
+/// Only the file's footer is read.
+///
+/// # Examples
+///
+/// ```
+/// use std::{fs::File, io::Cursor};
+/// use stac::{Item, geoparquet::WriterBuilder};
+///
+/// let mut cursor = Cursor::new(Vec::new());
+/// WriterBuilder::new(&mut cursor)
+///     .key_value_metadata("pipeline:version", "1.2.3")
+///     .build(vec![Item::new("an-id")])
+///     .unwrap()
+///     .finish()
+///     .unwrap();
+/// let info = stac::geoparquet::info(bytes::Bytes::from(cursor.into_inner())).unwrap();
+/// assert_eq!(info.num_items, 1);
+/// assert_eq!(info.key_value_metadata["pipeline:version"], "1.2.3");
+/// ```
+pub fn info<R>(reader: R) -> Result<FileInfo>
+where
+    R: ChunkReader + 'static,
+{
+    let builder = ParquetRecordBatchReaderBuilder::try_new(reader)?;
+    file_info(builder.metadata())
+}
+
+/// Returns information about a stac-geoparquet file from an
+/// [AsyncFileReader](parquet::arrow::async_reader::AsyncFileReader).
+///
+/// Only the file's footer is read.
+#[cfg(feature = "async")]
+pub async fn info_async<R>(reader: R) -> Result<FileInfo>
+where
+    R: parquet::arrow::async_reader::AsyncFileReader + Unpin + Send + 'static,
+{
+    let builder = parquet::arrow::ParquetRecordBatchStreamBuilder::new(reader).await?;
+    file_info(builder.metadata())
+}
+
+fn file_info(metadata: &ParquetMetaData) -> Result<FileInfo> {
+    let has_stac_geoparquet_metadata =
+        metadata
+            .file_metadata()
+            .key_value_metadata()
+            .is_some_and(|key_values| {
+                key_values
+                    .iter()
+                    .any(|key_value| key_value.key == METADATA_KEY || key_value.key == VERSION_KEY)
+            });
+    let stac_geoparquet_metadata = stac_geoparquet_metadata(metadata)?;
+    let mut collections: Vec<String> = stac_geoparquet_metadata.collections.into_keys().collect();
+    collections.sort();
+    Ok(FileInfo {
+        num_items: metadata.file_metadata().num_rows(),
+        num_row_groups: metadata.num_row_groups(),
+        version: has_stac_geoparquet_metadata.then_some(stac_geoparquet_metadata.version),
+        collections,
+        key_value_metadata: custom_key_value_metadata(metadata).into_iter().collect(),
+    })
+}
+
+fn stac_geoparquet_metadata(metadata: &ParquetMetaData) -> Result<Metadata> {
+    let value = |key: &str| {
+        metadata
+            .file_metadata()
+            .key_value_metadata()
+            .and_then(|key_values| key_values.iter().find(|key_value| key_value.key == key))
+            .and_then(|key_value| key_value.value.as_deref())
+    };
+    let mut stac_geoparquet_metadata = value(METADATA_KEY)
+        .map(serde_json::from_str::<Metadata>)
+        .transpose()?
+        .unwrap_or_default();
+    if let Some(version) = value(VERSION_KEY) {
+        stac_geoparquet_metadata.version = version.to_string();
+    }
+    Ok(stac_geoparquet_metadata)
+}
+
+fn geo_metadata(metadata: &ParquetMetaData) -> Option<String> {
+    metadata
+        .file_metadata()
+        .key_value_metadata()?
+        .iter()
+        .find(|key_value| key_value.key == "geo")?
+        .value
+        .clone()
+}
+
+/// Merges the bboxes and geometry types of existing geo metadata into the
+/// geo metadata of newly-written data.
+fn merge_geo_metadata(existing: &str, mut geo: KeyValue) -> Result<KeyValue> {
+    let Some(value) = geo.value.as_deref() else {
+        return Ok(geo);
+    };
+    let existing: serde_json::Value = serde_json::from_str(existing)?;
+    let mut value: serde_json::Value = serde_json::from_str(value)?;
+    let Some(columns) = value
+        .get_mut("columns")
+        .and_then(|columns| columns.as_object_mut())
+    else {
+        return Ok(geo);
+    };
+    for (name, column) in columns.iter_mut() {
+        let Some(existing) = existing["columns"].get(name) else {
+            continue;
+        };
+        let bboxes = (
+            column.get("bbox").and_then(|bbox| bbox.as_array()),
+            existing.get("bbox").and_then(|bbox| bbox.as_array()),
+        );
+        if let (Some(bbox), Some(existing_bbox)) = bboxes
+            && bbox.len() == existing_bbox.len()
+        {
+            let half = bbox.len() / 2;
+            let bbox: Vec<serde_json::Value> = bbox
+                .iter()
+                .zip(existing_bbox)
+                .enumerate()
+                .map(|(i, (a, b))| match (a.as_f64(), b.as_f64()) {
+                    (Some(a), Some(b)) if i < half => a.min(b).into(),
+                    (Some(a), Some(b)) => a.max(b).into(),
+                    _ => a.clone(),
+                })
+                .collect();
+            column["bbox"] = bbox.into();
+        }
+        if let (Some(geometry_types), Some(existing_geometry_types)) = (
+            column
+                .get("geometry_types")
+                .and_then(|geometry_types| geometry_types.as_array()),
+            existing
+                .get("geometry_types")
+                .and_then(|geometry_types| geometry_types.as_array()),
+        ) {
+            let mut geometry_types: Vec<serde_json::Value> = geometry_types.clone();
+            for geometry_type in existing_geometry_types {
+                if !geometry_types.contains(geometry_type) {
+                    geometry_types.push(geometry_type.clone());
+                }
+            }
+            column["geometry_types"] = geometry_types.into();
+        }
+    }
+    geo.value = Some(serde_json::to_string(&value)?);
+    Ok(geo)
+}
+
+/// Declares the `bbox` column as the primary geometry column's covering in
+/// the geo metadata.
+fn add_bbox_covering(mut geo: KeyValue) -> Result<KeyValue> {
+    let Some(value) = geo.value.as_deref() else {
+        return Ok(geo);
+    };
+    let mut value: serde_json::Value = serde_json::from_str(value)?;
+    let primary_column = value
+        .get("primary_column")
+        .and_then(|primary_column| primary_column.as_str())
+        .unwrap_or("geometry")
+        .to_string();
+    if let Some(column) = value
+        .get_mut("columns")
+        .and_then(|columns| columns.get_mut(&primary_column))
+        .and_then(|column| column.as_object_mut())
+    {
+        let bbox: serde_json::Map<String, serde_json::Value> = BBOX_COVERING_FIELDS
+            .iter()
+            .map(|field| (field.to_string(), serde_json::json!(["bbox", field])))
+            .collect();
+        let _ = column.insert("covering".to_string(), serde_json::json!({"bbox": bbox}));
+    }
+    geo.value = Some(serde_json::to_string(&value)?);
+    Ok(geo)
+}
+
+fn custom_key_value_metadata(metadata: &ParquetMetaData) -> Vec<(String, String)> {
+    metadata
+        .file_metadata()
+        .key_value_metadata()
+        .into_iter()
+        .flatten()
+        .filter(|key_value| !RESERVED_METADATA_KEYS.contains(&key_value.key.as_str()))
+        .filter_map(|key_value| {
+            key_value
+                .value
+                .clone()
+                .map(|value| (key_value.key.clone(), value))
+        })
+        .collect()
+}
+
+/// A writer that isn't created until there are items to define its schema.
+struct LazyWriter<W: Write + Send> {
+    builder: Option<WriterBuilder<W>>,
+    writer: Option<Writer<W>>,
+}
+
+impl<W: Write + Send> LazyWriter<W> {
+    fn new(writer: W, writer_options: WriterOptions) -> LazyWriter<W> {
+        LazyWriter {
+            builder: Some(WriterBuilder::new(writer).writer_options(writer_options)),
+            writer: None,
+        }
+    }
+
+    fn write(&mut self, items: Vec<Item>) -> Result<()> {
+        if items.is_empty() {
+            Ok(())
+        } else if let Some(writer) = self.writer.as_mut() {
+            writer.write(items)
+        } else if let Some(builder) = self.builder.take() {
+            self.writer = Some(builder.build(items)?);
+            Ok(())
+        } else {
+            unreachable!("the builder is only taken when the writer is created")
+        }
+    }
+
+    fn finish(self, metadata: Metadata, key_value_metadata: Vec<(String, String)>) -> Result<()> {
+        let mut writer = self.writer.ok_or(Error::NoItems)?;
+        for collection in metadata.collections.into_values() {
+            writer = writer.add_collection(collection)?;
+        }
+        for (key, value) in key_value_metadata {
+            writer = writer.add_key_value_metadata(key, value)?;
+        }
+        writer.finish()
+    }
+}
+
+fn chunks<R>(
+    builder: ParquetRecordBatchReaderBuilder<R>,
+) -> Result<impl Iterator<Item = Result<ItemCollection>>>
+where
+    R: ChunkReader + 'static,
+{
+    let row_group_sizes = builder
+        .metadata()
+        .row_groups()
+        .iter()
+        .map(|row_group| usize::try_from(row_group.num_rows()))
+        .collect::<std::result::Result<VecDeque<_>, _>>()?;
     let geoparquet_metadata = builder
         .geoparquet_metadata()
         .transpose()?
@@ -159,10 +830,88 @@ where
         builder.geoarrow_schema(&geoparquet_metadata, true, Default::default())?;
     let reader = builder.build()?;
     let reader = GeoParquetRecordBatchReader::try_new(reader, geoarrow_schema)?;
-    Ok(reader.map(|result| {
-        let record_batch = result?;
-        crate::geoarrow::items_from_record_batch(record_batch)
-    }))
+    Ok(Chunks {
+        batches: reader.map(|result| {
+            let record_batch = result?;
+            crate::geoarrow::items_from_record_batch(record_batch)
+        }),
+        row_group_sizes,
+        buffer: Vec::new(),
+    })
+}
+
+/// Returns a stream that yields one [ItemCollection] per row group from an
+/// [AsyncFileReader](parquet::arrow::async_reader::AsyncFileReader).
+///
+/// Each row group is fetched only when the stream is polled, so only one row
+/// group's worth of items is held in memory at a time.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "async")]
+/// # {
+/// use futures::TryStreamExt;
+///
+/// # tokio_test::block_on(async {
+/// let file = tokio::fs::File::open("data/extended-item.parquet").await.unwrap();
+/// let chunks = stac::geoparquet::read_chunks_async(file).await.unwrap();
+/// let item_collections: Vec<_> = chunks.try_collect().await.unwrap();
+/// assert_eq!(item_collections.len(), 1);
+/// # })
+/// # }
+/// ```
+#[cfg(feature = "async")]
+pub async fn read_chunks_async<R>(
+    reader: R,
+) -> Result<impl futures_core::Stream<Item = Result<ItemCollection>>>
+where
+    R: parquet::arrow::async_reader::AsyncFileReader + Unpin + Send + 'static,
+{
+    use parquet::arrow::ParquetRecordBatchStreamBuilder;
+
+    let builder = ParquetRecordBatchStreamBuilder::new(reader).await?;
+    let geoparquet_metadata = builder
+        .geoparquet_metadata()
+        .transpose()?
+        .ok_or(Error::MissingGeoparquetMetadata)?;
+    let geoarrow_schema =
+        builder.geoarrow_schema(&geoparquet_metadata, true, Default::default())?;
+    let mut stream = builder.build()?;
+    Ok(async_stream::try_stream! {
+        while let Some(reader) = stream.next_row_group().await? {
+            let reader = GeoParquetRecordBatchReader::try_new(reader, geoarrow_schema.clone())?;
+            yield crate::geoarrow::from_record_batch_reader(reader)?;
+        }
+    })
+}
+
+/// Groups record batches of items back into their row groups.
+struct Chunks<I> {
+    batches: I,
+    row_group_sizes: VecDeque<usize>,
+    buffer: Vec<Item>,
+}
+
+impl<I> Iterator for Chunks<I>
+where
+    I: Iterator<Item = Result<Vec<Item>>>,
+{
+    type Item = Result<ItemCollection>;
+
+    fn next(&mut self) -> Option<Result<ItemCollection>> {
+        let size = self.row_group_sizes.pop_front()?;
+        while self.buffer.len() < size {
+            match self.batches.next() {
+                Some(Ok(items)) => self.buffer.extend(items),
+                Some(Err(err)) => return Some(Err(err)),
+                None => break,
+            }
+        }
+        let rest = self.buffer.split_off(size.min(self.buffer.len()));
+        let items = std::mem::replace(&mut self.buffer, rest);
+        Some(Ok(items.into()))
+    }
 }
 
 /// Writes a [ItemCollection] to a [std::io::Write] as
@@ -218,12 +967,104 @@ where
         .and_then(|writer| writer.finish())
 }
 
+/// Writes record batches of items to a [std::io::Write] as
+/// [stac-geoparquet](https://github.com/stac-utils/stac-geoparquet), without
+/// decoding them into [Item]s.
+///
+/// This is for record batches that already have the stac-geoparquet layout,
+/// e.g. from [crate::geoarrow::encode] or a database query, with a geoarrow
+/// `geometry` column. The schema comes from the first record batch, and
+/// nothing is written if there aren't any.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Cursor;
+/// use stac::{Item, geoparquet::WriterOptions};
+///
+/// let item: Item = stac::read("examples/simple-item.json").unwrap();
+/// let (record_batch, _) = stac::geoarrow::encode(vec![item]).unwrap();
+/// let mut cursor = Cursor::new(Vec::new());
+/// stac::geoparquet::record_batches_into_writer(
+///     &mut cursor,
+///     [Ok::<_, stac::Error>(record_batch)],
+///     WriterOptions::new(),
+/// )
+/// .unwrap();
+/// let item_collection = stac::geoparquet::from_reader(bytes::Bytes::from(cursor.into_inner())).unwrap();
+/// assert_eq!(item_collection.items.len(), 1);
+/// ```
+pub fn record_batches_into_writer<W, I, E>(
+    writer: W,
+    record_batches: I,
+    writer_options: WriterOptions,
+) -> Result<()>
+where
+    W: Write + Send,
+    I: IntoIterator<Item = std::result::Result<RecordBatch, E>>,
+    E: std::error::Error + Send + Sync + 'static,
+{
+    let mut record_batches = record_batches.into_iter();
+    let error = |err: E| Error::ArrowAdapterClient(Box::new(err));
+    let Some(record_batch) = record_batches.next().transpose().map_err(error)? else {
+        return Ok(());
+    };
+    let options = GeoParquetWriterOptionsBuilder::default()
+        .set_primary_column("geometry".to_string())
+        .build();
+    let mut encoder = GeoParquetRecordBatchEncoder::try_new(&record_batch.schema(), &options)?;
+    let record_batch = encoder.encode_record_batch(&record_batch)?;
+    let mut arrow_writer =
+        ArrowWriter::try_new(writer, record_batch.schema(), Some(writer_options.into()))?;
+    arrow_writer.write(&record_batch)?;
+    for record_batch in record_batches {
+        let record_batch = record_batch.map_err(error)?;
+        arrow_writer.write(&encoder.encode_record_batch(&record_batch)?)?;
+    }
+    let mut geo = encoder.into_keyvalue()?;
+    if writer_options.bbox_covering {
+        geo = add_bbox_covering(geo)?;
+    }
+    let metadata = Metadata::default();
+    arrow_writer.append_key_value_metadata(geo);
+    arrow_writer.append_key_value_metadata(KeyValue::new(
+        METADATA_KEY.to_string(),
+        serde_json::to_string(&metadata)?,
+    ));
+    arrow_writer
+        .append_key_value_metadata(KeyValue::new(VERSION_KEY.to_string(), metadata.version));
+    let _ = arrow_writer.finish()?;
+    Ok(())
+}
+
+/// Builder for a stac-geoparquet reader.
+#[derive(Debug)]
+pub struct ReaderBuilder<R: ChunkReader> {
+    reader: R,
+    batch_size: Option<usize>,
+}
+
+/// Reads items from stac-geoparquet lazily.
+///
+/// Items are decoded one record batch at a time, so only one batch's worth of
+/// items is held in memory.
+#[allow(missing_debug_implementations)]
+pub struct Reader {
+    batches: GeoParquetRecordBatchReader,
+    buffer: VecDeque<Item>,
+    metadata: Metadata,
+    num_rows: usize,
+}
+
 /// Builder for a stac-geoparquet writer.
 #[derive(Debug)]
 pub struct WriterBuilder<W: Write + Send> {
     writer: W,
     options: Options,
     writer_options: WriterOptions,
+    collections: Vec<Collection>,
+    key_value_metadata: Vec<(String, String)>,
+    sample: Option<Vec<Item>>,
 }
 
 /// Write items to stac-geoparquet.
@@ -234,7 +1075,22 @@ pub struct Writer<W: Write + Send> {
 }
 
 /// stac-geoparquet metadata
-#[derive(Debug, Serialize, Deserialize)]
+///
+/// Deserializes from both stac-geoparquet 1.0 metadata, which has a single
+/// `collection`, and 1.1 metadata, which has a map of `collections`.
+///
+/// # Examples
+///
+/// ```
+/// use stac::{Collection, geoparquet::Metadata};
+///
+/// let collection = Collection::new("an-id", "a description");
+/// let value = serde_json::json!({"version": "1.0.0", "collection": collection});
+/// let metadata: Metadata = serde_json::from_value(value).unwrap();
+/// assert!(metadata.collections.contains_key("an-id"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "RawMetadata")]
 pub struct Metadata {
     /// The stac-geoparquet version.
     pub version: String,
@@ -244,6 +1100,16 @@ pub struct Metadata {
     pub collections: HashMap<String, Collection>,
 }
 
+#[derive(Deserialize)]
+struct RawMetadata {
+    #[serde(default = "default_version")]
+    version: String,
+    #[serde(default)]
+    collections: HashMap<String, Collection>,
+    #[serde(default)]
+    collection: Option<Collection>,
+}
+
 impl<W: Write + Send> WriterBuilder<W> {
     /// Creates a new writer builder.
     ///
@@ -262,6 +1128,9 @@ impl<W: Write + Send> WriterBuilder<W> {
             writer,
             options: Options::default(),
             writer_options: WriterOptions::default(),
+            collections: Vec::new(),
+            key_value_metadata: Vec::new(),
+            sample: None,
         }
     }
 
@@ -309,6 +1178,98 @@ impl<W: Write + Send> WriterBuilder<W> {
         self
     }
 
+    /// Embeds a collection in the stac-geoparquet metadata, so the file is
+    /// self-describing.
+    ///
+    /// Can be called more than once to embed multiple collections. The
+    /// collection can be read back with [FromGeoparquet] or [collections].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    /// use stac::{Collection, FromGeoparquet, Item, geoparquet::WriterBuilder};
+    ///
+    /// let mut item: Item = stac::read("examples/simple-item.json").unwrap();
+    /// item.collection = Some("an-id".to_string());
+    /// let collection = Collection::from_id_and_items("an-id", &[item.clone()]);
+    /// let mut cursor = Cursor::new(Vec::new());
+    /// WriterBuilder::new(&mut cursor)
+    ///     .collection(collection.clone())
+    ///     .build(vec![item])
+    ///     .unwrap()
+    ///     .finish()
+    ///     .unwrap();
+    /// let read = Collection::from_geoparquet_bytes(cursor.into_inner()).unwrap();
+    /// assert_eq!(read.id, collection.id);
+    /// ```
+    pub fn collection(mut self, collection: Collection) -> WriterBuilder<W> {
+        self.collections.push(collection);
+        self
+    }
+
+    /// Adds custom key-value metadata to the parquet file, e.g. provenance
+    /// information.
+    ///
+    /// Keys in [RESERVED_METADATA_KEYS] are managed by the writer, and
+    /// setting one will cause [WriterBuilder::build] to return an
+    /// [Error::ReservedMetadataKey].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    /// use stac::{Item, geoparquet::WriterBuilder};
+    ///
+    /// let item: Item = stac::read("examples/simple-item.json").unwrap();
+    /// let cursor = Cursor::new(Vec::new());
+    /// let writer = WriterBuilder::new(cursor)
+    ///     .key_value_metadata("pipeline:version", "1.2.3")
+    ///     .build(vec![item])
+    ///     .unwrap();
+    /// writer.finish().unwrap();
+    /// ```
+    pub fn key_value_metadata(
+        mut self,
+        key: impl ToString,
+        value: impl ToString,
+    ) -> WriterBuilder<W> {
+        self.key_value_metadata
+            .push((key.to_string(), value.to_string()));
+        self
+    }
+
+    /// Declares the writer's schema up front from a sample of items.
+    ///
+    /// Without a sample, the schema comes from the first batch of items, and
+    /// writing a later batch with a different schema is an error. With a
+    /// sample, every batch is conformed to the sample's schema: missing
+    /// columns are filled with nulls, and columns that aren't in the sample
+    /// are dropped with a warning. This implies
+    /// [SchemaEvolution::Unify](crate::geoarrow::SchemaEvolution::Unify).
+    ///
+    /// The sample items are only used for their schema, and aren't written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    /// use stac::{Item, geoparquet::WriterBuilder};
+    ///
+    /// let mut sample = Item::new("sample");
+    /// sample.properties.additional_fields.insert("foo".into(), "bar".into());
+    /// let cursor = Cursor::new(Vec::new());
+    /// let mut writer = WriterBuilder::new(cursor)
+    ///     .sample(vec![sample])
+    ///     .build(vec![Item::new("a")])
+    ///     .unwrap();
+    /// writer.finish().unwrap();
+    /// ```
+    pub fn sample(mut self, sample: Vec<Item>) -> WriterBuilder<W> {
+        self.sample = Some(sample);
+        self
+    }
+
     /// Builds the writer.
     ///
     /// # Examples
@@ -323,7 +1284,20 @@ impl<W: Write + Send> WriterBuilder<W> {
     /// writer.finish().unwrap();
     /// ```
     pub fn build(self, items: Vec<Item>) -> Result<Writer<W>> {
-        Writer::new(self.writer, self.options, self.writer_options, items)
+        let mut writer = Writer::new(
+            self.writer,
+            self.options,
+            self.writer_options,
+            self.sample,
+            items,
+        )?;
+        for collection in self.collections {
+            writer = writer.add_collection(collection)?;
+        }
+        for (key, value) in self.key_value_metadata {
+            writer = writer.add_key_value_metadata(key, value)?;
+        }
+        Ok(writer)
     }
 }
 
@@ -341,9 +1315,10 @@ impl WriterEncoder {
     /// assert_eq!(record_batch.num_rows(), 1);
     /// ```
     pub fn new(options: Options, items: Vec<Item>) -> Result<(WriterEncoder, RecordBatch)> {
+        let primary_column = options.geometry_column.clone();
         let (geoarrow_encoder, record_batch) = Encoder::new(items, options)?;
         let options = GeoParquetWriterOptionsBuilder::default()
-            .set_primary_column("geometry".to_string())
+            .set_primary_column(primary_column)
             .build();
         let mut encoder = GeoParquetRecordBatchEncoder::try_new(&record_batch.schema(), &options)?;
         let record_batch = encoder.encode_record_batch(&record_batch)?;
@@ -356,6 +1331,33 @@ impl WriterEncoder {
         ))
     }
 
+    /// Creates a new writer encoder with the schema of a sample of items.
+    ///
+    /// See [Encoder::from_sample].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Item, geoarrow::Options, geoparquet::WriterEncoder};
+    ///
+    /// let item: Item = stac::read("examples/simple-item.json").unwrap();
+    /// let mut encoder = WriterEncoder::from_sample(Options::default(), vec![item.clone()]).unwrap();
+    /// let record_batch = encoder.encode(vec![item]).unwrap();
+    /// assert_eq!(record_batch.num_rows(), 1);
+    /// ```
+    pub fn from_sample(options: Options, sample: Vec<Item>) -> Result<WriterEncoder> {
+        let primary_column = options.geometry_column.clone();
+        let geoarrow_encoder = Encoder::from_sample(sample, options)?;
+        let options = GeoParquetWriterOptionsBuilder::default()
+            .set_primary_column(primary_column)
+            .build();
+        let encoder = GeoParquetRecordBatchEncoder::try_new(&geoarrow_encoder.schema(), &options)?;
+        Ok(WriterEncoder {
+            geoarrow_encoder,
+            encoder,
+        })
+    }
+
     /// Encodes items into a record batch.
     ///
     /// # Examples
@@ -376,6 +1378,23 @@ impl WriterEncoder {
         Ok(record_batch)
     }
 
+    /// Returns the number of non-finite numbers that have been replaced.
+    ///
+    /// See [Encoder::non_finite_count].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Item, geoarrow::Options, geoparquet::WriterEncoder};
+    ///
+    /// let item: Item = stac::read("examples/simple-item.json").unwrap();
+    /// let (encoder, _) = WriterEncoder::new(Options::default(), vec![item]).unwrap();
+    /// assert_eq!(encoder.non_finite_count(), 0);
+    /// ```
+    pub fn non_finite_count(&self) -> usize {
+        self.geoarrow_encoder.non_finite_count()
+    }
+
     /// Consumes this encoder and returns the keys and values.
     ///
     /// # Examples
@@ -403,6 +1422,8 @@ impl WriterEncoder {
 pub struct WriterState {
     encoder: WriterEncoder,
     metadata: Metadata,
+    key_value_metadata: IndexMap<String, String>,
+    bbox_covering: bool,
 }
 
 impl WriterState {
@@ -426,11 +1447,37 @@ impl WriterState {
             WriterState {
                 encoder,
                 metadata: Metadata::default(),
+                key_value_metadata: IndexMap::new(),
+                bbox_covering: false,
             },
             record_batch,
         ))
     }
 
+    /// Creates a new WriterState with the schema of a sample of items.
+    ///
+    /// Unlike [WriterState::new], no record batch is returned, since the
+    /// sample items aren't written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Item, geoarrow::Options, geoparquet::WriterState};
+    ///
+    /// let item: Item = stac::read("examples/simple-item.json").unwrap();
+    /// let mut state = WriterState::from_sample(Options::default(), vec![item.clone()]).unwrap();
+    /// let record_batch = state.encode(vec![item]).unwrap();
+    /// assert_eq!(record_batch.num_rows(), 1);
+    /// ```
+    pub fn from_sample(options: Options, sample: Vec<Item>) -> Result<WriterState> {
+        Ok(WriterState {
+            encoder: WriterEncoder::from_sample(options, sample)?,
+            metadata: Metadata::default(),
+            key_value_metadata: IndexMap::new(),
+            bbox_covering: false,
+        })
+    }
+
     /// Encodes items into a record batch.
     ///
     /// # Examples
@@ -448,6 +1495,21 @@ impl WriterState {
         self.encoder.encode(items)
     }
 
+    /// Returns the number of non-finite numbers that have been replaced.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Item, geoarrow::Options, geoparquet::WriterState};
+    ///
+    /// let item: Item = stac::read("examples/simple-item.json").unwrap();
+    /// let (state, _) = WriterState::new(Options::default(), vec![item]).unwrap();
+    /// assert_eq!(state.non_finite_count(), 0);
+    /// ```
+    pub fn non_finite_count(&self) -> usize {
+        self.encoder.non_finite_count()
+    }
+
     /// Adds a collection to the metadata.
     ///
     /// Warns and overwrites if there's already a collection with the same id.
@@ -474,10 +1536,59 @@ impl WriterState {
         }
     }
 
+    /// Sets whether the geo metadata declares the `bbox` column as the
+    /// primary geometry's bbox covering.
+    ///
+    /// See [WriterOptions::bbox_covering].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Item, geoarrow::Options, geoparquet::WriterState};
+    ///
+    /// let item: Item = stac::read("examples/simple-item.json").unwrap();
+    /// let (mut state, _) = WriterState::new(Options::default(), vec![item]).unwrap();
+    /// state.set_bbox_covering(true);
+    /// let metadata = state.into_metadata().unwrap();
+    /// assert!(metadata[0].value.as_ref().unwrap().contains("covering"));
+    /// ```
+    pub fn set_bbox_covering(&mut self, bbox_covering: bool) {
+        self.bbox_covering = bbox_covering;
+    }
+
+    /// Adds custom key-value metadata.
+    ///
+    /// Returns an [Error::ReservedMetadataKey] if the key is one of
+    /// [RESERVED_METADATA_KEYS]. Overwrites any previous value for the key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Item, geoarrow::Options, geoparquet::WriterState};
+    ///
+    /// let item: Item = stac::read("examples/simple-item.json").unwrap();
+    /// let (mut state, _) = WriterState::new(Options::default(), vec![item]).unwrap();
+    /// state.add_key_value_metadata("pipeline:version", "1.2.3").unwrap();
+    /// assert!(state.add_key_value_metadata("geo", "{}").is_err());
+    /// ```
+    pub fn add_key_value_metadata(
+        &mut self,
+        key: impl ToString,
+        value: impl ToString,
+    ) -> Result<()> {
+        let key = key.to_string();
+        if RESERVED_METADATA_KEYS.contains(&key.as_str()) {
+            return Err(Error::ReservedMetadataKey(key));
+        }
+        let _ = self.key_value_metadata.insert(key, value.to_string());
+        Ok(())
+    }
+
     /// Consumes the state and returns the metadata key-value pairs.
     ///
-    /// This returns both the geo metadata and the stac-geoparquet metadata
-    /// that should be appended to the parquet file.
+    /// This returns the geo metadata, the stac-geoparquet metadata, and the
+    /// stac-geoparquet version, followed by any custom metadata, that should
+    /// be appended to the parquet file.
     ///
     /// # Examples
     ///
@@ -487,18 +1598,29 @@ impl WriterState {
     /// let item: Item = stac::read("examples/simple-item.json").unwrap();
     /// let (state, _) = WriterState::new(Options::default(), vec![item]).unwrap();
     /// let metadata = state.into_metadata().unwrap();
-    /// assert_eq!(metadata.len(), 2); // geo + stac-geoparquet metadata
+    /// assert_eq!(metadata.len(), 3); // geo + stac-geoparquet metadata + version
     /// assert_eq!(metadata[0].key, "geo");
     /// assert_eq!(metadata[1].key, "stac-geoparquet");
+    /// assert_eq!(metadata[2].key, "stac:geoparquet_version");
     /// ```
     pub fn into_metadata(self) -> Result<Vec<KeyValue>> {
-        let metadata = vec![
-            self.encoder.into_keyvalue()?,
+        let mut geo = self.encoder.into_keyvalue()?;
+        if self.bbox_covering {
+            geo = add_bbox_covering(geo)?;
+        }
+        let mut metadata = vec![
+            geo,
             KeyValue::new(
                 METADATA_KEY.to_string(),
                 serde_json::to_string(&self.metadata)?,
             ),
+            KeyValue::new(VERSION_KEY.to_string(), self.metadata.version),
         ];
+        metadata.extend(
+            self.key_value_metadata
+                .into_iter()
+                .map(|(key, value)| KeyValue::new(key, value)),
+        );
         Ok(metadata)
     }
 }
@@ -508,9 +1630,17 @@ impl<W: Write + Send> Writer<W> {
         writer: W,
         options: Options,
         writer_options: WriterOptions,
+        sample: Option<Vec<Item>>,
         items: Vec<Item>,
     ) -> Result<Self> {
-        let (state, record_batch) = WriterState::new(options, items)?;
+        let (mut state, record_batch) = if let Some(sample) = sample {
+            let mut state = WriterState::from_sample(options, sample)?;
+            let record_batch = state.encode(items)?;
+            (state, record_batch)
+        } else {
+            WriterState::new(options, items)?
+        };
+        state.set_bbox_covering(writer_options.bbox_covering);
         let mut arrow_writer =
             ArrowWriter::try_new(writer, record_batch.schema(), Some(writer_options.into()))?;
         arrow_writer.write(&record_batch)?;
@@ -567,28 +1697,266 @@ impl<W: Write + Send> Writer<W> {
         Ok(self)
     }
 
-    /// Finishes writing.
-    ///
-    /// It's an error to call finish twice.
+    /// Adds custom key-value metadata to this writer.
+    ///
+    /// See [WriterBuilder::key_value_metadata].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Item, geoparquet::WriterBuilder};
+    /// use std::io::Cursor;
+    ///
+    /// let item: Item = stac::read("examples/simple-item.json").unwrap();
+    /// let cursor = Cursor::new(Vec::new());
+    /// let writer = WriterBuilder::new(cursor)
+    ///     .build(vec![item])
+    ///     .unwrap()
+    ///     .add_key_value_metadata("source", "https://stac.example.com")
+    ///     .unwrap();
+    /// writer.finish().unwrap();
+    /// ```
+    pub fn add_key_value_metadata(
+        mut self,
+        key: impl ToString,
+        value: impl ToString,
+    ) -> Result<Writer<W>> {
+        self.state.add_key_value_metadata(key, value)?;
+        Ok(self)
+    }
+
+    /// Returns the number of non-finite numbers that have been replaced in
+    /// the items written so far, per [Options::non_finite].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    /// use stac::{Asset, Item, geoparquet::WriterBuilder};
+    ///
+    /// let mut item: Item = stac::read("examples/simple-item.json").unwrap();
+    /// let mut asset = Asset::new("a-href");
+    /// asset.nodata = Some(f64::INFINITY);
+    /// let _ = item.assets.insert("data".to_string(), asset);
+    /// let cursor = Cursor::new(Vec::new());
+    /// let writer = WriterBuilder::new(cursor).build(vec![item]).unwrap();
+    /// assert_eq!(writer.non_finite_count(), 1);
+    /// writer.finish().unwrap();
+    /// ```
+    pub fn non_finite_count(&self) -> usize {
+        self.state.non_finite_count()
+    }
+
+    /// Finishes writing.
+    ///
+    /// It's an error to call finish twice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    /// use stac::{Item, geoparquet::WriterBuilder};
+    ///
+    /// let item: Item = stac::read("examples/simple-item.json").unwrap();
+    /// let cursor = Cursor::new(Vec::new());
+    /// let writer = WriterBuilder::new(cursor).build(vec![item]).unwrap();
+    /// writer.finish().unwrap();
+    /// ```
+    pub fn finish(mut self) -> Result<()> {
+        let metadata = self.state.into_metadata()?;
+        for kv in metadata {
+            self.arrow_writer.append_key_value_metadata(kv);
+        }
+        let _ = self.arrow_writer.finish()?;
+        Ok(())
+    }
+}
+
+impl<R: ChunkReader + 'static> ReaderBuilder<R> {
+    /// Creates a new reader builder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use stac::geoparquet::ReaderBuilder;
+    ///
+    /// let file = File::open("data/extended-item.parquet").unwrap();
+    /// let builder = ReaderBuilder::new(file);
+    /// ```
+    pub fn new(reader: R) -> ReaderBuilder<R> {
+        ReaderBuilder {
+            reader,
+            batch_size: None,
+        }
+    }
+
+    /// Sets the maximum number of rows in each record batch.
+    ///
+    /// This is the most items that are decoded, and held in memory, at a
+    /// time. If not set, the parquet reader's default is used.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use stac::geoparquet::ReaderBuilder;
+    ///
+    /// let file = File::open("data/extended-item.parquet").unwrap();
+    /// let builder = ReaderBuilder::new(file).batch_size(1024);
+    /// ```
+    pub fn batch_size(mut self, batch_size: usize) -> ReaderBuilder<R> {
+        self.batch_size = Some(batch_size);
+        self
+    }
+
+    /// Builds the reader.
+    ///
+    /// Only the file's footer is read, items are read as the reader is
+    /// iterated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use stac::geoparquet::ReaderBuilder;
+    ///
+    /// let file = File::open("data/extended-item.parquet").unwrap();
+    /// let reader = ReaderBuilder::new(file).build().unwrap();
+    /// ```
+    pub fn build(self) -> Result<Reader> {
+        let mut builder = ParquetRecordBatchReaderBuilder::try_new(self.reader)?;
+        if let Some(batch_size) = self.batch_size {
+            builder = builder.with_batch_size(batch_size);
+        }
+        let metadata = stac_geoparquet_metadata(builder.metadata())?;
+        let num_rows = usize::try_from(builder.metadata().file_metadata().num_rows())?;
+        let geoparquet_metadata = builder
+            .geoparquet_metadata()
+            .transpose()?
+            .ok_or(Error::MissingGeoparquetMetadata)?;
+        let geoarrow_schema =
+            builder.geoarrow_schema(&geoparquet_metadata, true, Default::default())?;
+        let reader = builder.build()?;
+        Ok(Reader {
+            batches: GeoParquetRecordBatchReader::try_new(reader, geoarrow_schema)?,
+            buffer: VecDeque::new(),
+            metadata,
+            num_rows,
+        })
+    }
+}
+
+impl Reader {
+    /// Returns the total number of items in the file.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use stac::geoparquet::ReaderBuilder;
+    ///
+    /// let file = File::open("data/extended-item.parquet").unwrap();
+    /// let reader = ReaderBuilder::new(file).build().unwrap();
+    /// assert_eq!(reader.num_rows(), 1);
+    /// ```
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    /// Returns the file's stac-geoparquet metadata.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use stac::geoparquet::ReaderBuilder;
+    ///
+    /// let file = File::open("data/extended-item.parquet").unwrap();
+    /// let reader = ReaderBuilder::new(file).build().unwrap();
+    /// let version = &reader.metadata().version;
+    /// ```
+    pub fn metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+
+    /// Returns the collections stored in the file's stac-geoparquet metadata.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use stac::geoparquet::ReaderBuilder;
+    ///
+    /// let file = File::open("data/extended-item.parquet").unwrap();
+    /// let reader = ReaderBuilder::new(file).build().unwrap();
+    /// let collections = reader.collections();
+    /// ```
+    pub fn collections(&self) -> &HashMap<String, Collection> {
+        &self.metadata.collections
+    }
+
+    /// Reads the next batch of items.
+    ///
+    /// Any items that were decoded but not yet yielded by iterating over
+    /// this reader are returned first. Returns `None` when there are no more
+    /// items.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs::File;
+    /// use stac::geoparquet::ReaderBuilder;
+    ///
+    /// let file = File::open("data/extended-item.parquet").unwrap();
+    /// let mut reader = ReaderBuilder::new(file).build().unwrap();
+    /// while let Some(result) = reader.next_batch() {
+    ///     let items = result.unwrap();
+    /// }
+    /// ```
+    pub fn next_batch(&mut self) -> Option<Result<Vec<Item>>> {
+        if !self.buffer.is_empty() {
+            return Some(Ok(self.buffer.drain(..).collect()));
+        }
+        let record_batch = match self.batches.next()? {
+            Ok(record_batch) => record_batch,
+            Err(err) => return Some(Err(err.into())),
+        };
+        Some(crate::geoarrow::items_from_record_batch(record_batch))
+    }
+
+    /// Converts this reader into an iterator over batches of items.
     ///
     /// # Examples
     ///
     /// ```
-    /// use std::io::Cursor;
-    /// use stac::{Item, geoparquet::WriterBuilder};
+    /// use std::fs::File;
+    /// use stac::geoparquet::ReaderBuilder;
     ///
-    /// let item: Item = stac::read("examples/simple-item.json").unwrap();
-    /// let cursor = Cursor::new(Vec::new());
-    /// let writer = WriterBuilder::new(cursor).build(vec![item]).unwrap();
-    /// writer.finish().unwrap();
+    /// let file = File::open("data/extended-item.parquet").unwrap();
+    /// let reader = ReaderBuilder::new(file).build().unwrap();
+    /// for result in reader.batches() {
+    ///     let items = result.unwrap();
+    /// }
     /// ```
-    pub fn finish(mut self) -> Result<()> {
-        let metadata = self.state.into_metadata()?;
-        for kv in metadata {
-            self.arrow_writer.append_key_value_metadata(kv);
+    pub fn batches(mut self) -> impl Iterator<Item = Result<Vec<Item>>> {
+        std::iter::from_fn(move || self.next_batch())
+    }
+}
+
+impl Iterator for Reader {
+    type Item = Result<Item>;
+
+    fn next(&mut self) -> Option<Result<Item>> {
+        loop {
+            if let Some(item) = self.buffer.pop_front() {
+                return Some(Ok(item));
+            }
+            match self.next_batch()? {
+                Ok(items) => self.buffer.extend(items),
+                Err(err) => return Some(Err(err)),
+            }
         }
-        let _ = self.arrow_writer.finish()?;
-        Ok(())
     }
 }
 
@@ -664,10 +2032,20 @@ macro_rules! impl_into_geoparquet {
 
 impl_from_geoparquet!(Item);
 impl_from_geoparquet!(Catalog);
-impl_from_geoparquet!(Collection);
 impl_into_geoparquet!(Catalog);
 impl_into_geoparquet!(Collection);
 
+impl FromGeoparquet for Collection {
+    fn from_geoparquet_bytes(bytes: impl Into<Bytes>) -> Result<Self> {
+        let mut collections = collections(bytes.into())?;
+        if collections.len() == 1 {
+            Ok(collections.remove(0))
+        } else {
+            Err(Error::CollectionCount(collections.len()))
+        }
+    }
+}
+
 impl FromGeoparquet for ItemCollection {
     fn from_geoparquet_bytes(bytes: impl Into<Bytes>) -> Result<Self> {
         let item_collection = from_reader(bytes.into())?;
@@ -734,6 +2112,14 @@ impl From<WriterOptions> for WriterProperties {
             builder = builder.set_compression(compression);
         }
         builder = builder.set_max_row_group_row_count(Some(value.max_row_group_row_count));
+        if value.bbox_covering {
+            for field in BBOX_COVERING_FIELDS {
+                builder = builder.set_column_statistics_enabled(
+                    ColumnPath::new(vec!["bbox".to_string(), field.to_string()]),
+                    EnabledStatistics::Page,
+                );
+            }
+        }
         builder.build()
     }
 }
@@ -747,11 +2133,33 @@ impl Default for Metadata {
     }
 }
 
+impl From<RawMetadata> for Metadata {
+    fn from(raw: RawMetadata) -> Metadata {
+        let mut collections = raw.collections;
+        if let Some(collection) = raw.collection {
+            let _ = collections
+                .entry(collection.id.clone())
+                .or_insert(collection);
+        }
+        Metadata {
+            version: raw.version,
+            collections,
+        }
+    }
+}
+
+fn default_version() -> String {
+    VERSION.to_string()
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{
         Collection, FromGeoparquet, Item, ItemCollection, SelfHref, Value,
-        geoparquet::{METADATA_KEY, Metadata, VERSION, WriterBuilder},
+        geoparquet::{
+            METADATA_KEY, Metadata, ReaderBuilder, VERSION, VERSION_KEY, WriterBuilder,
+            WriterOptions,
+        },
     };
     use bytes::Bytes;
     use parquet::file::reader::{FileReader, SerializedFileReader};
@@ -760,6 +2168,48 @@ mod tests {
         io::{Cursor, Read},
     };
 
+    #[test]
+    fn from_paths() {
+        let paths = ["data/extended-item.parquet", "data/opr-one.parquet"];
+        let expected: Vec<_> = paths
+            .iter()
+            .flat_map(|path| super::from_reader(File::open(path).unwrap()).unwrap().items)
+            .collect();
+        for parallelism in [0, 1, 2, 8] {
+            let item_collection = super::from_paths(paths, parallelism).unwrap();
+            assert_eq!(item_collection.items, expected);
+        }
+    }
+
+    #[test]
+    fn from_readers() {
+        let paths = [
+            "data/extended-item.parquet",
+            "data/opr-one.parquet",
+            "data/extended-item.parquet",
+            "data/opr-one.parquet",
+            "data/extended-item.parquet",
+        ];
+        let expected: Vec<_> = paths
+            .iter()
+            .flat_map(|path| super::from_reader(File::open(path).unwrap()).unwrap().items)
+            .collect();
+        for parallelism in [1, 2, 3] {
+            let readers = paths.iter().map(|path| File::open(path).unwrap()).collect();
+            let item_collection = super::from_readers(readers, parallelism).unwrap();
+            assert_eq!(item_collection.items, expected);
+        }
+    }
+
+    #[test]
+    fn from_paths_missing_file() {
+        let paths = ["data/extended-item.parquet", "data/not-a-file.parquet"];
+        assert!(matches!(
+            super::from_paths(paths, 2).unwrap_err(),
+            crate::Error::Io(_)
+        ));
+    }
+
     #[test]
     fn to_writer_item_collection() {
         let mut cursor = Cursor::new(Vec::new());
@@ -775,6 +2225,425 @@ mod tests {
         assert_eq!(item_collection.items.len(), 1);
     }
 
+    #[test]
+    fn read_chunks() {
+        let items: Vec<_> = (0..5).map(|i| Item::new(format!("item-{i}"))).collect();
+        let mut cursor = Cursor::new(Vec::new());
+        WriterBuilder::new(&mut cursor)
+            .writer_options(WriterOptions::new().with_max_row_group_row_count(2))
+            .build(items)
+            .unwrap()
+            .finish()
+            .unwrap();
+        let bytes = Bytes::from(cursor.into_inner());
+        let chunks = super::read_chunks(bytes)
+            .unwrap()
+            .collect::<crate::Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(
+            chunks
+                .iter()
+                .map(|item_collection| item_collection.items.len())
+                .collect::<Vec<_>>(),
+            vec![2, 2, 1]
+        );
+        assert_eq!(chunks[2].items[0].id, "item-4");
+    }
+
+    #[test]
+    fn reader() {
+        let items: Vec<_> = (0..5).map(|i| Item::new(format!("item-{i}"))).collect();
+        let mut cursor = Cursor::new(Vec::new());
+        WriterBuilder::new(&mut cursor)
+            .build(items)
+            .unwrap()
+            .add_collection(Collection::new("an-id", "a description"))
+            .unwrap()
+            .finish()
+            .unwrap();
+        let bytes = Bytes::from(cursor.into_inner());
+
+        let reader = ReaderBuilder::new(bytes.clone())
+            .batch_size(2)
+            .build()
+            .unwrap();
+        assert_eq!(reader.num_rows(), 5);
+        assert!(reader.collections().contains_key("an-id"));
+        let batches = reader
+            .batches()
+            .map(|result| result.unwrap().len())
+            .collect::<Vec<_>>();
+        assert_eq!(batches, vec![2, 2, 1]);
+
+        let mut reader = ReaderBuilder::new(bytes).batch_size(2).build().unwrap();
+        assert_eq!(reader.next().unwrap().unwrap().id, "item-0");
+        assert_eq!(reader.next_batch().unwrap().unwrap().len(), 1);
+        let ids = reader.map(|result| result.unwrap().id).collect::<Vec<_>>();
+        assert_eq!(ids, vec!["item-2", "item-3", "item-4"]);
+    }
+
+    #[test]
+    fn append() {
+        let mut cursor = Cursor::new(Vec::new());
+        WriterBuilder::new(&mut cursor)
+            .build(vec![Item::new("a"), Item::new("b")])
+            .unwrap()
+            .add_collection(Collection::new("a-collection", "A description"))
+            .unwrap()
+            .finish()
+            .unwrap();
+        let bytes = Bytes::from(cursor.into_inner());
+        let mut cursor = Cursor::new(Vec::new());
+        super::append(
+            bytes,
+            &mut cursor,
+            vec![Item::new("c")],
+            WriterOptions::new(),
+        )
+        .unwrap();
+        let bytes = Bytes::from(cursor.into_inner());
+        let item_collection = super::from_reader(bytes.clone()).unwrap();
+        assert_eq!(
+            item_collection
+                .items
+                .iter()
+                .map(|item| item.id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["a", "b", "c"]
+        );
+        let reader = SerializedFileReader::new(bytes).unwrap();
+        let value = reader
+            .metadata()
+            .file_metadata()
+            .key_value_metadata()
+            .unwrap()
+            .iter()
+            .find(|key_value| key_value.key == METADATA_KEY)
+            .and_then(|key_value| key_value.value.clone())
+            .unwrap();
+        let metadata: Metadata = serde_json::from_str(&value).unwrap();
+        assert!(metadata.collections.contains_key("a-collection"));
+    }
+
+    #[test]
+    fn append_copies_record_batches() {
+        use arrow_array::Array;
+        use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+        let item = |id: &str, x: f64| {
+            let mut item: Item = crate::read("examples/simple-item.json").unwrap();
+            item.id = id.to_string();
+            item.geometry = Some(crate::Geometry::new_point(vec![x, 41.1]));
+            item.bbox = Some(vec![x, 41.1, x, 41.1].try_into().unwrap());
+            item
+        };
+        let mut cursor = Cursor::new(Vec::new());
+        super::into_writer(&mut cursor, vec![item("a", -105.1), item("b", -104.1)]).unwrap();
+        let before = Bytes::from(cursor.into_inner());
+        let mut cursor = Cursor::new(Vec::new());
+        super::append(
+            before.clone(),
+            &mut cursor,
+            vec![item("c", -103.1)],
+            WriterOptions::new(),
+        )
+        .unwrap();
+        let after = Bytes::from(cursor.into_inner());
+
+        let read = |bytes: Bytes| {
+            ParquetRecordBatchReaderBuilder::try_new(bytes)
+                .unwrap()
+                .build()
+                .unwrap()
+                .next()
+                .unwrap()
+                .unwrap()
+        };
+        let (before_batch, after_batch) = (read(before), read(after.clone()));
+        assert_eq!(after_batch.num_rows(), 3);
+        assert_eq!(after_batch.schema(), before_batch.schema());
+        for (before, after) in before_batch.columns().iter().zip(after_batch.columns()) {
+            assert_eq!(before.to_data(), after.slice(0, 2).to_data());
+        }
+
+        let reader = SerializedFileReader::new(after).unwrap();
+        let geo = reader
+            .metadata()
+            .file_metadata()
+            .key_value_metadata()
+            .unwrap()
+            .iter()
+            .find(|key_value| key_value.key == "geo")
+            .and_then(|key_value| key_value.value.clone())
+            .unwrap();
+        let geo: serde_json::Value = serde_json::from_str(&geo).unwrap();
+        assert_eq!(geo["columns"]["geometry"]["bbox"][0], -105.1);
+        assert_eq!(geo["columns"]["geometry"]["bbox"][2], -103.1);
+    }
+
+    #[test]
+    fn bbox_covering() {
+        let item: Item = crate::read("examples/simple-item.json").unwrap();
+        let mut cursor = Cursor::new(Vec::new());
+        WriterBuilder::new(&mut cursor)
+            .writer_options(WriterOptions::new().with_bbox_covering(true))
+            .build(vec![item])
+            .unwrap()
+            .finish()
+            .unwrap();
+        let reader = SerializedFileReader::new(Bytes::from(cursor.into_inner())).unwrap();
+        let value = reader
+            .metadata()
+            .file_metadata()
+            .key_value_metadata()
+            .unwrap()
+            .iter()
+            .find(|key_value| key_value.key == "geo")
+            .and_then(|key_value| key_value.value.clone())
+            .unwrap();
+        let geo: serde_json::Value = serde_json::from_str(&value).unwrap();
+        assert_eq!(
+            geo["columns"]["geometry"]["covering"]["bbox"]["xmin"],
+            serde_json::json!(["bbox", "xmin"])
+        );
+        let row_group = reader.metadata().row_group(0);
+        let column = row_group
+            .columns()
+            .iter()
+            .find(|column| column.column_path().string() == "bbox.xmin")
+            .unwrap();
+        assert!(column.statistics().is_some());
+    }
+
+    #[test]
+    fn key_value_metadata() {
+        let mut cursor = Cursor::new(Vec::new());
+        WriterBuilder::new(&mut cursor)
+            .key_value_metadata("pipeline:version", "1.2.3")
+            .build(vec![Item::new("a")])
+            .unwrap()
+            .add_collection(Collection::new("a-collection", "A description"))
+            .unwrap()
+            .add_key_value_metadata("source", "https://stac.example.com")
+            .unwrap()
+            .finish()
+            .unwrap();
+        let bytes = Bytes::from(cursor.into_inner());
+        let info = super::info(bytes.clone()).unwrap();
+        assert_eq!(info.num_items, 1);
+        assert_eq!(info.num_row_groups, 1);
+        assert_eq!(info.version.as_deref(), Some(VERSION));
+        assert_eq!(info.collections, vec!["a-collection"]);
+        assert_eq!(
+            info.key_value_metadata
+                .iter()
+                .map(|(key, value)| (key.as_str(), value.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("pipeline:version", "1.2.3"),
+                ("source", "https://stac.example.com")
+            ]
+        );
+
+        let mut cursor = Cursor::new(Vec::new());
+        super::append(
+            bytes,
+            &mut cursor,
+            vec![Item::new("b")],
+            WriterOptions::new(),
+        )
+        .unwrap();
+        let info = super::info(Bytes::from(cursor.into_inner())).unwrap();
+        assert_eq!(info.num_items, 2);
+        assert_eq!(info.key_value_metadata["pipeline:version"], "1.2.3");
+        assert_eq!(
+            info.key_value_metadata["source"],
+            "https://stac.example.com"
+        );
+    }
+
+    #[test]
+    fn embedded_collection() {
+        let mut item: Item = crate::read("examples/simple-item.json").unwrap();
+        item.collection = Some("an-id".to_string());
+        let collection = Collection::from_id_and_items("an-id", &[item.clone()]);
+        let mut cursor = Cursor::new(Vec::new());
+        WriterBuilder::new(&mut cursor)
+            .collection(collection.clone())
+            .build(vec![item])
+            .unwrap()
+            .finish()
+            .unwrap();
+        let bytes = cursor.into_inner();
+        assert_eq!(
+            Collection::from_geoparquet_bytes(bytes.clone()).unwrap(),
+            collection
+        );
+        let item_collection = ItemCollection::from_geoparquet_bytes(bytes).unwrap();
+        assert_eq!(item_collection.items.len(), 1);
+    }
+
+    #[test]
+    fn collection_count() {
+        let mut cursor = Cursor::new(Vec::new());
+        super::into_writer(&mut cursor, vec![Item::new("a")]).unwrap();
+        let error = Collection::from_geoparquet_bytes(cursor.into_inner()).unwrap_err();
+        assert!(matches!(error, crate::Error::CollectionCount(0)));
+
+        let mut cursor = Cursor::new(Vec::new());
+        WriterBuilder::new(&mut cursor)
+            .collection(Collection::new("a", "A"))
+            .collection(Collection::new("b", "B"))
+            .build(vec![Item::new("a")])
+            .unwrap()
+            .finish()
+            .unwrap();
+        let bytes = Bytes::from(cursor.into_inner());
+        assert_eq!(
+            super::collections(bytes.clone())
+                .unwrap()
+                .into_iter()
+                .map(|collection| collection.id)
+                .collect::<Vec<_>>(),
+            vec!["a", "b"]
+        );
+        let error = Collection::from_geoparquet_bytes(bytes).unwrap_err();
+        assert!(matches!(error, crate::Error::CollectionCount(2)));
+    }
+
+    #[test]
+    fn reserved_key_value_metadata() {
+        for key in super::RESERVED_METADATA_KEYS {
+            let result = WriterBuilder::new(Cursor::new(Vec::new()))
+                .key_value_metadata(key, "nope")
+                .build(vec![Item::new("a")]);
+            assert!(
+                matches!(result, Err(crate::Error::ReservedMetadataKey(ref k)) if k == key),
+                "{key}"
+            );
+        }
+    }
+
+    #[test]
+    fn append_schema_mismatch() {
+        let mut cursor = Cursor::new(Vec::new());
+        super::into_writer(&mut cursor, vec![Item::new("a")]).unwrap();
+        let bytes = Bytes::from(cursor.into_inner());
+        let mut item = Item::new("b");
+        let _ = item
+            .properties
+            .additional_fields
+            .insert("foo".to_string(), "bar".into());
+        let error = super::append(
+            bytes,
+            Cursor::new(Vec::new()),
+            vec![item],
+            WriterOptions::new(),
+        )
+        .unwrap_err();
+        assert!(matches!(error, crate::Error::ArrowSchemaMismatch));
+    }
+
+    #[test]
+    fn sample() {
+        let mut item = Item::new("c");
+        let _ = item
+            .properties
+            .additional_fields
+            .insert("foo".to_string(), "bar".into());
+        let mut cursor = Cursor::new(Vec::new());
+        let mut writer = WriterBuilder::new(&mut cursor)
+            .sample(vec![item.clone()])
+            .build(vec![Item::new("a"), Item::new("b")])
+            .unwrap();
+        writer.write(vec![item]).unwrap();
+        writer.finish().unwrap();
+        let item_collection = ItemCollection::from_geoparquet_bytes(cursor.into_inner()).unwrap();
+        assert_eq!(item_collection.items.len(), 3);
+        assert_eq!(
+            item_collection.items[2].properties.additional_fields["foo"],
+            "bar"
+        );
+        assert!(
+            !item_collection.items[0]
+                .properties
+                .additional_fields
+                .contains_key("foo")
+        );
+    }
+
+    #[test]
+    fn upsert() {
+        let mut cursor = Cursor::new(Vec::new());
+        WriterBuilder::new(&mut cursor)
+            .writer_options(WriterOptions::new().with_max_row_group_row_count(2))
+            .build(vec![Item::new("a"), Item::new("b"), Item::new("c")])
+            .unwrap()
+            .add_collection(Collection::new("a-collection", "A description"))
+            .unwrap()
+            .finish()
+            .unwrap();
+        let bytes = Bytes::from(cursor.into_inner());
+        let mut updated = Item::new("c");
+        updated.properties.title = Some("Updated".to_string());
+        let mut cursor = Cursor::new(Vec::new());
+        let summary = super::upsert(
+            bytes,
+            &mut cursor,
+            vec![Item::new("d"), updated],
+            vec!["a".to_string(), "not-an-item".to_string()],
+            WriterOptions::new(),
+        )
+        .unwrap();
+        assert_eq!(summary.inserted, vec!["d"]);
+        assert_eq!(summary.updated, vec!["c"]);
+        assert_eq!(summary.deleted, vec!["a"]);
+        let item_collection = super::from_reader(Bytes::from(cursor.into_inner())).unwrap();
+        assert_eq!(
+            item_collection
+                .items
+                .iter()
+                .map(|item| item.id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["b", "c", "d"]
+        );
+        assert_eq!(
+            item_collection.items[1].properties.title.as_deref(),
+            Some("Updated")
+        );
+    }
+
+    #[test]
+    fn upsert_conflict() {
+        let mut cursor = Cursor::new(Vec::new());
+        super::into_writer(&mut cursor, vec![Item::new("a")]).unwrap();
+        let error = super::upsert(
+            Bytes::from(cursor.into_inner()),
+            Cursor::new(Vec::new()),
+            vec![Item::new("a")],
+            vec!["a".to_string()],
+            WriterOptions::new(),
+        )
+        .unwrap_err();
+        assert!(matches!(error, crate::Error::UpsertConflict(ref id) if id == "a"));
+    }
+
+    #[test]
+    fn upsert_delete_everything() {
+        let mut cursor = Cursor::new(Vec::new());
+        super::into_writer(&mut cursor, vec![Item::new("a")]).unwrap();
+        let bytes = Bytes::from(cursor.into_inner());
+        let error = super::upsert(
+            bytes,
+            Cursor::new(Vec::new()),
+            Vec::new(),
+            vec!["a".to_string()],
+            WriterOptions::new(),
+        )
+        .unwrap_err();
+        assert!(matches!(error, crate::Error::UpsertDeletesEverything));
+    }
+
     #[test]
     fn roundtrip() {
         let mut item: Item = crate::read("examples/simple-item.json").unwrap();
@@ -817,6 +2686,39 @@ mod tests {
         assert_eq!(value["primary_column"], "geometry");
     }
 
+    #[test]
+    fn wkb_geometry_column() {
+        let item: Item = crate::read("examples/simple-item.json").unwrap();
+        let options = crate::geoarrow::Options {
+            geometry_encoding: crate::geoarrow::GeometryEncoding::Wkb,
+            geometry_column: "geom".to_string(),
+            ..Default::default()
+        };
+        let mut cursor = Cursor::new(Vec::new());
+        WriterBuilder::new(&mut cursor)
+            .options(options)
+            .build(vec![item.clone()])
+            .unwrap()
+            .finish()
+            .unwrap();
+        let bytes = Bytes::from(cursor.into_inner());
+        let reader = SerializedFileReader::new(bytes.clone()).unwrap();
+        let key_value = reader
+            .metadata()
+            .file_metadata()
+            .key_value_metadata()
+            .unwrap()
+            .iter()
+            .find(|key_value| key_value.key == "geo")
+            .unwrap();
+        let value: serde_json::Value =
+            serde_json::from_str(key_value.value.as_deref().unwrap()).unwrap();
+        assert_eq!(value["primary_column"], "geom");
+        assert_eq!(value["columns"]["geom"]["encoding"], "WKB");
+        let item_collection = super::from_reader(bytes).unwrap();
+        assert_eq!(item_collection.items[0].geometry, item.geometry);
+    }
+
     #[test]
     fn from_bytes() {
         let mut buf = Vec::new();
@@ -956,6 +2858,41 @@ mod tests {
         assert_eq!(metadata.collections["an-id"].description, "a description");
     }
 
+    #[test]
+    fn version_key() {
+        let mut cursor = Cursor::new(Vec::new());
+        WriterBuilder::new(&mut cursor)
+            .build(vec![Item::new("an-id")])
+            .unwrap()
+            .finish()
+            .unwrap();
+        let bytes = Bytes::from(cursor.into_inner());
+        let reader = SerializedFileReader::new(bytes.clone()).unwrap();
+        let version = reader
+            .metadata()
+            .file_metadata()
+            .key_value_metadata()
+            .unwrap()
+            .iter()
+            .find(|key_value| key_value.key == VERSION_KEY)
+            .and_then(|key_value| key_value.value.clone())
+            .unwrap();
+        assert_eq!(version, VERSION);
+        assert_eq!(super::metadata(bytes).unwrap().version, VERSION);
+    }
+
+    #[test]
+    fn v1_0_metadata() {
+        let collection = Collection::new("an-id", "a description");
+        let value = serde_json::json!({
+            "version": "1.0.0",
+            "collection": collection,
+        });
+        let metadata: Metadata = serde_json::from_value(value).unwrap();
+        assert_eq!(metadata.version, "1.0.0");
+        assert_eq!(metadata.collections["an-id"], collection);
+    }
+
     #[test]
     fn links_as_integer_list() {
         // https://github.com/stac-utils/rustac/issues/959
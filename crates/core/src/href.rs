@@ -3,9 +3,41 @@
 #[cfg(feature = "std")]
 use crate::Error;
 use crate::Result;
-use std::borrow::Cow;
+use std::{borrow::Cow, fmt::Display};
 use url::Url;
 
+/// Url schemes that are treated as object store locations.
+const OBJECT_STORE_SCHEMES: [&str; 8] = ["s3", "s3a", "gs", "gcs", "az", "azure", "abfs", "abfss"];
+
+/// A STAC href, which can be a url, a local path, or an object store location.
+///
+/// Unlike a raw string, an [Href] knows how to join relative hrefs and find
+/// its parent regardless of what kind of href it is.
+///
+/// # Examples
+///
+/// ```
+/// use stac::href::Href;
+///
+/// let href = Href::from("s3://bucket/catalog/catalog.json");
+/// assert!(matches!(href, Href::ObjectStorePath(_)));
+/// assert_eq!(
+///     href.join("./item/item.json").unwrap().to_string(),
+///     "s3://bucket/catalog/item/item.json"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Href {
+    /// A url, e.g. `https://stac.test/catalog.json` or `file:///data/catalog.json`.
+    Url(Url),
+
+    /// A local filesystem path, which may be relative.
+    LocalPath(String),
+
+    /// An object store location, e.g. `s3://bucket/catalog.json`.
+    ObjectStorePath(Url),
+}
+
 /// Implemented by all three STAC objects, the [SelfHref] trait allows getting
 /// and setting an object's href.
 ///
@@ -59,6 +91,216 @@ pub trait SelfHref {
     }
 }
 
+impl Href {
+    /// Returns this href as a string slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::href::Href;
+    ///
+    /// assert_eq!(Href::from("./item.json").as_str(), "./item.json");
+    /// ```
+    pub fn as_str(&self) -> &str {
+        match self {
+            Href::Url(url) | Href::ObjectStorePath(url) => url.as_str(),
+            Href::LocalPath(path) => path,
+        }
+    }
+
+    /// Returns true if this href is absolute.
+    ///
+    /// Urls and object store locations are always absolute.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::href::Href;
+    ///
+    /// assert!(Href::from("/data/item.json").is_absolute());
+    /// assert!(!Href::from("./item.json").is_absolute());
+    /// ```
+    pub fn is_absolute(&self) -> bool {
+        match self {
+            Href::Url(_) | Href::ObjectStorePath(_) => true,
+            Href::LocalPath(path) => is_absolute(path),
+        }
+    }
+
+    /// Resolves an href relative to this one.
+    ///
+    /// As with urls, this href is treated as a directory only if it ends with a
+    /// `/`. Absolute hrefs are returned unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::href::Href;
+    ///
+    /// let href = Href::from("/data/catalog.json");
+    /// assert_eq!(href.join("./item.json").unwrap().as_str(), "/data/item.json");
+    /// let href = Href::from("http://stac.test/catalog/");
+    /// assert_eq!(href.join("item.json").unwrap().as_str(), "http://stac.test/catalog/item.json");
+    /// ```
+    pub fn join(&self, href: &str) -> Result<Href> {
+        if is_absolute(href) {
+            return Ok(href.into());
+        }
+        match self {
+            Href::Url(url) => Ok(Href::Url(url.join(href)?)),
+            Href::ObjectStorePath(url) => Ok(Href::ObjectStorePath(url.join(href)?)),
+            Href::LocalPath(path) => {
+                let (base, _) = path.split_at(path.rfind('/').unwrap_or(0));
+                let joined = if base.is_empty() {
+                    normalize_path(href)
+                } else {
+                    normalize_path(&format!("{base}/{href}"))
+                };
+                if is_windows_absolute_path(path) {
+                    Ok(Href::LocalPath(joined.trim_start_matches('/').to_string()))
+                } else {
+                    Ok(Href::LocalPath(joined))
+                }
+            }
+        }
+    }
+
+    /// Returns the directory that contains this href, with a trailing slash.
+    ///
+    /// Returns `None` if this href is already at the root, or if it's a bare
+    /// relative file name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::href::Href;
+    ///
+    /// let href = Href::from("s3://bucket/catalog/item.json");
+    /// assert_eq!(href.parent().unwrap().as_str(), "s3://bucket/catalog/");
+    /// assert_eq!(Href::from("/data/").parent().unwrap().as_str(), "/");
+    /// assert!(Href::from("item.json").parent().is_none());
+    /// ```
+    pub fn parent(&self) -> Option<Href> {
+        match self {
+            Href::Url(url) | Href::ObjectStorePath(url) => {
+                let path = url.path().trim_end_matches('/');
+                let index = path.rfind('/')?;
+                let mut parent = url.clone();
+                parent.set_path(&path[..=index]);
+                parent.set_query(None);
+                parent.set_fragment(None);
+                if let Href::Url(_) = self {
+                    Some(Href::Url(parent))
+                } else {
+                    Some(Href::ObjectStorePath(parent))
+                }
+            }
+            Href::LocalPath(path) => {
+                let path = path.trim_end_matches('/');
+                let index = path.rfind('/')?;
+                Some(Href::LocalPath(path[..=index].to_string()))
+            }
+        }
+    }
+
+    /// Returns the last segment of this href, if it isn't a directory.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::href::Href;
+    ///
+    /// assert_eq!(Href::from("http://stac.test/item.json?foo=bar").file_name(), Some("item.json"));
+    /// assert_eq!(Href::from("/data/").file_name(), None);
+    /// ```
+    pub fn file_name(&self) -> Option<&str> {
+        let path = match self {
+            Href::Url(url) | Href::ObjectStorePath(url) => url.path(),
+            Href::LocalPath(path) => path,
+        };
+        path.rsplit('/').next().filter(|name| !name.is_empty())
+    }
+
+    /// Makes this href relative to a base.
+    ///
+    /// Returns `None` if this href can't be expressed relative to the base,
+    /// e.g. because they're urls with different schemes or hosts, or because
+    /// one is a local path and the other is a url or object store location.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::href::Href;
+    ///
+    /// let href = Href::from("http://stac.test/catalog/item.json");
+    /// assert_eq!(
+    ///     href.make_relative(&Href::from("http://stac.test/catalog.json")).unwrap(),
+    ///     "./catalog/item.json"
+    /// );
+    /// assert!(href.make_relative(&Href::from("http://other.test/catalog.json")).is_none());
+    /// assert!(href.make_relative(&Href::from("/data/catalog.json")).is_none());
+    /// ```
+    pub fn make_relative(&self, base: &Href) -> Option<String> {
+        let shares_root = match (self, base) {
+            (Href::Url(href), Href::Url(base))
+            | (Href::ObjectStorePath(href), Href::ObjectStorePath(base)) => {
+                href.scheme() == base.scheme()
+                    && href.host_str() == base.host_str()
+                    && href.port() == base.port()
+            }
+            (Href::LocalPath(href), Href::LocalPath(base)) => {
+                is_absolute(href) == is_absolute(base)
+            }
+            _ => false,
+        };
+        if shares_root {
+            Some(relative_path(self.as_str(), base.as_str()))
+        } else {
+            None
+        }
+    }
+}
+
+impl From<&str> for Href {
+    fn from(s: &str) -> Href {
+        if is_windows_absolute_path(s) {
+            return Href::LocalPath(s.to_string());
+        }
+        match Url::parse(s) {
+            Ok(url) => url.into(),
+            Err(_) => Href::LocalPath(s.to_string()),
+        }
+    }
+}
+
+impl From<String> for Href {
+    fn from(s: String) -> Href {
+        s.as_str().into()
+    }
+}
+
+impl From<Url> for Href {
+    fn from(url: Url) -> Href {
+        if OBJECT_STORE_SCHEMES.contains(&url.scheme()) {
+            Href::ObjectStorePath(url)
+        } else {
+            Href::Url(url)
+        }
+    }
+}
+
+impl AsRef<str> for Href {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl Display for Href {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 /// Returns `true` if the href looks like a Windows absolute path (e.g. `C:\foo` or `D:/bar`).
 pub fn is_windows_absolute_path(s: &str) -> bool {
     let bytes = s.as_bytes();
@@ -88,21 +330,34 @@ pub fn is_absolute(href: &str) -> bool {
 pub fn make_absolute<'a>(href: &'a str, base: &str) -> Result<Cow<'a, str>> {
     if is_absolute(href) {
         Ok(href.into())
-    } else if let Ok(url) = Url::parse(base) {
-        let url = url.join(href)?;
-        Ok(url.to_string().into())
     } else {
-        let (base, _) = base.split_at(base.rfind('/').unwrap_or(0));
-        if base.is_empty() {
-            Ok(normalize_path(href).into())
-        } else {
-            Ok(normalize_path(&format!("{base}/{href}")).into())
-        }
+        Ok(Href::from(base).join(href)?.to_string().into())
     }
 }
 
 /// Makes an href relative to a base.
+///
+/// Hrefs that can't be made relative to the base (see [Href::make_relative])
+/// are returned unchanged.
+///
+/// # Examples
+///
+/// ```
+/// use stac::href::make_relative;
+///
+/// assert_eq!(make_relative("/a/b/item.json", "/a/catalog.json"), "./b/item.json");
+/// assert_eq!(
+///     make_relative("https://stac.test/item.json", "/a/catalog.json"),
+///     "https://stac.test/item.json"
+/// );
+/// ```
 pub fn make_relative(href: &str, base: &str) -> String {
+    Href::from(href)
+        .make_relative(&Href::from(base))
+        .unwrap_or_else(|| href.to_string())
+}
+
+fn relative_path(href: &str, base: &str) -> String {
     // Cribbed from `Url::make_relative`
     let mut relative = String::new();
 
@@ -199,3 +454,55 @@ fn normalize_path(path: &str) -> String {
     }
     parts.join("/")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Href, make_relative};
+
+    #[test]
+    fn make_relative_same_root() {
+        assert_eq!(
+            make_relative(
+                "http://stac.test/a/b/item.json",
+                "http://stac.test/a/c/catalog.json"
+            ),
+            "../b/item.json"
+        );
+        assert_eq!(
+            make_relative("s3://bucket/a/item.json", "s3://bucket/catalog.json"),
+            "./a/item.json"
+        );
+        assert_eq!(
+            make_relative("a/item.json", "catalog.json"),
+            "./a/item.json"
+        );
+    }
+
+    #[test]
+    fn make_relative_mixed_schemes() {
+        for (href, base) in [
+            (
+                "https://stac.test/item.json",
+                "http://stac.test/catalog.json",
+            ),
+            (
+                "http://stac.test/item.json",
+                "http://other.test/catalog.json",
+            ),
+            (
+                "http://stac.test:8080/item.json",
+                "http://stac.test/catalog.json",
+            ),
+            ("s3://bucket/item.json", "gs://bucket/catalog.json"),
+            ("s3://bucket/item.json", "s3://other-bucket/catalog.json"),
+            ("s3://bucket/item.json", "https://bucket/catalog.json"),
+            ("https://stac.test/item.json", "/data/catalog.json"),
+            ("/data/item.json", "https://stac.test/catalog.json"),
+            ("/data/item.json", "s3://bucket/catalog.json"),
+            ("/data/item.json", "catalog.json"),
+        ] {
+            assert_eq!(make_relative(href, base), href, "{href} relative to {base}");
+            assert!(Href::from(href).make_relative(&Href::from(base)).is_none());
+        }
+    }
+}
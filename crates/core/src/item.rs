@@ -1,8 +1,8 @@
 //! STAC Items.
 
 use crate::{
-    Asset, Assets, Bbox, Error, Fields, Link, Result, STAC_VERSION, Version,
-    datetime::parse_datetime_permissively,
+    Asset, Assets, Bbox, Error, Fields, Link, Result, STAC_VERSION, Version, audit::FieldAudit,
+    create::Raster, datetime::parse_datetime_permissively, href::Href,
 };
 use chrono::{DateTime, Utc};
 use cql2::Expr;
@@ -11,9 +11,9 @@ use indexmap::IndexMap;
 use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::{Map, Value};
 use stac_derive::{Links, Migrate, SelfHref};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-const TOP_LEVEL_ATTRIBUTES: [&str; 8] = [
+pub(crate) const TOP_LEVEL_ATTRIBUTES: [&str; 8] = [
     "type",
     "stac_extensions",
     "id",
@@ -380,6 +380,34 @@ impl Item {
         }
     }
 
+    /// Creates an item from a GeoTIFF on the local filesystem.
+    ///
+    /// The item's id is the file's stem, and the GeoTIFF is added as its
+    /// `data` asset. See [create](crate::create) for which fields are set.
+    /// For remote files, see the **stac-io** crate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::Item;
+    ///
+    /// let item = Item::from_href("assets/dataset_geo.tif").unwrap();
+    /// assert_eq!(item.id, "dataset_geo");
+    /// assert_eq!(item.assets["data"].href, "assets/dataset_geo.tif");
+    /// ```
+    pub fn from_href(href: impl ToString) -> Result<Item> {
+        let href = href.to_string();
+        let Href::LocalPath(path) = Href::from(href.as_str()) else {
+            return Err(Error::InvalidFilePath(href));
+        };
+        let path = PathBuf::from(path);
+        let id = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .ok_or_else(|| Error::InvalidFilePath(href.clone()))?;
+        Ok(Raster::from_path(&path)?.to_item(id, href))
+    }
+
     /// Sets this item's collection id in the builder pattern.
     ///
     /// # Examples
@@ -408,9 +436,29 @@ impl Item {
         self.links.iter().find(|link| link.is_collection())
     }
 
+    /// Returns this item's bounding box as a polygon geometry.
+    ///
+    /// Useful for items that have a bbox but no geometry, e.g. to search them
+    /// spatially. Returns `None` if this item has no bbox.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Bbox, Item};
+    ///
+    /// let mut item = Item::new("an-id");
+    /// assert!(item.geometry_from_bbox().is_none());
+    /// item.bbox = Some(Bbox::new(-106.0, 41.0, -105.0, 42.0));
+    /// let geometry = item.geometry_from_bbox().unwrap();
+    /// ```
+    pub fn geometry_from_bbox(&self) -> Option<Geometry> {
+        self.bbox.map(|bbox| bbox.to_geometry())
+    }
+
     /// Sets this item's geometry.
     ///
-    /// Also sets this item's bounding box.
+    /// Also sets this item's bounding box. If the geometry's positions have
+    /// elevations, the bounding box will be three-dimensional.
     ///
     /// # Examples
     ///
@@ -427,17 +475,147 @@ impl Item {
         use geo::BoundingRect;
 
         let geometry = geometry.into();
+        let value = serde_json::to_value(&geometry)?;
         self.bbox = geometry
             .as_ref()
             .and_then(|geometry| geo::Geometry::try_from(geometry).ok())
             .and_then(|geometry| geometry.bounding_rect())
-            .map(Bbox::from);
-        self.geometry = serde_json::from_value(serde_json::to_value(geometry)?)?;
+            .map(|rect| match elevation_range(&value) {
+                Some((zmin, zmax)) => Bbox::ThreeDimensional([
+                    rect.min().x,
+                    rect.min().y,
+                    zmin,
+                    rect.max().x,
+                    rect.max().y,
+                    zmax,
+                ]),
+                None => Bbox::from(rect),
+            });
+        self.geometry = serde_json::from_value(value)?;
         Ok(())
     }
 
+    /// Recomputes this item's bounding box from its geometry.
+    ///
+    /// Use this after changing the geometry in place, e.g. by editing its
+    /// coordinates. Items without a geometry keep their bounding box.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Bbox, Item};
+    /// use geojson::Geometry;
+    ///
+    /// let mut item = Item::new("an-id");
+    /// item.geometry = Some(Geometry::new_point(vec![-105.1, 41.1]));
+    /// item.bbox = Some(Bbox::new(0.0, 0.0, 1.0, 1.0));
+    /// item.update_bbox().unwrap();
+    /// assert_eq!(item.bbox.unwrap(), Bbox::new(-105.1, 41.1, -105.1, 41.1));
+    /// ```
+    #[cfg(feature = "geo")]
+    pub fn update_bbox(&mut self) -> Result<()> {
+        match self.geometry.take() {
+            Some(geometry) => self.set_geometry(geometry),
+            None => Ok(()),
+        }
+    }
+
+    /// Sets this item's geometry and bbox from its projected geometry.
+    ///
+    /// Reads the `proj:geometry` property from the [projection
+    /// extension](https://github.com/stac-extensions/projection), and its crs
+    /// from `proj:code` (or `proj:epsg`, from older versions of the
+    /// extension), and reprojects it to CRS84 with
+    /// [crs::geometry_to_crs84](crate::crs::geometry_to_crs84). This fixes up
+    /// items that only have a valid projected geometry. Reprojecting from
+    /// anything other than EPSG:4326 requires the `proj` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::Item;
+    /// use serde_json::json;
+    ///
+    /// let mut item = Item::new("an-id");
+    /// let _ = item.properties.additional_fields.insert("proj:code".into(), "EPSG:4326".into());
+    /// let _ = item.properties.additional_fields.insert(
+    ///     "proj:geometry".into(),
+    ///     json!({"type": "Point", "coordinates": [41.1, -105.1]}),
+    /// );
+    /// item.set_geometry_from_proj().unwrap();
+    /// assert_eq!(item.bbox.unwrap(), vec![-105.1, 41.1, -105.1, 41.1].try_into().unwrap());
+    /// ```
+    #[cfg(feature = "geo")]
+    pub fn set_geometry_from_proj(&mut self) -> Result<()> {
+        let fields = &self.properties.additional_fields;
+        let geometry: Geometry = serde_json::from_value(
+            fields
+                .get("proj:geometry")
+                .cloned()
+                .ok_or(Error::MissingField("proj:geometry"))?,
+        )?;
+        let crs = match (fields.get("proj:code"), fields.get("proj:epsg")) {
+            (Some(Value::String(code)), _) => code.clone(),
+            (_, Some(Value::Number(epsg))) => format!("EPSG:{epsg}"),
+            _ => return Err(Error::MissingField("proj:code")),
+        };
+        let geometry = crate::crs::geometry_to_crs84(geometry, &crs)?;
+        self.set_geometry(geometry)
+    }
+
+    /// Rewinds this item's polygon rings to follow the [RFC
+    /// 7946](https://datatracker.ietf.org/doc/html/rfc7946#section-3.1.6)
+    /// winding order (counterclockwise exteriors, clockwise holes).
+    ///
+    /// Returns `true` if the geometry was changed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::Item;
+    ///
+    /// let mut item: Item = stac::read("examples/simple-item.json").unwrap();
+    /// assert!(!item.normalize_winding_order().unwrap());
+    /// ```
+    pub fn normalize_winding_order(&mut self) -> Result<bool> {
+        if let Some(geometry) = self.geometry.as_ref() {
+            let mut value = serde_json::to_value(geometry)?;
+            if crate::winding::normalize(&mut value) {
+                self.geometry = Some(serde_json::from_value(value)?);
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Returns true if this item's geometry follows the [RFC
+    /// 7946](https://datatracker.ietf.org/doc/html/rfc7946#section-3.1.6)
+    /// winding order.
+    ///
+    /// Items without a geometry, or with non-polygonal geometries, always pass.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::Item;
+    ///
+    /// let item: Item = stac::read("examples/simple-item.json").unwrap();
+    /// assert!(item.has_rfc7946_winding_order().unwrap());
+    /// ```
+    pub fn has_rfc7946_winding_order(&self) -> Result<bool> {
+        if let Some(geometry) = self.geometry.as_ref() {
+            let value = serde_json::to_value(geometry)?;
+            Ok(crate::winding::is_normalized(&value))
+        } else {
+            Ok(true)
+        }
+    }
+
     /// Returns true if this item's geometry intersects the provided geojson geometry.
     ///
+    /// If this item has no geometry, its bbox is used instead (see
+    /// [Item::geometry_from_bbox]). Items with neither never intersect.
+    ///
     /// # Examples
     ///
     /// ```
@@ -458,7 +636,7 @@ impl Item {
     where
         T: geo::Intersects<geo::Geometry>,
     {
-        match self.geometry.clone() {
+        match self.geometry.clone().or_else(|| self.geometry_from_bbox()) {
             Some(geometry) => {
                 let geometry: geo::Geometry = geometry.try_into().map_err(Box::new)?;
                 Ok(intersects.intersects(&geometry))
@@ -569,6 +747,36 @@ impl Item {
     /// let flat_item = item.into_flat_item(true).unwrap();
     /// ```
     pub fn into_flat_item(self, drop_invalid_attributes: bool) -> Result<FlatItem> {
+        self.flatten(drop_invalid_attributes, None)
+    }
+
+    /// Converts this item into a [FlatItem], dropping invalid attributes and
+    /// recording them in an audit.
+    ///
+    /// Properties that conflict with top-level field names are recorded as
+    /// `properties.{key}`, and out-of-spec top-level fields are recorded by
+    /// their key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Item, audit::FieldAudit};
+    ///
+    /// let mut item = Item::new("an-id");
+    /// let _ = item.additional_fields.insert("vendor_field".into(), 42.into());
+    /// let mut audit = FieldAudit::new();
+    /// let flat_item = item.into_flat_item_with_audit(&mut audit).unwrap();
+    /// assert_eq!(audit.count("vendor_field"), 1);
+    /// ```
+    pub fn into_flat_item_with_audit(self, audit: &mut FieldAudit) -> Result<FlatItem> {
+        self.flatten(true, Some(audit))
+    }
+
+    pub(crate) fn flatten(
+        self,
+        drop_invalid_attributes: bool,
+        mut audit: Option<&mut FieldAudit>,
+    ) -> Result<FlatItem> {
         let properties = match serde_json::to_value(self.properties)? {
             Value::Object(object) => object,
             _ => {
@@ -579,6 +787,9 @@ impl Item {
             if TOP_LEVEL_ATTRIBUTES.contains(&key.as_str()) {
                 if drop_invalid_attributes {
                     log::warn!("dropping invalid property: {key}");
+                    if let Some(audit) = audit.as_deref_mut() {
+                        audit.record(format!("properties.{key}"));
+                    }
                 } else {
                     return Err(Error::InvalidAttribute(key.to_string()));
                 }
@@ -587,6 +798,9 @@ impl Item {
         for (key, _) in self.additional_fields {
             if drop_invalid_attributes {
                 log::warn!("dropping out-of-spec top-level attribute: {key}");
+                if let Some(audit) = audit.as_deref_mut() {
+                    audit.record(key);
+                }
             } else {
                 return Err(Error::InvalidAttribute(key));
             }
@@ -712,6 +926,26 @@ impl FlatItem {
     }
 }
 
+/// Returns the minimum and maximum elevation of a GeoJSON geometry, if its
+/// positions have a third value.
+#[cfg(feature = "geo")]
+fn elevation_range(value: &Value) -> Option<(f64, f64)> {
+    match value {
+        Value::Array(array) if array.len() >= 3 && array.iter().all(Value::is_number) => {
+            array[2].as_f64().map(|z| (z, z))
+        }
+        Value::Array(array) => array
+            .iter()
+            .filter_map(elevation_range)
+            .reduce(|a, b| (a.0.min(b.0), a.1.max(b.1))),
+        Value::Object(object) => object
+            .get("coordinates")
+            .or_else(|| object.get("geometries"))
+            .and_then(elevation_range),
+        _ => None,
+    }
+}
+
 fn default_stac_version() -> Version {
     STAC_VERSION
 }
@@ -777,6 +1011,26 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg(feature = "geo")]
+    fn set_geometry_sets_3d_bbox() {
+        let mut item = Item::new("an-id");
+        item.set_geometry(Some(
+            serde_json::from_value(json!({
+                "type": "LineString",
+                "coordinates": [[-105.0, 41.0, 10.0], [-104.0, 42.0, 20.0]]
+            }))
+            .unwrap(),
+        ))
+        .unwrap();
+        assert_eq!(
+            item.bbox.unwrap(),
+            vec![-105.0, 41.0, 10.0, -104.0, 42.0, 20.0]
+                .try_into()
+                .unwrap()
+        );
+    }
+
     #[test]
     #[cfg(feature = "geo")]
     fn set_geometry_clears_bbox() {
@@ -790,6 +1044,76 @@ mod tests {
         assert_eq!(item.bbox, None);
     }
 
+    #[test]
+    #[cfg(feature = "geo")]
+    fn update_bbox_keeps_bbox_without_geometry() {
+        use crate::Bbox;
+        let mut item = Item::new("an-id");
+        item.bbox = Some(Bbox::new(-106.0, 41.0, -105.0, 42.0));
+        item.update_bbox().unwrap();
+        assert_eq!(item.bbox.unwrap(), Bbox::new(-106.0, 41.0, -105.0, 42.0));
+    }
+
+    #[test]
+    #[cfg(feature = "geo")]
+    fn set_geometry_from_proj_requires_fields() {
+        let mut item = Item::new("an-id");
+        assert!(matches!(
+            item.set_geometry_from_proj().unwrap_err(),
+            crate::Error::MissingField("proj:geometry")
+        ));
+        let _ = item.properties.additional_fields.insert(
+            "proj:geometry".into(),
+            json!({"type": "Point", "coordinates": [500000.0, 0.0]}),
+        );
+        assert!(matches!(
+            item.set_geometry_from_proj().unwrap_err(),
+            crate::Error::MissingField("proj:code")
+        ));
+    }
+
+    #[test]
+    #[cfg(all(feature = "geo", feature = "proj"))]
+    fn set_geometry_from_proj_epsg() {
+        let mut item = Item::new("an-id");
+        let _ = item.properties.additional_fields.insert(
+            "proj:geometry".into(),
+            json!({
+                "type": "Polygon",
+                "coordinates": [[
+                    [500000.0, 0.0], [600000.0, 0.0], [600000.0, 100000.0],
+                    [500000.0, 100000.0], [500000.0, 0.0]
+                ]]
+            }),
+        );
+        let _ = item
+            .properties
+            .additional_fields
+            .insert("proj:epsg".into(), 32615.into());
+        item.set_geometry_from_proj().unwrap();
+        let bbox = item.bbox.unwrap();
+        assert!((bbox.xmin() + 93.0).abs() < 1e-6);
+        assert!(bbox.ymin().abs() < 1e-6);
+        assert!(bbox.xmax() > -92.2 && bbox.xmax() < -92.0);
+        assert!(bbox.ymax() > 0.8 && bbox.ymax() < 1.0);
+    }
+
+    #[test]
+    fn normalize_winding_order() {
+        let mut item = Item::new("an-id");
+        item.geometry = Some(
+            serde_json::from_value(json!({
+                "type": "Polygon",
+                "coordinates": [[[0.0, 0.0], [0.0, 1.0], [1.0, 1.0], [1.0, 0.0], [0.0, 0.0]]]
+            }))
+            .unwrap(),
+        );
+        assert!(!item.has_rfc7946_winding_order().unwrap());
+        assert!(item.normalize_winding_order().unwrap());
+        assert!(item.has_rfc7946_winding_order().unwrap());
+        assert!(!item.normalize_winding_order().unwrap());
+    }
+
     #[test]
     #[cfg(feature = "geo")]
     fn insersects() {
@@ -805,6 +1129,18 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg(feature = "geo")]
+    fn intersects_bbox_only() {
+        let mut item = Item::new("an-id");
+        let bbox = crate::geo::bbox(&[-106.0, 41.0, -105.0, 42.0]).unwrap();
+        assert!(!item.intersects(&bbox).unwrap());
+        item.bbox = Some(crate::Bbox::new(-105.5, 41.5, -104.5, 42.5));
+        assert!(item.intersects(&bbox).unwrap());
+        let bbox = crate::geo::bbox(&[0.0, 0.0, 1.0, 1.0]).unwrap();
+        assert!(!item.intersects(&bbox).unwrap());
+    }
+
     #[test]
     fn intersects_datetime() {
         let mut item = Item::new("an-id");
@@ -1,8 +1,11 @@
-use crate::{Error, Item, Link, Migrate, Result, Version};
+use crate::{
+    Assets, Error, Item, Link, Migrate, Result, Version, datetime::parse_datetime_permissively,
+};
+use geojson::{Feature, FeatureCollection};
 use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::{Map, Value};
 use stac_derive::{Links, SelfHref};
-use std::{ops::Deref, vec::IntoIter};
+use std::{collections::HashMap, fmt::Display, ops::Deref, str::FromStr, vec::IntoIter};
 
 const ITEM_COLLECTION_TYPE: &str = "FeatureCollection";
 
@@ -56,6 +59,425 @@ pub struct ItemCollection {
     self_href: Option<String>,
 }
 
+/// Options for converting items to GeoJSON with
+/// [ItemCollection::to_geojson_feature_collection].
+///
+/// The defaults leave geometries as they are. Use [GeoJsonOptions::rfc7946]
+/// for output that strict [RFC 7946](https://www.rfc-editor.org/rfc/rfc7946)
+/// consumers, like some web map clients, will accept.
+///
+/// # Examples
+///
+/// ```
+/// use stac::GeoJsonOptions;
+///
+/// let options = GeoJsonOptions::rfc7946().precision(7);
+/// assert!(options.normalize_winding_order);
+/// assert_eq!(options.precision, Some(7));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GeoJsonOptions {
+    /// Rewind polygon rings so that exteriors are counterclockwise and holes
+    /// are clockwise, as RFC 7946 requires.
+    ///
+    /// See [winding](crate::winding).
+    pub normalize_winding_order: bool,
+
+    /// The number of decimal places to round geometry and bbox coordinates
+    /// to.
+    ///
+    /// RFC 7946 recommends six decimal places, about ten centimeters.
+    pub precision: Option<u32>,
+}
+
+/// How to resolve items with the same id when merging item collections.
+///
+/// Items are the same if they have the same id and the same collection.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MergeStrategy {
+    /// Keep the item with the latest `updated` property.
+    ///
+    /// Items without a (parseable) `updated` are older than any item with
+    /// one, and ties keep the left item.
+    #[default]
+    PreferNewest,
+
+    /// Keep the item from the collection being merged into.
+    PreferLeft,
+
+    /// Keep the item from the collection being merged in.
+    PreferRight,
+
+    /// Return an [Error::DuplicateItemId].
+    Error,
+}
+
+impl ItemCollection {
+    /// Replaces every asset href of every item with the result of a function.
+    ///
+    /// See [Assets::map_asset_hrefs].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Asset, Item, ItemCollection};
+    ///
+    /// let mut item = Item::new("an-id");
+    /// item.assets.insert("data".into(), Asset::new("s3://bucket/data.tif"));
+    /// let mut item_collection = ItemCollection::from(vec![item]);
+    /// item_collection.map_asset_hrefs(|href| href.replace("s3://", "gs://"));
+    /// assert_eq!(item_collection.items[0].assets["data"].href, "gs://bucket/data.tif");
+    /// ```
+    pub fn map_asset_hrefs(&mut self, mut f: impl FnMut(&str) -> String) {
+        for item in &mut self.items {
+            item.map_asset_hrefs(&mut f);
+        }
+    }
+
+    /// Makes the relative asset hrefs of every item absolute with respect to
+    /// a base.
+    ///
+    /// See [Assets::make_asset_hrefs_absolute].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Asset, Item, ItemCollection};
+    ///
+    /// let mut item = Item::new("an-id");
+    /// item.assets.insert("data".into(), Asset::new("./data.tif"));
+    /// let mut item_collection = ItemCollection::from(vec![item]);
+    /// item_collection.make_asset_hrefs_absolute("/data/items.json").unwrap();
+    /// assert_eq!(item_collection.items[0].assets["data"].href, "/data/data.tif");
+    /// ```
+    pub fn make_asset_hrefs_absolute(&mut self, base: &str) -> Result<()> {
+        for item in &mut self.items {
+            item.make_asset_hrefs_absolute(base)?;
+        }
+        Ok(())
+    }
+
+    /// Makes the asset hrefs of every item relative to a base.
+    ///
+    /// See [Assets::make_asset_hrefs_relative].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Asset, Item, ItemCollection};
+    ///
+    /// let mut item = Item::new("an-id");
+    /// item.assets.insert("data".into(), Asset::new("/data/data.tif"));
+    /// let mut item_collection = ItemCollection::from(vec![item]);
+    /// item_collection.make_asset_hrefs_relative("/data/items.json");
+    /// assert_eq!(item_collection.items[0].assets["data"].href, "./data.tif");
+    /// ```
+    pub fn make_asset_hrefs_relative(&mut self, base: &str) {
+        for item in &mut self.items {
+            item.make_asset_hrefs_relative(base);
+        }
+    }
+
+    /// Recomputes the bounding box of every item from its geometry.
+    ///
+    /// See [Item::update_bbox].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Bbox, Item, ItemCollection};
+    /// use geojson::Geometry;
+    ///
+    /// let mut item = Item::new("an-id");
+    /// item.geometry = Some(Geometry::new_point(vec![-105.1, 41.1]));
+    /// let mut item_collection = ItemCollection::from(vec![item]);
+    /// item_collection.update_bboxes().unwrap();
+    /// assert_eq!(
+    ///     item_collection.items[0].bbox.unwrap(),
+    ///     Bbox::new(-105.1, 41.1, -105.1, 41.1)
+    /// );
+    /// ```
+    #[cfg(feature = "geo")]
+    pub fn update_bboxes(&mut self) -> Result<()> {
+        for item in &mut self.items {
+            item.update_bbox()?;
+        }
+        Ok(())
+    }
+
+    /// Merges another item collection into this one.
+    ///
+    /// Items that are only in `other` are appended, and items that are in
+    /// both are resolved with the [MergeStrategy]. The winning item is kept
+    /// whole, in this collection's order.
+    ///
+    /// Links are unioned by `rel` and `href`, except that `other`'s `self`
+    /// link is dropped if this collection already has one. Additional fields
+    /// from `other` are added if they aren't already set, and
+    /// `stac_extensions` arrays are unioned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Item, ItemCollection, MergeStrategy};
+    ///
+    /// let left = ItemCollection::from(vec![Item::new("a"), Item::new("b")]);
+    /// let right = ItemCollection::from(vec![Item::new("b"), Item::new("c")]);
+    /// let merged = left.clone().merge(right.clone(), MergeStrategy::PreferLeft).unwrap();
+    /// assert_eq!(merged.items.len(), 3);
+    /// assert!(left.merge(right, MergeStrategy::Error).is_err());
+    /// ```
+    pub fn merge(
+        mut self,
+        other: ItemCollection,
+        strategy: MergeStrategy,
+    ) -> Result<ItemCollection> {
+        let mut index: HashMap<(Option<String>, String), usize> = self
+            .items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| ((item.collection.clone(), item.id.clone()), i))
+            .collect();
+        for item in other.items {
+            push_item(&mut self.items, &mut index, item, strategy)?;
+        }
+        let has_self_link = self.links.iter().any(Link::is_self);
+        for link in other.links {
+            if (link.is_self() && has_self_link)
+                || self
+                    .links
+                    .iter()
+                    .any(|l| l.rel == link.rel && l.href == link.href)
+            {
+                continue;
+            }
+            self.links.push(link);
+        }
+        for (key, value) in other.additional_fields {
+            match (self.additional_fields.get_mut(&key), value) {
+                (Some(Value::Array(extensions)), Value::Array(other_extensions))
+                    if key == "stac_extensions" =>
+                {
+                    for extension in other_extensions {
+                        if !extensions.contains(&extension) {
+                            extensions.push(extension);
+                        }
+                    }
+                }
+                (Some(_), _) => {}
+                (None, value) => {
+                    let _ = self.additional_fields.insert(key, value);
+                }
+            }
+        }
+        Ok(self)
+    }
+
+    /// Removes items with the same id and collection as an earlier item.
+    ///
+    /// Duplicates are resolved with the [MergeStrategy], where the earlier
+    /// item is on the left. The winning item takes the place of the first
+    /// duplicate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Item, ItemCollection, MergeStrategy};
+    ///
+    /// let mut old = Item::new("a");
+    /// old.properties.updated = Some("2024-01-01T00:00:00Z".to_string());
+    /// let mut new = Item::new("a");
+    /// new.properties.updated = Some("2024-06-01T00:00:00Z".to_string());
+    /// let mut item_collection = ItemCollection::from(vec![new, Item::new("b"), old]);
+    /// item_collection.dedupe_by_id(MergeStrategy::PreferNewest).unwrap();
+    /// assert_eq!(item_collection.items.len(), 2);
+    /// assert_eq!(
+    ///     item_collection.items[0].properties.updated.as_deref(),
+    ///     Some("2024-06-01T00:00:00Z")
+    /// );
+    /// ```
+    pub fn dedupe_by_id(&mut self, strategy: MergeStrategy) -> Result<()> {
+        let mut items = Vec::with_capacity(self.items.len());
+        let mut index = HashMap::new();
+        for item in std::mem::take(&mut self.items) {
+            push_item(&mut items, &mut index, item, strategy)?;
+        }
+        self.items = items;
+        Ok(())
+    }
+
+    /// Converts this item collection to a GeoJSON feature collection.
+    ///
+    /// Each item becomes a feature with its properties, and its other fields,
+    /// like `assets` and `links`, become foreign members. This collection's
+    /// links and additional fields become foreign members of the feature
+    /// collection.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{GeoJsonOptions, Item, ItemCollection};
+    ///
+    /// let mut item = Item::new("an-id");
+    /// item.geometry = Some(stac::Geometry::new_point(vec![-105.123456789, 41.123456789]));
+    /// let item_collection = ItemCollection::from(vec![item]);
+    /// let feature_collection = item_collection
+    ///     .to_geojson_feature_collection(GeoJsonOptions::rfc7946())
+    ///     .unwrap();
+    /// let value = serde_json::to_value(&feature_collection).unwrap();
+    /// assert_eq!(
+    ///     value["features"][0]["geometry"]["coordinates"],
+    ///     serde_json::json!([-105.123457, 41.123457])
+    /// );
+    /// ```
+    pub fn to_geojson_feature_collection(
+        &self,
+        options: GeoJsonOptions,
+    ) -> Result<FeatureCollection> {
+        let features = self
+            .items
+            .iter()
+            .map(|item| to_feature(item, options))
+            .collect::<Result<Vec<_>>>()?;
+        let mut foreign_members = self.additional_fields.clone();
+        if !self.links.is_empty() {
+            let _ = foreign_members.insert("links".into(), serde_json::to_value(&self.links)?);
+        }
+        Ok(FeatureCollection {
+            bbox: None,
+            features,
+            foreign_members: if foreign_members.is_empty() {
+                None
+            } else {
+                Some(foreign_members)
+            },
+        })
+    }
+}
+
+impl GeoJsonOptions {
+    /// Returns options for RFC 7946 output, with normalized winding order
+    /// and six decimal places of precision.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::GeoJsonOptions;
+    ///
+    /// let options = GeoJsonOptions::rfc7946();
+    /// assert_eq!(options.precision, Some(6));
+    /// ```
+    pub fn rfc7946() -> GeoJsonOptions {
+        GeoJsonOptions {
+            normalize_winding_order: true,
+            precision: Some(6),
+        }
+    }
+
+    /// Sets whether polygon rings are rewound to the RFC 7946 winding order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::GeoJsonOptions;
+    ///
+    /// let options = GeoJsonOptions::default().normalize_winding_order(true);
+    /// ```
+    pub fn normalize_winding_order(mut self, normalize_winding_order: bool) -> GeoJsonOptions {
+        self.normalize_winding_order = normalize_winding_order;
+        self
+    }
+
+    /// Sets the number of decimal places to round coordinates to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::GeoJsonOptions;
+    ///
+    /// let options = GeoJsonOptions::default().precision(5);
+    /// ```
+    pub fn precision(mut self, precision: u32) -> GeoJsonOptions {
+        self.precision = Some(precision);
+        self
+    }
+}
+
+fn to_feature(item: &Item, options: GeoJsonOptions) -> Result<Feature> {
+    let mut value = serde_json::to_value(item)?;
+    if let Some(geometry) = value.get_mut("geometry") {
+        if options.normalize_winding_order {
+            let _ = crate::winding::normalize(geometry);
+        }
+        if let Some(precision) = options.precision {
+            round_coordinates(geometry, precision);
+        }
+    }
+    if let Some(precision) = options.precision
+        && let Some(bbox) = value.get_mut("bbox")
+    {
+        round_coordinates(bbox, precision);
+    }
+    serde_json::from_value(value).map_err(Error::from)
+}
+
+/// Rounds every number in a value to a number of decimal places.
+fn round_coordinates(value: &mut Value, precision: u32) {
+    match value {
+        Value::Number(number) => {
+            if let Some(n) = number.as_f64() {
+                let scale = 10f64.powi(precision.min(15) as i32);
+                *value = Value::from((n * scale).round() / scale);
+            }
+        }
+        Value::Array(values) => {
+            for value in values {
+                round_coordinates(value, precision);
+            }
+        }
+        Value::Object(object) => {
+            for value in object.values_mut() {
+                round_coordinates(value, precision);
+            }
+        }
+        Value::Null | Value::Bool(_) | Value::String(_) => {}
+    }
+}
+
+/// Adds an item to `items`, or resolves it against an existing item with the
+/// same collection and id.
+fn push_item(
+    items: &mut Vec<Item>,
+    index: &mut HashMap<(Option<String>, String), usize>,
+    item: Item,
+    strategy: MergeStrategy,
+) -> Result<()> {
+    let key = (item.collection.clone(), item.id.clone());
+    if let Some(&i) = index.get(&key) {
+        match strategy {
+            MergeStrategy::PreferNewest => {
+                if updated(&item) > updated(&items[i]) {
+                    items[i] = item;
+                }
+            }
+            MergeStrategy::PreferLeft => {}
+            MergeStrategy::PreferRight => items[i] = item,
+            MergeStrategy::Error => return Err(Error::DuplicateItemId(item.id)),
+        }
+    } else {
+        let _ = index.insert(key, items.len());
+        items.push(item);
+    }
+    Ok(())
+}
+
+fn updated(item: &Item) -> Option<chrono::DateTime<chrono::Utc>> {
+    item.properties
+        .updated
+        .as_deref()
+        .and_then(|updated| parse_datetime_permissively(updated).ok())
+}
+
 impl From<Vec<Item>> for ItemCollection {
     fn from(items: Vec<Item>) -> Self {
         ItemCollection {
@@ -121,12 +543,76 @@ impl TryFrom<Value> for ItemCollection {
     }
 }
 
+impl FromStr for MergeStrategy {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<MergeStrategy> {
+        match s {
+            "newest" => Ok(MergeStrategy::PreferNewest),
+            "left" => Ok(MergeStrategy::PreferLeft),
+            "right" => Ok(MergeStrategy::PreferRight),
+            "error" => Ok(MergeStrategy::Error),
+            _ => Err(Error::UnknownMergeStrategy(s.to_string())),
+        }
+    }
+}
+
+impl Display for MergeStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MergeStrategy::PreferNewest => f.write_str("newest"),
+            MergeStrategy::PreferLeft => f.write_str("left"),
+            MergeStrategy::PreferRight => f.write_str("right"),
+            MergeStrategy::Error => f.write_str("error"),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::ItemCollection;
-    use crate::Item;
+    use super::{GeoJsonOptions, ItemCollection, MergeStrategy};
+    use crate::{Item, Link};
     use serde_json::json;
 
+    #[test]
+    fn to_geojson_feature_collection() {
+        let mut item = Item::new("an-id");
+        item.geometry = Some(
+            serde_json::from_value(json!({
+                "type": "Polygon",
+                "coordinates": [[
+                    [0.0, 0.0], [0.0, 1.123456789], [1.0, 1.0], [1.0, 0.0], [0.0, 0.0]
+                ]]
+            }))
+            .unwrap(),
+        );
+        item.bbox = Some(crate::Bbox::new(0.0, 0.0, 1.0, 1.123456789));
+        let mut item_collection = ItemCollection::from(vec![item]);
+        item_collection
+            .links
+            .push(Link::new("http://stac.test/next", "next"));
+
+        let feature_collection = item_collection
+            .to_geojson_feature_collection(GeoJsonOptions::default())
+            .unwrap();
+        let value = serde_json::to_value(&feature_collection).unwrap();
+        assert_eq!(value["type"], "FeatureCollection");
+        assert_eq!(value["links"][0]["rel"], "next");
+        let feature = &value["features"][0];
+        assert_eq!(feature["id"], "an-id");
+        assert_eq!(feature["stac_version"], crate::STAC_VERSION.to_string());
+        assert!(!crate::winding::is_normalized(&feature["geometry"]));
+
+        let feature_collection = item_collection
+            .to_geojson_feature_collection(GeoJsonOptions::rfc7946())
+            .unwrap();
+        let value = serde_json::to_value(&feature_collection).unwrap();
+        let feature = &value["features"][0];
+        assert!(crate::winding::is_normalized(&feature["geometry"]));
+        assert_eq!(feature["geometry"]["coordinates"][0][3][1], 1.123457);
+        assert_eq!(feature["bbox"][3], 1.123457);
+    }
+
     #[test]
     fn item_collection_from_vec() {
         let items = vec![Item::new("a"), Item::new("b")];
@@ -150,4 +636,130 @@ mod tests {
         let value = serde_json::to_value(item_collection).unwrap();
         assert_eq!(value.as_object().unwrap()["type"], "FeatureCollection");
     }
+
+    fn item(id: &str, updated: Option<&str>) -> Item {
+        let mut item = Item::new(id);
+        item.properties.updated = updated.map(String::from);
+        item
+    }
+
+    #[test]
+    fn merge_prefer_newest() {
+        let left = ItemCollection::from(vec![
+            item("a", Some("2024-01-01T00:00:00Z")),
+            item("b", Some("2024-06-01T00:00:00Z")),
+            item("c", None),
+        ]);
+        let right = ItemCollection::from(vec![
+            item("a", Some("2024-02-01T00:00:00Z")),
+            item("b", Some("2024-01-01T00:00:00Z")),
+            item("c", Some("2020-01-01T00:00:00Z")),
+            item("d", None),
+        ]);
+        let merged = left.merge(right, MergeStrategy::PreferNewest).unwrap();
+        let updated: Vec<_> = merged
+            .items
+            .iter()
+            .map(|item| (item.id.as_str(), item.properties.updated.as_deref()))
+            .collect();
+        assert_eq!(
+            updated,
+            vec![
+                ("a", Some("2024-02-01T00:00:00Z")),
+                ("b", Some("2024-06-01T00:00:00Z")),
+                ("c", Some("2020-01-01T00:00:00Z")),
+                ("d", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn merge_prefer_right() {
+        let left = ItemCollection::from(vec![item("a", Some("2024-01-01T00:00:00Z"))]);
+        let right = ItemCollection::from(vec![item("a", None)]);
+        let merged = left.merge(right, MergeStrategy::PreferRight).unwrap();
+        assert!(merged.items[0].properties.updated.is_none());
+    }
+
+    #[test]
+    fn dedupe_by_id() {
+        let mut item_collection = ItemCollection::from(vec![
+            item("a", Some("2024-01-01T00:00:00Z")),
+            item("b", None),
+            item("a", None),
+            item("b", Some("2024-01-01T00:00:00Z")),
+        ]);
+        let mut prefer_right = item_collection.clone();
+        prefer_right
+            .dedupe_by_id(MergeStrategy::PreferRight)
+            .unwrap();
+        assert_eq!(prefer_right.items.len(), 2);
+        assert!(prefer_right.items[0].properties.updated.is_none());
+
+        assert!(
+            item_collection
+                .clone()
+                .dedupe_by_id(MergeStrategy::Error)
+                .is_err()
+        );
+
+        item_collection
+            .dedupe_by_id(MergeStrategy::PreferNewest)
+            .unwrap();
+        let ids: Vec<_> = item_collection
+            .items
+            .iter()
+            .map(|item| (item.id.as_str(), item.properties.updated.is_some()))
+            .collect();
+        assert_eq!(ids, vec![("a", true), ("b", true)]);
+    }
+
+    #[test]
+    fn merge_different_collections() {
+        let left = ItemCollection::from(vec![Item::new("a").collection("left")]);
+        let right = ItemCollection::from(vec![Item::new("a").collection("right")]);
+        let merged = left.merge(right, MergeStrategy::Error).unwrap();
+        assert_eq!(merged.items.len(), 2);
+    }
+
+    #[test]
+    fn merge_links_and_fields() {
+        let mut left = ItemCollection::from(vec![]);
+        left.links.push(Link::self_("left.json"));
+        left.links.push(Link::new("license.html", "license"));
+        let _ = left
+            .additional_fields
+            .insert("stac_extensions".into(), json!(["a"]));
+        let mut right = ItemCollection::from(vec![]);
+        right.links.push(Link::self_("right.json"));
+        right.links.push(Link::new("license.html", "license"));
+        right.links.push(Link::new("about.html", "about"));
+        let _ = right
+            .additional_fields
+            .insert("stac_extensions".into(), json!(["a", "b"]));
+        let _ = right.additional_fields.insert("foo".into(), "bar".into());
+        let merged = left.merge(right, MergeStrategy::default()).unwrap();
+        assert_eq!(merged.links.len(), 3);
+        assert_eq!(
+            merged.additional_fields["stac_extensions"],
+            json!(["a", "b"])
+        );
+        assert_eq!(merged.additional_fields["foo"], "bar");
+    }
+
+    #[test]
+    fn parse_merge_strategy() {
+        for strategy in [
+            MergeStrategy::PreferNewest,
+            MergeStrategy::PreferLeft,
+            MergeStrategy::PreferRight,
+            MergeStrategy::Error,
+        ] {
+            assert_eq!(
+                strategy.to_string().parse::<MergeStrategy>().unwrap(),
+                strategy
+            );
+        }
+        let _ = "oldest".parse::<MergeStrategy>().unwrap_err();
+    }
 }
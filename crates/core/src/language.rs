@@ -0,0 +1,422 @@
+//! Support for the [language extension](https://github.com/stac-extensions/language)
+//! and localized fields.
+//!
+//! The language extension describes the language of an object with the
+//! `language` and `languages` fields. Localized versions of an object's
+//! fields, e.g. its title and description, are stored in a `localization`
+//! object keyed by language code:
+//!
+//! ```json
+//! {
+//!   "title": "Forests",
+//!   "language": {"code": "en"},
+//!   "languages": [{"code": "de", "name": "Deutsch"}],
+//!   "localization": {"de": {"title": "Wälder"}}
+//! }
+//! ```
+//!
+//! All of these fields are stored in `additional_fields` (or in an item's
+//! properties), so they're preserved whenever an object is read and written.
+//! Use [localize] to pick a language, e.g. one negotiated from an
+//! `Accept-Language` header with [negotiate].
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// The language extension schema.
+pub const SCHEMA_URI: &str = "https://stac-extensions.github.io/language/v1.0.0/schema.json";
+
+/// The field that holds localized versions of an object's fields, keyed by
+/// language code.
+pub const LOCALIZATION_FIELD: &str = "localization";
+
+/// A language, as used by the `language` and `languages` fields.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Language {
+    /// The language code, e.g. `en` or `de-CH`, per
+    /// [RFC 5646](https://www.rfc-editor.org/rfc/rfc5646).
+    pub code: String,
+
+    /// The name of the language, in the language itself.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    /// The name of the language, in English.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alternate: Option<String>,
+
+    /// The direction of the text, `ltr` or `rtl`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dir: Option<String>,
+}
+
+impl Language {
+    /// Creates a new language with the given code.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::language::Language;
+    ///
+    /// let language = Language::new("de");
+    /// assert_eq!(language.code, "de");
+    /// ```
+    pub fn new(code: impl ToString) -> Language {
+        Language {
+            code: code.to_string(),
+            name: None,
+            alternate: None,
+            dir: None,
+        }
+    }
+}
+
+/// Returns the language codes available for a JSON object.
+///
+/// This includes the codes in `language`, `languages`, and `localization`,
+/// without duplicates. If the object has `properties` (i.e. it's an item),
+/// they're used instead.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+///
+/// let value = json!({
+///     "language": {"code": "en"},
+///     "localization": {"de": {"title": "Wälder"}}
+/// });
+/// let languages = stac::language::available(value.as_object().unwrap());
+/// assert_eq!(languages, ["en", "de"]);
+/// ```
+pub fn available(object: &Map<String, Value>) -> Vec<String> {
+    let object = fields(object);
+    let mut codes: Vec<String> = Vec::new();
+    let mut push = |code: &str| {
+        if !codes.iter().any(|c| c.eq_ignore_ascii_case(code)) {
+            codes.push(code.to_string());
+        }
+    };
+    if let Some(code) = object
+        .get("language")
+        .and_then(|language| language.get("code"))
+        .and_then(Value::as_str)
+    {
+        push(code);
+    }
+    for code in object
+        .get("languages")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|language| language.get("code").and_then(Value::as_str))
+    {
+        push(code);
+    }
+    for code in object
+        .get(LOCALIZATION_FIELD)
+        .and_then(Value::as_object)
+        .into_iter()
+        .flat_map(Map::keys)
+    {
+        push(code);
+    }
+    codes
+}
+
+/// Picks the best available language for an `Accept-Language` header value.
+///
+/// Ranges are tried in order of their quality values. A range matches a
+/// language code that's equal to it, or that starts with it followed by a
+/// `-` (e.g. `de` matches `de-CH`). If no range matches, a range's primary
+/// subtag is tried (e.g. `de-CH` falls back to `de`). `*` matches the first
+/// available language that isn't rejected by a range with `q=0`.
+///
+/// # Examples
+///
+/// ```
+/// use stac::language::negotiate;
+///
+/// assert_eq!(negotiate("fr;q=0.5, de", ["en", "de", "fr"]), Some("de"));
+/// assert_eq!(negotiate("de-CH", ["en", "de"]), Some("de"));
+/// assert_eq!(negotiate("es", ["en", "de"]), None);
+/// ```
+pub fn negotiate<'a>(
+    accept_language: &str,
+    available: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+    let available: Vec<&str> = available.into_iter().collect();
+    let (mut ranges, rejected): (Vec<(&str, f64)>, Vec<(&str, f64)>) = accept_language
+        .split(',')
+        .filter_map(|range| {
+            let mut parts = range.split(';');
+            let tag = parts.next()?.trim();
+            if tag.is_empty() {
+                return None;
+            }
+            let quality = parts
+                .filter_map(|part| part.trim().strip_prefix("q="))
+                .find_map(|q| q.trim().parse::<f64>().ok())
+                .unwrap_or(1.);
+            Some((tag, quality))
+        })
+        .partition(|(_, quality)| *quality > 0.);
+    // A stable sort keeps the header's order for equal qualities.
+    ranges.sort_by(|a, b| b.1.total_cmp(&a.1));
+    for (range, _) in &ranges {
+        if *range == "*" {
+            if let Some(code) = available
+                .iter()
+                .find(|code| !rejected.iter().any(|(range, _)| matches(range, code)))
+            {
+                return Some(*code);
+            }
+        } else if let Some(code) = available.iter().find(|code| matches(range, code)) {
+            return Some(*code);
+        }
+    }
+    for (range, _) in &ranges {
+        if let Some((primary, _)) = range.split_once('-')
+            && let Some(code) = available.iter().find(|code| matches(primary, code))
+        {
+            return Some(*code);
+        }
+    }
+    None
+}
+
+/// Localizes a JSON object to a language.
+///
+/// Fields in the object's `localization` entry for the language replace the
+/// object's own fields, and `language` is set to the matching entry of
+/// `languages` (or just the code). If the object has `properties` (i.e. it's
+/// an item), they're localized instead. Returns false, and leaves the object
+/// unchanged, if the language isn't the object's current language and
+/// there's no localization for it.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+///
+/// let mut value = json!({
+///     "title": "Forests",
+///     "language": {"code": "en"},
+///     "localization": {"de": {"title": "Wälder"}}
+/// });
+/// assert!(stac::language::localize(value.as_object_mut().unwrap(), "de"));
+/// assert_eq!(value["title"], "Wälder");
+/// assert_eq!(value["language"]["code"], "de");
+/// ```
+pub fn localize(object: &mut Map<String, Value>, code: &str) -> bool {
+    let object = if object.get("properties").is_some_and(Value::is_object) {
+        object
+            .get_mut("properties")
+            .and_then(Value::as_object_mut)
+            .expect("properties should be an object")
+    } else {
+        object
+    };
+    let current = object
+        .get("language")
+        .and_then(|language| language.get("code"))
+        .and_then(Value::as_str);
+    let localized = object
+        .get(LOCALIZATION_FIELD)
+        .and_then(Value::as_object)
+        .and_then(|localization| {
+            localization
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case(code))
+        })
+        .and_then(|(_, value)| value.as_object())
+        .cloned();
+    let Some(localized) = localized else {
+        return current.is_some_and(|current| current.eq_ignore_ascii_case(code));
+    };
+    for (key, value) in localized {
+        let _ = object.insert(key, value);
+    }
+    let language = object
+        .get("languages")
+        .and_then(Value::as_array)
+        .and_then(|languages| {
+            languages.iter().find(|language| {
+                language
+                    .get("code")
+                    .and_then(Value::as_str)
+                    .is_some_and(|c| c.eq_ignore_ascii_case(code))
+            })
+        })
+        .cloned()
+        .unwrap_or_else(|| serde_json::json!({"code": code}));
+    let _ = object.insert("language".to_string(), language);
+    true
+}
+
+fn fields(object: &Map<String, Value>) -> &Map<String, Value> {
+    object
+        .get("properties")
+        .and_then(Value::as_object)
+        .unwrap_or(object)
+}
+
+fn matches(range: &str, code: &str) -> bool {
+    code.get(..range.len())
+        .is_some_and(|prefix| prefix.eq_ignore_ascii_case(range))
+        && code
+            .as_bytes()
+            .get(range.len())
+            .is_none_or(|&byte| byte == b'-')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Language, available, localize, negotiate};
+    use crate::{Collection, Item};
+    use serde_json::json;
+
+    fn collection() -> serde_json::Value {
+        json!({
+            "type": "Collection",
+            "stac_version": "1.1.0",
+            "stac_extensions": [super::SCHEMA_URI],
+            "id": "forests",
+            "title": "Forests",
+            "description": "Forests of the world",
+            "license": "CC-BY-4.0",
+            "extent": {
+                "spatial": {"bbox": [[-180.0, -90.0, 180.0, 90.0]]},
+                "temporal": {"interval": [[null, null]]}
+            },
+            "language": {"code": "en", "name": "English"},
+            "languages": [{"code": "de", "name": "Deutsch", "alternate": "German"}],
+            "localization": {
+                "de": {"title": "Wälder", "description": "Wälder der Welt"}
+            },
+            "links": [
+                {"href": "./de/collection.json", "rel": "alternate", "hreflang": "de"}
+            ]
+        })
+    }
+
+    #[test]
+    fn round_trip() {
+        let value = collection();
+        let collection: Collection = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(serde_json::to_value(collection).unwrap(), value);
+    }
+
+    #[test]
+    fn language() {
+        let language: Language =
+            serde_json::from_value(collection()["languages"][0].clone()).unwrap();
+        assert_eq!(language.code, "de");
+        assert_eq!(language.alternate.as_deref(), Some("German"));
+    }
+
+    #[test]
+    fn available_languages() {
+        assert_eq!(
+            available(collection().as_object().unwrap()),
+            ["en".to_string(), "de".to_string()]
+        );
+        assert!(available(json!({}).as_object().unwrap()).is_empty());
+    }
+
+    #[test]
+    fn negotiation() {
+        let available = ["en", "de", "fr-CA"];
+        assert_eq!(negotiate("de", available), Some("de"));
+        assert_eq!(negotiate("DE", available), Some("de"));
+        assert_eq!(negotiate("fr", available), Some("fr-CA"));
+        assert_eq!(negotiate("de-AT, en;q=0.5", available), Some("en"));
+        assert_eq!(negotiate("de-AT", available), Some("de"));
+        assert_eq!(negotiate("en;q=0.1, de;q=0.9", available), Some("de"));
+        assert_eq!(negotiate("en;q=0, *", available), Some("de"));
+        assert_eq!(negotiate("en;q=0", available), None);
+        assert_eq!(negotiate("es, *;q=0.1", available), Some("en"));
+        assert_eq!(negotiate("", available), None);
+        assert_eq!(negotiate("d", available), None);
+    }
+
+    #[test]
+    fn localize_collection() {
+        let mut value = collection();
+        let object = value.as_object_mut().unwrap();
+        assert!(localize(object, "de"));
+        assert_eq!(object["title"], "Wälder");
+        assert_eq!(object["description"], "Wälder der Welt");
+        assert_eq!(object["language"]["name"], "Deutsch");
+        assert_eq!(object["license"], "CC-BY-4.0");
+
+        let mut value = collection();
+        let object = value.as_object_mut().unwrap();
+        assert!(localize(object, "en"));
+        assert_eq!(object["title"], "Forests");
+        assert!(!localize(object, "fr"));
+        assert_eq!(value, collection());
+    }
+
+    #[test]
+    fn localize_item() {
+        let mut item = Item::new("an-id");
+        let _ = item
+            .properties
+            .additional_fields
+            .insert("title".into(), "Forest".into());
+        let _ = item
+            .properties
+            .additional_fields
+            .insert("localization".into(), json!({"de": {"title": "Wald"}}));
+        let mut value = serde_json::to_value(item).unwrap();
+        assert!(localize(value.as_object_mut().unwrap(), "de"));
+        assert_eq!(value["properties"]["title"], "Wald");
+        assert_eq!(value["properties"]["language"]["code"], "de");
+        assert!(value.get("language").is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "geoparquet")]
+    fn geoparquet_round_trip() {
+        use crate::{FromGeoparquet, IntoGeoparquet, ItemCollection};
+
+        let mut item: Item = crate::read("examples/simple-item.json").unwrap();
+        for (key, value) in [
+            ("language", json!({"code": "en", "name": "English"})),
+            ("languages", json!([{"code": "de", "name": "Deutsch"}])),
+            ("localization", json!({"de": {"title": "Wald"}})),
+        ] {
+            let _ = item
+                .properties
+                .additional_fields
+                .insert(key.to_string(), value);
+        }
+        let mut link = crate::Link::new("./de/item.json", "alternate");
+        let _ = link
+            .additional_fields
+            .insert("hreflang".to_string(), "de".into());
+        item.links.push(link);
+        let bytes = ItemCollection::from(vec![item.clone()])
+            .into_geoparquet_vec(Default::default())
+            .unwrap();
+        let round_tripped = ItemCollection::from_geoparquet_bytes(bytes)
+            .unwrap()
+            .items
+            .pop()
+            .unwrap();
+        for key in ["language", "languages", "localization"] {
+            assert_eq!(
+                round_tripped.properties.additional_fields[key],
+                item.properties.additional_fields[key],
+                "{key}"
+            );
+        }
+        let link = round_tripped
+            .links
+            .iter()
+            .find(|link| link.rel == "alternate")
+            .unwrap();
+        assert_eq!(link.additional_fields["hreflang"], "de");
+    }
+}
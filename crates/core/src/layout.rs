@@ -0,0 +1,259 @@
+//! Templates for laying out items into a partitioned directory structure.
+//!
+//! A [Template] is a path with `${variable}` placeholders, e.g.
+//! `${collection}/${year}/${month}/items.parquet`. Rendering a template for
+//! an item fills in the placeholders from the item, so items can be grouped
+//! into files that tools like DuckDB can query with hive-style globs.
+
+use crate::{Error, Item, Result};
+use chrono::{DateTime, Datelike, Utc};
+use serde_json::Value;
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+/// A path template with `${variable}` placeholders.
+///
+/// These variables are supported:
+///
+/// - `collection`: the item's collection id
+/// - `id`: the item's id
+/// - `year`, `month`, `day`: parts of the item's datetime, or its start
+///   datetime if it doesn't have one, with the month and day zero-padded
+/// - anything else is looked up in the item's properties, with or without a
+///   `properties.` prefix
+///
+/// Path separators in values are replaced with underscores, so a value can't
+/// add directories to the rendered path.
+///
+/// # Examples
+///
+/// ```
+/// use stac::{Item, layout::Template};
+///
+/// let template: Template = "${collection}/${year}/${month}/items.parquet".parse().unwrap();
+/// let mut item = Item::new("an-id").collection("a-collection");
+/// item.properties.datetime = Some("2024-03-15T00:00:00Z".parse().unwrap());
+/// assert_eq!(template.render(&item).unwrap(), "a-collection/2024/03/items.parquet");
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Template {
+    segments: Vec<Segment>,
+    fallback: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Segment {
+    Literal(String),
+    Variable(String),
+}
+
+impl Template {
+    /// Parses a template.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::layout::Template;
+    ///
+    /// let template = Template::new("${collection}/items.ndjson").unwrap();
+    /// assert!(Template::new("${collection/items.ndjson").is_err());
+    /// ```
+    pub fn new(template: &str) -> Result<Template> {
+        let mut segments = Vec::new();
+        let mut rest = template;
+        while let Some(start) = rest.find("${") {
+            if start > 0 {
+                segments.push(Segment::Literal(rest[..start].to_string()));
+            }
+            let after = &rest[start + 2..];
+            let end = after.find('}').ok_or_else(|| {
+                Error::InvalidTemplate(format!("unterminated variable in {template}"))
+            })?;
+            let name = after[..end].trim();
+            if name.is_empty() {
+                return Err(Error::InvalidTemplate(format!(
+                    "empty variable in {template}"
+                )));
+            }
+            segments.push(Segment::Variable(name.to_string()));
+            rest = &after[end + 1..];
+        }
+        if !rest.is_empty() {
+            segments.push(Segment::Literal(rest.to_string()));
+        }
+        Ok(Template {
+            segments,
+            fallback: None,
+        })
+    }
+
+    /// Sets the value to use for variables that an item doesn't have.
+    ///
+    /// Without a fallback, [Template::render] returns an error for those items.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Item, layout::Template};
+    ///
+    /// let template = Template::new("${collection}.ndjson").unwrap().fallback("unknown");
+    /// assert_eq!(template.render(&Item::new("an-id")).unwrap(), "unknown.ndjson");
+    /// ```
+    pub fn fallback(mut self, fallback: impl ToString) -> Template {
+        self.fallback = Some(fallback.to_string());
+        self
+    }
+
+    /// Returns the names of the variables in this template.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::layout::Template;
+    ///
+    /// let template = Template::new("${collection}/${year}.ndjson").unwrap();
+    /// assert_eq!(template.variables().collect::<Vec<_>>(), ["collection", "year"]);
+    /// ```
+    pub fn variables(&self) -> impl Iterator<Item = &str> {
+        self.segments.iter().filter_map(|segment| match segment {
+            Segment::Variable(name) => Some(name.as_str()),
+            Segment::Literal(_) => None,
+        })
+    }
+
+    /// Renders this template for an item.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Item, layout::Template};
+    ///
+    /// let template = Template::new("${collection}/${id}.json").unwrap();
+    /// let item = Item::new("an-id").collection("a-collection");
+    /// assert_eq!(template.render(&item).unwrap(), "a-collection/an-id.json");
+    /// ```
+    pub fn render(&self, item: &Item) -> Result<String> {
+        let mut rendered = String::new();
+        for segment in &self.segments {
+            match segment {
+                Segment::Literal(literal) => rendered.push_str(literal),
+                Segment::Variable(name) => {
+                    let value =
+                        match value(item, name) {
+                            Some(value) => value,
+                            None => self.fallback.clone().ok_or_else(|| {
+                                Error::MissingTemplateValue {
+                                    id: item.id.clone(),
+                                    variable: name.clone(),
+                                }
+                            })?,
+                        };
+                    rendered.push_str(&value.replace(['/', '\\'], "_"));
+                }
+            }
+        }
+        Ok(rendered)
+    }
+}
+
+fn value(item: &Item, name: &str) -> Option<String> {
+    match name {
+        "collection" => item.collection.clone(),
+        "id" => Some(item.id.clone()),
+        "year" => datetime(item).map(|datetime| datetime.year().to_string()),
+        "month" => datetime(item).map(|datetime| format!("{:02}", datetime.month())),
+        "day" => datetime(item).map(|datetime| format!("{:02}", datetime.day())),
+        _ => {
+            let name = name.strip_prefix("properties.").unwrap_or(name);
+            match name {
+                "datetime" => item.properties.datetime.map(|d| d.to_rfc3339()),
+                "start_datetime" => item.properties.start_datetime.map(|d| d.to_rfc3339()),
+                "end_datetime" => item.properties.end_datetime.map(|d| d.to_rfc3339()),
+                "title" => item.properties.title.clone(),
+                "description" => item.properties.description.clone(),
+                "created" => item.properties.created.clone(),
+                "updated" => item.properties.updated.clone(),
+                _ => match item.properties.additional_fields.get(name)? {
+                    Value::String(s) => Some(s.clone()),
+                    Value::Number(n) => Some(n.to_string()),
+                    Value::Bool(b) => Some(b.to_string()),
+                    Value::Null | Value::Array(_) | Value::Object(_) => None,
+                },
+            }
+        }
+    }
+}
+
+fn datetime(item: &Item) -> Option<DateTime<Utc>> {
+    item.properties.datetime.or(item.properties.start_datetime)
+}
+
+impl FromStr for Template {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Template> {
+        Template::new(s)
+    }
+}
+
+impl Display for Template {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for segment in &self.segments {
+            match segment {
+                Segment::Literal(literal) => f.write_str(literal)?,
+                Segment::Variable(name) => write!(f, "${{{name}}}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Template;
+    use crate::{Error, Item};
+
+    #[test]
+    fn render_properties() {
+        let template = Template::new("${platform}/${properties.eo:cloud_cover}.ndjson").unwrap();
+        let mut item = Item::new("an-id");
+        let _ = item
+            .properties
+            .additional_fields
+            .insert("platform".to_string(), "sentinel/2a".into());
+        let _ = item
+            .properties
+            .additional_fields
+            .insert("eo:cloud_cover".to_string(), 42.into());
+        assert_eq!(template.render(&item).unwrap(), "sentinel_2a/42.ndjson");
+    }
+
+    #[test]
+    fn missing_value() {
+        let template = Template::new("${year}/items.ndjson").unwrap();
+        let mut item = Item::new("an-id");
+        item.properties.datetime = None;
+        assert!(matches!(
+            template.render(&item).unwrap_err(),
+            Error::MissingTemplateValue { .. }
+        ));
+        assert_eq!(
+            template.fallback("none").render(&item).unwrap(),
+            "none/items.ndjson"
+        );
+    }
+
+    #[test]
+    fn display() {
+        let s = "prefix-${collection}/${year}/${month}/items.parquet";
+        assert_eq!(Template::new(s).unwrap().to_string(), s);
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(Template::new("${}").is_err());
+        assert!(Template::new("${collection").is_err());
+    }
+}
@@ -101,14 +101,19 @@ extern crate self as stac;
 
 pub mod api;
 mod asset;
+pub mod audit;
 mod band;
 mod bbox;
 mod catalog;
 mod collection;
+pub mod create;
+pub mod crs;
 mod data_type;
 pub mod datetime;
+pub mod diff;
 mod error;
 mod fields;
+pub mod fix;
 #[cfg(feature = "geo")]
 pub mod geo;
 #[cfg(feature = "geoarrow")]
@@ -120,13 +125,22 @@ pub mod item;
 mod item_asset;
 mod item_collection;
 mod json;
+pub mod language;
+pub mod layout;
+pub mod lineage;
 pub mod link;
 mod migrate;
 pub mod mime;
 mod ndjson;
+pub mod non_finite;
+pub mod patch;
+pub mod role;
+pub mod sort;
 mod statistics;
+pub mod testing;
 mod value;
 mod version;
+pub mod winding;
 
 use std::fmt::Display;
 
@@ -141,14 +155,15 @@ pub use fields::Fields;
 pub use geojson::Geometry;
 #[cfg(feature = "geoparquet")]
 pub use geoparquet::{FromGeoparquet, IntoGeoparquet};
-pub use href::SelfHref;
+pub use href::{Href, SelfHref};
 pub use item::{FlatItem, Item, Properties};
 pub use item_asset::ItemAsset;
-pub use item_collection::ItemCollection;
+pub use item_collection::{GeoJsonOptions, ItemCollection, MergeStrategy};
 pub use json::{FromJson, ToJson};
 pub use link::{Link, Links};
 pub use migrate::Migrate;
 pub use ndjson::{FromNdjson, ToNdjson};
+pub use patch::Patch;
 pub use statistics::Statistics;
 pub use value::Value;
 pub use version::Version;
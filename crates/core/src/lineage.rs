@@ -0,0 +1,376 @@
+//! Lineage graphs built from `derived_from` links.
+//!
+//! STAC objects record their provenance with links that have
+//! `rel="derived_from"`, pointing at the objects they were made from. A
+//! [Lineage] collects those links from many objects into a graph that can be
+//! written as [DOT](https://graphviz.org/doc/info/lang.html) or as a JSON
+//! adjacency list, and checked for cycles.
+//!
+//! ```
+//! use stac::{Item, Link, lineage::Lineage};
+//!
+//! let mut derived = Item::new("derived");
+//! derived.links.push(Link::new("source", "derived_from"));
+//! let lineage = Lineage::from_items(&[Item::new("source"), derived]).unwrap();
+//! assert_eq!(lineage.sources("derived").unwrap(), ["source"]);
+//! assert!(lineage.cycles().is_empty());
+//! ```
+
+use crate::{Item, Link, Links, Result, SelfHref, Value, link::Rel};
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+/// The link relation type for the objects that an object was derived from.
+pub const DERIVED_FROM_REL: &str = "derived_from";
+
+/// A graph of `derived_from` relationships.
+///
+/// Nodes are identified by the object's self href, or by its id if it doesn't
+/// have one. `derived_from` hrefs are made absolute with respect to the
+/// object's self href, so objects read from disk or the network line up with
+/// the links that point at them. Serializes as a JSON adjacency list, mapping
+/// each node to the nodes it was derived from.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Lineage {
+    sources: IndexMap<String, Vec<String>>,
+}
+
+impl Lineage {
+    /// Creates a new, empty lineage graph.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::lineage::Lineage;
+    ///
+    /// let lineage = Lineage::new();
+    /// assert!(lineage.is_empty());
+    /// ```
+    pub fn new() -> Lineage {
+        Lineage::default()
+    }
+
+    /// Creates a lineage graph from items.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Item, lineage::Lineage};
+    ///
+    /// let lineage = Lineage::from_items(&[Item::new("an-id")]).unwrap();
+    /// assert_eq!(lineage.len(), 1);
+    /// ```
+    pub fn from_items<'a>(items: impl IntoIterator<Item = &'a Item>) -> Result<Lineage> {
+        let mut lineage = Lineage::new();
+        for item in items {
+            lineage.add_item(item)?;
+        }
+        Ok(lineage)
+    }
+
+    /// Adds an item and its `derived_from` links.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Item, Link, SelfHref, lineage::Lineage};
+    ///
+    /// let mut item: Item = stac::read("examples/simple-item.json").unwrap();
+    /// item.links.push(Link::new("./source.json", "derived_from"));
+    /// let mut lineage = Lineage::new();
+    /// lineage.add_item(&item).unwrap();
+    /// let sources = lineage.sources(item.self_href().unwrap()).unwrap();
+    /// assert!(sources[0].ends_with("examples/source.json"));
+    /// ```
+    pub fn add_item(&mut self, item: &Item) -> Result<()> {
+        self.add(&item.id, item)
+    }
+
+    /// Adds a STAC value and its `derived_from` links.
+    ///
+    /// Item collections add each of their items.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Value, lineage::Lineage};
+    ///
+    /// let value: Value = stac::read("examples/collection.json").unwrap();
+    /// let mut lineage = Lineage::new();
+    /// lineage.add_value(&value).unwrap();
+    /// assert_eq!(lineage.len(), 1);
+    /// ```
+    pub fn add_value(&mut self, value: &Value) -> Result<()> {
+        match value {
+            Value::Item(item) => self.add_item(item),
+            Value::Catalog(catalog) => self.add(&catalog.id, catalog),
+            Value::Collection(collection) => self.add(&collection.id, collection),
+            Value::ItemCollection(item_collection) => {
+                for item in &item_collection.items {
+                    self.add_item(item)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn add<T: Links + SelfHref>(&mut self, id: &str, object: &T) -> Result<()> {
+        let base = object.self_href();
+        let node = base.unwrap_or(id).to_string();
+        let mut sources = Vec::new();
+        for link in object
+            .links()
+            .iter()
+            .filter(|link| link.rel == Rel::Derived)
+        {
+            let source = if let Some(base) = base {
+                let mut link: Link = link.clone();
+                link.make_absolute(base)?;
+                link.href
+            } else {
+                link.href.clone()
+            };
+            if !sources.contains(&source) {
+                sources.push(source);
+            }
+        }
+        let entry = self.sources.entry(node).or_default();
+        for source in &sources {
+            if !entry.contains(source) {
+                entry.push(source.clone());
+            }
+        }
+        for source in sources {
+            let _ = self.sources.entry(source).or_default();
+        }
+        Ok(())
+    }
+
+    /// Returns the nodes that a node was directly derived from.
+    ///
+    /// Returns `None` if the node isn't in the graph.
+    pub fn sources(&self, node: &str) -> Option<&[String]> {
+        self.sources.get(node).map(Vec::as_slice)
+    }
+
+    /// Returns the nodes that were directly derived from a node.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Item, Link, lineage::Lineage};
+    ///
+    /// let mut derived = Item::new("derived");
+    /// derived.links.push(Link::new("source", "derived_from"));
+    /// let lineage = Lineage::from_items(&[derived]).unwrap();
+    /// assert_eq!(lineage.derived("source").collect::<Vec<_>>(), ["derived"]);
+    /// ```
+    pub fn derived<'a>(&'a self, node: &'a str) -> impl Iterator<Item = &'a str> {
+        self.sources
+            .iter()
+            .filter(move |(_, sources)| sources.iter().any(|source| source == node))
+            .map(|(derived, _)| derived.as_str())
+    }
+
+    /// Returns an iterator over all nodes.
+    ///
+    /// Sources that weren't added themselves, but are pointed to by a
+    /// `derived_from` link, are included.
+    pub fn nodes(&self) -> impl Iterator<Item = &str> {
+        self.sources.keys().map(String::as_str)
+    }
+
+    /// Returns the number of nodes.
+    pub fn len(&self) -> usize {
+        self.sources.len()
+    }
+
+    /// Returns true if there are no nodes.
+    pub fn is_empty(&self) -> bool {
+        self.sources.is_empty()
+    }
+
+    /// Returns the cycles in this graph.
+    ///
+    /// Each cycle is a list of nodes, where each node is derived from the
+    /// next, and the last is derived from the first. A valid provenance chain
+    /// has no cycles.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Item, Link, lineage::Lineage};
+    ///
+    /// let mut a = Item::new("a");
+    /// a.links.push(Link::new("b", "derived_from"));
+    /// let mut b = Item::new("b");
+    /// b.links.push(Link::new("a", "derived_from"));
+    /// let lineage = Lineage::from_items(&[a, b]).unwrap();
+    /// assert_eq!(lineage.cycles(), vec![vec!["a", "b"]]);
+    /// ```
+    pub fn cycles(&self) -> Vec<Vec<String>> {
+        #[derive(Clone, Copy, PartialEq)]
+        enum State {
+            Unvisited,
+            Visiting,
+            Done,
+        }
+
+        let mut states = vec![State::Unvisited; self.sources.len()];
+        let mut cycles = Vec::new();
+        for start in 0..self.sources.len() {
+            if states[start] != State::Unvisited {
+                continue;
+            }
+            states[start] = State::Visiting;
+            let mut stack = vec![(start, 0)];
+            while let Some(&(node, next)) = stack.last() {
+                let Some(source) = self.sources[node].get(next) else {
+                    states[node] = State::Done;
+                    let _ = stack.pop();
+                    continue;
+                };
+                if let Some((_, next)) = stack.last_mut() {
+                    *next += 1;
+                }
+                let Some(source) = self.sources.get_index_of(source) else {
+                    continue;
+                };
+                match states[source] {
+                    State::Unvisited => {
+                        states[source] = State::Visiting;
+                        stack.push((source, 0));
+                    }
+                    State::Visiting => {
+                        let position = stack
+                            .iter()
+                            .position(|&(node, _)| node == source)
+                            .unwrap_or_default();
+                        cycles.push(
+                            stack[position..]
+                                .iter()
+                                .filter_map(|&(node, _)| self.sources.get_index(node))
+                                .map(|(node, _)| node.clone())
+                                .collect(),
+                        );
+                    }
+                    State::Done => {}
+                }
+            }
+        }
+        cycles
+    }
+
+    /// Writes this graph in the [DOT](https://graphviz.org/doc/info/lang.html)
+    /// language.
+    ///
+    /// Edges point from the derived node to its source.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Item, Link, lineage::Lineage};
+    ///
+    /// let mut derived = Item::new("derived");
+    /// derived.links.push(Link::new("source", "derived_from"));
+    /// let lineage = Lineage::from_items(&[derived]).unwrap();
+    /// assert!(lineage.to_dot().contains("\"derived\" -> \"source\";"));
+    /// ```
+    pub fn to_dot(&self) -> String {
+        let mut dot = "digraph lineage {\n".to_string();
+        for node in self.sources.keys() {
+            dot.push_str(&format!("  {};\n", dot_id(node)));
+        }
+        for (derived, sources) in &self.sources {
+            for source in sources {
+                dot.push_str(&format!("  {} -> {};\n", dot_id(derived), dot_id(source)));
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+fn dot_id(node: &str) -> String {
+    format!("\"{}\"", node.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Lineage;
+    use crate::{Item, Link, SelfHref};
+
+    fn item(id: &str, sources: &[&str]) -> Item {
+        let mut item = Item::new(id);
+        for source in sources {
+            item.links.push(Link::new(*source, "derived_from"));
+        }
+        item
+    }
+
+    #[test]
+    fn chain() {
+        let lineage = Lineage::from_items(&[
+            item("c", &["b"]),
+            item("b", &["a"]),
+            item("a", &[]),
+            item("d", &["a", "b", "a"]),
+        ])
+        .unwrap();
+        assert_eq!(lineage.len(), 4);
+        assert_eq!(lineage.sources("c").unwrap(), ["b"]);
+        assert_eq!(lineage.sources("d").unwrap(), ["a", "b"]);
+        assert!(lineage.sources("a").unwrap().is_empty());
+        let mut derived: Vec<_> = lineage.derived("a").collect();
+        derived.sort();
+        assert_eq!(derived, ["b", "d"]);
+        assert!(lineage.cycles().is_empty());
+    }
+
+    #[test]
+    fn absolute_hrefs() {
+        let mut derived = item("derived", &["../sources/source.json"]);
+        derived.set_self_href("/data/derived/derived.json");
+        let mut source = item("source", &[]);
+        source.set_self_href("/data/sources/source.json");
+        let lineage = Lineage::from_items(&[derived, source]).unwrap();
+        assert_eq!(lineage.len(), 2);
+        assert_eq!(
+            lineage.sources("/data/derived/derived.json").unwrap(),
+            ["/data/sources/source.json"]
+        );
+    }
+
+    #[test]
+    fn cycles() {
+        let lineage = Lineage::from_items(&[
+            item("a", &["b"]),
+            item("b", &["c"]),
+            item("c", &["a"]),
+            item("d", &["d"]),
+            item("e", &["a"]),
+        ])
+        .unwrap();
+        let cycles = lineage.cycles();
+        assert_eq!(cycles, vec![vec!["a", "b", "c"], vec!["d"]]);
+    }
+
+    #[test]
+    fn json() {
+        let lineage = Lineage::from_items(&[item("b", &["a"])]).unwrap();
+        assert_eq!(
+            serde_json::to_value(&lineage).unwrap(),
+            serde_json::json!({"b": ["a"], "a": []})
+        );
+    }
+
+    #[test]
+    fn dot() {
+        let lineage = Lineage::from_items(&[item("b", &["a \"quoted\""])]).unwrap();
+        assert_eq!(
+            lineage.to_dot(),
+            "digraph lineage {\n  \"b\";\n  \"a \\\"quoted\\\"\";\n  \"b\" -> \"a \\\"quoted\\\"\";\n}\n"
+        );
+    }
+}
@@ -5,6 +5,12 @@ use mime::APPLICATION_JSON;
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
 use stac_derive::Fields;
+use std::{
+    convert::Infallible,
+    fmt::Display,
+    hash::{Hash, Hasher},
+    str::FromStr,
+};
 
 /// Child links.
 pub const CHILD_REL: &str = "child";
@@ -19,6 +25,59 @@ pub const SELF_REL: &str = "self";
 /// Collection link.
 pub const COLLECTION_REL: &str = "collection";
 
+/// The relationship between a document and a linked document.
+///
+/// Well-known relation types get their own variants, and all others are kept
+/// as [Rel::Other]. Rels compare equal to strings, so existing string
+/// comparisons keep working:
+///
+/// ```
+/// use stac::{Link, link::Rel};
+///
+/// let link = Link::new("an-href", "child");
+/// assert_eq!(link.rel, Rel::Child);
+/// assert_eq!(link.rel, "child");
+/// assert_eq!(Rel::from("derived_from"), Rel::Other("derived_from".to_string()));
+/// ```
+///
+/// Rels are serialized as plain strings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum Rel {
+    /// `self`, the absolute location of this document.
+    SelfLink,
+
+    /// `root`, the root catalog or collection.
+    Root,
+
+    /// `parent`, the parent catalog or collection.
+    Parent,
+
+    /// `child`, a child catalog or collection.
+    Child,
+
+    /// `item`, an item.
+    Item,
+
+    /// `collection`, the collection an item belongs to.
+    Collection,
+
+    /// `next`, the next page of results.
+    Next,
+
+    /// `prev`, the previous page of results.
+    Prev,
+
+    /// `license`, the license of the data.
+    License,
+
+    /// `derived_from`, a document this document was derived from.
+    Derived,
+
+    /// Any other relation type.
+    Other(String),
+}
+
 /// This object describes a relationship with another entity.
 ///
 /// Data providers are advised to be liberal with the links section, to describe
@@ -44,7 +103,7 @@ pub struct Link {
     /// See the chapter on ["Relation
     /// types"](https://github.com/radiantearth/stac-spec/blob/master/item-spec/item-spec.md#relation-types)
     /// in the STAC spec for more information.
-    pub rel: String,
+    pub rel: Rel,
 
     /// [Media type](crate::mime) of the referenced entity.
     #[serde(rename = "type")]
@@ -129,6 +188,20 @@ pub trait Links: SelfHref {
         self.links().iter().find(|link| link.rel == rel)
     }
 
+    /// Returns the first link with the given [Rel].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Links, link::Rel};
+    /// let item: stac::Item = stac::read("examples/simple-item.json").unwrap();
+    /// let link = item.link_of(Rel::Root).unwrap();
+    /// assert!(link.is_root());
+    /// ```
+    fn link_of(&self, rel: Rel) -> Option<&Link> {
+        self.links().iter().find(|link| link.rel == rel)
+    }
+
     /// Sets a link of the given rel type.
     ///
     /// This will remove all other links of that rel type, so should only be
@@ -280,6 +353,35 @@ pub trait Links: SelfHref {
     }
 }
 
+/// Links a child (or item) to its parent in both directions.
+///
+/// The child is given the href, and `parent` and `root` links back up the
+/// tree. The parent gets the `link`, replacing any existing link with the same
+/// rel and href.
+pub(crate) fn link_child<P, C>(parent: &mut P, child: &mut C, link: Link) -> Result<()>
+where
+    P: Links + SelfHref,
+    C: Links + SelfHref,
+{
+    let parent_href = parent
+        .self_href()
+        .or(parent.self_link().map(|link| link.href.as_str()))
+        .ok_or(Error::NoHref)?
+        .to_string();
+    let root_href = parent
+        .root_link()
+        .map(|link| link.href.clone())
+        .unwrap_or_else(|| parent_href.clone());
+    child.set_self_href(&link.href);
+    child.set_link(Link::root(root_href));
+    child.set_link(Link::parent(parent_href));
+    parent
+        .links_mut()
+        .retain(|l| l.rel != link.rel || l.href != link.href);
+    parent.links_mut().push(link);
+    Ok(())
+}
+
 impl Link {
     /// Creates a new link with the provided href and rel type.
     ///
@@ -294,7 +396,7 @@ impl Link {
     pub fn new(href: impl ToString, rel: impl ToString) -> Link {
         Link {
             href: href.to_string(),
-            rel: rel.to_string(),
+            rel: rel.to_string().into(),
             r#type: None,
             title: None,
             method: None,
@@ -489,7 +591,7 @@ impl Link {
     /// assert!(!link.is_item());
     /// ```
     pub fn is_item(&self) -> bool {
-        self.rel == ITEM_REL
+        self.rel == Rel::Item
     }
 
     /// Returns true if this link's rel is `"child"`.
@@ -504,7 +606,7 @@ impl Link {
     /// assert!(!link.is_child());
     /// ```
     pub fn is_child(&self) -> bool {
-        self.rel == CHILD_REL
+        self.rel == Rel::Child
     }
 
     /// Returns true if this link's rel is `"parent"`.
@@ -519,7 +621,7 @@ impl Link {
     /// assert!(!link.is_parent());
     /// ```
     pub fn is_parent(&self) -> bool {
-        self.rel == PARENT_REL
+        self.rel == Rel::Parent
     }
 
     /// Returns true if this link's rel is `"root"`.
@@ -534,7 +636,7 @@ impl Link {
     /// assert!(!link.is_root());
     /// ```
     pub fn is_root(&self) -> bool {
-        self.rel == ROOT_REL
+        self.rel == Rel::Root
     }
 
     /// Returns true if this link's rel is `"self"`.
@@ -549,7 +651,7 @@ impl Link {
     /// assert!(!link.is_self());
     /// ```
     pub fn is_self(&self) -> bool {
-        self.rel == SELF_REL
+        self.rel == Rel::SelfLink
     }
 
     /// Returns true if this link's rel is `"collection"`.
@@ -564,7 +666,7 @@ impl Link {
     /// assert!(!link.is_collection());
     /// ```
     pub fn is_collection(&self) -> bool {
-        self.rel == COLLECTION_REL
+        self.rel == Rel::Collection
     }
 
     /// Returns true if this link is structural (i.e. not child, parent, item,
@@ -596,8 +698,8 @@ impl Link {
             || self.rel == "search"
             || self.rel == "service-desc"
             || self.rel == "service-doc"
-            || self.rel == "next"
-            || self.rel == "prev"
+            || self.rel == Rel::Next
+            || self.rel == Rel::Prev
     }
 
     /// Returns true if this link's href is an absolute path or url.
@@ -684,15 +786,162 @@ impl Link {
         Ok(())
     }
 
-    /// Makes this link relative
+    /// Makes this link relative.
+    ///
+    /// Links that can't be made relative to the base, e.g. links to a
+    /// different host or scheme, are left absolute.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::Link;
+    ///
+    /// let mut link = Link::new("http://stac.test/a/item.json", "item");
+    /// link.make_relative("http://stac.test/catalog.json");
+    /// assert_eq!(link.href, "./a/item.json");
+    ///
+    /// let mut link = Link::new("http://other.test/item.json", "item");
+    /// link.make_relative("http://stac.test/catalog.json");
+    /// assert_eq!(link.href, "http://other.test/item.json");
+    /// ```
     pub fn make_relative(&mut self, base: &str) {
         self.href = crate::href::make_relative(&self.href, base);
     }
 }
 
+impl Rel {
+    /// Returns this rel as a string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::link::Rel;
+    ///
+    /// assert_eq!(Rel::SelfLink.as_str(), "self");
+    /// assert_eq!(Rel::Other("alternate".to_string()).as_str(), "alternate");
+    /// ```
+    pub fn as_str(&self) -> &str {
+        match self {
+            Rel::SelfLink => SELF_REL,
+            Rel::Root => ROOT_REL,
+            Rel::Parent => PARENT_REL,
+            Rel::Child => CHILD_REL,
+            Rel::Item => ITEM_REL,
+            Rel::Collection => COLLECTION_REL,
+            Rel::Next => "next",
+            Rel::Prev => "prev",
+            Rel::License => "license",
+            Rel::Derived => crate::lineage::DERIVED_FROM_REL,
+            Rel::Other(rel) => rel,
+        }
+    }
+}
+
+impl From<&str> for Rel {
+    fn from(value: &str) -> Self {
+        match value {
+            SELF_REL => Rel::SelfLink,
+            ROOT_REL => Rel::Root,
+            PARENT_REL => Rel::Parent,
+            CHILD_REL => Rel::Child,
+            ITEM_REL => Rel::Item,
+            COLLECTION_REL => Rel::Collection,
+            "next" => Rel::Next,
+            "prev" => Rel::Prev,
+            "license" => Rel::License,
+            crate::lineage::DERIVED_FROM_REL => Rel::Derived,
+            _ => Rel::Other(value.to_string()),
+        }
+    }
+}
+
+impl From<String> for Rel {
+    fn from(value: String) -> Self {
+        match Rel::from(value.as_str()) {
+            Rel::Other(_) => Rel::Other(value),
+            rel => rel,
+        }
+    }
+}
+
+impl From<Rel> for String {
+    fn from(value: Rel) -> Self {
+        match value {
+            Rel::Other(rel) => rel,
+            rel => rel.as_str().to_string(),
+        }
+    }
+}
+
+impl FromStr for Rel {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Ok(s.into())
+    }
+}
+
+impl Display for Rel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl AsRef<str> for Rel {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+// Rels are compared by their string values, so e.g. `Rel::Other("self")`
+// (which can only be built by hand) equals `Rel::SelfLink`.
+impl PartialEq for Rel {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for Rel {}
+
+impl Hash for Rel {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state)
+    }
+}
+
+impl PartialEq<str> for Rel {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for Rel {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl PartialEq<String> for Rel {
+    fn eq(&self, other: &String) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<Rel> for String {
+    fn eq(&self, other: &Rel) -> bool {
+        self == other.as_str()
+    }
+}
+
+impl PartialEq<Rel> for &str {
+    fn eq(&self, other: &Rel) -> bool {
+        *self == other.as_str()
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::Link;
+    use super::{Link, Rel};
 
     #[test]
     fn new() {
@@ -703,6 +952,41 @@ mod tests {
         assert!(link.title.is_none());
     }
 
+    #[test]
+    fn rel_serde() {
+        let link: Link =
+            serde_json::from_value(serde_json::json!({"href": "an-href", "rel": "self"})).unwrap();
+        assert_eq!(link.rel, Rel::SelfLink);
+        let link: Link =
+            serde_json::from_value(serde_json::json!({"href": "an-href", "rel": "alternate"}))
+                .unwrap();
+        assert_eq!(link.rel, Rel::Other("alternate".to_string()));
+        assert_eq!(serde_json::to_value(link).unwrap()["rel"], "alternate");
+        assert_eq!(
+            serde_json::to_value(Link::new("an-href", Rel::Derived)).unwrap()["rel"],
+            "derived_from"
+        );
+    }
+
+    #[test]
+    fn make_links_relative() {
+        use crate::{Catalog, Links, SelfHref};
+
+        let mut catalog = Catalog::new("an-id", "a description");
+        catalog.set_self_href("s3://bucket/catalog.json");
+        catalog.links = vec![
+            Link::new("s3://bucket/a/item.json", "item"),
+            Link::new("s3://other-bucket/item.json", "item"),
+            Link::new("https://stac.test/item.json", "item"),
+            Link::new("/data/item.json", "item"),
+        ];
+        catalog.make_links_relative().unwrap();
+        assert_eq!(catalog.links[0].href, "./a/item.json");
+        assert_eq!(catalog.links[1].href, "s3://other-bucket/item.json");
+        assert_eq!(catalog.links[2].href, "https://stac.test/item.json");
+        assert_eq!(catalog.links[3].href, "/data/item.json");
+    }
+
     #[test]
     fn skip_serializing() {
         let link = Link::new("an-href", "a-rel");
@@ -1,4 +1,4 @@
-use crate::{Error, Result, Version};
+use crate::{Error, Result, Version, audit::FieldAudit};
 use serde::{Serialize, de::DeserializeOwned};
 use serde_json::{Map, Value};
 use std::collections::HashMap;
@@ -39,6 +39,30 @@ pub trait Migrate: Sized + Serialize + DeserializeOwned + std::fmt::Debug {
         }
         serde_json::from_value(value).map_err(Error::from)
     }
+
+    /// Migrates this object to another version, recording any top-level
+    /// fields that aren't part of the core spec.
+    ///
+    /// Migration leaves unknown fields as they are, but the audit lets you
+    /// check what you're carrying along before e.g. writing stac-geoparquet,
+    /// which drops them. See
+    /// [FieldAudit::record_unknown_fields](crate::audit::FieldAudit::record_unknown_fields).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Item, Migrate, Version, audit::FieldAudit};
+    ///
+    /// let mut item: Item = stac::read("../../spec-examples/v1.0.0/simple-item.json").unwrap();
+    /// let _ = item.additional_fields.insert("vendor_field".into(), 42.into());
+    /// let mut audit = FieldAudit::new();
+    /// let item = item.migrate_with_audit(&Version::v1_1_0, &mut audit).unwrap();
+    /// assert_eq!(audit.count("vendor_field"), 1);
+    /// ```
+    fn migrate_with_audit(self, to: &Version, audit: &mut FieldAudit) -> Result<Self> {
+        audit.record_unknown_fields(&serde_json::to_value(&self)?);
+        self.migrate(to)
+    }
 }
 
 #[allow(non_camel_case_types)]
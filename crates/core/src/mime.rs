@@ -80,6 +80,22 @@ pub const HDF: &str = "application/x-hdf";
 /// Hierarchical Data Format versions 4 and earlier.
 pub const APPLICATION_HDF: &str = "application/x-hdf";
 
+/// [NetCDF](https://www.unidata.ucar.edu/software/netcdf/)
+pub const APPLICATION_NETCDF: &str = "application/netcdf";
+
+/// [Zarr](https://zarr.dev/) (unofficial).
+///
+/// Zarr stores are directories, so a zarr asset's href points at the root of
+/// the store.
+pub const APPLICATION_ZARR: &str = "application/vnd.zarr";
+
+/// [Kerchunk](https://fsspec.github.io/kerchunk/) references, which are
+/// JSON documents that describe byte ranges in other files.
+///
+/// Kerchunk references can also be written as parquet, in which case use
+/// [APPLICATION_PARQUET].
+pub const APPLICATION_KERCHUNK_JSON: &str = "application/json";
+
 /// The OpenAPI 3.0 content type.
 pub const APPLICATION_OPENAPI_3_0: &str = "application/vnd.oai.openapi+json;version=3.0";
 
@@ -94,3 +110,30 @@ pub const APPLICATION_3DTILES: &str = "application/3dtiles+json";
 
 /// Protomaps [PMTiles](https://github.com/protomaps/PMTiles/blob/main/spec/v3/spec.md)
 pub const APPLICATION_PMTILES: &str = "application/vnd.pmtiles";
+
+/// Returns true if this media type is for a datacube format, i.e. zarr,
+/// NetCDF, or HDF.
+///
+/// Parameters are ignored, and the comparison is case-insensitive.
+///
+/// # Examples
+///
+/// ```
+/// use stac::mime;
+///
+/// assert!(mime::is_datacube(mime::APPLICATION_ZARR));
+/// assert!(mime::is_datacube("application/x-hdf5; subtype=netcdf4"));
+/// assert!(!mime::is_datacube(mime::IMAGE_COG));
+/// ```
+pub fn is_datacube(media_type: &str) -> bool {
+    let essence = media_type.split(';').next().unwrap_or_default().trim();
+    [
+        APPLICATION_ZARR,
+        APPLICATION_NETCDF,
+        "application/x-netcdf",
+        APPLICATION_HDF5,
+        APPLICATION_HDF,
+    ]
+    .iter()
+    .any(|media_type| essence.eq_ignore_ascii_case(media_type))
+}
@@ -0,0 +1,282 @@
+//! Handling for non-finite numbers, i.e. NaN and infinity.
+//!
+//! JSON can't represent NaN or infinity, so [serde_json] silently writes them
+//! as `null`, and they produce meaningless statistics in parquet files. These
+//! values usually come from upstream float statistics, e.g. a band's `mean`
+//! or `nodata`. Use [Item::replace_non_finite](crate::Item::replace_non_finite)
+//! to deal with them explicitly before writing.
+
+use crate::{Asset, Band, Bbox, Error, Item, Result, Statistics};
+use std::{fmt::Display, str::FromStr};
+
+/// What to do with non-finite numbers.
+///
+/// Defaults to [Policy::Null], which matches how [serde_json] has always
+/// written them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Policy {
+    /// Return an [Error::NonFiniteNumber].
+    Error,
+
+    /// Replace non-finite numbers with null.
+    ///
+    /// A bbox or geometry with any non-finite number is removed entirely.
+    #[default]
+    Null,
+
+    /// Clamp infinities to the largest finite values, and replace NaN with
+    /// null.
+    ///
+    /// Geometries can't be clamped, so a geometry with any non-finite number
+    /// is removed entirely.
+    Clamp,
+}
+
+impl Item {
+    /// Applies a non-finite number [Policy] to this item's bbox, geometry,
+    /// and asset `nodata` and `statistics` values.
+    ///
+    /// Returns the number of values that were replaced.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Asset, Item, non_finite::Policy};
+    ///
+    /// let mut item = Item::new("an-id");
+    /// let mut asset = Asset::new("a-href");
+    /// asset.nodata = Some(f64::NAN);
+    /// let _ = item.assets.insert("data".to_string(), asset);
+    /// assert!(item.clone().replace_non_finite(Policy::Error).is_err());
+    /// assert_eq!(item.replace_non_finite(Policy::Null).unwrap(), 1);
+    /// assert!(item.assets["data"].nodata.is_none());
+    /// ```
+    pub fn replace_non_finite(&mut self, policy: Policy) -> Result<usize> {
+        let mut replacer = Replacer {
+            policy,
+            id: &self.id,
+            count: 0,
+        };
+        if let Some(bbox) = self.bbox {
+            self.bbox = replacer.bbox(bbox)?;
+        }
+        if let Some(geometry) = self.geometry.as_ref() {
+            let value = serde_json::to_value(geometry)?;
+            if value
+                .get("coordinates")
+                .or_else(|| value.get("geometries"))
+                .is_some_and(contains_null)
+            {
+                replacer.check("geometry")?;
+                self.geometry = None;
+            }
+        }
+        for (key, asset) in self.assets.iter_mut() {
+            replacer.asset(key, asset)?;
+        }
+        Ok(replacer.count)
+    }
+}
+
+struct Replacer<'a> {
+    policy: Policy,
+    id: &'a str,
+    count: usize,
+}
+
+impl Replacer<'_> {
+    fn check(&mut self, field: &str) -> Result<()> {
+        if self.policy == Policy::Error {
+            Err(Error::NonFiniteNumber {
+                id: self.id.to_string(),
+                field: field.to_string(),
+            })
+        } else {
+            self.count += 1;
+            Ok(())
+        }
+    }
+
+    fn number(&mut self, field: &str, value: &mut Option<f64>) -> Result<()> {
+        if let Some(n) = *value
+            && !n.is_finite()
+        {
+            self.check(field)?;
+            *value = if self.policy == Policy::Clamp && !n.is_nan() {
+                Some(n.clamp(f64::MIN, f64::MAX))
+            } else {
+                None
+            };
+        }
+        Ok(())
+    }
+
+    fn bbox(&mut self, mut bbox: Bbox) -> Result<Option<Bbox>> {
+        let values: &mut [f64] = match &mut bbox {
+            Bbox::TwoDimensional(values) => values,
+            Bbox::ThreeDimensional(values) => values,
+        };
+        if values.iter().all(|n| n.is_finite()) {
+            return Ok(Some(bbox));
+        }
+        self.check("bbox")?;
+        if self.policy == Policy::Clamp && !values.iter().any(|n| n.is_nan()) {
+            for n in values.iter_mut() {
+                *n = n.clamp(f64::MIN, f64::MAX);
+            }
+            Ok(Some(bbox))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn asset(&mut self, key: &str, asset: &mut Asset) -> Result<()> {
+        let prefix = format!("assets.{key}");
+        self.number(&format!("{prefix}.nodata"), &mut asset.nodata)?;
+        if let Some(statistics) = asset.statistics.as_mut() {
+            self.statistics(&prefix, statistics)?;
+        }
+        for (i, band) in asset.bands.iter_mut().enumerate() {
+            self.band(&format!("{prefix}.bands.{i}"), band)?;
+        }
+        Ok(())
+    }
+
+    fn band(&mut self, prefix: &str, band: &mut Band) -> Result<()> {
+        self.number(&format!("{prefix}.nodata"), &mut band.nodata)?;
+        if let Some(statistics) = band.statistics.as_mut() {
+            self.statistics(prefix, statistics)?;
+        }
+        Ok(())
+    }
+
+    fn statistics(&mut self, prefix: &str, statistics: &mut Statistics) -> Result<()> {
+        let prefix = format!("{prefix}.statistics");
+        self.number(&format!("{prefix}.mean"), &mut statistics.mean)?;
+        self.number(&format!("{prefix}.minimum"), &mut statistics.minimum)?;
+        self.number(&format!("{prefix}.maximum"), &mut statistics.maximum)?;
+        self.number(&format!("{prefix}.stddev"), &mut statistics.stddev)?;
+        self.number(
+            &format!("{prefix}.valid_percent"),
+            &mut statistics.valid_percent,
+        )
+    }
+}
+
+fn contains_null(value: &serde_json::Value) -> bool {
+    match value {
+        serde_json::Value::Null => true,
+        serde_json::Value::Array(values) => values.iter().any(contains_null),
+        serde_json::Value::Object(object) => object
+            .get("coordinates")
+            .or_else(|| object.get("geometries"))
+            .is_some_and(contains_null),
+        _ => false,
+    }
+}
+
+impl FromStr for Policy {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Policy> {
+        match s {
+            "error" => Ok(Policy::Error),
+            "null" => Ok(Policy::Null),
+            "clamp" => Ok(Policy::Clamp),
+            _ => Err(Error::UnknownNonFinitePolicy(s.to_string())),
+        }
+    }
+}
+
+impl Display for Policy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Policy::Error => f.write_str("error"),
+            Policy::Null => f.write_str("null"),
+            Policy::Clamp => f.write_str("clamp"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Policy;
+    use crate::{Asset, Band, Bbox, Item, Statistics};
+
+    fn item() -> Item {
+        let mut item: Item = crate::read("examples/simple-item.json").unwrap();
+        let mut asset = Asset::new("a-href");
+        asset.bands = vec![Band {
+            name: None,
+            description: None,
+            nodata: None,
+            data_type: None,
+            statistics: Some(Statistics {
+                mean: Some(f64::NAN),
+                minimum: Some(f64::NEG_INFINITY),
+                maximum: Some(42.0),
+                stddev: None,
+                valid_percent: None,
+            }),
+            unit: None,
+            additional_fields: Default::default(),
+        }];
+        let _ = item.assets.insert("data".to_string(), asset);
+        item
+    }
+
+    #[test]
+    fn finite() {
+        let mut item: Item = crate::read("examples/simple-item.json").unwrap();
+        assert_eq!(item.replace_non_finite(Policy::Error).unwrap(), 0);
+    }
+
+    #[test]
+    fn error() {
+        let error = item().replace_non_finite(Policy::Error).unwrap_err();
+        assert!(matches!(
+            error,
+            crate::Error::NonFiniteNumber { field, .. } if field == "assets.data.bands.0.statistics.mean"
+        ));
+    }
+
+    #[test]
+    fn null() {
+        let mut item = item();
+        assert_eq!(item.replace_non_finite(Policy::Null).unwrap(), 2);
+        let statistics = item.assets["data"].bands[0].statistics.as_ref().unwrap();
+        assert_eq!(statistics.mean, None);
+        assert_eq!(statistics.minimum, None);
+        assert_eq!(statistics.maximum, Some(42.0));
+    }
+
+    #[test]
+    fn clamp() {
+        let mut item = item();
+        item.bbox = Some(Bbox::new(f64::NEG_INFINITY, 0.0, 1.0, 1.0));
+        assert_eq!(item.replace_non_finite(Policy::Clamp).unwrap(), 3);
+        let statistics = item.assets["data"].bands[0].statistics.as_ref().unwrap();
+        assert_eq!(statistics.mean, None);
+        assert_eq!(statistics.minimum, Some(f64::MIN));
+        assert_eq!(item.bbox.unwrap().xmin(), f64::MIN);
+    }
+
+    #[test]
+    fn geometry() {
+        let mut item = Item::new("an-id");
+        item.geometry = Some(geojson::Geometry::new_point(vec![f64::NAN, 0.0]));
+        assert_eq!(item.replace_non_finite(Policy::Clamp).unwrap(), 1);
+        assert!(item.geometry.is_none());
+    }
+
+    #[test]
+    fn default() {
+        assert_eq!(Policy::default(), Policy::Null);
+    }
+
+    #[test]
+    fn from_str() {
+        assert_eq!("clamp".parse::<Policy>().unwrap(), Policy::Clamp);
+        assert!("nope".parse::<Policy>().is_err());
+    }
+}
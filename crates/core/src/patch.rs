@@ -0,0 +1,472 @@
+//! [JSON Patch](https://datatracker.ietf.org/doc/html/rfc6902) and [JSON Merge
+//! Patch](https://datatracker.ietf.org/doc/html/rfc7386) for STAC objects.
+//!
+//! Items, catalogs, and collections implement [Patch]:
+//!
+//! ```
+//! use serde_json::json;
+//! use stac::{Item, Patch, patch::Operation};
+//!
+//! let mut item = Item::new("an-id");
+//! item.apply_patch(&[Operation::Add {
+//!     path: "/properties/title".to_string(),
+//!     value: json!("A title"),
+//! }])
+//! .unwrap();
+//! assert_eq!(item.properties.title.as_deref(), Some("A title"));
+//!
+//! item.apply_merge_patch(&json!({"properties": {"title": null}})).unwrap();
+//! assert!(item.properties.title.is_none());
+//! ```
+
+use crate::{Catalog, Collection, Error, Item, Result, SelfHref};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use serde_json::Value;
+use std::fmt::Display;
+
+/// A [JSON Patch](https://datatracker.ietf.org/doc/html/rfc6902) operation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum Operation {
+    /// Adds a value.
+    Add {
+        /// The JSON pointer to the value.
+        path: String,
+
+        /// The value.
+        value: Value,
+    },
+
+    /// Removes a value.
+    Remove {
+        /// The JSON pointer to the value.
+        path: String,
+    },
+
+    /// Replaces a value.
+    Replace {
+        /// The JSON pointer to the value.
+        path: String,
+
+        /// The new value.
+        value: Value,
+    },
+
+    /// Moves a value.
+    Move {
+        /// The JSON pointer to the value to move.
+        from: String,
+
+        /// The JSON pointer to move the value to.
+        path: String,
+    },
+
+    /// Copies a value.
+    Copy {
+        /// The JSON pointer to the value to copy.
+        from: String,
+
+        /// The JSON pointer to copy the value to.
+        path: String,
+    },
+
+    /// Checks that a value is equal to the expected value.
+    Test {
+        /// The JSON pointer to the value.
+        path: String,
+
+        /// The expected value.
+        value: Value,
+    },
+}
+
+/// Applies patches to STAC objects.
+///
+/// Patches are applied to the object's JSON, and the result is deserialized
+/// back into the object. If a patch can't be applied, or the patched JSON
+/// isn't a valid object, an error is returned and the object is unchanged.
+/// The object's self href is kept.
+pub trait Patch: Serialize + DeserializeOwned + SelfHref {
+    /// Applies [JSON Patch](https://datatracker.ietf.org/doc/html/rfc6902) operations.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use serde_json::json;
+    /// use stac::{Item, Patch, patch::Operation};
+    ///
+    /// let mut item = Item::new("an-id");
+    /// let result = item.apply_patch(&[
+    ///     Operation::Test { path: "/id".to_string(), value: json!("another-id") },
+    ///     Operation::Replace { path: "/id".to_string(), value: json!("a-new-id") },
+    /// ]);
+    /// assert!(result.is_err());
+    /// assert_eq!(item.id, "an-id");
+    /// ```
+    fn apply_patch(&mut self, operations: &[Operation]) -> Result<()> {
+        let mut value = serde_json::to_value(&*self)?;
+        for operation in operations {
+            apply(&mut value, operation)?;
+        }
+        replace(self, value)
+    }
+
+    /// Applies a [JSON Merge Patch](https://datatracker.ietf.org/doc/html/rfc7386).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use serde_json::json;
+    /// use stac::{Collection, Patch};
+    ///
+    /// let mut collection = Collection::new("an-id", "a description");
+    /// collection
+    ///     .apply_merge_patch(&json!({"description": "A better description"}))
+    ///     .unwrap();
+    /// assert_eq!(collection.description, "A better description");
+    /// ```
+    fn apply_merge_patch(&mut self, patch: &Value) -> Result<()> {
+        let mut value = serde_json::to_value(&*self)?;
+        merge(&mut value, patch);
+        replace(self, value)
+    }
+}
+
+impl Patch for Item {}
+impl Patch for Catalog {}
+impl Patch for Collection {}
+
+/// Applies [JSON Patch](https://datatracker.ietf.org/doc/html/rfc6902)
+/// operations to a JSON value.
+///
+/// If an operation can't be applied, an error is returned and the value is
+/// unchanged.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+/// use stac::patch::Operation;
+///
+/// let mut value = json!({"a": 1});
+/// stac::patch::apply_patch(
+///     &mut value,
+///     &[Operation::Move { from: "/a".to_string(), path: "/b".to_string() }],
+/// )
+/// .unwrap();
+/// assert_eq!(value, json!({"b": 1}));
+/// ```
+pub fn apply_patch(value: &mut Value, operations: &[Operation]) -> Result<()> {
+    let mut patched = value.clone();
+    for operation in operations {
+        apply(&mut patched, operation)?;
+    }
+    *value = patched;
+    Ok(())
+}
+
+/// Applies a [JSON Merge Patch](https://datatracker.ietf.org/doc/html/rfc7386)
+/// to a JSON value.
+///
+/// Objects are merged recursively, `null` removes a field, and anything else
+/// replaces the existing value.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+///
+/// let mut value = json!({"a": 1, "b": {"c": 2, "d": 3}});
+/// stac::patch::apply_merge_patch(&mut value, &json!({"a": null, "b": {"c": 4}}));
+/// assert_eq!(value, json!({"b": {"c": 4, "d": 3}}));
+/// ```
+pub fn apply_merge_patch(value: &mut Value, patch: &Value) {
+    merge(value, patch)
+}
+
+impl Display for Operation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Operation::Add { path, value } => write!(f, "add {path}: {value}"),
+            Operation::Remove { path } => write!(f, "remove {path}"),
+            Operation::Replace { path, value } => write!(f, "replace {path}: {value}"),
+            Operation::Move { from, path } => write!(f, "move {from} -> {path}"),
+            Operation::Copy { from, path } => write!(f, "copy {from} -> {path}"),
+            Operation::Test { path, value } => write!(f, "test {path}: {value}"),
+        }
+    }
+}
+
+fn replace<T: Patch>(object: &mut T, value: Value) -> Result<()> {
+    let mut patched: T = serde_json::from_value(value)?;
+    *patched.self_href_mut() = object.self_href_mut().take();
+    *object = patched;
+    Ok(())
+}
+
+fn apply(value: &mut Value, operation: &Operation) -> Result<()> {
+    match operation {
+        Operation::Add { path, value: new } => add(value, path, new.clone()),
+        Operation::Remove { path } => remove(value, path).map(|_| ()),
+        Operation::Replace { path, value: new } => {
+            let tokens = tokens(path)?;
+            let target =
+                get_mut(value, &tokens).ok_or_else(|| invalid(path, "value does not exist"))?;
+            *target = new.clone();
+            Ok(())
+        }
+        Operation::Move { from, path } => {
+            if path.starts_with(&format!("{from}/")) {
+                return Err(invalid(path, "cannot move a value into itself"));
+            }
+            let moved = remove(value, from)?;
+            add(value, path, moved)
+        }
+        Operation::Copy { from, path } => {
+            let tokens = tokens(from)?;
+            let copied = get_mut(value, &tokens)
+                .ok_or_else(|| invalid(from, "value does not exist"))?
+                .clone();
+            add(value, path, copied)
+        }
+        Operation::Test {
+            path,
+            value: expected,
+        } => {
+            let tokens = tokens(path)?;
+            if get_mut(value, &tokens).is_some_and(|actual| actual == expected) {
+                Ok(())
+            } else {
+                Err(invalid(path, "test failed"))
+            }
+        }
+    }
+}
+
+fn add(value: &mut Value, path: &str, new: Value) -> Result<()> {
+    let mut tokens = tokens(path)?;
+    let Some(last) = tokens.pop() else {
+        *value = new;
+        return Ok(());
+    };
+    match get_mut(value, &tokens) {
+        Some(Value::Object(object)) => {
+            let _ = object.insert(last, new);
+            Ok(())
+        }
+        Some(Value::Array(array)) => {
+            if last == "-" {
+                array.push(new);
+            } else {
+                let index = index(&last)
+                    .filter(|index| *index <= array.len())
+                    .ok_or_else(|| invalid(path, "invalid array index"))?;
+                array.insert(index, new);
+            }
+            Ok(())
+        }
+        Some(_) => Err(invalid(path, "parent is not an object or array")),
+        None => Err(invalid(path, "parent does not exist")),
+    }
+}
+
+fn remove(value: &mut Value, path: &str) -> Result<Value> {
+    let mut tokens = tokens(path)?;
+    let last = tokens
+        .pop()
+        .ok_or_else(|| invalid(path, "cannot remove the whole document"))?;
+    let removed = match get_mut(value, &tokens) {
+        Some(Value::Object(object)) => object.remove(&last),
+        Some(Value::Array(array)) => index(&last)
+            .filter(|index| *index < array.len())
+            .map(|index| array.remove(index)),
+        _ => None,
+    };
+    removed.ok_or_else(|| invalid(path, "value does not exist"))
+}
+
+fn merge(value: &mut Value, patch: &Value) {
+    if let Value::Object(patch) = patch {
+        if !value.is_object() {
+            *value = Value::Object(Default::default());
+        }
+        if let Value::Object(object) = value {
+            for (key, patch) in patch {
+                if patch.is_null() {
+                    let _ = object.remove(key);
+                } else {
+                    merge(object.entry(key.as_str()).or_insert(Value::Null), patch);
+                }
+            }
+        }
+    } else {
+        *value = patch.clone();
+    }
+}
+
+fn get_mut<'a>(value: &'a mut Value, tokens: &[String]) -> Option<&'a mut Value> {
+    tokens.iter().try_fold(value, |value, token| match value {
+        Value::Object(object) => object.get_mut(token),
+        Value::Array(array) => index(token).and_then(|index| array.get_mut(index)),
+        _ => None,
+    })
+}
+
+fn tokens(path: &str) -> Result<Vec<String>> {
+    if path.is_empty() {
+        return Ok(Vec::new());
+    }
+    let path = path
+        .strip_prefix('/')
+        .ok_or_else(|| invalid(path, "pointers must start with a slash"))?;
+    Ok(path
+        .split('/')
+        .map(|token| token.replace("~1", "/").replace("~0", "~"))
+        .collect())
+}
+
+fn index(token: &str) -> Option<usize> {
+    if token.is_empty()
+        || !token.bytes().all(|b| b.is_ascii_digit())
+        || (token.len() > 1 && token.starts_with('0'))
+    {
+        None
+    } else {
+        token.parse().ok()
+    }
+}
+
+fn invalid(path: &str, reason: &'static str) -> Error {
+    Error::InvalidPatch {
+        path: path.to_string(),
+        reason,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Operation, Patch};
+    use crate::{Item, SelfHref, diff::json_patch};
+    use serde_json::json;
+
+    #[test]
+    fn rfc_6902_examples() {
+        let mut value = json!({"foo": ["bar", "baz"]});
+        super::apply_patch(
+            &mut value,
+            &[Operation::Add {
+                path: "/foo/1".to_string(),
+                value: json!("qux"),
+            }],
+        )
+        .unwrap();
+        assert_eq!(value, json!({"foo": ["bar", "qux", "baz"]}));
+
+        let mut value = json!({"foo": {"bar": "baz", "waldo": "fred"}, "qux": {"corge": "grault"}});
+        super::apply_patch(
+            &mut value,
+            &[Operation::Move {
+                from: "/foo/waldo".to_string(),
+                path: "/qux/thud".to_string(),
+            }],
+        )
+        .unwrap();
+        assert_eq!(
+            value,
+            json!({"foo": {"bar": "baz"}, "qux": {"corge": "grault", "thud": "fred"}})
+        );
+
+        let mut value = json!({"foo": ["bar"]});
+        super::apply_patch(
+            &mut value,
+            &[Operation::Add {
+                path: "/foo/-".to_string(),
+                value: json!(["abc", "def"]),
+            }],
+        )
+        .unwrap();
+        assert_eq!(value, json!({"foo": ["bar", ["abc", "def"]]}));
+
+        let mut value = json!({"a/b": 1, "m~n": 2});
+        super::apply_patch(
+            &mut value,
+            &[
+                Operation::Test {
+                    path: "/a~1b".to_string(),
+                    value: json!(1),
+                },
+                Operation::Copy {
+                    from: "/m~0n".to_string(),
+                    path: "/c".to_string(),
+                },
+            ],
+        )
+        .unwrap();
+        assert_eq!(value, json!({"a/b": 1, "m~n": 2, "c": 2}));
+    }
+
+    #[test]
+    fn errors_leave_value_unchanged() {
+        let mut value = json!({"foo": "bar"});
+        let result = super::apply_patch(
+            &mut value,
+            &[
+                Operation::Remove {
+                    path: "/foo".to_string(),
+                },
+                Operation::Remove {
+                    path: "/baz".to_string(),
+                },
+            ],
+        );
+        assert!(result.is_err());
+        assert_eq!(value, json!({"foo": "bar"}));
+        for path in ["foo", "/foo/01", "/missing/child"] {
+            let result = super::apply_patch(
+                &mut json!({"foo": [1, 2]}),
+                &[Operation::Add {
+                    path: path.to_string(),
+                    value: json!(3),
+                }],
+            );
+            assert!(result.is_err(), "{path}");
+        }
+    }
+
+    #[test]
+    fn apply_diff() {
+        let before: Item = crate::read("examples/simple-item.json").unwrap();
+        let after: Item = crate::read("examples/extended-item.json").unwrap();
+        let operations = json_patch(
+            &serde_json::to_value(&before).unwrap(),
+            &serde_json::to_value(&after).unwrap(),
+        );
+        let mut item = before.clone();
+        item.apply_patch(&operations).unwrap();
+        assert_eq!(
+            serde_json::to_value(&item).unwrap(),
+            serde_json::to_value(&after).unwrap()
+        );
+        assert_eq!(item.self_href(), before.self_href());
+    }
+
+    #[test]
+    fn merge_patch() {
+        let mut value = json!({"title": "Goodbye!", "author": {"givenName": "John", "familyName": "Doe"}, "tags": ["example", "sample"]});
+        super::apply_merge_patch(
+            &mut value,
+            &json!({"title": "Hello!", "author": {"familyName": null}, "tags": ["example"], "phoneNumber": "+01-123-456-7890"}),
+        );
+        assert_eq!(
+            value,
+            json!({"title": "Hello!", "author": {"givenName": "John"}, "tags": ["example"], "phoneNumber": "+01-123-456-7890"})
+        );
+
+        let mut item = Item::new("an-id");
+        assert!(
+            item.apply_merge_patch(&json!({"type": "Collection"}))
+                .is_err()
+        );
+        assert_eq!(item.id, "an-id");
+    }
+}
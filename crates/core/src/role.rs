@@ -0,0 +1,365 @@
+//! Well-known asset and provider roles.
+//!
+//! Roles are free-form strings, so a typo (e.g. `thumbnails`) isn't an error,
+//! but it silently breaks any tooling that looks for the well-known role. Use
+//! [Role] and [ProviderRole] instead of string literals, and
+//! [check_roles] to find roles that look like misspellings:
+//!
+//! ```
+//! use stac::{Asset, role::Role};
+//!
+//! let asset = Asset::new("thumbnail.png").role(Role::Thumbnail);
+//! assert!(asset.has_role(Role::Thumbnail));
+//! ```
+
+use crate::{Error, Result};
+use serde_json::Value;
+use std::{fmt::Display, str::FromStr};
+
+/// A well-known [asset role](https://github.com/radiantearth/stac-spec/blob/master/best-practices.md#list-of-asset-roles).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Role {
+    /// The data itself.
+    Data,
+
+    /// A small preview, e.g. for a search result.
+    Thumbnail,
+
+    /// A larger preview, e.g. for a map, that covers the whole asset.
+    Overview,
+
+    /// Metadata sidecar files.
+    Metadata,
+
+    /// A full-resolution, true-color (or close to it) version of the data.
+    Visual,
+}
+
+/// A well-known [provider role](https://github.com/radiantearth/stac-spec/blob/master/commons/common-metadata.md#provider-object).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ProviderRole {
+    /// The organization that is licensing the dataset.
+    Licensor,
+
+    /// The producer of the data.
+    Producer,
+
+    /// A processor of the data.
+    Processor,
+
+    /// The host of the data.
+    Host,
+}
+
+/// A role that looks like a misspelling of a well-known role.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoleWarning {
+    /// The path to the role, e.g. `assets.thumbnail.roles[0]`.
+    pub path: String,
+
+    /// The role as written.
+    pub role: String,
+
+    /// The well-known role it was probably meant to be.
+    pub suggestion: &'static str,
+}
+
+impl Role {
+    /// All the well-known asset roles.
+    pub const ALL: [Role; 5] = [
+        Role::Data,
+        Role::Thumbnail,
+        Role::Overview,
+        Role::Metadata,
+        Role::Visual,
+    ];
+
+    /// Returns this role as a string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::role::Role;
+    ///
+    /// assert_eq!(Role::Thumbnail.as_str(), "thumbnail");
+    /// ```
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::Data => "data",
+            Role::Thumbnail => "thumbnail",
+            Role::Overview => "overview",
+            Role::Metadata => "metadata",
+            Role::Visual => "visual",
+        }
+    }
+}
+
+impl ProviderRole {
+    /// All the well-known provider roles.
+    pub const ALL: [ProviderRole; 4] = [
+        ProviderRole::Licensor,
+        ProviderRole::Producer,
+        ProviderRole::Processor,
+        ProviderRole::Host,
+    ];
+
+    /// Returns this role as a string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::role::ProviderRole;
+    ///
+    /// assert_eq!(ProviderRole::Host.as_str(), "host");
+    /// ```
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProviderRole::Licensor => "licensor",
+            ProviderRole::Producer => "producer",
+            ProviderRole::Processor => "processor",
+            ProviderRole::Host => "host",
+        }
+    }
+}
+
+/// Returns the well-known role that `role` is probably a misspelling of.
+///
+/// Returns `None` if `role` is well-known, or if it isn't close to any
+/// well-known role. Matching ignores case, and allows up to two edits (only
+/// one for well-known roles of five characters or fewer).
+///
+/// # Examples
+///
+/// ```
+/// use stac::role::{Role, suggest};
+///
+/// assert_eq!(suggest("thumbnails", Role::ALL.map(|r| r.as_str())), Some("thumbnail"));
+/// assert_eq!(suggest("thumbnail", Role::ALL.map(|r| r.as_str())), None);
+/// assert_eq!(suggest("cloud", Role::ALL.map(|r| r.as_str())), None);
+/// ```
+pub fn suggest(role: &str, known: impl IntoIterator<Item = &'static str>) -> Option<&'static str> {
+    let lowercase = role.to_lowercase();
+    let mut best = None;
+    for known in known {
+        if known == role {
+            return None;
+        }
+        let max_distance = if known.len() <= 5 { 1 } else { 2 };
+        let distance = edit_distance(&lowercase, known);
+        if distance <= max_distance
+            && best.is_none_or(|(_, best_distance)| distance < best_distance)
+        {
+            best = Some((known, distance));
+        }
+    }
+    best.map(|(known, _)| known)
+}
+
+/// Returns warnings for asset and provider roles that look like
+/// misspellings of well-known roles.
+///
+/// Checks the assets and item assets of items and collections, the
+/// providers of collections, and the items of item collections.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+///
+/// let warnings = stac::role::check_roles(&json!({
+///     "type": "Feature",
+///     "assets": {"preview": {"href": "preview.png", "roles": ["thumbnails"]}},
+/// }));
+/// assert_eq!(warnings[0].path, "assets.preview.roles[0]");
+/// assert_eq!(warnings[0].suggestion, "thumbnail");
+/// ```
+pub fn check_roles(value: &Value) -> Vec<RoleWarning> {
+    let mut warnings = Vec::new();
+    check_value(value, "", &mut warnings);
+    warnings
+}
+
+fn check_value(value: &Value, prefix: &str, warnings: &mut Vec<RoleWarning>) {
+    let Some(object) = value.as_object() else {
+        return;
+    };
+    for key in ["assets", "item_assets"] {
+        if let Some(assets) = object.get(key).and_then(Value::as_object) {
+            for (asset_key, asset) in assets {
+                check_list(
+                    asset.get("roles"),
+                    &format!("{prefix}{key}.{asset_key}.roles"),
+                    &Role::ALL.map(|role| role.as_str()),
+                    warnings,
+                );
+            }
+        }
+    }
+    if let Some(providers) = object.get("providers").and_then(Value::as_array) {
+        for (i, provider) in providers.iter().enumerate() {
+            check_list(
+                provider.get("roles"),
+                &format!("{prefix}providers[{i}].roles"),
+                &ProviderRole::ALL.map(|role| role.as_str()),
+                warnings,
+            );
+        }
+    }
+    if let Some(features) = object.get("features").and_then(Value::as_array) {
+        for (i, feature) in features.iter().enumerate() {
+            check_value(feature, &format!("{prefix}features[{i}]."), warnings);
+        }
+    }
+}
+
+fn check_list(
+    roles: Option<&Value>,
+    path: &str,
+    known: &[&'static str],
+    warnings: &mut Vec<RoleWarning>,
+) {
+    let Some(roles) = roles.and_then(Value::as_array) else {
+        return;
+    };
+    for (i, role) in roles.iter().enumerate() {
+        if let Some(role) = role.as_str()
+            && let Some(suggestion) = suggest(role, known.iter().copied())
+        {
+            warnings.push(RoleWarning {
+                path: format!("{path}[{i}]"),
+                role: role.to_string(),
+                suggestion,
+            });
+        }
+    }
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, &b) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a != b);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+impl Display for Role {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Role {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Role> {
+        Role::ALL
+            .into_iter()
+            .find(|role| role.as_str() == s)
+            .ok_or_else(|| Error::UnknownRole(s.to_string()))
+    }
+}
+
+impl AsRef<str> for Role {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl Display for ProviderRole {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ProviderRole {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<ProviderRole> {
+        ProviderRole::ALL
+            .into_iter()
+            .find(|role| role.as_str() == s)
+            .ok_or_else(|| Error::UnknownRole(s.to_string()))
+    }
+}
+
+impl AsRef<str> for ProviderRole {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl Display for RoleWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: unknown role \"{}\", did you mean \"{}\"?",
+            self.path, self.role, self.suggestion
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ProviderRole, Role, check_roles, suggest};
+    use serde_json::json;
+
+    #[test]
+    fn parse() {
+        for role in Role::ALL {
+            assert_eq!(role.to_string().parse::<Role>().unwrap(), role);
+        }
+        for role in ProviderRole::ALL {
+            assert_eq!(role.to_string().parse::<ProviderRole>().unwrap(), role);
+        }
+        let _ = "thumbnails".parse::<Role>().unwrap_err();
+    }
+
+    #[test]
+    fn suggestions() {
+        let roles = Role::ALL.map(|role| role.as_str());
+        assert_eq!(suggest("Thumbnail", roles), Some("thumbnail"));
+        assert_eq!(suggest("overveiw", roles), Some("overview"));
+        assert_eq!(suggest("metdata", roles), Some("metadata"));
+        assert_eq!(suggest("cloud", roles), None);
+        assert_eq!(suggest("mask", roles), None);
+        assert_eq!(suggest("vis", roles), None);
+        let roles = ProviderRole::ALL.map(|role| role.as_str());
+        assert_eq!(suggest("hosts", roles), Some("host"));
+        assert_eq!(suggest("licenser", roles), Some("licensor"));
+    }
+
+    #[test]
+    fn check_item_collection() {
+        let warnings = check_roles(&json!({
+            "type": "FeatureCollection",
+            "features": [
+                {"assets": {"data": {"href": "data.tif", "roles": ["data", "datas"]}}},
+            ],
+        }));
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].path, "features[0].assets.data.roles[1]");
+        assert_eq!(
+            warnings[0].to_string(),
+            "features[0].assets.data.roles[1]: unknown role \"datas\", did you mean \"data\"?"
+        );
+    }
+
+    #[test]
+    fn check_collection() {
+        let warnings = check_roles(&json!({
+            "type": "Collection",
+            "providers": [{"name": "a provider", "roles": ["producer", "procesor"]}],
+            "item_assets": {"visual": {"roles": ["visuals"]}},
+        }));
+        assert_eq!(warnings.len(), 2);
+        assert_eq!(warnings[0].path, "item_assets.visual.roles[0]");
+        assert_eq!(warnings[1].path, "providers[0].roles[1]");
+        assert_eq!(warnings[1].suggestion, "processor");
+    }
+}
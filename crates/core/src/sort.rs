@@ -0,0 +1,227 @@
+//! Sort items by [Sortby] fields.
+
+use crate::{
+    Item,
+    api::{Direction, Sortby},
+};
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use std::cmp::Ordering;
+
+/// Compares [items](Item) by a list of [Sortby] fields.
+///
+/// Fields are compared in order, and the first one that differs decides the
+/// ordering. A field can be `id`, `collection`, or a property, with or without
+/// a `properties.` prefix.
+///
+/// Datetime strings are compared as datetimes, numbers as numbers, and
+/// everything else as strings. Items that don't have a field, or whose value
+/// is null, an array, or an object, sort after items that do, whatever the
+/// direction. Ties are broken by collection and then id, so the ordering is
+/// deterministic.
+///
+/// # Examples
+///
+/// ```
+/// use stac::{Item, api::Sortby, sort::ItemComparator};
+///
+/// let mut items = vec![Item::new("a"), Item::new("b")];
+/// let comparator = ItemComparator::new([Sortby::desc("id")]);
+/// comparator.sort(&mut items);
+/// assert_eq!(items[0].id, "b");
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ItemComparator {
+    sortby: Vec<Sortby>,
+}
+
+#[derive(Debug)]
+enum Key<'a> {
+    Bool(bool),
+    Number(f64),
+    DateTime(DateTime<Utc>),
+    String(&'a str),
+}
+
+impl ItemComparator {
+    /// Creates a new comparator from sortby fields.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{api::Sortby, sort::ItemComparator};
+    ///
+    /// let comparator = ItemComparator::new([Sortby::desc("datetime"), Sortby::asc("id")]);
+    /// ```
+    pub fn new(sortby: impl IntoIterator<Item = Sortby>) -> ItemComparator {
+        ItemComparator {
+            sortby: sortby.into_iter().collect(),
+        }
+    }
+
+    /// Compares two items.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Item, api::Sortby, sort::ItemComparator};
+    /// use std::cmp::Ordering;
+    ///
+    /// let comparator = ItemComparator::new([Sortby::asc("id")]);
+    /// assert_eq!(comparator.compare(&Item::new("a"), &Item::new("b")), Ordering::Less);
+    /// ```
+    pub fn compare(&self, a: &Item, b: &Item) -> Ordering {
+        for sortby in &self.sortby {
+            let ordering = match (key(a, &sortby.field), key(b, &sortby.field)) {
+                (Some(a), Some(b)) => {
+                    let ordering = compare_keys(&a, &b);
+                    match sortby.direction {
+                        Direction::Ascending => ordering,
+                        Direction::Descending => ordering.reverse(),
+                    }
+                }
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            };
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+        }
+        a.collection
+            .cmp(&b.collection)
+            .then_with(|| a.id.cmp(&b.id))
+    }
+
+    /// Sorts a slice of items.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Item, api::Sortby, sort::ItemComparator};
+    ///
+    /// let mut items = vec![Item::new("b"), Item::new("a")];
+    /// ItemComparator::new([Sortby::asc("id")]).sort(&mut items);
+    /// assert_eq!(items[0].id, "a");
+    /// ```
+    pub fn sort(&self, items: &mut [Item]) {
+        items.sort_by(|a, b| self.compare(a, b));
+    }
+
+    /// Returns true if there are no sortby fields.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::sort::ItemComparator;
+    ///
+    /// assert!(ItemComparator::default().is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.sortby.is_empty()
+    }
+}
+
+fn key<'a>(item: &'a Item, field: &str) -> Option<Key<'a>> {
+    match field {
+        "id" => return Some(Key::String(&item.id)),
+        "collection" => return item.collection.as_deref().map(Key::String),
+        _ => {}
+    }
+    let properties = &item.properties;
+    match field.strip_prefix("properties.").unwrap_or(field) {
+        "datetime" => properties.datetime.map(Key::DateTime),
+        "start_datetime" => properties.start_datetime.map(Key::DateTime),
+        "end_datetime" => properties.end_datetime.map(Key::DateTime),
+        "title" => properties.title.as_deref().map(Key::String),
+        "description" => properties.description.as_deref().map(Key::String),
+        "created" => properties.created.as_deref().map(string_key),
+        "updated" => properties.updated.as_deref().map(string_key),
+        name => match properties.additional_fields.get(name)? {
+            Value::Bool(b) => Some(Key::Bool(*b)),
+            Value::Number(n) => n.as_f64().map(Key::Number),
+            Value::String(s) => Some(string_key(s)),
+            Value::Null | Value::Array(_) | Value::Object(_) => None,
+        },
+    }
+}
+
+fn string_key(s: &str) -> Key<'_> {
+    DateTime::parse_from_rfc3339(s)
+        .map(|datetime| Key::DateTime(datetime.to_utc()))
+        .unwrap_or(Key::String(s))
+}
+
+fn compare_keys(a: &Key<'_>, b: &Key<'_>) -> Ordering {
+    match (a, b) {
+        (Key::Bool(a), Key::Bool(b)) => a.cmp(b),
+        (Key::Number(a), Key::Number(b)) => a.total_cmp(b),
+        (Key::DateTime(a), Key::DateTime(b)) => a.cmp(b),
+        (Key::String(a), Key::String(b)) => a.cmp(b),
+        _ => rank(a).cmp(&rank(b)),
+    }
+}
+
+fn rank(key: &Key<'_>) -> u8 {
+    match key {
+        Key::Bool(_) => 0,
+        Key::Number(_) => 1,
+        Key::DateTime(_) => 2,
+        Key::String(_) => 3,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ItemComparator;
+    use crate::{Item, api::Sortby};
+
+    fn item(id: &str, datetime: &str, cloud_cover: Option<f64>) -> Item {
+        let mut item = Item::new(id);
+        item.properties.datetime = Some(datetime.parse().unwrap());
+        if let Some(cloud_cover) = cloud_cover {
+            let _ = item
+                .properties
+                .additional_fields
+                .insert("eo:cloud_cover".to_string(), cloud_cover.into());
+        }
+        item
+    }
+
+    #[test]
+    fn datetime_descending() {
+        let mut items = vec![
+            item("a", "2023-01-01T00:00:00Z", None),
+            item("b", "2023-01-01T00:00:00.5Z", None),
+            item("c", "2022-01-01T00:00:00Z", None),
+        ];
+        ItemComparator::new(["-properties.datetime".parse::<Sortby>().unwrap()]).sort(&mut items);
+        let ids: Vec<_> = items.iter().map(|item| item.id.as_str()).collect();
+        assert_eq!(ids, ["b", "a", "c"]);
+    }
+
+    #[test]
+    fn missing_last() {
+        let mut items = vec![
+            item("a", "2023-01-01T00:00:00Z", None),
+            item("b", "2023-01-01T00:00:00Z", Some(10.0)),
+            item("c", "2023-01-01T00:00:00Z", Some(2.0)),
+        ];
+        ItemComparator::new([Sortby::desc("eo:cloud_cover")]).sort(&mut items);
+        let ids: Vec<_> = items.iter().map(|item| item.id.as_str()).collect();
+        assert_eq!(ids, ["b", "c", "a"]);
+        ItemComparator::new([Sortby::asc("eo:cloud_cover")]).sort(&mut items);
+        let ids: Vec<_> = items.iter().map(|item| item.id.as_str()).collect();
+        assert_eq!(ids, ["c", "b", "a"]);
+    }
+
+    #[test]
+    fn ties_break_by_id() {
+        let mut items = vec![
+            item("b", "2023-01-01T00:00:00Z", None),
+            item("a", "2023-01-01T00:00:00Z", None),
+        ];
+        ItemComparator::new([Sortby::asc("datetime")]).sort(&mut items);
+        assert_eq!(items[0].id, "a");
+    }
+}
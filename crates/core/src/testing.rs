@@ -0,0 +1,374 @@
+//! Deterministic generation of synthetic items.
+//!
+//! Useful for benchmarks, load testing, and reproducing bugs without real
+//! data. The same [Generator] settings (including the seed) always produce the
+//! same items, on every platform and in every version of this crate.
+//!
+//! ```
+//! use stac::{Bbox, testing::Generator};
+//!
+//! let items = Generator::new(100)
+//!     .seed(42)
+//!     .bbox(Bbox::new(-106., 39., -104., 41.))
+//!     .property("platform", 3)
+//!     .assets(2)
+//!     .generate();
+//! assert_eq!(items.len(), 100);
+//! assert_eq!(items, Generator::new(100)
+//!     .seed(42)
+//!     .bbox(Bbox::new(-106., 39., -104., 41.))
+//!     .property("platform", 3)
+//!     .assets(2)
+//!     .generate());
+//! ```
+
+use crate::{Asset, Bbox, Item, mime};
+use chrono::{DateTime, TimeZone, Utc};
+
+/// The default seed.
+pub const DEFAULT_SEED: u64 = 0;
+
+/// How item footprints are distributed within the bounding box.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Distribution {
+    /// Footprint centers are uniformly random.
+    #[default]
+    Uniform,
+
+    /// Footprints are laid out in a regular grid, in row-major order.
+    Grid,
+
+    /// Footprint centers are grouped around randomly placed cluster centers.
+    Clustered {
+        /// The number of clusters.
+        clusters: usize,
+
+        /// The maximum distance of a footprint center from its cluster
+        /// center, as a fraction of the bounding box's width and height.
+        spread: f64,
+    },
+}
+
+/// Generates synthetic items.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Generator {
+    count: usize,
+    seed: u64,
+    bbox: Bbox,
+    distribution: Distribution,
+    footprint_size: f64,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    properties: Vec<(String, usize)>,
+    assets: usize,
+    collection: Option<String>,
+    id_prefix: String,
+}
+
+/// A small, fast, and stable pseudo-random number generator (SplitMix64).
+///
+/// We use our own instead of a crate so that output doesn't change when a
+/// dependency is upgraded.
+#[derive(Debug, Clone)]
+struct Rng(u64);
+
+impl Generator {
+    /// Creates a new generator for `count` items.
+    ///
+    /// By default, items have one-degree-square footprints uniformly
+    /// distributed over the whole world, datetimes in 2024, no extra
+    /// properties, and one asset.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::testing::Generator;
+    ///
+    /// let items = Generator::new(10).generate();
+    /// assert_eq!(items[0].id, "item-0");
+    /// ```
+    pub fn new(count: usize) -> Generator {
+        Generator {
+            count,
+            seed: DEFAULT_SEED,
+            bbox: Bbox::new(-180., -90., 180., 90.),
+            distribution: Distribution::default(),
+            footprint_size: 1.,
+            start: Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
+            end: Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap(),
+            properties: Vec::new(),
+            assets: 1,
+            collection: None,
+            id_prefix: "item-".to_string(),
+        }
+    }
+
+    /// Sets the random seed.
+    pub fn seed(mut self, seed: u64) -> Generator {
+        self.seed = seed;
+        self
+    }
+
+    /// Sets the bounding box that all footprints fall within.
+    pub fn bbox(mut self, bbox: Bbox) -> Generator {
+        self.bbox = bbox;
+        self
+    }
+
+    /// Sets the spatial distribution of footprints.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::testing::{Distribution, Generator};
+    ///
+    /// let generator = Generator::new(10).distribution(Distribution::Clustered {
+    ///     clusters: 2,
+    ///     spread: 0.1,
+    /// });
+    /// ```
+    pub fn distribution(mut self, distribution: Distribution) -> Generator {
+        self.distribution = distribution;
+        self
+    }
+
+    /// Sets the width and height of each footprint, in degrees.
+    ///
+    /// Footprints are clipped to the bounding box.
+    pub fn footprint_size(mut self, footprint_size: f64) -> Generator {
+        self.footprint_size = footprint_size;
+        self
+    }
+
+    /// Sets the range of item datetimes, with `start` inclusive and `end`
+    /// exclusive.
+    ///
+    /// Datetimes are uniformly distributed and truncated to the second.
+    pub fn datetimes(mut self, start: DateTime<Utc>, end: DateTime<Utc>) -> Generator {
+        self.start = start;
+        self.end = end;
+        self
+    }
+
+    /// Adds a string property with the given number of distinct values.
+    ///
+    /// Values look like `{name}-{n}`, where `n` is less than `cardinality`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::testing::Generator;
+    ///
+    /// let items = Generator::new(10).property("platform", 1).generate();
+    /// assert_eq!(items[0].properties.additional_fields["platform"], "platform-0");
+    /// ```
+    pub fn property(mut self, name: impl ToString, cardinality: usize) -> Generator {
+        self.properties.push((name.to_string(), cardinality.max(1)));
+        self
+    }
+
+    /// Sets the number of assets on each item.
+    pub fn assets(mut self, assets: usize) -> Generator {
+        self.assets = assets;
+        self
+    }
+
+    /// Sets the collection id on each item.
+    pub fn collection(mut self, collection: impl ToString) -> Generator {
+        self.collection = Some(collection.to_string());
+        self
+    }
+
+    /// Sets the prefix of item ids, which are the prefix followed by the
+    /// item's index.
+    pub fn id_prefix(mut self, id_prefix: impl ToString) -> Generator {
+        self.id_prefix = id_prefix.to_string();
+        self
+    }
+
+    /// Returns an iterator over the generated items.
+    ///
+    /// Items are generated lazily, so this can be used to produce more items
+    /// than fit in memory.
+    pub fn items(&self) -> impl Iterator<Item = Item> + '_ {
+        let mut rng = Rng(self.seed);
+        let clusters: Vec<(f64, f64)> = match self.distribution {
+            Distribution::Clustered { clusters, .. } => (0..clusters.max(1))
+                .map(|_| (rng.next_f64(), rng.next_f64()))
+                .collect(),
+            _ => Vec::new(),
+        };
+        (0..self.count).map(move |i| self.item(i, &mut rng, &clusters))
+    }
+
+    /// Generates all items.
+    pub fn generate(&self) -> Vec<Item> {
+        self.items().collect()
+    }
+
+    fn item(&self, i: usize, rng: &mut Rng, clusters: &[(f64, f64)]) -> Item {
+        let mut item = Item::new(format!("{}{i}", self.id_prefix));
+        let (x, y) = match self.distribution {
+            Distribution::Uniform => (rng.next_f64(), rng.next_f64()),
+            Distribution::Grid => {
+                let columns = (self.count as f64).sqrt().ceil().max(1.) as usize;
+                let rows = self.count.div_ceil(columns).max(1);
+                (
+                    ((i % columns) as f64 + 0.5) / columns as f64,
+                    ((i / columns) as f64 + 0.5) / rows as f64,
+                )
+            }
+            Distribution::Clustered { spread, .. } => {
+                let (cx, cy) = clusters[rng.next_below(clusters.len() as u64) as usize];
+                (
+                    (cx + (rng.next_f64() * 2. - 1.) * spread).clamp(0., 1.),
+                    (cy + (rng.next_f64() * 2. - 1.) * spread).clamp(0., 1.),
+                )
+            }
+        };
+        let (xmin, ymin, xmax, ymax) = (
+            self.bbox.xmin(),
+            self.bbox.ymin(),
+            self.bbox.xmax(),
+            self.bbox.ymax(),
+        );
+        let x = xmin + x * (xmax - xmin);
+        let y = ymin + y * (ymax - ymin);
+        let half = self.footprint_size / 2.;
+        let bbox = Bbox::new(
+            (x - half).max(xmin),
+            (y - half).max(ymin),
+            (x + half).min(xmax),
+            (y + half).min(ymax),
+        );
+        item.geometry = Some(bbox.to_geometry());
+        item.bbox = Some(bbox);
+
+        let seconds = (self.end - self.start).num_seconds().max(1) as u64;
+        item.properties.datetime =
+            Some(self.start + chrono::Duration::seconds(rng.next_below(seconds) as i64));
+
+        for (name, cardinality) in &self.properties {
+            let value = rng.next_below(*cardinality as u64);
+            let _ = item
+                .properties
+                .additional_fields
+                .insert(name.clone(), format!("{name}-{value}").into());
+        }
+        for j in 0..self.assets {
+            let key = format!("asset-{j}");
+            let mut asset =
+                Asset::new(format!("https://example.com/{}/{key}.tif", item.id)).role("data");
+            asset.r#type = Some(mime::COG.to_string());
+            let _ = item.assets.insert(key, asset);
+        }
+        item.collection = self.collection.clone();
+        item
+    }
+}
+
+impl Rng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a float in `[0, 1)`.
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Returns an integer in `[0, n)`.
+    fn next_below(&mut self, n: u64) -> u64 {
+        self.next_u64() % n.max(1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Distribution, Generator};
+    use crate::Bbox;
+    use chrono::{TimeZone, Utc};
+    use std::collections::HashSet;
+
+    #[test]
+    fn deterministic() {
+        let generator = Generator::new(50).seed(1).property("foo", 5).assets(3);
+        assert_eq!(generator.generate(), generator.generate());
+        assert_ne!(generator.generate(), generator.clone().seed(2).generate());
+    }
+
+    #[test]
+    fn within_bbox() {
+        let bbox = Bbox::new(-106., 39., -104., 41.);
+        for distribution in [
+            Distribution::Uniform,
+            Distribution::Grid,
+            Distribution::Clustered {
+                clusters: 3,
+                spread: 0.5,
+            },
+        ] {
+            for item in Generator::new(100)
+                .bbox(bbox)
+                .distribution(distribution)
+                .footprint_size(0.5)
+                .items()
+            {
+                let item_bbox = item.bbox.unwrap();
+                assert!(item_bbox.xmin() >= bbox.xmin(), "{distribution:?}");
+                assert!(item_bbox.ymin() >= bbox.ymin(), "{distribution:?}");
+                assert!(item_bbox.xmax() <= bbox.xmax(), "{distribution:?}");
+                assert!(item_bbox.ymax() <= bbox.ymax(), "{distribution:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn grid() {
+        let items = Generator::new(4)
+            .bbox(Bbox::new(0., 0., 2., 2.))
+            .distribution(Distribution::Grid)
+            .footprint_size(1.)
+            .generate();
+        assert_eq!(items[0].bbox.unwrap(), Bbox::new(0., 0., 1., 1.));
+        assert_eq!(items[3].bbox.unwrap(), Bbox::new(1., 1., 2., 2.));
+    }
+
+    #[test]
+    fn datetimes() {
+        let start = Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2020, 1, 2, 0, 0, 0).unwrap();
+        for item in Generator::new(100).datetimes(start, end).items() {
+            let datetime = item.properties.datetime.unwrap();
+            assert!(datetime >= start && datetime < end);
+        }
+    }
+
+    #[test]
+    fn cardinality() {
+        let values: HashSet<_> = Generator::new(1000)
+            .property("platform", 3)
+            .items()
+            .map(|item| item.properties.additional_fields["platform"].clone())
+            .collect();
+        assert_eq!(values.len(), 3);
+    }
+
+    #[test]
+    fn assets_and_collection() {
+        let item = Generator::new(1)
+            .assets(4)
+            .collection("a-collection")
+            .id_prefix("test-")
+            .generate()
+            .pop()
+            .unwrap();
+        assert_eq!(item.id, "test-0");
+        assert_eq!(item.assets.len(), 4);
+        assert_eq!(item.collection.as_deref(), Some("a-collection"));
+    }
+}
@@ -1,7 +1,8 @@
 use crate::{
-    Catalog, Collection, Error, Item, ItemCollection, Link, Links, Migrate, Result, SelfHref,
-    Version,
+    Asset, Assets, Catalog, Collection, Error, Item, ItemCollection, Link, Links, Migrate, Result,
+    SelfHref, Version,
 };
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use serde_json::Map;
 use std::convert::TryFrom;
@@ -185,6 +186,129 @@ impl Value {
             ItemCollection(_) => "ItemCollection",
         }
     }
+
+    /// Returns a reference to this value's assets.
+    ///
+    /// Returns `None` for catalogs and item collections, which don't have
+    /// assets.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Catalog, Item, Value};
+    ///
+    /// assert!(Value::Item(Item::new("an-id")).assets().is_some());
+    /// assert!(Value::Catalog(Catalog::new("an-id", "a description")).assets().is_none());
+    /// ```
+    pub fn assets(&self) -> Option<&IndexMap<String, Asset>> {
+        match self {
+            Value::Item(item) => Some(item.assets()),
+            Value::Collection(collection) => Some(collection.assets()),
+            Value::Catalog(_) | Value::ItemCollection(_) => None,
+        }
+    }
+
+    /// Returns a mutable reference to this value's assets.
+    ///
+    /// Returns `None` for catalogs and item collections, which don't have
+    /// assets.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Asset, Item, Value};
+    ///
+    /// let mut value = Value::Item(Item::new("an-id"));
+    /// value.assets_mut().unwrap().insert("data".into(), Asset::new("./data.tif"));
+    /// ```
+    pub fn assets_mut(&mut self) -> Option<&mut IndexMap<String, Asset>> {
+        match self {
+            Value::Item(item) => Some(item.assets_mut()),
+            Value::Collection(collection) => Some(collection.assets_mut()),
+            Value::Catalog(_) | Value::ItemCollection(_) => None,
+        }
+    }
+
+    /// Returns the keys and assets that have a role.
+    ///
+    /// See [Assets::assets_with_role].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Value, role::Role};
+    ///
+    /// let value: Value = stac::read("examples/simple-item.json").unwrap();
+    /// assert_eq!(value.assets_with_role(Role::Visual).count(), 1);
+    /// ```
+    pub fn assets_with_role(&self, role: impl AsRef<str>) -> impl Iterator<Item = (&str, &Asset)> {
+        self.assets()
+            .into_iter()
+            .flatten()
+            .filter(move |(_, asset)| asset.roles.iter().any(|r| r == role.as_ref()))
+            .map(|(key, asset)| (key.as_str(), asset))
+    }
+
+    /// Returns the thumbnail asset.
+    ///
+    /// See [Assets::thumbnail].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::Value;
+    ///
+    /// let value: Value = stac::read("examples/simple-item.json").unwrap();
+    /// assert!(value.thumbnail().is_some());
+    /// ```
+    pub fn thumbnail(&self) -> Option<&Asset> {
+        match self {
+            Value::Item(item) => item.thumbnail(),
+            Value::Collection(collection) => collection.thumbnail(),
+            Value::Catalog(_) | Value::ItemCollection(_) => None,
+        }
+    }
+
+    /// Returns the key and the first asset with a media type.
+    ///
+    /// See [Assets::asset_by_media_type].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::Value;
+    ///
+    /// let value: Value = stac::read("examples/simple-item.json").unwrap();
+    /// assert!(value.asset_by_media_type("image/tiff").is_some());
+    /// ```
+    pub fn asset_by_media_type(&self, media_type: &str) -> Option<(&str, &Asset)> {
+        match self {
+            Value::Item(item) => item.asset_by_media_type(media_type),
+            Value::Collection(collection) => collection.asset_by_media_type(media_type),
+            Value::Catalog(_) | Value::ItemCollection(_) => None,
+        }
+    }
+
+    /// Keeps only the assets for which a predicate returns true.
+    ///
+    /// Does nothing for catalogs and item collections.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Value, role::Role};
+    ///
+    /// let mut value: Value = stac::read("examples/simple-item.json").unwrap();
+    /// value.retain_assets(|_, asset| asset.has_role(Role::Visual));
+    /// assert_eq!(value.assets().unwrap().len(), 1);
+    /// ```
+    pub fn retain_assets(&mut self, f: impl FnMut(&str, &Asset) -> bool) {
+        match self {
+            Value::Item(item) => item.retain_assets(f),
+            Value::Collection(collection) => collection.retain_assets(f),
+            Value::Catalog(_) | Value::ItemCollection(_) => {}
+        }
+    }
 }
 
 impl SelfHref for Value {
@@ -0,0 +1,211 @@
+//! Polygon winding order, as described by [RFC 7946](https://datatracker.ietf.org/doc/html/rfc7946#section-3.1.6).
+//!
+//! RFC 7946 says that polygon exterior rings should be counterclockwise and
+//! holes should be clockwise. Plenty of producers get this backwards, and some
+//! strict GeoJSON consumers will reject those geometries.
+//!
+//! These functions work on GeoJSON geometries as [serde_json::Value]s so they
+//! don't require the `geo` feature. Most users will want
+//! [Item::normalize_winding_order](crate::Item::normalize_winding_order) and
+//! [Item::has_rfc7946_winding_order](crate::Item::has_rfc7946_winding_order)
+//! instead.
+
+use serde_json::Value;
+
+/// Rewinds all polygon rings in a GeoJSON geometry so that exteriors are
+/// counterclockwise and holes are clockwise.
+///
+/// Returns `true` if any ring was reversed. Values that aren't polygonal
+/// geometries are left alone.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+///
+/// let mut geometry = json!({
+///     "type": "Polygon",
+///     "coordinates": [[[0.0, 0.0], [0.0, 1.0], [1.0, 1.0], [1.0, 0.0], [0.0, 0.0]]]
+/// });
+/// assert!(stac::winding::normalize(&mut geometry));
+/// assert!(stac::winding::is_normalized(&geometry));
+/// ```
+pub fn normalize(geometry: &mut Value) -> bool {
+    let Some(object) = geometry.as_object_mut() else {
+        return false;
+    };
+    match object.get("type").and_then(Value::as_str) {
+        Some("Polygon") => object
+            .get_mut("coordinates")
+            .map(normalize_polygon)
+            .unwrap_or_default(),
+        Some("MultiPolygon") => object
+            .get_mut("coordinates")
+            .and_then(Value::as_array_mut)
+            .map(|polygons| {
+                polygons.iter_mut().fold(false, |changed, polygon| {
+                    normalize_polygon(polygon) | changed
+                })
+            })
+            .unwrap_or_default(),
+        Some("GeometryCollection") => object
+            .get_mut("geometries")
+            .and_then(Value::as_array_mut)
+            .map(|geometries| {
+                geometries
+                    .iter_mut()
+                    .fold(false, |changed, geometry| normalize(geometry) | changed)
+            })
+            .unwrap_or_default(),
+        _ => false,
+    }
+}
+
+/// Returns true if all polygon rings in this GeoJSON geometry follow the RFC
+/// 7946 winding order.
+///
+/// Degenerate rings (with zero area) are always considered to be correctly
+/// wound.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+///
+/// let geometry = json!({
+///     "type": "Polygon",
+///     "coordinates": [[[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0], [0.0, 0.0]]]
+/// });
+/// assert!(stac::winding::is_normalized(&geometry));
+/// ```
+pub fn is_normalized(geometry: &Value) -> bool {
+    match geometry.get("type").and_then(Value::as_str) {
+        Some("Polygon") => geometry
+            .get("coordinates")
+            .map(is_polygon_normalized)
+            .unwrap_or(true),
+        Some("MultiPolygon") => geometry
+            .get("coordinates")
+            .and_then(Value::as_array)
+            .map(|polygons| polygons.iter().all(is_polygon_normalized))
+            .unwrap_or(true),
+        Some("GeometryCollection") => geometry
+            .get("geometries")
+            .and_then(Value::as_array)
+            .map(|geometries| geometries.iter().all(is_normalized))
+            .unwrap_or(true),
+        _ => true,
+    }
+}
+
+fn normalize_polygon(rings: &mut Value) -> bool {
+    let mut changed = false;
+    if let Some(rings) = rings.as_array_mut() {
+        for (i, ring) in rings.iter_mut().enumerate() {
+            if !is_ring_normalized(ring, i == 0)
+                && let Some(positions) = ring.as_array_mut()
+            {
+                positions.reverse();
+                changed = true;
+            }
+        }
+    }
+    changed
+}
+
+fn is_polygon_normalized(rings: &Value) -> bool {
+    rings
+        .as_array()
+        .map(|rings| {
+            rings
+                .iter()
+                .enumerate()
+                .all(|(i, ring)| is_ring_normalized(ring, i == 0))
+        })
+        .unwrap_or(true)
+}
+
+fn is_ring_normalized(ring: &Value, exterior: bool) -> bool {
+    match signed_area(ring) {
+        Some(area) if area > 0.0 => exterior,
+        Some(area) if area < 0.0 => !exterior,
+        _ => true,
+    }
+}
+
+/// Twice the signed area of the ring, positive when counterclockwise.
+fn signed_area(ring: &Value) -> Option<f64> {
+    let positions = ring
+        .as_array()?
+        .iter()
+        .map(|position| {
+            let x = position.get(0).and_then(Value::as_f64)?;
+            let y = position.get(1).and_then(Value::as_f64)?;
+            Some((x, y))
+        })
+        .collect::<Option<Vec<_>>>()?;
+    Some(
+        positions
+            .windows(2)
+            .map(|pair| pair[0].0 * pair[1].1 - pair[1].0 * pair[0].1)
+            .sum(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    #[test]
+    fn clockwise_exterior() {
+        let mut geometry = json!({
+            "type": "Polygon",
+            "coordinates": [[[0.0, 0.0], [0.0, 1.0], [1.0, 1.0], [1.0, 0.0], [0.0, 0.0]]]
+        });
+        assert!(!super::is_normalized(&geometry));
+        assert!(super::normalize(&mut geometry));
+        assert_eq!(
+            geometry["coordinates"][0],
+            json!([[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0], [0.0, 0.0]])
+        );
+        assert!(!super::normalize(&mut geometry));
+    }
+
+    #[test]
+    fn counterclockwise_hole() {
+        let mut geometry = json!({
+            "type": "MultiPolygon",
+            "coordinates": [[
+                [[0.0, 0.0], [4.0, 0.0], [4.0, 4.0], [0.0, 4.0], [0.0, 0.0]],
+                [[1.0, 1.0], [2.0, 1.0], [2.0, 2.0], [1.0, 2.0], [1.0, 1.0]]
+            ]]
+        });
+        assert!(!super::is_normalized(&geometry));
+        assert!(super::normalize(&mut geometry));
+        assert!(super::is_normalized(&geometry));
+        assert_eq!(geometry["coordinates"][0][1][1], json!([1.0, 2.0]));
+    }
+
+    #[test]
+    fn geometry_collection() {
+        let mut geometry = json!({
+            "type": "GeometryCollection",
+            "geometries": [
+                {"type": "Point", "coordinates": [0.0, 0.0]},
+                {
+                    "type": "Polygon",
+                    "coordinates": [[[0.0, 0.0], [0.0, 1.0], [1.0, 1.0], [0.0, 0.0]]]
+                }
+            ]
+        });
+        assert!(super::normalize(&mut geometry));
+        assert!(super::is_normalized(&geometry));
+    }
+
+    #[test]
+    fn not_polygonal() {
+        let mut geometry = json!({"type": "LineString", "coordinates": [[0.0, 0.0], [1.0, 1.0]]});
+        assert!(super::is_normalized(&geometry));
+        assert!(!super::normalize(&mut geometry));
+    }
+}
@@ -1,4 +1,8 @@
-use crate::{Error, Extension, Result};
+use crate::{
+    Error, Extension, FieldStatistics, FileStatistics, NullCount, Percentile, Result,
+    RowGroupStatistics, ValueCount,
+    statistics::{PERCENTILES, VALUE_COUNT_LIMIT},
+};
 use arrow_array::{RecordBatch, RecordBatchIterator};
 use arrow_schema::{ArrowError, SchemaRef};
 use chrono::DateTime;
@@ -6,15 +10,27 @@ use cql2::{Expr, ToDuckSQL};
 use duckdb::{Connection, Statement, types::Value};
 use geo::BoundingRect;
 use geojson::GeometryValue;
+use serde_json::Map;
 #[cfg(feature = "async")]
 use stac::api::StreamItemsClient;
 use stac::api::{
     ArrowItemsClient, CollectionsClient, Direction, ItemsClient, RecordBatchReaderAdapter, Search,
 };
-use stac::{Collection, SpatialExtent, TemporalExtent, geoarrow::DATETIME_COLUMNS};
+use stac::{Bbox, Collection, Extent, SpatialExtent, TemporalExtent, geoarrow::DATETIME_COLUMNS};
+use std::collections::{HashMap, HashSet};
 use std::ops::{Deref, DerefMut};
 use std::sync::Mutex;
 
+/// Columns of stac-geoparquet that aren't queryable.
+const NON_QUERYABLE_COLUMNS: [&str; 6] = [
+    "type",
+    "stac_version",
+    "stac_extensions",
+    "bbox",
+    "links",
+    "assets",
+];
+
 /// Default hive partitioning value
 pub const DEFAULT_USE_HIVE_PARTITIONING: bool = false;
 
@@ -25,12 +41,18 @@ pub const DEFAULT_CONVERT_WKB: bool = true;
 pub const DEFAULT_COLLECTION_DESCRIPTION: &str =
     "Auto-generated collection from stac-geoparquet extents";
 
+/// The parquet key-value metadata key of the stac-geoparquet metadata.
+const STAC_GEOPARQUET_METADATA_KEY: &str = "stac-geoparquet";
+
 /// The default union by name value.
 pub const DEFAULT_UNION_BY_NAME: bool = true;
 
 /// Whether to remove the filename column by default.
 pub const DEFAULT_REMOVE_FILENAME_COLUMN: bool = true;
 
+/// Whether to treat `*` in search collections as a wildcard by default.
+pub const DEFAULT_COLLECTION_WILDCARDS: bool = false;
+
 /// A client for making DuckDB requests for STAC objects.
 #[derive(Debug)]
 pub struct Client {
@@ -39,6 +61,12 @@ pub struct Client {
     /// Whether to use hive partitioning
     pub use_hive_partitioning: bool,
 
+    /// Hrefs that are read with hive partitioning, even if
+    /// `use_hive_partitioning` is false.
+    ///
+    /// Lets one query mix hive-partitioned directories with plain files.
+    pub hive_partitioned_hrefs: HashSet<String>,
+
     /// Whether to convert WKB to native geometries.
     ///
     /// If False, WKB metadata will be added.
@@ -53,6 +81,12 @@ pub struct Client {
     ///
     /// Defaults to true.
     pub remove_filename_column: bool,
+
+    /// Whether to treat `*` in search collections as a wildcard, e.g.
+    /// `sentinel-*` matches every collection whose id starts with `sentinel-`.
+    ///
+    /// This isn't part of the STAC API spec, so it defaults to false.
+    pub collection_wildcards: bool,
 }
 
 impl Client {
@@ -112,6 +146,10 @@ impl Client {
 
     /// Returns one or more [stac::Collection] from the items in the stac-geoparquet file.
     ///
+    /// Collections that are embedded in the file's stac-geoparquet metadata
+    /// are returned as-is. For any other collection ids in the items, a
+    /// collection is generated with extents computed from the items.
+    ///
     /// # Examples
     ///
     /// ```
@@ -121,6 +159,78 @@ impl Client {
     /// let collections = client.collections("data/100-sentinel-2-items.parquet").unwrap();
     /// ```
     pub fn collections(&self, href: &str) -> Result<Vec<Collection>> {
+        let (start_datetime, end_datetime) = self.datetime_bounds(href)?;
+        let mut embedded = self.embedded_collections(href)?;
+        let mut statement = self.prepare(&format!(
+            "SELECT DISTINCT collection FROM {}",
+            self.format_parquet_href(href)
+        ))?;
+        let mut collections = Vec::new();
+        for row in statement.query_map([], |row| row.get::<_, String>(0))? {
+            let collection_id = row?;
+            if let Some(collection) = embedded.remove(&collection_id) {
+                collections.push(collection);
+                continue;
+            }
+            let mut collection = Collection::new(&collection_id, DEFAULT_COLLECTION_DESCRIPTION);
+            collection.extent =
+                self.extent_with_bounds(href, &collection_id, start_datetime, end_datetime)?;
+            collections.push(collection);
+        }
+        let mut embedded: Vec<_> = embedded.into_values().collect();
+        embedded.sort_by(|a, b| a.id.cmp(&b.id));
+        collections.extend(embedded);
+        Ok(collections)
+    }
+
+    /// Returns the number of items in a collection.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_duckdb::Client;
+    ///
+    /// let client = Client::new().unwrap();
+    /// let count = client
+    ///     .item_count("data/100-sentinel-2-items.parquet", "sentinel-2-l2a")
+    ///     .unwrap();
+    /// assert_eq!(count, 100);
+    /// ```
+    pub fn item_count(&self, href: &str, collection_id: &str) -> Result<u64> {
+        let count = self
+            .prepare(&format!(
+                "SELECT count(*) FROM {} WHERE collection = $1",
+                self.format_parquet_href(href)
+            ))?
+            .query_row([collection_id], |row| row.get::<_, i64>(0))?;
+        Ok(count.try_into()?)
+    }
+
+    /// Computes a collection's extent from its items.
+    ///
+    /// Unlike [Client::collections], this ignores any collections embedded in
+    /// the stac-geoparquet metadata. The collection must have at least one
+    /// item.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_duckdb::Client;
+    ///
+    /// let client = Client::new().unwrap();
+    /// let extent = client
+    ///     .extent("data/100-sentinel-2-items.parquet", "sentinel-2-l2a")
+    ///     .unwrap();
+    /// assert_eq!(extent.spatial.bbox.len(), 1);
+    /// ```
+    pub fn extent(&self, href: &str, collection_id: &str) -> Result<Extent> {
+        let (start_datetime, end_datetime) = self.datetime_bounds(href)?;
+        self.extent_with_bounds(href, collection_id, start_datetime, end_datetime)
+    }
+
+    /// Returns the sql expressions for the start and end of the temporal
+    /// extent, which depend on whether the file has range columns.
+    fn datetime_bounds(&self, href: &str) -> Result<(&'static str, &'static str)> {
         let start_datetime= if self.prepare(&format!(
             "SELECT column_name FROM (DESCRIBE SELECT * from {}) where column_name = 'start_datetime'",
             self.format_parquet_href(href)
@@ -142,44 +252,411 @@ impl Client {
         } else {
             "strftime(max(datetime), '%xT%X%z')"
         };
-        let mut statement = self.prepare(&format!(
-            "SELECT DISTINCT collection FROM {}",
+        Ok((start_datetime, end_datetime))
+    }
+
+    fn extent_with_bounds(
+        &self,
+        href: &str,
+        collection_id: &str,
+        start_datetime: &str,
+        end_datetime: &str,
+    ) -> Result<Extent> {
+        let mut statement = self.connection.prepare(&format!(
+            "SELECT ST_AsGeoJSON(ST_Extent_Agg(geometry)), {}, {} FROM {} WHERE collection = $1",
+            start_datetime,
+            end_datetime,
             self.format_parquet_href(href)
         ))?;
-        let mut collections = Vec::new();
-        for row in statement.query_map([], |row| row.get::<_, String>(0))? {
-            let collection_id = row?;
-            let mut statement = self.connection.prepare(&
-                format!("SELECT ST_AsGeoJSON(ST_Extent_Agg(geometry)), {}, {} FROM {} WHERE collection = $1", start_datetime, end_datetime,
-                self.format_parquet_href(href)
-            ))?;
-            let row = statement.query_row([&collection_id], |row| {
-                Ok((
-                    row.get::<_, String>(0)?,
-                    row.get::<_, String>(1)?,
-                    row.get::<_, String>(2)?,
-                ))
-            })?;
-            let mut collection = Collection::new(collection_id, DEFAULT_COLLECTION_DESCRIPTION);
-            let geometry: geo::Geometry = serde_json::from_str::<GeometryValue>(&row.0)?
-                .try_into()
-                .map_err(Box::new)?;
-            if let Some(bbox) = geometry.bounding_rect() {
-                collection.extent.spatial = SpatialExtent {
-                    bbox: vec![bbox.into()],
-                };
-            }
-            collection.extent.temporal = TemporalExtent {
-                interval: vec![[
-                    Some(DateTime::parse_from_str(&row.1, "%FT%T%#z")?.into()),
-                    Some(DateTime::parse_from_str(&row.2, "%FT%T%#z")?.into()),
-                ]],
+        let row = statement.query_row([collection_id], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+            ))
+        })?;
+        let mut extent = Extent::default();
+        let geometry: geo::Geometry = serde_json::from_str::<GeometryValue>(&row.0)?
+            .try_into()
+            .map_err(Box::new)?;
+        if let Some(bbox) = geometry.bounding_rect() {
+            extent.spatial = SpatialExtent {
+                bbox: vec![bbox.into()],
             };
-            collections.push(collection);
+        }
+        extent.temporal = TemporalExtent {
+            interval: vec![[
+                Some(DateTime::parse_from_str(&row.1, "%FT%T%#z")?.into()),
+                Some(DateTime::parse_from_str(&row.2, "%FT%T%#z")?.into()),
+            ]],
+        };
+        Ok(extent)
+    }
+
+    /// Returns the collections embedded in the stac-geoparquet metadata of
+    /// one or more files, keyed by id.
+    fn embedded_collections(&self, href: &str) -> Result<HashMap<String, Collection>> {
+        let mut statement = self.prepare(&format!(
+            "SELECT value FROM parquet_kv_metadata({}) WHERE decode(key) = '{STAC_GEOPARQUET_METADATA_KEY}'",
+            format_hrefs(&[href])
+        ))?;
+        let mut collections = HashMap::new();
+        for row in statement.query_map([], |row| row.get::<_, Vec<u8>>(0))? {
+            let mut metadata: serde_json::Value = serde_json::from_slice(&row?)?;
+            if let Some(value) = metadata.get_mut("collections").map(serde_json::Value::take) {
+                let embedded: HashMap<String, Collection> = serde_json::from_value(value)?;
+                collections.extend(embedded);
+            }
+            // stac-geoparquet 1.0 stores a single collection
+            if let Some(value) = metadata.get_mut("collection").map(serde_json::Value::take) {
+                let collection: Collection = serde_json::from_value(value)?;
+                let _ = collections
+                    .entry(collection.id.clone())
+                    .or_insert(collection);
+            }
         }
         Ok(collections)
     }
 
+    /// Returns true if every file declares the `bbox` column as its primary
+    /// geometry's bbox covering, per geoparquet 1.1.
+    fn has_bbox_covering(&self, hrefs: &[&str]) -> Result<bool> {
+        let mut statement = self.prepare(&format!(
+            "SELECT value FROM parquet_kv_metadata({}) WHERE decode(key) = 'geo'",
+            format_hrefs(hrefs)
+        ))?;
+        let mut has_bbox_covering = false;
+        for row in statement.query_map([], |row| row.get::<_, Vec<u8>>(0))? {
+            let geo: serde_json::Value = serde_json::from_slice(&row?)?;
+            let primary_column = geo["primary_column"].as_str().unwrap_or("geometry");
+            let bbox = &geo["columns"][primary_column]["covering"]["bbox"];
+            if ["xmin", "ymin", "xmax", "ymax"]
+                .iter()
+                .all(|field| bbox[field] == serde_json::json!(["bbox", field]))
+            {
+                has_bbox_covering = true;
+            } else {
+                return Ok(false);
+            }
+        }
+        Ok(has_bbox_covering)
+    }
+
+    /// Computes statistics for fields across a stac-geoparquet file.
+    ///
+    /// Statistics are computed with SQL aggregates, so items are never loaded
+    /// into memory. Numeric fields get a minimum, maximum, mean, and
+    /// [PERCENTILES], and other scalar fields get a minimum and maximum. All
+    /// scalar fields get counts of their most common values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_duckdb::Client;
+    ///
+    /// let client = Client::new().unwrap();
+    /// let statistics = client
+    ///     .statistics("data/100-sentinel-2-items.parquet", &["eo:cloud_cover", "platform"])
+    ///     .unwrap();
+    /// assert_eq!(statistics[0].count + statistics[0].null_count, 100);
+    /// ```
+    pub fn statistics(&self, href: &str, fields: &[&str]) -> Result<Vec<FieldStatistics>> {
+        let source = self.format_parquet_href(href);
+        let mut statistics = Vec::with_capacity(fields.len());
+        for field in fields {
+            let column = format!("\"{}\"", field.replace('"', "\"\""));
+            let column_type: String = self.query_row(
+                &format!("SELECT column_type FROM (DESCRIBE SELECT {column} FROM {source})"),
+                [],
+                |row| row.get(0),
+            )?;
+            let mut field_statistics = FieldStatistics::new(field);
+            let (count, null_count) = self.query_row(
+                &format!("SELECT count({column}), count(*) - count({column}) FROM {source}"),
+                [],
+                |row| Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?)),
+            )?;
+            field_statistics.count = count.try_into()?;
+            field_statistics.null_count = null_count.try_into()?;
+            if is_numeric(&column_type) {
+                let percentiles = PERCENTILES
+                    .iter()
+                    .map(|percentile| {
+                        format!(
+                            "quantile_cont({column}, {})::DOUBLE",
+                            f64::from(*percentile) / 100.
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                self.query_row(
+                    &format!(
+                        "SELECT min({column})::DOUBLE, max({column})::DOUBLE, avg({column})::DOUBLE, {percentiles} FROM {source}"
+                    ),
+                    [],
+                    |row| {
+                        field_statistics.minimum = row.get::<_, Option<f64>>(0)?.map(Into::into);
+                        field_statistics.maximum = row.get::<_, Option<f64>>(1)?.map(Into::into);
+                        field_statistics.mean = row.get(2)?;
+                        for (i, percentile) in PERCENTILES.into_iter().enumerate() {
+                            if let Some(value) = row.get::<_, Option<f64>>(i + 3)? {
+                                field_statistics
+                                    .percentiles
+                                    .push(Percentile { percentile, value });
+                            }
+                        }
+                        Ok(())
+                    },
+                )?;
+            } else if is_scalar(&column_type) {
+                self.query_row(
+                    &format!("SELECT min({column})::VARCHAR, max({column})::VARCHAR FROM {source}"),
+                    [],
+                    |row| {
+                        field_statistics.minimum = row.get::<_, Option<String>>(0)?.map(Into::into);
+                        field_statistics.maximum = row.get::<_, Option<String>>(1)?.map(Into::into);
+                        Ok(())
+                    },
+                )?;
+            }
+            if is_numeric(&column_type) || is_scalar(&column_type) {
+                let mut statement = self.prepare(&format!(
+                    "SELECT {column}::VARCHAR, count(*) FROM {source} WHERE {column} IS NOT NULL GROUP BY 1 ORDER BY 2 DESC, 1 LIMIT {VALUE_COUNT_LIMIT}"
+                ))?;
+                for row in statement.query_map([], |row| {
+                    Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
+                })? {
+                    let (value, count) = row?;
+                    field_statistics.value_counts.push(ValueCount {
+                        value,
+                        count: count.try_into()?,
+                    });
+                }
+            }
+            statistics.push(field_statistics);
+        }
+        Ok(statistics)
+    }
+
+    /// Computes statistics for a whole stac-geoparquet file.
+    ///
+    /// This includes the number of items, the distinct collections, the
+    /// spatial and temporal extents, the null count of each column, and the
+    /// sizes of the row groups.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_duckdb::Client;
+    ///
+    /// let client = Client::new().unwrap();
+    /// let statistics = client
+    ///     .file_statistics("data/100-sentinel-2-items.parquet")
+    ///     .unwrap();
+    /// assert_eq!(statistics.count, 100);
+    /// assert_eq!(statistics.collections, vec!["sentinel-2-l2a"]);
+    /// ```
+    pub fn file_statistics(&self, href: &str) -> Result<FileStatistics> {
+        let source = self.format_parquet_href(href);
+        let mut statement = self.prepare(&format!(
+            "SELECT column_name, column_type FROM (DESCRIBE SELECT * FROM {source})"
+        ))?;
+        let mut columns = Vec::new();
+        let mut has_bbox = false;
+        for row in statement.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })? {
+            let (column, column_type) = row?;
+            if column == "bbox" && column_type.contains("xmin") {
+                has_bbox = true;
+            }
+            columns.push(column);
+        }
+        let mut file_statistics = FileStatistics::default();
+
+        let null_counts = columns
+            .iter()
+            .map(|column| {
+                let column = format!("\"{}\"", column.replace('"', "\"\""));
+                format!("count(*) - count({column})")
+            })
+            .collect::<Vec<_>>();
+        self.query_row(
+            &format!(
+                "SELECT count(*), {} FROM {source}",
+                if null_counts.is_empty() {
+                    "NULL".to_string()
+                } else {
+                    null_counts.join(", ")
+                }
+            ),
+            [],
+            |row| {
+                file_statistics.count = row.get::<_, i64>(0)?.try_into().unwrap_or_default();
+                for (i, column) in columns.iter().enumerate() {
+                    file_statistics.null_counts.push(NullCount {
+                        column: column.clone(),
+                        null_count: row.get::<_, i64>(i + 1)?.try_into().unwrap_or_default(),
+                    });
+                }
+                Ok(())
+            },
+        )?;
+
+        if columns.iter().any(|column| column == "collection") {
+            let mut statement = self.prepare(&format!(
+                "SELECT DISTINCT collection FROM {source} WHERE collection IS NOT NULL ORDER BY 1"
+            ))?;
+            for row in statement.query_map([], |row| row.get::<_, String>(0))? {
+                file_statistics.collections.push(row?);
+            }
+        }
+
+        if has_bbox {
+            let bounds = self.query_row(
+                &format!(
+                    "SELECT min(bbox.xmin)::DOUBLE, min(bbox.ymin)::DOUBLE, max(bbox.xmax)::DOUBLE, max(bbox.ymax)::DOUBLE FROM {source}"
+                ),
+                [],
+                |row| {
+                    Ok((
+                        row.get::<_, Option<f64>>(0)?,
+                        row.get::<_, Option<f64>>(1)?,
+                        row.get::<_, Option<f64>>(2)?,
+                        row.get::<_, Option<f64>>(3)?,
+                    ))
+                },
+            )?;
+            if let (Some(xmin), Some(ymin), Some(xmax), Some(ymax)) = bounds {
+                file_statistics.bbox = Some(Bbox::new(xmin, ymin, xmax, ymax));
+            }
+        } else if columns.iter().any(|column| column == "geometry") {
+            let geometry = self.query_row(
+                &format!("SELECT ST_AsGeoJSON(ST_Extent_Agg(geometry)) FROM {source}"),
+                [],
+                |row| row.get::<_, Option<String>>(0),
+            )?;
+            if let Some(geometry) = geometry {
+                let geometry: geo::Geometry = serde_json::from_str::<GeometryValue>(&geometry)?
+                    .try_into()
+                    .map_err(Box::new)?;
+                file_statistics.bbox = geometry.bounding_rect().map(Bbox::from);
+            }
+        }
+
+        if columns.iter().any(|column| column == "datetime") {
+            let (start_datetime, end_datetime) = self.datetime_bounds(href)?;
+            let (start, end) = self.query_row(
+                &format!("SELECT {start_datetime}, {end_datetime} FROM {source}"),
+                [],
+                |row| {
+                    Ok((
+                        row.get::<_, Option<String>>(0)?,
+                        row.get::<_, Option<String>>(1)?,
+                    ))
+                },
+            )?;
+            file_statistics.start_datetime = start
+                .map(|start| DateTime::parse_from_str(&start, "%FT%T%#z"))
+                .transpose()?
+                .map(Into::into);
+            file_statistics.end_datetime = end
+                .map(|end| DateTime::parse_from_str(&end, "%FT%T%#z"))
+                .transpose()?
+                .map(Into::into);
+        }
+
+        let mut statement = self.prepare(&format!(
+            "SELECT file_name, row_group_id, any_value(row_group_num_rows), any_value(row_group_bytes), sum(total_compressed_size)::BIGINT FROM parquet_metadata({}) GROUP BY 1, 2 ORDER BY 1, 2",
+            format_hrefs(&[href])
+        ))?;
+        for row in statement.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, i64>(1)?,
+                row.get::<_, i64>(2)?,
+                row.get::<_, i64>(3)?,
+                row.get::<_, i64>(4)?,
+            ))
+        })? {
+            let (file, id, num_rows, total_byte_size, compressed_size) = row?;
+            file_statistics.row_groups.push(RowGroupStatistics {
+                file,
+                id: id.try_into()?,
+                num_rows: num_rows.try_into()?,
+                total_byte_size: total_byte_size.try_into()?,
+                compressed_size: compressed_size.try_into()?,
+            });
+        }
+        Ok(file_statistics)
+    }
+
+    /// Returns the [queryables](https://github.com/stac-api-extensions/filter#queryables)
+    /// of a stac-geoparquet file, as a JSON Schema.
+    ///
+    /// Each column becomes a queryable property, with a JSON type derived
+    /// from its parquet type. Columns that aren't filterable item fields,
+    /// e.g. `links` and `assets`, are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_duckdb::Client;
+    ///
+    /// let client = Client::new().unwrap();
+    /// let queryables = client.queryables("data/100-sentinel-2-items.parquet").unwrap();
+    /// assert_eq!(queryables["properties"]["eo:cloud_cover"]["type"], "number");
+    /// ```
+    pub fn queryables(&self, href: &str) -> Result<serde_json::Value> {
+        self.queryables_hrefs(&[href])
+    }
+
+    /// Returns the queryables of several stac-geoparquet files, as one JSON Schema.
+    ///
+    /// See [Client::queryables].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_duckdb::Client;
+    ///
+    /// let client = Client::new().unwrap();
+    /// let queryables = client
+    ///     .queryables_hrefs(&[
+    ///         "data/100-sentinel-2-items.parquet",
+    ///         "data/100-landsat-items.parquet",
+    ///     ])
+    ///     .unwrap();
+    /// assert_eq!(queryables["properties"]["datetime"]["format"], "date-time");
+    /// ```
+    pub fn queryables_hrefs(&self, hrefs: &[&str]) -> Result<serde_json::Value> {
+        let mut properties = Map::new();
+        if !hrefs.is_empty() {
+            let mut statement = self.prepare(&format!(
+                "SELECT column_name, column_type FROM (DESCRIBE SELECT * FROM {})",
+                self.format_parquet_hrefs(hrefs)
+            ))?;
+            for row in statement.query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })? {
+                let (column, column_type) = row?;
+                if NON_QUERYABLE_COLUMNS.contains(&column.as_str()) {
+                    continue;
+                }
+                if let Some(queryable) = queryable(&column, &column_type) {
+                    let _ = properties.insert(column, queryable);
+                }
+            }
+        }
+        Ok(serde_json::json!({
+            "$schema": "https://json-schema.org/draft/2019-09/schema",
+            "type": "object",
+            "title": "Queryables",
+            "properties": properties,
+            "additionalProperties": true,
+        }))
+    }
+
     /// Searches a single stac-geoparquet file.
     ///
     /// # Examples
@@ -190,8 +667,102 @@ impl Client {
     /// let client = Client::new().unwrap();
     /// let item_collection = client.search("data/100-sentinel-2-items.parquet", Default::default()).unwrap();
     /// ```
+    ///
+    /// If the search has a limit and there are more matching items, the item
+    /// collection's `next` is set to the offset of the next page, and if the
+    /// search has an offset, `prev` is set to the offset of the previous page.
+    ///
+    /// The href can be a glob, e.g. `s3://bucket/items/**/*.parquet`, to
+    /// search many files as one table. See [Client::search_hrefs] to search a
+    /// list of files.
     pub fn search(&self, href: &str, search: Search) -> Result<stac::api::ItemCollection> {
-        let mut arrow_iter = self.search_to_arrow(href, search)?;
+        self.search_hrefs(&[href], search)
+    }
+
+    /// Searches several stac-geoparquet hrefs (or globs) as one table.
+    ///
+    /// The files' columns are unioned by name (if
+    /// [union_by_name](Client::union_by_name) is set), and DuckDB pushes the
+    /// search's filters down to each file. Searching no hrefs returns no
+    /// items.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_duckdb::Client;
+    ///
+    /// let client = Client::new().unwrap();
+    /// let item_collection = client
+    ///     .search_hrefs(&["data/100-sentinel-2-items.parquet"], Default::default())
+    ///     .unwrap();
+    /// assert_eq!(item_collection.items.len(), 100);
+    /// ```
+    pub fn search_hrefs(
+        &self,
+        hrefs: &[&str],
+        mut search: Search,
+    ) -> Result<stac::api::ItemCollection> {
+        let limit = search.items.limit;
+        let offset = search_offset(&search).unwrap_or_default();
+        if let Some(limit) = limit {
+            // Ask for one extra item so we know if there's another page.
+            search.items.limit = Some(limit + 1);
+        }
+        let mut item_collection = self.search_page(hrefs, search)?;
+        if let Some(limit) = limit {
+            if item_collection.items.len() > usize::try_from(limit)? {
+                item_collection.items.truncate(limit.try_into()?);
+                let mut next = Map::new();
+                let _ = next.insert("offset".to_string(), (offset + limit).into());
+                item_collection.next = Some(next);
+            }
+            if offset > 0 {
+                let mut prev = Map::new();
+                let _ = prev.insert("offset".to_string(), offset.saturating_sub(limit).into());
+                item_collection.prev = Some(prev);
+            }
+        }
+        Ok(item_collection)
+    }
+
+    /// Searches a stac-geoparquet href (or glob), returning an iterator over
+    /// pages of at most `page_size` items.
+    ///
+    /// Each page's `next` holds the offset of the page after it, which the
+    /// iterator uses to fetch that page. Iteration starts at the search's
+    /// offset, if it has one, and stops after the last page.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_duckdb::Client;
+    ///
+    /// let client = Client::new().unwrap();
+    /// let pages: Vec<_> = client
+    ///     .search_paginated("data/100-sentinel-2-items.parquet", Default::default(), 40)
+    ///     .map(|page| page.unwrap())
+    ///     .collect();
+    /// assert_eq!(pages.len(), 3);
+    /// assert_eq!(pages[2].items.len(), 20);
+    /// ```
+    pub fn search_paginated(
+        &self,
+        href: impl ToString,
+        mut search: Search,
+        page_size: u64,
+    ) -> SearchPages<'_> {
+        search.items.offset = search_offset(&search);
+        let _ = search.items.additional_fields.remove("offset");
+        search.items.limit = Some(page_size.max(1));
+        SearchPages {
+            client: self,
+            href: href.to_string(),
+            search: Some(search),
+        }
+    }
+
+    fn search_page(&self, hrefs: &[&str], search: Search) -> Result<stac::api::ItemCollection> {
+        let mut arrow_iter = self.search_hrefs_to_arrow(hrefs, search)?;
         let Some(schema) = arrow_iter.schema() else {
             return Ok(Default::default());
         };
@@ -234,7 +805,29 @@ impl Client {
         href: &str,
         search: Search,
     ) -> Result<SearchArrowBatchIter<'conn>> {
-        if let Some((sql, params)) = self.build_query(href, search)? {
+        self.search_hrefs_to_arrow(&[href], search)
+    }
+
+    /// Searches several hrefs (or globs) to an iterator of record batches.
+    ///
+    /// See [Client::search_hrefs].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_duckdb::Client;
+    ///
+    /// let client = Client::new().unwrap();
+    /// let batches = client
+    ///     .search_hrefs_to_arrow(&["data/100-sentinel-2-items.parquet"], Default::default())
+    ///     .unwrap();
+    /// ```
+    pub fn search_hrefs_to_arrow<'conn>(
+        &'conn self,
+        hrefs: &[&str],
+        search: Search,
+    ) -> Result<SearchArrowBatchIter<'conn>> {
+        if let Some((sql, params)) = self.build_hrefs_query(hrefs, search)? {
             log::debug!("duckdb sql: {sql}");
             let mut statement = self.prepare(&sql)?;
             statement.execute(duckdb::params_from_iter(params))?;
@@ -265,29 +858,49 @@ impl Client {
     /// let (sql, params) = client.build_query("data/100-sentinel-2-items.parquet", Default::default()).unwrap().unwrap();
     /// ```
     pub fn build_query(&self, href: &str, search: Search) -> Result<Option<(String, Vec<Value>)>> {
+        self.build_hrefs_query(&[href], search)
+    }
+
+    fn build_hrefs_query(
+        &self,
+        hrefs: &[&str],
+        search: Search,
+    ) -> Result<Option<(String, Vec<Value>)>> {
         // Note that we pull out some fields early so we can avoid closing some search strings below.
 
-        if search.items.query.is_some() {
-            return Err(Error::QueryNotImplemented);
+        if hrefs.is_empty() {
+            return Ok(None);
         }
 
         // Check which columns we'll be selecting
         let mut statement = self.prepare(&format!(
-            "SELECT column_name FROM (DESCRIBE SELECT * from {})",
-            self.format_parquet_href(href)
+            "SELECT column_name, column_type FROM (DESCRIBE SELECT * from {})",
+            self.format_parquet_hrefs(hrefs)
         ))?;
         let mut has_start_datetime = false;
         let mut has_end_datetime = false;
+        let mut has_bbox = false;
+        let mut has_bbox_elevation = false;
         let mut column_names = Vec::new();
+        let mut all_column_names = Vec::new();
         let mut columns = Vec::new();
-        for row in statement.query_map([], |row| row.get::<_, String>(0))? {
-            let column = row?;
+        for row in statement.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })? {
+            let (column, column_type) = row?;
+            all_column_names.push(column.clone());
             if column == "start_datetime" {
                 has_start_datetime = true;
             }
             if column == "end_datetime" {
                 has_end_datetime = true;
             }
+            if column == "bbox" && column_type.contains("xmin") {
+                has_bbox = true;
+                if column_type.contains("zmin") && column_type.contains("zmax") {
+                    has_bbox_elevation = true;
+                }
+            }
 
             if let Some(fields) = search.fields.as_ref()
                 && (fields.exclude.contains(&column)
@@ -308,11 +921,7 @@ impl Client {
 
         // Get limit and offset
         let limit = search.items.limit;
-        let offset = search
-            .items
-            .additional_fields
-            .get("offset")
-            .and_then(|v| v.as_i64());
+        let offset = search_offset(&search);
 
         // Build order_by
         let mut order_by = Vec::with_capacity(search.sortby.len());
@@ -327,6 +936,13 @@ impl Client {
             ));
         }
 
+        // Items without a geometry are matched spatially by their bbox.
+        let geometry = if has_bbox {
+            "COALESCE(geometry, ST_MakeEnvelope(bbox.xmin, bbox.ymin, bbox.xmax, bbox.ymax))"
+        } else {
+            "geometry"
+        };
+
         // Build wheres and params
         let mut wheres = Vec::new();
         let mut params = Vec::new();
@@ -341,22 +957,60 @@ impl Client {
             params.extend(search.ids.into_iter().map(Value::Text));
         }
         if let Some(intersects) = search.intersects {
-            wheres.push("ST_Intersects(geometry, ST_GeomFromGeoJSON(?))".to_string());
+            wheres.push(format!("ST_Intersects({geometry}, ST_GeomFromGeoJSON(?))"));
             params.push(Value::Text(intersects.to_string()));
         }
         if !search.collections.is_empty() {
-            wheres.push(format!(
-                "collection IN ({})",
-                (0..search.collections.len())
-                    .map(|_| "?")
-                    .collect::<Vec<_>>()
-                    .join(",")
-            ));
-            params.extend(search.collections.into_iter().map(Value::Text));
+            let (patterns, collections): (Vec<_>, Vec<_>) = search
+                .collections
+                .into_iter()
+                .partition(|collection| self.collection_wildcards && collection.contains('*'));
+            let mut clauses = Vec::new();
+            if !collections.is_empty() {
+                clauses.push(format!(
+                    "collection IN ({})",
+                    (0..collections.len())
+                        .map(|_| "?")
+                        .collect::<Vec<_>>()
+                        .join(",")
+                ));
+                params.extend(collections.into_iter().map(Value::Text));
+            }
+            for pattern in patterns {
+                clauses.push("collection LIKE ? ESCAPE '\\'".to_string());
+                params.push(Value::Text(like_pattern(&pattern)));
+            }
+            wheres.push(format!("({})", clauses.join(" OR ")));
         }
         if let Some(bbox) = search.items.bbox {
-            wheres.push("ST_Intersects(geometry, ST_GeomFromGeoJSON(?))".to_string());
-            params.push(Value::Text(bbox.to_geometry().to_string()));
+            // The bbox covering's statistics let DuckDB skip row groups, but
+            // the geometry still decides what matches.
+            if has_bbox && bbox.xmin() <= bbox.xmax() && self.has_bbox_covering(hrefs)? {
+                wheres.push(
+                    "bbox.xmin <= ? AND bbox.xmax >= ? AND bbox.ymin <= ? AND bbox.ymax >= ?"
+                        .to_string(),
+                );
+                params.push(Value::Double(bbox.xmax()));
+                params.push(Value::Double(bbox.xmin()));
+                params.push(Value::Double(bbox.ymax()));
+                params.push(Value::Double(bbox.ymin()));
+            }
+            wheres.push(format!("ST_Intersects({geometry}, ST_GeomFromGeoJSON(?))"));
+            params.push(Value::Text(
+                bbox.to_two_dimensional().to_geometry().to_string(),
+            ));
+            if let Some((zmin, zmax)) = bbox.zmin().zip(bbox.zmax()) {
+                if has_bbox_elevation {
+                    // Items without an elevation aren't constrained by it.
+                    wheres.push(
+                        "(bbox.zmin IS NULL OR (bbox.zmin <= ? AND bbox.zmax >= ?))".to_string(),
+                    );
+                    params.push(Value::Double(zmax));
+                    params.push(Value::Double(zmin));
+                } else {
+                    log::debug!("bbox has an elevation but the data do not, ignoring elevation");
+                }
+            }
         }
         if let Some(datetime) = search.items.datetime {
             let interval = stac::datetime::parse(&datetime)?;
@@ -383,11 +1037,20 @@ impl Client {
                 params.push(Value::Text(end.to_rfc3339()));
             }
         }
+        if let Some(query) = search.items.query
+            && !push_query(query, &all_column_names, &mut wheres, &mut params)?
+        {
+            return Ok(None);
+        }
         if let Some(filter) = search.items.filter {
-            let expr: Expr = filter.try_into()?;
-            if expr_properties_match(&expr, &column_names) {
+            let mut expr: Expr = filter.try_into()?;
+            let mut accessors = Vec::new();
+            rewrite_nested_properties(&mut expr, &column_names, &mut accessors);
+            let mut properties = column_names.clone();
+            properties.extend((0..accessors.len()).map(nested_placeholder));
+            if expr_properties_match(&expr, &properties) {
                 let sql = expr.to_ducksql().map_err(Box::new)?;
-                wheres.push(sql);
+                wheres.push(substitute_nested_properties(&sql, &accessors));
             } else {
                 return Ok(None);
             }
@@ -410,23 +1073,210 @@ impl Client {
         let sql = format!(
             "SELECT {} FROM {}{}",
             columns.join(","),
-            self.format_parquet_href(href),
+            self.format_parquet_hrefs(hrefs),
             suffix,
         );
         Ok(Some((sql, params)))
     }
 
-    fn format_parquet_href(&self, href: &str) -> String {
-        format!(
-            "read_parquet('{}', hive_partitioning={}, union_by_name={})",
-            href,
-            if self.use_hive_partitioning {
-                "true"
-            } else {
-                "false"
-            },
-            if self.union_by_name { "true" } else { "false" }
-        )
+    fn format_parquet_href(&self, href: &str) -> String {
+        self.format_parquet_hrefs(&[href])
+    }
+
+    fn format_parquet_hrefs(&self, hrefs: &[&str]) -> String {
+        let (hive, plain): (Vec<&str>, Vec<&str>) = hrefs.iter().partition(|href| {
+            self.use_hive_partitioning || self.hive_partitioned_hrefs.contains(**href)
+        });
+        if hive.is_empty() || plain.is_empty() {
+            self.format_read_parquet(hrefs, !hive.is_empty())
+        } else {
+            // `read_parquet` takes one hive partitioning setting for all of
+            // its files, so the two groups are read separately.
+            format!(
+                "(SELECT * FROM {} UNION ALL BY NAME SELECT * FROM {})",
+                self.format_read_parquet(&plain, false),
+                self.format_read_parquet(&hive, true)
+            )
+        }
+    }
+
+    fn format_read_parquet(&self, hrefs: &[&str], hive_partitioning: bool) -> String {
+        format!(
+            "read_parquet({}, hive_partitioning={}, union_by_name={})",
+            format_hrefs(hrefs),
+            if hive_partitioning { "true" } else { "false" },
+            if self.union_by_name { "true" } else { "false" }
+        )
+    }
+}
+
+/// Formats hrefs as a SQL literal or a list of literals.
+fn format_hrefs(hrefs: &[&str]) -> String {
+    match hrefs {
+        [href] => quote(href),
+        _ => format!(
+            "[{}]",
+            hrefs
+                .iter()
+                .map(|href| quote(href))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+/// Quotes a string as a SQL literal.
+fn quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "''"))
+}
+
+/// Returns the search's offset, falling back to an `offset` additional field.
+fn search_offset(search: &Search) -> Option<u64> {
+    search.items.offset.or_else(|| {
+        search
+            .items
+            .additional_fields
+            .get("offset")
+            .and_then(|v| v.as_u64())
+    })
+}
+
+/// Returns the JSON Schema of a queryable column, or `None` if its type
+/// can't be described.
+fn queryable(column: &str, column_type: &str) -> Option<serde_json::Value> {
+    let queryable = if column == "geometry" {
+        serde_json::json!({"$ref": "https://geojson.org/schema/Geometry.json"})
+    } else if column_type.ends_with(']') {
+        serde_json::json!({"type": "array"})
+    } else if column_type.starts_with("STRUCT") || column_type.starts_with("MAP") {
+        serde_json::json!({"type": "object"})
+    } else if column_type == "BOOLEAN" {
+        serde_json::json!({"type": "boolean"})
+    } else if column_type.starts_with("TIMESTAMP") {
+        serde_json::json!({"type": "string", "format": "date-time"})
+    } else if column_type == "DATE" {
+        serde_json::json!({"type": "string", "format": "date"})
+    } else if is_numeric(column_type) {
+        if column_type.ends_with("INT") || column_type.ends_with("INTEGER") {
+            serde_json::json!({"type": "integer"})
+        } else {
+            serde_json::json!({"type": "number"})
+        }
+    } else if is_scalar(column_type) {
+        serde_json::json!({"type": "string"})
+    } else {
+        return None;
+    };
+    Some(queryable)
+}
+
+fn is_numeric(column_type: &str) -> bool {
+    matches!(
+        column_type,
+        "TINYINT"
+            | "SMALLINT"
+            | "INTEGER"
+            | "BIGINT"
+            | "HUGEINT"
+            | "UTINYINT"
+            | "USMALLINT"
+            | "UINTEGER"
+            | "UBIGINT"
+            | "UHUGEINT"
+            | "FLOAT"
+            | "DOUBLE"
+    ) || column_type.starts_with("DECIMAL")
+}
+
+fn is_scalar(column_type: &str) -> bool {
+    !(column_type.ends_with(']')
+        || column_type.starts_with("STRUCT")
+        || column_type.starts_with("MAP")
+        || column_type.starts_with("UNION")
+        || column_type.starts_with("GEOMETRY")
+        || column_type == "BLOB")
+}
+
+/// Adds the SQL predicates for a [query](https://github.com/stac-api-extensions/query) to `wheres`.
+///
+/// Returns false if the query can't match anything, e.g. if it uses a
+/// property that isn't a column.
+fn push_query(
+    query: Map<String, serde_json::Value>,
+    columns: &[String],
+    wheres: &mut Vec<String>,
+    params: &mut Vec<Value>,
+) -> Result<bool> {
+    for (property, operators) in query {
+        let serde_json::Value::Object(operators) = operators else {
+            return Err(Error::InvalidQuery(format!(
+                "operators for {property} must be an object"
+            )));
+        };
+        if !columns.contains(&property) {
+            return Ok(false);
+        }
+        let column = format!("\"{}\"", property.replace('"', "\"\""));
+        for (operator, value) in operators {
+            let comparison = match operator.as_str() {
+                "eq" => "=",
+                "neq" => "<>",
+                "lt" => "<",
+                "lte" => "<=",
+                "gt" => ">",
+                "gte" => ">=",
+                "startsWith" => {
+                    let serde_json::Value::String(prefix) = value else {
+                        return Err(Error::InvalidQuery(format!(
+                            "startsWith for {property} must be a string"
+                        )));
+                    };
+                    wheres.push(format!("starts_with({column}, ?)"));
+                    params.push(Value::Text(prefix));
+                    continue;
+                }
+                "in" => {
+                    let serde_json::Value::Array(values) = value else {
+                        return Err(Error::InvalidQuery(format!(
+                            "in for {property} must be an array"
+                        )));
+                    };
+                    if values.is_empty() {
+                        return Ok(false);
+                    }
+                    wheres.push(format!(
+                        "{column} IN ({})",
+                        (0..values.len()).map(|_| "?").collect::<Vec<_>>().join(",")
+                    ));
+                    for value in values {
+                        params.push(query_param(&property, value)?);
+                    }
+                    continue;
+                }
+                _ => {
+                    return Err(Error::InvalidQuery(format!(
+                        "unsupported operator for {property}: {operator}"
+                    )));
+                }
+            };
+            wheres.push(format!("{column} {comparison} ?"));
+            params.push(query_param(&property, value)?);
+        }
+    }
+    Ok(true)
+}
+
+fn query_param(property: &str, value: serde_json::Value) -> Result<Value> {
+    match value {
+        serde_json::Value::String(s) => Ok(Value::Text(s)),
+        serde_json::Value::Bool(b) => Ok(Value::Boolean(b)),
+        serde_json::Value::Number(n) => Ok(n
+            .as_i64()
+            .map(Value::BigInt)
+            .unwrap_or_else(|| Value::Double(n.as_f64().unwrap_or(f64::NAN)))),
+        value => Err(Error::InvalidQuery(format!(
+            "unsupported value for {property}: {value}"
+        ))),
     }
 }
 
@@ -454,6 +1304,158 @@ fn expr_properties_match(expr: &Expr, properties: &[String]) -> bool {
     }
 }
 
+/// Replaces properties that are paths into nested columns, e.g.
+/// `assets.B04.eo:bands[0].common_name`, with placeholders, and pushes the
+/// DuckDB accessor for each placeholder.
+///
+/// cql2 quotes properties as single identifiers, so the accessors are
+/// substituted into the SQL after it's rendered.
+fn rewrite_nested_properties(expr: &mut Expr, columns: &[String], accessors: &mut Vec<String>) {
+    use Expr::*;
+
+    match expr {
+        Property { property } => {
+            if !columns.contains(property)
+                && let Some(accessor) = nested_accessor(property, columns)
+            {
+                *property = nested_placeholder(accessors.len());
+                accessors.push(accessor);
+            }
+        }
+        Float(_) | Literal(_) | Bool(_) | Geometry(_) | Null => {}
+        Operation { args, .. } => {
+            for expr in args {
+                rewrite_nested_properties(expr, columns, accessors);
+            }
+        }
+        Interval { interval } => {
+            for expr in interval {
+                rewrite_nested_properties(expr, columns, accessors);
+            }
+        }
+        Timestamp { timestamp } => rewrite_nested_properties(timestamp, columns, accessors),
+        Date { date } => rewrite_nested_properties(date, columns, accessors),
+        Array(exprs) => {
+            for expr in exprs {
+                rewrite_nested_properties(expr, columns, accessors);
+            }
+        }
+        BBox { bbox } => {
+            for expr in bbox {
+                rewrite_nested_properties(expr, columns, accessors);
+            }
+        }
+    }
+}
+
+fn nested_placeholder(i: usize) -> String {
+    format!("__rustac_nested_property_{i}")
+}
+
+/// Replaces the quoted placeholder identifiers in rendered SQL with their
+/// accessors.
+///
+/// Only whole identifiers, quoted or not, are replaced. String literals are
+/// copied as-is, so a filter literal that contains a placeholder's text is
+/// left alone.
+fn substitute_nested_properties(sql: &str, accessors: &[String]) -> String {
+    let mut output = String::with_capacity(sql.len());
+    let mut chars = sql.chars().peekable();
+    let accessor = |identifier: &str| {
+        (0..accessors.len())
+            .position(|i| identifier == nested_placeholder(i))
+            .map(|i| accessors[i].as_str())
+    };
+    while let Some(c) = chars.next() {
+        if c.is_ascii_alphanumeric() || c == '_' {
+            let mut word = c.to_string();
+            while let Some(next) = chars.next_if(|c| c.is_ascii_alphanumeric() || *c == '_') {
+                word.push(next);
+            }
+            output.push_str(accessor(&word).unwrap_or(&word));
+            continue;
+        } else if c != '\'' && c != '"' {
+            output.push(c);
+            continue;
+        }
+        let mut token = String::new();
+        while let Some(next) = chars.next() {
+            if next == c {
+                if chars.peek() == Some(&c) {
+                    let _ = chars.next();
+                    token.push(c);
+                } else {
+                    break;
+                }
+            } else {
+                token.push(next);
+            }
+        }
+        if c == '"'
+            && let Some(accessor) = accessor(&token)
+        {
+            output.push_str(accessor);
+        } else {
+            output.push(c);
+            output.push_str(&token.replace(c, &format!("{c}{c}")));
+            output.push(c);
+        }
+    }
+    output
+}
+
+/// Converts a property path to DuckDB struct and list accessors, e.g.
+/// `assets.B04.eo:bands[0].common_name` to
+/// `"assets"['B04']['eo:bands'][1]['common_name']`.
+///
+/// List indices are zero-based, either in brackets or as their own path
+/// segment, and a `properties.` prefix is dropped because stac-geoparquet
+/// stores properties as top-level columns. Returns `None` if the path doesn't
+/// start with one of the columns.
+fn nested_accessor(property: &str, columns: &[String]) -> Option<String> {
+    let path = property.strip_prefix("properties.").unwrap_or(property);
+    let mut segments = path.split('.');
+    let (column, indices) = split_indices(segments.next()?)?;
+    if !columns.iter().any(|c| c == column) {
+        return None;
+    }
+    let mut accessor = format!("\"{}\"", column.replace('"', "\"\""));
+    push_indices(&mut accessor, indices);
+    for segment in segments {
+        if let Ok(index) = segment.parse::<usize>() {
+            push_indices(&mut accessor, [index]);
+        } else {
+            let (field, indices) = split_indices(segment)?;
+            accessor.push_str(&format!("[{}]", quote(field)));
+            push_indices(&mut accessor, indices);
+        }
+    }
+    Some(accessor)
+}
+
+/// Splits a path segment like `eo:bands[0]` into its field and indices.
+fn split_indices(segment: &str) -> Option<(&str, Vec<usize>)> {
+    let (field, mut rest) = segment.split_at(segment.find('[').unwrap_or(segment.len()));
+    let mut indices = Vec::new();
+    while let Some(index) = rest.strip_prefix('[') {
+        let (index, after) = index.split_once(']')?;
+        indices.push(index.trim().parse().ok()?);
+        rest = after;
+    }
+    if field.is_empty() || !rest.is_empty() {
+        None
+    } else {
+        Some((field, indices))
+    }
+}
+
+/// DuckDB lists are one-based.
+fn push_indices(accessor: &mut String, indices: impl IntoIterator<Item = usize>) {
+    for index in indices {
+        accessor.push_str(&format!("[{}]", index + 1));
+    }
+}
+
 impl Deref for Client {
     type Target = Connection;
 
@@ -468,14 +1470,33 @@ impl DerefMut for Client {
     }
 }
 
+/// Converts a `*` wildcard pattern to a SQL `LIKE` pattern, escaping `%`,
+/// `_`, and the escape character itself.
+fn like_pattern(pattern: &str) -> String {
+    let mut like = String::with_capacity(pattern.len());
+    for c in pattern.chars() {
+        match c {
+            '*' => like.push('%'),
+            '%' | '_' | '\\' => {
+                like.push('\\');
+                like.push(c);
+            }
+            _ => like.push(c),
+        }
+    }
+    like
+}
+
 impl From<Connection> for Client {
     fn from(connection: Connection) -> Self {
         Client {
             connection,
             use_hive_partitioning: DEFAULT_USE_HIVE_PARTITIONING,
+            hive_partitioned_hrefs: HashSet::new(),
             convert_wkb: DEFAULT_CONVERT_WKB,
             union_by_name: DEFAULT_UNION_BY_NAME,
             remove_filename_column: DEFAULT_REMOVE_FILENAME_COLUMN,
+            collection_wildcards: DEFAULT_COLLECTION_WILDCARDS,
         }
     }
 }
@@ -626,6 +1647,34 @@ impl StreamItemsClient for SyncHrefClient {
     }
 }
 
+/// Iterator returned by [`Client::search_paginated`].
+#[derive(Debug)]
+pub struct SearchPages<'conn> {
+    client: &'conn Client,
+    href: String,
+    search: Option<Search>,
+}
+
+impl Iterator for SearchPages<'_> {
+    type Item = Result<stac::api::ItemCollection>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut search = self.search.take()?;
+        let result = self.client.search(&self.href, search.clone());
+        if let Ok(item_collection) = &result
+            && let Some(offset) = item_collection
+                .next
+                .as_ref()
+                .and_then(|next| next.get("offset"))
+                .and_then(|offset| offset.as_u64())
+        {
+            search.items.offset = Some(offset);
+            self.search = Some(search);
+        }
+        Some(result)
+    }
+}
+
 /// A wrapper around [`SearchArrowBatchIter`] that implements
 /// [`arrow_array::RecordBatchReader`].
 ///
@@ -725,6 +1774,7 @@ mod tests {
     use duckdb::Connection;
     use geo::Geometry;
     use rstest::{fixture, rstest};
+    use serde_json::json;
     use stac::Bbox;
     use stac::api::{Items, Search, Sortby};
     use stac_validate::Validate;
@@ -748,6 +1798,95 @@ mod tests {
         let _ = client.extensions().unwrap();
     }
 
+    #[rstest]
+    fn statistics(client: Client) {
+        let statistics = client
+            .statistics(
+                "data/100-sentinel-2-items.parquet",
+                &["eo:cloud_cover", "platform", "assets"],
+            )
+            .unwrap();
+        assert_eq!(statistics.len(), 3);
+
+        let cloud_cover = &statistics[0];
+        assert_eq!(cloud_cover.field, "eo:cloud_cover");
+        assert_eq!(cloud_cover.count + cloud_cover.null_count, 100);
+        let minimum = cloud_cover.minimum.as_ref().unwrap().as_f64().unwrap();
+        let maximum = cloud_cover.maximum.as_ref().unwrap().as_f64().unwrap();
+        let mean = cloud_cover.mean.unwrap();
+        assert!(minimum <= mean && mean <= maximum);
+        assert_eq!(cloud_cover.percentiles.len(), 5);
+        assert!(
+            cloud_cover
+                .percentiles
+                .windows(2)
+                .all(|w| w[0].value <= w[1].value)
+        );
+
+        let platform = &statistics[1];
+        assert!(platform.mean.is_none());
+        assert!(platform.minimum.as_ref().unwrap().is_string());
+        assert_eq!(
+            platform
+                .value_counts
+                .iter()
+                .map(|value_count| value_count.count)
+                .sum::<u64>(),
+            platform.count
+        );
+
+        let assets = &statistics[2];
+        assert!(assets.minimum.is_none());
+        assert!(assets.value_counts.is_empty());
+
+        let _ = client
+            .statistics("data/100-sentinel-2-items.parquet", &["not-a-field"])
+            .unwrap_err();
+    }
+
+    #[rstest]
+    fn queryables(client: Client) {
+        let queryables = client
+            .queryables("data/100-sentinel-2-items.parquet")
+            .unwrap();
+        let properties = queryables["properties"].as_object().unwrap();
+        assert_eq!(properties["id"]["type"], "string");
+        assert_eq!(properties["datetime"]["format"], "date-time");
+        assert_eq!(
+            properties["geometry"]["$ref"],
+            "https://geojson.org/schema/Geometry.json"
+        );
+        assert_eq!(properties["platform"]["type"], "string");
+        assert!(!properties.contains_key("assets"));
+        assert!(!properties.contains_key("links"));
+    }
+
+    #[rstest]
+    fn file_statistics(client: Client) {
+        let statistics = client
+            .file_statistics("data/100-sentinel-2-items.parquet")
+            .unwrap();
+        assert_eq!(statistics.count, 100);
+        assert_eq!(statistics.collections, vec!["sentinel-2-l2a"]);
+        let bbox = statistics.bbox.unwrap();
+        assert!(bbox.xmin() <= bbox.xmax() && bbox.ymin() <= bbox.ymax());
+        assert!(statistics.start_datetime.unwrap() <= statistics.end_datetime.unwrap());
+        let id = statistics
+            .null_counts
+            .iter()
+            .find(|null_count| null_count.column == "id")
+            .unwrap();
+        assert_eq!(id.null_count, 0);
+        assert_eq!(
+            statistics
+                .row_groups
+                .iter()
+                .map(|row_group| row_group.num_rows)
+                .sum::<u64>(),
+            100
+        );
+    }
+
     #[rstest]
     #[tokio::test]
     async fn search(client: Client) {
@@ -768,6 +1907,46 @@ mod tests {
         assert_eq!(record_batches.len(), 1);
     }
 
+    #[rstest]
+    fn search_collection_wildcards(mut client: Client) {
+        let search = Search::default().collections(vec!["sentinel-*".to_string()]);
+        let item_collection = client
+            .search("data/100-sentinel-2-items.parquet", search.clone())
+            .unwrap();
+        assert!(item_collection.items.is_empty());
+
+        client.collection_wildcards = true;
+        let item_collection = client
+            .search("data/100-sentinel-2-items.parquet", search)
+            .unwrap();
+        assert_eq!(item_collection.items.len(), 100);
+        for collections in [
+            vec!["*-l2a".to_string()],
+            vec!["sentinel-2-l2a".to_string(), "sentinel-2-*".to_string()],
+            vec!["foo".to_string(), "sentinel*".to_string()],
+        ] {
+            let item_collection = client
+                .search(
+                    "data/100-sentinel-2-items.parquet",
+                    Search::default().collections(collections.clone()),
+                )
+                .unwrap();
+            assert_eq!(item_collection.items.len(), 100, "{collections:?}");
+        }
+        for collections in [
+            vec!["landsat-*".to_string()],
+            vec!["sentinel_2*".to_string()],
+        ] {
+            let item_collection = client
+                .search(
+                    "data/100-sentinel-2-items.parquet",
+                    Search::default().collections(collections.clone()),
+                )
+                .unwrap();
+            assert!(item_collection.items.is_empty(), "{collections:?}");
+        }
+    }
+
     #[rstest]
     fn search_ids(client: Client) {
         let item_collection = client
@@ -826,6 +2005,181 @@ mod tests {
         assert_eq!(item_collection.items.len(), 50);
     }
 
+    #[rstest]
+    fn search_bbox_only_items(client: Client) {
+        let mut item = stac::Item::new("bbox-only");
+        item.bbox = Some(Bbox::new(1., 2., 3., 4.));
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("items.parquet");
+        stac::geoparquet::WriterBuilder::new(std::fs::File::create(&path).unwrap())
+            .build(vec![item])
+            .unwrap()
+            .finish()
+            .unwrap();
+        let href = path.to_str().unwrap();
+        let item_collection = client
+            .search(href, Search::default().bbox(Bbox::new(2., 3., 5., 5.)))
+            .unwrap();
+        assert_eq!(item_collection.items.len(), 1);
+        assert!(item_collection.items[0]["geometry"].is_null());
+        let item_collection = client
+            .search(
+                href,
+                Search::default().intersects(Bbox::new(0., 0., 1.5, 2.5).to_geometry()),
+            )
+            .unwrap();
+        assert_eq!(item_collection.items.len(), 1);
+        let item_collection = client
+            .search(href, Search::default().bbox(Bbox::new(10., 10., 11., 11.)))
+            .unwrap();
+        assert!(item_collection.items.is_empty());
+    }
+
+    #[rstest]
+    fn search_bbox_covering(client: Client) {
+        let item_collection = stac::geoparquet::from_reader(
+            std::fs::File::open("data/100-sentinel-2-items.parquet").unwrap(),
+        )
+        .unwrap();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("items.parquet");
+        stac::geoparquet::WriterBuilder::new(std::fs::File::create(&path).unwrap())
+            .writer_options(stac::geoparquet::WriterOptions::new().with_bbox_covering(true))
+            .build(item_collection.items)
+            .unwrap()
+            .finish()
+            .unwrap();
+        let href = path.to_str().unwrap();
+        let search = Search::default().bbox(Bbox::new(-106.1, 40.5, -106.0, 40.6));
+        let (sql, _) = client.build_query(href, search.clone()).unwrap().unwrap();
+        assert!(sql.contains("bbox.xmin <= ?"), "{sql}");
+        let (sql, _) = client
+            .build_query("data/100-sentinel-2-items.parquet", search.clone())
+            .unwrap()
+            .unwrap();
+        assert!(!sql.contains("bbox.xmin <= ?"), "{sql}");
+        assert_eq!(
+            client.search(href, search.clone()).unwrap().items.len(),
+            client
+                .search("data/100-sentinel-2-items.parquet", search)
+                .unwrap()
+                .items
+                .len()
+        );
+        let item_collection = client
+            .search(href, Search::default().bbox(Bbox::new(10., 10., 11., 11.)))
+            .unwrap();
+        assert!(item_collection.items.is_empty());
+    }
+
+    #[rstest]
+    fn search_hrefs_mixed_hive_partitioning(mut client: Client) {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let partition = temp_dir.path().join("lake").join("year=2024");
+        std::fs::create_dir_all(&partition).unwrap();
+        for (path, collection) in [
+            (partition.join("items.parquet"), "collection-a"),
+            (temp_dir.path().join("items.parquet"), "collection-b"),
+        ] {
+            let items = (0..2)
+                .map(|i| stac::Item::new(format!("{collection}-{i}")).collection(collection))
+                .collect();
+            stac::geoparquet::WriterBuilder::new(std::fs::File::create(&path).unwrap())
+                .build(items)
+                .unwrap()
+                .finish()
+                .unwrap();
+        }
+        let lake = format!("{}/lake/**/*.parquet", temp_dir.path().to_str().unwrap());
+        let file = format!("{}/items.parquet", temp_dir.path().to_str().unwrap());
+        let _ = client.hive_partitioned_hrefs.insert(lake.clone());
+        let item_collection = client
+            .search_hrefs(&[&lake, &file], Search::default())
+            .unwrap();
+        assert_eq!(item_collection.items.len(), 4);
+        let item = item_collection
+            .items
+            .iter()
+            .find(|item| item["collection"] == "collection-a")
+            .unwrap();
+        assert_eq!(item["properties"]["year"], 2024);
+        let item = item_collection
+            .items
+            .iter()
+            .find(|item| item["collection"] == "collection-b")
+            .unwrap();
+        assert!(item["properties"].get("year").is_none());
+    }
+
+    #[rstest]
+    fn collections_quoted_href(client: Client) {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("it's.parquet");
+        let collection = stac::Collection::new("a-collection", "A description");
+        stac::geoparquet::WriterBuilder::new(std::fs::File::create(&path).unwrap())
+            .build(vec![stac::Item::new("an-id").collection("a-collection")])
+            .unwrap()
+            .add_collection(collection)
+            .unwrap()
+            .finish()
+            .unwrap();
+        let collections = client.collections(path.to_str().unwrap()).unwrap();
+        assert_eq!(collections.len(), 1);
+        assert_eq!(collections[0].description, "A description");
+    }
+
+    #[rstest]
+    fn search_hrefs(client: Client) {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut hrefs = Vec::new();
+        for collection in ["collection-a", "collection-b"] {
+            let path = temp_dir.path().join(format!("{collection}.parquet"));
+            let items = (0..2)
+                .map(|i| stac::Item::new(format!("{collection}-{i}")).collection(collection))
+                .collect();
+            stac::geoparquet::WriterBuilder::new(std::fs::File::create(&path).unwrap())
+                .build(items)
+                .unwrap()
+                .finish()
+                .unwrap();
+            hrefs.push(path.to_str().unwrap().to_string());
+        }
+        let hrefs: Vec<&str> = hrefs.iter().map(String::as_str).collect();
+        let item_collection = client.search_hrefs(&hrefs, Search::default()).unwrap();
+        assert_eq!(item_collection.items.len(), 4);
+        let item_collection = client
+            .search_hrefs(
+                &hrefs,
+                Search::default().collections(vec!["collection-b".to_string()]),
+            )
+            .unwrap();
+        assert_eq!(item_collection.items.len(), 2);
+        let glob = format!("{}/*.parquet", temp_dir.path().to_str().unwrap());
+        let item_collection = client.search(&glob, Search::default()).unwrap();
+        assert_eq!(item_collection.items.len(), 4);
+        assert!(
+            client
+                .search_hrefs(&[], Search::default())
+                .unwrap()
+                .items
+                .is_empty()
+        );
+    }
+
+    #[rstest]
+    fn search_bbox_3d_without_elevation(client: Client) {
+        let bbox: Bbox = vec![-106.1, 40.5, 0.0, -106.0, 40.6, 100.0]
+            .try_into()
+            .unwrap();
+        let item_collection = client
+            .search(
+                "data/100-sentinel-2-items.parquet",
+                Search::default().bbox(bbox),
+            )
+            .unwrap();
+        assert_eq!(item_collection.items.len(), 50);
+    }
+
     #[rstest]
     fn search_datetime(client: Client) {
         let item_collection = client
@@ -893,6 +2247,40 @@ mod tests {
         );
     }
 
+    #[rstest]
+    fn search_offset_pagination(client: Client) {
+        let search = Search::default().limit(10).offset(10);
+        let item_collection = client
+            .search("data/100-sentinel-2-items.parquet", search)
+            .unwrap();
+        assert_eq!(item_collection.items.len(), 10);
+        assert_eq!(item_collection.next.unwrap()["offset"], 20);
+        assert_eq!(item_collection.prev.unwrap()["offset"], 0);
+
+        let search = Search::default().limit(10).offset(90);
+        let item_collection = client
+            .search("data/100-sentinel-2-items.parquet", search)
+            .unwrap();
+        assert_eq!(item_collection.items.len(), 10);
+        assert!(item_collection.next.is_none());
+    }
+
+    #[rstest]
+    fn search_paginated(client: Client) {
+        let pages = client
+            .search_paginated(
+                "data/100-sentinel-2-items.parquet",
+                Search::default().offset(50),
+                30,
+            )
+            .map(|page| page.unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(pages.len(), 2);
+        assert_eq!(pages[0].items.len(), 30);
+        assert_eq!(pages[1].items.len(), 20);
+        assert!(pages[1].next.is_none());
+    }
+
     #[rstest]
     fn search_sortby(client: Client) {
         let item_collection = client
@@ -941,6 +2329,41 @@ mod tests {
         assert_eq!(collections.len(), 1);
     }
 
+    #[rstest]
+    fn embedded_collections(client: Client) {
+        use stac::{Collection, testing::Generator};
+
+        let mut collection = Collection::new("embedded", "Real collection metadata");
+        collection.title = Some("An embedded collection".to_string());
+        let items = Generator::new(2)
+            .collection("embedded")
+            .id_prefix("embedded-")
+            .items()
+            .chain(
+                Generator::new(2)
+                    .collection("generated")
+                    .id_prefix("generated-")
+                    .items(),
+            )
+            .collect();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("items.parquet");
+        stac::geoparquet::WriterBuilder::new(std::fs::File::create(&path).unwrap())
+            .collection(collection.clone())
+            .collection(Collection::new("no-items", "A collection without items"))
+            .build(items)
+            .unwrap()
+            .finish()
+            .unwrap();
+        let collections = client.collections(path.to_str().unwrap()).unwrap();
+        assert_eq!(collections.len(), 3);
+        let embedded = collections.iter().find(|c| c.id == "embedded").unwrap();
+        assert_eq!(embedded, &collection);
+        let generated = collections.iter().find(|c| c.id == "generated").unwrap();
+        assert_eq!(generated.description, super::DEFAULT_COLLECTION_DESCRIPTION);
+        assert_eq!(collections[2].id, "no-items");
+    }
+
     #[rstest]
     fn no_convert_wkb(mut client: Client) {
         client.convert_wkb = false;
@@ -971,6 +2394,105 @@ mod tests {
         assert_eq!(item_collection.items.len(), 49);
     }
 
+    #[rstest]
+    fn filter_nested(client: Client) {
+        let search = |common_name: &str| Search {
+            items: Items {
+                filter: Some(stac::api::Filter::Cql2Json(
+                    serde_json::json!({
+                        "op": "=",
+                        "args": [
+                            {"property": "assets.B04.eo:bands[0].common_name"},
+                            common_name
+                        ]
+                    })
+                    .as_object()
+                    .unwrap()
+                    .clone(),
+                )),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let item_collection = client
+            .search("data/100-sentinel-2-items.parquet", search("red"))
+            .unwrap();
+        assert_eq!(item_collection.items.len(), 100);
+        let item_collection = client
+            .search("data/100-sentinel-2-items.parquet", search("blue"))
+            .unwrap();
+        assert!(item_collection.items.is_empty());
+
+        let search = Search {
+            items: Items {
+                filter: Some(
+                    "assets.B04.eo:bands.0.common_name = 'red' AND properties.sat:relative_orbit = 98"
+                        .parse()
+                        .unwrap(),
+                ),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let item_collection = client
+            .search("data/100-sentinel-2-items.parquet", search)
+            .unwrap();
+        assert_eq!(item_collection.items.len(), 49);
+
+        for literal in [
+            "__rustac_nested_property_0",
+            "\"__rustac_nested_property_0\"",
+            "' OR 1=1 --\"__rustac_nested_property_0\"",
+        ] {
+            let item_collection = client
+                .search("data/100-sentinel-2-items.parquet", search(literal))
+                .unwrap();
+            assert!(item_collection.items.is_empty());
+        }
+    }
+
+    #[test]
+    fn substitute_nested_properties() {
+        let accessors = vec!["\"assets\"['B04']".to_string()];
+        assert_eq!(
+            super::substitute_nested_properties(
+                "(\"__rustac_nested_property_0\" = 'a \"__rustac_nested_property_0\" ''b''')",
+                &accessors
+            ),
+            "(\"assets\"['B04'] = 'a \"__rustac_nested_property_0\" ''b''')"
+        );
+        assert_eq!(
+            super::substitute_nested_properties("\"__rustac_nested_property_1\" = 1", &accessors),
+            "\"__rustac_nested_property_1\" = 1"
+        );
+        assert_eq!(
+            super::substitute_nested_properties(
+                "__rustac_nested_property_0 = x__rustac_nested_property_0",
+                &accessors
+            ),
+            "\"assets\"['B04'] = x__rustac_nested_property_0"
+        );
+    }
+
+    #[test]
+    fn nested_accessor() {
+        let columns = ["assets".to_string(), "eo:cloud_cover".to_string()];
+        assert_eq!(
+            super::nested_accessor("assets.B04.eo:bands[0].common_name", &columns).unwrap(),
+            "\"assets\"['B04']['eo:bands'][1]['common_name']"
+        );
+        assert_eq!(
+            super::nested_accessor("assets.B04.eo:bands.0.common_name", &columns).unwrap(),
+            "\"assets\"['B04']['eo:bands'][1]['common_name']"
+        );
+        assert_eq!(
+            super::nested_accessor("properties.eo:cloud_cover", &columns).unwrap(),
+            "\"eo:cloud_cover\""
+        );
+        assert!(super::nested_accessor("foo.bar", &columns).is_none());
+        assert!(super::nested_accessor("assets.B04[x]", &columns).is_none());
+    }
+
     #[rstest]
     fn filter_no_column(client: Client) {
         let search = Search {
@@ -986,6 +2508,42 @@ mod tests {
         assert_eq!(item_collection.items.len(), 0);
     }
 
+    #[rstest]
+    fn query(client: Client) {
+        let search = |query: serde_json::Value| Search {
+            items: Items {
+                query: Some(query.as_object().unwrap().clone()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let count = |query| {
+            client
+                .search("data/100-sentinel-2-items.parquet", search(query))
+                .unwrap()
+                .items
+                .len()
+        };
+        assert_eq!(count(json!({"sat:relative_orbit": {"eq": 98}})), 49);
+        assert_eq!(count(json!({"sat:relative_orbit": {"neq": 98}})), 51);
+        assert_eq!(count(json!({"sat:relative_orbit": {"in": [98]}})), 49);
+        assert_eq!(count(json!({"sat:relative_orbit": {"in": []}})), 0);
+        assert_eq!(count(json!({"foo:bar": {"eq": 42}})), 0);
+        let low_clouds = count(json!({"eo:cloud_cover": {"lt": 10}}));
+        let high_clouds = count(json!({"eo:cloud_cover": {"gte": 10}}));
+        assert!(low_clouds > 0);
+        assert_eq!(low_clouds + high_clouds, 100);
+        assert_eq!(count(json!({"eo:cloud_cover": {"gt": 10, "lte": 10}})), 0);
+        assert_eq!(count(json!({"id": {"startsWith": "S2"}})), 100);
+        assert_eq!(count(json!({"id": {"startsWith": "not-a-prefix"}})), 0);
+        let _ = client
+            .search(
+                "data/100-sentinel-2-items.parquet",
+                search(json!({"id": {"like": "S2%"}})),
+            )
+            .unwrap_err();
+    }
+
     #[rstest]
     fn sortby_property(client: Client) {
         let search = Search {
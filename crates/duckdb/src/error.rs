@@ -32,9 +32,9 @@ pub enum Error {
     #[error(transparent)]
     Stac(#[from] stac::Error),
 
-    /// The query search extension is not implemented.
-    #[error("query is not implemented")]
-    QueryNotImplemented,
+    /// An invalid or unsupported query (from the query search extension).
+    #[error("invalid query: {0}")]
+    InvalidQuery(String),
 
     /// [std::num::TryFromIntError]
     #[error(transparent)]
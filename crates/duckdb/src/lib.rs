@@ -5,10 +5,20 @@
 mod client;
 mod error;
 mod extension;
+pub mod statistics;
 
 pub use {
-    client::ArrowBatchReader, client::Client, client::HrefClient, client::SearchArrowBatchIter,
-    client::SyncHrefClient, error::Error, extension::Extension,
+    client::ArrowBatchReader,
+    client::Client,
+    client::HrefClient,
+    client::SearchArrowBatchIter,
+    client::SearchPages,
+    client::SyncHrefClient,
+    error::Error,
+    extension::Extension,
+    statistics::{
+        FieldStatistics, FileStatistics, NullCount, Percentile, RowGroupStatistics, ValueCount,
+    },
 };
 
 use getrandom as _;
@@ -0,0 +1,152 @@
+//! Field and file statistics computed from stac-geoparquet.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use stac::Bbox;
+
+/// The percentiles computed for numeric fields.
+pub const PERCENTILES: [u8; 5] = [5, 25, 50, 75, 95];
+
+/// The maximum number of value counts reported for each field.
+pub const VALUE_COUNT_LIMIT: usize = 10;
+
+/// Statistics for a single field across many items.
+///
+/// Computed by [Client::statistics](crate::Client::statistics).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FieldStatistics {
+    /// The field name.
+    pub field: String,
+
+    /// The number of non-null values.
+    pub count: u64,
+
+    /// The number of null (or missing) values.
+    pub null_count: u64,
+
+    /// The minimum value.
+    ///
+    /// A number for numeric fields, otherwise a string.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minimum: Option<Value>,
+
+    /// The maximum value.
+    ///
+    /// A number for numeric fields, otherwise a string.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maximum: Option<Value>,
+
+    /// The mean value of a numeric field.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mean: Option<f64>,
+
+    /// The [PERCENTILES] of a numeric field, interpolated between values.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub percentiles: Vec<Percentile>,
+
+    /// The most common values, most common first, up to [VALUE_COUNT_LIMIT].
+    ///
+    /// Values are formatted as strings.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub value_counts: Vec<ValueCount>,
+}
+
+/// Statistics for a whole stac-geoparquet file (or glob of files).
+///
+/// Computed by [Client::file_statistics](crate::Client::file_statistics).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FileStatistics {
+    /// The number of items.
+    pub count: u64,
+
+    /// The distinct collection ids, sorted.
+    pub collections: Vec<String>,
+
+    /// The bounds of the items' bboxes, or of their geometries if there's no
+    /// `bbox` column.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bbox: Option<Bbox>,
+
+    /// The earliest `start_datetime` or `datetime`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_datetime: Option<DateTime<Utc>>,
+
+    /// The latest `end_datetime` or `datetime`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_datetime: Option<DateTime<Utc>>,
+
+    /// The number of null values in each column, in column order.
+    pub null_counts: Vec<NullCount>,
+
+    /// The row groups, in file and row group order.
+    pub row_groups: Vec<RowGroupStatistics>,
+}
+
+/// The number of null values in a column.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NullCount {
+    /// The column name.
+    pub column: String,
+
+    /// The number of rows where the column is null.
+    pub null_count: u64,
+}
+
+/// The size of a parquet row group, read from the file's footer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RowGroupStatistics {
+    /// The file that holds the row group.
+    pub file: String,
+
+    /// The row group's index in its file.
+    pub id: u64,
+
+    /// The number of rows.
+    pub num_rows: u64,
+
+    /// The uncompressed size of the row group's data, in bytes.
+    pub total_byte_size: u64,
+
+    /// The compressed size of the row group's column chunks, in bytes.
+    pub compressed_size: u64,
+}
+
+/// A percentile of a numeric field.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Percentile {
+    /// The percentile, e.g. `50` for the median.
+    pub percentile: u8,
+
+    /// The value at that percentile.
+    pub value: f64,
+}
+
+/// The number of times a value appears.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValueCount {
+    /// The value, formatted as a string.
+    pub value: String,
+
+    /// The number of items with this value.
+    pub count: u64,
+}
+
+impl FieldStatistics {
+    /// Creates new, empty statistics for a field.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_duckdb::FieldStatistics;
+    ///
+    /// let statistics = FieldStatistics::new("eo:cloud_cover");
+    /// assert_eq!(statistics.count, 0);
+    /// ```
+    pub fn new(field: impl ToString) -> FieldStatistics {
+        FieldStatistics {
+            field: field.to_string(),
+            ..Default::default()
+        }
+    }
+}
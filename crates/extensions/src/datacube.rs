@@ -0,0 +1,418 @@
+//! The [Datacube](https://github.com/stac-extensions/datacube) extension.
+
+use crate::Extension;
+use chrono::{DateTime, SecondsFormat, TimeDelta, Utc};
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// The datacube extension describes the dimensions and variables of
+/// multi-dimensional data, e.g. zarr stores or NetCDF files.
+///
+/// # Examples
+///
+/// ```
+/// use stac::Collection;
+/// use stac_extensions::{
+///     Datacube, Extensions,
+///     datacube::{Axis, Dimension, Variable},
+/// };
+///
+/// let mut datacube = Datacube::default();
+/// datacube.dimensions.insert("x".into(), Dimension::spatial(Axis::X, -180.0, 180.0));
+/// datacube.dimensions.insert("y".into(), Dimension::spatial(Axis::Y, -90.0, 90.0));
+/// datacube.variables.insert("temperature".into(), Variable::data(["y", "x"]));
+/// assert!(datacube.validate().is_empty());
+///
+/// let mut collection = Collection::new("an-id", "a description");
+/// collection.set_extension(datacube).unwrap();
+/// assert!(collection.additional_fields.contains_key("cube:dimensions"));
+/// ```
+#[derive(Debug, Serialize, Deserialize, Default, PartialEq, Clone)]
+pub struct Datacube {
+    /// The dimensions of the datacube, keyed by name.
+    #[serde(skip_serializing_if = "IndexMap::is_empty", default)]
+    pub dimensions: IndexMap<String, Dimension>,
+
+    /// The variables of the datacube, keyed by name.
+    #[serde(skip_serializing_if = "IndexMap::is_empty", default)]
+    pub variables: IndexMap<String, Variable>,
+}
+
+/// A dimension of a datacube.
+///
+/// Spatial dimensions have an [Axis], temporal dimensions have ISO 8601
+/// datetimes as their extent and values, and any other dimension (e.g.
+/// bands) has numbers or strings.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct Dimension {
+    /// The type of the dimension.
+    pub r#type: DimensionType,
+
+    /// The axis of a spatial dimension.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub axis: Option<Axis>,
+
+    /// A detailed description of the dimension.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// The lower and upper bounds of the dimension.
+    ///
+    /// Either bound can be `null` for an open extent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extent: Option<[Value; 2]>,
+
+    /// All the values of the dimension, if they're irregularly spaced or
+    /// aren't numbers.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub values: Option<Vec<Value>>,
+
+    /// The space between values, which is a number, an ISO 8601 duration for
+    /// temporal dimensions, or `null` if the values are irregularly spaced.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub step: Option<Value>,
+
+    /// The unit of measurement, preferably compliant with
+    /// [UDUNITS-2](https://ncics.org/portfolio/other-resources/udunits2/).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
+
+    /// The spatial reference system, as an EPSG code, WKT2 string, or
+    /// PROJJSON object.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reference_system: Option<Value>,
+
+    /// Additional fields on the dimension.
+    #[serde(flatten)]
+    pub additional_fields: Map<String, Value>,
+}
+
+/// The type of a [Dimension].
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(rename_all = "lowercase")]
+pub enum DimensionType {
+    /// A spatial dimension, with an [Axis].
+    Spatial,
+
+    /// A temporal dimension.
+    Temporal,
+
+    /// Any other dimension, e.g. `bands`.
+    #[serde(untagged)]
+    Other(String),
+}
+
+/// The axis of a spatial [Dimension].
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum Axis {
+    /// The horizontal x axis, e.g. longitude.
+    X,
+
+    /// The horizontal y axis, e.g. latitude.
+    Y,
+
+    /// The vertical z axis, e.g. elevation.
+    Z,
+}
+
+/// A variable of a datacube.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct Variable {
+    /// The names of the dimensions of this variable, in order.
+    pub dimensions: Vec<String>,
+
+    /// The type of the variable.
+    pub r#type: VariableType,
+
+    /// A detailed description of the variable.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// The lower and upper bounds of the variable's values.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extent: Option<[Value; 2]>,
+
+    /// All the values of the variable, if there are only a few.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub values: Option<Vec<Value>>,
+
+    /// The unit of measurement.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
+
+    /// Additional fields on the variable.
+    #[serde(flatten)]
+    pub additional_fields: Map<String, Value>,
+}
+
+/// The type of a [Variable].
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum VariableType {
+    /// A variable that holds data.
+    Data,
+
+    /// A variable that describes other variables, e.g. coordinates.
+    Auxiliary,
+}
+
+impl Datacube {
+    /// Returns a message for each problem with this datacube, which is empty
+    /// if it is valid.
+    ///
+    /// This checks the things that a JSON schema can't: that variables only
+    /// use dimensions that exist, that spatial dimensions (and only spatial
+    /// dimensions) have an axis, and that numeric extents aren't backwards.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_extensions::{Datacube, datacube::Variable};
+    ///
+    /// let mut datacube = Datacube::default();
+    /// datacube.variables.insert("temperature".into(), Variable::data(["time"]));
+    /// assert_eq!(
+    ///     datacube.validate(),
+    ///     ["variable 'temperature' has an unknown dimension 'time'"]
+    /// );
+    /// ```
+    pub fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        for (name, dimension) in &self.dimensions {
+            match (&dimension.r#type, dimension.axis) {
+                (DimensionType::Spatial, None) => {
+                    problems.push(format!("spatial dimension '{name}' has no axis"))
+                }
+                (DimensionType::Spatial, Some(_)) | (_, None) => {}
+                (_, Some(_)) => {
+                    problems.push(format!("dimension '{name}' has an axis but isn't spatial"))
+                }
+            }
+            if let Some([Value::Number(min), Value::Number(max)]) = &dimension.extent
+                && min.as_f64() > max.as_f64()
+            {
+                problems.push(format!(
+                    "dimension '{name}' has an extent minimum greater than its maximum"
+                ));
+            }
+        }
+        for (name, variable) in &self.variables {
+            for dimension in &variable.dimensions {
+                if !self.dimensions.contains_key(dimension) {
+                    problems.push(format!(
+                        "variable '{name}' has an unknown dimension '{dimension}'"
+                    ));
+                }
+            }
+        }
+        problems
+    }
+}
+
+impl Dimension {
+    /// Creates a new spatial dimension along an axis, with an extent and the
+    /// default reference system, EPSG:4326.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_extensions::datacube::{Axis, Dimension};
+    ///
+    /// let dimension = Dimension::spatial(Axis::X, -180.0, 180.0);
+    /// ```
+    pub fn spatial(axis: Axis, min: f64, max: f64) -> Dimension {
+        Dimension {
+            axis: Some(axis),
+            extent: Some([min.into(), max.into()]),
+            ..Dimension::new(DimensionType::Spatial)
+        }
+    }
+
+    /// Creates a new temporal dimension from datetimes.
+    ///
+    /// The extent is the first and last datetime, and the sorted, distinct
+    /// datetimes are the values. If the datetimes are evenly spaced, the step
+    /// is that spacing as an ISO 8601 duration, and otherwise it's `null`.
+    /// Returns `None` if there aren't any datetimes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chrono::{TimeZone, Utc};
+    /// use stac_extensions::datacube::Dimension;
+    ///
+    /// let datetimes = [1, 2, 3].map(|day| Utc.with_ymd_and_hms(2024, 1, day, 0, 0, 0).unwrap());
+    /// let dimension = Dimension::temporal(datetimes).unwrap();
+    /// assert_eq!(dimension.extent.unwrap()[0], "2024-01-01T00:00:00Z");
+    /// assert_eq!(dimension.step.unwrap(), "P1D");
+    /// ```
+    pub fn temporal(datetimes: impl IntoIterator<Item = DateTime<Utc>>) -> Option<Dimension> {
+        let mut datetimes: Vec<_> = datetimes.into_iter().collect();
+        datetimes.sort();
+        datetimes.dedup();
+        let first = *datetimes.first()?;
+        let last = *datetimes.last()?;
+        let step = if datetimes.len() < 2 {
+            None
+        } else {
+            let step = datetimes[1] - datetimes[0];
+            if datetimes
+                .windows(2)
+                .all(|window| window[1] - window[0] == step)
+            {
+                Some(duration(step).map(Value::from).unwrap_or(Value::Null))
+            } else {
+                Some(Value::Null)
+            }
+        };
+        Some(Dimension {
+            extent: Some([rfc3339(first).into(), rfc3339(last).into()]),
+            values: Some(
+                datetimes
+                    .into_iter()
+                    .map(|datetime| rfc3339(datetime).into())
+                    .collect(),
+            ),
+            step,
+            ..Dimension::new(DimensionType::Temporal)
+        })
+    }
+
+    fn new(r#type: DimensionType) -> Dimension {
+        Dimension {
+            r#type,
+            axis: None,
+            description: None,
+            extent: None,
+            values: None,
+            step: None,
+            unit: None,
+            reference_system: None,
+            additional_fields: Map::new(),
+        }
+    }
+}
+
+impl Variable {
+    /// Creates a new data variable with the given dimensions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_extensions::datacube::Variable;
+    ///
+    /// let variable = Variable::data(["time", "y", "x"]);
+    /// ```
+    pub fn data(dimensions: impl IntoIterator<Item = impl ToString>) -> Variable {
+        Variable {
+            dimensions: dimensions.into_iter().map(|d| d.to_string()).collect(),
+            r#type: VariableType::Data,
+            description: None,
+            extent: None,
+            values: None,
+            unit: None,
+            additional_fields: Map::new(),
+        }
+    }
+}
+
+impl Extension for Datacube {
+    const IDENTIFIER: &'static str =
+        "https://stac-extensions.github.io/datacube/v2.2.0/schema.json";
+    const PREFIX: &'static str = "cube";
+}
+
+fn rfc3339(datetime: DateTime<Utc>) -> String {
+    datetime.to_rfc3339_opts(SecondsFormat::AutoSi, true)
+}
+
+/// Formats a positive, whole-second time delta as an ISO 8601 duration.
+fn duration(delta: TimeDelta) -> Option<String> {
+    if delta.subsec_nanos() != 0 || delta <= TimeDelta::zero() {
+        return None;
+    }
+    let seconds = delta.num_seconds();
+    let duration = if seconds % 86400 == 0 {
+        format!("P{}D", seconds / 86400)
+    } else if seconds % 3600 == 0 {
+        format!("PT{}H", seconds / 3600)
+    } else if seconds % 60 == 0 {
+        format!("PT{}M", seconds / 60)
+    } else {
+        format!("PT{seconds}S")
+    };
+    Some(duration)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Axis, Datacube, Dimension, DimensionType, VariableType};
+    use crate::{Extensions, Item};
+    use chrono::{TimeZone, Utc};
+    use serde_json::json;
+
+    #[test]
+    fn round_trip() {
+        let mut item = Item::new("an-id");
+        let _ = item.properties.additional_fields.insert(
+            "cube:dimensions".to_string(),
+            json!({
+                "x": {"type": "spatial", "axis": "x", "extent": [-180, 180], "reference_system": 4326},
+                "time": {"type": "temporal", "extent": ["2024-01-01T00:00:00Z", null]},
+                "bands": {"type": "bands", "values": ["red", "green", "blue"]}
+            }),
+        );
+        let _ = item.properties.additional_fields.insert(
+            "cube:variables".to_string(),
+            json!({"lat": {"dimensions": ["x"], "type": "auxiliary"}}),
+        );
+        let datacube: Datacube = item.extension().unwrap();
+        assert_eq!(datacube.dimensions["x"].axis, Some(Axis::X));
+        assert_eq!(
+            datacube.dimensions["bands"].r#type,
+            DimensionType::Other("bands".to_string())
+        );
+        assert_eq!(datacube.variables["lat"].r#type, VariableType::Auxiliary);
+        assert!(datacube.validate().is_empty());
+
+        item.set_extension(datacube.clone()).unwrap();
+        assert_eq!(
+            item.extensions,
+            ["https://stac-extensions.github.io/datacube/v2.2.0/schema.json"]
+        );
+        assert_eq!(
+            item.properties.additional_fields["cube:dimensions"]["bands"]["type"],
+            "bands"
+        );
+        assert_eq!(item.extension::<Datacube>().unwrap(), datacube);
+    }
+
+    #[test]
+    fn validate() {
+        let mut datacube = Datacube::default();
+        let _ = datacube
+            .dimensions
+            .insert("x".to_string(), Dimension::spatial(Axis::X, 180.0, -180.0));
+        let mut y = Dimension::spatial(Axis::Y, -90.0, 90.0);
+        y.axis = None;
+        let _ = datacube.dimensions.insert("y".to_string(), y);
+        assert_eq!(datacube.validate().len(), 2);
+    }
+
+    #[test]
+    fn temporal_irregular() {
+        let datetimes = [1, 2, 4].map(|day| Utc.with_ymd_and_hms(2024, 1, day, 0, 0, 0).unwrap());
+        let dimension = Dimension::temporal(datetimes).unwrap();
+        assert_eq!(dimension.step, Some(serde_json::Value::Null));
+        assert_eq!(dimension.values.unwrap().len(), 3);
+
+        let datetimes =
+            [0, 6, 12].map(|hour| Utc.with_ymd_and_hms(2024, 1, 1, hour, 0, 0).unwrap());
+        let dimension = Dimension::temporal(datetimes).unwrap();
+        assert_eq!(dimension.step.unwrap(), "PT6H");
+
+        assert!(Dimension::temporal([]).is_none());
+    }
+}
@@ -10,7 +10,7 @@ use serde::{Deserialize, Serialize};
 /// electromagnetic spectrum. Examples of EO data include sensors with visible,
 /// short-wave and mid-wave IR bands (e.g., the OLI instrument on Landsat-8),
 /// long-wave IR bands (e.g. TIRS aboard Landsat-8).
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Default, PartialEq, Clone)]
 pub struct ElectroOptical {
     /// An array of available bands where each object is a [Band].
     ///
@@ -30,7 +30,7 @@ pub struct ElectroOptical {
 /// [Spectral
 /// bands](https://www.sciencedirect.com/topics/earth-and-planetary-sciences/spectral-band)
 /// in an [Asset](stac::Asset).
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Default, PartialEq, Clone)]
 pub struct Band {
     /// The name of the band (e.g., "B01", "B8", "band2", "red").
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -11,13 +11,15 @@
 //! | Extension | Maturity | **rustac** supported version |
 //! | -- | -- | -- |
 //! | [Authentication](https://github.com/stac-extensions/authentication) | Proposal | v1.1.0 |
+//! | [Datacube](https://github.com/stac-extensions/datacube) | Candidate | v2.2.0 |
 //! | [Electro-Optical](https://github.com/stac-extensions/eo) | Stable | v1.1.0 |
 //! | [File Info](https://github.com/stac-extensions/file) | Stable | n/a |
 //! | [Landsat](https://github.com/stac-extensions/landsat) | Stable | n/a |
 //! | [Projection](https://github.com/stac-extensions/projection) | Stable | v1.1.0 |
 //! | [Raster](https://github.com/stac-extensions/raster) | Candidate | v1.1.0 |
+//! | [SAR](https://github.com/stac-extensions/sar) | Candidate | v1.0.0 |
 //! | [Scientific Citation](https://github.com/stac-extensions/scientific) | Stable | n/a |
-//! | [View Geometry](https://github.com/stac-extensions/view) | Stable | n/a |
+//! | [View Geometry](https://github.com/stac-extensions/view) | Stable | v1.0.0 |
 //!
 //! ## Usage
 //!
@@ -44,14 +46,22 @@
 //! ```
 
 pub mod authentication;
+pub mod datacube;
 pub mod electro_optical;
 pub mod projection;
 pub mod raster;
+pub mod registry;
+pub mod sar;
+pub mod view;
 
+pub use datacube::Datacube;
+pub use electro_optical::ElectroOptical;
 pub use projection::Projection;
 pub use raster::Raster;
+pub use sar::Sar;
 use serde::{Serialize, de::DeserializeOwned};
-use stac::{Catalog, Collection, Fields, Item, Result};
+use stac::{Catalog, Collection, Error, Fields, Item, Result, SelfHref};
+pub use view::View;
 
 /// A trait implemented by extensions.
 ///
@@ -172,12 +182,15 @@ pub trait Extensions: Fields {
     /// use stac_extensions::{Projection, Extensions};
     ///
     /// let mut item = Item::new("an-id");
+    /// item.extensions.push("https://stac-extensions.github.io/projection/v1.1.0/schema.json".to_string());
     /// let projection = Projection { code: Some("EPSG:4326".to_string()), ..Default::default() };
     /// item.set_extension(projection).unwrap();
+    /// assert_eq!(item.extensions, ["https://stac-extensions.github.io/projection/v2.0.0/schema.json"]);
     /// ```
     fn set_extension<E: Extension>(&mut self, extension: E) -> Result<()> {
+        self.extensions_mut()
+            .retain(|extension| !extension.starts_with(E::identifier_prefix()));
         self.extensions_mut().push(E::IDENTIFIER.to_string());
-        self.extensions_mut().dedup();
         self.remove_fields_with_prefix(E::PREFIX);
         self.set_fields_with_prefix(E::PREFIX, extension)
     }
@@ -200,6 +213,59 @@ pub trait Extensions: Fields {
         self.extensions_mut()
             .retain(|extension| !extension.starts_with(E::identifier_prefix()))
     }
+
+    /// Pins an extension on this object to a version, rewriting its schema
+    /// URI and migrating fields that were renamed between versions, e.g.
+    /// `proj:epsg` to `proj:code`.
+    ///
+    /// The extension is looked up by name or field prefix in
+    /// [registry::KNOWN], and downgrades are supported as well as upgrades.
+    /// Returns false if this object doesn't use the extension. Returns an
+    /// error if the version isn't known, or if it's a different major version
+    /// that there's no field migration for, e.g. raster v1 to v2.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::Item;
+    /// use stac_extensions::Extensions;
+    ///
+    /// let mut item: Item = stac::read("examples/extensions-collection/proj-example/proj-example.json").unwrap();
+    /// assert!(item.upgrade("proj", "v2.0.0").unwrap());
+    /// assert!(!item.upgrade("raster", "v2.0.0").unwrap());
+    /// ```
+    fn upgrade(&mut self, name: &str, version: &str) -> Result<bool>
+    where
+        Self: Serialize + DeserializeOwned + SelfHref,
+    {
+        let entry =
+            registry::entry(name).ok_or_else(|| Error::UnknownExtension(name.to_string()))?;
+        let version = entry.check_version(version)?;
+        let Some(from) = self
+            .extensions()
+            .iter()
+            .find_map(|uri| entry.version(uri))
+            .map(String::from)
+        else {
+            return Ok(false);
+        };
+        let uri = entry.schema_uri(&version);
+        let mut value = serde_json::to_value(&*self)?;
+        entry.migrate(&mut value, &from, &version)?;
+        let self_href = self.self_href_mut().take();
+        *self = serde_json::from_value(value)?;
+        *self.self_href_mut() = self_href;
+        let mut added = false;
+        for extension in std::mem::take(self.extensions_mut()) {
+            if entry.version(&extension).is_none() {
+                self.extensions_mut().push(extension);
+            } else if !added {
+                self.extensions_mut().push(uri.clone());
+                added = true;
+            }
+        }
+        Ok(true)
+    }
 }
 
 macro_rules! impl_extensions {
@@ -0,0 +1,491 @@
+//! A registry of known extensions, their versions, and local schema copies.
+//!
+//! Objects can be pinned to a specific version of an extension with
+//! [Extensions::upgrade](crate::Extensions::upgrade), which rewrites the
+//! schema URI and migrates fields that were renamed between versions:
+//!
+//! ```
+//! use stac::Item;
+//! use stac_extensions::Extensions;
+//!
+//! let mut item = Item::new("an-id");
+//! item.extensions.push("https://stac-extensions.github.io/projection/v1.1.0/schema.json".to_string());
+//! let _ = item.properties.additional_fields.insert("proj:epsg".into(), 32614.into());
+//! assert!(item.upgrade("proj", "v2.0.0").unwrap());
+//! assert_eq!(item.extensions[0], "https://stac-extensions.github.io/projection/v2.0.0/schema.json");
+//! assert_eq!(item.properties.additional_fields["proj:code"], "EPSG:32614");
+//! ```
+
+use serde_json::{Map, Value};
+use stac::{Error, Result};
+use std::collections::HashMap;
+
+/// The extensions known to this crate.
+pub const KNOWN: &[Entry] = &[
+    Entry::new("authentication", "auth", &["v1.0.0", "v1.1.0"]),
+    Entry::new("datacube", "cube", &["v2.0.0", "v2.1.0", "v2.2.0"]),
+    Entry::new("eo", "eo", &["v1.0.0", "v1.1.0", "v2.0.0"]),
+    Entry::new("file", "file", &["v1.0.0", "v2.0.0", "v2.1.0"]),
+    Entry::new("landsat", "landsat", &["v2.0.0"]),
+    Entry::new(
+        "projection",
+        "proj",
+        &["v1.0.0", "v1.1.0", "v1.2.0", "v2.0.0"],
+    ),
+    Entry::new("raster", "raster", &["v1.0.0", "v1.1.0", "v2.0.0"]),
+    Entry::new("sar", "sar", &["v1.0.0"]),
+    Entry::new("scientific", "sci", &["v1.0.0"]),
+    Entry::new("view", "view", &["v1.0.0"]),
+];
+
+const BASE_URI: &str = "https://stac-extensions.github.io/";
+
+/// A known extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Entry {
+    /// The extension's name, which is the first path segment of its schema
+    /// URI, e.g. `projection`.
+    pub name: &'static str,
+
+    /// The extension's field name prefix, e.g. `proj`.
+    pub prefix: &'static str,
+
+    /// The published versions, oldest first.
+    pub versions: &'static [&'static str],
+}
+
+/// Local copies of extension schemas, and the versions that objects should
+/// be pinned to.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json::json;
+/// use stac::Item;
+/// use stac_extensions::registry::Registry;
+///
+/// let mut registry = Registry::new();
+/// registry.pin("projection", "v2.0.0").unwrap();
+/// registry.add_schema(
+///     "https://stac-extensions.github.io/projection/v2.0.0/schema.json",
+///     json!({"type": "object"}),
+/// );
+/// let mut item = Item::new("an-id");
+/// item.extensions.push("https://stac-extensions.github.io/projection/v1.1.0/schema.json".to_string());
+/// registry.apply(&mut item).unwrap();
+/// let schema = registry.schema(&item.extensions[0]).unwrap();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Registry {
+    schemas: HashMap<String, Value>,
+    pins: HashMap<&'static str, String>,
+}
+
+impl Entry {
+    const fn new(
+        name: &'static str,
+        prefix: &'static str,
+        versions: &'static [&'static str],
+    ) -> Entry {
+        Entry {
+            name,
+            prefix,
+            versions,
+        }
+    }
+
+    /// Returns the latest known version.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let entry = stac_extensions::registry::entry("proj").unwrap();
+    /// assert_eq!(entry.latest(), "v2.0.0");
+    /// ```
+    pub fn latest(&self) -> &'static str {
+        self.versions
+            .last()
+            .expect("all known extensions should have at least one version")
+    }
+
+    /// Returns everything in this extension's schema URIs up until the version.
+    pub fn identifier_prefix(&self) -> String {
+        format!("{BASE_URI}{}/", self.name)
+    }
+
+    /// Returns the schema URI for a version.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let entry = stac_extensions::registry::entry("raster").unwrap();
+    /// assert_eq!(
+    ///     entry.schema_uri("v2.0.0"),
+    ///     "https://stac-extensions.github.io/raster/v2.0.0/schema.json"
+    /// );
+    /// ```
+    pub fn schema_uri(&self, version: &str) -> String {
+        format!("{BASE_URI}{}/{}/schema.json", self.name, normalize(version))
+    }
+
+    /// Returns the version of a schema URI, if it belongs to this extension.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let entry = stac_extensions::registry::entry("eo").unwrap();
+    /// assert_eq!(
+    ///     entry.version("https://stac-extensions.github.io/eo/v1.1.0/schema.json"),
+    ///     Some("v1.1.0")
+    /// );
+    /// ```
+    pub fn version<'a>(&self, uri: &'a str) -> Option<&'a str> {
+        uri.strip_prefix(BASE_URI)?
+            .strip_prefix(self.name)?
+            .strip_prefix('/')?
+            .split('/')
+            .next()
+            .filter(|version| !version.is_empty())
+    }
+
+    /// Returns the normalized version if it's one of this extension's
+    /// published versions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let entry = stac_extensions::registry::entry("raster").unwrap();
+    /// assert_eq!(entry.check_version("2.0.0").unwrap(), "v2.0.0");
+    /// let _ = entry.check_version("v3.0.0").unwrap_err();
+    /// ```
+    pub fn check_version(&self, version: &str) -> Result<String> {
+        let version = normalize(version);
+        if self.versions.contains(&version.as_str()) {
+            Ok(version)
+        } else {
+            Err(Error::UnknownExtensionVersion {
+                name: self.name.to_string(),
+                version,
+            })
+        }
+    }
+
+    /// Migrates this extension's fields in a JSON object from one version to
+    /// another.
+    ///
+    /// Fields are migrated in the object itself, and in its `properties`,
+    /// `assets`, `item_assets`, and `summaries`.
+    ///
+    /// Returns an error, and leaves the object unchanged, if the versions
+    /// have different major versions and this crate doesn't know how to
+    /// migrate between them. Only `projection` has such a migration.
+    pub fn migrate(&self, value: &mut Value, from: &str, to: &str) -> Result<()> {
+        if major(from) != major(to) && self.name != "projection" {
+            return Err(Error::UnsupportedExtensionMigration {
+                name: self.name.to_string(),
+                from: from.to_string(),
+                to: to.to_string(),
+            });
+        }
+        let Some(object) = value.as_object_mut() else {
+            return Ok(());
+        };
+        let migrate = |fields: &mut Map<String, Value>, summaries: bool| {
+            if self.name == "projection" {
+                migrate_projection(fields, from, to, summaries);
+            }
+        };
+        migrate(object, false);
+        if let Some(Value::Object(properties)) = object.get_mut("properties") {
+            migrate(properties, false);
+        }
+        for key in ["assets", "item_assets"] {
+            if let Some(Value::Object(assets)) = object.get_mut(key) {
+                for asset in assets.values_mut().filter_map(Value::as_object_mut) {
+                    migrate(asset, false);
+                }
+            }
+        }
+        if let Some(Value::Object(summaries)) = object.get_mut("summaries") {
+            migrate(summaries, true);
+        }
+        Ok(())
+    }
+}
+
+impl Registry {
+    /// Creates a new, empty registry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_extensions::registry::Registry;
+    ///
+    /// let registry = Registry::new();
+    /// ```
+    pub fn new() -> Registry {
+        Registry::default()
+    }
+
+    /// Adds a local copy of a schema.
+    pub fn add_schema(&mut self, uri: impl ToString, schema: Value) {
+        let _ = self.schemas.insert(uri.to_string(), schema);
+    }
+
+    /// Returns the local copy of a schema, if there is one.
+    pub fn schema(&self, uri: &str) -> Option<&Value> {
+        self.schemas.get(uri)
+    }
+
+    /// Pins an extension, by name or prefix, to a version.
+    ///
+    /// Returns an error if the extension, or the version, isn't known.
+    pub fn pin(&mut self, name: &str, version: &str) -> Result<()> {
+        let entry = entry(name).ok_or_else(|| Error::UnknownExtension(name.to_string()))?;
+        let version = entry.check_version(version)?;
+        let _ = self.pins.insert(entry.name, version);
+        Ok(())
+    }
+
+    /// Returns the version an extension, by name or prefix, is pinned to.
+    pub fn pinned(&self, name: &str) -> Option<&str> {
+        entry(name)
+            .and_then(|entry| self.pins.get(entry.name))
+            .map(String::as_str)
+    }
+
+    /// Upgrades (or downgrades) every pinned extension on an object to its
+    /// pinned version.
+    pub fn apply<E: crate::Extensions>(&self, object: &mut E) -> Result<()>
+    where
+        E: serde::Serialize + serde::de::DeserializeOwned + stac::SelfHref,
+    {
+        for (name, version) in &self.pins {
+            let _ = object.upgrade(name, version)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns a known extension by its name (e.g. `projection`) or field prefix
+/// (e.g. `proj`).
+///
+/// # Examples
+///
+/// ```
+/// let entry = stac_extensions::registry::entry("proj").unwrap();
+/// assert_eq!(entry.name, "projection");
+/// ```
+pub fn entry(name: &str) -> Option<&'static Entry> {
+    KNOWN
+        .iter()
+        .find(|entry| entry.name == name || entry.prefix == name)
+}
+
+/// Returns the known extension that a schema URI belongs to.
+///
+/// # Examples
+///
+/// ```
+/// let entry = stac_extensions::registry::entry_for_uri(
+///     "https://stac-extensions.github.io/raster/v1.1.0/schema.json"
+/// ).unwrap();
+/// assert_eq!(entry.prefix, "raster");
+/// ```
+pub fn entry_for_uri(uri: &str) -> Option<&'static Entry> {
+    KNOWN.iter().find(|entry| entry.version(uri).is_some())
+}
+
+fn normalize(version: &str) -> String {
+    if version.starts_with('v') {
+        version.to_string()
+    } else {
+        format!("v{version}")
+    }
+}
+
+fn major(version: &str) -> Option<u64> {
+    version
+        .trim_start_matches('v')
+        .split('.')
+        .next()
+        .and_then(|major| major.parse().ok())
+}
+
+/// `proj:epsg` was replaced by `proj:code` in v2.0.0.
+fn migrate_projection(fields: &mut Map<String, Value>, from: &str, to: &str, summaries: bool) {
+    let (Some(from), Some(to)) = (major(from), major(to)) else {
+        return;
+    };
+    let convert = |value: Value, f: &dyn Fn(Value) -> Option<Value>| -> Option<Value> {
+        match value {
+            Value::Array(values) if summaries => values
+                .into_iter()
+                .map(f)
+                .collect::<Option<Vec<_>>>()
+                .map(Value::Array),
+            value => f(value),
+        }
+    };
+    if from < 2 && to >= 2 {
+        if let Some(epsg) = fields.remove("proj:epsg") {
+            let code = convert(epsg.clone(), &|epsg| match epsg {
+                Value::Null => Some(Value::Null),
+                Value::Number(number) => Some(format!("EPSG:{number}").into()),
+                _ => None,
+            });
+            match code {
+                Some(code) => {
+                    let _ = fields.insert("proj:code".to_string(), code);
+                }
+                None => {
+                    let _ = fields.insert("proj:epsg".to_string(), epsg);
+                }
+            }
+        }
+    } else if from >= 2
+        && to < 2
+        && let Some(code) = fields.remove("proj:code")
+    {
+        let epsg = convert(code.clone(), &|code| match code {
+            Value::Null => Some(Value::Null),
+            Value::String(code) => code
+                .strip_prefix("EPSG:")
+                .and_then(|epsg| epsg.parse::<u64>().ok())
+                .map(Value::from),
+            _ => None,
+        });
+        match epsg {
+            Some(epsg) => {
+                let _ = fields.insert("proj:epsg".to_string(), epsg);
+            }
+            None => {
+                let _ = fields.insert("proj:code".to_string(), code);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Registry;
+    use crate::Extensions;
+    use serde_json::{Value, json};
+    use stac::{Asset, Collection, Error, Item};
+
+    #[test]
+    fn known() {
+        for entry in super::KNOWN {
+            assert_eq!(super::entry(entry.name), Some(entry));
+            assert_eq!(super::entry(entry.prefix), Some(entry));
+            assert_eq!(
+                super::entry_for_uri(&entry.schema_uri(entry.latest())),
+                Some(entry)
+            );
+        }
+        assert!(super::entry("not-an-extension").is_none());
+    }
+
+    #[test]
+    fn upgrade_projection() {
+        let mut item = Item::new("an-id");
+        item.extensions
+            .push("https://stac-extensions.github.io/projection/v1.1.0/schema.json".to_string());
+        let _ = item
+            .properties
+            .additional_fields
+            .insert("proj:epsg".into(), json!(4326));
+        let mut asset = Asset::new("a-href");
+        let _ = asset
+            .additional_fields
+            .insert("proj:epsg".into(), Value::Null);
+        let _ = item.assets.insert("data".into(), asset);
+        assert!(item.upgrade("projection", "2.0.0").unwrap());
+        assert_eq!(
+            item.extensions,
+            ["https://stac-extensions.github.io/projection/v2.0.0/schema.json"]
+        );
+        assert_eq!(item.properties.additional_fields["proj:code"], "EPSG:4326");
+        assert!(item.assets["data"].additional_fields["proj:code"].is_null());
+
+        assert!(item.upgrade("proj", "v1.2.0").unwrap());
+        assert_eq!(item.properties.additional_fields["proj:epsg"], 4326);
+        assert!(!item.properties.additional_fields.contains_key("proj:code"));
+    }
+
+    #[test]
+    fn upgrade_summaries() {
+        let mut collection = Collection::new("an-id", "a description");
+        collection
+            .extensions
+            .push("https://stac-extensions.github.io/projection/v1.0.0/schema.json".to_string());
+        collection.summaries = Some(
+            json!({"proj:epsg": [32614, 32615]})
+                .as_object()
+                .unwrap()
+                .clone(),
+        );
+        assert!(collection.upgrade("proj", "v2.0.0").unwrap());
+        assert_eq!(
+            collection.summaries.unwrap()["proj:code"],
+            json!(["EPSG:32614", "EPSG:32615"])
+        );
+    }
+
+    #[test]
+    fn upgrade_missing_or_unknown() {
+        let mut item = Item::new("an-id");
+        assert!(!item.upgrade("proj", "v2.0.0").unwrap());
+        let _ = item.upgrade("not-an-extension", "v1.0.0").unwrap_err();
+    }
+
+    #[test]
+    fn upgrade_without_migration() {
+        let mut item = Item::new("an-id");
+        item.extensions
+            .push("https://stac-extensions.github.io/raster/v1.1.0/schema.json".to_string());
+        let _ = item
+            .properties
+            .additional_fields
+            .insert("raster:bands".into(), json!([{"nodata": 0}]));
+        assert!(matches!(
+            item.upgrade("raster", "v2.0.0").unwrap_err(),
+            Error::UnsupportedExtensionMigration { .. }
+        ));
+        assert_eq!(
+            item.extensions,
+            ["https://stac-extensions.github.io/raster/v1.1.0/schema.json"]
+        );
+        assert!(
+            item.properties
+                .additional_fields
+                .contains_key("raster:bands")
+        );
+        let _ = item.upgrade("raster", "v3.0.0").unwrap_err();
+    }
+
+    #[test]
+    fn pin() {
+        let mut registry = Registry::new();
+        registry.pin("raster", "1.0.0").unwrap();
+        assert_eq!(registry.pinned("raster"), Some("v1.0.0"));
+        let _ = registry.pin("not-an-extension", "v1.0.0").unwrap_err();
+        assert!(matches!(
+            registry.pin("raster", "v1.2.0").unwrap_err(),
+            Error::UnknownExtensionVersion { .. }
+        ));
+        let mut item = Item::new("an-id");
+        item.extensions
+            .push("https://stac-extensions.github.io/raster/v1.1.0/schema.json".to_string());
+        registry.apply(&mut item).unwrap();
+        assert_eq!(
+            item.extensions,
+            ["https://stac-extensions.github.io/raster/v1.0.0/schema.json"]
+        );
+
+        registry.pin("eo", "v2.0.0").unwrap();
+        item.extensions
+            .push("https://stac-extensions.github.io/eo/v1.1.0/schema.json".to_string());
+        let _ = registry.apply(&mut item).unwrap_err();
+    }
+}
@@ -0,0 +1,102 @@
+//! The [SAR](https://github.com/stac-extensions/sar) extension.
+
+use crate::Extension;
+use serde::{Deserialize, Serialize};
+
+/// The synthetic-aperture radar extension covers data that represents a
+/// snapshot of the Earth for a single date and time, taken by a radar
+/// instrument.
+#[derive(Debug, Serialize, Deserialize, Default, PartialEq, Clone)]
+pub struct Sar {
+    /// The name of the sensor acquisition mode that is commonly used, e.g.
+    /// `IW` for Sentinel-1's interferometric wide swath mode.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instrument_mode: Option<String>,
+
+    /// The common name for the frequency band to make it easier to search
+    /// for bands across instruments, e.g. `C` or `L`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frequency_band: Option<String>,
+
+    /// The center frequency of the instrument, in gigahertz (GHz).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub center_frequency: Option<f64>,
+
+    /// Any combination of polarizations, e.g. `VV` and `VH`.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub polarizations: Vec<String>,
+
+    /// The product type, e.g. `GRD` or `SLC`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub product_type: Option<String>,
+
+    /// The range resolution, which is the maximum ability to distinguish two
+    /// adjacent targets perpendicular to the flight path, in meters.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolution_range: Option<f64>,
+
+    /// The azimuth resolution, which is the maximum ability to distinguish
+    /// two adjacent targets parallel to the flight path, in meters.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolution_azimuth: Option<f64>,
+
+    /// The range pixel spacing, which is the distance between adjacent
+    /// pixels perpendicular to the flight path, in meters.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pixel_spacing_range: Option<f64>,
+
+    /// The azimuth pixel spacing, which is the distance between adjacent
+    /// pixels parallel to the flight path, in meters.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pixel_spacing_azimuth: Option<f64>,
+
+    /// Number of range looks.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub looks_range: Option<f64>,
+
+    /// Number of azimuth looks.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub looks_azimuth: Option<f64>,
+
+    /// The equivalent number of looks.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub looks_equivalent_number: Option<f64>,
+
+    /// Antenna pointing direction relative to the flight trajectory of the
+    /// satellite, either `left` or `right`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub observation_direction: Option<String>,
+}
+
+impl Extension for Sar {
+    const IDENTIFIER: &'static str = "https://stac-extensions.github.io/sar/v1.0.0/schema.json";
+    const PREFIX: &'static str = "sar";
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Sar;
+    use crate::{Extensions, Item};
+
+    #[test]
+    fn round_trip() {
+        let mut item = Item::new("an-id");
+        let sar = Sar {
+            instrument_mode: Some("IW".to_string()),
+            frequency_band: Some("C".to_string()),
+            polarizations: vec!["VV".to_string(), "VH".to_string()],
+            product_type: Some("GRD".to_string()),
+            ..Default::default()
+        };
+        item.set_extension(sar.clone()).unwrap();
+        assert_eq!(
+            item.extensions,
+            ["https://stac-extensions.github.io/sar/v1.0.0/schema.json"]
+        );
+        assert_eq!(
+            item.properties.additional_fields["sar:instrument_mode"],
+            "IW"
+        );
+        assert_eq!(item.extension::<Sar>().unwrap(), sar);
+    }
+}
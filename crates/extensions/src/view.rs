@@ -0,0 +1,60 @@
+//! The [View Geometry](https://github.com/stac-extensions/view) extension.
+
+use crate::Extension;
+use serde::{Deserialize, Serialize};
+
+/// The view geometry extension describes the angles of the sensor and the
+/// sun relative to the scene, which is useful for satellite imagery.
+#[derive(Debug, Serialize, Deserialize, Default, PartialEq, Clone)]
+pub struct View {
+    /// The angle from the sensor between nadir (straight down) and the scene
+    /// center, in degrees (0-90).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub off_nadir: Option<f64>,
+
+    /// The incidence angle is the angle between the vertical (normal) to the
+    /// intercepting surface and the line of sight back to the satellite at
+    /// the scene center, in degrees (0-90).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub incidence_angle: Option<f64>,
+
+    /// Viewing azimuth angle, in degrees (0-360).
+    ///
+    /// The angle measured from the sub-satellite point (point on the ground
+    /// below the platform) between the scene center and true north, measured
+    /// clockwise from north.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub azimuth: Option<f64>,
+
+    /// Sun azimuth angle, in degrees (0-360).
+    ///
+    /// From the scene center point on the ground, this is the angle between
+    /// truth north and the sun, measured clockwise.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sun_azimuth: Option<f64>,
+
+    /// Sun elevation angle, in degrees (-90-90).
+    ///
+    /// The angle from the tangent of the scene center point to the sun.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sun_elevation: Option<f64>,
+}
+
+impl Extension for View {
+    const IDENTIFIER: &'static str = "https://stac-extensions.github.io/view/v1.0.0/schema.json";
+    const PREFIX: &'static str = "view";
+}
+
+#[cfg(test)]
+mod tests {
+    use super::View;
+    use crate::{Extensions, Item};
+
+    #[test]
+    fn item() {
+        let item: Item = stac::read("examples/extended-item.json").unwrap();
+        let view: View = item.extension().unwrap();
+        assert_eq!(view.sun_elevation, Some(54.9));
+        assert_eq!(view.off_nadir, Some(3.8));
+    }
+}
@@ -1,19 +1,19 @@
 //! A STAC API client.
 
-use crate::{Error, Result};
+use crate::{Error, Result, retry::RetryPolicy};
 use async_stream::try_stream;
 use futures::{Stream, StreamExt, pin_mut};
 use http::header::HeaderName;
 pub use reqwest::ClientBuilder;
-use reqwest::{IntoUrl, Method, StatusCode, header::HeaderMap};
+use reqwest::{IntoUrl, Method, header::HeaderMap};
 use serde::{Serialize, de::DeserializeOwned};
 use serde_json::{Map, Value};
 use stac::api::{
-    Collections, GetItems, Item, ItemCollection, Items, ItemsClient, Search, StreamItemsClient,
-    UrlBuilder,
+    CollectionSearch, Collections, GetCollectionSearch, GetItems, Item, ItemCollection, Items,
+    ItemsClient, Search, StreamItemsClient, UrlBuilder,
 };
 use stac::{Collection, Link, Links, SelfHref};
-use std::pin::Pin;
+use std::{fmt::Debug, pin::Pin, sync::Arc};
 use tokio::{
     runtime::{Builder, Runtime},
     sync::mpsc::{self, error::SendError},
@@ -68,12 +68,184 @@ pub async fn search_with_client_builder(
     Ok(item_collection)
 }
 
+/// Searches a STAC API, returning a stream of items.
+///
+/// Unlike [search], items aren't collected. Pages are only fetched when the
+/// stream is polled, by following each page's `next` link, so huge result
+/// sets can be written out without buffering them in memory.
+///
+/// # Examples
+///
+/// ```no_run
+/// use futures::StreamExt;
+/// use stac::api::Search;
+///
+/// let search = Search {
+///     collections: vec!["sentinel-2-l2a".to_string()],
+///     ..Default::default()
+/// };
+/// # tokio_test::block_on(async {
+/// let items: Vec<_> = stac_io::api::search_stream(
+///     "https://planetarycomputer.microsoft.com/api/stac/v1",
+///     search,
+/// )
+/// .take(10)
+/// .collect()
+/// .await;
+/// assert_eq!(items.len(), 10);
+/// # })
+/// ```
+pub fn search_stream(
+    href: &str,
+    search: Search,
+) -> impl Stream<Item = Result<Item>> + Send + 'static {
+    search_stream_with_client_builder(href, search, ClientBuilder::new())
+}
+
+/// Searches a STAC API with the provided client builder, returning a stream
+/// of items.
+///
+/// See [search_stream] for details.
+pub fn search_stream_with_client_builder(
+    href: &str,
+    search: Search,
+    builder: ClientBuilder,
+) -> impl Stream<Item = Result<Item>> + Send + 'static {
+    let client = Client::with_client_builder(builder, href);
+    try_stream! {
+        let client = client?;
+        let stream = search_stream_with_client(client, search);
+        pin_mut!(stream);
+        while let Some(item) = stream.next().await {
+            yield item?;
+        }
+    }
+}
+
+/// Searches a STAC API with the provided client, returning a stream of items.
+///
+/// Use this when you need to configure the [Client], e.g. with a
+/// [RetryPolicy]. See [search_stream] for details.
+///
+/// # Examples
+///
+/// ```no_run
+/// use futures::StreamExt;
+/// use stac::api::Search;
+/// use stac_io::{api::Client, retry::RetryPolicy};
+///
+/// let client = Client::new("https://planetarycomputer.microsoft.com/api/stac/v1")
+///     .unwrap()
+///     .retry_policy(RetryPolicy::new(3));
+/// let search = Search {
+///     collections: vec!["sentinel-2-l2a".to_string()],
+///     ..Default::default()
+/// };
+/// # tokio_test::block_on(async {
+/// let items: Vec<_> = stac_io::api::search_stream_with_client(client, search)
+///     .take(10)
+///     .collect()
+///     .await;
+/// assert_eq!(items.len(), 10);
+/// # })
+/// ```
+pub fn search_stream_with_client(
+    client: Client,
+    search: Search,
+) -> impl Stream<Item = Result<Item>> + Send + 'static {
+    try_stream! {
+        let page = ItemsClient::search(&client, search).await?;
+        let pages = stream_pages(client, page);
+        pin_mut!(pages);
+        while let Some(page) = pages.next().await {
+            for item in page?.items {
+                yield item;
+            }
+        }
+    }
+}
+
 /// A client for interacting with STAC APIs.
 #[derive(Clone, Debug)]
 pub struct Client {
     client: reqwest::Client,
     channel_buffer: usize,
     url_builder: UrlBuilder,
+    max_intersects_size: Option<usize>,
+    retry_policy: RetryPolicy,
+    options: ClientOptions,
+}
+
+/// Options for the requests that a [Client] sends, e.g. to authenticate with
+/// a private STAC API.
+///
+/// # Examples
+///
+/// ```
+/// use stac_io::api::{Client, ClientOptions};
+///
+/// let options = ClientOptions::new()
+///     .header("x-my-header", "value")
+///     .unwrap()
+///     .bearer_token("a-token");
+/// let client = Client::new("https://stac.eoapi.dev").unwrap().options(options);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ClientOptions {
+    /// Headers that are sent with every request.
+    pub headers: HeaderMap,
+
+    /// How requests are authenticated.
+    pub auth: Option<Auth>,
+
+    /// A hook that signs every request just before it's sent.
+    pub signer: Option<Arc<dyn RequestSigner>>,
+}
+
+/// How a [Client] authenticates its requests.
+#[derive(Clone, Debug)]
+pub enum Auth {
+    /// An `Authorization: Bearer {token}` header.
+    Bearer(String),
+
+    /// An API key, sent in a header.
+    ApiKey {
+        /// The header name, e.g. `x-api-key`.
+        header: HeaderName,
+
+        /// The key.
+        key: String,
+    },
+}
+
+/// Signs requests before they're sent, e.g. with [AWS Signature Version
+/// 4](https://docs.aws.amazon.com/IAM/latest/UserGuide/reference_sigv.html).
+///
+/// Requests are signed again before each retry, so signatures with a short
+/// lifetime stay valid.
+///
+/// # Examples
+///
+/// ```
+/// use stac_io::api::{ClientOptions, RequestSigner};
+///
+/// #[derive(Debug)]
+/// struct Signer;
+///
+/// impl RequestSigner for Signer {
+///     fn sign(&self, request: &mut reqwest::Request) -> stac_io::Result<()> {
+///         let _ = request
+///             .headers_mut()
+///             .insert("x-signature", "a-signature".parse()?);
+///         Ok(())
+///     }
+/// }
+///
+/// let options = ClientOptions::new().signer(Signer);
+/// ```
+pub trait RequestSigner: Debug + Send + Sync {
+    /// Signs a request, e.g. by adding headers to it.
+    fn sign(&self, request: &mut reqwest::Request) -> Result<()>;
 }
 
 /// A client for interacting with STAC APIs without async.
@@ -119,9 +291,66 @@ impl Client {
                 .build()?,
             channel_buffer: DEFAULT_CHANNEL_BUFFER,
             url_builder: UrlBuilder::new(url)?,
+            max_intersects_size: None,
+            retry_policy: RetryPolicy::default(),
+            options: ClientOptions::default(),
         })
     }
 
+    /// Sets the maximum size, in bytes, of a search's serialized intersects geometry.
+    ///
+    /// Searches with larger intersects geometries will return an error before
+    /// any request is sent to the server. By default there is no limit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_io::api::Client;
+    ///
+    /// let client = Client::new("https://stac.eoapi.dev")
+    ///     .unwrap()
+    ///     .max_intersects_size(100_000);
+    /// ```
+    pub fn max_intersects_size(mut self, max_intersects_size: impl Into<Option<usize>>) -> Client {
+        self.max_intersects_size = max_intersects_size.into();
+        self
+    }
+
+    /// Sets the policy for retrying failed requests.
+    ///
+    /// By default, requests aren't retried.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_io::{api::Client, retry::RetryPolicy};
+    ///
+    /// let client = Client::new("https://stac.eoapi.dev")
+    ///     .unwrap()
+    ///     .retry_policy(RetryPolicy::new(5));
+    /// ```
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Client {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Sets the options for this client's requests, e.g. headers and
+    /// authentication.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_io::api::{Client, ClientOptions};
+    ///
+    /// let client = Client::new("https://stac.eoapi.dev")
+    ///     .unwrap()
+    ///     .options(ClientOptions::new().bearer_token("a-token"));
+    /// ```
+    pub fn options(mut self, options: ClientOptions) -> Client {
+        self.options = options;
+        self
+    }
+
     /// Returns a single collection.
     ///
     /// # Examples
@@ -171,6 +400,48 @@ impl Client {
         ))
     }
 
+    /// Returns a stream of collections matching a [collection
+    /// search](https://github.com/stac-api-extensions/collection-search).
+    ///
+    /// The search parameters are sent as query parameters to the collections
+    /// endpoint, and `next` links are followed until there are no more pages.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use futures::StreamExt;
+    /// use stac::api::CollectionSearch;
+    /// use stac_io::api::Client;
+    ///
+    /// let client = Client::new("https://stac.eoapi.dev/").unwrap();
+    /// let search = CollectionSearch::default().q("sentinel").limit(10);
+    /// # tokio_test::block_on(async {
+    /// let collections: Vec<_> = client
+    ///     .collection_search(search)
+    ///     .await
+    ///     .unwrap()
+    ///     .map(|result| result.unwrap())
+    ///     .collect()
+    ///     .await;
+    /// # })
+    /// ```
+    pub async fn collection_search(
+        &self,
+        search: CollectionSearch,
+    ) -> Result<impl Stream<Item = Result<Collection>>> {
+        let url = self.url_builder.collections();
+        let search = GetCollectionSearch::from(search);
+        tracing::debug!("searching collections at {url}: {:?}", search);
+        let page = self
+            .request(Method::GET, url.clone(), &search, None)
+            .await?;
+        Ok(stream::<Collections>(
+            self.clone(),
+            page,
+            self.channel_buffer,
+        ))
+    }
+
     /// Returns a stream of items belonging to a collection, using the [items
     /// endpoint](https://github.com/radiantearth/stac-api-spec/tree/main/ogcapi-features#collection-items-collectionscollectioniditems).
     ///
@@ -271,10 +542,47 @@ impl Client {
             }
             _ => unimplemented!(),
         };
+        if !self.options.headers.is_empty() {
+            request = request.headers(self.options.headers.clone());
+        }
         if let Some(headers) = headers.into() {
             request = request.headers(headers);
         }
-        let response = request.send().await?.error_for_status()?;
+        match &self.options.auth {
+            Some(Auth::Bearer(token)) => request = request.bearer_auth(token),
+            Some(Auth::ApiKey { header, key }) => request = request.header(header, key),
+            None => {}
+        }
+        let request = request.build()?;
+        self.retry_policy
+            .run(|| {
+                let mut request = request
+                    .try_clone()
+                    .expect("request bodies are serialized json, which can be cloned");
+                let signed = match &self.options.signer {
+                    Some(signer) => signer.sign(&mut request),
+                    None => Ok(()),
+                };
+                async move {
+                    signed?;
+                    self.execute(request).await
+                }
+            })
+            .await
+    }
+
+    async fn execute<R>(&self, request: reqwest::Request) -> Result<R>
+    where
+        R: DeserializeOwned,
+    {
+        let response = self.client.execute(request).await?;
+        let status = response.status();
+        let href = response.url().to_string();
+        let response = response.error_for_status().map_err(|source| Error::Http {
+            href,
+            status,
+            source,
+        })?;
         response.json().await.map_err(Error::from)
     }
 
@@ -305,7 +613,10 @@ impl Client {
 impl ItemsClient for Client {
     type Error = Error;
 
-    async fn search(&self, search: Search) -> std::result::Result<ItemCollection, Error> {
+    async fn search(&self, mut search: Search) -> std::result::Result<ItemCollection, Error> {
+        if let Some(max_intersects_size) = self.max_intersects_size {
+            search = search.check_intersects_size(max_intersects_size)?;
+        }
         let url = self.url_builder.search().clone();
         tracing::debug!("searching {url}: {:?}", search);
         self.post(url, &search).await
@@ -334,6 +645,76 @@ impl StreamItemsClient for Client {
     }
 }
 
+impl ClientOptions {
+    /// Creates new, empty client options.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_io::api::ClientOptions;
+    ///
+    /// let options = ClientOptions::new();
+    /// ```
+    pub fn new() -> ClientOptions {
+        ClientOptions::default()
+    }
+
+    /// Adds a header that is sent with every request.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_io::api::ClientOptions;
+    ///
+    /// let options = ClientOptions::new().header("x-my-header", "value").unwrap();
+    /// ```
+    pub fn header(mut self, name: &str, value: &str) -> Result<ClientOptions> {
+        let _ = self
+            .headers
+            .insert(name.parse::<HeaderName>()?, value.parse()?);
+        Ok(self)
+    }
+
+    /// Authenticates requests with a bearer token.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_io::api::ClientOptions;
+    ///
+    /// let options = ClientOptions::new().bearer_token("a-token");
+    /// ```
+    pub fn bearer_token(mut self, token: impl ToString) -> ClientOptions {
+        self.auth = Some(Auth::Bearer(token.to_string()));
+        self
+    }
+
+    /// Authenticates requests with an API key, sent in the given header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_io::api::ClientOptions;
+    ///
+    /// let options = ClientOptions::new().api_key("x-api-key", "a-key").unwrap();
+    /// ```
+    pub fn api_key(mut self, header: &str, key: impl ToString) -> Result<ClientOptions> {
+        self.auth = Some(Auth::ApiKey {
+            header: header.parse()?,
+            key: key.to_string(),
+        });
+        Ok(self)
+    }
+
+    /// Signs every request with the given signer.
+    ///
+    /// See [RequestSigner] for an example.
+    pub fn signer(mut self, signer: impl RequestSigner + 'static) -> ClientOptions {
+        self.signer = Some(Arc::new(signer));
+        self
+    }
+}
+
 impl BlockingClient {
     /// Creates a new blocking client.
     ///
@@ -472,29 +853,29 @@ fn stream_pages<Page: Streamable>(
 }
 
 fn not_found_to_none<T>(result: Result<T>) -> Result<Option<T>> {
-    let mut result = result.map(Some);
-    if let Err(Error::Reqwest(ref err)) = result
-        && err
-            .status()
-            .map(|s| s == StatusCode::NOT_FOUND)
-            .unwrap_or_default()
-    {
-        result = Ok(None);
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(err) if err.is_not_found() => Ok(None),
+        Err(err) => Err(err),
     }
-    result
 }
 
 #[cfg(test)]
 mod tests {
     use crate::api::ClientBuilder;
 
-    use super::Client;
+    use super::{Client, ClientOptions};
+    use crate::retry::RetryPolicy;
     use futures::StreamExt;
     use http::HeaderMap;
     use mockito::{Matcher, Server};
     use serde_json::json;
     use stac::Links;
-    use stac::api::{Collections, ItemCollection, Items, ItemsClient, Search, StreamItemsClient};
+    use stac::api::{
+        CollectionSearch, Collections, ItemCollection, Items, ItemsClient, Search,
+        StreamItemsClient,
+    };
+    use std::time::Duration;
     use url::Url;
 
     #[tokio::test]
@@ -519,6 +900,38 @@ mod tests {
         collection.assert_async().await;
     }
 
+    #[tokio::test]
+    async fn retry_unavailable() {
+        let mut server = Server::new_async().await;
+        let unavailable = server
+            .mock("GET", "/collections/not-a-collection")
+            .with_status(503)
+            .expect(2)
+            .create_async()
+            .await;
+        let not_found = server
+            .mock("GET", "/collections/not-a-collection")
+            .with_body(include_str!("../mocks/not-a-collection.json"))
+            .with_header("content-type", "application/json")
+            .with_status(404)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = Client::new(&server.url())
+            .unwrap()
+            .retry_policy(RetryPolicy::new(3).initial_backoff(Duration::from_millis(1)));
+        assert!(
+            client
+                .collection("not-a-collection")
+                .await
+                .unwrap()
+                .is_none()
+        );
+        unavailable.assert_async().await;
+        not_found.assert_async().await;
+    }
+
     #[tokio::test]
     async fn search_with_paging() {
         let mut server = Server::new_async().await;
@@ -568,6 +981,56 @@ mod tests {
         assert!(items[0]["id"] != items[1]["id"]);
     }
 
+    #[tokio::test]
+    async fn search_stream_is_lazy() {
+        let mut server = Server::new_async().await;
+        let mut page_1_body: ItemCollection =
+            serde_json::from_str(include_str!("../mocks/search-page-1.json")).unwrap();
+        let mut next_link = page_1_body.link("next").unwrap().clone();
+        next_link.href = format!("{}/search", server.url());
+        page_1_body.set_link(next_link);
+        let page_1 = server
+            .mock("POST", "/search")
+            .match_body(Matcher::Json(json!({
+                "collections": ["sentinel-2-l2a"],
+                "limit": 1
+            })))
+            .with_body(serde_json::to_string(&page_1_body).unwrap())
+            .with_header("content-type", "application/geo+json")
+            .expect(2)
+            .create_async()
+            .await;
+        let page_2 = server
+            .mock("POST", "/search")
+            .match_body(Matcher::Json(json!({
+                "collections": ["sentinel-2-l2a"],
+                "limit": 1,
+                "token": "next:S2A_MSIL2A_20230216T150721_R082_T19PHS_20230217T082924"
+            })))
+            .with_body(include_str!("../mocks/search-page-2.json"))
+            .with_header("content-type", "application/geo+json")
+            .expect(1)
+            .create_async()
+            .await;
+
+        let mut search = Search {
+            collections: vec!["sentinel-2-l2a".to_string()],
+            ..Default::default()
+        };
+        search.items.limit = Some(1);
+        let stream = super::search_stream(&server.url(), search.clone());
+        let items: Vec<_> = stream.take(1).collect().await;
+        assert_eq!(items.len(), 1);
+        let items: Vec<_> = super::search_stream(&server.url(), search)
+            .map(|result| result.unwrap())
+            .take(2)
+            .collect()
+            .await;
+        page_1.assert_async().await;
+        page_2.assert_async().await;
+        assert_eq!(items.len(), 2);
+    }
+
     #[tokio::test]
     async fn items_with_paging() {
         let mut server = Server::new_async().await;
@@ -668,6 +1131,22 @@ mod tests {
         let _ = client.search(Default::default()).await.unwrap();
     }
 
+    #[tokio::test]
+    async fn http_error() {
+        let mut server = Server::new_async().await;
+        let _ = server
+            .mock("POST", "/search")
+            .with_status(401)
+            .create_async()
+            .await;
+        let client = Client::new(&server.url()).unwrap();
+        let error = client.search(Default::default()).await.unwrap_err();
+        assert!(error.is_auth());
+        assert!(!error.is_not_found());
+        assert_eq!(error.status().unwrap(), 401);
+        assert_eq!(error.href().unwrap(), format!("{}/search", server.url()));
+    }
+
     #[tokio::test]
     async fn custom_header() {
         let mut server = Server::new_async().await;
@@ -686,6 +1165,50 @@ mod tests {
         let _ = client.search(Default::default()).await.unwrap();
     }
 
+    #[tokio::test]
+    async fn client_options() {
+        #[derive(Debug)]
+        struct Signer;
+
+        impl super::RequestSigner for Signer {
+            fn sign(&self, request: &mut reqwest::Request) -> crate::Result<()> {
+                let _ = request
+                    .headers_mut()
+                    .insert("x-signature", "signed".parse().unwrap());
+                Ok(())
+            }
+        }
+
+        let mut server = Server::new_async().await;
+        let search = server
+            .mock("POST", "/search")
+            .with_body_from_file("mocks/items-page-1.json")
+            .match_header("x-my-header", "value")
+            .match_header("authorization", "Bearer a-token")
+            .match_header("x-signature", "signed")
+            .create_async()
+            .await;
+        let options = ClientOptions::new()
+            .header("x-my-header", "value")
+            .unwrap()
+            .bearer_token("a-token")
+            .signer(Signer);
+        let client = Client::new(&server.url()).unwrap().options(options);
+        let _ = client.search(Default::default()).await.unwrap();
+        search.assert_async().await;
+
+        let search = server
+            .mock("POST", "/search")
+            .with_body_from_file("mocks/items-page-1.json")
+            .match_header("x-api-key", "a-key")
+            .create_async()
+            .await;
+        let options = ClientOptions::new().api_key("x-api-key", "a-key").unwrap();
+        let client = Client::new(&server.url()).unwrap().options(options);
+        let _ = client.search(Default::default()).await.unwrap();
+        search.assert_async().await;
+    }
+
     #[tokio::test]
     async fn collections() {
         let mut server = Server::new_async().await;
@@ -722,4 +1245,67 @@ mod tests {
         assert_eq!(collections.len(), 20);
         assert!(collections[0].id != collections[1].id);
     }
+
+    #[tokio::test]
+    async fn collection_search() {
+        let mut server = Server::new_async().await;
+        let mut page_1_body: Collections =
+            serde_json::from_str(include_str!("../mocks/collections-page-1.json")).unwrap();
+        let mut next_link = page_1_body.link("next").unwrap().clone();
+        let url: Url = next_link.href.as_str().parse().unwrap();
+        let query = url.query().unwrap();
+        next_link.href = format!("{}/collections?{}", server.url(), query);
+        page_1_body.set_link(next_link);
+        let page_1 = server
+            .mock("GET", "/collections")
+            .match_query(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("limit".into(), "10".into()),
+                Matcher::UrlEncoded("bbox".into(), "-106,41,-105,42".into()),
+                Matcher::UrlEncoded("q".into(), "landsat".into()),
+            ]))
+            .with_body(serde_json::to_string(&page_1_body).unwrap())
+            .with_header("content-type", "application/json")
+            .create_async()
+            .await;
+        let page_2 = server
+            .mock("GET", "/collections?offset=10")
+            .with_body(include_str!("../mocks/collections-page-2.json"))
+            .with_header("content-type", "application/json")
+            .create_async()
+            .await;
+
+        let client = Client::new(&server.url()).unwrap();
+        let search = CollectionSearch::default()
+            .limit(10)
+            .bbox(stac::Bbox::new(-106., 41., -105., 42.))
+            .q("landsat");
+        let collections: Vec<_> = client
+            .collection_search(search)
+            .await
+            .unwrap()
+            .map(|result| result.unwrap())
+            .collect()
+            .await;
+        page_1.assert_async().await;
+        page_2.assert_async().await;
+        assert_eq!(collections.len(), 20);
+    }
+
+    #[tokio::test]
+    async fn search_intersects_too_large() {
+        let mut server = Server::new_async().await;
+        let search_mock = server
+            .mock("POST", "/search")
+            .expect(0)
+            .create_async()
+            .await;
+        let client = Client::new(&server.url()).unwrap().max_intersects_size(8);
+        let search = Search::default().intersects(geojson::Geometry::new_point(vec![0.0, 0.0]));
+        let error = client.search(search).await.unwrap_err();
+        assert!(matches!(
+            error,
+            crate::Error::Stac(stac::Error::IntersectsTooLarge(_, 8))
+        ));
+        search_mock.assert_async().await;
+    }
 }
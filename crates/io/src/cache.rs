@@ -0,0 +1,317 @@
+//! Caches for conditional gets.
+//!
+//! When a [StacStore](crate::StacStore) has a [Cache], each get sends the
+//! cached `ETag` and `Last-Modified` values with the request (as
+//! `If-None-Match` and `If-Modified-Since`). If the object hasn't changed, the
+//! store doesn't send it again and the cached bytes are used instead, which
+//! makes re-crawling a large catalog much cheaper.
+//!
+//! # Examples
+//!
+//! ```
+//! use stac_io::cache::FileSystemCache;
+//! use std::sync::Arc;
+//!
+//! let (store, _) = stac_io::parse_href("examples/catalog.json").unwrap();
+//! let store = store.cache(Arc::new(FileSystemCache::new("cache")));
+//! ```
+
+use crate::Result;
+use async_trait::async_trait;
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use object_store::GetOptions;
+use serde_json::{Map, Value};
+use std::{
+    fmt::Debug,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+static TEMPORARY_FILE_COUNT: AtomicU64 = AtomicU64::new(0);
+
+/// A cached object and the values used to check whether it has changed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CacheEntry {
+    /// The object's `ETag`.
+    pub e_tag: Option<String>,
+
+    /// When the object was last modified.
+    pub last_modified: Option<DateTime<Utc>>,
+
+    /// The object's bytes.
+    pub bytes: Bytes,
+}
+
+/// Stores objects fetched by a [StacStore](crate::StacStore).
+///
+/// Entries are keyed by the object's absolute href. Caches are called from
+/// async code, so implementations shouldn't block, e.g. on filesystem IO.
+#[async_trait]
+pub trait Cache: Debug + Send + Sync {
+    /// Returns the cached entry for a key, if there is one.
+    async fn get(&self, key: &str) -> Result<Option<CacheEntry>>;
+
+    /// Caches an entry, replacing any existing entry for the key.
+    async fn put(&self, key: &str, entry: &CacheEntry) -> Result<()>;
+}
+
+/// A [Cache] that keeps entries in a local directory.
+///
+/// Each entry is written as two files, named from a 64-bit
+/// [FNV-1a](https://en.wikipedia.org/wiki/Fowler%E2%80%93Noll%E2%80%93Vo_hash_function)
+/// hash of its key: the object's bytes, and a JSON file with its key, `ETag`,
+/// and last modified time. The hash is stable, so the cache can be reused
+/// across runs and Rust versions. Hash collisions are detected by checking
+/// the key, and are treated as cache misses.
+///
+/// Both files are written to a temporary path and renamed into place, with
+/// the JSON file last. It also records a hash of the bytes, so a get that
+/// races a put and sees the new bytes with the old `ETag` (or vice versa) is
+/// a cache miss instead of a stale entry.
+#[derive(Clone, Debug)]
+pub struct FileSystemCache {
+    directory: PathBuf,
+}
+
+impl CacheEntry {
+    /// Creates a new cache entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_io::cache::CacheEntry;
+    ///
+    /// let entry = CacheEntry::new("{}".into()).e_tag("\"abc\"");
+    /// ```
+    pub fn new(bytes: Bytes) -> CacheEntry {
+        CacheEntry {
+            e_tag: None,
+            last_modified: None,
+            bytes,
+        }
+    }
+
+    /// Sets this entry's `ETag`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_io::cache::CacheEntry;
+    ///
+    /// let entry = CacheEntry::new("{}".into()).e_tag("\"abc\"");
+    /// assert_eq!(entry.e_tag.unwrap(), "\"abc\"");
+    /// ```
+    pub fn e_tag(mut self, e_tag: impl ToString) -> CacheEntry {
+        self.e_tag = Some(e_tag.to_string());
+        self
+    }
+
+    /// Sets when this entry's object was last modified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chrono::Utc;
+    /// use stac_io::cache::CacheEntry;
+    ///
+    /// let entry = CacheEntry::new("{}".into()).last_modified(Utc::now());
+    /// ```
+    pub fn last_modified(mut self, last_modified: DateTime<Utc>) -> CacheEntry {
+        self.last_modified = Some(last_modified);
+        self
+    }
+
+    /// Returns the options for a conditional get of this entry's object.
+    pub(crate) fn get_options(&self) -> GetOptions {
+        GetOptions {
+            if_none_match: self.e_tag.clone(),
+            if_modified_since: self.last_modified,
+            ..Default::default()
+        }
+    }
+}
+
+impl FileSystemCache {
+    /// Creates a new cache in a directory.
+    ///
+    /// The directory is created when the first entry is cached.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_io::cache::FileSystemCache;
+    ///
+    /// let cache = FileSystemCache::new("cache");
+    /// ```
+    pub fn new(directory: impl AsRef<Path>) -> FileSystemCache {
+        FileSystemCache {
+            directory: directory.as_ref().to_path_buf(),
+        }
+    }
+
+    fn paths(&self, key: &str) -> (PathBuf, PathBuf) {
+        let name = format!("{:016x}", fnv1a(key.as_bytes()));
+        (
+            self.directory.join(&name),
+            self.directory.join(format!("{name}.json")),
+        )
+    }
+}
+
+#[async_trait]
+impl Cache for FileSystemCache {
+    async fn get(&self, key: &str) -> Result<Option<CacheEntry>> {
+        let (bytes_path, metadata_path) = self.paths(key);
+        let metadata = match tokio::fs::read(&metadata_path).await {
+            Ok(metadata) => metadata,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+        let metadata: Map<String, Value> = serde_json::from_slice(&metadata)?;
+        if metadata.get("key").and_then(Value::as_str) != Some(key) {
+            return Ok(None);
+        }
+        let bytes = match tokio::fs::read(&bytes_path).await {
+            Ok(bytes) => bytes,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+        if let Some(bytes_hash) = metadata.get("bytes_hash").and_then(Value::as_str)
+            && bytes_hash != format!("{:016x}", fnv1a(&bytes))
+        {
+            return Ok(None);
+        }
+        let last_modified = metadata
+            .get("last_modified")
+            .and_then(Value::as_str)
+            .and_then(|last_modified| DateTime::parse_from_rfc3339(last_modified).ok())
+            .map(|last_modified| last_modified.with_timezone(&Utc));
+        Ok(Some(CacheEntry {
+            e_tag: metadata
+                .get("e_tag")
+                .and_then(Value::as_str)
+                .map(String::from),
+            last_modified,
+            bytes: bytes.into(),
+        }))
+    }
+
+    async fn put(&self, key: &str, entry: &CacheEntry) -> Result<()> {
+        let (bytes_path, metadata_path) = self.paths(key);
+        let mut metadata = Map::new();
+        let _ = metadata.insert("key".to_string(), key.into());
+        let _ = metadata.insert(
+            "bytes_hash".to_string(),
+            format!("{:016x}", fnv1a(&entry.bytes)).into(),
+        );
+        if let Some(e_tag) = entry.e_tag.as_ref() {
+            let _ = metadata.insert("e_tag".to_string(), e_tag.as_str().into());
+        }
+        if let Some(last_modified) = entry.last_modified {
+            let _ = metadata.insert(
+                "last_modified".to_string(),
+                last_modified.to_rfc3339().into(),
+            );
+        }
+        tokio::fs::create_dir_all(&self.directory).await?;
+        write_atomic(&bytes_path, &entry.bytes).await?;
+        write_atomic(&metadata_path, &serde_json::to_vec(&metadata)?).await?;
+        Ok(())
+    }
+}
+
+/// Writes to a temporary file next to the path, then renames it into place,
+/// so readers never see a partially written file.
+async fn write_atomic(path: &Path, contents: &[u8]) -> Result<()> {
+    let count = TEMPORARY_FILE_COUNT.fetch_add(1, Ordering::Relaxed);
+    let mut temporary_path = path.as_os_str().to_owned();
+    temporary_path.push(format!(".{}.{count}.tmp", std::process::id()));
+    let temporary_path = PathBuf::from(temporary_path);
+    tokio::fs::write(&temporary_path, contents).await?;
+    if let Err(err) = tokio::fs::rename(&temporary_path, path).await {
+        let _ = tokio::fs::remove_file(&temporary_path).await;
+        return Err(err.into());
+    }
+    Ok(())
+}
+
+/// Hashes bytes with 64-bit FNV-1a.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(FNV_OFFSET_BASIS, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Cache, CacheEntry, FileSystemCache};
+    use chrono::{DateTime, Utc};
+
+    #[test]
+    fn fnv1a() {
+        assert_eq!(super::fnv1a(b""), 0xcbf29ce484222325);
+        assert_eq!(super::fnv1a(b"a"), 0xaf63dc4c8601ec8c);
+        assert_eq!(super::fnv1a(b"foobar"), 0x85944171f73967e8);
+    }
+
+    #[tokio::test]
+    async fn file_system_cache() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let cache = FileSystemCache::new(tempdir.path().join("cache"));
+        assert!(
+            cache
+                .get("http://stac.test/item.json")
+                .await
+                .unwrap()
+                .is_none()
+        );
+        let last_modified: DateTime<Utc> = "2024-01-01T00:00:00Z".parse().unwrap();
+        let entry = CacheEntry::new("{}".into())
+            .e_tag("\"abc\"")
+            .last_modified(last_modified);
+        cache
+            .put("http://stac.test/item.json", &entry)
+            .await
+            .unwrap();
+        assert_eq!(
+            cache
+                .get("http://stac.test/item.json")
+                .await
+                .unwrap()
+                .unwrap(),
+            entry
+        );
+        assert!(
+            cache
+                .get("http://stac.test/other.json")
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn file_system_cache_overwrite() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let cache = FileSystemCache::new(tempdir.path());
+        let key = "http://stac.test/item.json";
+        cache
+            .put(key, &CacheEntry::new("{}".into()).e_tag("\"abc\""))
+            .await
+            .unwrap();
+        let entry = CacheEntry::new("{\"id\": 1}".into()).e_tag("\"def\"");
+        cache.put(key, &entry).await.unwrap();
+        assert_eq!(cache.get(key).await.unwrap().unwrap(), entry);
+        assert_eq!(std::fs::read_dir(tempdir.path()).unwrap().count(), 2);
+
+        // Bytes that don't match the metadata, e.g. from a racing put.
+        let (bytes_path, _) = cache.paths(key);
+        std::fs::write(bytes_path, "{}").unwrap();
+        assert!(cache.get(key).await.unwrap().is_none());
+    }
+}
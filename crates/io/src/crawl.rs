@@ -0,0 +1,560 @@
+//! Crawl static catalogs by following their links.
+//!
+//! A [Crawler] starts from a catalog or collection, fetches its child and
+//! item links, and yields every collection and item it finds (and, if asked,
+//! every catalog):
+//!
+//! ```no_run
+//! use futures::TryStreamExt;
+//! use stac_io::crawl::Crawler;
+//!
+//! # tokio_test::block_on(async {
+//! let (store, path) = stac_io::parse_href("examples/catalog.json").unwrap();
+//! let catalog: stac::Value = store.get(path).await.unwrap();
+//! let values: Vec<_> = Crawler::new(store)
+//!     .crawl(catalog)
+//!     .try_collect()
+//!     .await
+//!     .unwrap();
+//! # })
+//! ```
+
+use crate::{Error, Result, StacStore};
+use async_stream::try_stream;
+use futures::Stream;
+use stac::{Assets, Link, Links, SelfHref, Value};
+use std::{
+    collections::{HashSet, VecDeque},
+    fmt::Debug,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::{task::JoinSet, time::Instant};
+
+/// The default number of links that are fetched at the same time.
+pub const DEFAULT_CONCURRENCY: usize = 16;
+
+type Filter = Arc<dyn Fn(&Value) -> bool + Send + Sync>;
+type Visit = Arc<dyn Fn(&Value, usize) + Send + Sync>;
+
+/// Which links a [Crawler] follows.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum LinkPolicy {
+    /// Follow `child` and `item` links.
+    #[default]
+    ChildrenAndItems,
+
+    /// Only follow `child` links, so only catalogs and collections are
+    /// fetched.
+    Children,
+
+    /// Follow links with any of these rel types.
+    Rels(Vec<String>),
+}
+
+/// Limits how often requests are started.
+///
+/// Clones share their limit, so a rate limiter can be used by many tasks at
+/// once.
+///
+/// # Examples
+///
+/// ```
+/// use stac_io::crawl::RateLimiter;
+///
+/// # tokio_test::block_on(async {
+/// let rate_limiter = RateLimiter::new(10.).unwrap();
+/// rate_limiter.wait().await;
+/// # })
+/// ```
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    interval: Duration,
+    next: Arc<Mutex<Option<Instant>>>,
+}
+
+/// Crawls catalogs and collections, yielding their collections and items.
+#[derive(Clone)]
+pub struct Crawler {
+    store: StacStore,
+    concurrency: usize,
+    rate_limiter: Option<RateLimiter>,
+    max_depth: Option<usize>,
+    link_policy: LinkPolicy,
+    dedupe: bool,
+    include_catalogs: bool,
+    filter: Option<Filter>,
+    visit: Option<Visit>,
+}
+
+impl Crawler {
+    /// Creates a new crawler that fetches links with the given store.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_io::crawl::Crawler;
+    ///
+    /// let (store, _) = stac_io::parse_href("examples/catalog.json").unwrap();
+    /// let crawler = Crawler::new(store);
+    /// ```
+    pub fn new(store: StacStore) -> Crawler {
+        Crawler {
+            store,
+            concurrency: DEFAULT_CONCURRENCY,
+            rate_limiter: None,
+            max_depth: None,
+            link_policy: LinkPolicy::default(),
+            dedupe: false,
+            include_catalogs: false,
+            filter: None,
+            visit: None,
+        }
+    }
+
+    /// Sets the maximum number of links that are fetched at the same time.
+    ///
+    /// Values less than one are treated as one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_io::crawl::Crawler;
+    ///
+    /// let (store, _) = stac_io::parse_href("examples/catalog.json").unwrap();
+    /// let crawler = Crawler::new(store).concurrency(4);
+    /// ```
+    pub fn concurrency(mut self, concurrency: usize) -> Crawler {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Sets the maximum number of links that are fetched per second.
+    ///
+    /// By default, the rate isn't limited. Returns an error if the rate isn't
+    /// a positive, finite number.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_io::crawl::Crawler;
+    ///
+    /// let (store, _) = stac_io::parse_href("examples/catalog.json").unwrap();
+    /// let crawler = Crawler::new(store).requests_per_second(5.).unwrap();
+    /// ```
+    pub fn requests_per_second(
+        mut self,
+        requests_per_second: impl Into<Option<f64>>,
+    ) -> Result<Crawler> {
+        self.rate_limiter = requests_per_second
+            .into()
+            .map(RateLimiter::new)
+            .transpose()?;
+        Ok(self)
+    }
+
+    /// Sets the maximum depth to crawl.
+    ///
+    /// The starting value has a depth of zero, its children have a depth of
+    /// one, and so on. Links aren't followed from values at the maximum depth.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_io::crawl::Crawler;
+    ///
+    /// let (store, _) = stac_io::parse_href("examples/catalog.json").unwrap();
+    /// let crawler = Crawler::new(store).max_depth(1);
+    /// ```
+    pub fn max_depth(mut self, max_depth: impl Into<Option<usize>>) -> Crawler {
+        self.max_depth = max_depth.into();
+        self
+    }
+
+    /// Sets which links are followed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_io::crawl::{Crawler, LinkPolicy};
+    ///
+    /// let (store, _) = stac_io::parse_href("examples/catalog.json").unwrap();
+    /// let crawler = Crawler::new(store).link_policy(LinkPolicy::Children);
+    /// ```
+    pub fn link_policy(mut self, link_policy: LinkPolicy) -> Crawler {
+        self.link_policy = link_policy;
+        self
+    }
+
+    /// Only follows links to hrefs that haven't been fetched yet.
+    ///
+    /// Use this when following links that can form cycles, e.g. `root` or
+    /// `parent` links. Hrefs are compared after they've been made absolute.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_io::crawl::{Crawler, LinkPolicy};
+    ///
+    /// let (store, _) = stac_io::parse_href("examples/catalog.json").unwrap();
+    /// let crawler = Crawler::new(store)
+    ///     .link_policy(LinkPolicy::Rels(vec!["child".to_string(), "root".to_string()]))
+    ///     .dedupe(true);
+    /// ```
+    pub fn dedupe(mut self, dedupe: bool) -> Crawler {
+        self.dedupe = dedupe;
+        self
+    }
+
+    /// Yields catalogs as well as collections and items.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_io::crawl::Crawler;
+    ///
+    /// let (store, _) = stac_io::parse_href("examples/catalog.json").unwrap();
+    /// let crawler = Crawler::new(store).include_catalogs(true);
+    /// ```
+    pub fn include_catalogs(mut self, include_catalogs: bool) -> Crawler {
+        self.include_catalogs = include_catalogs;
+        self
+    }
+
+    /// Sets a filter on crawled values.
+    ///
+    /// Values for which the filter returns `false` aren't yielded, and their
+    /// links aren't followed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_io::crawl::Crawler;
+    ///
+    /// let (store, _) = stac_io::parse_href("examples/catalog.json").unwrap();
+    /// let crawler = Crawler::new(store).filter(|value| {
+    ///     !matches!(value, stac::Value::Collection(c) if c.id == "skip-me")
+    /// });
+    /// ```
+    pub fn filter(mut self, filter: impl Fn(&Value) -> bool + Send + Sync + 'static) -> Crawler {
+        self.filter = Some(Arc::new(filter));
+        self
+    }
+
+    /// Sets a callback that is called with every fetched value and its depth.
+    ///
+    /// The callback sees every value, including catalogs (which aren't
+    /// yielded unless [included](Crawler::include_catalogs)) and values that are then rejected by the [filter](Crawler::filter).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_io::crawl::Crawler;
+    ///
+    /// let (store, _) = stac_io::parse_href("examples/catalog.json").unwrap();
+    /// let crawler = Crawler::new(store).visit(|value, depth| {
+    ///     println!("{depth}: {}", value.type_name());
+    /// });
+    /// ```
+    pub fn visit(mut self, visit: impl Fn(&Value, usize) + Send + Sync + 'static) -> Crawler {
+        self.visit = Some(Arc::new(visit));
+        self
+    }
+
+    /// Crawls a value, yielding the collections and items that are found.
+    ///
+    /// The starting value is yielded too, unless it's a catalog and catalogs
+    /// aren't [included](Crawler::include_catalogs).
+    ///
+    /// Link and asset hrefs of the yielded values are made absolute. Links
+    /// are fetched breadth-first, so values are yielded in no particular
+    /// order within a level.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures::TryStreamExt;
+    /// use stac_io::crawl::Crawler;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let (store, path) = stac_io::parse_href("examples/catalog.json").unwrap();
+    /// let catalog: stac::Value = store.get(path).await.unwrap();
+    /// let values: Vec<_> = Crawler::new(store)
+    ///     .crawl(catalog)
+    ///     .try_collect()
+    ///     .await
+    ///     .unwrap();
+    /// assert!(!values.is_empty());
+    /// # })
+    /// ```
+    pub fn crawl(&self, value: Value) -> impl Stream<Item = Result<Value>> + Send + 'static {
+        let crawler = self.clone();
+        try_stream! {
+            let mut values = VecDeque::from([(value, 0)]);
+            let mut seen = HashSet::new();
+            while let Some((mut value, depth)) = values.pop_front() {
+                value.make_links_absolute()?;
+                if crawler.dedupe
+                    && let Some(self_href) = value.self_href()
+                {
+                    let _ = seen.insert(self_href.to_string());
+                }
+                if let Some(visit) = crawler.visit.as_ref() {
+                    visit(&value, depth);
+                }
+                if let Some(filter) = crawler.filter.as_ref()
+                    && !filter(&value)
+                {
+                    continue;
+                }
+                match value {
+                    Value::Catalog(_) | Value::Collection(_) => {
+                        if let Value::Catalog(ref catalog) = value {
+                            tracing::info!("got catalog={}", catalog.id);
+                        }
+                        if let Value::Collection(ref mut collection) = value {
+                            tracing::info!("got collection={}", collection.id);
+                            if let Some(self_href) = collection.self_href() {
+                                let self_href = self_href.to_string();
+                                collection.make_assets_absolute(&self_href)?;
+                            }
+                        }
+                        if crawler.max_depth.is_none_or(|max_depth| depth < max_depth) {
+                            for child in crawler.fetch(&value, &mut seen).await? {
+                                values.push_back((child, depth + 1));
+                            }
+                        }
+                        if crawler.include_catalogs || matches!(value, Value::Collection(_)) {
+                            yield value;
+                        }
+                    }
+                    Value::Item(mut item) => {
+                        if let Some(self_href) = item.self_href() {
+                            let self_href = self_href.to_string();
+                            item.make_assets_absolute(&self_href)?;
+                        }
+                        yield Value::Item(item);
+                    }
+                    Value::ItemCollection(item_collection) => {
+                        for mut item in item_collection.items {
+                            if let Some(self_href) = item.self_href() {
+                                let self_href = self_href.to_string();
+                                item.make_assets_absolute(&self_href)?;
+                            }
+                            yield Value::Item(item);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    async fn fetch(&self, value: &Value, seen: &mut HashSet<String>) -> Result<Vec<Value>> {
+        let mut join_set: JoinSet<Result<Value>> = JoinSet::new();
+        let mut values = Vec::new();
+        for link in value
+            .links()
+            .iter()
+            .filter(|link| self.follows(link))
+            .filter(|link| !self.dedupe || seen.insert(link.href.clone()))
+            .cloned()
+        {
+            if join_set.len() >= self.concurrency
+                && let Some(result) = join_set.join_next().await
+            {
+                values.push(result??);
+            }
+            if let Some(rate_limiter) = self.rate_limiter.as_ref() {
+                rate_limiter.wait().await;
+            }
+            let store = self.store.clone();
+            let _ = join_set.spawn(async move { store.get(link.href.as_str()).await });
+        }
+        while let Some(result) = join_set.join_next().await {
+            values.push(result??);
+        }
+        Ok(values)
+    }
+
+    fn follows(&self, link: &Link) -> bool {
+        match &self.link_policy {
+            LinkPolicy::ChildrenAndItems => link.is_child() || link.is_item(),
+            LinkPolicy::Children => link.is_child(),
+            LinkPolicy::Rels(rels) => rels.iter().any(|rel| *rel == link.rel),
+        }
+    }
+}
+
+impl RateLimiter {
+    /// Creates a rate limiter that allows this many requests per second.
+    ///
+    /// Requests are spaced evenly, so there are no bursts. Returns an error if
+    /// the rate isn't a positive, finite number.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_io::crawl::RateLimiter;
+    ///
+    /// let rate_limiter = RateLimiter::new(0.5).unwrap();
+    /// assert!(RateLimiter::new(0.).is_err());
+    /// ```
+    pub fn new(requests_per_second: f64) -> Result<RateLimiter> {
+        if !requests_per_second.is_finite() || requests_per_second <= 0. {
+            return Err(Error::InvalidOption {
+                key: "requests_per_second".to_string(),
+                value: requests_per_second.to_string(),
+            });
+        }
+        Ok(RateLimiter {
+            interval: Duration::try_from_secs_f64(requests_per_second.recip()).unwrap_or_default(),
+            next: Arc::new(Mutex::new(None)),
+        })
+    }
+
+    /// Waits until the next request is allowed to start.
+    pub async fn wait(&self) {
+        let now = Instant::now();
+        let start = {
+            let mut next = self.next.lock().unwrap_or_else(|err| err.into_inner());
+            let start = next.map_or(now, |next| next.max(now));
+            *next = Some(start + self.interval);
+            start
+        };
+        tokio::time::sleep_until(start).await;
+    }
+}
+
+impl Debug for Crawler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Crawler")
+            .field("store", &self.store)
+            .field("concurrency", &self.concurrency)
+            .field("rate_limiter", &self.rate_limiter)
+            .field("max_depth", &self.max_depth)
+            .field("link_policy", &self.link_policy)
+            .field("dedupe", &self.dedupe)
+            .field("include_catalogs", &self.include_catalogs)
+            .field("filter", &self.filter.is_some())
+            .field("visit", &self.visit.is_some())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Crawler, LinkPolicy, RateLimiter};
+    use futures::TryStreamExt;
+    use stac::Value;
+    use std::{
+        sync::{
+            Arc,
+            atomic::{AtomicUsize, Ordering},
+        },
+        time::{Duration, Instant},
+    };
+
+    async fn crawl(crawler: impl FnOnce(Crawler) -> Crawler) -> Vec<Value> {
+        let (store, path) = crate::parse_href("examples/catalog.json").unwrap();
+        let catalog: Value = store.get(path).await.unwrap();
+        crawler(Crawler::new(store))
+            .crawl(catalog)
+            .try_collect()
+            .await
+            .unwrap()
+    }
+
+    fn count_items(values: &[Value]) -> usize {
+        values
+            .iter()
+            .filter(|value| matches!(value, Value::Item(_)))
+            .count()
+    }
+
+    #[tokio::test]
+    async fn crawl_all() {
+        let values = crawl(|crawler| crawler.concurrency(1)).await;
+        assert!(count_items(&values) > 0);
+        assert!(
+            values
+                .iter()
+                .any(|value| matches!(value, Value::Collection(_)))
+        );
+    }
+
+    #[tokio::test]
+    async fn children_only() {
+        let values = crawl(|crawler| crawler.link_policy(LinkPolicy::Children)).await;
+        assert!(!values.is_empty());
+        assert_eq!(count_items(&values), 0);
+    }
+
+    #[tokio::test]
+    async fn max_depth() {
+        let values = crawl(|crawler| crawler.max_depth(0)).await;
+        assert!(values.is_empty());
+    }
+
+    #[tokio::test]
+    async fn filter_and_visit() {
+        let visited = Arc::new(AtomicUsize::new(0));
+        let counter = visited.clone();
+        let values = crawl(move |crawler| {
+            crawler
+                .filter(|value| !matches!(value, Value::Collection(_)))
+                .visit(move |_, _| {
+                    let _ = counter.fetch_add(1, Ordering::SeqCst);
+                })
+        })
+        .await;
+        assert_eq!(values.len(), 1);
+        assert_eq!(count_items(&values), 1);
+        assert!(visited.load(Ordering::SeqCst) > 1);
+    }
+
+    #[tokio::test]
+    async fn dedupe() {
+        let rels = ["child", "item", "root", "parent"]
+            .map(String::from)
+            .to_vec();
+        let values = crawl(|crawler| {
+            crawler
+                .link_policy(LinkPolicy::Rels(rels))
+                .dedupe(true)
+                .include_catalogs(true)
+        })
+        .await;
+        let expected = crawl(|crawler| crawler.include_catalogs(true)).await;
+        assert_eq!(count_items(&values), count_items(&expected));
+    }
+
+    #[tokio::test]
+    async fn include_catalogs() {
+        let values = crawl(|crawler| crawler.include_catalogs(true)).await;
+        assert!(matches!(values[0], Value::Catalog(_)));
+        assert!(count_items(&values) > 0);
+    }
+
+    #[tokio::test]
+    async fn rate_limiter() {
+        let rate_limiter = RateLimiter::new(100.).unwrap();
+        let start = Instant::now();
+        for _ in 0..5 {
+            rate_limiter.clone().wait().await;
+        }
+        assert!(start.elapsed() >= Duration::from_millis(40));
+    }
+
+    #[tokio::test]
+    async fn requests_per_second() {
+        let values = crawl(|crawler| crawler.requests_per_second(1000.).unwrap()).await;
+        assert!(count_items(&values) > 0);
+    }
+
+    #[test]
+    fn invalid_requests_per_second() {
+        for requests_per_second in [0., -1., f64::NAN, f64::INFINITY] {
+            assert!(RateLimiter::new(requests_per_second).is_err());
+        }
+    }
+}
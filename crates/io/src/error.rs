@@ -15,6 +15,20 @@ pub enum Error {
         path: String,
     },
 
+    /// An HTTP request returned an error status code.
+    #[error("HTTP status {status} for {href}")]
+    Http {
+        /// The requested href.
+        href: String,
+
+        /// The response status code.
+        status: http::StatusCode,
+
+        /// The underlying [reqwest::Error].
+        #[source]
+        source: reqwest::Error,
+    },
+
     /// [http::header::InvalidHeaderName]
     #[error(transparent)]
     InvalidHeaderName(#[from] http::header::InvalidHeaderName),
@@ -31,15 +45,55 @@ pub enum Error {
     #[error(transparent)]
     Join(#[from] tokio::task::JoinError),
 
+    /// Returned when an id can't be used as a path segment, e.g. because it
+    /// contains a `/` or is `..`.
+    #[error("id can't be used as a path segment: {0}")]
+    InvalidPathId(String),
+
+    /// Returned when an option has an invalid value.
+    #[error("invalid value for option {key}: {value}")]
+    InvalidOption {
+        /// The option key.
+        key: String,
+
+        /// The option value.
+        value: String,
+    },
+
     /// [std::io::Error]
     #[error(transparent)]
     Io(#[from] std::io::Error),
 
+    /// Returned when a line of newline-delimited JSON could not be parsed.
+    #[error("could not parse line {line_number}: {source}")]
+    NdjsonLine {
+        /// The line number, starting at one.
+        line_number: usize,
+
+        /// The contents of the line.
+        line: String,
+
+        /// The underlying [serde_json::Error].
+        #[source]
+        source: serde_json::Error,
+    },
+
     #[cfg(feature = "store")]
     #[error(transparent)]
     /// [object_store::Error]
     ObjectStore(#[from] object_store::Error),
 
+    /// Returned when the contents of an href could not be parsed.
+    #[error("could not parse {href}: {source}")]
+    Parse {
+        /// The href.
+        href: String,
+
+        /// The underlying error.
+        #[source]
+        source: Box<Error>,
+    },
+
     #[cfg(feature = "geoparquet")]
     #[error(transparent)]
     /// [parquet::errors::ParquetError]
@@ -69,3 +123,114 @@ pub enum Error {
     #[error(transparent)]
     UrlParse(#[from] url::ParseError),
 }
+
+impl Error {
+    /// Returns the href that this error is about, if known.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let error = stac_io::read::<stac::Item>("not-a-file.json").unwrap_err();
+    /// assert!(error.href().unwrap().ends_with("not-a-file.json"));
+    /// ```
+    pub fn href(&self) -> Option<&str> {
+        match self {
+            Error::FromPath { path, .. } => Some(path),
+            Error::Http { href, .. } | Error::Parse { href, .. } => Some(href),
+            _ => None,
+        }
+    }
+
+    /// Returns the raw contents of the newline-delimited JSON line that caused
+    /// this error, if there was one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    ///
+    /// let mut items = stac_io::ndjson_item_reader(Cursor::new("not an item\n"));
+    /// let error = items.next().unwrap().unwrap_err();
+    /// assert_eq!(error.line(), Some("not an item"));
+    /// ```
+    pub fn line(&self) -> Option<&str> {
+        match self {
+            Error::NdjsonLine { line, .. } => Some(line),
+            _ => None,
+        }
+    }
+
+    /// Returns the HTTP status code for this error, if there was one.
+    pub fn status(&self) -> Option<http::StatusCode> {
+        match self {
+            Error::Http { status, .. } => Some(*status),
+            Error::Reqwest(err) => err.status(),
+            _ => None,
+        }
+    }
+
+    /// Returns true if this error was caused by something not existing, e.g. a
+    /// missing file, a missing object, or an HTTP 404.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let error = stac_io::read::<stac::Item>("not-a-file.json").unwrap_err();
+    /// assert!(error.is_not_found());
+    /// ```
+    pub fn is_not_found(&self) -> bool {
+        match self {
+            Error::FromPath { io, .. } | Error::Io(io) => io.kind() == std::io::ErrorKind::NotFound,
+            #[cfg(feature = "store")]
+            Error::ObjectStore(object_store::Error::NotFound { .. }) => true,
+            Error::Parse { source, .. } => source.is_not_found(),
+            _ => self.status() == Some(http::StatusCode::NOT_FOUND),
+        }
+    }
+
+    /// Returns true if this error was caused by missing or invalid
+    /// credentials, e.g. an HTTP 401 or 403.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let error = stac_io::read::<stac::Item>("not-a-file.json").unwrap_err();
+    /// assert!(!error.is_auth());
+    /// ```
+    pub fn is_auth(&self) -> bool {
+        match self {
+            Error::FromPath { io, .. } | Error::Io(io) => {
+                io.kind() == std::io::ErrorKind::PermissionDenied
+            }
+            #[cfg(feature = "store")]
+            Error::ObjectStore(
+                object_store::Error::PermissionDenied { .. }
+                | object_store::Error::Unauthenticated { .. },
+            ) => true,
+            _ => matches!(
+                self.status(),
+                Some(http::StatusCode::UNAUTHORIZED | http::StatusCode::FORBIDDEN)
+            ),
+        }
+    }
+
+    /// Returns true if this error was caused by malformed content.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let error = stac_io::Format::json()
+    ///     .from_bytes::<stac::Item>(b"not json".as_slice())
+    ///     .unwrap_err();
+    /// assert!(error.is_parse());
+    /// ```
+    pub fn is_parse(&self) -> bool {
+        matches!(
+            self,
+            Error::Parse { .. }
+                | Error::NdjsonLine { .. }
+                | Error::SerdeJson(_)
+                | Error::Stac(stac::Error::SerdeJson(_))
+        )
+    }
+}
@@ -33,6 +33,44 @@ impl Format {
         href.rsplit_once('.').and_then(|(_, ext)| ext.parse().ok())
     }
 
+    /// Infer the format from the content of some bytes.
+    ///
+    /// Useful for standard input and for hrefs without a file extension.
+    /// Parquet files are recognized by their magic bytes. JSON objects are
+    /// newline-delimited JSON if another object follows the first one, and
+    /// JSON otherwise. Returns `None` if the bytes don't look like any format.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_io::Format;
+    ///
+    /// assert_eq!(Format::infer_from_bytes(b"{\"type\": \"Feature\"}"), Some(Format::json()));
+    /// assert_eq!(Format::infer_from_bytes(b"{}\n{}\n"), Some(Format::ndjson()));
+    /// assert_eq!(Format::infer_from_bytes(b"not stac"), None);
+    /// ```
+    pub fn infer_from_bytes(bytes: &[u8]) -> Option<Format> {
+        if bytes.starts_with(b"PAR1") {
+            #[cfg(feature = "geoparquet")]
+            return Some(Format::geoparquet());
+            #[cfg(not(feature = "geoparquet"))]
+            return None;
+        }
+        let bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+        if bytes.trim_ascii_start().first() != Some(&b'{') {
+            return None;
+        }
+        let mut values =
+            serde_json::Deserializer::from_slice(bytes).into_iter::<serde::de::IgnoredAny>();
+        if values.next().is_some_and(|result| result.is_ok())
+            && bytes[values.byte_offset()..].trim_ascii_start().first() == Some(&b'{')
+        {
+            Some(Format::NdJson)
+        } else {
+            Some(Format::json())
+        }
+    }
+
     /// Returns this format's file extension.
     ///
     /// # Examples
@@ -74,11 +112,26 @@ impl Format {
         let mut href = href.to_string();
         let mut value: T = match href.as_str().into() {
             RealizedHref::Url(url) => {
-                let bytes = reqwest::blocking::get(url)?.bytes()?;
-                self.from_bytes(bytes)?
+                let response = reqwest::blocking::get(url)?;
+                let status = response.status();
+                let bytes = response
+                    .error_for_status()
+                    .map_err(|source| Error::Http {
+                        href: href.clone(),
+                        status,
+                        source,
+                    })?
+                    .bytes()?;
+                self.from_bytes(bytes).map_err(|err| Error::Parse {
+                    href: href.clone(),
+                    source: Box::new(err),
+                })?
             }
             RealizedHref::PathBuf(path) => {
-                let path = path.canonicalize()?;
+                let path = path.canonicalize().map_err(|io| Error::FromPath {
+                    io,
+                    path: href.clone(),
+                })?;
                 let value = self.from_path(&path)?;
                 href = path.as_path().to_string_lossy().into_owned();
                 value
@@ -274,6 +327,21 @@ fn infer_geoparquet_format(s: &str) -> Result<Format> {
 mod tests {
     use super::Format;
 
+    #[test]
+    fn infer_from_bytes() {
+        let item = std::fs::read("examples/simple-item.json").unwrap();
+        assert_eq!(Format::infer_from_bytes(&item).unwrap(), Format::json());
+        let mut ndjson = Vec::new();
+        for _ in 0..2 {
+            ndjson.extend(serde_json::to_vec(&stac::Item::new("an-id")).unwrap());
+            ndjson.push(b'\n');
+        }
+        assert_eq!(Format::infer_from_bytes(&ndjson).unwrap(), Format::NdJson);
+        assert_eq!(Format::infer_from_bytes(b"  \n{}").unwrap(), Format::json());
+        assert!(Format::infer_from_bytes(b"").is_none());
+        assert!(Format::infer_from_bytes(b"[]").is_none());
+    }
+
     #[test]
     #[cfg(not(feature = "geoparquet"))]
     fn parse_geoparquet() {
@@ -296,6 +364,15 @@ mod tests {
             assert_eq!(format, expected);
         }
 
+        #[test]
+        fn infer_from_bytes() {
+            let bytes = std::fs::read("data/extended-item.parquet").unwrap();
+            assert_eq!(
+                Format::infer_from_bytes(&bytes).unwrap(),
+                Format::Geoparquet(WriterOptions::default())
+            );
+        }
+
         #[test]
         fn infer_from_href() {
             let format = Format::infer_from_href("out.parquet").unwrap();
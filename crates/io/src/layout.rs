@@ -0,0 +1,444 @@
+//! Write catalog trees with the best-practices layout.
+//!
+//! A [Renderer] writes a [Node] (a catalog or collection, with its children
+//! and items) with the hrefs recommended by the [STAC best
+//! practices](https://github.com/radiantearth/stac-spec/blob/master/best-practices.md#catalog-layout):
+//!
+//! ```text
+//! {prefix}/catalog.json
+//! {prefix}/{child_id}/collection.json
+//! {prefix}/{child_id}/{item_id}/{item_id}.json
+//! ```
+//!
+//! The `self`, `root`, `parent`, `child`, `item`, and `collection` links are
+//! rewritten to match the tree, and other links are left as they are. Since
+//! hrefs come from ids, siblings with the same id overwrite each other:
+//!
+//! ```no_run
+//! use stac::{Catalog, Collection, Item};
+//! use stac_io::layout::{Node, Renderer};
+//!
+//! let mut collection = Node::from(Collection::new("a-collection", "a description"));
+//! collection.items.push(Item::new("an-item"));
+//! let mut catalog = Node::from(Catalog::new("a-catalog", "a description"));
+//! catalog.children.push(collection);
+//!
+//! # tokio_test::block_on(async {
+//! let (store, path) = stac_io::parse_href("out").unwrap();
+//! let _ = Renderer::new(store, path).render(catalog).await.unwrap();
+//! # })
+//! ```
+
+use crate::{Result, StacStore};
+use stac::{Catalog, Collection, Item, Link, Links, SelfHref, Value};
+use std::{future::Future, pin::Pin};
+
+const CATALOG_FILE_NAME: &str = "catalog.json";
+const COLLECTION_FILE_NAME: &str = "collection.json";
+
+/// A catalog or collection in a [Node] tree.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Container {
+    /// A catalog.
+    Catalog(Catalog),
+
+    /// A collection.
+    Collection(Collection),
+}
+
+/// A catalog or collection, with its children and items.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Node {
+    /// The catalog or collection.
+    pub value: Container,
+
+    /// The child catalogs and collections.
+    pub children: Vec<Node>,
+
+    /// The items.
+    pub items: Vec<Item>,
+}
+
+/// How a [Renderer] writes link hrefs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LinkStyle {
+    /// Hrefs are relative, and there are no `self` links, so the output
+    /// can be moved without rewriting anything.
+    #[default]
+    Relative,
+
+    /// Hrefs are absolute, and every value has a `self` link.
+    Absolute,
+}
+
+/// Writes [Node] trees to a store.
+#[derive(Debug, Clone)]
+pub struct Renderer {
+    store: StacStore,
+    prefix: String,
+    link_style: LinkStyle,
+}
+
+impl Node {
+    /// Reads a node tree by following the `child` and `item` links of a
+    /// catalog or collection.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_io::layout::Node;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let (store, path) = stac_io::parse_href("examples/catalog.json").unwrap();
+    /// let node = Node::get(&store, path).await.unwrap();
+    /// assert!(!node.children.is_empty());
+    /// # })
+    /// ```
+    pub async fn get(store: &StacStore, href: impl ToString) -> Result<Node> {
+        get_node(store, href.to_string()).await
+    }
+
+    fn lay_out(
+        &mut self,
+        href: String,
+        root_href: &str,
+        parent_href: Option<&str>,
+        link_style: LinkStyle,
+    ) {
+        let dir = href
+            .rsplit_once('/')
+            .map(|(dir, _)| format!("{dir}/"))
+            .unwrap_or_default();
+        let link_href = |target: &str| match link_style {
+            LinkStyle::Relative => stac::href::make_relative(target, &href),
+            LinkStyle::Absolute => target.to_string(),
+        };
+        let child_hrefs: Vec<String> = self
+            .children
+            .iter()
+            .map(|child| format!("{dir}{}/{}", child.value.id(), child.value.file_name()))
+            .collect();
+        let item_hrefs: Vec<String> = self
+            .items
+            .iter()
+            .map(|item| format!("{dir}{}/{}.json", item.id, item.id))
+            .collect();
+
+        let collection_id = match &self.value {
+            Container::Catalog(_) => None,
+            Container::Collection(collection) => Some(collection.id.clone()),
+        };
+        let links = self.value.links_mut();
+        links.retain(|link| !is_tree_link(link));
+        if link_style == LinkStyle::Absolute {
+            links.push(Link::self_(href.as_str()).json());
+        }
+        links.push(Link::root(link_href(root_href)).json());
+        if let Some(parent_href) = parent_href {
+            links.push(Link::parent(link_href(parent_href)).json());
+        }
+        for child_href in &child_hrefs {
+            links.push(Link::child(link_href(child_href)).json());
+        }
+        for item_href in &item_hrefs {
+            links.push(Link::item(link_href(item_href)).geojson());
+        }
+        self.value.set_self_href(&href);
+
+        for (child, child_href) in self.children.iter_mut().zip(child_hrefs) {
+            child.lay_out(child_href, root_href, Some(&href), link_style);
+        }
+        for (item, item_href) in self.items.iter_mut().zip(item_hrefs) {
+            let link_href = |target: &str| match link_style {
+                LinkStyle::Relative => stac::href::make_relative(target, &item_href),
+                LinkStyle::Absolute => target.to_string(),
+            };
+            item.links.retain(|link| !is_tree_link(link));
+            if link_style == LinkStyle::Absolute {
+                item.links.push(Link::self_(item_href.as_str()).geojson());
+            }
+            item.links.push(Link::root(link_href(root_href)).json());
+            item.links.push(Link::parent(link_href(&href)).json());
+            if let Some(collection_id) = collection_id.as_ref() {
+                item.links.push(Link::collection(link_href(&href)).json());
+                item.collection = Some(collection_id.clone());
+            }
+            item.set_self_href(item_href);
+        }
+    }
+}
+
+impl Container {
+    fn id(&self) -> &str {
+        match self {
+            Container::Catalog(catalog) => &catalog.id,
+            Container::Collection(collection) => &collection.id,
+        }
+    }
+
+    fn file_name(&self) -> &'static str {
+        match self {
+            Container::Catalog(_) => CATALOG_FILE_NAME,
+            Container::Collection(_) => COLLECTION_FILE_NAME,
+        }
+    }
+}
+
+impl Renderer {
+    /// Creates a new renderer that writes below `prefix` in the store.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_io::layout::Renderer;
+    ///
+    /// let (store, path) = stac_io::parse_href("out").unwrap();
+    /// let renderer = Renderer::new(store, path);
+    /// ```
+    pub fn new(store: StacStore, prefix: impl ToString) -> Renderer {
+        Renderer {
+            store,
+            prefix: prefix.to_string().trim_end_matches('/').to_string(),
+            link_style: LinkStyle::default(),
+        }
+    }
+
+    /// Sets how link hrefs are written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_io::layout::{LinkStyle, Renderer};
+    ///
+    /// let (store, path) = stac_io::parse_href("out").unwrap();
+    /// let renderer = Renderer::new(store, path).link_style(LinkStyle::Absolute);
+    /// ```
+    pub fn link_style(mut self, link_style: LinkStyle) -> Renderer {
+        self.link_style = link_style;
+        self
+    }
+
+    /// Writes a node tree, returning it with its links rewritten and its
+    /// self hrefs set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Catalog, Item, Links};
+    /// use stac_io::layout::{Node, Renderer};
+    ///
+    /// let dir = tempfile::tempdir().unwrap();
+    /// let (store, path) = stac_io::parse_href(dir.path().to_str().unwrap()).unwrap();
+    /// let mut node = Node::from(Catalog::new("a-catalog", "a description"));
+    /// node.items.push(Item::new("an-item"));
+    /// # tokio_test::block_on(async {
+    /// let node = Renderer::new(store, path).render(node).await.unwrap();
+    /// assert_eq!(node.items[0].link("parent").unwrap().href, "../catalog.json");
+    /// # })
+    /// ```
+    pub async fn render(&self, mut node: Node) -> Result<Node> {
+        let path = if self.prefix.is_empty() {
+            node.value.file_name().to_string()
+        } else {
+            format!("{}/{}", self.prefix, node.value.file_name())
+        };
+        let href = self.store.absolute_href(&path)?;
+        node.lay_out(href.clone(), &href, None, self.link_style);
+
+        let mut nodes = vec![&node];
+        while let Some(node) = nodes.pop() {
+            let href = node
+                .value
+                .self_href()
+                .expect("self hrefs should be set by lay_out");
+            let _ = match &node.value {
+                Container::Catalog(catalog) => self.store.put(href, catalog.clone()).await?,
+                Container::Collection(collection) => {
+                    self.store.put(href, collection.clone()).await?
+                }
+            };
+            for item in &node.items {
+                let href = item
+                    .self_href()
+                    .expect("self hrefs should be set by lay_out");
+                let _ = self.store.put(href, item.clone()).await?;
+            }
+            nodes.extend(&node.children);
+        }
+        Ok(node)
+    }
+}
+
+// Recursive async functions need a boxed future, and spelling out the type
+// keeps the future `Send`.
+fn get_node(
+    store: &StacStore,
+    href: String,
+) -> Pin<Box<dyn Future<Output = Result<Node>> + Send + '_>> {
+    Box::pin(async move {
+        let value: Value = store.get(href.as_str()).await?;
+        let value = match value {
+            Value::Catalog(catalog) => Container::Catalog(catalog),
+            Value::Collection(collection) => Container::Collection(collection),
+            value => {
+                return Err(stac::Error::IncorrectType {
+                    actual: value.type_name().to_string(),
+                    expected: "Catalog or Collection".to_string(),
+                }
+                .into());
+            }
+        };
+        let mut node = Node::from(value);
+        let self_href = node.value.self_href().map(String::from).unwrap_or(href);
+        for link in node.value.links().to_vec() {
+            let href = stac::href::make_absolute(&link.href, &self_href)?.into_owned();
+            if link.is_child() {
+                node.children.push(get_node(store, href).await?);
+            } else if link.is_item() {
+                node.items.push(store.get(href).await?);
+            }
+        }
+        Ok(node)
+    })
+}
+
+fn is_tree_link(link: &Link) -> bool {
+    link.is_self()
+        || link.is_root()
+        || link.is_parent()
+        || link.is_child()
+        || link.is_item()
+        || link.is_collection()
+}
+
+impl Links for Container {
+    fn links(&self) -> &[Link] {
+        match self {
+            Container::Catalog(catalog) => catalog.links(),
+            Container::Collection(collection) => collection.links(),
+        }
+    }
+
+    fn links_mut(&mut self) -> &mut Vec<Link> {
+        match self {
+            Container::Catalog(catalog) => catalog.links_mut(),
+            Container::Collection(collection) => collection.links_mut(),
+        }
+    }
+}
+
+impl SelfHref for Container {
+    fn self_href(&self) -> Option<&str> {
+        match self {
+            Container::Catalog(catalog) => catalog.self_href(),
+            Container::Collection(collection) => collection.self_href(),
+        }
+    }
+
+    fn self_href_mut(&mut self) -> &mut Option<String> {
+        match self {
+            Container::Catalog(catalog) => catalog.self_href_mut(),
+            Container::Collection(collection) => collection.self_href_mut(),
+        }
+    }
+}
+
+impl From<Catalog> for Container {
+    fn from(catalog: Catalog) -> Container {
+        Container::Catalog(catalog)
+    }
+}
+
+impl From<Collection> for Container {
+    fn from(collection: Collection) -> Container {
+        Container::Collection(collection)
+    }
+}
+
+impl From<Container> for Value {
+    fn from(container: Container) -> Value {
+        match container {
+            Container::Catalog(catalog) => Value::Catalog(catalog),
+            Container::Collection(collection) => Value::Collection(collection),
+        }
+    }
+}
+
+impl<T> From<T> for Node
+where
+    T: Into<Container>,
+{
+    fn from(value: T) -> Node {
+        Node {
+            value: value.into(),
+            children: Vec::new(),
+            items: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LinkStyle, Node, Renderer};
+    use stac::{Catalog, Collection, Item, Link, Links};
+
+    fn tree() -> Node {
+        let mut collection = Node::from(Collection::new("a-collection", "a description"));
+        collection.items.push(Item::new("an-item"));
+        let mut catalog = Catalog::new("a-catalog", "a description");
+        catalog
+            .links
+            .push(Link::new("https://stac.test/license", "license"));
+        catalog.links.push(Link::child("./stale/catalog.json"));
+        let mut catalog = Node::from(catalog);
+        catalog.children.push(collection);
+        catalog
+    }
+
+    #[tokio::test]
+    async fn render_relative() {
+        let dir = tempfile::tempdir().unwrap();
+        let (store, path) = crate::parse_href(dir.path().to_str().unwrap()).unwrap();
+        let _ = Renderer::new(store, path).render(tree()).await.unwrap();
+
+        let root = dir.path().to_str().unwrap();
+        let catalog: Catalog = crate::read(format!("{root}/catalog.json")).unwrap();
+        assert!(catalog.link("self").is_none());
+        assert!(catalog.link("license").is_some());
+        let children: Vec<_> = catalog.iter_child_links().collect();
+        assert_eq!(children.len(), 1);
+        assert_eq!(children[0].href, "./a-collection/collection.json");
+        let collection: Collection =
+            crate::read(format!("{root}/a-collection/collection.json")).unwrap();
+        assert_eq!(collection.link("root").unwrap().href, "../catalog.json");
+        assert_eq!(collection.link("parent").unwrap().href, "../catalog.json");
+        assert_eq!(
+            collection.iter_item_links().next().unwrap().href,
+            "./an-item/an-item.json"
+        );
+        let item: Item = crate::read(format!("{root}/a-collection/an-item/an-item.json")).unwrap();
+        assert_eq!(item.collection.as_deref(), Some("a-collection"));
+        assert_eq!(item.link("root").unwrap().href, "../../catalog.json");
+        assert_eq!(item.link("collection").unwrap().href, "../collection.json");
+    }
+
+    #[tokio::test]
+    async fn render_absolute() {
+        let dir = tempfile::tempdir().unwrap();
+        let (store, path) = crate::parse_href(dir.path().to_str().unwrap()).unwrap();
+        let node = Renderer::new(store, path)
+            .link_style(LinkStyle::Absolute)
+            .render(tree())
+            .await
+            .unwrap();
+        let item = &node.children[0].items[0];
+        assert!(item.link("self").unwrap().is_absolute());
+        assert!(item.link("root").unwrap().href.ends_with("/catalog.json"));
+
+        let root = dir.path().to_str().unwrap();
+        let (store, path) = crate::parse_href(format!("{root}/catalog.json")).unwrap();
+        let read = Node::get(&store, path).await.unwrap();
+        assert_eq!(read.children.len(), 1);
+        assert_eq!(read.children[0].items.len(), 1);
+    }
+}
@@ -1,12 +1,21 @@
 pub mod api;
+#[cfg(feature = "store")]
+pub mod cache;
+#[cfg(feature = "store")]
+pub mod crawl;
 mod error;
 mod format;
 #[cfg(feature = "geoparquet")]
 mod geoparquet;
 mod json;
+#[cfg(feature = "store")]
+pub mod layout;
 mod ndjson;
+#[cfg(all(feature = "store", feature = "geoparquet"))]
+pub mod publish;
 mod read;
 mod realized_href;
+pub mod retry;
 #[cfg(feature = "store")]
 pub mod store;
 mod write;
@@ -14,12 +23,12 @@ mod write;
 #[cfg(feature = "geoparquet")]
 pub use geoparquet::{FromGeoparquetPath, IntoGeoparquetPath};
 #[cfg(feature = "store")]
-pub use store::{StacStore, parse_href, parse_href_opts};
+pub use store::{StacStore, get, get_opts, parse_href, parse_href_opts, put, put_opts};
 pub use {
     error::Error,
     format::Format,
     json::{FromJsonPath, ToJsonPath},
-    ndjson::{FromNdjsonPath, ToNdjsonPath, ndjson_item_reader},
+    ndjson::{AsyncNdjsonWriter, FromNdjsonPath, NdjsonWriter, ToNdjsonPath, ndjson_item_reader},
     read::read,
     realized_href::RealizedHref,
     write::write,
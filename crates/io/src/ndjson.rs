@@ -1,10 +1,14 @@
 use crate::{Error, FromJsonPath, Result};
+use flate2::{Compression, write::GzEncoder};
+use futures::{Stream, StreamExt, TryStream, TryStreamExt, pin_mut};
+use serde::Serialize;
 use stac::{Catalog, Collection, FromNdjson, Item, ItemCollection, SelfHref, ToNdjson, Value};
 use std::{
-    fs::File,
-    io::{BufRead, BufReader, BufWriter},
+    fs::{File, OpenOptions},
+    io::{BufRead, BufReader, BufWriter, Write},
     path::Path,
 };
+use tokio::io::{AsyncWrite, AsyncWriteExt};
 
 /// Create a STAC object from newline-delimited JSON.
 pub trait FromNdjsonPath: FromNdjson + FromJsonPath + SelfHref {
@@ -110,6 +114,10 @@ impl ToNdjsonPath for Value {
 
 /// Returns an iterator that yields one [Item] per line from a reader.
 ///
+/// Empty lines are skipped. A line that can't be parsed yields an
+/// [Error::NdjsonLine] that includes the line's contents, and iteration can
+/// continue past it.
+///
 /// # Examples
 ///
 /// ```
@@ -121,11 +129,20 @@ impl ToNdjsonPath for Value {
 /// assert_eq!(items.len(), 2);
 /// ```
 pub fn ndjson_item_reader(reader: impl BufRead) -> impl Iterator<Item = Result<Item>> {
-    reader.lines().filter_map(|line| match line {
-        Ok(line) if line.is_empty() => None,
-        Ok(line) => Some(serde_json::from_str(&line).map_err(Error::from)),
-        Err(err) => Some(Err(Error::from(err))),
-    })
+    reader
+        .lines()
+        .enumerate()
+        .filter_map(|(i, line)| match line {
+            Ok(line) if line.is_empty() => None,
+            Ok(line) => Some(
+                serde_json::from_str(&line).map_err(|source| Error::NdjsonLine {
+                    line_number: i + 1,
+                    line,
+                    source,
+                }),
+            ),
+            Err(err) => Some(Err(Error::from(err))),
+        })
 }
 
 impl ToNdjsonPath for serde_json::Value {
@@ -136,6 +153,424 @@ impl ToNdjsonPath for serde_json::Value {
     }
 }
 
+/// Writes values as newline-delimited JSON, one line at a time.
+///
+/// Unlike [ToNdjsonPath], which serializes a whole [ItemCollection] at once,
+/// values are written as they're produced, so a pipeline never has to hold
+/// every item in memory. Output can optionally be gzip-compressed.
+///
+/// Call [NdjsonWriter::finish] when you're done to flush the output and, for
+/// gzip output, to write the gzip trailer.
+///
+/// # Examples
+///
+/// ```
+/// use stac::Item;
+/// use stac_io::NdjsonWriter;
+///
+/// let mut writer = NdjsonWriter::new(Vec::new());
+/// writer.write(&Item::new("a")).unwrap();
+/// writer.write_iter([Item::new("b"), Item::new("c")]).unwrap();
+/// let bytes = writer.finish().unwrap();
+/// assert_eq!(bytes.iter().filter(|&&b| b == b'\n').count(), 3);
+/// ```
+#[derive(Debug)]
+pub struct NdjsonWriter<W: Write> {
+    encoder: Encoder<W>,
+}
+
+#[derive(Debug)]
+enum Encoder<W: Write> {
+    Plain(W),
+    Gzip(GzEncoder<W>),
+}
+
+impl<W: Write> NdjsonWriter<W> {
+    /// Creates a writer that writes uncompressed newline-delimited JSON.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_io::NdjsonWriter;
+    ///
+    /// let writer = NdjsonWriter::new(Vec::new());
+    /// ```
+    pub fn new(writer: W) -> NdjsonWriter<W> {
+        NdjsonWriter {
+            encoder: Encoder::Plain(writer),
+        }
+    }
+
+    /// Creates a writer that gzip-compresses its output.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_io::NdjsonWriter;
+    ///
+    /// let writer = NdjsonWriter::gzip(Vec::new());
+    /// ```
+    pub fn gzip(writer: W) -> NdjsonWriter<W> {
+        NdjsonWriter {
+            encoder: Encoder::Gzip(GzEncoder::new(writer, Compression::default())),
+        }
+    }
+
+    /// Writes a single value as one line.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::Item;
+    /// use stac_io::NdjsonWriter;
+    ///
+    /// let mut writer = NdjsonWriter::new(Vec::new());
+    /// writer.write(&Item::new("an-id")).unwrap();
+    /// ```
+    pub fn write(&mut self, value: &impl Serialize) -> Result<()> {
+        let writer: &mut dyn Write = match &mut self.encoder {
+            Encoder::Plain(writer) => writer,
+            Encoder::Gzip(encoder) => encoder,
+        };
+        serde_json::to_writer(&mut *writer, value)?;
+        writer.write_all(b"\n")?;
+        Ok(())
+    }
+
+    /// Writes every value from an iterator, returning the number of values written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::Item;
+    /// use stac_io::NdjsonWriter;
+    ///
+    /// let mut writer = NdjsonWriter::new(Vec::new());
+    /// let count = writer.write_iter([Item::new("a"), Item::new("b")]).unwrap();
+    /// assert_eq!(count, 2);
+    /// ```
+    pub fn write_iter<T: Serialize>(
+        &mut self,
+        values: impl IntoIterator<Item = T>,
+    ) -> Result<usize> {
+        let mut count = 0;
+        for value in values {
+            self.write(&value)?;
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    /// Flushes all output and returns the underlying writer.
+    ///
+    /// For gzip output this also writes the gzip trailer, so the output
+    /// isn't a complete gzip stream until this is called.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_io::NdjsonWriter;
+    ///
+    /// let writer = NdjsonWriter::gzip(Vec::new());
+    /// let bytes = writer.finish().unwrap();
+    /// ```
+    pub fn finish(self) -> Result<W> {
+        let mut writer = match self.encoder {
+            Encoder::Plain(writer) => writer,
+            Encoder::Gzip(encoder) => encoder.finish()?,
+        };
+        writer.flush()?;
+        Ok(writer)
+    }
+
+    fn get_mut(&mut self) -> &mut W {
+        match &mut self.encoder {
+            Encoder::Plain(writer) => writer,
+            Encoder::Gzip(encoder) => encoder.get_mut(),
+        }
+    }
+}
+
+impl NdjsonWriter<BufWriter<File>> {
+    /// Creates a writer to a new file, truncating it if it already exists.
+    ///
+    /// If the path has a `.gz` extension, the output is gzip-compressed.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use stac::Item;
+    /// use stac_io::NdjsonWriter;
+    ///
+    /// let mut writer = NdjsonWriter::create("items.ndjson.gz").unwrap();
+    /// writer.write(&Item::new("an-id")).unwrap();
+    /// writer.finish().unwrap();
+    /// ```
+    pub fn create(path: impl AsRef<Path>) -> Result<NdjsonWriter<BufWriter<File>>> {
+        let path = path.as_ref();
+        let file = File::create(path)?;
+        Ok(NdjsonWriter::from_file(file, path))
+    }
+
+    /// Creates a writer that appends to a file, creating it if it doesn't exist.
+    ///
+    /// If the path has a `.gz` extension, the appended lines are written as a
+    /// new gzip member. Multi-member gzip files are valid gzip, and tools like
+    /// `gunzip` read them as one stream.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use stac::Item;
+    /// use stac_io::NdjsonWriter;
+    ///
+    /// let mut writer = NdjsonWriter::append("items.ndjson").unwrap();
+    /// writer.write(&Item::new("another-id")).unwrap();
+    /// writer.finish().unwrap();
+    /// ```
+    pub fn append(path: impl AsRef<Path>) -> Result<NdjsonWriter<BufWriter<File>>> {
+        let path = path.as_ref();
+        let file = OpenOptions::new().append(true).create(true).open(path)?;
+        Ok(NdjsonWriter::from_file(file, path))
+    }
+
+    fn from_file(file: File, path: &Path) -> NdjsonWriter<BufWriter<File>> {
+        let writer = BufWriter::new(file);
+        if is_gzip_path(path) {
+            NdjsonWriter::gzip(writer)
+        } else {
+            NdjsonWriter::new(writer)
+        }
+    }
+}
+
+/// Asynchronously writes values as newline-delimited JSON, one line at a time.
+///
+/// Each value is written to the underlying writer before the next one is
+/// accepted, so a slow destination slows down the producer instead of values
+/// piling up in memory. Output can optionally be gzip-compressed.
+///
+/// Call [AsyncNdjsonWriter::finish] when you're done to flush the output and,
+/// for gzip output, to write the gzip trailer.
+///
+/// # Examples
+///
+/// ```
+/// use stac::Item;
+/// use stac_io::AsyncNdjsonWriter;
+///
+/// # tokio_test::block_on(async {
+/// let mut writer = AsyncNdjsonWriter::new(Vec::new());
+/// let items = futures::stream::iter([Item::new("a"), Item::new("b")]);
+/// let count = writer.write_stream(items).await.unwrap();
+/// assert_eq!(count, 2);
+/// let bytes = writer.finish().await.unwrap();
+/// # })
+/// ```
+#[derive(Debug)]
+pub struct AsyncNdjsonWriter<W: AsyncWrite + Unpin> {
+    writer: W,
+    buffer: NdjsonWriter<Vec<u8>>,
+}
+
+impl<W: AsyncWrite + Unpin> AsyncNdjsonWriter<W> {
+    /// Creates a writer that writes uncompressed newline-delimited JSON.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_io::AsyncNdjsonWriter;
+    ///
+    /// let writer = AsyncNdjsonWriter::new(Vec::new());
+    /// ```
+    pub fn new(writer: W) -> AsyncNdjsonWriter<W> {
+        AsyncNdjsonWriter {
+            writer,
+            buffer: NdjsonWriter::new(Vec::new()),
+        }
+    }
+
+    /// Creates a writer that gzip-compresses its output.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_io::AsyncNdjsonWriter;
+    ///
+    /// let writer = AsyncNdjsonWriter::gzip(Vec::new());
+    /// ```
+    pub fn gzip(writer: W) -> AsyncNdjsonWriter<W> {
+        AsyncNdjsonWriter {
+            writer,
+            buffer: NdjsonWriter::gzip(Vec::new()),
+        }
+    }
+
+    /// Writes a single value as one line.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::Item;
+    /// use stac_io::AsyncNdjsonWriter;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let mut writer = AsyncNdjsonWriter::new(Vec::new());
+    /// writer.write(&Item::new("an-id")).await.unwrap();
+    /// # })
+    /// ```
+    pub async fn write(&mut self, value: &impl Serialize) -> Result<()> {
+        self.buffer.write(value)?;
+        self.drain().await
+    }
+
+    /// Writes every value from a stream, returning the number of values written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::Item;
+    /// use stac_io::AsyncNdjsonWriter;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let mut writer = AsyncNdjsonWriter::new(Vec::new());
+    /// let items = futures::stream::iter([Item::new("a"), Item::new("b")]);
+    /// assert_eq!(writer.write_stream(items).await.unwrap(), 2);
+    /// # })
+    /// ```
+    pub async fn write_stream<T: Serialize>(
+        &mut self,
+        stream: impl Stream<Item = T>,
+    ) -> Result<usize> {
+        pin_mut!(stream);
+        let mut count = 0;
+        while let Some(value) = stream.next().await {
+            self.write(&value).await?;
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    /// Writes every value from a fallible stream, returning the number of values written.
+    ///
+    /// Writing stops at the first error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::Item;
+    /// use stac_io::AsyncNdjsonWriter;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let mut writer = AsyncNdjsonWriter::new(Vec::new());
+    /// let items = futures::stream::iter([Ok::<_, stac_io::Error>(Item::new("a"))]);
+    /// assert_eq!(writer.try_write_stream(items).await.unwrap(), 1);
+    /// # })
+    /// ```
+    pub async fn try_write_stream<S>(&mut self, stream: S) -> Result<usize>
+    where
+        S: TryStream,
+        S::Ok: Serialize,
+        Error: From<S::Error>,
+    {
+        let stream = stream.into_stream();
+        pin_mut!(stream);
+        let mut count = 0;
+        while let Some(value) = stream.try_next().await? {
+            self.write(&value).await?;
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    /// Flushes all output and returns the underlying writer.
+    ///
+    /// For gzip output this also writes the gzip trailer, so the output
+    /// isn't a complete gzip stream until this is called.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_io::AsyncNdjsonWriter;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let writer = AsyncNdjsonWriter::gzip(Vec::new());
+    /// let bytes = writer.finish().await.unwrap();
+    /// # })
+    /// ```
+    pub async fn finish(mut self) -> Result<W> {
+        let bytes = self.buffer.finish()?;
+        self.writer.write_all(&bytes).await?;
+        self.writer.flush().await?;
+        Ok(self.writer)
+    }
+
+    async fn drain(&mut self) -> Result<()> {
+        let buffer = self.buffer.get_mut();
+        if !buffer.is_empty() {
+            self.writer.write_all(buffer).await?;
+            buffer.clear();
+        }
+        Ok(())
+    }
+}
+
+impl AsyncNdjsonWriter<tokio::fs::File> {
+    /// Creates a writer to a new file, truncating it if it already exists.
+    ///
+    /// If the path has a `.gz` extension, the output is gzip-compressed.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use stac_io::AsyncNdjsonWriter;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let writer = AsyncNdjsonWriter::create("items.ndjson").await.unwrap();
+    /// # })
+    /// ```
+    pub async fn create(path: impl AsRef<Path>) -> Result<AsyncNdjsonWriter<tokio::fs::File>> {
+        let path = path.as_ref();
+        let file = tokio::fs::File::create(path).await?;
+        Ok(AsyncNdjsonWriter::from_file(file, path))
+    }
+
+    /// Creates a writer that appends to a file, creating it if it doesn't exist.
+    ///
+    /// See [NdjsonWriter::append] for how gzip files are appended to.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use stac_io::AsyncNdjsonWriter;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let writer = AsyncNdjsonWriter::append("items.ndjson").await.unwrap();
+    /// # })
+    /// ```
+    pub async fn append(path: impl AsRef<Path>) -> Result<AsyncNdjsonWriter<tokio::fs::File>> {
+        let path = path.as_ref();
+        let file = tokio::fs::OpenOptions::new()
+            .append(true)
+            .create(true)
+            .open(path)
+            .await?;
+        Ok(AsyncNdjsonWriter::from_file(file, path))
+    }
+
+    fn from_file(file: tokio::fs::File, path: &Path) -> AsyncNdjsonWriter<tokio::fs::File> {
+        if is_gzip_path(path) {
+            AsyncNdjsonWriter::gzip(file)
+        } else {
+            AsyncNdjsonWriter::new(file)
+        }
+    }
+}
+
+fn is_gzip_path(path: &Path) -> bool {
+    path.extension().is_some_and(|extension| extension == "gz")
+}
+
 #[cfg(test)]
 mod tests {
     use super::FromNdjsonPath;
@@ -157,4 +592,76 @@ mod tests {
     fn value_read() {
         let _ = Value::from_ndjson_path("data/items.ndjson").unwrap();
     }
+
+    #[test]
+    fn item_reader_bad_line() {
+        let item = serde_json::to_string(&stac::Item::new("an-id")).unwrap();
+        let ndjson = format!("{item}\nnot an item\n\n{item}\n");
+        let results: Vec<_> = super::ndjson_item_reader(std::io::Cursor::new(ndjson)).collect();
+        assert_eq!(results.len(), 3);
+        assert!(results[0].is_ok());
+        let error = results[1].as_ref().unwrap_err();
+        assert!(matches!(
+            error,
+            crate::Error::NdjsonLine { line_number: 2, .. }
+        ));
+        assert_eq!(error.line(), Some("not an item"));
+        assert!(results[2].is_ok());
+    }
+
+    #[test]
+    fn writer_append() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let path = tempdir.path().join("items.ndjson");
+        let mut writer = super::NdjsonWriter::create(&path).unwrap();
+        let _ = writer
+            .write_iter([stac::Item::new("a"), stac::Item::new("b")])
+            .unwrap();
+        let _ = writer.finish().unwrap();
+        let mut writer = super::NdjsonWriter::append(&path).unwrap();
+        writer.write(&stac::Item::new("c")).unwrap();
+        let _ = writer.finish().unwrap();
+        let item_collection = ItemCollection::from_ndjson_path(&path).unwrap();
+        assert_eq!(item_collection.items.len(), 3);
+        assert_eq!(item_collection.items[2].id, "c");
+    }
+
+    #[test]
+    fn writer_gzip() {
+        use std::io::Read;
+
+        let mut writer = super::NdjsonWriter::gzip(Vec::new());
+        let _ = writer
+            .write_iter([stac::Item::new("a"), stac::Item::new("b")])
+            .unwrap();
+        let bytes = writer.finish().unwrap();
+        let mut decoder = flate2::read::GzDecoder::new(bytes.as_slice());
+        let mut ndjson = String::new();
+        let _ = decoder.read_to_string(&mut ndjson).unwrap();
+        let items: Vec<_> = super::ndjson_item_reader(std::io::Cursor::new(ndjson))
+            .collect::<crate::Result<_>>()
+            .unwrap();
+        assert_eq!(items.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn async_writer_gzip_append() {
+        use std::io::Read;
+
+        let tempdir = tempfile::tempdir().unwrap();
+        let path = tempdir.path().join("items.ndjson.gz");
+        let mut writer = super::AsyncNdjsonWriter::create(&path).await.unwrap();
+        let items = futures::stream::iter([stac::Item::new("a"), stac::Item::new("b")]);
+        assert_eq!(writer.write_stream(items).await.unwrap(), 2);
+        let _ = writer.finish().await.unwrap();
+        let mut writer = super::AsyncNdjsonWriter::append(&path).await.unwrap();
+        let items = futures::stream::iter([Ok::<_, crate::Error>(stac::Item::new("c"))]);
+        assert_eq!(writer.try_write_stream(items).await.unwrap(), 1);
+        let _ = writer.finish().await.unwrap();
+
+        let mut decoder = flate2::read::MultiGzDecoder::new(std::fs::File::open(&path).unwrap());
+        let mut ndjson = String::new();
+        let _ = decoder.read_to_string(&mut ndjson).unwrap();
+        assert_eq!(ndjson.lines().count(), 3);
+    }
 }
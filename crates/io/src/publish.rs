@@ -0,0 +1,408 @@
+//! Publish items as a static catalog backed by stac-geoparquet.
+//!
+//! A [Publisher] writes a layout that can be served as a static site, read
+//! with DuckDB, or loaded into a STAC API server:
+//!
+//! ```text
+//! {prefix}/catalog.json
+//! {prefix}/{collection_id}/collection.json
+//! {prefix}/{collection_id}/items.parquet
+//! {prefix}/{collection_id}/{item_id}/{item_id}.json
+//! ```
+//!
+//! All links are relative, so the output can be moved or copied (e.g. to a
+//! bucket) without rewriting anything.
+
+use crate::{Error, Result, StacStore};
+use stac::{
+    Asset, Catalog, Collection, Item, Link, Links, geoparquet::WriterOptions,
+    mime::APPLICATION_PARQUET,
+};
+use std::{collections::BTreeMap, fmt::Display, str::FromStr};
+
+/// The default id of the published root catalog.
+pub const DEFAULT_CATALOG_ID: &str = "rustac-publish";
+
+/// The default description of the published root catalog.
+pub const DEFAULT_CATALOG_DESCRIPTION: &str = "A static catalog published by rustac";
+
+/// The key of the stac-geoparquet asset added to each collection.
+pub const GEOPARQUET_ASSET_KEY: &str = "geoparquet-items";
+
+const CATALOG_FILE_NAME: &str = "catalog.json";
+const COLLECTION_FILE_NAME: &str = "collection.json";
+const GEOPARQUET_FILE_NAME: &str = "items.parquet";
+
+/// Which item formats to publish.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Formats {
+    /// Write each collection's items to a stac-geoparquet file.
+    pub parquet: bool,
+
+    /// Write each item to its own JSON file, linked from its collection.
+    pub json: bool,
+}
+
+/// Writes items and collections to a static catalog.
+#[derive(Debug, Clone)]
+pub struct Publisher {
+    store: StacStore,
+    prefix: String,
+    formats: Formats,
+    id: String,
+    description: String,
+    writer_options: WriterOptions,
+}
+
+/// What was published.
+#[derive(Debug, Clone)]
+pub struct Published {
+    /// The root catalog, with child links to the collections.
+    pub catalog: Catalog,
+
+    /// The collections, with item links and assets.
+    pub collections: Vec<Collection>,
+
+    /// The number of items that were published.
+    pub item_count: usize,
+}
+
+impl Publisher {
+    /// Creates a new publisher that writes below `prefix` in the store.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_io::publish::Publisher;
+    ///
+    /// let (store, path) = stac_io::parse_href("out").unwrap();
+    /// let publisher = Publisher::new(store, path);
+    /// ```
+    pub fn new(store: StacStore, prefix: impl ToString) -> Publisher {
+        Publisher {
+            store,
+            prefix: prefix.to_string().trim_end_matches('/').to_string(),
+            formats: Formats::default(),
+            id: DEFAULT_CATALOG_ID.to_string(),
+            description: DEFAULT_CATALOG_DESCRIPTION.to_string(),
+            writer_options: WriterOptions::default(),
+        }
+    }
+
+    /// Sets the item formats to publish.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_io::publish::{Formats, Publisher};
+    ///
+    /// let (store, path) = stac_io::parse_href("out").unwrap();
+    /// let publisher = Publisher::new(store, path).formats("parquet".parse().unwrap());
+    /// ```
+    pub fn formats(mut self, formats: Formats) -> Publisher {
+        self.formats = formats;
+        self
+    }
+
+    /// Sets the id of the root catalog.
+    pub fn id(mut self, id: impl ToString) -> Publisher {
+        self.id = id.to_string();
+        self
+    }
+
+    /// Sets the description of the root catalog.
+    pub fn description(mut self, description: impl ToString) -> Publisher {
+        self.description = description.to_string();
+        self
+    }
+
+    /// Sets the options used to write stac-geoparquet.
+    pub fn writer_options(mut self, writer_options: WriterOptions) -> Publisher {
+        self.writer_options = writer_options;
+        self
+    }
+
+    /// Publishes collections and their items.
+    ///
+    /// Items are grouped by their `collection` field, and every item must
+    /// have one. Collections that aren't provided are generated from their
+    /// items. Provided collections keep their metadata, but their extents
+    /// are recomputed from the published items and their links are replaced.
+    ///
+    /// Collection and item ids are used as paths, so ids that are empty,
+    /// `.` or `..`, or that contain a `/` or `\`, are an error. Nothing is
+    /// written if any id is invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Collection, Item};
+    /// use stac_io::publish::Publisher;
+    ///
+    /// let dir = tempfile::tempdir().unwrap();
+    /// let (store, path) = stac_io::parse_href(dir.path().to_str().unwrap()).unwrap();
+    /// let item: Item = stac::read("examples/simple-item.json").unwrap();
+    /// # tokio_test::block_on(async {
+    /// let published = Publisher::new(store, path)
+    ///     .publish(Vec::new(), vec![item])
+    ///     .await
+    ///     .unwrap();
+    /// assert_eq!(published.item_count, 1);
+    /// # })
+    /// ```
+    pub async fn publish(
+        &self,
+        collections: Vec<Collection>,
+        items: Vec<Item>,
+    ) -> Result<Published> {
+        let mut grouped: BTreeMap<String, Vec<Item>> = BTreeMap::new();
+        for item in items {
+            let collection_id = item
+                .collection
+                .clone()
+                .ok_or(stac::Error::MissingField("collection"))?;
+            grouped.entry(collection_id).or_default().push(item);
+        }
+        let mut collections: BTreeMap<String, Collection> = collections
+            .into_iter()
+            .map(|collection| (collection.id.clone(), collection))
+            .collect();
+        for (collection_id, items) in &grouped {
+            if self.formats.json {
+                for item in items {
+                    check_path_id(&item.id)?;
+                }
+            }
+            if !collections.contains_key(collection_id) {
+                let _ = collections.insert(
+                    collection_id.clone(),
+                    Collection::from_id_and_items(collection_id, items),
+                );
+            }
+        }
+
+        for collection_id in collections.keys() {
+            check_path_id(collection_id)?;
+        }
+
+        let mut catalog = Catalog::new(&self.id, &self.description);
+        let mut published = Vec::with_capacity(collections.len());
+        let mut item_count = 0;
+        for (collection_id, mut collection) in collections {
+            let items = grouped.remove(&collection_id).unwrap_or_default();
+            item_count += items.len();
+            collection.links.clear();
+            collection.set_link(Link::root(format!("../{CATALOG_FILE_NAME}")).json());
+            collection.set_link(Link::parent(format!("../{CATALOG_FILE_NAME}")).json());
+            if !items.is_empty() {
+                collection.refresh_from_items(&items);
+            }
+            if self.formats.parquet && !items.is_empty() {
+                let mut asset = Asset::new(format!("./{GEOPARQUET_FILE_NAME}"));
+                asset.r#type = Some(APPLICATION_PARQUET.to_string());
+                asset.roles = vec!["stac-items".to_string()];
+                let _ = collection
+                    .assets
+                    .insert(GEOPARQUET_ASSET_KEY.to_string(), asset);
+                let _ = self
+                    .store
+                    .put_geoparquet(
+                        self.href(&format!("{collection_id}/{GEOPARQUET_FILE_NAME}")),
+                        items.clone(),
+                        self.writer_options,
+                        Vec::new(),
+                    )
+                    .await?;
+            }
+            if self.formats.json {
+                for mut item in items {
+                    let file_name = format!("{}/{}.json", item.id, item.id);
+                    collection
+                        .links
+                        .push(Link::item(format!("./{file_name}")).json());
+                    item.links.retain(|link| {
+                        !(link.is_root() || link.is_parent() || link.is_collection())
+                            && !link.is_self()
+                    });
+                    item.set_link(Link::root(format!("../../{CATALOG_FILE_NAME}")).json());
+                    item.set_link(Link::parent(format!("../{COLLECTION_FILE_NAME}")).json());
+                    item.set_link(Link::collection(format!("../{COLLECTION_FILE_NAME}")).json());
+                    let _ = self
+                        .store
+                        .put(self.href(&format!("{collection_id}/{file_name}")), item)
+                        .await?;
+                }
+            }
+            let _ = self
+                .store
+                .put(
+                    self.href(&format!("{collection_id}/{COLLECTION_FILE_NAME}")),
+                    collection.clone(),
+                )
+                .await?;
+            catalog
+                .links
+                .push(Link::child(format!("./{collection_id}/{COLLECTION_FILE_NAME}")).json());
+            published.push(collection);
+        }
+        catalog.set_link(Link::root(format!("./{CATALOG_FILE_NAME}")).json());
+        let _ = self
+            .store
+            .put(self.href(CATALOG_FILE_NAME), catalog.clone())
+            .await?;
+        Ok(Published {
+            catalog,
+            collections: published,
+            item_count,
+        })
+    }
+
+    fn href(&self, path: &str) -> String {
+        if self.prefix.is_empty() {
+            path.to_string()
+        } else {
+            format!("{}/{path}", self.prefix)
+        }
+    }
+}
+
+/// Checks that an id can be used as a single path segment below the prefix.
+fn check_path_id(id: &str) -> Result<()> {
+    if id.is_empty() || id == "." || id == ".." || id.contains(['/', '\\']) {
+        Err(Error::InvalidPathId(id.to_string()))
+    } else {
+        Ok(())
+    }
+}
+
+impl Default for Formats {
+    fn default() -> Formats {
+        Formats {
+            parquet: true,
+            json: true,
+        }
+    }
+}
+
+impl FromStr for Formats {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Formats> {
+        let mut formats = Formats {
+            parquet: false,
+            json: false,
+        };
+        for format in s.split(',').map(str::trim) {
+            match format {
+                "parquet" | "geoparquet" => formats.parquet = true,
+                "json" => formats.json = true,
+                _ => return Err(Error::UnsupportedFormat(format.to_string())),
+            }
+        }
+        Ok(formats)
+    }
+}
+
+impl Display for Formats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.parquet, self.json) {
+            (true, true) => f.write_str("parquet,json"),
+            (true, false) => f.write_str("parquet"),
+            (false, true) => f.write_str("json"),
+            (false, false) => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Formats, Publisher};
+    use stac::{Catalog, Collection, Item, ItemCollection, Links};
+
+    #[test]
+    fn parse_formats() {
+        let formats: Formats = "parquet,json".parse().unwrap();
+        assert!(formats.parquet);
+        assert!(formats.json);
+        let formats: Formats = "parquet".parse().unwrap();
+        assert!(!formats.json);
+        assert_eq!(formats.to_string(), "parquet");
+        let _ = "csv".parse::<Formats>().unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn publish() {
+        let dir = tempfile::tempdir().unwrap();
+        let (store, path) = crate::parse_href(dir.path().to_str().unwrap()).unwrap();
+        let collection = Collection::new("a-collection", "a description");
+        let items = vec![
+            Item::new("a").collection("a-collection"),
+            Item::new("b").collection("a-collection"),
+            Item::new("c").collection("another-collection"),
+        ];
+        let published = Publisher::new(store, path)
+            .publish(vec![collection], items)
+            .await
+            .unwrap();
+        assert_eq!(published.item_count, 3);
+        assert_eq!(published.collections.len(), 2);
+
+        let root = dir.path().to_str().unwrap();
+        let catalog: Catalog = crate::read(format!("{root}/catalog.json")).unwrap();
+        assert_eq!(catalog.iter_child_links().count(), 2);
+        let collection: Collection =
+            crate::read(format!("{root}/a-collection/collection.json")).unwrap();
+        assert_eq!(collection.description, "a description");
+        assert_eq!(collection.iter_item_links().count(), 2);
+        assert!(collection.assets.contains_key(super::GEOPARQUET_ASSET_KEY));
+        let item_collection: ItemCollection =
+            crate::read(format!("{root}/a-collection/items.parquet")).unwrap();
+        assert_eq!(item_collection.items.len(), 2);
+        let item: Item = crate::read(format!("{root}/a-collection/a/a.json")).unwrap();
+        assert_eq!(
+            item.link("collection").unwrap().href.as_str(),
+            "../collection.json"
+        );
+    }
+
+    #[tokio::test]
+    async fn publish_invalid_ids() {
+        let dir = tempfile::tempdir().unwrap();
+        let (store, path) = crate::parse_href(dir.path().join("out").to_str().unwrap()).unwrap();
+        let publisher = Publisher::new(store, path);
+        for items in [
+            vec![Item::new("../../escaped").collection("a-collection")],
+            vec![Item::new("a/b").collection("a-collection")],
+            vec![Item::new("a").collection("..")],
+            vec![Item::new("a").collection("nested/collection")],
+        ] {
+            assert!(matches!(
+                publisher.publish(Vec::new(), items).await.unwrap_err(),
+                crate::Error::InvalidPathId(_)
+            ));
+        }
+        assert!(!dir.path().join("out").exists());
+        assert!(!dir.path().join("escaped").exists());
+
+        let published = publisher
+            .clone()
+            .formats("parquet".parse().unwrap())
+            .publish(
+                Vec::new(),
+                vec![Item::new("a/b").collection("a-collection")],
+            )
+            .await
+            .unwrap();
+        assert_eq!(published.item_count, 1);
+    }
+
+    #[tokio::test]
+    async fn publish_requires_collection() {
+        let dir = tempfile::tempdir().unwrap();
+        let (store, path) = crate::parse_href(dir.path().to_str().unwrap()).unwrap();
+        let _ = Publisher::new(store, path)
+            .publish(Vec::new(), vec![Item::new("a")])
+            .await
+            .unwrap_err();
+    }
+}
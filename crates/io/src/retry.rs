@@ -0,0 +1,346 @@
+//! Retry policies for object store and STAC API requests.
+//!
+//! A [RetryPolicy] can be configured from `key=value` options, the same way
+//! object stores are (e.g. `rustac --opt retry_max=5`):
+//!
+//! - `retry_max`: the maximum number of retries after the first attempt
+//! - `retry_backoff`: the delay before the first retry, e.g. `100ms` or `2s`
+//! - `retry_max_backoff`: the maximum delay between retries
+//! - `retry_status`: a comma-delimited list of HTTP status codes to retry
+
+use crate::{Error, Result};
+use http::StatusCode;
+use std::{future::Future, time::Duration};
+
+/// The HTTP status codes that are retried by default.
+pub const DEFAULT_RETRY_STATUS: [u16; 6] = [408, 429, 500, 502, 503, 504];
+
+/// How failed requests are retried.
+///
+/// Retries use exponential backoff: the delay doubles after each retry, up to
+/// a maximum. Requests that fail with a status in the retry list, or with a
+/// connection error or timeout, are retried. Object store errors other than
+/// not found, already exists, precondition, permission, and authentication
+/// errors are retried too, since the object store doesn't expose their status.
+///
+/// By default, requests aren't retried.
+///
+/// # Examples
+///
+/// ```
+/// use stac_io::retry::RetryPolicy;
+/// use std::time::Duration;
+///
+/// let policy = RetryPolicy::new(5).initial_backoff(Duration::from_millis(50));
+/// assert_eq!(policy.backoff(0), Duration::from_millis(50));
+/// assert_eq!(policy.backoff(2), Duration::from_millis(200));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct RetryPolicy {
+    /// The maximum number of retries after the first attempt.
+    pub max_retries: u32,
+
+    /// The delay before the first retry.
+    pub initial_backoff: Duration,
+
+    /// The maximum delay between retries.
+    pub max_backoff: Duration,
+
+    /// The HTTP status codes to retry.
+    pub retry_status: Vec<u16>,
+}
+
+impl RetryPolicy {
+    /// Creates a new retry policy with a maximum number of retries.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_io::retry::RetryPolicy;
+    ///
+    /// let policy = RetryPolicy::new(3);
+    /// assert_eq!(policy.max_retries, 3);
+    /// ```
+    pub fn new(max_retries: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_retries,
+            ..Default::default()
+        }
+    }
+
+    /// Creates a retry policy from `key=value` options.
+    ///
+    /// Returns the policy and the options that aren't retry options, so they
+    /// can be passed on to an object store. See the [module
+    /// documentation](self) for the keys.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_io::retry::RetryPolicy;
+    ///
+    /// let (policy, options) = RetryPolicy::from_options([
+    ///     ("retry_max", "5"),
+    ///     ("retry_status", "429,503"),
+    ///     ("aws_region", "us-west-2"),
+    /// ])
+    /// .unwrap();
+    /// assert_eq!(policy.max_retries, 5);
+    /// assert_eq!(policy.retry_status, [429, 503]);
+    /// assert_eq!(options, [("aws_region".to_string(), "us-west-2".to_string())]);
+    /// ```
+    pub fn from_options<I, K, V>(options: I) -> Result<(RetryPolicy, Vec<(String, String)>)>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: Into<String>,
+    {
+        let mut policy = RetryPolicy::default();
+        let mut rest = Vec::new();
+        for (key, value) in options {
+            let key = key.as_ref();
+            let value = value.into();
+            let invalid = || Error::InvalidOption {
+                key: key.to_string(),
+                value: value.clone(),
+            };
+            match key {
+                "retry_max" => policy.max_retries = value.trim().parse().map_err(|_| invalid())?,
+                "retry_backoff" => {
+                    policy.initial_backoff = parse_duration(&value).ok_or_else(invalid)?
+                }
+                "retry_max_backoff" => {
+                    policy.max_backoff = parse_duration(&value).ok_or_else(invalid)?
+                }
+                "retry_status" => {
+                    policy.retry_status = value
+                        .split(',')
+                        .map(|status| status.trim().parse())
+                        .collect::<std::result::Result<_, _>>()
+                        .map_err(|_| invalid())?
+                }
+                _ => rest.push((key.to_string(), value)),
+            }
+        }
+        Ok((policy, rest))
+    }
+
+    /// Sets the delay before the first retry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_io::retry::RetryPolicy;
+    /// use std::time::Duration;
+    ///
+    /// let policy = RetryPolicy::new(3).initial_backoff(Duration::from_secs(1));
+    /// ```
+    pub fn initial_backoff(mut self, initial_backoff: Duration) -> RetryPolicy {
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    /// Sets the maximum delay between retries.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_io::retry::RetryPolicy;
+    /// use std::time::Duration;
+    ///
+    /// let policy = RetryPolicy::new(3).max_backoff(Duration::from_secs(30));
+    /// ```
+    pub fn max_backoff(mut self, max_backoff: Duration) -> RetryPolicy {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Sets the HTTP status codes to retry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_io::retry::RetryPolicy;
+    ///
+    /// let policy = RetryPolicy::new(3).retry_status([429, 503]);
+    /// ```
+    pub fn retry_status(mut self, retry_status: impl IntoIterator<Item = u16>) -> RetryPolicy {
+        self.retry_status = retry_status.into_iter().collect();
+        self
+    }
+
+    /// Returns the delay before a retry, starting at zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_io::retry::RetryPolicy;
+    /// use std::time::Duration;
+    ///
+    /// let policy = RetryPolicy::new(10).max_backoff(Duration::from_secs(1));
+    /// assert_eq!(policy.backoff(20), Duration::from_secs(1));
+    /// ```
+    pub fn backoff(&self, retry: u32) -> Duration {
+        self.initial_backoff
+            .saturating_mul(2u32.saturating_pow(retry))
+            .min(self.max_backoff)
+    }
+
+    /// Returns true if a response with this status should be retried.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use http::StatusCode;
+    /// use stac_io::retry::RetryPolicy;
+    ///
+    /// let policy = RetryPolicy::default();
+    /// assert!(policy.should_retry_status(StatusCode::SERVICE_UNAVAILABLE));
+    /// assert!(!policy.should_retry_status(StatusCode::NOT_FOUND));
+    /// ```
+    pub fn should_retry_status(&self, status: StatusCode) -> bool {
+        self.retry_status.contains(&status.as_u16())
+    }
+
+    /// Returns true if a request that failed with this error should be retried.
+    pub(crate) fn should_retry(&self, error: &Error) -> bool {
+        match error {
+            Error::Http { status, .. } => self.should_retry_status(*status),
+            Error::Reqwest(error) => {
+                if let Some(status) = error.status() {
+                    self.should_retry_status(status)
+                } else {
+                    error.is_timeout() || error.is_connect() || error.is_request()
+                }
+            }
+            #[cfg(feature = "store")]
+            Error::ObjectStore(error) => !matches!(
+                error,
+                object_store::Error::NotFound { .. }
+                    | object_store::Error::AlreadyExists { .. }
+                    | object_store::Error::Precondition { .. }
+                    | object_store::Error::NotModified { .. }
+                    | object_store::Error::NotImplemented { .. }
+                    | object_store::Error::PermissionDenied { .. }
+                    | object_store::Error::Unauthenticated { .. }
+                    | object_store::Error::InvalidPath { .. }
+                    | object_store::Error::NotSupported { .. }
+                    | object_store::Error::UnknownConfigurationKey { .. }
+            ),
+            _ => false,
+        }
+    }
+
+    /// Runs a request, retrying it according to this policy.
+    pub(crate) async fn run<T, F, Fut>(&self, mut f: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let mut retry = 0;
+        loop {
+            match f().await {
+                Err(error) if retry < self.max_retries && self.should_retry(&error) => {
+                    let backoff = self.backoff(retry);
+                    tracing::warn!(
+                        "retrying in {}ms ({}/{}): {error}",
+                        backoff.as_millis(),
+                        retry + 1,
+                        self.max_retries
+                    );
+                    tokio::time::sleep(backoff).await;
+                    retry += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_retries: 0,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(10),
+            retry_status: DEFAULT_RETRY_STATUS.to_vec(),
+        }
+    }
+}
+
+/// Parses a duration like `250ms`, `2s`, or `1.5`, where a bare number is in
+/// seconds.
+fn parse_duration(s: &str) -> Option<Duration> {
+    let s = s.trim();
+    let (number, scale) = if let Some(number) = s.strip_suffix("ms") {
+        (number, 0.001)
+    } else if let Some(number) = s.strip_suffix('s') {
+        (number, 1.0)
+    } else {
+        (s, 1.0)
+    };
+    let seconds = number.trim().parse::<f64>().ok()? * scale;
+    Duration::try_from_secs_f64(seconds).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RetryPolicy;
+    use crate::Error;
+    use std::{
+        sync::atomic::{AtomicU32, Ordering},
+        time::Duration,
+    };
+
+    #[test]
+    fn parse_duration() {
+        assert_eq!(
+            super::parse_duration("250ms"),
+            Some(Duration::from_millis(250))
+        );
+        assert_eq!(super::parse_duration("2s"), Some(Duration::from_secs(2)));
+        assert_eq!(
+            super::parse_duration("1.5"),
+            Some(Duration::from_millis(1500))
+        );
+        assert_eq!(super::parse_duration("-1s"), None);
+        assert_eq!(super::parse_duration("soon"), None);
+    }
+
+    #[test]
+    fn invalid_option() {
+        let _ = RetryPolicy::from_options([("retry_max", "many")]).unwrap_err();
+        let _ = RetryPolicy::from_options([("retry_status", "429,oops")]).unwrap_err();
+    }
+
+    #[cfg(feature = "store")]
+    #[tokio::test]
+    async fn run() {
+        let policy = RetryPolicy::new(2).initial_backoff(Duration::from_millis(1));
+        let attempts = &AtomicU32::new(0);
+        let result: crate::Result<()> = policy
+            .run(|| async move {
+                let _ = attempts.fetch_add(1, Ordering::SeqCst);
+                Err(Error::ObjectStore(object_store::Error::Generic {
+                    store: "test",
+                    source: "connection reset".into(),
+                }))
+            })
+            .await;
+        let _ = result.unwrap_err();
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+
+        let attempts = &AtomicU32::new(0);
+        let result: crate::Result<()> = policy
+            .run(|| async move {
+                let _ = attempts.fetch_add(1, Ordering::SeqCst);
+                Err(Error::ObjectStore(object_store::Error::NotFound {
+                    path: "a/path".to_string(),
+                    source: "not found".into(),
+                }))
+            })
+            .await;
+        let _ = result.unwrap_err();
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+    }
+}
@@ -1,9 +1,20 @@
-use crate::{Format, Readable, Result, Writeable};
-use object_store::{ObjectStore, ObjectStoreExt, ObjectStoreScheme, PutResult, path::Path};
+use crate::{
+    Error, Format, Readable, Result, Writeable,
+    cache::{Cache, CacheEntry},
+    retry::RetryPolicy,
+};
+use bytes::Bytes;
+use object_store::{
+    GetResult, ObjectStore, ObjectStoreExt, ObjectStoreScheme, PutPayload, PutResult, path::Path,
+};
 use std::{fmt::Debug, sync::Arc};
 use tracing::instrument;
 use url::Url;
 
+/// The number of bytes fetched from the start of a GeoTIFF by
+/// [StacStore::create_item] before its tags are read.
+const CREATE_ITEM_PROBE_SIZE: usize = 64 * 1024;
+
 /// Parses an href into a [StacStore] and a [Path].
 pub fn parse_href(href: impl ToString) -> Result<(StacStore, Path)> {
     parse_href_opts(href, [] as [(&str, &str); 0])
@@ -12,12 +23,16 @@ pub fn parse_href(href: impl ToString) -> Result<(StacStore, Path)> {
 /// Parses an href and options into [StacStore] and a [Path].
 ///
 /// Relative string hrefs are made absolute `file://` hrefs relative to the current directory.`
+///
+/// [Retry options](crate::retry) configure the store's [RetryPolicy], and
+/// all other options are passed to the object store.
 pub fn parse_href_opts<I, K, V>(href: impl ToString, options: I) -> Result<(StacStore, Path)>
 where
     I: IntoIterator<Item = (K, V)>,
     K: AsRef<str>,
     V: Into<String>,
 {
+    let (retry_policy, options) = RetryPolicy::from_options(options)?;
     let href = href.to_string();
     let mut url = stac::href::make_url(&href)?;
     let parse = || -> Result<(Box<dyn ObjectStore>, Path)> {
@@ -58,7 +73,106 @@ where
     let (store, path) = parse()?;
     tracing::debug!("{url} parsed into path {path}");
     url.set_path("");
-    Ok((StacStore::new(Arc::new(store), url), path))
+    Ok((
+        StacStore::new(Arc::new(store), url).retry_policy(retry_policy),
+        path,
+    ))
+}
+
+/// Gets a STAC value from an href.
+///
+/// The format will be inferred from the href's file extension, and the object
+/// store will be configured from the environment.
+///
+/// # Examples
+///
+/// ```
+/// # tokio_test::block_on(async {
+/// let item: stac::Item = stac_io::get("examples/simple-item.json").await.unwrap();
+/// # })
+/// ```
+pub async fn get<T>(href: impl ToString) -> Result<T>
+where
+    T: Readable,
+{
+    get_opts(href, [] as [(&str, &str); 0]).await
+}
+
+/// Gets a STAC value from an href, configuring the object store with options.
+///
+/// The format will be inferred from the href's file extension.
+///
+/// # Examples
+///
+/// ```
+/// # tokio_test::block_on(async {
+/// let item: stac::Item = stac_io::get_opts("examples/simple-item.json", [] as [(&str, &str); 0])
+///     .await
+///     .unwrap();
+/// # })
+/// ```
+pub async fn get_opts<T, I, K, V>(href: impl ToString, options: I) -> Result<T>
+where
+    T: Readable,
+    I: IntoIterator<Item = (K, V)>,
+    K: AsRef<str>,
+    V: Into<String>,
+{
+    let href = href.to_string();
+    let format = Format::infer_from_href(&href).unwrap_or_default();
+    let (store, path) = parse_href_opts(href, options)?;
+    store.get_format(path, format).await
+}
+
+/// Puts a STAC value to an href.
+///
+/// The format will be inferred from the href's file extension, and the object
+/// store will be configured from the environment.
+///
+/// # Examples
+///
+/// ```no_run
+/// # tokio_test::block_on(async {
+/// let item = stac::Item::new("an-id");
+/// let _ = stac_io::put("item.json", item).await.unwrap();
+/// # })
+/// ```
+pub async fn put<T>(href: impl ToString, value: T) -> Result<PutResult>
+where
+    T: Writeable + Debug,
+{
+    put_opts(href, value, [] as [(&str, &str); 0]).await
+}
+
+/// Puts a STAC value to an href, configuring the object store with options.
+///
+/// The format will be inferred from the href's file extension.
+///
+/// # Examples
+///
+/// ```no_run
+/// # tokio_test::block_on(async {
+/// let item = stac::Item::new("an-id");
+/// let _ = stac_io::put_opts(
+///     "s3://bucket/item.json",
+///     item,
+///     [("aws_region", "us-west-2")],
+/// )
+/// .await
+/// .unwrap();
+/// # })
+/// ```
+pub async fn put_opts<T, I, K, V>(href: impl ToString, value: T, options: I) -> Result<PutResult>
+where
+    T: Writeable + Debug,
+    I: IntoIterator<Item = (K, V)>,
+    K: AsRef<str>,
+    V: Into<String>,
+{
+    let href = href.to_string();
+    let format = Format::infer_from_href(&href).unwrap_or_default();
+    let (store, path) = parse_href_opts(href, options)?;
+    store.put_format(path, value, format).await
 }
 
 /// Reads STAC from an [ObjectStore].
@@ -66,6 +180,8 @@ where
 pub struct StacStore {
     store: Arc<dyn ObjectStore>,
     root: Option<Url>,
+    retry_policy: RetryPolicy,
+    cache: Option<Arc<dyn Cache>>,
 }
 
 impl StacStore {
@@ -87,18 +203,61 @@ impl StacStore {
         StacStore {
             store: Arc::new(store),
             root: Some(root),
+            retry_policy: RetryPolicy::default(),
+            cache: None,
         }
     }
 
+    /// Sets the policy for retrying failed gets and puts.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_io::retry::RetryPolicy;
+    ///
+    /// let (store, _) = stac_io::parse_href("examples/simple-item.json").unwrap();
+    /// let store = store.retry_policy(RetryPolicy::new(5));
+    /// ```
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> StacStore {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Sets the cache used for conditional gets.
+    ///
+    /// Gets send the cached entry's `ETag` and last modified time, and reuse
+    /// its bytes if the object hasn't changed. See [crate::cache] for more.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_io::cache::FileSystemCache;
+    /// use std::sync::Arc;
+    ///
+    /// let (store, _) = stac_io::parse_href("examples/simple-item.json").unwrap();
+    /// let store = store.cache(Arc::new(FileSystemCache::new("cache")));
+    /// ```
+    pub fn cache(mut self, cache: Arc<dyn Cache>) -> StacStore {
+        self.cache = Some(cache);
+        self
+    }
+
     /// Gets a STAC value from the store.
     ///
-    /// The format will be inferred from the href's file extension.
+    /// The format will be inferred from the href's file extension or, if it
+    /// doesn't have one, from the content (see [Format::infer_from_bytes]).
     pub async fn get<T>(&self, href: impl ToString + AsRef<str> + Debug) -> Result<T>
     where
         T: Readable,
     {
-        let format = Format::infer_from_href(href.as_ref()).unwrap_or_default();
-        self.get_format(href, format).await
+        if let Some(format) = Format::infer_from_href(href.as_ref()) {
+            return self.get_format(href, format).await;
+        }
+        let href = href.to_string();
+        let path = self.path(&href)?;
+        let bytes = self.get_bytes(&path).await?;
+        let format = Format::infer_from_bytes(&bytes).unwrap_or_default();
+        self.parse_bytes(href, path, bytes, format)
     }
 
     /// Gets a STAC value from the store in a specific format.
@@ -109,9 +268,112 @@ impl StacStore {
     {
         let href = href.to_string();
         let path = self.path(&href)?;
-        let get_result = self.store.get(&path).await?;
-        let bytes = get_result.bytes().await?;
-        let mut value: T = format.from_bytes(bytes)?;
+        let bytes = self.get_bytes(&path).await?;
+        self.parse_bytes(href, path, bytes, format)
+    }
+
+    /// Gets a JSON value from the store without parsing it as STAC.
+    ///
+    /// This is useful for documents that aren't STAC objects, e.g. a STAC
+    /// API's `/collections` response.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # tokio_test::block_on(async {
+    /// let (store, path) = stac_io::parse_href("examples/simple-item.json").unwrap();
+    /// let value = store.get_json(path).await.unwrap();
+    /// assert_eq!(value["type"], "Feature");
+    /// # })
+    /// ```
+    #[instrument(skip(self))]
+    pub async fn get_json(&self, href: impl ToString + Debug) -> Result<serde_json::Value> {
+        let path = self.path(&href.to_string())?;
+        let bytes = self.get_bytes(&path).await?;
+        let value = serde_json::from_slice(&bytes)?;
+        Ok(value)
+    }
+
+    async fn get_result(&self, path: &Path) -> Result<GetResult> {
+        self.retry_policy
+            .run(|| async move {
+                let get_result = self.store.get(path).await?;
+                Ok(get_result)
+            })
+            .await
+    }
+
+    async fn get_bytes(&self, path: &Path) -> Result<Bytes> {
+        let Some(cache) = self.cache.as_ref() else {
+            return self
+                .retry_policy
+                .run(|| async move {
+                    let bytes = self.store.get(path).await?.bytes().await?;
+                    Ok(bytes)
+                })
+                .await;
+        };
+        let key = self.path_href(path)?;
+        let entry = cache.get(&key).await?;
+        let options = entry
+            .as_ref()
+            .map(CacheEntry::get_options)
+            .unwrap_or_default();
+        let result = self
+            .retry_policy
+            .run(|| {
+                let options = options.clone();
+                async move {
+                    let get_result = self.store.get_opts(path, options).await?;
+                    let meta = get_result.meta.clone();
+                    let bytes = get_result.bytes().await?;
+                    Ok((meta, bytes))
+                }
+            })
+            .await;
+        match (result, entry) {
+            (Ok((meta, bytes)), _) => {
+                let mut entry = CacheEntry::new(bytes.clone()).last_modified(meta.last_modified);
+                entry.e_tag = meta.e_tag;
+                cache.put(&key, &entry).await?;
+                Ok(bytes)
+            }
+            (Err(Error::ObjectStore(object_store::Error::NotModified { .. })), Some(entry)) => {
+                tracing::debug!("{key} is not modified, using the cached value");
+                Ok(entry.bytes)
+            }
+            (Err(err), _) => Err(err),
+        }
+    }
+
+    fn path_href(&self, path: &Path) -> Result<String> {
+        if let Some(root) = self.root.as_ref() {
+            Ok(root.join(path.as_ref())?.to_string())
+        } else {
+            Ok(path.to_string())
+        }
+    }
+
+    async fn put_bytes(&self, path: &Path, payload: PutPayload) -> Result<PutResult> {
+        self.retry_policy
+            .run(|| {
+                let payload = payload.clone();
+                async move {
+                    let put_result = self.store.put(path, payload).await?;
+                    Ok(put_result)
+                }
+            })
+            .await
+    }
+
+    fn parse_bytes<T>(&self, href: String, path: Path, bytes: Bytes, format: Format) -> Result<T>
+    where
+        T: Readable,
+    {
+        let mut value: T = format.from_bytes(bytes).map_err(|err| Error::Parse {
+            href,
+            source: Box::new(err),
+        })?;
         if let Some(root) = self.root.as_ref() {
             value.set_self_href(root.join(path.as_ref())?);
         }
@@ -140,7 +402,7 @@ impl StacStore {
     {
         let path = self.path(href.as_ref())?;
         let bytes = format.into_vec(value)?;
-        let put_result = self.store.put(&path, bytes.into()).await?;
+        let put_result = self.put_bytes(&path, bytes.into()).await?;
         Ok(put_result)
     }
 
@@ -157,7 +419,7 @@ impl StacStore {
     ) -> Result<Box<dyn Iterator<Item = Result<stac::Item>> + Send>> {
         let href = href.to_string();
         let path = self.path(&href)?;
-        let get_result = self.store.get(&path).await?;
+        let get_result = self.get_result(&path).await?;
         let bytes = get_result.bytes().await?;
         match format {
             Format::NdJson => {
@@ -180,6 +442,279 @@ impl StacStore {
         }
     }
 
+    /// Reads the first `n` items from the store.
+    ///
+    /// Only as much of the source as is needed is read: ndjson is streamed
+    /// until `n` lines have been parsed, and stac-geoparquet is read one row
+    /// group at a time. JSON can't be read partially, so the full value is
+    /// read and then truncated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_io::Format;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let (store, path) = stac_io::parse_href("data/items.ndjson").unwrap();
+    /// let items = store.head(path, Format::NdJson, 1).await.unwrap();
+    /// assert_eq!(items.len(), 1);
+    /// # })
+    /// ```
+    #[instrument(skip(self))]
+    pub async fn head(
+        &self,
+        href: impl ToString + Debug,
+        format: Format,
+        n: usize,
+    ) -> Result<Vec<stac::Item>> {
+        use futures::TryStreamExt;
+
+        let href = href.to_string();
+        let mut items = Vec::new();
+        if n == 0 {
+            return Ok(items);
+        }
+        match format {
+            Format::NdJson => {
+                let path = self.path(&href)?;
+                let mut stream = self.get_result(&path).await?.into_stream();
+                let mut buf = Vec::new();
+                while items.len() < n {
+                    let Some(bytes) = stream.try_next().await? else {
+                        if !buf.trim_ascii().is_empty() {
+                            items.push(serde_json::from_slice(&buf)?);
+                        }
+                        break;
+                    };
+                    buf.extend_from_slice(&bytes);
+                    while items.len() < n
+                        && let Some(i) = buf.iter().position(|&b| b == b'\n')
+                    {
+                        let line: Vec<u8> = buf.drain(..=i).collect();
+                        if !line.trim_ascii().is_empty() {
+                            items.push(serde_json::from_slice(&line)?);
+                        }
+                    }
+                }
+            }
+            #[cfg(feature = "geoparquet")]
+            Format::Geoparquet(_) => {
+                let chunks = self.read_chunks(&href).await?;
+                futures::pin_mut!(chunks);
+                while items.len() < n {
+                    let Some(item_collection) = chunks.try_next().await? else {
+                        break;
+                    };
+                    let take = n - items.len();
+                    items.extend(item_collection.items.into_iter().take(take));
+                }
+            }
+            Format::Json(_) => {
+                let value: stac::Value = self.get_format(href, format).await?;
+                items = stac::ItemCollection::try_from(value)?.items;
+                items.truncate(n);
+            }
+        }
+        Ok(items)
+    }
+
+    /// Creates an item from a GeoTIFF in the store.
+    ///
+    /// Only the start of the file is fetched, which is enough for
+    /// cloud-optimized GeoTIFFs. If the tags aren't at the start of the file,
+    /// the rest of it is fetched too. The item's id is the file's stem. See
+    /// [stac::create] for which fields are set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # tokio_test::block_on(async {
+    /// let (store, path) = stac_io::parse_href("../core/assets/dataset_geo.tif").unwrap();
+    /// let item = store.create_item(path).await.unwrap();
+    /// assert_eq!(item.id, "dataset_geo");
+    /// # })
+    /// ```
+    #[instrument(skip(self))]
+    pub async fn create_item(&self, href: impl ToString + Debug) -> Result<stac::Item> {
+        use futures::TryStreamExt;
+        use stac::create::Raster;
+        use std::io::{Cursor, ErrorKind};
+
+        let href = href.to_string();
+        let path = self.path(&href)?;
+        let mut stream = self.get_result(&path).await?.into_stream();
+        let mut buf = Vec::new();
+        let mut exhausted = false;
+        while buf.len() < CREATE_ITEM_PROBE_SIZE {
+            let Some(bytes) = stream.try_next().await? else {
+                exhausted = true;
+                break;
+            };
+            buf.extend_from_slice(&bytes);
+        }
+        let raster = match Raster::read(Cursor::new(&buf)) {
+            Err(stac::Error::Io(err)) if err.kind() == ErrorKind::UnexpectedEof && !exhausted => {
+                while let Some(bytes) = stream.try_next().await? {
+                    buf.extend_from_slice(&bytes);
+                }
+                Raster::read(Cursor::new(&buf))?
+            }
+            result => result?,
+        };
+        let id = path
+            .filename()
+            .map(|name| name.rsplit_once('.').map_or(name, |(stem, _)| stem))
+            .ok_or_else(|| stac::Error::InvalidFilePath(href.clone()))?;
+        Ok(raster.to_item(id, href))
+    }
+
+    /// Reads a stac-geoparquet file from the store one row group at a time.
+    ///
+    /// Each row group is fetched when the stream is polled, so only one row
+    /// group's worth of items is in memory at a time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "geoparquet")]
+    /// # {
+    /// use futures::TryStreamExt;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let (store, path) = stac_io::parse_href("data/extended-item.parquet").unwrap();
+    /// let chunks = store.read_chunks(path).await.unwrap();
+    /// let item_collections: Vec<_> = chunks.try_collect().await.unwrap();
+    /// # })
+    /// # }
+    /// ```
+    #[cfg(feature = "geoparquet")]
+    #[instrument(skip(self))]
+    pub async fn read_chunks(
+        &self,
+        href: impl AsRef<str> + Debug,
+    ) -> Result<impl futures::Stream<Item = Result<stac::ItemCollection>>> {
+        use futures::TryStreamExt;
+
+        let path = self.path(href.as_ref())?;
+        let reader =
+            parquet::arrow::async_reader::ParquetObjectReader::new(self.store.clone(), path);
+        let stream = stac::geoparquet::read_chunks_async(reader).await?;
+        Ok(stream.map_err(Error::from))
+    }
+
+    /// Returns information about a stac-geoparquet file in the store,
+    /// including its custom key-value metadata.
+    ///
+    /// Only the file's footer is fetched. See [stac::geoparquet::info] for
+    /// details.
+    #[cfg(feature = "geoparquet")]
+    #[instrument(skip(self))]
+    pub async fn geoparquet_info(
+        &self,
+        href: impl AsRef<str> + Debug,
+    ) -> Result<stac::geoparquet::FileInfo> {
+        let path = self.path(href.as_ref())?;
+        let reader =
+            parquet::arrow::async_reader::ParquetObjectReader::new(self.store.clone(), path);
+        let info = stac::geoparquet::info_async(reader).await?;
+        Ok(info)
+    }
+
+    /// Puts items to the store as stac-geoparquet, with custom key-value
+    /// metadata.
+    ///
+    /// Setting a key in
+    /// [RESERVED_METADATA_KEYS](stac::geoparquet::RESERVED_METADATA_KEYS)
+    /// returns an error.
+    #[cfg(feature = "geoparquet")]
+    #[instrument(skip(self, items))]
+    pub async fn put_geoparquet(
+        &self,
+        href: impl AsRef<str> + Debug,
+        items: Vec<stac::Item>,
+        writer_options: stac::geoparquet::WriterOptions,
+        key_value_metadata: Vec<(String, String)>,
+    ) -> Result<PutResult> {
+        let path = self.path(href.as_ref())?;
+        let mut buf = Vec::new();
+        let mut builder =
+            stac::geoparquet::WriterBuilder::new(&mut buf).writer_options(writer_options);
+        for (key, value) in key_value_metadata {
+            builder = builder.key_value_metadata(key, value);
+        }
+        builder.build(items)?.finish()?;
+        let put_result = self.put_bytes(&path, buf.into()).await?;
+        Ok(put_result)
+    }
+
+    /// Appends items to an existing stac-geoparquet file in the store.
+    ///
+    /// The existing file is read, combined with the new items, and written
+    /// back to the same href. See [stac::geoparquet::append] for details.
+    #[cfg(feature = "geoparquet")]
+    #[instrument(skip(self, items))]
+    pub async fn append_geoparquet(
+        &self,
+        href: impl AsRef<str> + Debug,
+        items: Vec<stac::Item>,
+        writer_options: stac::geoparquet::WriterOptions,
+    ) -> Result<PutResult> {
+        let path = self.path(href.as_ref())?;
+        let bytes = self.get_bytes(&path).await?;
+        let mut buf = Vec::new();
+        stac::geoparquet::append(bytes, &mut buf, items, writer_options)?;
+        let put_result = self.put_bytes(&path, buf.into()).await?;
+        Ok(put_result)
+    }
+
+    /// Merges a change set into an existing stac-geoparquet file in the store
+    /// and writes the result as a new snapshot.
+    ///
+    /// The existing file at `href` is read, merged with the new, updated, and
+    /// deleted items, and written to `to`, leaving the existing file as it
+    /// was. If `to` is the same as `href`, the existing file is replaced with
+    /// a single put, which object stores (including the local filesystem)
+    /// apply atomically, so readers never see a partially-written file. See
+    /// [stac::geoparquet::upsert] for details.
+    #[cfg(feature = "geoparquet")]
+    #[instrument(skip(self, items, deletes))]
+    pub async fn upsert_geoparquet(
+        &self,
+        href: impl AsRef<str> + Debug,
+        to: impl AsRef<str> + Debug,
+        items: Vec<stac::Item>,
+        deletes: Vec<String>,
+        writer_options: stac::geoparquet::WriterOptions,
+    ) -> Result<stac::geoparquet::UpsertSummary> {
+        self.upsert_geoparquet_to(href, self, to, items, deletes, writer_options)
+            .await
+    }
+
+    /// Merges a change set into an existing stac-geoparquet file in the store
+    /// and writes the result to another store, e.g. a different bucket.
+    ///
+    /// See [StacStore::upsert_geoparquet] for details.
+    #[cfg(feature = "geoparquet")]
+    #[instrument(skip(self, to_store, items, deletes))]
+    pub async fn upsert_geoparquet_to(
+        &self,
+        href: impl AsRef<str> + Debug,
+        to_store: &StacStore,
+        to: impl AsRef<str> + Debug,
+        items: Vec<stac::Item>,
+        deletes: Vec<String>,
+        writer_options: stac::geoparquet::WriterOptions,
+    ) -> Result<stac::geoparquet::UpsertSummary> {
+        let path = self.path(href.as_ref())?;
+        let bytes = self.get_bytes(&path).await?;
+        let mut buf = Vec::new();
+        let summary = stac::geoparquet::upsert(bytes, &mut buf, items, deletes, writer_options)?;
+        let _ = to_store
+            .put_bytes(&to_store.path(to.as_ref())?, buf.into())
+            .await?;
+        Ok(summary)
+    }
+
     /// Puts items from an iterator to the store.
     ///
     /// For ndjson, items are serialized one per line. For geoparquet, items
@@ -201,7 +736,7 @@ impl StacStore {
                     serde_json::to_writer(&mut buf, &item)?;
                     buf.push(b'\n');
                 }
-                let put_result = self.store.put(&path, buf.into()).await?;
+                let put_result = self.put_bytes(&path, buf.into()).await?;
                 Ok(put_result)
             }
             #[cfg(feature = "geoparquet")]
@@ -253,12 +788,22 @@ impl StacStore {
             _ => {
                 let item_collection = stac::ItemCollection::from(items.collect::<Vec<_>>());
                 let bytes = format.into_vec(item_collection)?;
-                let put_result = self.store.put(&path, bytes.into()).await?;
+                let put_result = self.put_bytes(&path, bytes.into()).await?;
                 Ok(put_result)
             }
         }
     }
 
+    /// Returns the absolute href of a path in this store.
+    ///
+    /// This is the same href that's used as the self href of values read
+    /// from the store. If the store doesn't have a root, the path is
+    /// returned as-is.
+    pub(crate) fn absolute_href(&self, href: &str) -> Result<String> {
+        let path = self.path(href)?;
+        self.path_href(&path)
+    }
+
     fn path(&self, href: &str) -> Result<Path> {
         let result = if stac::href::is_windows_absolute_path(href) {
             Path::parse(href)
@@ -279,7 +824,12 @@ where
 {
     fn from(store: T) -> Self {
         let store: Arc<dyn ObjectStore> = store.into();
-        StacStore { store, root: None }
+        StacStore {
+            store,
+            root: None,
+            retry_policy: RetryPolicy::default(),
+            cache: None,
+        }
     }
 }
 
@@ -307,7 +857,8 @@ pub mod geoparquet {
             options: Options,
             writer_options: WriterOptions,
         ) -> Result<StacGeoparquetObjectWriter> {
-            let (state, record_batch) = WriterState::new(options, items)?;
+            let (mut state, record_batch) = WriterState::new(options, items)?;
+            state.set_bbox_covering(writer_options.bbox_covering);
             let object_store_writer = ParquetObjectWriter::new(store.clone(), path);
             let mut writer = AsyncArrowWriter::try_new(
                 object_store_writer,
@@ -331,6 +882,19 @@ pub mod geoparquet {
             self.state.add_collection(collection);
         }
 
+        /// Adds custom key-value metadata to this writer.
+        ///
+        /// Returns an error if the key is reserved, see
+        /// [RESERVED_METADATA_KEYS](stac::geoparquet::RESERVED_METADATA_KEYS).
+        pub fn add_key_value_metadata(
+            &mut self,
+            key: impl ToString,
+            value: impl ToString,
+        ) -> Result<()> {
+            self.state.add_key_value_metadata(key, value)?;
+            Ok(())
+        }
+
         pub async fn close(mut self) -> Result<()> {
             let metadata = self.state.into_metadata()?;
             for kv in metadata {
@@ -358,6 +922,61 @@ mod tests {
         assert!(self_href.ends_with("examples/simple-item.json"));
     }
 
+    #[tokio::test]
+    async fn get_opts() {
+        let item: Item = super::get_opts("examples/simple-item.json", [] as [(&str, &str); 0])
+            .await
+            .unwrap();
+        assert!(
+            item.self_href()
+                .unwrap()
+                .ends_with("examples/simple-item.json")
+        );
+    }
+
+    #[tokio::test]
+    async fn put_and_get() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let href = tempdir
+            .path()
+            .join("item.json")
+            .to_string_lossy()
+            .into_owned();
+        let _ = super::put(&href, Item::new("an-id")).await.unwrap();
+        let item: Item = super::get(&href).await.unwrap();
+        assert_eq!(item.id, "an-id");
+    }
+
+    #[tokio::test]
+    async fn get_with_cache() {
+        use crate::cache::{Cache, CacheEntry, FileSystemCache};
+        use object_store::ObjectStoreExt;
+
+        let tempdir = tempfile::tempdir().unwrap();
+        let href = tempdir
+            .path()
+            .join("item.json")
+            .to_string_lossy()
+            .into_owned();
+        let _ = super::put(&href, Item::new("an-id")).await.unwrap();
+        let cache = Arc::new(FileSystemCache::new(tempdir.path().join("cache")));
+        let (store, path) = super::parse_href(&href).unwrap();
+        let store = store.cache(cache.clone());
+        let item: Item = store.get(path.clone()).await.unwrap();
+        assert_eq!(item.id, "an-id");
+
+        let key = store.path_href(&path).unwrap();
+        let entry = cache.get(&key).await.unwrap().unwrap();
+        let meta = store.store.head(&path).await.unwrap();
+        assert_eq!(entry.e_tag, meta.e_tag);
+        let bytes = serde_json::to_vec(&Item::new("cached-id")).unwrap();
+        let mut cached = CacheEntry::new(bytes.into()).last_modified(meta.last_modified);
+        cached.e_tag = meta.e_tag;
+        cache.put(&key, &cached).await.unwrap();
+        let item: Item = store.get(path).await.unwrap();
+        assert_eq!(item.id, "cached-id");
+    }
+
     #[tokio::test]
     async fn get_local_href() {
         let (store, path) = super::parse_href("examples/simple-item.json").unwrap();
@@ -365,6 +984,211 @@ mod tests {
         let _: Item = store.get(href).await.unwrap();
     }
 
+    #[tokio::test]
+    #[cfg(feature = "geoparquet")]
+    async fn read_chunks() {
+        use futures::TryStreamExt;
+
+        let (store, path) = super::parse_href("data/extended-item.parquet").unwrap();
+        let item_collections: Vec<_> = store
+            .read_chunks(path)
+            .await
+            .unwrap()
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(item_collections.len(), 1);
+        assert_eq!(item_collections[0].items.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn head_ndjson() {
+        let (store, path) = super::parse_href("data/items.ndjson").unwrap();
+        let items = store
+            .head(path.clone(), crate::Format::NdJson, 1)
+            .await
+            .unwrap();
+        assert_eq!(items.len(), 1);
+        let items = store
+            .head(path, crate::Format::NdJson, usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(items.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn create_item() {
+        let (store, path) = super::parse_href("../core/assets/dataset_geo.tif").unwrap();
+        let item = store.create_item(path).await.unwrap();
+        assert_eq!(item.id, "dataset_geo");
+        assert_eq!(item.properties.additional_fields["proj:code"], "EPSG:32621");
+
+        // The tags of this (non-cloud-optimized) GeoTIFF are at the end of the file.
+        let (store, path) = super::parse_href("../core/assets/dataset.tif").unwrap();
+        let item = store.create_item(path).await.unwrap();
+        assert_eq!(item.assets["data"].bands.len(), 1);
+
+        let (store, path) = super::parse_href("data/items.ndjson").unwrap();
+        let _ = store.create_item(path).await.unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn head_json() {
+        let (store, path) = super::parse_href("data/item-collection.json").unwrap();
+        let items = store.head(path, crate::Format::json(), 1).await.unwrap();
+        assert_eq!(items.len(), 1);
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "geoparquet")]
+    async fn head_geoparquet() {
+        let (store, path) = super::parse_href("data/extended-item.parquet").unwrap();
+        let items = store
+            .head(path, crate::Format::geoparquet(), 5)
+            .await
+            .unwrap();
+        assert_eq!(items.len(), 1);
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "geoparquet")]
+    async fn append_geoparquet() {
+        use object_store::{ObjectStore, ObjectStoreExt};
+
+        let store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let mut buf = Vec::new();
+        stac::geoparquet::into_writer(&mut buf, vec![Item::new("a")]).unwrap();
+        let _ = store
+            .put(&Path::from("items.parquet"), buf.into())
+            .await
+            .unwrap();
+        let stac_store = super::StacStore::from(store.clone());
+        let _ = stac_store
+            .append_geoparquet("items.parquet", vec![Item::new("b")], Default::default())
+            .await
+            .unwrap();
+        let item_collection: stac::ItemCollection = stac_store.get("items.parquet").await.unwrap();
+        assert_eq!(item_collection.items.len(), 2);
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "geoparquet")]
+    async fn put_geoparquet() {
+        use object_store::ObjectStore;
+
+        let store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let stac_store = super::StacStore::from(store);
+        let _ = stac_store
+            .put_geoparquet(
+                "items.parquet",
+                vec![Item::new("a")],
+                Default::default(),
+                vec![("pipeline:version".to_string(), "1.2.3".to_string())],
+            )
+            .await
+            .unwrap();
+        let info = stac_store.geoparquet_info("items.parquet").await.unwrap();
+        assert_eq!(info.num_items, 1);
+        assert_eq!(info.key_value_metadata["pipeline:version"], "1.2.3");
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "geoparquet")]
+    async fn geoparquet_info() {
+        use object_store::{ObjectStore, ObjectStoreExt};
+        use stac::geoparquet::WriterBuilder;
+
+        let store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let mut buf = Vec::new();
+        WriterBuilder::new(&mut buf)
+            .key_value_metadata("pipeline:version", "1.2.3")
+            .build(vec![Item::new("a")])
+            .unwrap()
+            .finish()
+            .unwrap();
+        let _ = store
+            .put(&Path::from("items.parquet"), buf.into())
+            .await
+            .unwrap();
+        let stac_store = super::StacStore::from(store);
+        let info = stac_store.geoparquet_info("items.parquet").await.unwrap();
+        assert_eq!(info.num_items, 1);
+        assert_eq!(info.key_value_metadata["pipeline:version"], "1.2.3");
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "geoparquet")]
+    async fn upsert_geoparquet() {
+        use object_store::{ObjectStore, ObjectStoreExt};
+
+        let store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let mut buf = Vec::new();
+        stac::geoparquet::into_writer(&mut buf, vec![Item::new("a"), Item::new("b")]).unwrap();
+        let _ = store
+            .put(&Path::from("items.parquet"), buf.into())
+            .await
+            .unwrap();
+        let stac_store = super::StacStore::from(store.clone());
+        let summary = stac_store
+            .upsert_geoparquet(
+                "items.parquet",
+                "items-v2.parquet",
+                vec![Item::new("c")],
+                vec!["a".to_string()],
+                Default::default(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(summary.deleted, vec!["a"]);
+        let item_collection: stac::ItemCollection = stac_store.get("items.parquet").await.unwrap();
+        assert_eq!(item_collection.items.len(), 2);
+        let item_collection: stac::ItemCollection =
+            stac_store.get("items-v2.parquet").await.unwrap();
+        assert_eq!(
+            item_collection
+                .items
+                .iter()
+                .map(|item| item.id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["b", "c"]
+        );
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "geoparquet")]
+    async fn upsert_geoparquet_to() {
+        use object_store::{ObjectStore, ObjectStoreExt};
+
+        let store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let mut buf = Vec::new();
+        stac::geoparquet::into_writer(&mut buf, vec![Item::new("a")]).unwrap();
+        let _ = store
+            .put(&Path::from("items.parquet"), buf.into())
+            .await
+            .unwrap();
+        let stac_store = super::StacStore::from(store);
+        let to_store = super::StacStore::from(Arc::new(InMemory::new()) as Arc<dyn ObjectStore>);
+        let _ = stac_store
+            .upsert_geoparquet_to(
+                "items.parquet",
+                &to_store,
+                "items-v2.parquet",
+                vec![Item::new("b")],
+                Vec::new(),
+                Default::default(),
+            )
+            .await
+            .unwrap();
+        assert!(
+            stac_store
+                .get::<stac::ItemCollection>("items-v2.parquet")
+                .await
+                .is_err()
+        );
+        let item_collection: stac::ItemCollection = to_store.get("items-v2.parquet").await.unwrap();
+        assert_eq!(item_collection.items.len(), 2);
+    }
+
     #[tokio::test]
     #[cfg(feature = "geoparquet")]
     async fn write_parquet() {
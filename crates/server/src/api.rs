@@ -1,13 +1,29 @@
-use crate::{Backend, DEFAULT_DESCRIPTION, DEFAULT_ID, Error, Result};
+use crate::{
+    Backend, COLLECTION_WILDCARDS_URI, DEFAULT_DESCRIPTION, DEFAULT_ID, DEFAULT_LIMIT,
+    DEFAULT_MAX_GET_INTERSECTS_SIZE, DEFAULT_MAX_LIMIT, Error, OFFSET_PAGINATION_URI, Result,
+    TRANSACTION_URI,
+};
 use http::Method;
+use indexmap::IndexMap;
 use serde::Serialize;
 use serde_json::{Map, Value, json};
 use stac::api::{
-    Collections, CollectionsClient, Conformance, ItemCollection, Items, ItemsClient, Root, Search,
+    COLLECTION_SEARCH_FREE_TEXT_URI, CollectionSearch, Collections, CollectionsClient, Conformance,
+    GetCollectionSearch, ItemCollection, Items, ItemsClient, Root, Search,
+};
+use stac::{Catalog, Collection, Item, Link, Links, mime::APPLICATION_OPENAPI_3_0};
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
-use stac::{Catalog, Collection, Fields, Item, Link, Links, mime::APPLICATION_OPENAPI_3_0};
 use url::Url;
 
+const STATS_EXTENSION_URI: &str = "https://stac-extensions.github.io/stats/v0.2.0/schema.json";
+
+/// The pagination field that records when a pagination link was issued, in
+/// seconds since the unix epoch.
+const TOKEN_ISSUED_KEY: &str = "token_issued";
+
 /// A STAC server API.
 #[derive(Clone, Debug)]
 pub struct Api<B: Backend> {
@@ -20,8 +36,77 @@ pub struct Api<B: Backend> {
     /// The catalog id of this API.
     pub id: String,
 
+    /// The title of this API.
+    pub title: Option<String>,
+
+    /// Additional links for the landing page, e.g. `license` or `about`
+    /// links.
+    pub links: Vec<Link>,
+
+    /// Additional conformance classes, e.g. for extensions that are
+    /// implemented outside of the backend.
+    pub conforms_to: Vec<String>,
+
+    /// The API description document, served at `/api`.
+    pub service_desc: Document,
+
+    /// The API documentation, served at `/api.html`.
+    pub service_doc: Document,
+
+    /// Static assets, served at `/assets/{path}`, keyed by path.
+    pub assets: IndexMap<String, Document>,
+
     /// The root url of this API.
     pub root: Url,
+
+    /// The maximum size, in bytes, of an intersects geometry in GET pagination links.
+    ///
+    /// If a GET search's intersects is larger than this, its pagination links
+    /// will use POST instead.
+    pub max_get_intersects_size: usize,
+
+    /// The limit used for item listings and searches that don't set one.
+    pub default_limit: u64,
+
+    /// The largest limit that item listings and searches can ask for.
+    ///
+    /// Requests with a larger limit are errors, which the routes return as
+    /// 400s.
+    pub max_limit: u64,
+
+    /// How long pagination links stay valid.
+    ///
+    /// If set, `next` and `prev` links record when they were issued, and
+    /// following one that's older than this is an error, which the routes
+    /// return as a 400. Pages without that record, e.g. a hand-written
+    /// `offset`, aren't checked.
+    pub token_ttl: Option<Duration>,
+
+    /// The coordinate reference systems accepted as `bbox-crs`.
+    ///
+    /// These always include CRS84. Responses are never reprojected, so
+    /// collections only advertise CRS84 as their `crs`.
+    pub crs: Vec<String>,
+
+    /// Whether to localize responses to the request's `Accept-Language`
+    /// header.
+    ///
+    /// See [stac::language] for how localized fields are stored. The
+    /// localization is done by the `routes::negotiate_language` middleware.
+    pub language_negotiation: bool,
+
+    /// Whether to add item counts and freshly computed extents to
+    /// collections.
+    ///
+    /// This uses [Backend::collection_statistics], which can be expensive, so
+    /// it's off by default.
+    pub collection_statistics: bool,
+
+    /// Whether clients can add items with the POST
+    /// `/collections/{collectionId}/items` endpoint.
+    ///
+    /// Off by default, so an API is read-only unless asked otherwise.
+    pub transactions: bool,
 }
 
 impl<B: Backend> Api<B> {
@@ -36,12 +121,28 @@ impl<B: Backend> Api<B> {
     /// let api = Api::new(backend, "http://stac.test").unwrap();
     /// ```
     pub fn new(backend: B, root: &str) -> Result<Api<B>> {
-        Ok(Api {
-            backend,
-            id: DEFAULT_ID.to_string(),
-            description: DEFAULT_DESCRIPTION.to_string(),
-            root: root.parse()?,
-        })
+        Api::builder(backend, root).build()
+    }
+
+    /// Creates a builder for an API with the given backend.
+    ///
+    /// Use the builder to customize the landing page and the documents that
+    /// the API serves.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_server::{Api, MemoryBackend};
+    ///
+    /// let api = Api::builder(MemoryBackend::new(), "http://stac.test")
+    ///     .id("my-api")
+    ///     .title("My API")
+    ///     .license("https://creativecommons.org/licenses/by/4.0/")
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn builder(backend: B, root: &str) -> ApiBuilder<B> {
+        ApiBuilder::new(backend, root)
     }
 
     /// Sets this API's id.
@@ -74,6 +175,266 @@ impl<B: Backend> Api<B> {
         self
     }
 
+    /// Sets the maximum size, in bytes, of an intersects geometry in GET pagination links.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_server::{Api, MemoryBackend};
+    ///
+    /// let backend = MemoryBackend::new();
+    /// let api = Api::new(backend, "http://stac.test")
+    ///     .unwrap()
+    ///     .max_get_intersects_size(1024);
+    /// ```
+    pub fn max_get_intersects_size(mut self, max_get_intersects_size: usize) -> Api<B> {
+        self.max_get_intersects_size = max_get_intersects_size;
+        self
+    }
+
+    /// Sets the limit used for item listings and searches that don't set one.
+    ///
+    /// The default limit is never larger than the [max limit](Api::max_limit).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_server::{Api, MemoryBackend};
+    ///
+    /// let backend = MemoryBackend::new();
+    /// let api = Api::new(backend, "http://stac.test").unwrap().default_limit(100);
+    /// ```
+    pub fn default_limit(mut self, default_limit: u64) -> Api<B> {
+        self.default_limit = default_limit;
+        self
+    }
+
+    /// Sets the largest limit that item listings and searches can ask for.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_server::{Api, MemoryBackend};
+    ///
+    /// let backend = MemoryBackend::new();
+    /// let api = Api::new(backend, "http://stac.test").unwrap().max_limit(1000);
+    /// ```
+    pub fn max_limit(mut self, max_limit: u64) -> Api<B> {
+        self.max_limit = max_limit;
+        self
+    }
+
+    /// Sets how long pagination links stay valid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use stac_server::{Api, MemoryBackend};
+    ///
+    /// let backend = MemoryBackend::new();
+    /// let api = Api::new(backend, "http://stac.test")
+    ///     .unwrap()
+    ///     .token_ttl(Duration::from_secs(3600));
+    /// ```
+    pub fn token_ttl(mut self, token_ttl: Duration) -> Api<B> {
+        self.token_ttl = Some(token_ttl);
+        self
+    }
+
+    /// Sets whether responses are localized to the request's
+    /// `Accept-Language` header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_server::{Api, MemoryBackend};
+    ///
+    /// let backend = MemoryBackend::new();
+    /// let api = Api::new(backend, "http://stac.test")
+    ///     .unwrap()
+    ///     .language_negotiation(true);
+    /// ```
+    pub fn language_negotiation(mut self, language_negotiation: bool) -> Api<B> {
+        self.language_negotiation = language_negotiation;
+        self
+    }
+
+    /// Sets whether collections include item counts and extents computed from
+    /// their items.
+    ///
+    /// The count is added as `stats:items` from the [stats
+    /// extension](https://github.com/stac-extensions/stats). Backends that
+    /// can't compute these cheaply leave their collections as they are.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_server::{Api, MemoryBackend};
+    ///
+    /// let backend = MemoryBackend::new();
+    /// let api = Api::new(backend, "http://stac.test")
+    ///     .unwrap()
+    ///     .collection_statistics(true);
+    /// ```
+    pub fn collection_statistics(mut self, collection_statistics: bool) -> Api<B> {
+        self.collection_statistics = collection_statistics;
+        self
+    }
+
+    /// Sets whether clients can add items to collections.
+    ///
+    /// This turns on the POST `/collections/{collectionId}/items` endpoint
+    /// and advertises the [transaction
+    /// extension](https://github.com/stac-api-extensions/transaction)'s
+    /// conformance class. Read-only backends still refuse the writes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_server::{Api, MemoryBackend, TRANSACTION_URI};
+    ///
+    /// let api = Api::new(MemoryBackend::new(), "http://stac.test")
+    ///     .unwrap()
+    ///     .transactions(true);
+    /// assert!(api.conformance().conforms_to.contains(&TRANSACTION_URI.to_string()));
+    /// ```
+    pub fn transactions(mut self, transactions: bool) -> Api<B> {
+        self.transactions = transactions;
+        self
+    }
+
+    /// Adds coordinate reference systems that are accepted as `bbox-crs`.
+    ///
+    /// Anything other than CRS84 requires the `proj` feature, and crs values
+    /// that can't be reprojected are skipped with a warning.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_server::{Api, MemoryBackend};
+    ///
+    /// let backend = MemoryBackend::new();
+    /// let api = Api::new(backend, "http://stac.test")
+    ///     .unwrap()
+    ///     .crs(["http://www.opengis.net/def/crs/EPSG/0/3857"]);
+    /// ```
+    pub fn crs(mut self, crs: impl IntoIterator<Item = impl ToString>) -> Api<B> {
+        for crs in crs {
+            let crs = crs.to_string();
+            if !stac::crs::is_supported(&crs) {
+                tracing::warn!("unsupported crs, skipping: {crs}");
+            } else if !self.crs.contains(&crs) {
+                self.crs.push(crs);
+            }
+        }
+        self
+    }
+
+    /// Sets the limit of an item listing or search, if it doesn't have one,
+    /// and checks it against the max limit.
+    ///
+    /// [Api::items] and [Api::search] do this themselves, so every backend
+    /// sees the same limits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::api::Items;
+    /// use stac_server::{Api, MemoryBackend};
+    ///
+    /// let api = Api::new(MemoryBackend::new(), "http://stac.test")
+    ///     .unwrap()
+    ///     .max_limit(100);
+    /// let mut items = Items::default();
+    /// api.limit(&mut items).unwrap();
+    /// assert_eq!(items.limit, Some(10));
+    /// items.limit = Some(1000);
+    /// assert!(api.limit(&mut items).is_err());
+    /// ```
+    pub fn limit(&self, items: &mut Items) -> Result<()> {
+        match items.limit {
+            Some(limit) if limit > self.max_limit => Err(Error::LimitTooLarge {
+                limit,
+                max_limit: self.max_limit,
+            }),
+            Some(_) => Ok(()),
+            None => {
+                items.limit = Some(self.default_limit.min(self.max_limit));
+                Ok(())
+            }
+        }
+    }
+
+    /// Checks that an item listing or search's `bbox-crs` and `crs` are
+    /// among this API's [crs](Api::crs).
+    ///
+    /// The routes do this before reprojecting the bbox, so a crs that isn't
+    /// advertised is rejected even if it could be reprojected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::api::Items;
+    /// use stac_server::{Api, MemoryBackend};
+    ///
+    /// let api = Api::new(MemoryBackend::new(), "http://stac.test").unwrap();
+    /// let mut items = Items {
+    ///     bbox_crs: Some(stac::crs::CRS84.to_string()),
+    ///     ..Default::default()
+    /// };
+    /// api.check_crs(&items).unwrap();
+    /// items.bbox_crs = Some("http://www.opengis.net/def/crs/EPSG/0/3857".to_string());
+    /// assert!(api.check_crs(&items).is_err());
+    /// ```
+    pub fn check_crs(&self, items: &Items) -> Result<()> {
+        for crs in [items.bbox_crs.as_deref(), items.crs.as_deref()]
+            .into_iter()
+            .flatten()
+        {
+            if !self.crs.iter().any(|supported| same_crs(supported, crs)) {
+                return Err(Error::UnsupportedCrs(crs.to_string()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks the age of an item listing or search's pagination token
+    /// against the token TTL.
+    ///
+    /// The issued time is removed, so backends never see it.
+    fn check_token(&self, items: &mut Items) -> Result<()> {
+        let Some(issued) = items.additional_fields.remove(TOKEN_ISSUED_KEY) else {
+            return Ok(());
+        };
+        let Some(ttl) = self.token_ttl else {
+            return Ok(());
+        };
+        let issued = issued
+            .as_u64()
+            .or_else(|| issued.as_str().and_then(|s| s.parse().ok()))
+            .ok_or_else(|| Error::InvalidToken(issued.to_string()))?;
+        let age = now().saturating_sub(issued);
+        if age > ttl.as_secs() {
+            Err(Error::TokenExpired {
+                age,
+                ttl: ttl.as_secs(),
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Records when a pagination token was issued, if tokens can expire.
+    fn issue_token(&self, items: &mut Items, pagination: Map<String, Value>) {
+        items.set_pagination(pagination);
+        if self.token_ttl.is_some() {
+            let _ = items
+                .additional_fields
+                .insert(TOKEN_ISSUED_KEY.to_string(), now().into());
+        }
+    }
+
     fn url(&self, path: &str) -> Result<Url> {
         self.root.join(path).map_err(Error::from)
     }
@@ -92,15 +453,18 @@ impl<B: Backend> Api<B> {
     /// ```
     pub async fn root(&self) -> Result<Root> {
         let mut catalog = Catalog::new(&self.id, &self.description);
+        catalog.title = self.title.clone();
         catalog.set_link(Link::root(self.root.clone()).json());
         catalog.set_link(Link::self_(self.root.clone()).json());
         catalog.set_link(
             Link::new(self.url("/api")?, "service-desc")
-                .r#type(APPLICATION_OPENAPI_3_0.to_string()),
+                .r#type(self.service_desc.content_type.clone()),
         );
         catalog.set_link(
-            Link::new(self.url("/api.html")?, "service-doc").r#type("text/html".to_string()),
+            Link::new(self.url("/api.html")?, "service-doc")
+                .r#type(self.service_doc.content_type.clone()),
         );
+        catalog.links.extend(self.links.iter().cloned());
         catalog.set_link(Link::new(self.url("/conformance")?, "conformance").json());
         catalog.set_link(Link::new(self.url("/collections")?, "data").json());
         for collection in self.backend.collections().await? {
@@ -144,28 +508,94 @@ impl<B: Backend> Api<B> {
     /// ```
     pub fn conformance(&self) -> Conformance {
         let mut conformance = Conformance::new().ogcapi_features();
+        if !self.crs.is_empty() {
+            conformance = conformance.crs();
+        }
         if self.backend.has_item_search() {
             conformance = conformance.item_search();
         }
         if self.backend.has_filter() {
             conformance = conformance.filter();
         }
+        if self.backend.has_sort() {
+            conformance = conformance.sort();
+        }
+        if self.backend.has_free_text() {
+            conformance = conformance.free_text();
+        }
+        conformance
+            .conforms_to
+            .push(COLLECTION_SEARCH_FREE_TEXT_URI.to_string());
+        if self.backend.has_collection_wildcards() {
+            conformance
+                .conforms_to
+                .push(COLLECTION_WILDCARDS_URI.to_string());
+        }
+        if self.backend.has_offset_pagination() {
+            conformance
+                .conforms_to
+                .push(OFFSET_PAGINATION_URI.to_string());
+        }
+        if self.transactions {
+            conformance.conforms_to.push(TRANSACTION_URI.to_string());
+        }
+        for conforms_to in &self.conforms_to {
+            if !conformance.conforms_to.contains(conforms_to) {
+                conformance.conforms_to.push(conforms_to.clone());
+            }
+        }
         conformance
     }
 
-    /// Returns queryables.
-    pub fn queryables(&self) -> Value {
-        // This is a pure punt from https://github.com/stac-api-extensions/filter?tab=readme-ov-file#queryables
-        json!({
-          "$schema" : "https://json-schema.org/draft/2019-09/schema",
-          "$id" : "https://stac-api.example.com/queryables",
-          "type" : "object",
-          "title" : "Queryables for Example STAC API",
-          "description" : "Queryable names for the example STAC API Item Search filter.",
-          "properties" : {
-          },
-          "additionalProperties": true
-        })
+    /// Returns the queryables for all collections.
+    ///
+    /// If the backend doesn't provide queryables, the returned schema allows
+    /// any property.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_server::{Api, MemoryBackend};
+    ///
+    /// let api = Api::new(MemoryBackend::new(), "http://stac.test").unwrap();
+    /// # tokio_test::block_on(async {
+    /// let queryables = api.queryables().await.unwrap();
+    /// assert_eq!(queryables["$id"], "http://stac.test/queryables");
+    /// # })
+    /// ```
+    pub async fn queryables(&self) -> Result<Value> {
+        let queryables = self.backend.queryables(None).await?;
+        Ok(with_queryables_id(queryables, self.url("/queryables")?))
+    }
+
+    /// Returns the queryables for a collection, or `None` if the collection
+    /// doesn't exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Collection, api::TransactionClient};
+    /// use stac_server::{Api, MemoryBackend};
+    ///
+    /// let mut backend = MemoryBackend::new();
+    /// # tokio_test::block_on(async {
+    /// backend.add_collection(Collection::new("an-id", "a description")).await.unwrap();
+    /// let api = Api::new(backend, "http://stac.test").unwrap();
+    /// let queryables = api.collection_queryables("an-id").await.unwrap().unwrap();
+    /// assert_eq!(queryables["$id"], "http://stac.test/collections/an-id/queryables");
+    /// assert!(api.collection_queryables("not-an-id").await.unwrap().is_none());
+    /// # })
+    /// ```
+    pub async fn collection_queryables(&self, collection_id: &str) -> Result<Option<Value>> {
+        if CollectionsClient::collection(&self.backend, collection_id)
+            .await?
+            .is_none()
+        {
+            return Ok(None);
+        }
+        let queryables = self.backend.queryables(Some(collection_id)).await?;
+        let url = self.url(&format!("/collections/{collection_id}/queryables"))?;
+        Ok(Some(with_queryables_id(queryables, url)))
     }
 
     /// Returns the collections from the backend.
@@ -181,11 +611,36 @@ impl<B: Backend> Api<B> {
     /// # })
     /// ```
     pub async fn collections(&self) -> Result<Collections> {
-        let mut collections: Collections = self.backend.collections().await?.into();
+        self.collection_search(CollectionSearch::default()).await
+    }
+
+    /// Returns the collections that match a [collection
+    /// search](https://github.com/stac-api-extensions/collection-search).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::api::CollectionSearch;
+    /// use stac_server::{Api, MemoryBackend};
+    ///
+    /// let api = Api::new(MemoryBackend::new(), "http://stac.test").unwrap();
+    /// # tokio_test::block_on(async {
+    /// let search = CollectionSearch::default().q("sentinel");
+    /// let collections = api.collection_search(search).await.unwrap();
+    /// # })
+    /// ```
+    pub async fn collection_search(&self, search: CollectionSearch) -> Result<Collections> {
+        let mut url = self.url("/collections")?;
+        let query = serde_urlencoded::to_string(GetCollectionSearch::from(search.clone()))?;
+        if !query.is_empty() {
+            url.set_query(Some(&query));
+        }
+        let mut collections: Collections = self.backend.collection_search(search).await?.into();
         collections.set_link(Link::root(self.root.clone()).json());
-        collections.set_link(Link::self_(self.url("/collections")?).json());
+        collections.set_link(Link::self_(url).json());
         for collection in collections.collections.iter_mut() {
             self.set_collection_links(collection)?;
+            self.set_collection_statistics(collection).await?;
         }
         Ok(collections)
     }
@@ -210,6 +665,7 @@ impl<B: Backend> Api<B> {
         match self.backend.collection(id).await? {
             Some(mut collection) => {
                 self.set_collection_links(&mut collection)?;
+                self.set_collection_statistics(&mut collection).await?;
                 Ok(Some(collection))
             }
             _ => Ok(None),
@@ -234,7 +690,13 @@ impl<B: Backend> Api<B> {
     /// assert_eq!(items.items.len(), 1);
     /// # })
     /// ```
-    pub async fn items(&self, collection_id: &str, items: Items) -> Result<Option<ItemCollection>> {
+    pub async fn items(
+        &self,
+        collection_id: &str,
+        mut items: Items,
+    ) -> Result<Option<ItemCollection>> {
+        self.limit(&mut items)?;
+        self.check_token(&mut items)?;
         if CollectionsClient::collection(&self.backend, collection_id)
             .await?
             .is_none()
@@ -249,19 +711,21 @@ impl<B: Backend> Api<B> {
         item_collection.set_link(Link::self_(items_url.clone()).geojson());
         item_collection.set_link(Link::collection(collection_url).json());
         if let Some(next) = item_collection.next.take() {
+            let mut items = items.clone();
+            self.issue_token(&mut items, next);
             item_collection.set_link(self.pagination_link(
                 items_url.clone(),
-                items.clone(),
-                next,
+                items,
                 "next",
                 &Method::GET,
             )?);
         }
         if let Some(prev) = item_collection.prev.take() {
+            let mut items = items;
+            self.issue_token(&mut items, prev);
             item_collection.set_link(self.pagination_link(
                 items_url,
                 items,
-                prev,
                 "prev",
                 &Method::GET,
             )?);
@@ -323,7 +787,10 @@ impl<B: Backend> Api<B> {
     /// # })
     /// ```
     pub async fn search(&self, mut search: Search, method: Method) -> Result<ItemCollection> {
+        self.limit(&mut search.items)?;
+        self.check_token(&mut search.items)?;
         let mut item_collection = self.backend.search(search.clone()).await?;
+        let method = self.pagination_method(&search, method)?;
         if method == Method::GET
             && let Some(filter) = search.filter.take()
         {
@@ -333,18 +800,19 @@ impl<B: Backend> Api<B> {
         let search_url = self.url("/search")?;
         if let Some(next) = item_collection.next.take() {
             tracing::debug!("adding next pagination link");
+            let mut search = search.clone();
+            self.issue_token(&mut search.items, next);
             item_collection.set_link(self.pagination_link(
                 search_url.clone(),
-                search.clone(),
-                next,
+                search,
                 "next",
                 &method,
             )?);
         }
         if let Some(prev) = item_collection.prev.take() {
             tracing::debug!("adding prev pagination link");
-            item_collection
-                .set_link(self.pagination_link(search_url, search, prev, "prev", &method)?);
+            self.issue_token(&mut search.items, prev);
+            item_collection.set_link(self.pagination_link(search_url, search, "prev", &method)?);
         }
         for item in item_collection.items.iter_mut() {
             self.set_item_links(item)?;
@@ -352,88 +820,444 @@ impl<B: Backend> Api<B> {
         Ok(item_collection)
     }
 
-    fn set_collection_links(&self, collection: &mut Collection) -> Result<()> {
-        collection.set_link(Link::root(self.root.clone()).json());
-        collection
-            .set_link(Link::self_(self.url(&format!("/collections/{}", collection.id))?).json());
-        collection.set_link(Link::parent(self.root.clone()).json());
-        collection.set_link(
-            Link::new(
-                self.url(&format!("/collections/{}/items", collection.id))?,
-                "items",
-            )
-            .geojson(),
-        );
-        Ok(())
+    fn set_collection_links(&self, collection: &mut Collection) -> Result<()> {
+        collection.set_link(Link::root(self.root.clone()).json());
+        collection
+            .set_link(Link::self_(self.url(&format!("/collections/{}", collection.id))?).json());
+        collection.set_link(Link::parent(self.root.clone()).json());
+        collection.set_link(
+            Link::new(
+                self.url(&format!("/collections/{}/items", collection.id))?,
+                "items",
+            )
+            .geojson(),
+        );
+        if self.backend.has_filter() {
+            collection.set_link(
+                Link::new(
+                    self.url(&format!("/collections/{}/queryables", collection.id))?,
+                    "http://www.opengis.net/def/rel/ogc/1.0/queryables",
+                )
+                .r#type("application/schema+json".to_string()),
+            );
+        }
+        // Only the output crs, which is always CRS84, goes in `crs`.
+        let _ = collection
+            .additional_fields
+            .insert("crs".to_string(), vec![stac::crs::CRS84].into());
+        let _ = collection
+            .additional_fields
+            .insert("storageCrs".to_string(), stac::crs::CRS84.into());
+        Ok(())
+    }
+
+    async fn set_collection_statistics(&self, collection: &mut Collection) -> Result<()> {
+        if !self.collection_statistics {
+            return Ok(());
+        }
+        if let Some(statistics) = self.backend.collection_statistics(&collection.id).await? {
+            let _ = collection.additional_fields.insert(
+                "stats:items".to_string(),
+                json!({"count": statistics.item_count}),
+            );
+            if !collection
+                .extensions
+                .iter()
+                .any(|e| e == STATS_EXTENSION_URI)
+            {
+                collection.extensions.push(STATS_EXTENSION_URI.to_string());
+            }
+            if let Some(extent) = statistics.extent {
+                collection.extent = extent;
+            }
+        }
+        Ok(())
+    }
+
+    fn pagination_method(&self, search: &Search, method: Method) -> Result<Method> {
+        if method == Method::GET && search.intersects_size()? > self.max_get_intersects_size {
+            tracing::debug!("intersects is too large for GET pagination links, using POST");
+            Ok(Method::POST)
+        } else {
+            Ok(method)
+        }
+    }
+
+    fn pagination_link<D>(&self, mut url: Url, data: D, rel: &str, method: &Method) -> Result<Link>
+    where
+        D: Serialize,
+    {
+        match *method {
+            Method::GET => {
+                url.set_query(Some(&serde_urlencoded::to_string(data)?));
+                Ok(Link::new(url, rel).geojson().method("GET"))
+            }
+            Method::POST => Ok(Link::new(url, rel).geojson().method("POST").body(data)?),
+            _ => unimplemented!(),
+        }
+    }
+
+    fn set_item_links(&self, item: &mut stac::api::Item) -> Result<()> {
+        let mut collection_url = None;
+        let mut item_link = None;
+        if let Some(item_id) = item.get("id").and_then(|id| id.as_str())
+            && let Some(collection_id) = item.get("collection").and_then(|id| id.as_str())
+        {
+            collection_url = Some(self.url(&format!("/collections/{collection_id}"))?);
+            item_link = Some(serde_json::to_value(
+                Link::self_(self.url(&format!("/collections/{collection_id}/items/{item_id}"))?)
+                    .geojson(),
+            )?);
+        }
+        if item
+            .get("links")
+            .map(|links| !links.is_array())
+            .unwrap_or(true)
+        {
+            let _ = item.insert("links".to_string(), Value::Array(Vec::new()));
+        }
+        let links = item.get_mut("links").unwrap().as_array_mut().unwrap();
+        links.push(serde_json::to_value(Link::root(self.root.clone()).json())?);
+        if let Some(item_link) = item_link {
+            links.push(item_link);
+        }
+        if let Some(collection_url) = collection_url {
+            links.push(serde_json::to_value(
+                Link::collection(collection_url.clone()).json(),
+            )?);
+            links.push(serde_json::to_value(Link::parent(collection_url).json())?);
+        }
+        Ok(())
+    }
+}
+
+/// Sets the `$id` of a queryables schema, or returns a schema that allows any
+/// property if there isn't one.
+/// A builder for an [Api].
+///
+/// # Examples
+///
+/// ```
+/// use stac_server::{Api, MemoryBackend};
+///
+/// let api = Api::builder(MemoryBackend::new(), "http://stac.test")
+///     .title("My API")
+///     .description("Imagery for everyone")
+///     .conforms_to("https://api.stacspec.org/v1.0.0/ogcapi-features/extensions/transaction")
+///     .service_doc("<html><body>My API</body></html>")
+///     .build()
+///     .unwrap();
+/// assert_eq!(api.title.as_deref(), Some("My API"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct ApiBuilder<B: Backend> {
+    backend: B,
+    root: String,
+    id: String,
+    description: String,
+    title: Option<String>,
+    links: Vec<Link>,
+    conforms_to: Vec<String>,
+    service_desc: Document,
+    service_doc: Document,
+    assets: IndexMap<String, Document>,
+}
+
+/// A static document served by an [Api].
+///
+/// The body is reference-counted so the [Api] stays cheap to clone.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Document {
+    /// The media type of this document, used as its `Content-Type`.
+    pub content_type: String,
+
+    /// The contents of this document.
+    pub body: Arc<[u8]>,
+}
+
+impl<B: Backend> ApiBuilder<B> {
+    /// Creates a new builder with the given backend and root url.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_server::{ApiBuilder, MemoryBackend};
+    ///
+    /// let builder = ApiBuilder::new(MemoryBackend::new(), "http://stac.test");
+    /// ```
+    pub fn new(backend: B, root: &str) -> ApiBuilder<B> {
+        ApiBuilder {
+            backend,
+            root: root.to_string(),
+            id: DEFAULT_ID.to_string(),
+            description: DEFAULT_DESCRIPTION.to_string(),
+            title: None,
+            links: Vec::new(),
+            conforms_to: Vec::new(),
+            service_desc: Document::new(APPLICATION_OPENAPI_3_0, include_str!("openapi.yaml")),
+            service_doc: Document::new("text/html", include_str!("redoc.html")),
+            assets: IndexMap::new(),
+        }
+    }
+
+    /// Sets the catalog id of the landing page.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_server::{Api, MemoryBackend};
+    ///
+    /// let builder = Api::builder(MemoryBackend::new(), "http://stac.test").id("my-api");
+    /// ```
+    pub fn id(mut self, id: impl ToString) -> ApiBuilder<B> {
+        self.id = id.to_string();
+        self
+    }
+
+    /// Sets the title of the landing page.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_server::{Api, MemoryBackend};
+    ///
+    /// let builder = Api::builder(MemoryBackend::new(), "http://stac.test").title("My API");
+    /// ```
+    pub fn title(mut self, title: impl ToString) -> ApiBuilder<B> {
+        self.title = Some(title.to_string());
+        self
+    }
+
+    /// Sets the description of the landing page.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_server::{Api, MemoryBackend};
+    ///
+    /// let builder = Api::builder(MemoryBackend::new(), "http://stac.test")
+    ///     .description("Imagery for everyone");
+    /// ```
+    pub fn description(mut self, description: impl ToString) -> ApiBuilder<B> {
+        self.description = description.to_string();
+        self
+    }
+
+    /// Adds a `license` link to the landing page.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_server::{Api, MemoryBackend};
+    ///
+    /// let builder = Api::builder(MemoryBackend::new(), "http://stac.test")
+    ///     .license("https://creativecommons.org/licenses/by/4.0/");
+    /// ```
+    pub fn license(self, href: impl ToString) -> ApiBuilder<B> {
+        self.link(Link::new(href.to_string(), "license"))
+    }
+
+    /// Adds a link to the landing page.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::Link;
+    /// use stac_server::{Api, MemoryBackend};
+    ///
+    /// let builder = Api::builder(MemoryBackend::new(), "http://stac.test")
+    ///     .link(Link::new("https://example.com/about", "about").r#type("text/html".to_string()));
+    /// ```
+    pub fn link(mut self, link: Link) -> ApiBuilder<B> {
+        self.links.push(link);
+        self
+    }
+
+    /// Adds a conformance class, in addition to the ones the backend
+    /// supports.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_server::{Api, MemoryBackend};
+    ///
+    /// let builder = Api::builder(MemoryBackend::new(), "http://stac.test")
+    ///     .conforms_to("https://api.stacspec.org/v1.0.0/ogcapi-features/extensions/transaction");
+    /// ```
+    pub fn conforms_to(mut self, conforms_to: impl ToString) -> ApiBuilder<B> {
+        let conforms_to = conforms_to.to_string();
+        if !self.conforms_to.contains(&conforms_to) {
+            self.conforms_to.push(conforms_to);
+        }
+        self
+    }
+
+    /// Sets the API description document served at `/api`, e.g. an OpenAPI
+    /// definition.
+    ///
+    /// The content type is used for the landing page's `service-desc` link,
+    /// too.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_server::{Api, MemoryBackend};
+    ///
+    /// let builder = Api::builder(MemoryBackend::new(), "http://stac.test").service_desc(
+    ///     "application/vnd.oai.openapi+json;version=3.0",
+    ///     r#"{"openapi": "3.0.0"}"#,
+    /// );
+    /// ```
+    pub fn service_desc(
+        mut self,
+        content_type: impl ToString,
+        body: impl Into<Vec<u8>>,
+    ) -> ApiBuilder<B> {
+        self.service_desc = Document::new(content_type, body);
+        self
+    }
+
+    /// Sets the HTML documentation served at `/api.html`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_server::{Api, MemoryBackend};
+    ///
+    /// let builder = Api::builder(MemoryBackend::new(), "http://stac.test")
+    ///     .service_doc("<html><body>My API</body></html>");
+    /// ```
+    pub fn service_doc(mut self, html: impl Into<Vec<u8>>) -> ApiBuilder<B> {
+        self.service_doc = Document::new("text/html", html);
+        self
+    }
+
+    /// Adds a static asset, served at `/assets/{path}`.
+    ///
+    /// This is useful for stylesheets, scripts, and images used by the
+    /// [service doc](ApiBuilder::service_doc).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_server::{Api, MemoryBackend};
+    ///
+    /// let builder = Api::builder(MemoryBackend::new(), "http://stac.test")
+    ///     .asset("style.css", "text/css", "body { font-family: sans-serif; }");
+    /// ```
+    pub fn asset(
+        mut self,
+        path: impl ToString,
+        content_type: impl ToString,
+        body: impl Into<Vec<u8>>,
+    ) -> ApiBuilder<B> {
+        let path = path.to_string().trim_start_matches('/').to_string();
+        let _ = self.assets.insert(path, Document::new(content_type, body));
+        self
     }
 
-    fn pagination_link<D>(
-        &self,
-        mut url: Url,
-        mut data: D,
-        pagination: Map<String, Value>,
-        rel: &str,
-        method: &Method,
-    ) -> Result<Link>
-    where
-        D: Fields + Serialize,
-    {
-        for (key, value) in pagination {
-            let _ = data.set_field(key, value)?;
-        }
-        match *method {
-            Method::GET => {
-                url.set_query(Some(&serde_urlencoded::to_string(data)?));
-                Ok(Link::new(url, rel).geojson().method("GET"))
-            }
-            Method::POST => Ok(Link::new(url, rel).geojson().method("POST").body(data)?),
-            _ => unimplemented!(),
-        }
+    /// Builds the [Api].
+    ///
+    /// Returns an error if the root url can't be parsed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_server::{Api, MemoryBackend};
+    ///
+    /// let api = Api::builder(MemoryBackend::new(), "http://stac.test").build().unwrap();
+    /// assert!(Api::builder(MemoryBackend::new(), "not a url").build().is_err());
+    /// ```
+    pub fn build(self) -> Result<Api<B>> {
+        Ok(Api {
+            backend: self.backend,
+            id: self.id,
+            description: self.description,
+            title: self.title,
+            links: self.links,
+            conforms_to: self.conforms_to,
+            service_desc: self.service_desc,
+            service_doc: self.service_doc,
+            assets: self.assets,
+            root: self.root.parse()?,
+            max_get_intersects_size: DEFAULT_MAX_GET_INTERSECTS_SIZE,
+            default_limit: DEFAULT_LIMIT,
+            max_limit: DEFAULT_MAX_LIMIT,
+            token_ttl: None,
+            crs: vec![stac::crs::CRS84.to_string()],
+            language_negotiation: false,
+            collection_statistics: false,
+            transactions: false,
+        })
     }
+}
 
-    fn set_item_links(&self, item: &mut stac::api::Item) -> Result<()> {
-        let mut collection_url = None;
-        let mut item_link = None;
-        if let Some(item_id) = item.get("id").and_then(|id| id.as_str())
-            && let Some(collection_id) = item.get("collection").and_then(|id| id.as_str())
-        {
-            collection_url = Some(self.url(&format!("/collections/{collection_id}"))?);
-            item_link = Some(serde_json::to_value(
-                Link::self_(self.url(&format!("/collections/{collection_id}/items/{item_id}"))?)
-                    .geojson(),
-            )?);
-        }
-        if item
-            .get("links")
-            .map(|links| !links.is_array())
-            .unwrap_or(true)
-        {
-            let _ = item.insert("links".to_string(), Value::Array(Vec::new()));
-        }
-        let links = item.get_mut("links").unwrap().as_array_mut().unwrap();
-        links.push(serde_json::to_value(Link::root(self.root.clone()).json())?);
-        if let Some(item_link) = item_link {
-            links.push(item_link);
+impl Document {
+    /// Creates a new document.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_server::Document;
+    ///
+    /// let document = Document::new("text/css", "body { margin: 0; }");
+    /// assert_eq!(document.content_type, "text/css");
+    /// ```
+    pub fn new(content_type: impl ToString, body: impl Into<Vec<u8>>) -> Document {
+        Document {
+            content_type: content_type.to_string(),
+            body: Arc::from(body.into()),
         }
-        if let Some(collection_url) = collection_url {
-            links.push(serde_json::to_value(
-                Link::collection(collection_url.clone()).json(),
-            )?);
-            links.push(serde_json::to_value(Link::parent(collection_url).json())?);
+    }
+}
+
+fn with_queryables_id(queryables: Option<Value>, url: Url) -> Value {
+    match queryables {
+        Some(Value::Object(mut queryables)) => {
+            let _ = queryables.insert("$id".to_string(), url.to_string().into());
+            Value::Object(queryables)
         }
-        Ok(())
+        // This is a pure punt from https://github.com/stac-api-extensions/filter?tab=readme-ov-file#queryables
+        _ => json!({
+          "$schema" : "https://json-schema.org/draft/2019-09/schema",
+          "$id" : url.to_string(),
+          "type" : "object",
+          "title" : "Queryables for Example STAC API",
+          "description" : "Queryable names for the example STAC API Item Search filter.",
+          "properties" : {
+          },
+          "additionalProperties": true
+        }),
     }
 }
 
+/// Returns the current time in seconds since the unix epoch.
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
+/// Returns true if two crs identifiers refer to the same crs, e.g. a uri and
+/// its CURIE.
+fn same_crs(a: &str, b: &str) -> bool {
+    a == b
+        || (stac::crs::is_crs84(a) && stac::crs::is_crs84(b))
+        || stac::crs::epsg_code(a).is_some_and(|code| stac::crs::epsg_code(b) == Some(code))
+}
+
 #[cfg(test)]
 mod tests {
-    use super::Api;
-    use crate::MemoryBackend;
+    use super::{Api, TOKEN_ISSUED_KEY, now};
+    use crate::{COLLECTION_WILDCARDS_URI, Error, MemoryBackend, OFFSET_PAGINATION_URI};
     use http::Method;
+    use serde_json::json;
     use stac::api::TransactionClient;
     use stac::api::{ITEM_SEARCH_URI, Items, Search};
     use stac::{Catalog, Collection, Item, Links};
-    use std::collections::HashSet;
+    use std::{collections::HashSet, time::Duration};
+    use url::Url;
 
     macro_rules! assert_link {
         ($link:expr_2021, $href:expr_2021, $media_type:expr_2021) => {
@@ -522,6 +1346,7 @@ mod tests {
             "https://api.stacspec.org/v1.0.0/collections",
             "http://www.opengis.net/spec/ogcapi-features-1/1.0/conf/geojson",
             "http://www.opengis.net/spec/ogcapi-features-1/1.0/conf/core",
+            "http://www.opengis.net/spec/ogcapi-features-2/1.0/conf/crs",
         ] {
             assert!(
                 conformance
@@ -532,6 +1357,64 @@ mod tests {
         }
     }
 
+    #[test]
+    fn conformance_without_crs() {
+        let mut api = test_api(MemoryBackend::new());
+        api.crs.clear();
+        assert!(
+            !api.conformance().conforms_to.contains(
+                &"http://www.opengis.net/spec/ogcapi-features-2/1.0/conf/crs".to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn check_crs() {
+        let api = test_api(MemoryBackend::new());
+        let mut items = Items {
+            bbox_crs: Some("OGC:CRS84".to_string()),
+            crs: Some(stac::crs::CRS84.to_string()),
+            ..Default::default()
+        };
+        api.check_crs(&items).unwrap();
+        items.bbox_crs = Some("EPSG:4326".to_string());
+        assert!(matches!(
+            api.check_crs(&items).unwrap_err(),
+            Error::UnsupportedCrs(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn builder() {
+        let conforms_to = "https://api.stacspec.org/v1.0.0/ogcapi-features/extensions/transaction";
+        let api = Api::builder(MemoryBackend::new(), "http://stac.test/")
+            .id("my-api")
+            .title("My API")
+            .license("https://creativecommons.org/licenses/by/4.0/")
+            .conforms_to(conforms_to)
+            .service_desc("application/json", "{}")
+            .build()
+            .unwrap();
+        let root = api.root().await.unwrap();
+        assert!(
+            root.conformance
+                .conforms_to
+                .contains(&conforms_to.to_string())
+        );
+        let catalog: Catalog = serde_json::from_value(serde_json::to_value(root).unwrap()).unwrap();
+        assert_eq!(catalog.id, "my-api");
+        assert_eq!(catalog.title.as_deref(), Some("My API"));
+        assert_eq!(
+            catalog.link("license").unwrap().href,
+            "https://creativecommons.org/licenses/by/4.0/"
+        );
+        assert_link!(
+            catalog.link("service-desc"),
+            "http://stac.test/api",
+            "application/json"
+        );
+    }
+
     #[tokio::test]
     async fn collections() {
         let mut backend = MemoryBackend::new();
@@ -576,6 +1459,44 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn collection_statistics() {
+        let mut backend = MemoryBackend::new();
+        backend
+            .add_collection(Collection::new("a-collection", "A description"))
+            .await
+            .unwrap();
+        let mut item = Item::new("an-item").collection("a-collection");
+        item.bbox = Some(stac::Bbox::new(1., 2., 3., 4.));
+        backend.add_item(item).await.unwrap();
+
+        let api = test_api(backend.clone());
+        let collection = api.collection("a-collection").await.unwrap().unwrap();
+        assert!(!collection.additional_fields.contains_key("stats:items"));
+        assert_ne!(
+            collection.extent.spatial.bbox,
+            vec![stac::Bbox::new(1., 2., 3., 4.)]
+        );
+
+        let api = test_api(backend).collection_statistics(true);
+        let collections = api.collections().await.unwrap();
+        let collection = &collections.collections[0];
+        assert_eq!(
+            collection.additional_fields["stats:items"],
+            json!({"count": 1})
+        );
+        assert_eq!(
+            collection.extent.spatial.bbox,
+            vec![stac::Bbox::new(1., 2., 3., 4.)]
+        );
+        assert!(
+            collection
+                .extensions
+                .iter()
+                .any(|extension| extension.contains("stats"))
+        );
+    }
+
     #[tokio::test]
     async fn collection() {
         let mut backend = MemoryBackend::new();
@@ -606,6 +1527,29 @@ mod tests {
             "http://stac.test/collections/a-collection/items",
             "application/geo+json"
         );
+        assert_eq!(
+            collection.additional_fields["crs"],
+            json!(["http://www.opengis.net/def/crs/OGC/1.3/CRS84"])
+        );
+        assert_eq!(
+            collection.additional_fields["storageCrs"],
+            "http://www.opengis.net/def/crs/OGC/1.3/CRS84"
+        );
+    }
+
+    #[tokio::test]
+    async fn collection_crs() {
+        let mut backend = MemoryBackend::new();
+        backend
+            .add_collection(Collection::new("a-collection", "A description"))
+            .await
+            .unwrap();
+        let api = test_api(backend).crs(["http://www.opengis.net/def/crs/EPSG/0/3857"]);
+        let collection = api.collection("a-collection").await.unwrap().unwrap();
+        assert_eq!(
+            collection.additional_fields["crs"],
+            json!(["http://www.opengis.net/def/crs/OGC/1.3/CRS84"])
+        );
     }
 
     #[tokio::test]
@@ -687,24 +1631,23 @@ mod tests {
         assert_eq!(items.items.len(), 1);
         assert_link!(
             items.link("next"),
-            "http://stac.test/collections/collection-id/items?limit=1&skip=1",
+            "http://stac.test/collections/collection-id/items?limit=1&offset=1",
             "application/geo+json"
         );
 
-        let mut items = Items {
+        let items = Items {
             limit: Some(1),
+            offset: Some(1),
             ..Default::default()
         };
-        let _ = items
-            .additional_fields
-            .insert("skip".to_string(), "1".into());
         let items = api.items("collection-id", items).await.unwrap().unwrap();
         assert_eq!(items.items.len(), 1);
         assert_link!(
             items.link("prev"),
-            "http://stac.test/collections/collection-id/items?limit=1&skip=0",
+            "http://stac.test/collections/collection-id/items?limit=1&offset=0",
             "application/geo+json"
         );
+        assert!(items.link("next").is_none());
     }
 
     #[tokio::test]
@@ -757,6 +1700,121 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn default_limit() {
+        let mut backend = MemoryBackend::new();
+        backend
+            .add_collection(Collection::new("collection-id", "a description"))
+            .await
+            .unwrap();
+        for id in ["item-a", "item-b", "item-c"] {
+            backend
+                .add_item(Item::new(id).collection("collection-id"))
+                .await
+                .unwrap();
+        }
+        let api = test_api(backend).default_limit(5).max_limit(2);
+        let item_collection = api.search(Search::default(), Method::GET).await.unwrap();
+        assert_eq!(item_collection.items.len(), 2);
+        assert!(
+            api.search(Search::default().limit(3u64), Method::GET)
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn token_ttl() {
+        let mut backend = MemoryBackend::new();
+        backend
+            .add_collection(Collection::new("collection-id", "a description"))
+            .await
+            .unwrap();
+        for id in ["item-a", "item-b"] {
+            backend
+                .add_item(Item::new(id).collection("collection-id"))
+                .await
+                .unwrap();
+        }
+        let api = test_api(backend).token_ttl(Duration::from_secs(60));
+        let items = api
+            .items(
+                "collection-id",
+                Items {
+                    limit: Some(1),
+                    ..Default::default()
+                },
+            )
+            .await
+            .unwrap()
+            .unwrap();
+        let next = items.link("next").unwrap().href.to_string();
+        let issued: u64 = Url::parse(&next)
+            .unwrap()
+            .query_pairs()
+            .find(|(key, _)| key == TOKEN_ISSUED_KEY)
+            .unwrap()
+            .1
+            .parse()
+            .unwrap();
+        assert!(now() - issued <= 1);
+
+        let mut items = Items {
+            limit: Some(1),
+            offset: Some(1),
+            ..Default::default()
+        };
+        let _ = items
+            .additional_fields
+            .insert(TOKEN_ISSUED_KEY.to_string(), issued.to_string().into());
+        assert_eq!(
+            api.items("collection-id", items.clone())
+                .await
+                .unwrap()
+                .unwrap()
+                .items
+                .len(),
+            1
+        );
+        let _ = items
+            .additional_fields
+            .insert(TOKEN_ISSUED_KEY.to_string(), (issued - 120).into());
+        assert!(matches!(
+            api.items("collection-id", items).await.unwrap_err(),
+            Error::TokenExpired { ttl: 60, .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn no_token_ttl() {
+        let api = test_api(MemoryBackend::new());
+        let mut search = Search::default();
+        let _ = search
+            .items
+            .additional_fields
+            .insert(TOKEN_ISSUED_KEY.to_string(), 0.into());
+        let _ = api.search(search, Method::GET).await.unwrap();
+    }
+
+    #[test]
+    fn pagination_method_falls_back_to_post() {
+        let api = test_api(MemoryBackend::new()).max_get_intersects_size(8);
+        let search = Search::default();
+        assert_eq!(
+            api.pagination_method(&search, Method::GET).unwrap(),
+            Method::GET
+        );
+        let search = search.intersects(stac::Geometry::new_point(vec![0.0, 0.0]));
+        assert_eq!(
+            api.pagination_method(&search, Method::GET).unwrap(),
+            Method::POST
+        );
+        assert_eq!(
+            api.pagination_method(&search, Method::POST).unwrap(),
+            Method::POST
+        );
+    }
+
     #[test]
     fn memory_item_search_conformance() {
         let api = test_api(MemoryBackend::new());
@@ -767,4 +1825,68 @@ mod tests {
                 .contains(&ITEM_SEARCH_URI.to_string())
         );
     }
+
+    #[test]
+    fn memory_sort_conformance() {
+        let api = test_api(MemoryBackend::new());
+        let conformance = api.conformance();
+        for uri in stac::api::SORT_URIS {
+            assert!(conformance.conforms_to.contains(&uri.to_string()));
+        }
+    }
+
+    #[test]
+    fn memory_free_text_conformance() {
+        let api = test_api(MemoryBackend::new());
+        let conformance = api.conformance();
+        for uri in stac::api::FREE_TEXT_URIS {
+            assert!(conformance.conforms_to.contains(&uri.to_string()));
+        }
+        assert!(
+            conformance
+                .conforms_to
+                .contains(&stac::api::COLLECTION_SEARCH_FREE_TEXT_URI.to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn memory_filter_conformance() {
+        let api = test_api(MemoryBackend::new());
+        let conformance = api.conformance();
+        for uri in stac::api::FILTER_URIS {
+            assert!(conformance.conforms_to.contains(&uri.to_string()));
+        }
+        let root = api.root().await.unwrap();
+        assert!(
+            root.catalog
+                .link("http://www.opengis.net/def/rel/ogc/1.0/queryables")
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn offset_pagination_conformance() {
+        let api = test_api(MemoryBackend::new());
+        assert!(
+            api.conformance()
+                .conforms_to
+                .contains(&OFFSET_PAGINATION_URI.to_string())
+        );
+    }
+
+    #[test]
+    fn collection_wildcards_conformance() {
+        let api = test_api(MemoryBackend::new());
+        assert!(
+            !api.conformance()
+                .conforms_to
+                .contains(&COLLECTION_WILDCARDS_URI.to_string())
+        );
+        let api = test_api(MemoryBackend::new().collection_wildcards(true));
+        assert!(
+            api.conformance()
+                .conforms_to
+                .contains(&COLLECTION_WILDCARDS_URI.to_string())
+        );
+    }
 }
@@ -1,27 +1,38 @@
-use super::Backend;
-use crate::{Error, Result};
+use super::{Backend, EncodedItems};
+use crate::{CollectionStatistics, Error, Format, Result};
 use bb8::{ManageConnection, Pool};
 use futures_core::Stream;
-use stac::Collection;
+use serde_json::Value;
 use stac::api::{
     CollectionsClient, ItemsClient, Search, StreamItemsClient, TransactionClient, stream_pages,
 };
+use stac::{Collection, Extent};
 use stac_duckdb::Client;
+use std::{collections::HashSet, io::Write, path::Path};
+use tokio::sync::mpsc::Sender;
+
+/// The size of the chunks of encoded search results, in bytes.
+const ENCODED_CHUNK_SIZE: usize = 1024 * 1024;
+
+/// How many chunks of encoded search results can be waiting to be sent.
+const ENCODED_CHUNK_BUFFER: usize = 4;
 
 /// A backend that uses [DuckDB](https://duckdb.org/) to query
 /// [stac-geoparquet](https://github.com/stac-utils/stac-geoparquet).
 #[derive(Clone, Debug)]
 pub struct DuckdbBackend {
     pool: Pool<DuckdbConnectionManager>,
+    collection_wildcards: bool,
 }
 
 struct DuckdbConnectionManager {
-    href: String,
+    hrefs: Vec<String>,
+    hive_partitioned_hrefs: HashSet<String>,
 }
 
 struct DuckdbConnection {
     client: Client,
-    href: String,
+    hrefs: Vec<String>,
 }
 
 impl DuckdbBackend {
@@ -36,12 +47,79 @@ impl DuckdbBackend {
     /// # })
     /// ```
     pub async fn new(href: impl ToString) -> Result<DuckdbBackend> {
+        DuckdbBackend::from_hrefs([href]).await
+    }
+
+    /// Creates a new DuckDB backend that serves several **stac-geoparquet**
+    /// hrefs as one API.
+    ///
+    /// Each href can be a file, a glob, or a directory of hive-partitioned
+    /// files, e.g. `s3://bucket/lake/` or `lake/collection=a/year=2024/`.
+    /// Directories are read with `**/*.parquet` and hive partitioning, and
+    /// everything else is read without it.
+    /// Collections are gathered from all the hrefs, and searches query them
+    /// together.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_server::DuckdbBackend;
+    /// # tokio_test::block_on(async {
+    /// let backend = DuckdbBackend::from_hrefs([
+    ///     "data/100-sentinel-2-items.parquet",
+    ///     "data/100-landsat-items.parquet",
+    /// ])
+    /// .await
+    /// .unwrap();
+    /// # })
+    /// ```
+    pub async fn from_hrefs(
+        hrefs: impl IntoIterator<Item = impl ToString>,
+    ) -> Result<DuckdbBackend> {
+        let mut hive_partitioned_hrefs = HashSet::new();
+        let hrefs = hrefs
+            .into_iter()
+            .map(|href| {
+                let href = href.to_string();
+                if href.ends_with('/') || Path::new(&href).is_dir() {
+                    let href = format!("{}/**/*.parquet", href.trim_end_matches('/'));
+                    let _ = hive_partitioned_hrefs.insert(href.clone());
+                    href
+                } else {
+                    href
+                }
+            })
+            .collect();
         let pool = Pool::builder()
             .build(DuckdbConnectionManager {
-                href: href.to_string(),
+                hrefs,
+                hive_partitioned_hrefs,
             })
             .await?;
-        Ok(DuckdbBackend { pool })
+        Ok(DuckdbBackend {
+            pool,
+            collection_wildcards: false,
+        })
+    }
+
+    /// Sets whether `*` in search collections is treated as a wildcard.
+    ///
+    /// Wildcards are translated to SQL `LIKE` patterns.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_server::DuckdbBackend;
+    /// # tokio_test::block_on(async {
+    /// let backend = DuckdbBackend::new("data/100-sentinel-2-items.parquet")
+    ///     .await
+    ///     .unwrap()
+    ///     .collection_wildcards(true);
+    /// # })
+    /// ```
+    pub fn collection_wildcards(mut self, collection_wildcards: bool) -> DuckdbBackend {
+        self.collection_wildcards = collection_wildcards;
+        self
     }
 }
 
@@ -49,7 +127,8 @@ impl ItemsClient for DuckdbBackend {
     type Error = Error;
 
     async fn search(&self, search: Search) -> Result<stac::api::ItemCollection> {
-        let client = self.pool.get().await.map_err(Box::new)?;
+        let mut client = self.pool.get().await.map_err(Box::new)?;
+        client.client.collection_wildcards = self.collection_wildcards;
         client.search(search)
     }
 }
@@ -100,6 +179,68 @@ impl Backend for DuckdbBackend {
     fn has_filter(&self) -> bool {
         false
     }
+
+    fn has_sort(&self) -> bool {
+        true
+    }
+
+    fn has_free_text(&self) -> bool {
+        false
+    }
+
+    fn has_collection_wildcards(&self) -> bool {
+        self.collection_wildcards
+    }
+
+    fn has_offset_pagination(&self) -> bool {
+        true
+    }
+
+    async fn collection_statistics(
+        &self,
+        collection_id: &str,
+    ) -> Result<Option<CollectionStatistics>> {
+        let client = self.pool.get().await.map_err(Box::new)?;
+        client.collection_statistics(collection_id)
+    }
+
+    /// Derives the queryables from the stac-geoparquet schema.
+    ///
+    /// A collection's queryables only come from the files that hold that
+    /// collection.
+    async fn queryables(&self, collection_id: Option<&str>) -> Result<Option<Value>> {
+        let client = self.pool.get().await.map_err(Box::new)?;
+        client.queryables(collection_id)
+    }
+
+    /// Streams ndjson and stac-geoparquet straight from the query's record
+    /// batches, without building an item collection.
+    ///
+    /// The query runs on a blocking thread, which sends each chunk as it's
+    /// encoded. It stops early if the response is dropped.
+    async fn search_encoded(&self, search: Search, format: Format) -> Result<EncodedItems> {
+        if format == Format::GeoJson {
+            let item_collection = ItemsClient::search(self, search).await?;
+            return Ok(EncodedItems::from_bytes(
+                format.encode(item_collection.items)?,
+            ));
+        }
+        let mut connection = self.pool.get_owned().await.map_err(Box::new)?;
+        connection.client.collection_wildcards = self.collection_wildcards;
+        let (sender, encoded) = EncodedItems::channel(ENCODED_CHUNK_BUFFER);
+        let _ = tokio::task::spawn_blocking(move || {
+            if let Err(err) = connection.search_encoded(search, format, &sender) {
+                let _ = sender.blocking_send(Err(err));
+            }
+        });
+        Ok(encoded)
+    }
+
+    /// Checks that a connection can be checked out of the pool.
+    async fn health(&self) -> Result<()> {
+        let _ = self.pool.get().await.map_err(Box::new)?;
+        Ok(())
+    }
 }
 
 impl ManageConnection for DuckdbConnectionManager {
@@ -107,7 +248,9 @@ impl ManageConnection for DuckdbConnectionManager {
     type Error = Error;
 
     async fn connect(&self) -> Result<DuckdbConnection> {
-        DuckdbConnection::new(&self.href)
+        let mut connection = DuckdbConnection::new(self.hrefs.clone())?;
+        connection.client.hive_partitioned_hrefs = self.hive_partitioned_hrefs.clone();
+        Ok(connection)
     }
 
     async fn is_valid(&self, _conn: &mut DuckdbConnection) -> Result<()> {
@@ -120,35 +263,179 @@ impl ManageConnection for DuckdbConnectionManager {
 }
 
 impl DuckdbConnection {
-    fn new(href: impl ToString) -> Result<DuckdbConnection> {
+    fn new(hrefs: Vec<String>) -> Result<DuckdbConnection> {
         let client = Client::new()?;
-        Ok(DuckdbConnection {
-            client,
-            href: href.to_string(),
-        })
+        Ok(DuckdbConnection { client, hrefs })
     }
 
     fn collections(&self) -> Result<Vec<Collection>> {
-        let collections = self.client.collections(&self.href)?;
+        let mut collections: Vec<Collection> = Vec::new();
+        for href in &self.hrefs {
+            for collection in self.client.collections(href)? {
+                if let Some(existing) = collections.iter_mut().find(|c| c.id == collection.id) {
+                    merge_extent(&mut existing.extent, collection.extent);
+                } else {
+                    collections.push(collection);
+                }
+            }
+        }
         Ok(collections)
     }
 
     fn collection(&self, id: &str) -> Result<Option<Collection>> {
-        let collections = self.client.collections(&self.href)?;
+        let collections = self.collections()?;
         Ok(collections
             .into_iter()
             .find(|collection| collection.id == id))
     }
 
+    fn collection_statistics(&self, id: &str) -> Result<Option<CollectionStatistics>> {
+        let mut item_count = 0;
+        let mut extent: Option<Extent> = None;
+        for href in &self.hrefs {
+            let count = self.client.item_count(href, id)?;
+            if count == 0 {
+                continue;
+            }
+            item_count += count;
+            let href_extent = self.client.extent(href, id)?;
+            match extent.as_mut() {
+                Some(extent) => merge_extent(extent, href_extent),
+                None => extent = Some(href_extent),
+            }
+        }
+        if item_count == 0 {
+            // Collections only exist in a stac-geoparquet file if they have
+            // items, unless they're embedded in the metadata.
+            return Ok(self.collection(id)?.map(|_| CollectionStatistics {
+                item_count,
+                extent: None,
+            }));
+        }
+        Ok(Some(CollectionStatistics { item_count, extent }))
+    }
+
+    fn queryables(&self, collection_id: Option<&str>) -> Result<Option<Value>> {
+        let mut hrefs: Vec<&str> = Vec::new();
+        for href in &self.hrefs {
+            if let Some(collection_id) = collection_id {
+                let collections = self.client.collections(href)?;
+                if !collections
+                    .iter()
+                    .any(|collection| collection.id == collection_id)
+                {
+                    continue;
+                }
+            }
+            hrefs.push(href);
+        }
+        if hrefs.is_empty() {
+            return Ok(None);
+        }
+        let queryables = self.client.queryables_hrefs(&hrefs)?;
+        Ok(Some(queryables))
+    }
+
     fn search(&self, search: Search) -> Result<stac::api::ItemCollection> {
-        let item_collection = self.client.search(&self.href, search)?;
+        let hrefs: Vec<&str> = self.hrefs.iter().map(String::as_str).collect();
+        let item_collection = self.client.search_hrefs(&hrefs, search)?;
         Ok(item_collection)
     }
+
+    fn search_encoded(
+        &self,
+        search: Search,
+        format: Format,
+        sender: &Sender<Result<Vec<u8>>>,
+    ) -> Result<()> {
+        let hrefs: Vec<&str> = self.hrefs.iter().map(String::as_str).collect();
+        let record_batches = self.client.search_hrefs_to_arrow(&hrefs, search)?;
+        let mut writer = ChunkWriter::new(sender);
+        match format {
+            Format::Ndjson => {
+                for record_batch in record_batches {
+                    for item in stac::geoarrow::items_from_record_batch(record_batch?)? {
+                        serde_json::to_writer(&mut writer, &item)?;
+                        writer.write_all(b"\n")?;
+                    }
+                }
+            }
+            #[cfg(feature = "geoparquet")]
+            Format::Geoparquet => stac::geoparquet::record_batches_into_writer(
+                &mut writer,
+                record_batches,
+                Default::default(),
+            )?,
+            _ => return Err(Error::UnsupportedFormat(format.to_string())),
+        }
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Sends bytes written to it as chunks of encoded items.
+struct ChunkWriter<'a> {
+    sender: &'a Sender<Result<Vec<u8>>>,
+    buffer: Vec<u8>,
+}
+
+impl<'a> ChunkWriter<'a> {
+    fn new(sender: &'a Sender<Result<Vec<u8>>>) -> ChunkWriter<'a> {
+        ChunkWriter {
+            sender,
+            buffer: Vec::with_capacity(ENCODED_CHUNK_SIZE),
+        }
+    }
+}
+
+impl Write for ChunkWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.buffer.extend_from_slice(buf);
+        if self.buffer.len() >= ENCODED_CHUNK_SIZE {
+            self.flush()?;
+        }
+        Ok(buf.len())
+    }
+
+    /// Sends the buffered bytes, or errors if the response has been dropped.
+    fn flush(&mut self) -> std::io::Result<()> {
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+        let chunk = std::mem::replace(&mut self.buffer, Vec::with_capacity(ENCODED_CHUNK_SIZE));
+        self.sender
+            .blocking_send(Ok(chunk))
+            .map_err(|_| std::io::Error::from(std::io::ErrorKind::BrokenPipe))
+    }
+}
+
+/// Grows an extent to include another, e.g. for a collection that's split
+/// across several files.
+fn merge_extent(extent: &mut Extent, other: Extent) {
+    if let Some(bbox) = other.spatial.bbox.first() {
+        match extent.spatial.bbox.first_mut() {
+            Some(existing) => existing.update(*bbox),
+            None => extent.spatial.bbox.push(*bbox),
+        }
+    }
+    if let Some([start, end]) = other.temporal.interval.first() {
+        match extent.temporal.interval.first_mut() {
+            Some(existing) => {
+                // An open (`None`) bound stays open.
+                existing[0] = existing[0].zip(*start).map(|(a, b)| a.min(b));
+                existing[1] = existing[1].zip(*end).map(|(a, b)| a.max(b));
+            }
+            None => extent.temporal.interval.push([*start, *end]),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use stac::api::CollectionsClient;
+    use crate::{Backend, Format};
+    use http::Method;
+    use stac::Links;
+    use stac::api::{CollectionsClient, ItemsClient, Search};
 
     #[tokio::test]
     async fn backend() {
@@ -163,4 +450,162 @@ mod tests {
                 .is_some()
         );
     }
+
+    #[tokio::test]
+    async fn from_hrefs() {
+        let backend = super::DuckdbBackend::from_hrefs([
+            "data/100-sentinel-2-items.parquet",
+            "data/100-landsat-items.parquet",
+        ])
+        .await
+        .unwrap();
+        let collections = backend.collections().await.unwrap();
+        assert_eq!(collections.len(), 2);
+        let item_collection = backend
+            .search(Search::default().limit(200u64))
+            .await
+            .unwrap();
+        assert_eq!(item_collection.items.len(), 200);
+        let statistics = backend
+            .collection_statistics(&collections[1].id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(statistics.item_count, 100);
+    }
+
+    #[tokio::test]
+    async fn collection_wildcards() {
+        let backend = super::DuckdbBackend::new("data/100-sentinel-2-items.parquet")
+            .await
+            .unwrap()
+            .collection_wildcards(true);
+        let search = Search::default()
+            .collections(vec!["sentinel-*".to_string()])
+            .limit(1u64);
+        let item_collection = backend.search(search).await.unwrap();
+        assert_eq!(item_collection.items.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn search_encoded() {
+        let backend = super::DuckdbBackend::new("data/100-sentinel-2-items.parquet")
+            .await
+            .unwrap();
+        let mut encoded = backend
+            .search_encoded(Search::default(), Format::Ndjson)
+            .await
+            .unwrap();
+        let mut bytes = Vec::new();
+        while let Some(chunk) = encoded.next_chunk().await {
+            bytes.extend(chunk.unwrap());
+        }
+        assert_eq!(bytes.iter().filter(|&&b| b == b'\n').count(), 100);
+
+        let mut search = Search::default();
+        search.ids = vec!["not-an-item".to_string()];
+        let mut encoded = backend
+            .search_encoded(search, Format::Ndjson)
+            .await
+            .unwrap();
+        assert!(encoded.next_chunk().await.is_none());
+    }
+
+    #[cfg(feature = "geoparquet")]
+    #[tokio::test]
+    async fn search_encoded_geoparquet() {
+        use stac::FromGeoparquet;
+
+        let backend = super::DuckdbBackend::new("data/100-sentinel-2-items.parquet")
+            .await
+            .unwrap();
+        let mut encoded = backend
+            .search_encoded(Search::default(), Format::Geoparquet)
+            .await
+            .unwrap();
+        let mut bytes = Vec::new();
+        while let Some(chunk) = encoded.next_chunk().await {
+            bytes.extend(chunk.unwrap());
+        }
+        let item_collection = stac::ItemCollection::from_geoparquet_bytes(bytes).unwrap();
+        assert_eq!(item_collection.items.len(), 100);
+    }
+
+    #[tokio::test]
+    async fn queryables() {
+        let backend = super::DuckdbBackend::new("data/100-sentinel-2-items.parquet")
+            .await
+            .unwrap();
+        let queryables = backend.queryables(None).await.unwrap().unwrap();
+        assert_eq!(queryables["properties"]["eo:cloud_cover"]["type"], "number");
+        assert!(queryables["properties"].get("links").is_none());
+    }
+
+    #[tokio::test]
+    async fn collection_queryables() {
+        let backend = super::DuckdbBackend::from_hrefs([
+            "data/100-sentinel-2-items.parquet",
+            "data/100-landsat-items.parquet",
+        ])
+        .await
+        .unwrap();
+        let queryables = backend.queryables(None).await.unwrap().unwrap();
+        assert!(queryables["properties"].get("landsat:wrs_path").is_some());
+        let queryables = backend
+            .queryables(Some("sentinel-2-l2a"))
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(queryables["properties"].get("s2:datastrip_id").is_some());
+        assert!(queryables["properties"].get("landsat:wrs_path").is_none());
+        assert!(
+            backend
+                .queryables(Some("not-a-collection"))
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn collection_statistics() {
+        let backend = super::DuckdbBackend::new("data/100-sentinel-2-items.parquet")
+            .await
+            .unwrap();
+        let statistics = backend
+            .collection_statistics("sentinel-2-l2a")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(statistics.item_count, 100);
+        assert!(statistics.extent.is_some());
+        assert!(
+            backend
+                .collection_statistics("not-a-collection")
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn pagination_links() {
+        let backend = super::DuckdbBackend::new("data/100-sentinel-2-items.parquet")
+            .await
+            .unwrap();
+        let api = crate::Api::new(backend, "http://stac.test").unwrap();
+        let item_collection = api
+            .search(Search::default().limit(10u64).offset(10), Method::GET)
+            .await
+            .unwrap();
+        assert_eq!(item_collection.items.len(), 10);
+        assert_eq!(
+            item_collection.link("next").unwrap().href,
+            "http://stac.test/search?limit=10&offset=20"
+        );
+        assert_eq!(
+            item_collection.link("prev").unwrap().href,
+            "http://stac.test/search?limit=10&offset=0"
+        );
+    }
 }
@@ -1,15 +1,35 @@
-use crate::{Backend, DEFAULT_LIMIT, Error, Result};
+use super::{CollisionPolicy, FreeText, ItemOutcome, collection_wildcard_matches};
+use crate::{Backend, CollectionStatistics, DEFAULT_LIMIT, Error, Result};
 use futures_core::Stream;
+use indexmap::IndexMap;
 use serde_json::Map;
 use stac::api::{
     CollectionsClient, ItemCollection, ItemsClient, Search, StreamItemsClient, TransactionClient,
     stream_pages,
 };
-use stac::{Collection, Item};
+use stac::{Collection, Item, sort::ItemComparator};
 use std::{
     collections::{BTreeMap, HashMap},
-    sync::{Arc, RwLock},
+    sync::{
+        Arc, RwLock,
+        atomic::{AtomicU64, Ordering},
+    },
 };
+#[cfg(feature = "geoparquet")]
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+/// The name of the file that holds collections in a snapshot.
+#[cfg(feature = "geoparquet")]
+pub const SNAPSHOT_COLLECTIONS_FILE_NAME: &str = "collections.json";
+
+/// The name of the file that holds items in a snapshot.
+#[cfg(feature = "geoparquet")]
+pub const SNAPSHOT_ITEMS_FILE_NAME: &str = "items.parquet";
 
 /// A naive backend that stores collections and items in memory.
 ///
@@ -17,7 +37,10 @@ use std::{
 #[derive(Clone, Debug)]
 pub struct MemoryBackend {
     collections: Arc<RwLock<BTreeMap<String, Collection>>>,
-    items: Arc<RwLock<HashMap<String, Vec<Item>>>>,
+    items: Arc<RwLock<HashMap<String, IndexMap<String, Item>>>>,
+    collection_wildcards: bool,
+    collision_policy: CollisionPolicy,
+    generation: Arc<AtomicU64>,
 }
 
 impl MemoryBackend {
@@ -33,10 +56,225 @@ impl MemoryBackend {
         MemoryBackend {
             collections: Arc::new(RwLock::new(BTreeMap::new())),
             items: Arc::new(RwLock::new(HashMap::new())),
+            collection_wildcards: false,
+            collision_policy: CollisionPolicy::default(),
+            generation: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Sets what to do when an added item has the same id as an existing
+    /// item in its collection.
+    ///
+    /// Defaults to [CollisionPolicy::Overwrite].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_server::{CollisionPolicy, MemoryBackend};
+    /// let backend = MemoryBackend::new().collision_policy(CollisionPolicy::Error);
+    /// ```
+    pub fn collision_policy(mut self, collision_policy: CollisionPolicy) -> MemoryBackend {
+        self.collision_policy = collision_policy;
+        self
+    }
+
+    fn insert_item(&self, item: Item) -> Result<ItemOutcome> {
+        let outcome = self.try_insert_item(item)?;
+        if outcome != ItemOutcome::Skipped {
+            self.changed();
+        }
+        Ok(outcome)
+    }
+
+    fn try_insert_item(&self, mut item: Item) -> Result<ItemOutcome> {
+        let Some(collection_id) = item.collection.clone() else {
+            return Err(Error::MemoryBackend(format!(
+                "collection not set on item: {}",
+                item.id
+            )));
+        };
+        if !self
+            .collections
+            .read()
+            .unwrap()
+            .contains_key(&collection_id)
+        {
+            return Err(Error::MemoryBackend(format!(
+                "no collection with id='{collection_id}'",
+            )));
+        }
+        let mut items = self.items.write().unwrap();
+        let items = items.entry(collection_id.clone()).or_default();
+        if !items.contains_key(&item.id) {
+            let _ = items.insert(item.id.clone(), item);
+            return Ok(ItemOutcome::Created);
+        }
+        match self.collision_policy {
+            CollisionPolicy::Error => Err(Error::ItemExists {
+                collection_id,
+                item_id: item.id,
+            }),
+            CollisionPolicy::Skip => Ok(ItemOutcome::Skipped),
+            CollisionPolicy::Overwrite => {
+                let _ = items.insert(item.id.clone(), item);
+                Ok(ItemOutcome::Replaced)
+            }
+            CollisionPolicy::Version => {
+                let mut version = 2;
+                while items.contains_key(&format!("{}-v{version}", item.id)) {
+                    version += 1;
+                }
+                item.id = format!("{}-v{version}", item.id);
+                let _ = items.insert(item.id.clone(), item.clone());
+                Ok(ItemOutcome::Versioned(item.id))
+            }
+        }
+    }
+
+    fn changed(&self) {
+        let _ = self.generation.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Sets whether `*` in search collections is treated as a wildcard.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_server::MemoryBackend;
+    /// let backend = MemoryBackend::new().collection_wildcards(true);
+    /// ```
+    pub fn collection_wildcards(mut self, collection_wildcards: bool) -> MemoryBackend {
+        self.collection_wildcards = collection_wildcards;
+        self
+    }
+}
+
+#[cfg(feature = "geoparquet")]
+impl MemoryBackend {
+    /// Restores a memory backend from a snapshot directory written by
+    /// [MemoryBackend::to_path].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use stac_server::MemoryBackend;
+    /// let backend = MemoryBackend::from_path("snapshot").unwrap();
+    /// ```
+    pub fn from_path(path: impl AsRef<Path>) -> Result<MemoryBackend> {
+        let path = path.as_ref();
+        let backend = MemoryBackend::new();
+        let file = File::open(path.join(SNAPSHOT_COLLECTIONS_FILE_NAME))?;
+        let collections: Vec<Collection> = serde_json::from_reader(BufReader::new(file))?;
+        {
+            let mut map = backend.collections.write().unwrap();
+            for collection in collections {
+                let _ = map.insert(collection.id.clone(), collection);
+            }
+        }
+        let items_path = path.join(SNAPSHOT_ITEMS_FILE_NAME);
+        if items_path.exists() {
+            let item_collection = stac::geoparquet::from_reader(File::open(items_path)?)?;
+            for item in item_collection.items {
+                let _ = backend.insert_item(item)?;
+            }
+        }
+        Ok(backend)
+    }
+
+    /// Writes a snapshot of this backend's collections and items to a
+    /// directory.
+    ///
+    /// Collections are written to [SNAPSHOT_COLLECTIONS_FILE_NAME] as a JSON
+    /// array, and items to [SNAPSHOT_ITEMS_FILE_NAME] as stac-geoparquet. Each
+    /// file is written to a temporary file and then renamed, so an
+    /// interrupted snapshot doesn't clobber the previous one.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use stac_server::MemoryBackend;
+    /// let backend = MemoryBackend::new();
+    /// backend.to_path("snapshot").unwrap();
+    /// ```
+    pub fn to_path(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        std::fs::create_dir_all(path)?;
+        let (collections, items) = {
+            // Same lock order as `refresh_collections`.
+            let items = self.items.read().unwrap();
+            let collections = self.collections.read().unwrap();
+            (
+                collections.values().cloned().collect::<Vec<_>>(),
+                items
+                    .values()
+                    .flat_map(|items| items.values().cloned())
+                    .collect::<Vec<_>>(),
+            )
+        };
+        write_atomically(&path.join(SNAPSHOT_COLLECTIONS_FILE_NAME), |file| {
+            serde_json::to_writer(file, &collections).map_err(Error::from)
+        })?;
+        let items_path = path.join(SNAPSHOT_ITEMS_FILE_NAME);
+        if items.is_empty() {
+            if items_path.exists() {
+                std::fs::remove_file(items_path)?;
+            }
+        } else {
+            write_atomically(&items_path, |file| {
+                stac::geoparquet::into_writer(file, items).map_err(Error::from)
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Writes a snapshot to a directory every period, if anything has
+    /// changed since the last snapshot.
+    ///
+    /// This runs forever, so it should be spawned. Changes made since the
+    /// last snapshot are lost if the process exits.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use stac_server::MemoryBackend;
+    /// use std::time::Duration;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let backend = MemoryBackend::new();
+    /// let _ = tokio::spawn(backend.clone().persist("snapshot", Duration::from_secs(5)));
+    /// # })
+    /// ```
+    pub async fn persist(self, path: impl Into<PathBuf>, period: Duration) {
+        let path = path.into();
+        let mut interval = tokio::time::interval(period);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        let mut persisted = None;
+        loop {
+            let _ = interval.tick().await;
+            let generation = self.generation.load(Ordering::Relaxed);
+            if persisted == Some(generation) {
+                continue;
+            }
+            match self.to_path(&path) {
+                Ok(()) => persisted = Some(generation),
+                Err(err) => tracing::warn!("error when persisting the memory backend: {err}"),
+            }
         }
     }
 }
 
+#[cfg(feature = "geoparquet")]
+fn write_atomically(path: &Path, f: impl FnOnce(&mut BufWriter<File>) -> Result<()>) -> Result<()> {
+    let mut tmp_path = path.as_os_str().to_owned();
+    tmp_path.push(".tmp");
+    let mut writer = BufWriter::new(File::create(&tmp_path)?);
+    f(&mut writer)?;
+    writer.flush()?;
+    drop(writer);
+    std::fs::rename(tmp_path, path)?;
+    Ok(())
+}
+
 impl ItemsClient for MemoryBackend {
     type Error = Error;
 
@@ -44,44 +282,85 @@ impl ItemsClient for MemoryBackend {
         let items = self.items.read().unwrap();
         if search.collections.is_empty() {
             search.collections = items.keys().cloned().collect();
+        } else if self.collection_wildcards && search.collections.iter().any(|c| c.contains('*')) {
+            let mut collections: Vec<String> = items
+                .keys()
+                .filter(|id| {
+                    search
+                        .collections
+                        .iter()
+                        .any(|pattern| collection_wildcard_matches(pattern, id))
+                })
+                .cloned()
+                .collect();
+            collections.sort();
+            search.collections = collections;
+            if search.collections.is_empty() {
+                return Ok(ItemCollection::new(Vec::new())?);
+            }
         }
+        // Parse the filter once instead of for every item. Items that the
+        // filter can't be evaluated against (e.g. because they don't have a
+        // property it uses) don't match.
+        let filter = search.filter_expr()?;
+        search.filter = None;
+        let free_text = match search.additional_fields.get("q") {
+            Some(q) => Some(FreeText::from_value(q).ok_or_else(|| {
+                Error::MemoryBackend(format!("q must be a string or an array of strings: {q}"))
+            })?),
+            None => None,
+        };
         let mut item_references = Vec::new();
         for collection in &search.collections {
             if let Some(items) = items.get(collection) {
-                item_references.extend(
-                    items
-                        .iter()
-                        .filter(|item| search.matches(item).unwrap_or_default()),
-                );
+                item_references.extend(items.values().filter(|item| {
+                    search.matches(item).unwrap_or_default()
+                        && free_text
+                            .as_ref()
+                            .is_none_or(|free_text| free_text.matches_item(item))
+                        && filter.as_ref().is_none_or(|expr| {
+                            (*item)
+                                .clone()
+                                .matches_cql2(expr.clone())
+                                .unwrap_or_default()
+                        })
+                }));
             }
         }
+        let comparator = ItemComparator::new(search.sortby.iter().cloned());
+        if !comparator.is_empty() {
+            item_references.sort_by(|a, b| comparator.compare(a, b));
+        }
         let limit = search.limit.unwrap_or(DEFAULT_LIMIT).try_into()?;
-        let skip = search
-            .additional_fields
-            .get("skip")
-            .and_then(|skip| {
-                skip.as_u64()
-                    .or_else(|| skip.as_str().and_then(|skip| skip.parse::<u64>().ok()))
+        // `skip` is what this backend used before `offset`, so links from
+        // older responses still work.
+        let offset = search
+            .offset
+            .or_else(|| {
+                search.additional_fields.get("skip").and_then(|skip| {
+                    skip.as_u64()
+                        .or_else(|| skip.as_str().and_then(|skip| skip.parse::<u64>().ok()))
+                })
             })
             .unwrap_or_default()
             .try_into()?;
         let len = item_references.len();
         let items = item_references
             .into_iter()
-            .skip(skip)
+            .skip(offset)
             .take(limit)
             .map(|item| stac::api::Item::try_from(item.clone()).map_err(Error::from))
             .collect::<Result<Vec<_>>>()?;
         let mut item_collection = ItemCollection::new(items)?;
-        if len > item_collection.items.len() + skip {
+        if len > item_collection.items.len() + offset {
             let mut next = Map::new();
-            let _ = next.insert("skip".to_string(), (skip + limit).into());
+            let _ = next.insert("offset".to_string(), (offset + limit).into());
             item_collection.next = Some(next);
         }
-        if skip > 0 {
+        if offset > 0 {
             let mut prev = Map::new();
-            let skip = skip.saturating_sub(limit);
-            let _ = prev.insert("skip".to_string(), skip.into());
+            let offset = offset.saturating_sub(limit);
+            let _ = prev.insert("offset".to_string(), offset.into());
             item_collection.prev = Some(prev);
         }
         Ok(item_collection)
@@ -91,7 +370,7 @@ impl ItemsClient for MemoryBackend {
         let items = self.items.read().unwrap();
         Ok(items
             .get(collection_id)
-            .and_then(|items| items.iter().find(|item| item.id == item_id).cloned()))
+            .and_then(|items| items.get(item_id).cloned()))
     }
 }
 
@@ -115,29 +394,12 @@ impl TransactionClient for MemoryBackend {
     async fn add_collection(&mut self, collection: Collection) -> Result<()> {
         let mut collections = self.collections.write().unwrap();
         let _ = collections.insert(collection.id.clone(), collection);
+        self.changed();
         Ok(())
     }
 
     async fn add_item(&mut self, item: Item) -> Result<()> {
-        if let Some(collection_id) = item.collection.clone() {
-            if CollectionsClient::collection(self, &collection_id)
-                .await?
-                .is_none()
-            {
-                Err(Error::MemoryBackend(format!(
-                    "no collection with id='{collection_id}'",
-                )))
-            } else {
-                let mut items = self.items.write().unwrap();
-                items.entry(collection_id).or_default().push(item);
-                Ok(())
-            }
-        } else {
-            Err(Error::MemoryBackend(format!(
-                "collection not set on item: {}",
-                item.id
-            )))
-        }
+        self.insert_item(item).map(|_| ())
     }
 }
 
@@ -159,7 +421,74 @@ impl Backend for MemoryBackend {
     }
 
     fn has_filter(&self) -> bool {
-        false
+        true
+    }
+
+    fn has_sort(&self) -> bool {
+        true
+    }
+
+    fn has_free_text(&self) -> bool {
+        true
+    }
+
+    fn has_collection_wildcards(&self) -> bool {
+        self.collection_wildcards
+    }
+
+    fn has_offset_pagination(&self) -> bool {
+        true
+    }
+
+    async fn add_item_with_outcome(&mut self, item: Item) -> Result<ItemOutcome> {
+        self.insert_item(item)
+    }
+
+    async fn refresh_collections(&mut self) -> Result<()> {
+        let mut refreshed: Vec<Collection> =
+            self.collections.read().unwrap().values().cloned().collect();
+        {
+            let items = self.items.read().unwrap();
+            refreshed.retain_mut(|collection| {
+                if let Some(items) = items.get(&collection.id) {
+                    collection.refresh_from_items(items.values());
+                    true
+                } else {
+                    false
+                }
+            });
+        }
+        let mut collections = self.collections.write().unwrap();
+        for refreshed in refreshed {
+            if let Some(collection) = collections.get_mut(&refreshed.id) {
+                collection.extent = refreshed.extent;
+                collection.summaries = refreshed.summaries;
+            }
+        }
+        self.changed();
+        Ok(())
+    }
+
+    async fn collection_statistics(
+        &self,
+        collection_id: &str,
+    ) -> Result<Option<CollectionStatistics>> {
+        if !self.collections.read().unwrap().contains_key(collection_id) {
+            return Ok(None);
+        }
+        let items = self.items.read().unwrap();
+        let Some(items) = items.get(collection_id).filter(|items| !items.is_empty()) else {
+            return Ok(Some(CollectionStatistics {
+                item_count: 0,
+                extent: None,
+            }));
+        };
+        let mut collection = Collection::new(collection_id, "");
+        collection.refresh_from_items(items.values());
+        Ok(Some(CollectionStatistics {
+            item_count: items.len() as u64,
+            extent: Some(collection.extent),
+        }))
     }
 }
 
@@ -195,6 +524,50 @@ mod tests {
         backend
     }
 
+    #[tokio::test]
+    async fn refresh_collections() {
+        let mut backend = populated_backend().await;
+        let mut item = Item::new("item-d").collection("collection-id");
+        item.bbox = Some(stac::Bbox::new(1., 2., 3., 4.));
+        backend.add_item(item).await.unwrap();
+        let collection = backend.collection("collection-id").await.unwrap().unwrap();
+        assert_eq!(
+            collection.extent.spatial.bbox,
+            [stac::Bbox::new(-180., -90., 180., 90.)]
+        );
+        let generation = backend.generation.load(Ordering::Relaxed);
+        backend.refresh_collections().await.unwrap();
+        assert!(backend.generation.load(Ordering::Relaxed) > generation);
+        let collection = backend.collection("collection-id").await.unwrap().unwrap();
+        assert_eq!(
+            collection.extent.spatial.bbox,
+            [stac::Bbox::new(1., 2., 3., 4.)]
+        );
+    }
+
+    #[tokio::test]
+    async fn collection_statistics() {
+        let mut backend = populated_backend().await;
+        let mut item = Item::new("item-d").collection("collection-id");
+        item.bbox = Some(stac::Bbox::new(1., 2., 3., 4.));
+        backend.add_item(item).await.unwrap();
+        let statistics = backend
+            .collection_statistics("collection-id")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(statistics.item_count, 4);
+        let extent = statistics.extent.unwrap();
+        assert_eq!(extent.spatial.bbox, vec![stac::Bbox::new(1., 2., 3., 4.)]);
+        assert!(
+            backend
+                .collection_statistics("not-a-collection")
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
     #[tokio::test]
     async fn stream_items_across_pages_with_real_backend() {
         let backend = populated_backend().await;
@@ -228,6 +601,195 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn search_filter() {
+        let mut backend = populated_backend().await;
+        let mut item = Item::new("item-d").collection("collection-id");
+        let _ = item
+            .properties
+            .additional_fields
+            .insert("eo:cloud_cover".to_string(), 5.into());
+        backend.add_item(item).await.unwrap();
+        let mut item = Item::new("item-e").collection("collection-id");
+        let _ = item
+            .properties
+            .additional_fields
+            .insert("eo:cloud_cover".to_string(), 50.into());
+        backend.add_item(item).await.unwrap();
+
+        let mut search = Search::default();
+        search.filter = Some("eo:cloud_cover < 10".parse().unwrap());
+        let page = backend.search(search).await.unwrap();
+        assert_eq!(page.items.len(), 1);
+        assert_eq!(page.items[0]["id"], "item-d");
+
+        let mut search = Search::default();
+        search.filter = Some(stac::api::Filter::Cql2Json(
+            serde_json::json!({"op": "=", "args": [{"property": "id"}, "item-b"]})
+                .as_object()
+                .unwrap()
+                .clone(),
+        ));
+        let page = backend.search(search).await.unwrap();
+        assert_eq!(page.items.len(), 1);
+        assert_eq!(page.items[0]["id"], "item-b");
+
+        let mut search = Search::default();
+        search.filter = Some("this is not cql2".parse().unwrap());
+        let _ = backend.search(search).await.unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn search_offset() {
+        let backend = populated_backend().await;
+        let page = backend
+            .search(Search::default().limit(1u64).offset(1))
+            .await
+            .unwrap();
+        assert_eq!(page.items.len(), 1);
+        assert_eq!(
+            page.items[0].get("id").and_then(|value| value.as_str()),
+            Some("item-b")
+        );
+        assert_eq!(page.next.unwrap()["offset"], 2);
+        assert_eq!(page.prev.unwrap()["offset"], 0);
+    }
+
+    #[tokio::test]
+    async fn search_sortby() {
+        let backend = populated_backend().await;
+        let page = backend
+            .search(
+                Search::default()
+                    .sortby(vec!["-id".parse().unwrap()])
+                    .limit(2u64),
+            )
+            .await
+            .unwrap();
+        let ids: Vec<_> = page
+            .items
+            .iter()
+            .map(|item| item.get("id").and_then(|value| value.as_str()).unwrap())
+            .collect();
+        assert_eq!(ids, ["item-c", "item-b"]);
+        let page = backend
+            .search(
+                Search::default()
+                    .sortby(vec!["-id".parse().unwrap()])
+                    .limit(2u64)
+                    .offset(2),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            page.items[0].get("id").and_then(|value| value.as_str()),
+            Some("item-a")
+        );
+    }
+
+    #[tokio::test]
+    async fn search_collection_wildcards() {
+        let mut backend = populated_backend().await;
+        backend
+            .add_collection(Collection::new("other-collection", "another description"))
+            .await
+            .unwrap();
+        backend
+            .add_item(Item::new("item-d").collection("other-collection"))
+            .await
+            .unwrap();
+        let search = Search::default()
+            .collections(vec!["collection-*".to_string()])
+            .limit(10u64);
+        let page = backend.search(search.clone()).await.unwrap();
+        assert!(page.items.is_empty());
+
+        let backend = backend.collection_wildcards(true);
+        let page = backend.search(search).await.unwrap();
+        assert_eq!(page.items.len(), 3);
+        let search = Search::default()
+            .collections(vec!["*-collection".to_string()])
+            .limit(10u64);
+        let page = backend.search(search).await.unwrap();
+        assert_eq!(page.items.len(), 1);
+        let search = Search::default()
+            .collections(vec!["*".to_string()])
+            .limit(10u64);
+        let page = backend.search(search).await.unwrap();
+        assert_eq!(page.items.len(), 4);
+        let search = Search::default()
+            .collections(vec!["not-a-*".to_string()])
+            .limit(10u64);
+        let page = backend.search(search).await.unwrap();
+        assert!(page.items.is_empty());
+    }
+
+    #[tokio::test]
+    async fn collision_policies() {
+        let item = Item::new("item-a").collection("collection-id");
+
+        let mut backend = populated_backend().await;
+        assert_eq!(
+            backend.add_item_with_outcome(item.clone()).await.unwrap(),
+            ItemOutcome::Replaced
+        );
+        assert_eq!(
+            backend
+                .collect_items(Search::default())
+                .await
+                .unwrap()
+                .len(),
+            3
+        );
+
+        let mut backend = populated_backend()
+            .await
+            .collision_policy(CollisionPolicy::Error);
+        assert!(matches!(
+            backend.add_item(item.clone()).await.unwrap_err(),
+            Error::ItemExists { item_id, .. } if item_id == "item-a"
+        ));
+
+        let mut backend = populated_backend()
+            .await
+            .collision_policy(CollisionPolicy::Skip);
+        let mut skipped = item.clone();
+        let _ = skipped
+            .properties
+            .additional_fields
+            .insert("foo".into(), "bar".into());
+        assert_eq!(
+            backend.add_item_with_outcome(skipped).await.unwrap(),
+            ItemOutcome::Skipped
+        );
+        let stored = backend
+            .item("collection-id", "item-a")
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(stored.properties.additional_fields.is_empty());
+
+        let mut backend = populated_backend()
+            .await
+            .collision_policy(CollisionPolicy::Version);
+        assert_eq!(
+            backend.add_item_with_outcome(item.clone()).await.unwrap(),
+            ItemOutcome::Versioned("item-a-v2".to_string())
+        );
+        assert_eq!(
+            backend.add_item_with_outcome(item).await.unwrap(),
+            ItemOutcome::Versioned("item-a-v3".to_string())
+        );
+        assert_eq!(
+            backend
+                .collect_items(Search::default())
+                .await
+                .unwrap()
+                .len(),
+            5
+        );
+    }
+
     #[tokio::test]
     async fn collections_stream_with_real_backend() {
         let backend = populated_backend().await;
@@ -240,4 +802,39 @@ mod tests {
             .unwrap();
         assert_eq!(items.items.len(), 3);
     }
+
+    #[cfg(feature = "geoparquet")]
+    #[tokio::test]
+    async fn snapshot() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut backend = populated_backend().await;
+        backend
+            .add_collection(Collection::new("empty-collection", "no items"))
+            .await
+            .unwrap();
+        backend.to_path(temp_dir.path()).unwrap();
+
+        let backend = MemoryBackend::from_path(temp_dir.path()).unwrap();
+        let collections = backend.collections().await.unwrap();
+        assert_eq!(collections.len(), 2);
+        assert!(
+            backend
+                .item("collection-id", "item-b")
+                .await
+                .unwrap()
+                .is_some()
+        );
+        assert_eq!(
+            backend
+                .collect_items(Search::default())
+                .await
+                .unwrap()
+                .len(),
+            3
+        );
+
+        MemoryBackend::new().to_path(temp_dir.path()).unwrap();
+        let backend = MemoryBackend::from_path(temp_dir.path()).unwrap();
+        assert!(backend.collections().await.unwrap().is_empty());
+    }
 }
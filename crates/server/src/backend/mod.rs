@@ -4,13 +4,26 @@ mod memory;
 #[cfg(feature = "pgstac")]
 mod pgstac;
 
-use crate::Error;
+use crate::{Error, Format};
 #[cfg(feature = "duckdb")]
 pub use duckdb::DuckdbBackend;
+use futures_core::Stream;
 pub use memory::MemoryBackend;
+#[cfg(feature = "geoparquet")]
+pub use memory::{SNAPSHOT_COLLECTIONS_FILE_NAME, SNAPSHOT_ITEMS_FILE_NAME};
 #[cfg(feature = "pgstac")]
-pub use pgstac::PgstacBackend;
-use stac::api::{CollectionsClient, ItemsClient, StreamItemsClient, TransactionClient};
+pub use pgstac::{PgstacBackend, PgstacOptions, PgstacPage};
+use serde_json::Value;
+use stac::api::{
+    CollectionSearch, CollectionsClient, ItemsClient, Search, StreamItemsClient, TransactionClient,
+};
+use stac::{Collection, Extent, Item};
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::sync::mpsc::{Receiver, Sender};
 
 /// Storage backend for a STAC API.
 ///
@@ -45,7 +58,643 @@ pub trait Backend:
     /// ```
     /// use stac_server::{MemoryBackend, Backend};
     ///
-    /// assert!(!MemoryBackend::new().has_filter());
+    /// assert!(MemoryBackend::new().has_filter());
     /// ```
     fn has_filter(&self) -> bool;
+
+    /// Returns true if this backend has [sort](https://github.com/stac-api-extensions/sort) capabilities.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_server::{MemoryBackend, Backend};
+    ///
+    /// assert!(MemoryBackend::new().has_sort());
+    /// ```
+    fn has_sort(&self) -> bool;
+
+    /// Returns true if this backend has [free-text
+    /// search](https://github.com/stac-api-extensions/freetext-search)
+    /// capabilities for item search.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_server::{MemoryBackend, Backend};
+    ///
+    /// assert!(MemoryBackend::new().has_free_text());
+    /// ```
+    fn has_free_text(&self) -> bool;
+
+    /// Returns true if this backend treats `*` in search collections as a
+    /// wildcard.
+    ///
+    /// This is a non-standard extension, advertised with
+    /// [COLLECTION_WILDCARDS_URI](crate::COLLECTION_WILDCARDS_URI).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_server::{MemoryBackend, Backend};
+    ///
+    /// assert!(!MemoryBackend::new().has_collection_wildcards());
+    /// assert!(MemoryBackend::new().collection_wildcards(true).has_collection_wildcards());
+    /// ```
+    fn has_collection_wildcards(&self) -> bool;
+
+    /// Returns true if this backend supports the `offset` items and search
+    /// parameter.
+    ///
+    /// This is a non-standard extension, advertised with
+    /// [OFFSET_PAGINATION_URI](crate::OFFSET_PAGINATION_URI).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_server::{MemoryBackend, Backend};
+    ///
+    /// assert!(MemoryBackend::new().has_offset_pagination());
+    /// ```
+    fn has_offset_pagination(&self) -> bool;
+
+    /// Adds an item, returning what was done with it.
+    ///
+    /// The default implementation calls [TransactionClient::add_item] and
+    /// reports the item as [created](ItemOutcome::Created). Backends with a
+    /// [CollisionPolicy] report what the policy did instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Collection, Item, api::TransactionClient};
+    /// use stac_server::{Backend, ItemOutcome, MemoryBackend};
+    ///
+    /// let mut backend = MemoryBackend::new();
+    /// # tokio_test::block_on(async {
+    /// backend.add_collection(Collection::new("an-id", "a description")).await.unwrap();
+    /// let item = Item::new("item-id").collection("an-id");
+    /// let outcome = backend.add_item_with_outcome(item).await.unwrap();
+    /// assert_eq!(outcome, ItemOutcome::Created);
+    /// # })
+    /// ```
+    fn add_item_with_outcome(
+        &mut self,
+        item: Item,
+    ) -> impl Future<Output = Result<ItemOutcome, Error>> + Send {
+        async move { self.add_item(item).await.map(|()| ItemOutcome::Created) }
+    }
+
+    /// Recomputes the extents and summaries of all collections from their
+    /// items.
+    ///
+    /// Collection metadata drifts from the items as items are added. The
+    /// default implementation does nothing, which is right for backends that
+    /// compute collections from their items on every request.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Collection, Item, api::{CollectionsClient, TransactionClient}};
+    /// use stac_server::{Backend, MemoryBackend};
+    ///
+    /// let mut backend = MemoryBackend::new();
+    /// # tokio_test::block_on(async {
+    /// backend.add_collection(Collection::new("an-id", "a description")).await.unwrap();
+    /// let item: Item = stac::read("examples/simple-item.json").unwrap();
+    /// backend.add_item(item.collection("an-id")).await.unwrap();
+    /// backend.refresh_collections().await.unwrap();
+    /// let collection = backend.collection("an-id").await.unwrap().unwrap();
+    /// assert!(collection.extent.temporal.interval[0][0].is_some());
+    /// # })
+    /// ```
+    fn refresh_collections(&mut self) -> impl Future<Output = Result<(), Error>> + Send {
+        async { Ok(()) }
+    }
+
+    /// Returns a collection's item count and an extent computed from its
+    /// items, if this backend can compute them cheaply.
+    ///
+    /// The default implementation returns `None`. Backends that return
+    /// statistics but can't compute a fresh extent return `None` for the
+    /// extent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Collection, Item, api::TransactionClient};
+    /// use stac_server::{Backend, MemoryBackend};
+    ///
+    /// let mut backend = MemoryBackend::new();
+    /// # tokio_test::block_on(async {
+    /// backend.add_collection(Collection::new("an-id", "a description")).await.unwrap();
+    /// backend.add_item(Item::new("item-id").collection("an-id")).await.unwrap();
+    /// let statistics = backend.collection_statistics("an-id").await.unwrap().unwrap();
+    /// assert_eq!(statistics.item_count, 1);
+    /// # })
+    /// ```
+    fn collection_statistics(
+        &self,
+        _collection_id: &str,
+    ) -> impl Future<Output = Result<Option<CollectionStatistics>, Error>> + Send {
+        async { Ok(None) }
+    }
+
+    /// Returns the collections that match a [collection
+    /// search](https://github.com/stac-api-extensions/collection-search).
+    ///
+    /// The default implementation filters all collections by a simple
+    /// free-text match of `q` against their titles, descriptions, and
+    /// keywords. `q` is a comma-separated list of terms, and a collection
+    /// matches if any term does. A term matches if each of its words is a
+    /// word in the text, ignoring case. Other parameters are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Collection, api::{CollectionSearch, TransactionClient}};
+    /// use stac_server::{Backend, MemoryBackend};
+    ///
+    /// let mut backend = MemoryBackend::new();
+    /// # tokio_test::block_on(async {
+    /// backend.add_collection(Collection::new("an-id", "Sentinel-2 imagery")).await.unwrap();
+    /// backend.add_collection(Collection::new("another-id", "Landsat imagery")).await.unwrap();
+    /// let search = CollectionSearch::default().q("sentinel");
+    /// let collections = backend.collection_search(search).await.unwrap();
+    /// assert_eq!(collections.len(), 1);
+    /// # })
+    /// ```
+    fn collection_search(
+        &self,
+        search: CollectionSearch,
+    ) -> impl Future<Output = Result<Vec<Collection>, Error>> + Send {
+        async move {
+            let mut collections = self.collections().await?;
+            if let Some(q) = search.q.as_deref() {
+                let free_text = FreeText::new(q);
+                collections.retain(|collection| free_text.matches_collection(collection));
+            }
+            Ok(collections)
+        }
+    }
+
+    /// Returns the [queryables](https://github.com/stac-api-extensions/filter#queryables)
+    /// of a collection, or of all collections if `collection_id` is `None`.
+    ///
+    /// The default implementation returns `None`, and the [Api](crate::Api)
+    /// serves a schema that allows any property.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_server::{Backend, MemoryBackend};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let queryables = MemoryBackend::new().queryables(None).await.unwrap();
+    /// assert!(queryables.is_none());
+    /// # })
+    /// ```
+    fn queryables(
+        &self,
+        _collection_id: Option<&str>,
+    ) -> impl Future<Output = Result<Option<Value>, Error>> + Send {
+        async { Ok(None) }
+    }
+
+    /// Searches for a page of items and encodes them in a [Format], as a
+    /// stream of byte chunks.
+    ///
+    /// This is how `/search` responds to requests for formats other than
+    /// GeoJSON, which have no room for pagination links, so the items aren't
+    /// wrapped in an item collection. The default implementation encodes the
+    /// page from [ItemsClient::search] with [Format::encode] and returns it as
+    /// a single chunk. Backends that can produce an encoding more directly,
+    /// e.g. from their own storage, should override it and stream the chunks
+    /// as they're encoded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac::{Collection, Item, api::{Search, TransactionClient}};
+    /// use stac_server::{Backend, Format, MemoryBackend};
+    ///
+    /// let mut backend = MemoryBackend::new();
+    /// # tokio_test::block_on(async {
+    /// backend.add_collection(Collection::new("an-id", "a description")).await.unwrap();
+    /// backend.add_item(Item::new("item-id").collection("an-id")).await.unwrap();
+    /// let mut encoded = backend.search_encoded(Search::default(), Format::Ndjson).await.unwrap();
+    /// let bytes = encoded.next_chunk().await.unwrap().unwrap();
+    /// assert_eq!(bytes.iter().filter(|&&b| b == b'\n').count(), 1);
+    /// # })
+    /// ```
+    fn search_encoded(
+        &self,
+        search: Search,
+        format: Format,
+    ) -> impl Future<Output = Result<EncodedItems, Error>> + Send {
+        async move {
+            let item_collection = self.search(search).await?;
+            let bytes = format.encode(item_collection.items)?;
+            Ok(EncodedItems::from_bytes(bytes))
+        }
+    }
+
+    /// Checks that this backend can serve requests, e.g. that it can reach
+    /// its database.
+    ///
+    /// The default implementation always succeeds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_server::{Backend, MemoryBackend};
+    ///
+    /// # tokio_test::block_on(async {
+    /// MemoryBackend::new().health().await.unwrap();
+    /// # })
+    /// ```
+    fn health(&self) -> impl Future<Output = Result<(), Error>> + Send {
+        async { Ok(()) }
+    }
+}
+
+/// Refreshes a backend's collections every `period`, forever.
+///
+/// This is meant to be spawned as a background task next to a server that
+/// accepts transactions, e.g. with `tokio::spawn`. Errors are logged and
+/// don't stop the task.
+///
+/// # Examples
+///
+/// ```no_run
+/// use stac_server::MemoryBackend;
+/// use std::time::Duration;
+///
+/// # tokio_test::block_on(async {
+/// let backend = MemoryBackend::new();
+/// let _ = tokio::spawn(stac_server::refresh_collections(
+///     backend.clone(),
+///     Duration::from_secs(60),
+/// ));
+/// # })
+/// ```
+pub async fn refresh_collections<B: Backend>(mut backend: B, period: Duration) {
+    let mut interval = tokio::time::interval(period);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    // The first tick completes immediately, and collections are fresh at startup.
+    let _ = interval.tick().await;
+    loop {
+        let _ = interval.tick().await;
+        if let Err(err) = backend.refresh_collections().await {
+            tracing::warn!("error when refreshing collections: {err}");
+        }
+    }
+}
+
+/// What to do when an added item has the same id as an existing item in its
+/// collection.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CollisionPolicy {
+    /// Return an [Error::ItemExists](crate::Error::ItemExists).
+    Error,
+
+    /// Keep the existing item and drop the new one.
+    Skip,
+
+    /// Replace the existing item with the new one.
+    #[default]
+    Overwrite,
+
+    /// Keep the existing item, and add the new one with a version suffix on
+    /// its id, e.g. `an-id-v2`.
+    Version,
+}
+
+/// A collection's item count and extent, computed from its items.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CollectionStatistics {
+    /// The number of items in the collection.
+    pub item_count: u64,
+
+    /// The extent of the collection's items.
+    ///
+    /// `None` if the backend can't compute it cheaply, or if the collection
+    /// has no items.
+    pub extent: Option<Extent>,
+}
+
+/// Encoded search results, as a [Stream] of byte chunks.
+///
+/// Returned by [Backend::search_encoded]. Empty chunks are never yielded, so
+/// a search without any results has no chunks.
+#[derive(Debug)]
+pub struct EncodedItems {
+    first: Option<Vec<u8>>,
+    receiver: Receiver<Result<Vec<u8>, Error>>,
+}
+
+/// What was done with an added item.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ItemOutcome {
+    /// The item was added.
+    Created,
+
+    /// An item with the same id already existed, so the new item was dropped.
+    Skipped,
+
+    /// An item with the same id already existed, and was replaced.
+    Replaced,
+
+    /// An item with the same id already existed, so the new item was added
+    /// with this id instead.
+    Versioned(String),
+}
+
+impl ItemOutcome {
+    /// Returns this outcome as a lowercase string, e.g. `created`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_server::ItemOutcome;
+    ///
+    /// assert_eq!(ItemOutcome::Skipped.as_str(), "skipped");
+    /// ```
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ItemOutcome::Created => "created",
+            ItemOutcome::Skipped => "skipped",
+            ItemOutcome::Replaced => "replaced",
+            ItemOutcome::Versioned(_) => "versioned",
+        }
+    }
+}
+
+impl EncodedItems {
+    /// Creates a channel for encoded chunks.
+    ///
+    /// Chunks sent on the [Sender] are yielded in order, and the stream ends
+    /// when the sender is dropped. Up to `buffer` chunks are buffered, so the
+    /// encoding doesn't get far ahead of the client.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_server::EncodedItems;
+    ///
+    /// let (sender, mut encoded) = EncodedItems::channel(1);
+    /// # tokio_test::block_on(async {
+    /// sender.send(Ok(b"{}\n".to_vec())).await.unwrap();
+    /// drop(sender);
+    /// assert_eq!(encoded.next_chunk().await.unwrap().unwrap(), b"{}\n");
+    /// assert!(encoded.next_chunk().await.is_none());
+    /// # })
+    /// ```
+    pub fn channel(buffer: usize) -> (Sender<Result<Vec<u8>, Error>>, EncodedItems) {
+        let (sender, receiver) = tokio::sync::mpsc::channel(buffer);
+        (
+            sender,
+            EncodedItems {
+                first: None,
+                receiver,
+            },
+        )
+    }
+
+    /// Creates encoded items with a single chunk of bytes, or no chunks if
+    /// the bytes are empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_server::EncodedItems;
+    ///
+    /// # tokio_test::block_on(async {
+    /// assert!(EncodedItems::from_bytes(Vec::new()).next_chunk().await.is_none());
+    /// # })
+    /// ```
+    pub fn from_bytes(bytes: Vec<u8>) -> EncodedItems {
+        let (_, mut encoded) = EncodedItems::channel(1);
+        if !bytes.is_empty() {
+            encoded.first = Some(bytes);
+        }
+        encoded
+    }
+
+    /// Returns the next chunk, or `None` if there are no more chunks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_server::EncodedItems;
+    ///
+    /// let mut encoded = EncodedItems::from_bytes(b"{}\n".to_vec());
+    /// # tokio_test::block_on(async {
+    /// assert_eq!(encoded.next_chunk().await.unwrap().unwrap(), b"{}\n");
+    /// # })
+    /// ```
+    pub async fn next_chunk(&mut self) -> Option<Result<Vec<u8>, Error>> {
+        if let Some(first) = self.first.take() {
+            return Some(Ok(first));
+        }
+        loop {
+            match self.receiver.recv().await? {
+                Ok(bytes) if bytes.is_empty() => continue,
+                result => return Some(result),
+            }
+        }
+    }
+
+    /// Waits for the first chunk, and returns true if there isn't one.
+    pub(crate) async fn is_empty(&mut self) -> Result<bool, Error> {
+        match self.next_chunk().await.transpose()? {
+            Some(bytes) => {
+                self.first = Some(bytes);
+                Ok(false)
+            }
+            None => Ok(true),
+        }
+    }
+}
+
+impl Stream for EncodedItems {
+    type Item = Result<Vec<u8>, Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if let Some(first) = self.first.take() {
+            return Poll::Ready(Some(Ok(first)));
+        }
+        loop {
+            match self.receiver.poll_recv(cx) {
+                Poll::Ready(Some(Ok(bytes))) if bytes.is_empty() => continue,
+                poll => return poll,
+            }
+        }
+    }
+}
+
+impl std::str::FromStr for CollisionPolicy {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<CollisionPolicy, Error> {
+        match s {
+            "error" => Ok(CollisionPolicy::Error),
+            "skip" => Ok(CollisionPolicy::Skip),
+            "overwrite" => Ok(CollisionPolicy::Overwrite),
+            "version" => Ok(CollisionPolicy::Version),
+            _ => Err(Error::MemoryBackend(format!(
+                "unknown collision policy: {s}"
+            ))),
+        }
+    }
+}
+
+/// A parsed free-text query.
+///
+/// Terms are separated by commas and any of them can match. Each term is
+/// split into words, and all of a term's words must appear as words in the
+/// text, ignoring case.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct FreeText {
+    terms: Vec<Vec<String>>,
+}
+
+impl FreeText {
+    /// Parses a `q` string.
+    pub(crate) fn new(q: &str) -> FreeText {
+        let terms = q
+            .split(',')
+            .map(tokenize)
+            .filter(|words| !words.is_empty())
+            .collect();
+        FreeText { terms }
+    }
+
+    /// Parses a `q` value from a search, which is a string or an array of strings.
+    pub(crate) fn from_value(value: &Value) -> Option<FreeText> {
+        match value {
+            Value::String(q) => Some(FreeText::new(q)),
+            Value::Array(values) => {
+                let mut terms = Vec::new();
+                for value in values {
+                    terms.extend(FreeText::new(value.as_str()?).terms);
+                }
+                Some(FreeText { terms })
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns true if any term matches the texts.
+    pub(crate) fn matches<'a>(&self, texts: impl IntoIterator<Item = &'a str>) -> bool {
+        if self.terms.is_empty() {
+            return true;
+        }
+        let words: Vec<String> = texts.into_iter().flat_map(tokenize).collect();
+        self.terms
+            .iter()
+            .any(|term| term.iter().all(|word| words.contains(word)))
+    }
+
+    /// Returns true if any term matches an item's title, description, or keywords.
+    pub(crate) fn matches_item(&self, item: &Item) -> bool {
+        let keywords = item
+            .properties
+            .additional_fields
+            .get("keywords")
+            .and_then(Value::as_array);
+        self.matches(
+            item.properties
+                .title
+                .as_deref()
+                .into_iter()
+                .chain(item.properties.description.as_deref())
+                .chain(keywords.into_iter().flatten().filter_map(Value::as_str)),
+        )
+    }
+
+    /// Returns true if any term matches a collection's title, description, or keywords.
+    pub(crate) fn matches_collection(&self, collection: &Collection) -> bool {
+        self.matches(
+            collection
+                .title
+                .as_deref()
+                .into_iter()
+                .chain(std::iter::once(collection.description.as_str()))
+                .chain(collection.keywords.iter().flatten().map(String::as_str)),
+        )
+    }
+}
+
+fn tokenize(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Returns true if a collection id matches a pattern, where `*` matches any
+/// sequence of characters.
+pub(crate) fn collection_wildcard_matches(pattern: &str, id: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let Some(mut rest) = parts.next().and_then(|prefix| id.strip_prefix(prefix)) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    let Some((suffix, middle)) = parts.split_last() else {
+        return rest.is_empty();
+    };
+    for part in middle {
+        if let Some(index) = rest.find(part) {
+            rest = &rest[index + part.len()..];
+        } else {
+            return false;
+        }
+    }
+    rest.ends_with(suffix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FreeText, collection_wildcard_matches};
+    use serde_json::json;
+    use stac::Collection;
+
+    #[test]
+    fn collection_wildcards() {
+        for (pattern, id) in [
+            ("sentinel-*", "sentinel-2-l2a"),
+            ("*-l2a", "sentinel-2-l2a"),
+            ("sentinel-*-l2a", "sentinel-2-l2a"),
+            ("*", "anything"),
+            ("s*2*a", "sentinel-2-l2a"),
+            ("sentinel-2-l2a", "sentinel-2-l2a"),
+        ] {
+            assert!(collection_wildcard_matches(pattern, id), "{pattern} {id}");
+        }
+        for (pattern, id) in [
+            ("landsat-*", "sentinel-2-l2a"),
+            ("*-l1c", "sentinel-2-l2a"),
+            ("sentinel", "sentinel-2-l2a"),
+            ("a*a", "a"),
+        ] {
+            assert!(!collection_wildcard_matches(pattern, id), "{pattern} {id}");
+        }
+    }
+
+    #[test]
+    fn free_text() {
+        let mut collection = Collection::new("an-id", "Sentinel-2 Level-2A imagery");
+        collection.keywords = Some(vec!["ESA".to_string()]);
+        assert!(FreeText::new("sentinel").matches_collection(&collection));
+        assert!(FreeText::new("level 2a").matches_collection(&collection));
+        assert!(FreeText::new("landsat,esa").matches_collection(&collection));
+        assert!(!FreeText::new("landsat").matches_collection(&collection));
+        assert!(!FreeText::new("sentinel landsat").matches_collection(&collection));
+        assert!(!FreeText::new("senti").matches_collection(&collection));
+        assert!(
+            FreeText::from_value(&json!(["landsat", "imagery"]))
+                .unwrap()
+                .matches_collection(&collection)
+        );
+    }
 }
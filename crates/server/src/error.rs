@@ -19,10 +19,48 @@ pub enum Error {
     #[error(transparent)]
     StacDuckdb(#[from] stac_duckdb::Error),
 
+    /// [std::io::Error]
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
     /// A memory backend error.
     #[error("memory backend error: {0}")]
     MemoryBackend(String),
 
+    /// A pagination token couldn't be parsed.
+    #[error("invalid pagination token: {0}")]
+    InvalidToken(String),
+
+    /// A pagination token is older than the API's token TTL.
+    #[error("pagination token was issued {age}s ago, but tokens expire after {ttl}s")]
+    TokenExpired {
+        /// The age of the token, in seconds.
+        age: u64,
+
+        /// The token TTL, in seconds.
+        ttl: u64,
+    },
+
+    /// A request's limit is larger than the API's maximum limit.
+    #[error("limit={limit} is larger than the maximum limit={max_limit}")]
+    LimitTooLarge {
+        /// The requested limit.
+        limit: u64,
+
+        /// The maximum limit.
+        max_limit: u64,
+    },
+
+    /// An item with the same id already exists in the collection.
+    #[error("item with id='{item_id}' already exists in collection='{collection_id}'")]
+    ItemExists {
+        /// The collection id.
+        collection_id: String,
+
+        /// The item id.
+        item_id: String,
+    },
+
     /// [pgstac::Error]
     #[cfg(feature = "pgstac")]
     #[error(transparent)]
@@ -44,6 +82,14 @@ pub enum Error {
     #[error("this backend is read-only")]
     ReadOnly,
 
+    /// A request's `bbox-crs` or `crs` isn't one of the API's crs.
+    #[error("crs is not supported by this api: {0}")]
+    UnsupportedCrs(String),
+
+    /// The requested response format isn't supported.
+    #[error("unsupported format: {0}")]
+    UnsupportedFormat(String),
+
     /// [tokio_postgres::Error]
     #[cfg(feature = "pgstac")]
     #[error(transparent)]
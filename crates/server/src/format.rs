@@ -0,0 +1,226 @@
+use crate::{Error, Result};
+use stac::api::Item;
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+/// The media type of newline-delimited JSON.
+pub const APPLICATION_NDJSON: &str = "application/x-ndjson";
+
+/// The media type of stac-geoparquet.
+pub const APPLICATION_PARQUET: &str = stac::mime::APPLICATION_PARQUET;
+
+/// The encoding of an item search response.
+///
+/// Clients pick a format with the `Accept` header, or with a `format`
+/// search parameter, which takes precedence.
+///
+/// # Examples
+///
+/// ```
+/// use stac_server::Format;
+///
+/// let format: Format = "parquet".parse().unwrap();
+/// assert_eq!(format, Format::Geoparquet);
+/// assert_eq!(
+///     Format::from_accept("application/x-ndjson, application/geo+json;q=0.5"),
+///     Some(Format::Ndjson)
+/// );
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Format {
+    /// A GeoJSON feature collection with pagination links.
+    #[default]
+    GeoJson,
+
+    /// Newline-delimited JSON, one item per line.
+    Ndjson,
+
+    /// [stac-geoparquet](https://github.com/stac-utils/stac-geoparquet).
+    ///
+    /// Requires the `geoparquet` feature.
+    Geoparquet,
+}
+
+impl Format {
+    /// Returns the best format for an `Accept` header, or `None` if the
+    /// header doesn't accept any format that we can produce.
+    ///
+    /// Media types are tried in order of their quality values, and types
+    /// with `q=0` are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_server::Format;
+    ///
+    /// assert_eq!(Format::from_accept("*/*"), Some(Format::GeoJson));
+    /// assert_eq!(
+    ///     Format::from_accept("application/vnd.apache.parquet"),
+    ///     Some(Format::Geoparquet)
+    /// );
+    /// assert_eq!(Format::from_accept("text/html"), None);
+    /// ```
+    pub fn from_accept(accept: &str) -> Option<Format> {
+        let mut media_types: Vec<(&str, f32)> = accept
+            .split(',')
+            .filter_map(|media_range| {
+                let mut parts = media_range.split(';').map(str::trim);
+                let media_type = parts.next().filter(|media_type| !media_type.is_empty())?;
+                let quality = parts
+                    .filter_map(|parameter| parameter.strip_prefix("q="))
+                    .find_map(|quality| quality.parse().ok())
+                    .unwrap_or(1.0);
+                Some((media_type, quality))
+            })
+            .filter(|(_, quality)| *quality > 0.0)
+            .collect();
+        media_types.sort_by(|a, b| b.1.total_cmp(&a.1));
+        media_types
+            .into_iter()
+            .find_map(|(media_type, _)| Format::from_media_type(media_type))
+    }
+
+    fn from_media_type(media_type: &str) -> Option<Format> {
+        match media_type.to_ascii_lowercase().as_str() {
+            "application/geo+json" | "application/json" | "application/*" | "*/*" => {
+                Some(Format::GeoJson)
+            }
+            "application/x-ndjson" | "application/ndjson" => Some(Format::Ndjson),
+            "application/vnd.apache.parquet" | "application/x-parquet" => Some(Format::Geoparquet),
+            _ => None,
+        }
+    }
+
+    /// Returns this format's media type, used as the response's `Content-Type`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_server::Format;
+    ///
+    /// assert_eq!(Format::Ndjson.content_type(), "application/x-ndjson");
+    /// ```
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Format::GeoJson => stac::mime::APPLICATION_GEOJSON,
+            Format::Ndjson => APPLICATION_NDJSON,
+            Format::Geoparquet => APPLICATION_PARQUET,
+        }
+    }
+
+    /// Encodes items in this format.
+    ///
+    /// GeoJSON is encoded as a bare feature collection, without links. Empty
+    /// stac-geoparquet is encoded as no bytes, since a parquet file needs at
+    /// least one item to infer its schema.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_server::Format;
+    ///
+    /// let mut item = serde_json::Map::new();
+    /// let _ = item.insert("id".into(), "an-id".into());
+    /// let bytes = Format::Ndjson.encode(vec![item.clone(), item]).unwrap();
+    /// assert_eq!(bytes, b"{\"id\":\"an-id\"}\n{\"id\":\"an-id\"}\n");
+    /// ```
+    pub fn encode(&self, items: Vec<Item>) -> Result<Vec<u8>> {
+        match self {
+            Format::GeoJson => Ok(serde_json::to_vec(&serde_json::json!({
+                "type": "FeatureCollection",
+                "features": items,
+            }))?),
+            Format::Ndjson => {
+                let mut bytes = Vec::new();
+                for item in items {
+                    serde_json::to_writer(&mut bytes, &item)?;
+                    bytes.push(b'\n');
+                }
+                Ok(bytes)
+            }
+            #[cfg(feature = "geoparquet")]
+            Format::Geoparquet => {
+                let mut bytes = Vec::new();
+                if !items.is_empty() {
+                    let items = items
+                        .into_iter()
+                        .map(stac::Item::try_from)
+                        .collect::<std::result::Result<Vec<_>, _>>()?;
+                    stac::geoparquet::into_writer(&mut bytes, items)?;
+                }
+                Ok(bytes)
+            }
+            #[cfg(not(feature = "geoparquet"))]
+            Format::Geoparquet => Err(Error::UnsupportedFormat(
+                "geoparquet (stac-server was built without the geoparquet feature)".to_string(),
+            )),
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Format> {
+        match s.to_ascii_lowercase().as_str() {
+            "geojson" | "json" => Ok(Format::GeoJson),
+            "ndjson" | "jsonl" => Ok(Format::Ndjson),
+            "geoparquet" | "parquet" | "stac-geoparquet" => Ok(Format::Geoparquet),
+            _ => Err(Error::UnsupportedFormat(s.to_string())),
+        }
+    }
+}
+
+impl Display for Format {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Format::GeoJson => f.write_str("geojson"),
+            Format::Ndjson => f.write_str("ndjson"),
+            Format::Geoparquet => f.write_str("geoparquet"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Format;
+
+    #[test]
+    fn from_accept() {
+        assert_eq!(
+            Format::from_accept("application/geo+json;q=0.9, application/vnd.apache.parquet"),
+            Some(Format::Geoparquet)
+        );
+        assert_eq!(
+            Format::from_accept("application/x-ndjson;q=0, application/json"),
+            Some(Format::GeoJson)
+        );
+        assert_eq!(
+            Format::from_accept("text/html, application/x-ndjson;q=0.1"),
+            Some(Format::Ndjson)
+        );
+        assert_eq!(Format::from_accept(""), None);
+    }
+
+    #[test]
+    fn from_str() {
+        assert_eq!("NDJSON".parse::<Format>().unwrap(), Format::Ndjson);
+        let _ = "csv".parse::<Format>().unwrap_err();
+    }
+
+    #[cfg(feature = "geoparquet")]
+    #[test]
+    fn encode_geoparquet() {
+        let mut item = stac::Item::new("an-id");
+        item.geometry = Some(stac::Geometry::new_point(vec![-105.1, 41.1]));
+        let item = serde_json::to_value(item).unwrap();
+        let serde_json::Value::Object(item) = item else {
+            panic!("items are objects");
+        };
+        let bytes = Format::Geoparquet.encode(vec![item]).unwrap();
+        assert!(bytes.starts_with(b"PAR1"));
+        assert!(Format::Geoparquet.encode(Vec::new()).unwrap().is_empty());
+    }
+}
@@ -33,16 +33,23 @@
 mod api;
 mod backend;
 mod error;
+mod format;
 #[cfg(feature = "axum")]
 pub mod routes;
 
-pub use api::Api;
+pub use api::{Api, ApiBuilder, Document};
 #[cfg(feature = "duckdb")]
 pub use backend::DuckdbBackend;
+pub use backend::{
+    Backend, CollectionStatistics, CollisionPolicy, EncodedItems, ItemOutcome, MemoryBackend,
+    refresh_collections,
+};
 #[cfg(feature = "pgstac")]
-pub use backend::PgstacBackend;
-pub use backend::{Backend, MemoryBackend};
+pub use backend::{PgstacBackend, PgstacOptions, PgstacPage};
+#[cfg(feature = "geoparquet")]
+pub use backend::{SNAPSHOT_COLLECTIONS_FILE_NAME, SNAPSHOT_ITEMS_FILE_NAME};
 pub use error::Error;
+pub use format::{APPLICATION_NDJSON, APPLICATION_PARQUET, Format};
 
 /// A crate-specific result type.
 pub type Result<T> = std::result::Result<T, Error>;
@@ -56,6 +63,39 @@ pub const DEFAULT_DESCRIPTION: &str = "A STAC API server written in Rust";
 /// The default limit.
 pub const DEFAULT_LIMIT: u64 = 10;
 
+/// The default maximum limit.
+///
+/// Requests with a larger `limit` are rejected with a 400.
+pub const DEFAULT_MAX_LIMIT: u64 = 10_000;
+
+/// The default maximum size, in bytes, of an intersects geometry in GET pagination links.
+///
+/// Searches with larger geometries get POST pagination links instead.
+pub const DEFAULT_MAX_GET_INTERSECTS_SIZE: usize = 4096;
+
+/// The conformance class for `*` wildcards in search collections.
+///
+/// This isn't part of the STAC API spec. Backends only advertise it when
+/// wildcards are enabled, e.g. with
+/// [MemoryBackend::collection_wildcards].
+pub const COLLECTION_WILDCARDS_URI: &str =
+    "https://github.com/stac-utils/rustac/tree/main/crates/server#collection-wildcards";
+
+/// The conformance class for the [transaction
+/// extension](https://github.com/stac-api-extensions/transaction).
+///
+/// Only advertised when [Api::transactions] is on, since the API then only
+/// has the POST `/collections/{collectionId}/items` endpoint.
+pub const TRANSACTION_URI: &str =
+    "https://api.stacspec.org/v1.0.0/ogcapi-features/extensions/transaction";
+
+/// The conformance class for the `offset` items and search parameter.
+///
+/// This isn't part of the STAC API spec. Backends that can page by position
+/// advertise it, and use `offset` in their pagination links.
+pub const OFFSET_PAGINATION_URI: &str =
+    "https://github.com/stac-utils/rustac/tree/main/crates/server#offset-pagination";
+
 #[cfg(test)]
 use tokio_test as _;
 
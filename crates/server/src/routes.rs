@@ -1,21 +1,30 @@
 //! Routes for serving API endpoints.
 
-use crate::{Api, Backend};
+use crate::{Api, Backend, Document, Format, ItemOutcome};
 use axum::{
     Json, Router,
-    extract::{Path, Query, State, rejection::JsonRejection},
-    http::{HeaderValue, StatusCode, header::CONTENT_TYPE},
+    body::Body,
+    extract::{FromRef, Path, Query, Request, State, rejection::JsonRejection},
+    http::{
+        HeaderMap, HeaderValue, StatusCode,
+        header::{
+            ACCEPT, ACCEPT_LANGUAGE, ALLOW, CONTENT_LANGUAGE, CONTENT_LENGTH, CONTENT_TYPE,
+            LOCATION, VARY,
+        },
+    },
+    middleware::{Next, from_fn_with_state},
     response::{Html, IntoResponse, Response},
-    routing::{get, post},
+    routing::get,
 };
 use bytes::{BufMut, BytesMut};
 use http::Method;
 use serde::Serialize;
-use stac::api::{Collections, GetItems, GetSearch, ItemCollection, Items, Root, Search};
-use stac::{
-    Collection, Item,
-    mime::{APPLICATION_GEOJSON, APPLICATION_OPENAPI_3_0},
+use serde_json::json;
+use stac::api::{
+    CollectionSearch, Collections, CollectionsClient, GetCollectionSearch, GetItems, GetSearch,
+    ItemCollection, Items, Root, Search,
 };
+use stac::{Collection, Item, mime::APPLICATION_GEOJSON};
 use tower_http::{cors::CorsLayer, trace::TraceLayer};
 
 /// Errors for our axum routes.
@@ -30,6 +39,18 @@ pub enum Error {
 
     /// An error raised when it's a bad request from the client.
     BadRequest(String),
+
+    /// An error raised when the request conflicts with existing data, e.g.
+    /// an item with the same id already exists.
+    Conflict(String),
+
+    /// An error raised when the client asks for a response format that the
+    /// server can't produce.
+    NotAcceptable(String),
+
+    /// An error raised when the endpoint exists, but doesn't accept the
+    /// request's method, e.g. writes to a read-only API.
+    MethodNotAllowed(String),
 }
 
 type Result<T> = std::result::Result<T, Error>;
@@ -45,6 +66,17 @@ impl IntoResponse for Error {
             Error::Server(error) => (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()),
             Error::NotFound(message) => (StatusCode::NOT_FOUND, message),
             Error::BadRequest(message) => (StatusCode::BAD_REQUEST, message),
+            Error::Conflict(message) => (StatusCode::CONFLICT, message),
+            Error::NotAcceptable(message) => (StatusCode::NOT_ACCEPTABLE, message),
+            // Only writes are refused, so reads are still allowed.
+            Error::MethodNotAllowed(message) => {
+                return (
+                    StatusCode::METHOD_NOT_ALLOWED,
+                    [(ALLOW, GET_METHODS)],
+                    message,
+                )
+                    .into_response();
+            }
         }
         .into_response()
     }
@@ -52,7 +84,16 @@ impl IntoResponse for Error {
 
 impl From<crate::Error> for Error {
     fn from(error: crate::Error) -> Self {
-        Error::Server(error)
+        match error {
+            crate::Error::ItemExists { .. } => Error::Conflict(error.to_string()),
+            crate::Error::UnsupportedFormat(_) => Error::NotAcceptable(error.to_string()),
+            crate::Error::ReadOnly => Error::MethodNotAllowed(error.to_string()),
+            crate::Error::LimitTooLarge { .. }
+            | crate::Error::InvalidToken(_)
+            | crate::Error::TokenExpired { .. }
+            | crate::Error::UnsupportedCrs(_) => Error::BadRequest(error.to_string()),
+            _ => Error::Server(error),
+        }
     }
 }
 
@@ -91,6 +132,11 @@ where
 
 /// Creates an [axum::Router] from an [Api].
 ///
+/// This router has all endpoints, permissive CORS, request tracing, and
+/// [language negotiation](negotiate_language), and uses the [Api] as its
+/// state. To embed the STAC API in an existing application with its own state
+/// and middleware, use [router] (or one of the more granular routers) instead.
+///
 /// # Examples
 ///
 /// ```
@@ -100,23 +146,266 @@ where
 /// let router = routes::from_api(api);
 /// ```
 pub fn from_api<B: Backend>(api: Api<B>) -> Router {
-    Router::new()
-        .route("/", get(root))
-        .route("/api", get(service_desc))
-        .route("/api.html", get(service_doc))
-        .route("/conformance", get(conformance))
-        .route("/queryables", get(queryables))
-        .route("/collections", get(collections))
-        .route("/collections/{collection_id}", get(collection))
-        .route("/collections/{collection_id}/items", get(items))
-        .route("/collections/{collection_id}/items/{item_id}", get(item))
-        .route("/search", get(get_search))
-        .route("/search", post(post_search))
+    router::<B, Api<B>>()
+        .layer(from_fn_with_state(api.clone(), negotiate_language::<B>))
         .layer(CorsLayer::permissive()) // TODO make this configurable
         .layer(TraceLayer::new_for_http())
         .with_state(api)
 }
 
+/// Creates an [axum::Router] with all STAC API endpoints, generic over the
+/// router's state.
+///
+/// No middleware is added. The handlers extract an `State<Api<B>>`, so the
+/// application state `S` must implement `FromRef<S>` for [Api] — for an [Api]
+/// itself, that's automatic. The [Api]'s root href is used to build links, so
+/// when nesting this router under a path (e.g. `/stac`), that path must be
+/// included in the root href passed to [Api::new].
+///
+/// # Examples
+///
+/// ```
+/// use axum::{Router, extract::FromRef, routing::get};
+/// use stac_server::{Api, MemoryBackend, routes};
+///
+/// #[derive(Clone)]
+/// struct AppState {
+///     api: Api<MemoryBackend>,
+///     greeting: String,
+/// }
+///
+/// impl FromRef<AppState> for Api<MemoryBackend> {
+///     fn from_ref(state: &AppState) -> Api<MemoryBackend> {
+///         state.api.clone()
+///     }
+/// }
+///
+/// let state = AppState {
+///     api: Api::new(MemoryBackend::new(), "http://example.com/stac").unwrap(),
+///     greeting: "hello".to_string(),
+/// };
+/// let app: Router = Router::new()
+///     .route("/", get(|| async { "my app" }))
+///     .nest("/stac", routes::router::<MemoryBackend, AppState>())
+///     .with_state(state);
+/// ```
+pub fn router<B, S>() -> Router<S>
+where
+    B: Backend,
+    Api<B>: FromRef<S>,
+    S: Clone + Send + Sync + 'static,
+{
+    core_router::<B, S>()
+        .merge(features_router::<B, S>())
+        .merge(search_router::<B, S>())
+}
+
+/// Creates an [axum::Router] with the [core conformance
+/// class](https://github.com/radiantearth/stac-api-spec/tree/release/v1.0.0/core#endpoints)
+/// endpoints, plus `/conformance`, `/queryables`, `/healthz`, and `/assets/{path}`.
+///
+/// See [router] for the state requirements.
+pub fn core_router<B, S>() -> Router<S>
+where
+    B: Backend,
+    Api<B>: FromRef<S>,
+    S: Clone + Send + Sync + 'static,
+{
+    Router::new()
+        .route("/", get(root::<B>).options(options_get))
+        .route("/api", get(service_desc::<B>).options(options_get))
+        .route("/api.html", get(service_doc::<B>).options(options_get))
+        .route("/conformance", get(conformance::<B>).options(options_get))
+        .route("/queryables", get(queryables::<B>).options(options_get))
+        .route("/healthz", get(healthz::<B>).options(options_get))
+        .route("/assets/{*path}", get(asset::<B>).options(options_get))
+}
+
+/// Creates an [axum::Router] with the [ogcapi-features conformance
+/// class](https://github.com/radiantearth/stac-api-spec/blob/release/v1.0.0/ogcapi-features/README.md#endpoints)
+/// endpoints, i.e. `/collections` and everything under it.
+///
+/// See [router] for the state requirements.
+pub fn features_router<B, S>() -> Router<S>
+where
+    B: Backend,
+    Api<B>: FromRef<S>,
+    S: Clone + Send + Sync + 'static,
+{
+    Router::new()
+        .route("/collections", get(collections::<B>).options(options_get))
+        .route(
+            "/collections/{collection_id}",
+            get(collection::<B>).options(options_get),
+        )
+        .route(
+            "/collections/{collection_id}/queryables",
+            get(collection_queryables::<B>).options(options_get),
+        )
+        .route(
+            "/collections/{collection_id}/items",
+            get(items::<B>)
+                .post(create_item::<B>)
+                .options(options_items::<B>),
+        )
+        .route(
+            "/collections/{collection_id}/items/{item_id}",
+            get(item::<B>).options(options_get),
+        )
+}
+
+/// Creates an [axum::Router] with the GET and POST `/search` endpoints from
+/// the [item search conformance
+/// class](https://github.com/radiantearth/stac-api-spec/tree/release/v1.0.0/item-search).
+///
+/// See [router] for the state requirements.
+pub fn search_router<B, S>() -> Router<S>
+where
+    B: Backend,
+    Api<B>: FromRef<S>,
+    S: Clone + Send + Sync + 'static,
+{
+    Router::new().route(
+        "/search",
+        get(get_search::<B>)
+            .post(post_search::<B>)
+            .options(options_search),
+    )
+}
+
+/// Responds to OPTIONS requests for GET-only endpoints.
+///
+/// Every GET endpoint also responds to HEAD, and requests with any other
+/// method get a `405 Method Not Allowed` with an `Allow` header.
+pub async fn options_get() -> Response {
+    allow(GET_METHODS)
+}
+
+/// Responds to OPTIONS requests for the `/search` endpoint.
+pub async fn options_search() -> Response {
+    allow(SEARCH_METHODS)
+}
+
+/// Responds to OPTIONS requests for the collection items endpoint.
+///
+/// POST is only allowed if [Api::transactions] is on.
+pub async fn options_items<B: Backend>(State(api): State<Api<B>>) -> Response {
+    if api.transactions {
+        allow(ITEMS_METHODS)
+    } else {
+        allow(GET_METHODS)
+    }
+}
+
+const GET_METHODS: &str = "GET, HEAD, OPTIONS";
+const SEARCH_METHODS: &str = "GET, HEAD, POST, OPTIONS";
+const ITEMS_METHODS: &str = SEARCH_METHODS;
+
+/// Middleware that localizes JSON responses to the request's
+/// `Accept-Language` header.
+///
+/// This does nothing unless [Api::language_negotiation] is true. The response
+/// object, and any collections or features it contains, are localized with
+/// [stac::language::localize] to the best language they have available. If
+/// the response object itself was localized, its language is returned in the
+/// `Content-Language` header.
+///
+/// # Examples
+///
+/// ```
+/// use axum::middleware::from_fn_with_state;
+/// use stac_server::{Api, MemoryBackend, routes};
+///
+/// let api = Api::new(MemoryBackend::new(), "http://stac.test")
+///     .unwrap()
+///     .language_negotiation(true);
+/// let router = routes::router::<MemoryBackend, Api<MemoryBackend>>()
+///     .layer(from_fn_with_state(
+///         api.clone(),
+///         routes::negotiate_language::<MemoryBackend>,
+///     ))
+///     .with_state(api);
+/// ```
+pub async fn negotiate_language<B: Backend>(
+    State(api): State<Api<B>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let accept_language = request
+        .headers()
+        .get(ACCEPT_LANGUAGE)
+        .and_then(|value| value.to_str().ok())
+        .map(String::from);
+    let mut response = next.run(request).await;
+    if !api.language_negotiation {
+        return response;
+    }
+    let _ = response
+        .headers_mut()
+        .append(VARY, HeaderValue::from_static("accept-language"));
+    let Some(accept_language) = accept_language else {
+        return response;
+    };
+    let is_json = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|content_type| {
+            content_type.contains("json") && !content_type.starts_with(crate::APPLICATION_NDJSON)
+        });
+    if !response.status().is_success() || !is_json {
+        return response;
+    }
+    let (mut parts, body) = response.into_parts();
+    let bytes = match axum::body::to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(err) => return (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()).into_response(),
+    };
+    let Ok(mut value) = serde_json::from_slice::<serde_json::Value>(&bytes) else {
+        return Response::from_parts(parts, Body::from(bytes));
+    };
+    let language = value
+        .as_object_mut()
+        .and_then(|object| localize(object, &accept_language));
+    if let Some(language) = language.and_then(|language| HeaderValue::from_str(&language).ok()) {
+        let _ = parts.headers.insert(CONTENT_LANGUAGE, language);
+    }
+    let _ = parts.headers.remove(CONTENT_LENGTH);
+    match serde_json::to_vec(&value) {
+        Ok(bytes) => Response::from_parts(parts, Body::from(bytes)),
+        Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()).into_response(),
+    }
+}
+
+/// Localizes an object and its collections or features, returning the
+/// object's language if it was localized.
+fn localize(
+    object: &mut serde_json::Map<String, serde_json::Value>,
+    accept_language: &str,
+) -> Option<String> {
+    for key in ["collections", "features"] {
+        if let Some(children) = object
+            .get_mut(key)
+            .and_then(serde_json::Value::as_array_mut)
+        {
+            for child in children
+                .iter_mut()
+                .filter_map(serde_json::Value::as_object_mut)
+            {
+                let _ = localize(child, accept_language);
+            }
+        }
+    }
+    let available = stac::language::available(object);
+    let code = stac::language::negotiate(accept_language, available.iter().map(String::as_str))?
+        .to_string();
+    stac::language::localize(object, &code).then_some(code)
+}
+
+fn allow(methods: &'static str) -> Response {
+    (StatusCode::NO_CONTENT, [(ALLOW, methods)]).into_response()
+}
+
 /// Returns the `/` endpoint from the [core conformance
 /// class](https://github.com/radiantearth/stac-api-spec/tree/release/v1.0.0/core#endpoints).
 pub async fn root<B: Backend>(State(api): State<Api<B>>) -> Result<Json<Root>> {
@@ -125,22 +414,45 @@ pub async fn root<B: Backend>(State(api): State<Api<B>>) -> Result<Json<Root>> {
 
 /// Returns the `/api` endpoint from the [core conformance
 /// class](https://github.com/radiantearth/stac-api-spec/tree/release/v1.0.0/core#endpoints).
-pub async fn service_desc() -> Response {
-    // The OpenAPI definition is completely stolen from [stac-server](https://github.com/stac-utils/stac-server/blob/dd7e3acbf47485425e2068fd7fbbceeafe4b4e8c/src/lambdas/api/openapi.yaml).
+///
+/// This is the [Api::service_desc] document, which defaults to an OpenAPI
+/// definition of the STAC API.
+pub async fn service_desc<B: Backend>(State(api): State<Api<B>>) -> Response {
+    // The default OpenAPI definition is completely stolen from [stac-server](https://github.com/stac-utils/stac-server/blob/dd7e3acbf47485425e2068fd7fbbceeafe4b4e8c/src/lambdas/api/openapi.yaml).
     //
     // TODO add a script to update the definition in this library.
-    (
-        [(CONTENT_TYPE, APPLICATION_OPENAPI_3_0)],
-        include_str!("openapi.yaml"),
-    )
-        .into_response()
+    document(&api.service_desc)
 }
 
 /// Returns the `/api.html` endpoint from the [core conformance
 /// class](https://github.com/radiantearth/stac-api-spec/tree/release/v1.0.0/core#endpoints).
-pub async fn service_doc() -> Response {
-    // The redoc file is completely stolen from [stac-server](https://github.com/stac-utils/stac-server/blob/dd7e3acbf47485425e2068fd7fbbceeafe4b4e8c/src/lambdas/api/redoc.html).
-    Html(include_str!("redoc.html")).into_response()
+///
+/// This is the [Api::service_doc] document, which defaults to a
+/// [Redoc](https://github.com/Redocly/redoc) page for the `/api` definition.
+pub async fn service_doc<B: Backend>(State(api): State<Api<B>>) -> Response {
+    // The default redoc file is completely stolen from [stac-server](https://github.com/stac-utils/stac-server/blob/dd7e3acbf47485425e2068fd7fbbceeafe4b4e8c/src/lambdas/api/redoc.html).
+    Html(api.service_doc.body.to_vec()).into_response()
+}
+
+/// Returns a static asset from the `/assets/{path}` endpoint.
+///
+/// Assets are added with [ApiBuilder::asset](crate::ApiBuilder::asset).
+pub async fn asset<B: Backend>(
+    State(api): State<Api<B>>,
+    Path(path): Path<String>,
+) -> Result<Response> {
+    api.assets
+        .get(&path)
+        .map(document)
+        .ok_or_else(|| Error::NotFound(format!("no asset at path='{path}'")))
+}
+
+fn document(document: &Document) -> Response {
+    (
+        [(CONTENT_TYPE, document.content_type.clone())],
+        document.body.to_vec(),
+    )
+        .into_response()
 }
 
 /// Returns the `/conformance` endpoint from the [ogcapi-features conformance
@@ -149,19 +461,68 @@ pub async fn conformance<B: Backend>(State(api): State<Api<B>>) -> Response {
     Json(api.conformance()).into_response()
 }
 
+/// Returns the `/healthz` endpoint, which reports whether the backend is
+/// reachable.
+///
+/// Responds with `200 OK` if the [backend's health check](Backend::health)
+/// passes and `503 Service Unavailable` if it doesn't, so load balancers can
+/// route around a server that can't reach its database.
+pub async fn healthz<B: Backend>(State(api): State<Api<B>>) -> Response {
+    match api.backend.health().await {
+        Ok(()) => Json(json!({"status": "ok"})).into_response(),
+        Err(err) => {
+            tracing::warn!("health check failed: {err}");
+            (
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(json!({"status": "unavailable", "description": err.to_string()})),
+            )
+                .into_response()
+        }
+    }
+}
+
 /// Returns the `/queryables` endpoint.
-pub async fn queryables<B: Backend>(State(api): State<Api<B>>) -> Response {
-    (
+pub async fn queryables<B: Backend>(State(api): State<Api<B>>) -> Result<Response> {
+    let queryables = api.queryables().await?;
+    Ok((
         [(CONTENT_TYPE, "application/schema+json")],
-        Json(api.queryables()),
+        Json(queryables),
     )
-        .into_response()
+        .into_response())
+}
+
+/// Returns the `/collections/{collectionId}/queryables` endpoint.
+pub async fn collection_queryables<B: Backend>(
+    State(api): State<Api<B>>,
+    Path(collection_id): Path<String>,
+) -> Result<Response> {
+    let queryables = api
+        .collection_queryables(&collection_id)
+        .await?
+        .ok_or_else(|| Error::NotFound(format!("no collection with id='{collection_id}'")))?;
+    Ok((
+        [(CONTENT_TYPE, "application/schema+json")],
+        Json(queryables),
+    )
+        .into_response())
 }
 
 /// Returns the `/collections` endpoint from the [ogcapi-features conformance
 /// class](https://github.com/radiantearth/stac-api-spec/blob/release/v1.0.0/ogcapi-features/README.md#endpoints).
-pub async fn collections<B: Backend>(State(api): State<Api<B>>) -> Result<Json<Collections>> {
-    api.collections().await.map(Json).map_err(Error::from)
+///
+/// The `q` parameter of the [free-text
+/// search](https://github.com/stac-api-extensions/freetext-search) extension
+/// filters the collections.
+pub async fn collections<B: Backend>(
+    State(api): State<Api<B>>,
+    search: Query<GetCollectionSearch>,
+) -> Result<Json<Collections>> {
+    let search = CollectionSearch::try_from(search.0)
+        .map_err(|error| Error::BadRequest(error.to_string()))?;
+    api.collection_search(search)
+        .await
+        .map(Json)
+        .map_err(Error::from)
 }
 
 /// Returns the `/collections/{collectionId}` endpoint from the [ogcapi-features
@@ -192,6 +553,10 @@ pub async fn items<B: Backend>(
     let items = Items::try_from(items.0)
         .and_then(Items::valid)
         .map_err(|error| Error::BadRequest(format!("invalid query: {error}")))?;
+    api.check_crs(&items)?;
+    let items = items
+        .into_crs84()
+        .map_err(|error| Error::BadRequest(format!("invalid query: {error}")))?;
     api.items(&collection_id, items)
         .await
         .map_err(Error::from)
@@ -202,6 +567,74 @@ pub async fn items<B: Backend>(
         .map(GeoJson)
 }
 
+/// Adds an item to a collection, from the POST
+/// `/collections/{collectionId}/items` endpoint of the [transaction
+/// extension](https://github.com/stac-api-extensions/transaction).
+///
+/// This is `405 Method Not Allowed` unless [Api::transactions] is on, or if
+/// the backend is read-only.
+///
+/// The item's collection is set to the path's collection. The response body
+/// reports what the backend did with the item, e.g. `{"outcome": "versioned",
+/// "id": "an-id-v2", "collection": "a-collection"}`, which depends on the
+/// backend's [CollisionPolicy](crate::CollisionPolicy). The status is `201
+/// Created` (with a `Location` header) if a new item was stored, `200 OK` if an
+/// existing item was replaced or kept, and `409 Conflict` if the policy is to
+/// error.
+pub async fn create_item<B: Backend>(
+    State(api): State<Api<B>>,
+    Path(collection_id): Path<String>,
+    item: std::result::Result<Json<Item>, JsonRejection>,
+) -> Result<Response> {
+    if !api.transactions {
+        return Err(Error::MethodNotAllowed(
+            "transactions are not enabled on this api".to_string(),
+        ));
+    }
+    let mut item = item?.0;
+    if api.backend.collection(&collection_id).await?.is_none() {
+        return Err(Error::NotFound(format!(
+            "no collection with id='{collection_id}'"
+        )));
+    }
+    if item
+        .collection
+        .as_ref()
+        .is_some_and(|collection| *collection != collection_id)
+    {
+        return Err(Error::BadRequest(format!(
+            "item collection does not match the path collection id='{collection_id}'"
+        )));
+    }
+    item.collection = Some(collection_id.clone());
+    let mut id = item.id.clone();
+    let mut backend = api.backend.clone();
+    let outcome = backend.add_item_with_outcome(item).await?;
+    if let ItemOutcome::Versioned(versioned) = &outcome {
+        id = versioned.clone();
+    }
+    let body = Json(serde_json::json!({
+        "outcome": outcome.as_str(),
+        "id": id,
+        "collection": collection_id,
+    }));
+    match outcome {
+        ItemOutcome::Created | ItemOutcome::Versioned(_) => {
+            let location = api
+                .root
+                .join(&format!("/collections/{collection_id}/items/{id}"))
+                .map_err(crate::Error::from)?;
+            Ok((
+                StatusCode::CREATED,
+                [(LOCATION, location.to_string())],
+                body,
+            )
+                .into_response())
+        }
+        ItemOutcome::Replaced | ItemOutcome::Skipped => Ok(body.into_response()),
+    }
+}
+
 /// Returns the `/collections/{collectionId}/items/{itemId}` endpoint from the
 /// [ogcapi-features conformance
 /// class](https://github.com/radiantearth/stac-api-spec/tree/release/v1.0.0/ogcapi-features#collection-items-collectionscollectioniditems)
@@ -221,37 +654,121 @@ pub async fn item<B: Backend>(
 
 /// Returns the GET `/search` endpoint from the [item search conformance
 /// class](https://github.com/radiantearth/stac-api-spec/tree/release/v1.0.0/item-search)
+///
+/// The response is GeoJSON unless another [Format] is requested with the
+/// `format` parameter or the `Accept` header, see [search_format].
 pub async fn get_search<B: Backend>(
     State(api): State<Api<B>>,
+    headers: HeaderMap,
     search: Query<GetSearch>,
-) -> Result<GeoJson<ItemCollection>> {
+) -> Result<Response> {
     tracing::debug!("GET /search: {:?}", search.0);
-    let search = Search::try_from(search.0)
+    let mut search = search.0;
+    let format = search_format(
+        &headers,
+        search
+            .items
+            .additional_fields
+            .shift_remove("format")
+            .as_deref(),
+    )?;
+    let search = Search::try_from(search)
         .and_then(Search::valid)
         .map_err(|error| Error::BadRequest(error.to_string()))?;
-
-    Ok(GeoJson(api.search(search, Method::GET).await?))
+    api.check_crs(&search.items)?;
+    let search = search
+        .into_crs84()
+        .map_err(|error| Error::BadRequest(error.to_string()))?;
+    search_response(api, search, Method::GET, format).await
 }
 
 /// Returns the POST `/search` endpoint from the [item search conformance
 /// class](https://github.com/radiantearth/stac-api-spec/tree/release/v1.0.0/item-search)
+///
+/// The response is GeoJSON unless another [Format] is requested with the
+/// `format` field or the `Accept` header, see [search_format].
 pub async fn post_search<B: Backend>(
     State(api): State<Api<B>>,
+    headers: HeaderMap,
     search: std::result::Result<Json<Search>, JsonRejection>,
-) -> Result<GeoJson<ItemCollection>> {
-    let search = search?
-        .0
+) -> Result<Response> {
+    let mut search = search?.0;
+    let format = match search.items.additional_fields.remove("format") {
+        Some(serde_json::Value::String(format)) => Some(format),
+        Some(value) => {
+            return Err(Error::BadRequest(format!(
+                "format must be a string, got {value}"
+            )));
+        }
+        None => None,
+    };
+    let format = search_format(&headers, format.as_deref())?;
+    let search = search
         .valid()
         .map_err(|error| Error::BadRequest(error.to_string()))?;
-    Ok(GeoJson(api.search(search, Method::POST).await?))
+    api.check_crs(&search.items)?;
+    let search = search
+        .into_crs84()
+        .map_err(|error| Error::BadRequest(error.to_string()))?;
+    search_response(api, search, Method::POST, format).await
+}
+
+/// Returns the [Format] of a search response.
+///
+/// A `format` parameter, e.g. `format=parquet`, takes precedence over the
+/// `Accept` header. If neither asks for a format that we can produce, the
+/// response is GeoJSON.
+pub fn search_format(headers: &HeaderMap, format: Option<&str>) -> Result<Format> {
+    if let Some(format) = format {
+        return format
+            .parse()
+            .map_err(|error: crate::Error| Error::BadRequest(error.to_string()));
+    }
+    Ok(headers
+        .get(ACCEPT)
+        .and_then(|value| value.to_str().ok())
+        .and_then(Format::from_accept)
+        .unwrap_or_default())
+}
+
+async fn search_response<B: Backend>(
+    api: Api<B>,
+    mut search: Search,
+    method: Method,
+    format: Format,
+) -> Result<Response> {
+    api.limit(&mut search.items)?;
+    let mut response = if format == Format::GeoJson {
+        GeoJson(api.search(search, method).await?).into_response()
+    } else {
+        let mut encoded = api.backend.search_encoded(search, format).await?;
+        if encoded.is_empty().await? {
+            StatusCode::NO_CONTENT.into_response()
+        } else {
+            (
+                [(CONTENT_TYPE, format.content_type())],
+                Body::from_stream(encoded),
+            )
+                .into_response()
+        }
+    };
+    let _ = response
+        .headers_mut()
+        .append(VARY, HeaderValue::from_static("accept"));
+    Ok(response)
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{Api, MemoryBackend};
+    use crate::{Api, CollisionPolicy, MemoryBackend};
     use axum::{
         body::Body,
-        http::{Request, Response, StatusCode, header::CONTENT_TYPE},
+        http::{
+            Request, Response, StatusCode,
+            header::{
+                ACCEPT, ACCEPT_LANGUAGE, ALLOW, CONTENT_LANGUAGE, CONTENT_TYPE, LOCATION, VARY,
+            },
+        },
     };
     use stac::api::TransactionClient;
     use stac::{Collection, Item};
@@ -300,6 +817,17 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn healthz() {
+        let response = get(MemoryBackend::new(), "/healthz").await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(value["status"], "ok");
+    }
+
     #[tokio::test]
     async fn service_description() {
         let response = get(MemoryBackend::new(), "/api").await;
@@ -320,6 +848,44 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn custom_documents() {
+        let api = Api::builder(MemoryBackend::new(), "http://stac.test/")
+            .service_desc("application/json", r#"{"openapi": "3.0.0"}"#)
+            .service_doc("<html></html>")
+            .asset("css/style.css", "text/css", "body {}")
+            .build()
+            .unwrap();
+        let router = super::from_api(api);
+        for (uri, content_type, body) in [
+            ("/api", "application/json", r#"{"openapi": "3.0.0"}"#),
+            ("/api.html", "text/html; charset=utf-8", "<html></html>"),
+            ("/assets/css/style.css", "text/css", "body {}"),
+        ] {
+            let response = router
+                .clone()
+                .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK, "{uri}");
+            assert_eq!(response.headers().get(CONTENT_TYPE).unwrap(), content_type);
+            let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            assert_eq!(bytes, body);
+        }
+        let response = router
+            .oneshot(
+                Request::builder()
+                    .uri("/assets/missing.css")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
     #[tokio::test]
     async fn conformance() {
         let response = get(MemoryBackend::new(), "/conformance").await;
@@ -330,6 +896,98 @@ mod tests {
         );
     }
 
+    async fn request(method: &str, uri: &str) -> Response<Body> {
+        let router = super::from_api(Api::new(MemoryBackend::new(), "http://stac.test/").unwrap());
+        router
+            .oneshot(
+                Request::builder()
+                    .method(method)
+                    .uri(uri)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn head() {
+        for uri in ["/", "/conformance", "/collections", "/search"] {
+            let response = request("HEAD", uri).await;
+            assert_eq!(response.status(), StatusCode::OK, "HEAD {uri}");
+            let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            assert!(bytes.is_empty());
+        }
+    }
+
+    #[tokio::test]
+    async fn options() {
+        let response = request("OPTIONS", "/collections").await;
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        assert_eq!(response.headers().get(ALLOW).unwrap(), "GET, HEAD, OPTIONS");
+        let response = request("OPTIONS", "/search").await;
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        assert_eq!(
+            response.headers().get(ALLOW).unwrap(),
+            "GET, HEAD, POST, OPTIONS"
+        );
+    }
+
+    #[tokio::test]
+    async fn method_not_allowed() {
+        let response = request("DELETE", "/collections").await;
+        assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+        let allow = response.headers().get(ALLOW).unwrap().to_str().unwrap();
+        assert!(allow.contains("GET"));
+        assert!(allow.contains("HEAD"));
+        assert!(!allow.contains("POST"));
+    }
+
+    #[tokio::test]
+    async fn nested() {
+        use axum::{Router, extract::FromRef, routing::get};
+
+        #[derive(Clone)]
+        struct AppState {
+            api: Api<MemoryBackend>,
+        }
+
+        impl FromRef<AppState> for Api<MemoryBackend> {
+            fn from_ref(state: &AppState) -> Api<MemoryBackend> {
+                state.api.clone()
+            }
+        }
+
+        let state = AppState {
+            api: Api::new(MemoryBackend::new(), "http://stac.test/stac").unwrap(),
+        };
+        let router: Router = Router::new()
+            .route("/", get(|| async { "not stac" }))
+            .nest("/stac", super::router::<MemoryBackend, AppState>())
+            .with_state(state);
+        let response = router
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/stac/conformance")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let response = router
+            .oneshot(Request::builder().uri("/").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(bytes, "not stac");
+    }
+
     #[tokio::test]
     async fn collections() {
         let response = get(MemoryBackend::new(), "/collections").await;
@@ -340,6 +998,230 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn free_text() {
+        let mut backend = MemoryBackend::new();
+        backend
+            .add_collection(Collection::new("sentinel", "Sentinel-2 imagery"))
+            .await
+            .unwrap();
+        backend
+            .add_collection(Collection::new("landsat", "Landsat imagery"))
+            .await
+            .unwrap();
+        let mut item = Item::new("an-item").collection("sentinel");
+        item.properties.title = Some("A cloudy scene".to_string());
+        backend.add_item(item).await.unwrap();
+        backend
+            .add_item(Item::new("another-item").collection("sentinel"))
+            .await
+            .unwrap();
+
+        let response = get(backend.clone(), "/collections?q=sentinel").await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let collections: stac::api::Collections = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(collections.collections.len(), 1);
+        assert_eq!(collections.collections[0].id, "sentinel");
+
+        let response = get(backend, "/search?q=cloudy").await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let item_collection: stac::api::ItemCollection = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(item_collection.items.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn negotiate_language() {
+        let mut collection = Collection::new("an-id", "A description");
+        collection.title = Some("Forests".to_string());
+        for (key, value) in [
+            ("language", serde_json::json!({"code": "en"})),
+            (
+                "languages",
+                serde_json::json!([{"code": "de", "name": "Deutsch"}]),
+            ),
+            (
+                "localization",
+                serde_json::json!({"de": {"title": "Wälder", "description": "Eine Beschreibung"}}),
+            ),
+        ] {
+            let _ = collection.additional_fields.insert(key.to_string(), value);
+        }
+        let mut backend = MemoryBackend::new();
+        backend.add_collection(collection).await.unwrap();
+
+        async fn get_with_language(
+            backend: MemoryBackend,
+            uri: &str,
+            language_negotiation: bool,
+        ) -> serde_json::Value {
+            let router = super::from_api(
+                Api::new(backend, "http://stac.test/")
+                    .unwrap()
+                    .language_negotiation(language_negotiation),
+            );
+            let response = router
+                .oneshot(
+                    Request::builder()
+                        .uri(uri)
+                        .header(ACCEPT_LANGUAGE, "de-CH, en;q=0.5")
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let content_language = response
+                .headers()
+                .get(CONTENT_LANGUAGE)
+                .map(|value| value.to_str().unwrap().to_string());
+            let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            let mut value: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+            value["content_language"] = content_language.into();
+            value
+        }
+
+        let collection = get_with_language(backend.clone(), "/collections/an-id", false).await;
+        assert_eq!(collection["title"], "Forests");
+        assert!(collection["content_language"].is_null());
+
+        let collection = get_with_language(backend.clone(), "/collections/an-id", true).await;
+        assert_eq!(collection["title"], "Wälder");
+        assert_eq!(collection["description"], "Eine Beschreibung");
+        assert_eq!(collection["language"]["name"], "Deutsch");
+        assert_eq!(collection["content_language"], "de");
+
+        let collections = get_with_language(backend, "/collections", true).await;
+        assert_eq!(collections["collections"][0]["title"], "Wälder");
+        assert!(collections["content_language"].is_null());
+    }
+
+    #[tokio::test]
+    async fn create_item() {
+        async fn post_item(
+            backend: MemoryBackend,
+            collection_id: &str,
+            item: &Item,
+        ) -> (StatusCode, Option<String>, serde_json::Value) {
+            post_item_to(
+                Api::new(backend, "http://stac.test/")
+                    .unwrap()
+                    .transactions(true),
+                collection_id,
+                item,
+            )
+            .await
+        }
+
+        async fn post_item_to<B: crate::Backend>(
+            api: Api<B>,
+            collection_id: &str,
+            item: &Item,
+        ) -> (StatusCode, Option<String>, serde_json::Value) {
+            let router = super::from_api(api);
+            let response = router
+                .oneshot(
+                    Request::builder()
+                        .uri(format!("/collections/{collection_id}/items"))
+                        .method("POST")
+                        .header(CONTENT_TYPE, "application/json")
+                        .body(Body::from(serde_json::to_vec(item).unwrap()))
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            let status = response.status();
+            let location = response
+                .headers()
+                .get(LOCATION)
+                .map(|value| value.to_str().unwrap().to_string());
+            let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            let value = serde_json::from_slice(&bytes).unwrap_or_default();
+            (status, location, value)
+        }
+
+        let item = Item::new("item-id");
+        let (status, _, _) = post_item(MemoryBackend::new(), "an-id", &item).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let mut backend = MemoryBackend::new().collision_policy(CollisionPolicy::Version);
+        backend
+            .add_collection(Collection::new("an-id", "A description"))
+            .await
+            .unwrap();
+        let (status, location, value) = post_item(backend.clone(), "an-id", &item).await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(
+            location.unwrap(),
+            "http://stac.test/collections/an-id/items/item-id"
+        );
+        assert_eq!(value["outcome"], "created");
+        let (status, _, value) = post_item(backend.clone(), "an-id", &item).await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(value["outcome"], "versioned");
+        assert_eq!(value["id"], "item-id-v2");
+
+        let backend = backend.collision_policy(CollisionPolicy::Error);
+        let (status, _, _) = post_item(backend.clone(), "an-id", &item).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        let backend = backend.collision_policy(CollisionPolicy::Overwrite);
+        let (status, _, value) = post_item(backend.clone(), "an-id", &item).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(value["outcome"], "replaced");
+
+        let (status, _, _) = post_item(
+            backend.clone(),
+            "an-id",
+            &item.clone().collection("another-id"),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, _, _) = post_item_to(
+            Api::new(backend, "http://stac.test/").unwrap(),
+            "an-id",
+            &item,
+        )
+        .await;
+        assert_eq!(status, StatusCode::METHOD_NOT_ALLOWED);
+    }
+
+    #[cfg(feature = "duckdb")]
+    #[tokio::test]
+    async fn create_item_read_only() {
+        let backend = crate::DuckdbBackend::new("data/100-sentinel-2-items.parquet")
+            .await
+            .unwrap();
+        let router = super::from_api(
+            Api::new(backend, "http://stac.test/")
+                .unwrap()
+                .transactions(true),
+        );
+        let response = router
+            .oneshot(
+                Request::builder()
+                    .uri("/collections/sentinel-2-l2a/items")
+                    .method("POST")
+                    .header(CONTENT_TYPE, "application/json")
+                    .body(Body::from(
+                        serde_json::to_vec(&Item::new("item-id")).unwrap(),
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+    }
+
     #[tokio::test]
     async fn collection() {
         let response = get(MemoryBackend::new(), "/collections/an-id").await;
@@ -357,6 +1239,33 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn queryables() {
+        let response = get(MemoryBackend::new(), "/queryables").await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers().get(CONTENT_TYPE).unwrap(),
+            "application/schema+json"
+        );
+        let response = get(MemoryBackend::new(), "/collections/an-id/queryables").await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let mut backend = MemoryBackend::new();
+        backend
+            .add_collection(Collection::new("an-id", "A description"))
+            .await
+            .unwrap();
+        let response = get(backend, "/collections/an-id/queryables").await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let queryables: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(
+            queryables["$id"],
+            "http://stac.test/collections/an-id/queryables"
+        );
+    }
+
     #[tokio::test]
     async fn items() {
         let response = get(MemoryBackend::new(), "/collections/collection-id/items").await;
@@ -379,6 +1288,39 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn items_unsupported_crs() {
+        let mut backend = MemoryBackend::new();
+        backend
+            .add_collection(Collection::new("collection-id", "A description"))
+            .await
+            .unwrap();
+        let response = get(
+            backend,
+            "/collections/collection-id/items?crs=http://www.opengis.net/def/crs/EPSG/0/3857",
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn search_unadvertised_bbox_crs() {
+        let response = get(
+            MemoryBackend::new(),
+            "/search?bbox=40,-105,41,-104&bbox-crs=http://www.opengis.net/def/crs/EPSG/0/4326",
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert!(
+            String::from_utf8(body.to_vec())
+                .unwrap()
+                .contains("not supported by this api")
+        );
+    }
+
     #[tokio::test]
     async fn item() {
         let response = get(
@@ -415,6 +1357,146 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn search_ndjson() {
+        let mut backend = MemoryBackend::new();
+        backend
+            .add_collection(Collection::new("collection-id", "A description"))
+            .await
+            .unwrap();
+        for id in ["item-a", "item-b"] {
+            backend
+                .add_item(Item::new(id).collection("collection-id"))
+                .await
+                .unwrap();
+        }
+        let router = super::from_api(Api::new(backend.clone(), "http://stac.test/").unwrap());
+        let response = router
+            .oneshot(
+                Request::builder()
+                    .uri("/search")
+                    .header(ACCEPT, "application/x-ndjson")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers().get(CONTENT_TYPE).unwrap(),
+            "application/x-ndjson"
+        );
+        assert_eq!(response.headers().get(VARY).unwrap(), "accept");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let lines: Vec<serde_json::Value> = body
+            .split(|&b| b == b'\n')
+            .filter(|line| !line.is_empty())
+            .map(|line| serde_json::from_slice(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["type"], "Feature");
+
+        let response = get(backend.clone(), "/search?format=ndjson&limit=1").await;
+        assert_eq!(
+            response.headers().get(CONTENT_TYPE).unwrap(),
+            "application/x-ndjson"
+        );
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(body.iter().filter(|&&b| b == b'\n').count(), 1);
+
+        let response = get(backend, "/search?format=csv").await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn max_limit() {
+        let mut backend = MemoryBackend::new();
+        backend
+            .add_collection(Collection::new("collection-id", "A description"))
+            .await
+            .unwrap();
+        for uri in [
+            "/search?limit=10001",
+            "/collections/collection-id/items?limit=10001",
+        ] {
+            let response = get(backend.clone(), uri).await;
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{uri}");
+        }
+        let response = get(backend, "/search?limit=10000").await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn expired_token() {
+        let router = super::from_api(
+            Api::new(MemoryBackend::new(), "http://stac.test/")
+                .unwrap()
+                .token_ttl(std::time::Duration::from_secs(60)),
+        );
+        let response = router
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/search?offset=1&token_issued=0")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let response = router
+            .oneshot(
+                Request::builder()
+                    .uri("/search?offset=1&token_issued=not-a-time")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn search_geoparquet() {
+        let mut backend = MemoryBackend::new();
+        backend
+            .add_collection(Collection::new("collection-id", "A description"))
+            .await
+            .unwrap();
+        let mut item = Item::new("item-id").collection("collection-id");
+        item.geometry = Some(stac::Geometry::new_point(vec![-105.1, 41.1]));
+        backend.add_item(item).await.unwrap();
+        let router = super::from_api(Api::new(backend, "http://stac.test/").unwrap());
+        let response = router
+            .oneshot(
+                Request::builder()
+                    .uri("/search")
+                    .method("POST")
+                    .header(CONTENT_TYPE, "application/json")
+                    .body(r#"{"format": "geoparquet"}"#.to_string())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        if cfg!(feature = "geoparquet") {
+            assert_eq!(response.status(), StatusCode::OK);
+            assert_eq!(
+                response.headers().get(CONTENT_TYPE).unwrap(),
+                "application/vnd.apache.parquet"
+            );
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            assert!(body.starts_with(b"PAR1"));
+        } else {
+            assert_eq!(response.status(), StatusCode::NOT_ACCEPTABLE);
+        }
+    }
+
     #[tokio::test]
     async fn post_search() {
         let response = post(MemoryBackend::new(), "/search").await;
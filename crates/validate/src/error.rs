@@ -15,10 +15,18 @@ pub enum Error {
     /// [reqwest::Error]
     Reqwest(#[from] reqwest::Error),
 
+    /// [std::io::Error]
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
     /// JSON is a scalar when an array or object was expected
     #[error("json value is not an object or an array")]
     ScalarJson(serde_json::Value),
 
+    /// A schema isn't bundled or local, and the validator is offline.
+    #[error("schema is not available offline: {0}")]
+    SchemaUnavailable(String),
+
     #[error(transparent)]
     /// [serde_json::Error]
     SerdeJson(#[from] serde_json::Error),
@@ -31,6 +31,25 @@
 //! }
 //! ```
 //!
+//! To validate without network access, e.g. in CI or air-gapped environments,
+//! use a [ValidatorBuilder] with local schemas:
+//!
+//! ```
+//! use stac::Item;
+//! use stac_validate::ValidatorBuilder;
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let mut validator = ValidatorBuilder::new()
+//!         .schema_dir("schemas")
+//!         .offline(true)
+//!         .build()
+//!         .await
+//!         .unwrap();
+//!     validator.validate(&Item::new("an-id")).await.unwrap();
+//! }
+//! ```
+//!
 //! [Validator] is cheap to clone, so you are encouraged to validate a large
 //! number of objects at the same time if that's your use-case.
 
@@ -40,7 +59,10 @@ mod error;
 mod validator;
 use async_trait::async_trait;
 
-pub use {error::Error, validator::Validator};
+pub use {
+    error::Error,
+    validator::{Validator, ValidatorBuilder},
+};
 
 /// Public result type.
 pub type Result<T> = std::result::Result<T, Error>;
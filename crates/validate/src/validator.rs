@@ -7,35 +7,176 @@ use reqwest::Client;
 use serde::Serialize;
 use serde_json::{Map, Value};
 use stac::{Type, Version};
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 const SCHEMA_BASE: &str = "https://schemas.stacspec.org";
 
 /// A structure for validating STAC.
 pub struct Validator {
     validators: HashMap<Uri<String>, JsonschemaValidator>,
+    api_validators: ApiValidators,
     registry: Registry<'static>,
     retriever: Retriever,
 }
 
+/// Validators for STAC API documents, which don't have schema urls.
+struct ApiValidators {
+    landing_page: JsonschemaValidator,
+    collections: JsonschemaValidator,
+    item_collection: JsonschemaValidator,
+}
+
+/// A builder for a [Validator] that can use local schemas.
+///
+/// The core STAC schemas are always bundled. Any other schema, e.g. an
+/// extension schema, is looked up in the in-memory schemas, then in the
+/// schema directories, and then fetched over HTTP unless the validator is
+/// offline.
+///
+/// # Examples
+///
+/// ```
+/// use stac_validate::ValidatorBuilder;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let validator = ValidatorBuilder::new()
+///         .schema_dir("schemas")
+///         .offline(true)
+///         .build()
+///         .await
+///         .unwrap();
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ValidatorBuilder {
+    schemas: HashMap<String, Value>,
+    schema_dirs: Vec<PathBuf>,
+    offline: bool,
+}
+
 #[derive(Debug, Clone)]
-struct Retriever(Client);
+struct Retriever {
+    client: Client,
+    sources: Arc<ValidatorBuilder>,
+}
 
-impl Validator {
-    /// Creates a new validator.
+impl ValidatorBuilder {
+    /// Creates a new validator builder.
     ///
     /// # Examples
     ///
     /// ```
-    /// use stac_validate::Validator;
+    /// use stac_validate::ValidatorBuilder;
+    ///
+    /// let builder = ValidatorBuilder::new();
+    /// ```
+    pub fn new() -> ValidatorBuilder {
+        ValidatorBuilder::default()
+    }
+
+    /// Adds an in-memory schema for a url.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use serde_json::json;
+    /// use stac_validate::ValidatorBuilder;
+    ///
+    /// let builder = ValidatorBuilder::new().schema(
+    ///     "https://stac-extensions.github.io/example/v1.0.0/schema.json",
+    ///     json!({"type": "object"}),
+    /// );
+    /// ```
+    pub fn schema(mut self, url: impl ToString, schema: Value) -> ValidatorBuilder {
+        let _ = self.schemas.insert(url.to_string(), schema);
+        self
+    }
+
+    /// Adds many in-memory schemas, keyed by url.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use serde_json::json;
+    /// use stac_validate::ValidatorBuilder;
+    ///
+    /// let builder = ValidatorBuilder::new().schemas([(
+    ///     "https://stac-extensions.github.io/example/v1.0.0/schema.json",
+    ///     json!({"type": "object"}),
+    /// )]);
+    /// ```
+    pub fn schemas<I, K>(mut self, schemas: I) -> ValidatorBuilder
+    where
+        I: IntoIterator<Item = (K, Value)>,
+        K: ToString,
+    {
+        self.schemas.extend(
+            schemas
+                .into_iter()
+                .map(|(url, schema)| (url.to_string(), schema)),
+        );
+        self
+    }
+
+    /// Adds a directory of schemas.
+    ///
+    /// Schema urls are mapped to paths by dropping the scheme, so
+    /// `https://stac-extensions.github.io/eo/v1.1.0/schema.json` is read from
+    /// `{dir}/stac-extensions.github.io/eo/v1.1.0/schema.json`. Can be called
+    /// more than once, and directories are searched in the order they were
+    /// added.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_validate::ValidatorBuilder;
+    ///
+    /// let builder = ValidatorBuilder::new().schema_dir("schemas");
+    /// ```
+    pub fn schema_dir(mut self, dir: impl AsRef<Path>) -> ValidatorBuilder {
+        self.schema_dirs.push(dir.as_ref().to_path_buf());
+        self
+    }
+
+    /// Never fetch schemas over HTTP.
+    ///
+    /// Validating against a schema that isn't bundled or local returns an
+    /// [Error::SchemaUnavailable].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_validate::ValidatorBuilder;
+    ///
+    /// let builder = ValidatorBuilder::new().offline(true);
+    /// ```
+    pub fn offline(mut self, offline: bool) -> ValidatorBuilder {
+        self.offline = offline;
+        self
+    }
+
+    /// Builds the validator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_validate::ValidatorBuilder;
     ///
     /// #[tokio::main]
     /// async fn main() {
-    ///     let validator = Validator::new().await.unwrap();
+    ///     let validator = ValidatorBuilder::new().build().await.unwrap();
     /// }
     /// ```
-    pub async fn new() -> Result<Validator> {
-        let retriever = Retriever(Client::builder().user_agent(crate::user_agent()).build()?);
+    pub async fn build(self) -> Result<Validator> {
+        let retriever = Retriever {
+            client: Client::builder().user_agent(crate::user_agent()).build()?,
+            sources: Arc::new(self),
+        };
         let registry = Registry::new()
             .extend(prebuild_resources())
             .expect("prebuild resource URIs should be valid")
@@ -44,11 +185,51 @@ impl Validator {
         let validators = prebuild_validators(&registry, retriever.clone()).await;
         Ok(Validator {
             validators,
+            api_validators: ApiValidators::new(),
             registry,
             retriever,
         })
     }
 
+    /// Returns a schema from memory or from a schema directory, if there is one.
+    fn local(&self, url: &str) -> Result<Option<Value>> {
+        if let Some(schema) = self.schemas.get(url) {
+            return Ok(Some(schema.clone()));
+        }
+        let Some((_, path)) = url.split_once("://") else {
+            return Ok(None);
+        };
+        let path = path.split(['?', '#']).next().unwrap_or(path);
+        for dir in &self.schema_dirs {
+            let path = dir.join(path);
+            if path.is_file() {
+                let schema = serde_json::from_slice(&std::fs::read(path)?)?;
+                return Ok(Some(schema));
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl Validator {
+    /// Creates a new validator.
+    ///
+    /// Use [ValidatorBuilder] to validate with local schemas.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use stac_validate::Validator;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let validator = Validator::new().await.unwrap();
+    /// }
+    /// ```
+    pub async fn new() -> Result<Validator> {
+        ValidatorBuilder::new().build().await
+    }
+
     /// Validates a single value.
     ///
     /// # Examples
@@ -72,6 +253,47 @@ impl Validator {
         Ok(())
     }
 
+    /// Validates a STAC API document.
+    ///
+    /// Landing pages (catalogs with `conformsTo`), `/collections` responses,
+    /// and item collections are validated against the bundled STAC API
+    /// schemas, including their links and context, and then the STAC objects
+    /// they contain are validated as usual. Anything else is validated with
+    /// [Validator::validate].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use serde_json::json;
+    /// use stac_validate::Validator;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut validator = Validator::new().await.unwrap();
+    ///     let collections = json!({"collections": [], "links": []});
+    ///     validator.validate_api(&collections).await.unwrap();
+    ///     let collections = json!({"collections": []});
+    ///     assert!(validator.validate_api(&collections).await.is_err());
+    /// }
+    /// ```
+    pub async fn validate_api<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: Serialize,
+    {
+        let value = serde_json::to_value(value)?;
+        if let Some(validator) = self.api_validators.get(&value) {
+            let errors: Vec<_> = validator.iter_errors(&value).collect();
+            if !errors.is_empty() {
+                return Err(Error::from_validation_errors(
+                    errors.into_iter(),
+                    Some(&value),
+                ));
+            }
+        }
+        let _ = self.validate_value(value).await?;
+        Ok(())
+    }
+
     /// If you have a [serde_json::Value], you can skip a deserialization step by using this method.
     #[async_recursion]
     pub async fn validate_value(&mut self, value: Value) -> Result<Value> {
@@ -224,9 +446,7 @@ impl Validator {
 
     async fn ensure_validator(&mut self, uri: &Uri<String>) -> Result<()> {
         if !self.validators.contains_key(uri) {
-            let client = reqwest::Client::new();
-            let response = client.get(uri.as_str()).send().await?.error_for_status()?;
-            let json_data = response.json().await?;
+            let json_data = self.retriever.fetch(uri).await?;
             let validator = jsonschema::async_options()
                 .with_registry(&self.registry)
                 .with_retriever(self.retriever.clone())
@@ -243,14 +463,56 @@ impl Validator {
     }
 }
 
+impl ApiValidators {
+    fn new() -> ApiValidators {
+        fn build(schema: &str) -> JsonschemaValidator {
+            let schema = serde_json::from_str(schema).expect("bundled api schemas should be JSON");
+            jsonschema::validator_for(&schema).expect("bundled api schemas should be valid")
+        }
+        ApiValidators {
+            landing_page: build(include_str!("schemas/api/landing-page.json")),
+            collections: build(include_str!("schemas/api/collections.json")),
+            item_collection: build(include_str!("schemas/api/item-collection.json")),
+        }
+    }
+
+    fn get(&self, value: &Value) -> Option<&JsonschemaValidator> {
+        let object = value.as_object()?;
+        match object.get("type").and_then(|v| v.as_str()) {
+            Some("Catalog") if object.contains_key("conformsTo") => Some(&self.landing_page),
+            Some("FeatureCollection") => Some(&self.item_collection),
+            None if object.contains_key("collections") => Some(&self.collections),
+            _ => None,
+        }
+    }
+}
+
+impl Retriever {
+    async fn fetch(&self, uri: &Uri<String>) -> Result<Value> {
+        if let Some(value) = self.sources.local(uri.as_str())? {
+            Ok(value)
+        } else if self.sources.offline {
+            Err(Error::SchemaUnavailable(uri.to_string()))
+        } else {
+            let response = self
+                .client
+                .get(uri.as_str())
+                .send()
+                .await?
+                .error_for_status()?;
+            let value = response.json().await?;
+            Ok(value)
+        }
+    }
+}
+
 #[async_trait]
 impl AsyncRetrieve for Retriever {
     async fn retrieve(
         &self,
         uri: &Uri<String>,
     ) -> std::result::Result<Value, Box<dyn std::error::Error + Send + Sync>> {
-        let response = self.0.get(uri.as_str()).send().await?.error_for_status()?;
-        let value = response.json().await?;
+        let value = self.fetch(uri).await?;
         Ok(value)
     }
 }
@@ -404,8 +666,8 @@ fn prebuild_resources() -> Vec<(String, Resource)> {
 
 #[cfg(test)]
 mod tests {
-    use super::Validator;
-    use crate::Validate;
+    use super::{Validator, ValidatorBuilder};
+    use crate::{Error, Validate};
     use serde_json::json;
     use stac::{Collection, Item};
 
@@ -439,4 +701,119 @@ mod tests {
         });
         collections.validate().await.unwrap();
     }
+
+    fn extended_item() -> Item {
+        let mut item = Item::new("an-id");
+        item.extensions
+            .push("https://example.com/extension/v1.0.0/schema.json".to_string());
+        item
+    }
+
+    fn extension_schema() -> serde_json::Value {
+        json!({
+            "type": "object",
+            "required": ["properties"],
+            "properties": {
+                "properties": {"required": ["example:foo"]}
+            }
+        })
+    }
+
+    #[tokio::test]
+    async fn offline() {
+        let mut validator = ValidatorBuilder::new().offline(true).build().await.unwrap();
+        validator.validate(&Item::new("an-id")).await.unwrap();
+        assert!(matches!(
+            validator.validate(&extended_item()).await.unwrap_err(),
+            Error::SchemaUnavailable(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn in_memory_schema() {
+        let mut validator = ValidatorBuilder::new()
+            .schema(
+                "https://example.com/extension/v1.0.0/schema.json",
+                extension_schema(),
+            )
+            .offline(true)
+            .build()
+            .await
+            .unwrap();
+        let mut item = extended_item();
+        assert!(matches!(
+            validator.validate(&item).await.unwrap_err(),
+            Error::Validation(_)
+        ));
+        let _ = item
+            .properties
+            .additional_fields
+            .insert("example:foo".to_string(), "bar".into());
+        validator.validate(&item).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn schema_dir() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let dir = temp_dir.path().join("example.com/extension/v1.0.0");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("schema.json"),
+            serde_json::to_vec(&extension_schema()).unwrap(),
+        )
+        .unwrap();
+        let mut validator = ValidatorBuilder::new()
+            .schema_dir(temp_dir.path())
+            .offline(true)
+            .build()
+            .await
+            .unwrap();
+        assert!(matches!(
+            validator.validate(&extended_item()).await.unwrap_err(),
+            Error::Validation(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn validate_api() {
+        let mut validator = Validator::new().await.unwrap();
+        let mut landing_page = json!({
+            "type": "Catalog",
+            "stac_version": "1.1.0",
+            "id": "an-api",
+            "description": "A STAC API",
+            "links": [{"rel": "self", "href": "http://stac.test"}],
+            "conformsTo": ["https://api.stacspec.org/v1.0.0/core"]
+        });
+        validator.validate_api(&landing_page).await.unwrap();
+        landing_page["links"] = json!([{"rel": "root", "href": "http://stac.test"}]);
+        assert!(matches!(
+            validator.validate_api(&landing_page).await.unwrap_err(),
+            Error::Validation(_)
+        ));
+
+        let item_collection = json!({
+            "type": "FeatureCollection",
+            "features": [Item::new("an-id")],
+            "links": [],
+            "context": {"returned": 1, "limit": 10}
+        });
+        validator.validate_api(&item_collection).await.unwrap();
+        let item_collection = json!({
+            "type": "FeatureCollection",
+            "features": [Item::new("an-id")],
+            "context": {"limit": 10}
+        });
+        assert!(matches!(
+            validator.validate_api(&item_collection).await.unwrap_err(),
+            Error::Validation(_)
+        ));
+
+        let collection: Collection = stac_io::read("examples/collection.json").unwrap();
+        let collections = json!({
+            "collections": [collection],
+            "links": [{"rel": "self", "href": "http://stac.test/collections"}]
+        });
+        validator.validate_api(&collections).await.unwrap();
+    }
 }